@@ -0,0 +1,10 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE WKExecutionElements DROP CONSTRAINT wkexecutionelements_exercise_id_fkey;
+ALTER TABLE WKExecutionElements ADD CONSTRAINT wkexecutionelements_exercise_id_fkey
+    FOREIGN KEY (exercise_id) REFERENCES Exercises(id) ON DELETE CASCADE;
+
+ALTER TABLE WKTemplateElements DROP CONSTRAINT wktemplateelements_exercise_id_fkey;
+ALTER TABLE WKTemplateElements ADD CONSTRAINT wktemplateelements_exercise_id_fkey
+    FOREIGN KEY (exercise_id) REFERENCES Exercises(id) ON DELETE CASCADE;
+
+ALTER TABLE Exercises DROP COLUMN retired;
@@ -0,0 +1,11 @@
+-- Your SQL goes here
+ALTER TABLE Exercises ADD COLUMN retired BOOLEAN NOT NULL DEFAULT FALSE;
+
+-- Exercises referenced by templates or executions can't be deleted, only retired
+ALTER TABLE WKTemplateElements DROP CONSTRAINT wktemplateelements_exercise_id_fkey;
+ALTER TABLE WKTemplateElements ADD CONSTRAINT wktemplateelements_exercise_id_fkey
+    FOREIGN KEY (exercise_id) REFERENCES Exercises(id) ON DELETE RESTRICT;
+
+ALTER TABLE WKExecutionElements DROP CONSTRAINT wkexecutionelements_exercise_id_fkey;
+ALTER TABLE WKExecutionElements ADD CONSTRAINT wkexecutionelements_exercise_id_fkey
+    FOREIGN KEY (exercise_id) REFERENCES Exercises(id) ON DELETE RESTRICT;
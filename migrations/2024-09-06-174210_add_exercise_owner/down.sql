@@ -0,0 +1,8 @@
+-- This file should undo anything in `up.sql`
+DELETE FROM WKTemplateElements WHERE exercise_id IN (SELECT id FROM Exercises WHERE owner_id IS NOT NULL);
+DELETE FROM WKExecutionElements WHERE exercise_id IN (SELECT id FROM Exercises WHERE owner_id IS NOT NULL);
+DELETE FROM Exercises WHERE owner_id IS NOT NULL;
+DROP INDEX exercises_owner_name_key;
+DROP INDEX exercises_catalog_name_key;
+ALTER TABLE Exercises ADD CONSTRAINT exercises_name_key UNIQUE (name);
+ALTER TABLE Exercises DROP COLUMN owner_id;
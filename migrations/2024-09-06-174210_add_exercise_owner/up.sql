@@ -0,0 +1,7 @@
+-- Your SQL goes here
+ALTER TABLE Exercises ADD COLUMN owner_id UUID REFERENCES Users(id) ON DELETE CASCADE;
+
+-- Names are unique within the shared catalog and within each user's private exercises
+ALTER TABLE Exercises DROP CONSTRAINT exercises_name_key;
+CREATE UNIQUE INDEX exercises_catalog_name_key ON Exercises(name) WHERE owner_id IS NULL;
+CREATE UNIQUE INDEX exercises_owner_name_key ON Exercises(owner_id, name) WHERE owner_id IS NOT NULL;
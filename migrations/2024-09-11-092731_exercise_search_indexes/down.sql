@@ -0,0 +1,5 @@
+-- This file should undo anything in `up.sql`
+DROP INDEX exercises_exercise_type_idx;
+DROP INDEX exercises_necessary_equipment_idx;
+DROP INDEX exercises_main_muscle_group_idx;
+DROP INDEX exercises_name_trgm_idx;
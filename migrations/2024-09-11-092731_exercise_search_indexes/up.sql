@@ -0,0 +1,10 @@
+-- Your SQL goes here
+CREATE EXTENSION IF NOT EXISTS pg_trgm;
+
+-- Fuzzy, case-insensitive name search
+CREATE INDEX exercises_name_trgm_idx ON Exercises USING GIN (name gin_trgm_ops);
+
+-- Facet filters
+CREATE INDEX exercises_main_muscle_group_idx ON Exercises(main_muscle_group);
+CREATE INDEX exercises_necessary_equipment_idx ON Exercises(necessary_equipment);
+CREATE INDEX exercises_exercise_type_idx ON Exercises(exercise_type);
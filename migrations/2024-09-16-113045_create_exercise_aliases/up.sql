@@ -0,0 +1,13 @@
+-- Your SQL goes here
+CREATE TABLE ExerciseAliases (
+    id UUID DEFAULT gen_random_uuid() PRIMARY KEY,
+    exercise_id UUID NOT NULL REFERENCES Exercises(id) ON DELETE CASCADE,
+    locale VARCHAR(16) NOT NULL,
+    name VARCHAR(255) NOT NULL,
+    preferred BOOLEAN NOT NULL DEFAULT FALSE,
+    UNIQUE (exercise_id, locale, name)
+);
+
+-- At most one localized name per exercise and locale
+CREATE UNIQUE INDEX exercisealiases_preferred_key ON ExerciseAliases(exercise_id, locale) WHERE preferred;
+CREATE INDEX exercisealiases_name_trgm_idx ON ExerciseAliases USING GIN (name gin_trgm_ops);
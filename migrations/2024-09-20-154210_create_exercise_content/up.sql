@@ -0,0 +1,21 @@
+-- Your SQL goes here
+-- Texts are never NULL within the arrays either
+CREATE TABLE ExerciseInstructions (
+    exercise_id UUID PRIMARY KEY REFERENCES Exercises(id) ON DELETE CASCADE,
+    description TEXT,
+    steps TEXT[] NOT NULL DEFAULT '{}' CHECK (array_position(steps, NULL) IS NULL),
+    cues TEXT[] NOT NULL DEFAULT '{}' CHECK (array_position(cues, NULL) IS NULL),
+    common_mistakes TEXT[] NOT NULL DEFAULT '{}' CHECK (array_position(common_mistakes, NULL) IS NULL)
+);
+
+CREATE TABLE ExerciseMedia (
+    id UUID DEFAULT gen_random_uuid() PRIMARY KEY,
+    exercise_id UUID NOT NULL REFERENCES Exercises(id) ON DELETE CASCADE,
+    position SMALLINT NOT NULL,
+    media_type SMALLINT NOT NULL,
+    url TEXT NOT NULL,
+    thumbnail_url TEXT,
+    width INTEGER CHECK (width > 0),
+    height INTEGER CHECK (height > 0),
+    UNIQUE (exercise_id, position)
+);
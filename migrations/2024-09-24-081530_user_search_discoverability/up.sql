@@ -0,0 +1,6 @@
+-- Your SQL goes here
+ALTER TABLE Users ADD COLUMN discoverable BOOLEAN NOT NULL DEFAULT TRUE;
+
+-- Case-insensitive prefix and word matching (pg_trgm)
+CREATE INDEX users_username_trgm_idx ON Users USING GIN (username gin_trgm_ops);
+CREATE INDEX users_display_name_trgm_idx ON Users USING GIN (display_name gin_trgm_ops);
@@ -0,0 +1,3 @@
+-- This file should undo anything in `up.sql`
+DROP INDEX users_deletion_scheduled_for_idx;
+ALTER TABLE Users DROP COLUMN deletion_scheduled_for;
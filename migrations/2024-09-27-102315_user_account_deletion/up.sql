@@ -0,0 +1,4 @@
+-- Your SQL goes here
+ALTER TABLE Users ADD COLUMN deletion_scheduled_for TIMESTAMP;
+
+CREATE INDEX users_deletion_scheduled_for_idx ON Users(deletion_scheduled_for) WHERE deletion_scheduled_for IS NOT NULL;
@@ -0,0 +1,8 @@
+-- Your SQL goes here
+-- Workouts imported from other apps, so uploading the same export twice doesn't duplicate them
+CREATE TABLE WorkoutImports (
+    user_id UUID NOT NULL REFERENCES Users(id) ON DELETE CASCADE,
+    import_key TEXT NOT NULL,
+    workout_execution_id UUID NOT NULL REFERENCES WorkoutExecutions(id) ON DELETE CASCADE,
+    PRIMARY KEY (user_id, import_key)
+);
@@ -0,0 +1,19 @@
+-- Your SQL goes here
+-- Weights in kg, body fat in %, circumferences in cm
+CREATE TABLE BodyMeasurements (
+    id UUID DEFAULT gen_random_uuid() PRIMARY KEY,
+    user_id UUID NOT NULL REFERENCES Users(id) ON DELETE CASCADE,
+    date DATE NOT NULL DEFAULT CURRENT_DATE,
+    weight FLOAT4 CHECK (weight > 0),
+    body_fat FLOAT4 CHECK (body_fat >= 0 AND body_fat <= 100),
+    waist FLOAT4 CHECK (waist > 0),
+    chest FLOAT4 CHECK (chest > 0),
+    arms FLOAT4 CHECK (arms > 0),
+    thighs FLOAT4 CHECK (thighs > 0),
+    hips FLOAT4 CHECK (hips > 0),
+    UNIQUE (user_id, date)
+);
+
+-- Keep the weight history that was only stored in the profile
+INSERT INTO BodyMeasurements (user_id, date, weight)
+    SELECT id, CURRENT_DATE, weight FROM Users WHERE weight > 0;
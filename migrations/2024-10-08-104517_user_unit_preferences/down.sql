@@ -0,0 +1,9 @@
+-- This file should undo anything in `up.sql`
+COMMENT ON COLUMN WkExecutionElements.weight IS NULL;
+COMMENT ON COLUMN WkTemplateElements.weight IS NULL;
+COMMENT ON COLUMN Users.height IS NULL;
+COMMENT ON COLUMN Users.weight IS NULL;
+
+ALTER TABLE Users
+    DROP COLUMN length_unit,
+    DROP COLUMN weight_unit;
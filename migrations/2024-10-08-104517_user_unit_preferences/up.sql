@@ -0,0 +1,12 @@
+-- Your SQL goes here
+-- Measures are always stored in metric units, preferences only affect what the API takes and returns
+ALTER TABLE Users
+    ADD COLUMN weight_unit SMALLINT NOT NULL DEFAULT 0 CHECK (weight_unit IN (0, 1)),
+    ADD COLUMN length_unit SMALLINT NOT NULL DEFAULT 0 CHECK (length_unit IN (0, 1));
+
+COMMENT ON COLUMN Users.weight_unit IS '0 kg, 1 lb';
+COMMENT ON COLUMN Users.length_unit IS '0 cm, 1 in';
+COMMENT ON COLUMN Users.weight IS 'kg';
+COMMENT ON COLUMN Users.height IS 'cm';
+COMMENT ON COLUMN WkTemplateElements.weight IS 'kg';
+COMMENT ON COLUMN WkExecutionElements.weight IS 'kg';
@@ -0,0 +1,3 @@
+-- This file should undo anything in `up.sql`
+DROP TABLE Follows;
+ALTER TABLE Users DROP COLUMN private_profile;
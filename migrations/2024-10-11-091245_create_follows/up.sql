@@ -0,0 +1,14 @@
+-- Your SQL goes here
+ALTER TABLE Users ADD COLUMN private_profile BOOLEAN NOT NULL DEFAULT FALSE;
+
+-- Follows of private profiles stay pending until the followed user accepts them
+CREATE TABLE Follows (
+    follower_id UUID NOT NULL REFERENCES Users(id) ON DELETE CASCADE,
+    followed_id UUID NOT NULL REFERENCES Users(id) ON DELETE CASCADE,
+    accepted BOOLEAN NOT NULL,
+    requested_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (follower_id, followed_id),
+    CHECK (follower_id <> followed_id)
+);
+
+CREATE INDEX follows_followed_id_idx ON Follows(followed_id, accepted, requested_at);
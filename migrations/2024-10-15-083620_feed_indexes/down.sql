@@ -0,0 +1,4 @@
+-- This file should undo anything in `up.sql`
+DROP INDEX wkexecutionelements_execution_idx;
+DROP INDEX workoutexecutions_template_date_idx;
+DROP INDEX workouttemplates_user_id_idx;
@@ -0,0 +1,5 @@
+-- Your SQL goes here
+-- Feed goes from followed users to their templates, executions and elements
+CREATE INDEX workouttemplates_user_id_idx ON WorkoutTemplates(user_id);
+CREATE INDEX workoutexecutions_template_date_idx ON WorkoutExecutions(workout_template_id, date DESC, id DESC);
+CREATE INDEX wkexecutionelements_execution_idx ON WkExecutionElements(workout_execution_id);
@@ -0,0 +1,20 @@
+-- Your SQL goes here
+CREATE TABLE Comments (
+    id UUID DEFAULT gen_random_uuid() PRIMARY KEY,
+    workout_execution_id UUID NOT NULL REFERENCES WorkoutExecutions(id) ON DELETE CASCADE,
+    user_id UUID NOT NULL REFERENCES Users(id) ON DELETE CASCADE,
+    content TEXT NOT NULL CHECK (char_length(content) BETWEEN 1 AND 1000),
+    date_created TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    date_edited TIMESTAMP
+);
+
+CREATE INDEX comments_workout_execution_id_idx ON Comments(workout_execution_id, date_created);
+
+-- One reaction per user and execution, 0 like, 1 fire, 2 strong, 3 clap, 4 wow
+CREATE TABLE Reactions (
+    workout_execution_id UUID NOT NULL REFERENCES WorkoutExecutions(id) ON DELETE CASCADE,
+    user_id UUID NOT NULL REFERENCES Users(id) ON DELETE CASCADE,
+    reaction SMALLINT NOT NULL CHECK (reaction BETWEEN 0 AND 4),
+    date_created TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (workout_execution_id, user_id)
+);
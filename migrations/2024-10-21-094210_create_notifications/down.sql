@@ -0,0 +1,9 @@
+-- This file should undo anything in `up.sql`
+DROP TABLE Notifications;
+
+ALTER TABLE Users
+    DROP COLUMN notify_new_followers,
+    DROP COLUMN notify_comments,
+    DROP COLUMN notify_reactions,
+    DROP COLUMN notify_prs,
+    DROP COLUMN notify_template_saves;
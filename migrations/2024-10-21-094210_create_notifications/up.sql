@@ -0,0 +1,26 @@
+-- Your SQL goes here
+ALTER TABLE Users
+    ADD COLUMN notify_new_followers BOOLEAN NOT NULL DEFAULT TRUE,
+    ADD COLUMN notify_comments BOOLEAN NOT NULL DEFAULT TRUE,
+    ADD COLUMN notify_reactions BOOLEAN NOT NULL DEFAULT TRUE,
+    ADD COLUMN notify_prs BOOLEAN NOT NULL DEFAULT TRUE,
+    ADD COLUMN notify_template_saves BOOLEAN NOT NULL DEFAULT TRUE;
+
+-- Kind: 0 new follower, 1 comment, 2 reaction, 3 PR achieved, 4 shared template saved
+-- Rows go away with whatever they point to
+CREATE TABLE Notifications (
+    id UUID DEFAULT gen_random_uuid() PRIMARY KEY,
+    user_id UUID NOT NULL REFERENCES Users(id) ON DELETE CASCADE,
+    kind SMALLINT NOT NULL CHECK (kind BETWEEN 0 AND 4),
+    actor_id UUID REFERENCES Users(id) ON DELETE CASCADE,
+    workout_execution_id UUID REFERENCES WorkoutExecutions(id) ON DELETE CASCADE,
+    workout_template_id UUID REFERENCES WorkoutTemplates(id) ON DELETE CASCADE,
+    comment_id UUID REFERENCES Comments(id) ON DELETE CASCADE,
+    read BOOLEAN NOT NULL DEFAULT FALSE,
+    date_created TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX notifications_user_id_idx ON Notifications(user_id, read, date_created);
+
+-- A user reacting again to the same execution doesn't notify twice
+CREATE UNIQUE INDEX notifications_reaction_idx ON Notifications(user_id, actor_id, workout_execution_id) WHERE kind = 2;
@@ -0,0 +1,2 @@
+-- Your SQL goes here
+ALTER TABLE Users ADD COLUMN show_in_leaderboards BOOLEAN NOT NULL DEFAULT TRUE;
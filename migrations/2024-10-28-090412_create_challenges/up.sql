@@ -0,0 +1,26 @@
+-- Your SQL goes here
+-- Metric: 0 total volume, 1 number of workouts, 2 reps of one exercise
+-- Exercises of challenges can only be retired, like the ones in templates and executions
+CREATE TABLE Challenges (
+    id UUID DEFAULT gen_random_uuid() PRIMARY KEY,
+    creator_id UUID NOT NULL REFERENCES Users(id) ON DELETE CASCADE,
+    name VARCHAR NOT NULL,
+    metric SMALLINT NOT NULL CHECK (metric BETWEEN 0 AND 2),
+    exercise_id UUID REFERENCES Exercises(id) ON DELETE RESTRICT,
+    start_date DATE NOT NULL,
+    end_date DATE NOT NULL,
+    date_created TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    CHECK (start_date <= end_date),
+    CHECK ((metric = 2) = (exercise_id IS NOT NULL))
+);
+
+-- Invited users, only counted once they join
+CREATE TABLE ChallengeParticipants (
+    challenge_id UUID NOT NULL REFERENCES Challenges(id) ON DELETE CASCADE,
+    user_id UUID NOT NULL REFERENCES Users(id) ON DELETE CASCADE,
+    joined BOOLEAN NOT NULL DEFAULT FALSE,
+    invited_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (challenge_id, user_id)
+);
+
+CREATE INDEX challengeparticipants_user_id_idx ON ChallengeParticipants(user_id);
@@ -0,0 +1,5 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE WorkoutExecutions DROP COLUMN program_slot_id;
+DROP TABLE ProgramEnrolments;
+DROP TABLE ProgramSlots;
+DROP TABLE Programs;
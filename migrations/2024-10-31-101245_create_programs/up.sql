@@ -0,0 +1,40 @@
+-- Your SQL goes here
+CREATE TABLE Programs (
+    id UUID DEFAULT gen_random_uuid() PRIMARY KEY,
+    user_id UUID NOT NULL REFERENCES Users(id) ON DELETE CASCADE,
+    name VARCHAR(255) NOT NULL,
+    description TEXT,
+    weeks SMALLINT NOT NULL CHECK (weeks BETWEEN 1 AND 52),
+    date_created DATE NOT NULL DEFAULT CURRENT_DATE
+);
+
+CREATE INDEX programs_user_id_idx ON Programs(user_id);
+
+-- Template done on a day of a program week, days counted from the start of the enrolment
+-- * reps and intensity, a percentage of the template weights, override the template that week
+CREATE TABLE ProgramSlots (
+    id UUID DEFAULT gen_random_uuid() PRIMARY KEY,
+    program_id UUID NOT NULL REFERENCES Programs(id) ON DELETE CASCADE,
+    workout_template_id UUID NOT NULL REFERENCES WorkoutTemplates(id) ON DELETE CASCADE,
+    week SMALLINT NOT NULL CHECK (week >= 1),
+    day SMALLINT NOT NULL CHECK (day BETWEEN 1 AND 7),
+    reps SMALLINT CHECK (reps > 0),
+    intensity SMALLINT CHECK (intensity BETWEEN 1 AND 200),
+    UNIQUE (program_id, week, day)
+);
+
+-- Runs of a program, active until ended, one at a time per user
+CREATE TABLE ProgramEnrolments (
+    id UUID DEFAULT gen_random_uuid() PRIMARY KEY,
+    program_id UUID NOT NULL REFERENCES Programs(id) ON DELETE CASCADE,
+    user_id UUID NOT NULL REFERENCES Users(id) ON DELETE CASCADE,
+    start_date DATE NOT NULL,
+    date_ended DATE,
+    date_created TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE UNIQUE INDEX programenrolments_active_idx ON ProgramEnrolments(user_id) WHERE date_ended IS NULL;
+CREATE INDEX programenrolments_program_id_idx ON ProgramEnrolments(program_id);
+
+ALTER TABLE WorkoutExecutions ADD COLUMN program_slot_id UUID REFERENCES ProgramSlots(id) ON DELETE SET NULL;
+CREATE INDEX workoutexecutions_program_slot_id_idx ON WorkoutExecutions(program_slot_id);
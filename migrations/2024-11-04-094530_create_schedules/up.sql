@@ -0,0 +1,23 @@
+-- Your SQL goes here
+-- One-off on start_date when weekdays is NULL, otherwise on those ISO weekdays (1 Monday to 7 Sunday)
+-- from start_date until end_date, or indefinitely
+CREATE TABLE ScheduledWorkouts (
+    id UUID DEFAULT gen_random_uuid() PRIMARY KEY,
+    user_id UUID NOT NULL REFERENCES Users(id) ON DELETE CASCADE,
+    workout_template_id UUID NOT NULL REFERENCES WorkoutTemplates(id) ON DELETE CASCADE,
+    start_date DATE NOT NULL,
+    end_date DATE,
+    weekdays SMALLINT[] CHECK (cardinality(weekdays) > 0 AND array_position(weekdays, NULL) IS NULL AND weekdays <@ ARRAY[1, 2, 3, 4, 5, 6, 7]::SMALLINT[]),
+    date_created TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    CHECK (end_date IS NULL OR start_date <= end_date),
+    CHECK (weekdays IS NOT NULL OR end_date IS NULL)
+);
+
+CREATE INDEX scheduledworkouts_user_id_idx ON ScheduledWorkouts(user_id);
+
+-- Secret in the URL of the user's iCal feed, rotating it revokes the previous one
+CREATE TABLE CalendarTokens (
+    user_id UUID PRIMARY KEY REFERENCES Users(id) ON DELETE CASCADE,
+    token UUID NOT NULL UNIQUE,
+    date_created TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
@@ -0,0 +1,17 @@
+-- Your SQL goes here
+-- Kind: 0 linear, 1 double progression
+-- * Linear adds `weight_increment` once every set reached the element's reps
+-- * Double progression climbs from `min_reps` to `max_reps`, then adds the weight and starts over
+-- Both cut `deload_percent` of the weight after `deload_after` failed sessions in a row, if set
+CREATE TABLE ProgressionRules (
+    wk_template_element_id UUID PRIMARY KEY REFERENCES WkTemplateElements(id) ON DELETE CASCADE,
+    kind SMALLINT NOT NULL CHECK (kind BETWEEN 0 AND 1),
+    weight_increment REAL NOT NULL CHECK (weight_increment > 0),
+    min_reps SMALLINT CHECK (min_reps > 0),
+    max_reps SMALLINT,
+    deload_after SMALLINT CHECK (deload_after BETWEEN 1 AND 10),
+    deload_percent SMALLINT NOT NULL DEFAULT 10 CHECK (deload_percent BETWEEN 1 AND 50),
+    date_created TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    CHECK ((kind = 1) = (min_reps IS NOT NULL AND max_reps IS NOT NULL)),
+    CHECK (max_reps > min_reps)
+);
@@ -0,0 +1,3 @@
+-- This file should undo anything in `up.sql`
+DROP INDEX workoutexecutions_in_progress_idx;
+ALTER TABLE WorkoutExecutions DROP COLUMN status, DROP COLUMN last_activity;
@@ -0,0 +1,8 @@
+-- Your SQL goes here
+-- Status: 0 completed, 1 in progress, 2 abandoned
+-- Sessions in progress are filled in set by set, last_activity is when the latest one was logged
+ALTER TABLE WorkoutExecutions
+    ADD COLUMN status SMALLINT NOT NULL DEFAULT 0 CHECK (status BETWEEN 0 AND 2),
+    ADD COLUMN last_activity TIMESTAMP;
+
+CREATE INDEX workoutexecutions_in_progress_idx ON WorkoutExecutions(last_activity) WHERE status = 1;
@@ -0,0 +1,7 @@
+-- This file should undo anything in `up.sql`
+DROP TRIGGER wkexecutionelements_sync ON WkExecutionElements;
+DROP TRIGGER workoutexecutions_sync ON WorkoutExecutions;
+DROP TRIGGER wktemplateelements_sync ON WkTemplateElements;
+DROP TRIGGER workouttemplates_sync ON WorkoutTemplates;
+DROP FUNCTION track_sync_change();
+DROP TABLE SyncChanges;
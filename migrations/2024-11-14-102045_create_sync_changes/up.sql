@@ -0,0 +1,73 @@
+-- Your SQL goes here
+-- Kind: 0 workout template, 1 template element, 2 workout execution, 3 execution element
+-- One row per record of the user, kept by the triggers below, deleted records stay as tombstones
+-- created and updated are the ids of the transactions that wrote them, see txid_current()
+CREATE TABLE SyncChanges (
+    kind SMALLINT NOT NULL CHECK (kind BETWEEN 0 AND 3),
+    id UUID NOT NULL,
+    user_id UUID NOT NULL REFERENCES Users(id) ON DELETE CASCADE,
+    created BIGINT NOT NULL DEFAULT txid_current(),
+    updated BIGINT NOT NULL DEFAULT txid_current(),
+    deleted BOOLEAN NOT NULL DEFAULT FALSE,
+    PRIMARY KEY (kind, id)
+);
+
+CREATE INDEX syncchanges_user_updated_idx ON SyncChanges(user_id, updated);
+
+-- The kind of the table is its argument, the owner is looked up through the parents on insert
+-- Rows deleted with their parents still know their owner, so they get a tombstone too
+CREATE FUNCTION track_sync_change() RETURNS TRIGGER AS $$
+DECLARE
+    change_kind SMALLINT := TG_ARGV[0]::SMALLINT;
+    owner_id UUID;
+BEGIN
+    IF TG_OP = 'DELETE' THEN
+        UPDATE SyncChanges SET updated = txid_current(), deleted = TRUE WHERE kind = change_kind AND id = OLD.id;
+        RETURN OLD;
+    ELSIF TG_OP = 'UPDATE' THEN
+        UPDATE SyncChanges SET updated = txid_current() WHERE kind = change_kind AND id = NEW.id;
+        RETURN NEW;
+    END IF;
+
+    IF change_kind = 0 THEN
+        owner_id := NEW.user_id;
+    ELSIF change_kind IN (1, 2) THEN
+        SELECT wt.user_id INTO owner_id FROM WorkoutTemplates wt WHERE wt.id = NEW.workout_template_id;
+    ELSE
+        SELECT wt.user_id INTO owner_id
+        FROM WorkoutExecutions we
+        JOIN WorkoutTemplates wt ON wt.id = we.workout_template_id
+        WHERE we.id = NEW.workout_execution_id;
+    END IF;
+    -- An id can come back after being deleted, clients may reuse their own but never someone else's
+    INSERT INTO SyncChanges (kind, id, user_id) VALUES (change_kind, NEW.id, owner_id)
+    ON CONFLICT (kind, id) DO UPDATE
+    SET created = txid_current(), updated = txid_current(), deleted = FALSE
+    WHERE SyncChanges.user_id = EXCLUDED.user_id;
+    IF NOT FOUND THEN
+        RAISE EXCEPTION 'Id % was already used by another user', NEW.id USING ERRCODE = 'unique_violation';
+    END IF;
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER workouttemplates_sync AFTER INSERT OR UPDATE OR DELETE ON WorkoutTemplates
+    FOR EACH ROW EXECUTE FUNCTION track_sync_change(0);
+CREATE TRIGGER wktemplateelements_sync AFTER INSERT OR UPDATE OR DELETE ON WkTemplateElements
+    FOR EACH ROW EXECUTE FUNCTION track_sync_change(1);
+CREATE TRIGGER workoutexecutions_sync AFTER INSERT OR UPDATE OR DELETE ON WorkoutExecutions
+    FOR EACH ROW EXECUTE FUNCTION track_sync_change(2);
+CREATE TRIGGER wkexecutionelements_sync AFTER INSERT OR UPDATE OR DELETE ON WkExecutionElements
+    FOR EACH ROW EXECUTE FUNCTION track_sync_change(3);
+
+INSERT INTO SyncChanges (kind, id, user_id)
+SELECT 0, wt.id, wt.user_id FROM WorkoutTemplates wt;
+INSERT INTO SyncChanges (kind, id, user_id)
+SELECT 1, el.id, wt.user_id FROM WkTemplateElements el JOIN WorkoutTemplates wt ON wt.id = el.workout_template_id;
+INSERT INTO SyncChanges (kind, id, user_id)
+SELECT 2, we.id, wt.user_id FROM WorkoutExecutions we JOIN WorkoutTemplates wt ON wt.id = we.workout_template_id;
+INSERT INTO SyncChanges (kind, id, user_id)
+SELECT 3, el.id, wt.user_id
+FROM WkExecutionElements el
+JOIN WorkoutExecutions we ON we.id = el.workout_execution_id
+JOIN WorkoutTemplates wt ON wt.id = we.workout_template_id;
@@ -0,0 +1,66 @@
+use chrono::{Duration, Utc};
+use lambda_http::{service_fn, Error, LambdaEvent, tracing};
+use lambda_http::lambda_runtime;
+use serde_json::{json, Value};
+use tracing::{error, info};
+
+use salamandra_server::lib::db::workout_sessions_db::abandon_stale_sessions;
+use salamandra_server::lib::models::workout_execution_models::SESSION_TIMEOUT_HOURS;
+use salamandra_server::lib::db::DBConnector;
+
+#[tokio::main]
+async fn main() -> Result<(), Error> {
+    tracing::init_default_subscriber();
+    let handler = service_fn(|event| async {
+        let connector = DBConnector::default();
+        abandon_sessions(event, &connector).await
+    });
+    lambda_runtime::run(handler).await
+}
+
+/// Scheduled job giving up on the workouts in progress nobody logged a set in for too long
+/// * They are kept, but don't count anywhere until discarded
+async fn abandon_sessions(_event: LambdaEvent<Value>, connector: &DBConnector) -> Result<Value, Error> {
+    let until = Utc::now().naive_utc() - Duration::hours(SESSION_TIMEOUT_HOURS);
+    match abandon_stale_sessions(until, connector).await {
+        Ok(abandoned) => {
+            info!("Abandoned {} sessions", abandoned);
+            Ok(json!({ "abandoned": abandoned }))
+        }
+        Err(mes) => {
+            error!("INTERNAL SERVER ERROR: {}", mes);
+            Err(mes.to_string().into())
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lambda_http::Context;
+    use salamandra_server::lib::utils::tests::{pg_container, insert_helper, Items};
+    use salamandra_server::lib::db::workout_executions_db::lookup_workout_execution;
+    use salamandra_server::lib::db::workout_sessions_db::insert_workout_session;
+    use salamandra_server::lib::db::workout_templates_db::lookup_workout_template;
+    use salamandra_server::lib::models::workout_execution_models::{NewWorkoutSession, ABANDONED, IN_PROGRESS};
+
+    // TEST CASES
+    // * Abandons stale sessions only
+
+    #[tokio::test]
+    async fn test_abandon_sessions() {
+        let (connector, _container) = pg_container().await;
+        let template_id = insert_helper(1, Items::WkTemplates, &connector, None).await[0];
+        let user_id = lookup_workout_template(template_id, &connector).await.unwrap().user_id;
+        let now = Utc::now().naive_utc();
+        let stale = NewWorkoutSession::new(template_id, now.date(), None, now - Duration::hours(SESSION_TIMEOUT_HOURS + 1));
+        let stale_id = insert_workout_session(&stale, user_id, &connector).await.unwrap().id;
+        let active_id = insert_workout_session(&NewWorkoutSession::new(template_id, now.date(), None, now), user_id, &connector).await.unwrap().id;
+
+        let resp = abandon_sessions(LambdaEvent::new(json!({}), Context::default()), &connector).await;
+        assert_eq!(resp.unwrap(), json!({ "abandoned": 1 }));
+        assert_eq!(lookup_workout_execution(stale_id, &connector).await.unwrap().status, ABANDONED);
+        assert_eq!(lookup_workout_execution(active_id, &connector).await.unwrap().status, IN_PROGRESS);
+    }
+}
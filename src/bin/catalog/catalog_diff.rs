@@ -0,0 +1,139 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+use salamandra_server::lib::models::exercise_models::{CatalogEntry, Exercise};
+
+/// Differences between the current catalog and an imported file
+#[derive(Debug, Default, PartialEq)]
+pub struct CatalogDiff {
+    pub additions: Vec<CatalogEntry>,
+    pub changes: Vec<(CatalogEntry, CatalogEntry)>,
+    pub conflicts: Vec<(CatalogEntry, String)>,
+    pub unchanged: usize,
+}
+
+impl CatalogDiff {
+    pub fn is_empty(&self) -> bool {
+        self.additions.is_empty() && self.changes.is_empty() && self.conflicts.is_empty()
+    }
+}
+
+/// Compare imported entries with the catalog, matching by exact name
+/// * Conflicts are never applied: invalid values, names repeated in the file,
+///   retired exercises and names that only match an existing one ignoring case
+pub fn diff_catalog(current: &[Exercise], incoming: Vec<CatalogEntry>) -> CatalogDiff {
+    let by_name: HashMap<&str, &Exercise> = current.iter().map(|ex| (ex.name.as_str(), ex)).collect();
+    let by_lowercase: HashMap<String, &Exercise> = current.iter().map(|ex| (ex.name.to_lowercase(), ex)).collect();
+    let mut seen = HashSet::new();
+    let mut diff = CatalogDiff::default();
+
+    for entry in incoming {
+        let codes = [entry.main_muscle_group, entry.secondary_muscle_group, entry.necessary_equipment, entry.exercise_type];
+        if entry.name.trim().is_empty() || codes.iter().any(|code| code.is_some_and(|c| c < 0)) {
+            diff.conflicts.push((entry, "name can't be empty and codes can't be negative".to_string()));
+            continue;
+        }
+        if !seen.insert(entry.name.to_lowercase()) {
+            diff.conflicts.push((entry, "repeated in the file".to_string()));
+            continue;
+        }
+
+        match by_name.get(entry.name.as_str()) {
+            Some(exercise) if exercise.retired => {
+                diff.conflicts.push((entry, "exercise is retired".to_string()));
+            }
+            Some(exercise) => {
+                let existing = CatalogEntry {
+                    name: exercise.name.clone(),
+                    main_muscle_group: exercise.main_muscle_group,
+                    secondary_muscle_group: exercise.secondary_muscle_group,
+                    necessary_equipment: exercise.necessary_equipment,
+                    exercise_type: exercise.exercise_type,
+                };
+                if existing == entry {
+                    diff.unchanged += 1;
+                } else {
+                    diff.changes.push((existing, entry));
+                }
+            }
+            None => match by_lowercase.get(&entry.name.to_lowercase()) {
+                Some(exercise) => {
+                    let reason = format!("differs only in case from '{}'", exercise.name);
+                    diff.conflicts.push((entry, reason));
+                }
+                None => diff.additions.push(entry),
+            },
+        }
+    }
+    diff
+}
+
+fn codes(entry: &CatalogEntry) -> String {
+    [entry.main_muscle_group, entry.secondary_muscle_group, entry.necessary_equipment, entry.exercise_type]
+        .iter()
+        .map(|code| code.map_or("-".to_string(), |c| c.to_string()))
+        .collect::<Vec<String>>()
+        .join("/")
+}
+
+impl fmt::Display for CatalogDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for entry in &self.additions {
+            writeln!(f, "+ {} [{}]", entry.name, codes(entry))?;
+        }
+        for (old, new) in &self.changes {
+            writeln!(f, "~ {} [{}] -> [{}]", new.name, codes(old), codes(new))?;
+        }
+        for (entry, reason) in &self.conflicts {
+            writeln!(f, "! {} ({})", entry.name, reason)?;
+        }
+        write!(f, "{} additions, {} changes, {} conflicts, {} unchanged",
+            self.additions.len(), self.changes.len(), self.conflicts.len(), self.unchanged)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    // TEST CASES
+    // * Additions, changes and unchanged
+    // * Conflicts: invalid, repeated, retired, case mismatch
+
+    fn exercise(name: &str, exercise_type: Option<i16>, retired: bool) -> Exercise {
+        Exercise { id: Uuid::new_v4(), name: name.to_string(), main_muscle_group: None, secondary_muscle_group: None, necessary_equipment: None, exercise_type, retired, owner_id: None }
+    }
+
+    fn entry(name: &str, exercise_type: Option<i16>) -> CatalogEntry {
+        CatalogEntry { name: name.to_string(), main_muscle_group: None, secondary_muscle_group: None, necessary_equipment: None, exercise_type }
+    }
+
+    #[test]
+    fn test_diff_catalog_changes() {
+        let current = vec![exercise("Squat", Some(1), false), exercise("Push Up", Some(1), false)];
+        let incoming = vec![entry("Squat", Some(1)), entry("Push Up", Some(2)), entry("Bench Press", None)];
+
+        let diff = diff_catalog(&current, incoming);
+        assert_eq!(diff.additions, vec![entry("Bench Press", None)]);
+        assert_eq!(diff.changes, vec![(entry("Push Up", Some(1)), entry("Push Up", Some(2)))]);
+        assert!(diff.conflicts.is_empty());
+        assert_eq!(diff.unchanged, 1);
+        assert!(diff.to_string().ends_with("1 additions, 1 changes, 0 conflicts, 1 unchanged"));
+
+        assert!(diff_catalog(&current, vec![entry("Squat", Some(1))]).is_empty());
+    }
+
+    #[test]
+    fn test_diff_catalog_conflicts() {
+        let current = vec![exercise("Squat", None, false), exercise("Dips", None, true)];
+        let incoming = vec![entry("", None), entry("Lunge", Some(-1)), entry("Row", None), entry("row", None), entry("Dips", None), entry("squat", None)];
+
+        let diff = diff_catalog(&current, incoming);
+        assert_eq!(diff.additions, vec![entry("Row", None)]);
+        assert!(diff.changes.is_empty());
+        let conflicts: Vec<&str> = diff.conflicts.iter().map(|(entry, _)| entry.name.as_str()).collect();
+        assert_eq!(conflicts, vec!["", "Lunge", "row", "Dips", "squat"]);
+    }
+}
@@ -0,0 +1,92 @@
+use std::path::Path;
+
+use salamandra_server::lib::models::exercise_models::CatalogEntry;
+
+/// Supported catalog file formats, chosen by file extension
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Format {
+    Csv,
+    Json,
+}
+
+impl Format {
+    pub fn from_path(path: &str) -> Result<Format, String> {
+        match Path::new(path).extension().and_then(|ext| ext.to_str()) {
+            Some(ext) if ext.eq_ignore_ascii_case("csv") => Ok(Format::Csv),
+            Some(ext) if ext.eq_ignore_ascii_case("json") => Ok(Format::Json),
+            _ => Err(format!("Unsupported file format for '{}', expected .csv or .json", path)),
+        }
+    }
+}
+
+/// Parse catalog entries, CSV files need a header with the field names
+/// * Empty CSV cells are read as missing values
+pub fn read_entries(content: &str, format: Format) -> Result<Vec<CatalogEntry>, String> {
+    match format {
+        Format::Json => serde_json::from_str(content).map_err(|e| e.to_string()),
+        Format::Csv => {
+            let mut reader = csv::ReaderBuilder::new().trim(csv::Trim::All).from_reader(content.as_bytes());
+            reader.deserialize().collect::<Result<Vec<CatalogEntry>, _>>().map_err(|e| e.to_string())
+        }
+    }
+}
+
+/// Serialize catalog entries in the same format accepted by `read_entries`
+pub fn write_entries(entries: &[CatalogEntry], format: Format) -> Result<String, String> {
+    match format {
+        Format::Json => serde_json::to_string_pretty(entries).map_err(|e| e.to_string()),
+        Format::Csv => {
+            let mut writer = csv::Writer::from_writer(Vec::new());
+            for entry in entries {
+                writer.serialize(entry).map_err(|e| e.to_string())?;
+            }
+            let bytes = writer.into_inner().map_err(|e| e.to_string())?;
+            String::from_utf8(bytes).map_err(|e| e.to_string())
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // TEST CASES
+    // * Format from extension
+    // * Round trip CSV and JSON
+    // * CSV with empty cells and quoted names
+
+    fn entries() -> Vec<CatalogEntry> {
+        vec![
+            CatalogEntry { name: "Push Up".to_string(), main_muscle_group: Some(2), secondary_muscle_group: Some(3), necessary_equipment: Some(2), exercise_type: Some(1) },
+            CatalogEntry { name: "Row, Cable".to_string(), main_muscle_group: Some(4), secondary_muscle_group: None, necessary_equipment: None, exercise_type: None },
+        ]
+    }
+
+    #[test]
+    fn test_format_from_path() {
+        assert_eq!(Format::from_path("catalog.CSV"), Ok(Format::Csv));
+        assert_eq!(Format::from_path("dir/catalog.json"), Ok(Format::Json));
+        assert!(Format::from_path("catalog.xlsx").is_err());
+        assert!(Format::from_path("catalog").is_err());
+    }
+
+    #[test]
+    fn test_round_trip() {
+        for format in [Format::Csv, Format::Json] {
+            let written = write_entries(&entries(), format).unwrap();
+            assert_eq!(read_entries(&written, format).unwrap(), entries());
+        }
+    }
+
+    #[test]
+    fn test_read_csv() {
+        let content = "name,main_muscle_group,secondary_muscle_group,necessary_equipment,exercise_type\n\
+                       Push Up,2,3,2,1\n\
+                       \"Row, Cable\", 4,,,\n";
+        assert_eq!(read_entries(content, Format::Csv).unwrap(), entries());
+
+        let content = "name,main_muscle_group,secondary_muscle_group,necessary_equipment,exercise_type\nSquat,abc,,,\n";
+        assert!(read_entries(content, Format::Csv).is_err());
+    }
+}
@@ -0,0 +1,82 @@
+mod catalog_diff;
+mod formats;
+
+use std::fs;
+use std::process::ExitCode;
+
+use catalog_diff::diff_catalog;
+use formats::{read_entries, write_entries, Format};
+use salamandra_server::lib::db::exercises_db::{select_catalog_exercises, upsert_catalog_exercises};
+use salamandra_server::lib::db::DBConnector;
+use salamandra_server::lib::models::exercise_models::CatalogEntry;
+
+const USAGE: &str = "Usage:
+    catalog import <file.csv|file.json> [--dry-run]
+    catalog export <file.csv|file.json>";
+
+/// Admin tool to keep the exercise catalog in sync with a CSV or JSON file
+/// * Import upserts by name and is aborted if there are conflicts
+/// * Export writes the catalog in the format accepted by import
+#[tokio::main]
+async fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let connector = DBConnector::default();
+
+    let result = match args.iter().map(String::as_str).collect::<Vec<&str>>().as_slice() {
+        ["import", path] => import(path, false, &connector).await,
+        ["import", path, "--dry-run"] | ["import", "--dry-run", path] => import(path, true, &connector).await,
+        ["export", path] => export(path, &connector).await,
+        _ => Err(USAGE.to_string()),
+    };
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(mes) => {
+            eprintln!("{}", mes);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+async fn import(path: &str, dry_run: bool, connector: &DBConnector) -> Result<(), String> {
+    let format = Format::from_path(path)?;
+    let content = fs::read_to_string(path).map_err(|e| format!("Couldn't read '{}': {}", path, e))?;
+    let incoming = read_entries(&content, format).map_err(|e| format!("Couldn't parse '{}': {}", path, e))?;
+
+    let current = select_catalog_exercises(connector).await.map_err(|e| e.to_string())?;
+    let diff = diff_catalog(&current, incoming);
+    println!("{}", diff);
+
+    if dry_run || diff.is_empty() {
+        return Ok(());
+    }
+    if !diff.conflicts.is_empty() {
+        return Err("Import aborted, solve the conflicts and try again".to_string());
+    }
+
+    let changes: Vec<CatalogEntry> = diff.changes.into_iter().map(|(_, new)| new).collect();
+    let affected = upsert_catalog_exercises(&diff.additions, &changes, connector).await.map_err(|e| e.to_string())?;
+    println!("Imported {} exercises", affected);
+    Ok(())
+}
+
+async fn export(path: &str, connector: &DBConnector) -> Result<(), String> {
+    let format = Format::from_path(path)?;
+    let entries: Vec<CatalogEntry> = select_catalog_exercises(connector).await
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .filter(|exercise| !exercise.retired)
+        .map(|exercise| CatalogEntry {
+            name: exercise.name,
+            main_muscle_group: exercise.main_muscle_group,
+            secondary_muscle_group: exercise.secondary_muscle_group,
+            necessary_equipment: exercise.necessary_equipment,
+            exercise_type: exercise.exercise_type,
+        })
+        .collect();
+
+    let content = write_entries(&entries, format)?;
+    fs::write(path, content).map_err(|e| format!("Couldn't write '{}': {}", path, e))?;
+    println!("Exported {} exercises to {}", entries.len(), path);
+    Ok(())
+}
@@ -0,0 +1,125 @@
+use lambda_http::{Error, Request, Response, Body};
+use lambda_http::http::StatusCode;
+use tracing::{error, warn};
+
+use salamandra_server::lib::db::exercises_db::insert_exercise;
+use salamandra_server::lib::models::exercise_models::NewExercise;
+use salamandra_server::lib::utils::handlers::{build_resp, extract_admin};
+use salamandra_server::lib::db::DBConnector;
+use salamandra_server::lib::errors::DBError;
+
+/// Add new exercise to the catalog
+/// * Only admins can modify the catalog
+pub async fn create_exercise(event: Request, connector: &DBConnector) -> Result<Response<Body>, Error> {
+
+    // Check caller is an admin
+    match extract_admin(event.headers()) {
+        Ok(_) => (),
+        Err(resp) => return Ok(resp)
+    };
+
+    // Check and extract payload
+    let body = match event.into_body() {
+        Body::Text(body) => body,
+        _ => return Ok(build_resp(StatusCode::BAD_REQUEST, "Invalid payload")),
+    };
+    let mut req: NewExercise = match serde_json::from_str(&body) {
+        Ok(req) => req,
+        Err(_) => return Ok(build_resp(StatusCode::BAD_REQUEST, "Invalid payload")),
+    };
+
+    // Check input
+    let codes = [req.main_muscle_group, req.secondary_muscle_group, req.necessary_equipment, req.exercise_type];
+    if req.name.trim().is_empty() || codes.iter().any(|code| code.is_some_and(|c| c < 0)) {
+        return Ok(build_resp(StatusCode::BAD_REQUEST, "Invalid payload: name can't be empty and codes can't be negative"));
+    }
+
+    // Catalog exercises have no owner
+    req.owner_id = None;
+
+    // Insert in database
+    match insert_exercise(&req, connector).await {
+        Ok(exercise) => Ok(build_resp(StatusCode::CREATED, exercise)),
+        Err(DBError::UniqueViolation(mes)) => {
+            warn!("Tried to insert already exisiting exercise");
+            Ok(build_resp(StatusCode::CONFLICT, mes))
+        },
+        Err(error) => {
+            error!("INTERNAL SERVER ERROR: {}", error);
+            Ok(build_resp(StatusCode::INTERNAL_SERVER_ERROR, ""))
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+    use serde_json::to_string;
+    use lambda_http::http::header::{AUTHORIZATION, HeaderValue};
+    use salamandra_server::lib::utils::tests::{pg_container, test_jwt, test_admin_jwt};
+    use salamandra_server::lib::models::exercise_models::Exercise;
+
+    // TEST CASES
+    // * Not an admin
+    // * Invalid payload
+    // * Success and duplicate name
+
+    #[tokio::test]
+    async fn test_create_exercise_not_admin() {
+        let (connector, _container) = pg_container().await;
+        let payload = NewExercise {..Default::default()};
+
+        let mut req = Request::default();
+        let jwt = test_jwt(Uuid::new_v4());
+        req.headers_mut().insert(AUTHORIZATION, HeaderValue::from_str(&jwt).unwrap());
+        *req.body_mut() = Body::from(to_string(&payload).expect("Error"));
+
+        let resp = create_exercise(req, &connector).await;
+        assert!(resp.is_ok());
+        let response = resp.unwrap();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    }
+
+    #[tokio::test]
+    async fn test_create_exercise_invalid_payload() {
+        let (connector, _container) = pg_container().await;
+        let payload = NewExercise {main_muscle_group: Some(-1), ..Default::default()};
+
+        let mut req = Request::default();
+        let jwt = test_admin_jwt(Uuid::new_v4());
+        req.headers_mut().insert(AUTHORIZATION, HeaderValue::from_str(&jwt).unwrap());
+        *req.body_mut() = Body::from(to_string(&payload).expect("Error"));
+
+        let resp = create_exercise(req, &connector).await;
+        assert!(resp.is_ok());
+        let response = resp.unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_create_exercise_success_and_existing() {
+        let (connector, _container) = pg_container().await;
+        let payload = NewExercise {name: "Bench Press".to_string(), ..Default::default()};
+
+        let mut req = Request::default();
+        let jwt = test_admin_jwt(Uuid::new_v4());
+        req.headers_mut().insert(AUTHORIZATION, HeaderValue::from_str(&jwt).unwrap());
+        *req.body_mut() = Body::from(to_string(&payload).expect("Error"));
+
+        let resp = create_exercise(req.clone(), &connector).await;
+        assert!(resp.is_ok());
+        let response = resp.unwrap();
+        assert_eq!(response.status(), StatusCode::CREATED);
+        if let Body::Text(body) = response.into_body() {
+            let exercise: Result<Exercise, _> = serde_json::from_str(&body);
+            assert_eq!(exercise.unwrap().name, "Bench Press");
+        }
+
+        let resp = create_exercise(req, &connector).await;
+        assert!(resp.is_ok());
+        let response = resp.unwrap();
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+    }
+}
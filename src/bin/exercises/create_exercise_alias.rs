@@ -0,0 +1,136 @@
+use lambda_http::{Error, Request, Response, Body, RequestExt};
+use lambda_http::http::StatusCode;
+use serde::{Deserialize, Serialize};
+use tracing::{error, warn};
+use uuid::Uuid;
+
+use salamandra_server::lib::db::exercise_aliases_db::insert_exercise_alias;
+use salamandra_server::lib::models::exercise_alias_models::NewExerciseAlias;
+use salamandra_server::lib::utils::handlers::{build_resp, extract_admin};
+use salamandra_server::lib::db::DBConnector;
+use salamandra_server::lib::errors::DBError;
+
+#[derive(Debug, Serialize, Deserialize)]
+struct AliasPayload {
+    locale: String,
+    name: String,
+    #[serde(default)]
+    preferred: bool,
+}
+
+/// Add an alias or translated name to an exercise
+/// * Preferred aliases are shown as the exercise name for their locale
+/// * Only admins can modify the catalog
+pub async fn create_exercise_alias(event: Request, connector: &DBConnector) -> Result<Response<Body>, Error> {
+
+    // Get path parameter
+    let exercise_id = Uuid::parse_str(event.path_parameters().first("exercise_id").unwrap()).unwrap();
+
+    // Check caller is an admin
+    match extract_admin(event.headers()) {
+        Ok(_) => (),
+        Err(resp) => return Ok(resp)
+    };
+
+    // Check and extract payload
+    let body = match event.into_body() {
+        Body::Text(body) => body,
+        _ => return Ok(build_resp(StatusCode::BAD_REQUEST, "Invalid payload")),
+    };
+    let req: AliasPayload = match serde_json::from_str(&body) {
+        Ok(req) => req,
+        Err(_) => return Ok(build_resp(StatusCode::BAD_REQUEST, "Invalid payload")),
+    };
+
+    // Check input
+    let valid_locale = !req.locale.is_empty() && req.locale.len() <= 16 && req.locale.chars().all(|c| c.is_ascii_alphanumeric() || c == '-');
+    if !valid_locale || req.name.trim().is_empty() {
+        return Ok(build_resp(StatusCode::BAD_REQUEST, "Invalid payload: name can't be empty and locale must be a language tag"));
+    }
+
+    // Insert in database
+    let new_alias = NewExerciseAlias { exercise_id, locale: req.locale, name: req.name, preferred: req.preferred };
+    match insert_exercise_alias(&new_alias, connector).await {
+        Ok(alias) => Ok(build_resp(StatusCode::CREATED, alias)),
+        Err(DBError::ItemNotFound(mes)) => Ok(build_resp(StatusCode::NOT_FOUND, mes)),
+        Err(DBError::UniqueViolation(mes)) => {
+            warn!("Tried to insert already exisiting alias");
+            Ok(build_resp(StatusCode::CONFLICT, mes))
+        },
+        Err(error) => {
+            error!("INTERNAL SERVER ERROR: {}", error);
+            Ok(build_resp(StatusCode::INTERNAL_SERVER_ERROR, ""))
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use lambda_http::http::header::{AUTHORIZATION, HeaderValue};
+    use salamandra_server::lib::utils::tests::{pg_container, insert_helper, test_jwt, test_admin_jwt, Items};
+
+    // TEST CASES
+    // * Not an admin
+    // * Invalid locale
+    // * Non existing exercise
+    // * Success and duplicate preferred alias
+
+    fn alias_request(exercise_id: Uuid, jwt: &str, payload: &str) -> Request {
+        let mut req = Request::default();
+        req.headers_mut().insert(AUTHORIZATION, HeaderValue::from_str(jwt).unwrap());
+        *req.body_mut() = Body::from(payload.to_string());
+        req.with_path_parameters(HashMap::from([("exercise_id".to_string(), exercise_id.to_string())]))
+    }
+
+    #[tokio::test]
+    async fn test_create_exercise_alias_not_admin() {
+        let (connector, _container) = pg_container().await;
+        let exercise_id = insert_helper(1, Items::Exercises, &connector, None).await[0];
+
+        let req = alias_request(exercise_id, &test_jwt(Uuid::new_v4()), r#"{"locale": "es", "name": "Press banca"}"#);
+        let response = create_exercise_alias(req, &connector).await.unwrap();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    }
+
+    #[tokio::test]
+    async fn test_create_exercise_alias_invalid_locale() {
+        let (connector, _container) = pg_container().await;
+        let exercise_id = insert_helper(1, Items::Exercises, &connector, None).await[0];
+
+        let req = alias_request(exercise_id, &test_admin_jwt(Uuid::new_v4()), r#"{"locale": "es;q=1", "name": "Press banca"}"#);
+        let response = create_exercise_alias(req, &connector).await.unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_create_exercise_alias_not_found() {
+        let (connector, _container) = pg_container().await;
+
+        let req = alias_request(Uuid::new_v4(), &test_admin_jwt(Uuid::new_v4()), r#"{"locale": "es", "name": "Press banca"}"#);
+        let response = create_exercise_alias(req, &connector).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_create_exercise_alias_success_and_duplicate() {
+        let (connector, _container) = pg_container().await;
+        let exercise_id = insert_helper(1, Items::Exercises, &connector, None).await[0];
+        let jwt = test_admin_jwt(Uuid::new_v4());
+
+        let req = alias_request(exercise_id, &jwt, r#"{"locale": "es", "name": "Press banca", "preferred": true}"#);
+        let response = create_exercise_alias(req, &connector).await.unwrap();
+        assert_eq!(response.status(), StatusCode::CREATED);
+
+        // Only one preferred name per locale
+        let req = alias_request(exercise_id, &jwt, r#"{"locale": "es", "name": "Press de banca", "preferred": true}"#);
+        let response = create_exercise_alias(req, &connector).await.unwrap();
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+
+        let req = alias_request(exercise_id, &jwt, r#"{"locale": "es", "name": "Press de banca"}"#);
+        let response = create_exercise_alias(req, &connector).await.unwrap();
+        assert_eq!(response.status(), StatusCode::CREATED);
+    }
+}
@@ -0,0 +1,118 @@
+use lambda_http::{Error, Request, Response, Body, RequestExt};
+use lambda_http::http::StatusCode;
+use tracing::{error, warn};
+use uuid::Uuid;
+
+use salamandra_server::lib::db::exercises_db::insert_exercise;
+use salamandra_server::lib::models::exercise_models::NewExercise;
+use salamandra_server::lib::utils::handlers::{build_resp, extract_sub};
+use salamandra_server::lib::db::DBConnector;
+use salamandra_server::lib::errors::DBError;
+
+/// Add private exercise for the given user
+/// * Only visible to its owner, who can use it in templates and executions
+pub async fn create_private_exercise(event: Request, connector: &DBConnector) -> Result<Response<Body>, Error> {
+
+    // Get path parameter
+    let user_id = Uuid::parse_str(event.path_parameters().first("user_id").unwrap()).unwrap();
+
+    // Check user is the same as sub in claim
+    match extract_sub(event.headers(), Some(user_id)) {
+        Ok(_) => (),
+        Err(resp) => return Ok(resp)
+    };
+
+    // Check and extract payload
+    let body = match event.into_body() {
+        Body::Text(body) => body,
+        _ => return Ok(build_resp(StatusCode::BAD_REQUEST, "Invalid payload")),
+    };
+    let mut req: NewExercise = match serde_json::from_str(&body) {
+        Ok(req) => req,
+        Err(_) => return Ok(build_resp(StatusCode::BAD_REQUEST, "Invalid payload")),
+    };
+
+    // Check input
+    let codes = [req.main_muscle_group, req.secondary_muscle_group, req.necessary_equipment, req.exercise_type];
+    if req.name.trim().is_empty() || codes.iter().any(|code| code.is_some_and(|c| c < 0)) {
+        return Ok(build_resp(StatusCode::BAD_REQUEST, "Invalid payload: name can't be empty and codes can't be negative"));
+    }
+    req.owner_id = Some(user_id);
+
+    // Insert in database
+    match insert_exercise(&req, connector).await {
+        Ok(exercise) => Ok(build_resp(StatusCode::CREATED, exercise)),
+        Err(DBError::UniqueViolation(mes)) => {
+            warn!("Tried to insert already exisiting private exercise");
+            Ok(build_resp(StatusCode::CONFLICT, mes))
+        },
+        Err(error) => {
+            error!("INTERNAL SERVER ERROR: {}", error);
+            Ok(build_resp(StatusCode::INTERNAL_SERVER_ERROR, ""))
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use serde_json::to_string;
+    use lambda_http::http::header::{AUTHORIZATION, HeaderValue};
+    use salamandra_server::lib::utils::tests::{pg_container, test_jwt, insert_helper, Items};
+    use salamandra_server::lib::models::exercise_models::Exercise;
+
+    // TEST CASES
+    // * Different user in claim
+    // * Success, duplicate for the same user and for another user
+
+    #[tokio::test]
+    async fn test_create_private_exercise_forbidden() {
+        let (connector, _container) = pg_container().await;
+        let user_id = insert_helper(1, Items::Users, &connector, None).await[0];
+        let payload = NewExercise {..Default::default()};
+
+        let mut req = Request::default();
+        let jwt = test_jwt(Uuid::new_v4());
+        req.headers_mut().insert(AUTHORIZATION, HeaderValue::from_str(&jwt).unwrap());
+        *req.body_mut() = Body::from(to_string(&payload).expect("Error"));
+        let req = req.with_path_parameters(HashMap::from([("user_id".to_string(), user_id.to_string())]));
+
+        let resp = create_private_exercise(req, &connector).await;
+        assert!(resp.is_ok());
+        let response = resp.unwrap();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    }
+
+    #[tokio::test]
+    async fn test_create_private_exercise_success() {
+        let (connector, _container) = pg_container().await;
+        let user_ids = insert_helper(2, Items::Users, &connector, None).await;
+        // Same name as a catalog exercise
+        let _ = insert_helper(1, Items::Exercises, &connector, None).await;
+        let payload = NewExercise {..Default::default()};
+
+        let build_req = |user_id: Uuid| {
+            let mut req = Request::default();
+            let jwt = test_jwt(user_id);
+            req.headers_mut().insert(AUTHORIZATION, HeaderValue::from_str(&jwt).unwrap());
+            *req.body_mut() = Body::from(to_string(&payload).expect("Error"));
+            req.with_path_parameters(HashMap::from([("user_id".to_string(), user_id.to_string())]))
+        };
+
+        let resp = create_private_exercise(build_req(user_ids[0]), &connector).await;
+        let response = resp.unwrap();
+        assert_eq!(response.status(), StatusCode::CREATED);
+        if let Body::Text(body) = response.into_body() {
+            let exercise: Exercise = serde_json::from_str(&body).unwrap();
+            assert_eq!(exercise.owner_id, Some(user_ids[0]));
+        }
+
+        // Names are unique per owner
+        let resp = create_private_exercise(build_req(user_ids[0]), &connector).await;
+        assert_eq!(resp.unwrap().status(), StatusCode::CONFLICT);
+        let resp = create_private_exercise(build_req(user_ids[1]), &connector).await;
+        assert_eq!(resp.unwrap().status(), StatusCode::CREATED);
+    }
+}
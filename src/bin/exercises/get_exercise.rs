@@ -4,22 +4,59 @@ use tracing::error;
 use uuid::Uuid;
 
 use salamandra_server::lib::db::exercises_db::lookup_exercise;
-use salamandra_server::lib::utils::handlers::build_resp;
+use salamandra_server::lib::db::exercise_aliases_db::select_localized_names;
+use salamandra_server::lib::db::exercise_content_db::select_exercise_contents;
+use salamandra_server::lib::models::exercise_content_models::ExerciseWithContent;
+use salamandra_server::lib::utils::handlers::{accept_languages, build_resp, extract_sub};
 use salamandra_server::lib::db::DBConnector;
 use salamandra_server::lib::errors::DBError;
 
 
 /// Fetch exercise information
 /// * Assumes exercise id has been previously checked
+/// * Private exercises are only visible to their owner
+/// * Name is localized with `Accept-Language` when available
+/// * Includes instructional content (description, steps, cues, mistakes and media)
 pub async fn get_exercise(event: Request, connector: &DBConnector) -> Result<Response<Body>, Error> {
 
     // Get path parameter
     let exercise_id = Uuid::parse_str(event.path_parameters().first("exercise_id").unwrap()).unwrap();
+
+    // Get user from claim
+    let user_id = match extract_sub(event.headers(), None) {
+        Ok(id) => id,
+        Err(resp) => return Ok(resp)
+    };
     
     // Fetch from database
-    match lookup_exercise(exercise_id, &connector).await {
-        Ok(exercise) => Ok(build_resp(StatusCode::OK, exercise)),
-        Err(DBError::ItemNotFound(mes)) => Ok(build_resp(StatusCode::NOT_FOUND, mes)),
+    let mut exercise = match lookup_exercise(exercise_id, user_id, connector).await {
+        Ok(exercise) => exercise,
+        Err(DBError::ItemNotFound(mes)) => return Ok(build_resp(StatusCode::NOT_FOUND, mes)),
+        Err(other_error) => {
+            error!("INTERNAL SERVER ERROR: {}", other_error);
+            return Ok(build_resp(StatusCode::INTERNAL_SERVER_ERROR, ""))
+        }
+    };
+
+    // Localize name
+    match select_localized_names(&[exercise.id], &accept_languages(event.headers()), connector).await {
+        Ok(mut names) => {
+            if let Some(localized) = names.remove(&exercise.id) {
+                exercise.name = localized;
+            }
+        }
+        Err(other_error) => {
+            error!("INTERNAL SERVER ERROR: {}", other_error);
+            return Ok(build_resp(StatusCode::INTERNAL_SERVER_ERROR, ""))
+        }
+    };
+
+    // Add instructional content
+    match select_exercise_contents(&[exercise.id], connector).await {
+        Ok(mut contents) => {
+            let content = contents.remove(&exercise.id).unwrap_or_default();
+            Ok(build_resp(StatusCode::OK, ExerciseWithContent { exercise, content }))
+        }
         Err(other_error) => {
             error!("INTERNAL SERVER ERROR: {}", other_error);
             Ok(build_resp(StatusCode::INTERNAL_SERVER_ERROR, ""))
@@ -34,18 +71,28 @@ mod tests {
     use uuid::Uuid;
     use std::collections::HashMap;
     use lambda_http::http::StatusCode;
-    use salamandra_server::lib::utils::tests::{pg_container, insert_helper, Items};
-    use salamandra_server::lib::models::exercise_models::Exercise;
+    use lambda_http::http::header::{ACCEPT_LANGUAGE, AUTHORIZATION, HeaderValue};
+    use salamandra_server::lib::utils::tests::{pg_container, insert_helper, test_jwt, Items};
+    use salamandra_server::lib::models::exercise_models::{Exercise, NewExercise};
+    use salamandra_server::lib::models::exercise_alias_models::NewExerciseAlias;
+    use salamandra_server::lib::db::exercises_db::insert_exercise;
+    use salamandra_server::lib::db::exercise_aliases_db::insert_exercise_alias;
+    use salamandra_server::lib::db::exercise_content_db::replace_exercise_content;
+    use salamandra_server::lib::models::exercise_content_models::{ExerciseContent, ExerciseMedia};
 
     // TEST CASES
     // * Non existing exercise
     // * Existing exercise
+    // * Private exercise of owner and other user
+    // * Localized name with fallback to canonical name
+    // * Instructional content
 
     #[tokio::test]
     async fn test_get_exercise_not_found() {
         let (connector, _container) = pg_container().await;
         let exercise_id = Uuid::new_v4().to_string();
-        let req = Request::default();
+        let mut req = Request::default();
+        req.headers_mut().insert(AUTHORIZATION, HeaderValue::from_str(&test_jwt(Uuid::new_v4())).unwrap());
         let req = req.clone().with_path_parameters(HashMap::from([("exercise_id".to_string(), exercise_id)]));
         
         let resp = get_exercise(req, &connector).await;
@@ -60,7 +107,8 @@ mod tests {
         let exercise_uuid = insert_helper(1, Items::Exercises, &connector, None).await[0];
         let exercise_id = exercise_uuid.to_string();
 
-        let req = Request::default();
+        let mut req = Request::default();
+        req.headers_mut().insert(AUTHORIZATION, HeaderValue::from_str(&test_jwt(Uuid::new_v4())).unwrap());
         let req = req.clone().with_path_parameters(HashMap::from([("exercise_id".to_string(), exercise_id)]));
        
         let resp = get_exercise(req, &connector).await;
@@ -74,4 +122,79 @@ mod tests {
             assert_eq!(exercise_uuid, fetched_exercise.unwrap().id);
         }
     }
+
+    #[tokio::test]
+    async fn test_get_exercise_private() {
+        let (connector, _container) = pg_container().await;
+        let owner_id = insert_helper(1, Items::Users, &connector, None).await[0];
+        let new_exercise = NewExercise {owner_id: Some(owner_id), ..Default::default()};
+        let exercise_id = insert_exercise(&new_exercise, &connector).await.unwrap().id.to_string();
+
+        for (user_id, expected) in [(owner_id, StatusCode::OK), (Uuid::new_v4(), StatusCode::NOT_FOUND)] {
+            let mut req = Request::default();
+            req.headers_mut().insert(AUTHORIZATION, HeaderValue::from_str(&test_jwt(user_id)).unwrap());
+            let req = req.with_path_parameters(HashMap::from([("exercise_id".to_string(), exercise_id.clone())]));
+
+            let resp = get_exercise(req, &connector).await;
+            assert!(resp.is_ok());
+            assert_eq!(resp.unwrap().status(), expected);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_get_exercise_localized() {
+        let (connector, _container) = pg_container().await;
+        let new_exercise = NewExercise {name: "Bench Press".to_string(), ..Default::default()};
+        let exercise_id = insert_exercise(&new_exercise, &connector).await.unwrap().id;
+        let new_alias = NewExerciseAlias {exercise_id, locale: "es".to_string(), name: "Press banca".to_string(), preferred: true};
+        let _ = insert_exercise_alias(&new_alias, &connector).await;
+
+        for (language, expected) in [("es-ES,es;q=0.9,en;q=0.8", "Press banca"), ("fr-FR,en;q=0.5", "Bench Press"), ("", "Bench Press")] {
+            let mut req = Request::default();
+            req.headers_mut().insert(AUTHORIZATION, HeaderValue::from_str(&test_jwt(Uuid::new_v4())).unwrap());
+            req.headers_mut().insert(ACCEPT_LANGUAGE, HeaderValue::from_str(language).unwrap());
+            let req = req.with_path_parameters(HashMap::from([("exercise_id".to_string(), exercise_id.to_string())]));
+
+            let response = get_exercise(req, &connector).await.unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+            if let Body::Text(body) = response.into_body() {
+                let fetched_exercise: Exercise = serde_json::from_str(&body).unwrap();
+                assert_eq!(fetched_exercise.name, expected);
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_get_exercise_content() {
+        let (connector, _container) = pg_container().await;
+        let exercise_id = insert_helper(1, Items::Exercises, &connector, None).await[0];
+
+        let fetch = || {
+            let mut req = Request::default();
+            req.headers_mut().insert(AUTHORIZATION, HeaderValue::from_str(&test_jwt(Uuid::new_v4())).unwrap());
+            get_exercise(req.with_path_parameters(HashMap::from([("exercise_id".to_string(), exercise_id.to_string())])), &connector)
+        };
+        let content = |body: Body| -> ExerciseContent {
+            if let Body::Text(body) = body {
+                let fetched: ExerciseWithContent = serde_json::from_str(&body).unwrap();
+                return fetched.content;
+            }
+            panic!("Expected text body")
+        };
+
+        // No content yet
+        let response = fetch().await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(content(response.into_body()), ExerciseContent::default());
+
+        let new_content = ExerciseContent {
+            description: Some("Hang from the bar".to_string()),
+            steps: vec!["Grip".to_string(), "Pull".to_string()],
+            media: vec![ExerciseMedia {media_type: 0, url: "https://cdn.example.com/a.jpg".to_string(), thumbnail_url: None, width: Some(640), height: Some(480)}],
+            ..Default::default()
+        };
+        let _ = replace_exercise_content(exercise_id, &new_content, &connector).await;
+        let response = fetch().await.unwrap();
+        assert_eq!(content(response.into_body()), new_content);
+    }
 }
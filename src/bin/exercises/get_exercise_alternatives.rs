@@ -0,0 +1,157 @@
+use lambda_http::{Error, Request, Response, Body, RequestExt};
+use lambda_http::http::StatusCode;
+use serde::{Deserialize, Serialize};
+use tracing::error;
+use uuid::Uuid;
+
+use salamandra_server::lib::db::exercises_db::{lookup_exercise, select_exercise_alternatives};
+use salamandra_server::lib::db::exercise_aliases_db::select_localized_names;
+use salamandra_server::lib::models::exercise_models::Exercise;
+use salamandra_server::lib::utils::handlers::{accept_languages, build_resp, extract_sub, pagination};
+use salamandra_server::lib::db::DBConnector;
+use salamandra_server::lib::errors::DBError;
+
+#[derive(Debug, Serialize, Deserialize)]
+struct ExerciseAlternatives {
+    exercises: Vec<Exercise>,
+}
+
+/// Return exercises that can replace the given one, most similar first
+/// * Optional `equipment` query parameter with comma separated equipment codes
+/// * Optional `limit` and `offset`
+/// * Includes the private exercises of the user making the call
+/// * Names are localized with `Accept-Language` when available
+pub async fn get_exercise_alternatives(event: Request, connector: &DBConnector) -> Result<Response<Body>, Error> {
+
+    // Get path parameter
+    let exercise_id = Uuid::parse_str(event.path_parameters().first("exercise_id").unwrap()).unwrap();
+
+    // Get user from claim
+    let user_id = match extract_sub(event.headers(), None) {
+        Ok(id) => id,
+        Err(resp) => return Ok(resp)
+    };
+
+    // Check query parameters
+    let equipment: Option<Vec<i16>> = match event.query_string_parameters().first("equipment") {
+        Some(value) => match value.split(',').map(|code| code.trim().parse::<i16>()).collect() {
+            Ok(codes) => Some(codes),
+            Err(_) => return Ok(build_resp(StatusCode::BAD_REQUEST, "Incorrect query parameters"))
+        },
+        None => None
+    };
+
+    let (limit, offset) = match pagination(&event) {
+        Ok(page) => page,
+        Err(resp) => return Ok(resp)
+    };
+
+    // Fetch from database
+    let exercise = match lookup_exercise(exercise_id, user_id, connector).await {
+        Ok(exercise) => exercise,
+        Err(DBError::ItemNotFound(mes)) => return Ok(build_resp(StatusCode::NOT_FOUND, mes)),
+        Err(other_error) => {
+            error!("INTERNAL SERVER ERROR: {}", other_error);
+            return Ok(build_resp(StatusCode::INTERNAL_SERVER_ERROR, ""))
+        }
+    };
+    let mut alternatives = match select_exercise_alternatives(&exercise, user_id, equipment.as_deref(), limit, offset, connector).await {
+        Ok(vec) => vec,
+        Err(other_error) => {
+            error!("INTERNAL SERVER ERROR: {}", other_error);
+            return Ok(build_resp(StatusCode::INTERNAL_SERVER_ERROR, ""))
+        }
+    };
+
+    // Localize names
+    let exercise_ids: Vec<Uuid> = alternatives.iter().map(|alternative| alternative.id).collect();
+    let mut localized_names = match select_localized_names(&exercise_ids, &accept_languages(event.headers()), connector).await {
+        Ok(names) => names,
+        Err(other_error) => {
+            error!("INTERNAL SERVER ERROR: {}", other_error);
+            return Ok(build_resp(StatusCode::INTERNAL_SERVER_ERROR, ""))
+        }
+    };
+    for alternative in alternatives.iter_mut() {
+        if let Some(localized) = localized_names.remove(&alternative.id) {
+            alternative.name = localized;
+        }
+    }
+
+    Ok(build_resp(StatusCode::OK, ExerciseAlternatives { exercises: alternatives }))
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use lambda_http::http::header::{AUTHORIZATION, HeaderValue};
+    use salamandra_server::lib::utils::tests::{pg_container, insert_helper, test_jwt, Items};
+    use salamandra_server::lib::models::exercise_models::NewExercise;
+    use salamandra_server::lib::db::exercises_db::insert_exercise;
+
+    // TEST CASES
+    // * Non existing exercise
+    // * Invalid equipment filter and pagination
+    // * Success with and without equipment filter, paginated
+
+    fn alternatives_request(exercise_id: Uuid, query_params: HashMap<String, String>) -> Request {
+        let mut req = Request::default();
+        req.headers_mut().insert(AUTHORIZATION, HeaderValue::from_str(&test_jwt(Uuid::new_v4())).unwrap());
+        req.with_query_string_parameters(query_params)
+            .with_path_parameters(HashMap::from([("exercise_id".to_string(), exercise_id.to_string())]))
+    }
+
+    #[tokio::test]
+    async fn test_get_exercise_alternatives_not_found() {
+        let (connector, _container) = pg_container().await;
+
+        let response = get_exercise_alternatives(alternatives_request(Uuid::new_v4(), HashMap::new()), &connector).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_get_exercise_alternatives_invalid_equipment() {
+        let (connector, _container) = pg_container().await;
+        let exercise_id = insert_helper(1, Items::Exercises, &connector, None).await[0];
+
+        let query_params = HashMap::from([("equipment".to_string(), "1,barbell".to_string())]);
+        let response = get_exercise_alternatives(alternatives_request(exercise_id, query_params), &connector).await.unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        let query_params = HashMap::from([("limit".to_string(), "0".to_string())]);
+        let response = get_exercise_alternatives(alternatives_request(exercise_id, query_params), &connector).await.unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_get_exercise_alternatives_success() {
+        let (connector, _container) = pg_container().await;
+        let mut ids = Vec::new();
+        for (exercise_name, equipment) in [("Barbell Row", 1), ("Cable Row", 2), ("Dumbbell Row", 3)] {
+            let new_exercise = NewExercise {name: exercise_name.to_string(), main_muscle_group: Some(3), necessary_equipment: Some(equipment), ..Default::default()};
+            ids.push(insert_exercise(&new_exercise, &connector).await.unwrap().id);
+        }
+
+        let names = |body: Body| -> Vec<String> {
+            if let Body::Text(body) = body {
+                let res: ExerciseAlternatives = serde_json::from_str(&body).unwrap();
+                return res.exercises.into_iter().map(|ex| ex.name).collect();
+            }
+            Vec::new()
+        };
+
+        let response = get_exercise_alternatives(alternatives_request(ids[0], HashMap::new()), &connector).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(names(response.into_body()), vec!["Cable Row", "Dumbbell Row"]);
+
+        let query_params = HashMap::from([("equipment".to_string(), "1, 3".to_string())]);
+        let response = get_exercise_alternatives(alternatives_request(ids[0], query_params), &connector).await.unwrap();
+        assert_eq!(names(response.into_body()), vec!["Dumbbell Row"]);
+
+        let query_params = HashMap::from([("limit".to_string(), "1".to_string()), ("offset".to_string(), "1".to_string())]);
+        let response = get_exercise_alternatives(alternatives_request(ids[0], query_params), &connector).await.unwrap();
+        assert_eq!(names(response.into_body()), vec!["Dumbbell Row"]);
+    }
+}
@@ -1,9 +1,23 @@
 mod get_exercise;
 mod search_exercises;
+mod create_exercise;
+mod patch_exercise;
+mod retire_exercise;
+mod create_private_exercise;
+mod create_exercise_alias;
+mod put_exercise_content;
+mod get_exercise_alternatives;
 
 use get_exercise::get_exercise;
 use salamandra_server::lib::utils::handlers::{not_found, UUID_PATTERN};
 use search_exercises::search_exercises_;
+use create_exercise::create_exercise;
+use patch_exercise::patch_exercise;
+use retire_exercise::retire_exercise_;
+use create_private_exercise::create_private_exercise;
+use create_exercise_alias::create_exercise_alias;
+use put_exercise_content::put_exercise_content;
+use get_exercise_alternatives::get_exercise_alternatives;
 use salamandra_server::lib::db::DBConnector;
 
 use lambda_http::{run, service_fn, Error, Request, Response, Body, tracing};
@@ -20,9 +34,21 @@ async fn main() -> Result<(), Error> {
 async fn router(event: Request) -> Result<Response<Body>, Error> {
     let path = event.uri().path();
     let connector = DBConnector::default();
+    let specific_exercise = Regex::new(&format!(r"^/exercises/{}$", UUID_PATTERN));
+    let user_exercises = Regex::new(&format!(r"^/users/{}/exercises$", UUID_PATTERN));
+    let exercise_aliases = Regex::new(&format!(r"^/exercises/{}/aliases$", UUID_PATTERN));
+    let exercise_content = Regex::new(&format!(r"^/exercises/{}/content$", UUID_PATTERN));
+    let exercise_alternatives = Regex::new(&format!(r"^/exercises/{}/alternatives$", UUID_PATTERN));
     let response = match (event.method(), path) {
-        (&Method::GET, _) if Regex::new(&format!(r"^/exercises/{}$", UUID_PATTERN)).unwrap().is_match(path) => get_exercise(event, &connector).await,
+        (&Method::GET, _) if specific_exercise.clone().unwrap().is_match(path) => get_exercise(event, &connector).await,
+        (&Method::PATCH, _) if specific_exercise.clone().unwrap().is_match(path) => patch_exercise(event, &connector).await,
+        (&Method::DELETE, _) if specific_exercise.unwrap().is_match(path) => retire_exercise_(event, &connector).await,
         (&Method::GET, "/exercises") => search_exercises_(event, &connector).await,
+        (&Method::POST, "/exercises") => create_exercise(event, &connector).await,
+        (&Method::POST, _) if user_exercises.unwrap().is_match(path) => create_private_exercise(event, &connector).await,
+        (&Method::POST, _) if exercise_aliases.unwrap().is_match(path) => create_exercise_alias(event, &connector).await,
+        (&Method::PUT, _) if exercise_content.unwrap().is_match(path) => put_exercise_content(event, &connector).await,
+        (&Method::GET, _) if exercise_alternatives.unwrap().is_match(path) => get_exercise_alternatives(event, &connector).await,
         _ => not_found()
     };
 
@@ -0,0 +1,119 @@
+use lambda_http::{Error, Request, Response, Body, RequestExt};
+use lambda_http::http::StatusCode;
+use tracing::{error, info, warn};
+use uuid::Uuid;
+
+use salamandra_server::lib::db::exercises_db::update_exercise;
+use salamandra_server::lib::models::exercise_models::UncompleteExercise;
+use salamandra_server::lib::utils::handlers::{build_resp, extract_admin};
+use salamandra_server::lib::db::DBConnector;
+use salamandra_server::lib::errors::DBError;
+
+/// Update exercise
+/// * Update certain values of an existing catalog exercise
+/// * Only admins can modify the catalog
+pub async fn patch_exercise(event: Request, connector: &DBConnector) -> Result<Response<Body>, Error> {
+
+    // Check caller is an admin
+    match extract_admin(event.headers()) {
+        Ok(_) => (),
+        Err(resp) => return Ok(resp)
+    };
+
+    if let Body::Text(body) = event.clone().into_body() {
+        if let Ok(req) = serde_json::from_str::<UncompleteExercise>(&body) {
+            let exercise_id = Uuid::parse_str(event.path_parameters().first("exercise_id").unwrap()).unwrap();
+
+            let codes = [req.main_muscle_group, req.secondary_muscle_group, req.necessary_equipment, req.exercise_type];
+            if req.name.as_ref().is_some_and(|name| name.trim().is_empty()) || codes.iter().any(|code| code.is_some_and(|c| c < 0)) {
+                return Ok(build_resp(StatusCode::BAD_REQUEST, "Invalid payload: name can't be empty and codes can't be negative"));
+            }
+
+            let res = match update_exercise(exercise_id, &req, connector).await {
+                Ok(updated_exercise) => build_resp(StatusCode::OK, updated_exercise),
+                Err(DBError::ItemNotFound(mes)) => {
+                    warn!("Tried to update non-existing exercise");
+                    build_resp(StatusCode::NOT_FOUND, mes)
+                }
+                Err(DBError::UniqueViolation(mes)) => build_resp(StatusCode::CONFLICT, mes),
+                Err(DBError::QueryError(mes)) => {
+                    info!("Didn't modify exercise: {}", mes);
+                    build_resp(StatusCode::NOT_MODIFIED, "")
+                }
+                Err(error) => {
+                    error!("INTERNAL SERVER ERROR: {}", error);
+                    build_resp(StatusCode::INTERNAL_SERVER_ERROR, "")
+                }
+            };
+            return Ok(res)
+        }
+    }
+    Ok(build_resp(StatusCode::BAD_REQUEST, "Invalid payload"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use lambda_http::http::{header::AUTHORIZATION, HeaderValue};
+    use serde_json::to_string;
+    use salamandra_server::lib::utils::tests::{insert_helper, pg_container, test_jwt, test_admin_jwt, Items};
+
+    // TEST CASES
+    // * Not an admin
+    // * Non existing exercise
+    // * Success
+
+    #[tokio::test]
+    async fn test_patch_exercise_not_admin() {
+        let (connector, _container) = pg_container().await;
+        let exercise_id = insert_helper(1, Items::Exercises, &connector, None).await[0];
+
+        let payload = UncompleteExercise {name: Some("New".to_string()), ..Default::default()};
+        let mut req = Request::default();
+        let jwt = test_jwt(Uuid::new_v4());
+        req.headers_mut().insert(AUTHORIZATION, HeaderValue::from_str(&jwt).unwrap());
+        *req.body_mut() = Body::from(to_string(&payload).expect("Error"));
+        let req = req.with_path_parameters(HashMap::from([("exercise_id".to_string(), exercise_id.to_string())]));
+
+        let resp = patch_exercise(req, &connector).await;
+        assert!(resp.is_ok());
+        let response = resp.unwrap();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    }
+
+    #[tokio::test]
+    async fn test_patch_exercise_not_found() {
+        let (connector, _container) = pg_container().await;
+
+        let payload = UncompleteExercise {name: Some("New".to_string()), ..Default::default()};
+        let mut req = Request::default();
+        let jwt = test_admin_jwt(Uuid::new_v4());
+        req.headers_mut().insert(AUTHORIZATION, HeaderValue::from_str(&jwt).unwrap());
+        *req.body_mut() = Body::from(to_string(&payload).expect("Error"));
+        let req = req.with_path_parameters(HashMap::from([("exercise_id".to_string(), Uuid::new_v4().to_string())]));
+
+        let resp = patch_exercise(req, &connector).await;
+        assert!(resp.is_ok());
+        let response = resp.unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_patch_exercise_success() {
+        let (connector, _container) = pg_container().await;
+        let exercise_id = insert_helper(1, Items::Exercises, &connector, None).await[0];
+
+        let payload = UncompleteExercise {name: Some("New".to_string()), exercise_type: Some(2), ..Default::default()};
+        let mut req = Request::default();
+        let jwt = test_admin_jwt(Uuid::new_v4());
+        req.headers_mut().insert(AUTHORIZATION, HeaderValue::from_str(&jwt).unwrap());
+        *req.body_mut() = Body::from(to_string(&payload).expect("Error"));
+        let req = req.with_path_parameters(HashMap::from([("exercise_id".to_string(), exercise_id.to_string())]));
+
+        let resp = patch_exercise(req, &connector).await;
+        assert!(resp.is_ok());
+        let response = resp.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+}
@@ -0,0 +1,137 @@
+use lambda_http::{Error, Request, Response, Body, RequestExt};
+use lambda_http::http::StatusCode;
+use tracing::error;
+use uuid::Uuid;
+
+use salamandra_server::lib::db::exercise_content_db::replace_exercise_content;
+use salamandra_server::lib::models::exercise_content_models::ExerciseContent;
+use salamandra_server::lib::utils::handlers::{build_resp, extract_admin};
+use salamandra_server::lib::db::DBConnector;
+use salamandra_server::lib::errors::DBError;
+
+/// Media types accepted for exercise content
+const MEDIA_TYPES: [i16; 2] = [0, 1];
+
+/// Replace the instructional content of an exercise
+/// * Media is stored in the order it is given
+/// * Only admins can modify the catalog
+pub async fn put_exercise_content(event: Request, connector: &DBConnector) -> Result<Response<Body>, Error> {
+
+    // Get path parameter
+    let exercise_id = Uuid::parse_str(event.path_parameters().first("exercise_id").unwrap()).unwrap();
+
+    // Check caller is an admin
+    match extract_admin(event.headers()) {
+        Ok(_) => (),
+        Err(resp) => return Ok(resp)
+    };
+
+    // Check and extract payload
+    let body = match event.into_body() {
+        Body::Text(body) => body,
+        _ => return Ok(build_resp(StatusCode::BAD_REQUEST, "Invalid payload")),
+    };
+    let req: ExerciseContent = match serde_json::from_str(&body) {
+        Ok(req) => req,
+        Err(_) => return Ok(build_resp(StatusCode::BAD_REQUEST, "Invalid payload")),
+    };
+
+    // Check input
+    let is_url = |url: &str| url.starts_with("https://") || url.starts_with("http://");
+    let valid_media = req.media.iter().all(|item| {
+        MEDIA_TYPES.contains(&item.media_type)
+            && is_url(&item.url)
+            && item.thumbnail_url.as_deref().is_none_or(is_url)
+            && [item.width, item.height].iter().all(|dim| dim.is_none_or(|d| d > 0))
+    });
+    let texts = req.steps.iter().chain(&req.cues).chain(&req.common_mistakes);
+    if !valid_media || req.media.len() > i16::MAX as usize || texts.into_iter().any(|text| text.trim().is_empty()) {
+        return Ok(build_resp(StatusCode::BAD_REQUEST, "Invalid payload: texts can't be empty and media needs a valid type, URLs and positive dimensions"));
+    }
+
+    // Replace in database
+    match replace_exercise_content(exercise_id, &req, connector).await {
+        Ok(()) => Ok(build_resp(StatusCode::OK, req)),
+        Err(DBError::ItemNotFound(mes)) => Ok(build_resp(StatusCode::NOT_FOUND, mes)),
+        Err(error) => {
+            error!("INTERNAL SERVER ERROR: {}", error);
+            Ok(build_resp(StatusCode::INTERNAL_SERVER_ERROR, ""))
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use lambda_http::http::header::{AUTHORIZATION, HeaderValue};
+    use salamandra_server::lib::utils::tests::{pg_container, insert_helper, test_jwt, test_admin_jwt, Items};
+
+    // TEST CASES
+    // * Not an admin
+    // * Invalid media
+    // * Non existing exercise
+    // * Success
+
+    fn content_request(exercise_id: Uuid, jwt: &str, payload: &str) -> Request {
+        let mut req = Request::default();
+        req.headers_mut().insert(AUTHORIZATION, HeaderValue::from_str(jwt).unwrap());
+        *req.body_mut() = Body::from(payload.to_string());
+        req.with_path_parameters(HashMap::from([("exercise_id".to_string(), exercise_id.to_string())]))
+    }
+
+    const PAYLOAD: &str = r#"{"description": "Hinge at the hips", "steps": ["Set up", "Pull"], "cues": ["Neutral spine"],
+        "media": [{"media_type": 1, "url": "https://cdn.example.com/a.mp4", "thumbnail_url": "https://cdn.example.com/a.jpg", "width": 1280, "height": 720}]}"#;
+
+    #[tokio::test]
+    async fn test_put_exercise_content_not_admin() {
+        let (connector, _container) = pg_container().await;
+        let exercise_id = insert_helper(1, Items::Exercises, &connector, None).await[0];
+
+        let req = content_request(exercise_id, &test_jwt(Uuid::new_v4()), PAYLOAD);
+        let response = put_exercise_content(req, &connector).await.unwrap();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    }
+
+    #[tokio::test]
+    async fn test_put_exercise_content_invalid_media() {
+        let (connector, _container) = pg_container().await;
+        let exercise_id = insert_helper(1, Items::Exercises, &connector, None).await[0];
+
+        for payload in [
+            r#"{"media": [{"media_type": 7, "url": "https://cdn.example.com/a.mp4"}]}"#,
+            r#"{"media": [{"media_type": 1, "url": "ftp://cdn.example.com/a.mp4"}]}"#,
+            r#"{"media": [{"media_type": 1, "url": "https://cdn.example.com/a.mp4", "width": 0}]}"#,
+            r#"{"steps": ["  "]}"#,
+        ] {
+            let req = content_request(exercise_id, &test_admin_jwt(Uuid::new_v4()), payload);
+            let response = put_exercise_content(req, &connector).await.unwrap();
+            assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_put_exercise_content_not_found() {
+        let (connector, _container) = pg_container().await;
+
+        let req = content_request(Uuid::new_v4(), &test_admin_jwt(Uuid::new_v4()), PAYLOAD);
+        let response = put_exercise_content(req, &connector).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_put_exercise_content_success() {
+        let (connector, _container) = pg_container().await;
+        let exercise_id = insert_helper(1, Items::Exercises, &connector, None).await[0];
+
+        let req = content_request(exercise_id, &test_admin_jwt(Uuid::new_v4()), PAYLOAD);
+        let response = put_exercise_content(req, &connector).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        if let Body::Text(body) = response.into_body() {
+            let content: ExerciseContent = serde_json::from_str(&body).unwrap();
+            assert_eq!(content.steps.len(), 2);
+            assert_eq!(content.media[0].width, Some(1280));
+        }
+    }
+}
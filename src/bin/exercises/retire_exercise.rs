@@ -0,0 +1,107 @@
+use lambda_http::{Error, Request, Response, Body, RequestExt};
+use lambda_http::http::StatusCode;
+use tracing::error;
+use uuid::Uuid;
+
+use salamandra_server::lib::db::exercises_db::retire_exercise;
+use salamandra_server::lib::utils::handlers::{build_resp, extract_admin};
+use salamandra_server::lib::db::DBConnector;
+
+
+/// Remove exercise from the catalog
+/// * Exercises used in templates or executions are retired instead of deleted
+/// * Only admins can modify the catalog
+pub async fn retire_exercise_(event: Request, connector: &DBConnector) -> Result<Response<Body>, Error> {
+
+    // Get path parameter
+    let exercise_id = Uuid::parse_str(event.path_parameters().first("exercise_id").unwrap()).unwrap();
+
+    // Check caller is an admin
+    match extract_admin(event.headers()) {
+        Ok(_) => (),
+        Err(resp) => return Ok(resp)
+    };
+
+    // Delete or retire in database
+    match retire_exercise(exercise_id, connector).await {
+        Ok(affected) => {
+            if affected > 0 {
+                return Ok(build_resp(StatusCode::NO_CONTENT, ""));
+            }
+            Ok(build_resp(StatusCode::NOT_FOUND, ""))
+        }
+        Err(mes) => {
+            error!("INTERNAL SERVER ERROR: {}", mes);
+            Ok(build_resp(StatusCode::INTERNAL_SERVER_ERROR, ""))
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use lambda_http::http::header::{AUTHORIZATION, HeaderValue};
+    use salamandra_server::lib::utils::tests::{pg_container, test_jwt, test_admin_jwt, insert_helper, Items};
+    use salamandra_server::lib::db::exercises_db::lookup_exercise;
+    use salamandra_server::lib::db::wk_execution_elements_db::lookup_wk_execution_element;
+
+    // TEST CASES
+    // * Not an admin
+    // * Non existing exercise
+    // * Referenced exercise is retired
+
+    #[tokio::test]
+    async fn test_retire_exercise_not_admin() {
+        let (connector, _container) = pg_container().await;
+        let exercise_id = insert_helper(1, Items::Exercises, &connector, None).await[0];
+
+        let mut req = Request::default();
+        let jwt = test_jwt(Uuid::new_v4());
+        req.headers_mut().insert(AUTHORIZATION, HeaderValue::from_str(&jwt).unwrap());
+        let req = req.with_path_parameters(HashMap::from([("exercise_id".to_string(), exercise_id.to_string())]));
+
+        let resp = retire_exercise_(req, &connector).await;
+        assert!(resp.is_ok());
+        let response = resp.unwrap();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+        assert!(lookup_exercise(exercise_id, Uuid::new_v4(), &connector).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_retire_exercise_not_found() {
+        let (connector, _container) = pg_container().await;
+
+        let mut req = Request::default();
+        let jwt = test_admin_jwt(Uuid::new_v4());
+        req.headers_mut().insert(AUTHORIZATION, HeaderValue::from_str(&jwt).unwrap());
+        let req = req.with_path_parameters(HashMap::from([("exercise_id".to_string(), Uuid::new_v4().to_string())]));
+
+        let resp = retire_exercise_(req, &connector).await;
+        assert!(resp.is_ok());
+        let response = resp.unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_retire_exercise_referenced() {
+        let (connector, _container) = pg_container().await;
+        let element_id = insert_helper(1, Items::WkExecutionElements, &connector, None).await[0];
+        let exercise_id = lookup_wk_execution_element(element_id, &connector).await.unwrap().exercise_id;
+
+        let mut req = Request::default();
+        let jwt = test_admin_jwt(Uuid::new_v4());
+        req.headers_mut().insert(AUTHORIZATION, HeaderValue::from_str(&jwt).unwrap());
+        let req = req.with_path_parameters(HashMap::from([("exercise_id".to_string(), exercise_id.to_string())]));
+
+        let resp = retire_exercise_(req, &connector).await;
+        assert!(resp.is_ok());
+        let response = resp.unwrap();
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+
+        // History is kept
+        assert!(lookup_exercise(exercise_id, Uuid::new_v4(), &connector).await.unwrap().retired);
+        assert!(lookup_wk_execution_element(element_id, &connector).await.is_ok());
+    }
+}
@@ -1,11 +1,13 @@
 use lambda_http::{Error, Request, Response, Body, RequestExt};
 use lambda_http::http::StatusCode;
 use serde::{Deserialize, Serialize};
+use uuid::Uuid;
 
 use salamandra_server::lib::db::exercises_db::search_exercises;
-use salamandra_server::lib::utils::handlers::build_resp;
+use salamandra_server::lib::db::exercise_aliases_db::select_localized_names;
+use salamandra_server::lib::utils::handlers::{accept_languages, build_resp, extract_sub};
 use salamandra_server::lib::db::DBConnector;
-use salamandra_server::lib::models::exercise_models::Exercise;
+use salamandra_server::lib::models::exercise_models::{Exercise, ExerciseFilters};
 use tracing::error;
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -13,17 +15,41 @@ struct ExerciseSearchResult {
     exercises: Vec<Exercise>,
 }
 
-/// Return all exercises with name that matches the given prefix
+/// Return all exercises with name or alias that matches the given term, most relevant first
+/// * Includes the private exercises of the user making the call
+/// * Can be narrowed down by muscle group, equipment and type
+/// * Names are localized with `Accept-Language` when available
 pub async fn search_exercises_(event: Request, connector: &DBConnector) -> Result<Response<Body>, Error> {
 
-    // Check query paramater
-    let name = match event.query_string_parameters().first("name") {
+    // Get user from claim
+    let user_id = match extract_sub(event.headers(), None) {
+        Ok(id) => id,
+        Err(resp) => return Ok(resp)
+    };
+
+    // Check query paramaters
+    let params = event.query_string_parameters();
+    let mut filters = ExerciseFilters::default();
+    for (key, filter) in [
+        ("main_muscle_group", &mut filters.main_muscle_group),
+        ("necessary_equipment", &mut filters.necessary_equipment),
+        ("exercise_type", &mut filters.exercise_type),
+    ] {
+        if let Some(value) = params.first(key) {
+            match value.parse::<i16>() {
+                Ok(code) => *filter = Some(code),
+                Err(_) => return Ok(build_resp(StatusCode::BAD_REQUEST, "Incorrect query parameters"))
+            }
+        }
+    }
+    let name = match params.first("name") {
         Some(name) => name.to_string(),
+        None if filters != ExerciseFilters::default() => String::new(),
         None => return Ok(build_resp(StatusCode::BAD_REQUEST, "Incorrect query parameters"))
     };
 
     // Search in database
-    let search_result = match search_exercises(&name, connector).await {
+    let mut search_result = match search_exercises(&name, &filters, user_id, connector).await {
         Ok(vec) => vec,
         Err(error) => {
             error!("INTERNAL SERVER ERROR: {}", error);
@@ -31,6 +57,21 @@ pub async fn search_exercises_(event: Request, connector: &DBConnector) -> Resul
         }
     };
 
+    // Localize names
+    let exercise_ids: Vec<Uuid> = search_result.iter().map(|exercise| exercise.id).collect();
+    let mut localized_names = match select_localized_names(&exercise_ids, &accept_languages(event.headers()), connector).await {
+        Ok(names) => names,
+        Err(error) => {
+            error!("INTERNAL SERVER ERROR: {}", error);
+            return Ok(build_resp(StatusCode::INTERNAL_SERVER_ERROR, ""))
+        }
+    };
+    for exercise in search_result.iter_mut() {
+        if let Some(localized) = localized_names.remove(&exercise.id) {
+            exercise.name = localized;
+        }
+    }
+
     // Format and return results
     let result = ExerciseSearchResult { exercises: search_result};
     Ok(build_resp(StatusCode::OK, result))
@@ -40,21 +81,29 @@ pub async fn search_exercises_(event: Request, connector: &DBConnector) -> Resul
 #[cfg(test)]
 mod tests {
     use super::*;
-    use uuid::Uuid;
     use std::collections::HashMap;
-    use salamandra_server::lib::utils::tests::{pg_container, insert_helper, Items};
+    use lambda_http::http::header::{ACCEPT_LANGUAGE, AUTHORIZATION, HeaderValue};
+    use salamandra_server::lib::utils::tests::{pg_container, insert_helper, test_jwt, Items};
+    use salamandra_server::lib::models::exercise_models::NewExercise;
+    use salamandra_server::lib::models::exercise_alias_models::NewExerciseAlias;
+    use salamandra_server::lib::db::exercises_db::insert_exercise;
+    use salamandra_server::lib::db::exercise_aliases_db::insert_exercise_alias;
 
     // TEST CASES
     // * Invalid query
     //      * No query parameters
     //      * Other parameters
     // * Search multiple exercises
+    // * Other users' private exercises are hidden
+    // * Case-insensitive, ranked and filtered search
+    // * Search by alias with localized names
 
     #[tokio::test]
     async fn test_search_exercises_invalid_query() {
         let (connector, _container) = pg_container().await;
         { // ------ No query parameters
-            let req_ = Request::default();
+            let mut req_ = Request::default();
+            req_.headers_mut().insert(AUTHORIZATION, HeaderValue::from_str(&test_jwt(Uuid::new_v4())).unwrap());
             
             let resp = search_exercises_(req_, &connector).await;
             assert!(resp.is_ok());
@@ -62,7 +111,8 @@ mod tests {
             assert_eq!(response.status(), StatusCode::BAD_REQUEST);
         }
         { // ------ No username parameter
-            let req_ = Request::default();
+            let mut req_ = Request::default();
+            req_.headers_mut().insert(AUTHORIZATION, HeaderValue::from_str(&test_jwt(Uuid::new_v4())).unwrap());
 
             let mut query_params = HashMap::new();
             query_params.insert("not_username".to_string(), "Test".to_string());
@@ -78,7 +128,8 @@ mod tests {
     #[tokio::test]
     async fn test_search_exercises_ok() {
         let (connector, _container) = pg_container().await;
-        let req_ = Request::default();
+        let mut req_ = Request::default();
+        req_.headers_mut().insert(AUTHORIZATION, HeaderValue::from_str(&test_jwt(Uuid::new_v4())).unwrap());
 
         let mut query_params = HashMap::new();
         query_params.insert("name".to_string(), "Test".to_string());
@@ -96,4 +147,95 @@ mod tests {
             assert_eq!(exercise_ids, id_vec);
         }
     }
+
+    #[tokio::test]
+    async fn test_search_exercises_private() {
+        let (connector, _container) = pg_container().await;
+        let owner_id = insert_helper(1, Items::Users, &connector, None).await[0];
+        let new_exercise = NewExercise {name: "Test private".to_string(), owner_id: Some(owner_id), ..Default::default()};
+        let _ = insert_exercise(&new_exercise, &connector).await;
+
+        for (user_id, expected) in [(owner_id, 1), (Uuid::new_v4(), 0)] {
+            let mut req_ = Request::default();
+            req_.headers_mut().insert(AUTHORIZATION, HeaderValue::from_str(&test_jwt(user_id)).unwrap());
+            let req = req_.with_query_string_parameters(HashMap::from([("name".to_string(), "Test".to_string())]));
+
+            let resp = search_exercises_(req, &connector).await;
+            assert!(resp.is_ok());
+            if let Body::Text(body) = resp.unwrap().into_body() {
+                let res: ExerciseSearchResult = serde_json::from_str(&body).unwrap();
+                assert_eq!(res.exercises.len(), expected);
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_search_exercises_ranked_and_filtered() {
+        let (connector, _container) = pg_container().await;
+        for (exercise_name, equipment) in [("Incline Bench Press", 1), ("Bench Press", 1), ("Overhead Press", 2), ("Squat", 1)] {
+            let new_exercise = NewExercise {name: exercise_name.to_string(), necessary_equipment: Some(equipment), ..Default::default()};
+            let _ = insert_exercise(&new_exercise, &connector).await;
+        }
+
+        let search = |params: Vec<(&str, &str)>| {
+            let mut req_ = Request::default();
+            req_.headers_mut().insert(AUTHORIZATION, HeaderValue::from_str(&test_jwt(Uuid::new_v4())).unwrap());
+            let params: HashMap<String, String> = params.into_iter().map(|(k, v)| (k.to_string(), v.to_string())).collect();
+            search_exercises_(req_.with_query_string_parameters(params), &connector)
+        };
+        let names = |body: Body| -> Vec<String> {
+            if let Body::Text(body) = body {
+                let res: ExerciseSearchResult = serde_json::from_str(&body).unwrap();
+                return res.exercises.into_iter().map(|ex| ex.name).collect();
+            }
+            Vec::new()
+        };
+
+        // Lowercase term, prefix matches first
+        let response = search(vec![("name", "bench")]).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(names(response.into_body()), vec!["Bench Press", "Incline Bench Press"]);
+
+        // Word in the middle of the name
+        let response = search(vec![("name", "press")]).await.unwrap();
+        assert_eq!(names(response.into_body()).len(), 3);
+
+        // Facet filter only
+        let response = search(vec![("necessary_equipment", "2")]).await.unwrap();
+        assert_eq!(names(response.into_body()), vec!["Overhead Press"]);
+
+        // Term and filter
+        let response = search(vec![("name", "press"), ("necessary_equipment", "1")]).await.unwrap();
+        assert_eq!(names(response.into_body()).len(), 2);
+
+        // Invalid filter value
+        let response = search(vec![("exercise_type", "abc")]).await.unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_search_exercises_alias_localized() {
+        let (connector, _container) = pg_container().await;
+        let new_exercise = NewExercise {name: "Bench Press".to_string(), ..Default::default()};
+        let exercise_id = insert_exercise(&new_exercise, &connector).await.unwrap().id;
+        for (alias_name, preferred) in [("Press banca", true), ("Press de banca plano", false)] {
+            let new_alias = NewExerciseAlias {exercise_id, locale: "es".to_string(), name: alias_name.to_string(), preferred};
+            let _ = insert_exercise_alias(&new_alias, &connector).await;
+        }
+
+        for (term, language, expected) in [("banca", "es", "Press banca"), ("plano", "en-US", "Bench Press"), ("bench", "es-MX", "Press banca")] {
+            let mut req_ = Request::default();
+            req_.headers_mut().insert(AUTHORIZATION, HeaderValue::from_str(&test_jwt(Uuid::new_v4())).unwrap());
+            req_.headers_mut().insert(ACCEPT_LANGUAGE, HeaderValue::from_str(language).unwrap());
+            let req = req_.with_query_string_parameters(HashMap::from([("name".to_string(), term.to_string())]));
+
+            let response = search_exercises_(req, &connector).await.unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+            if let Body::Text(body) = response.into_body() {
+                let res: ExerciseSearchResult = serde_json::from_str(&body).unwrap();
+                let names: Vec<String> = res.exercises.into_iter().map(|ex| ex.name).collect();
+                assert_eq!(names, vec![expected]);
+            }
+        }
+    }
 }
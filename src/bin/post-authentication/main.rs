@@ -0,0 +1,84 @@
+use lambda_http::{service_fn, Error, LambdaEvent, tracing};
+use lambda_http::lambda_runtime;
+use serde_json::Value;
+use tracing::{error, info, warn};
+use uuid::Uuid;
+
+use salamandra_server::lib::db::users_db::cancel_user_deletion;
+use salamandra_server::lib::db::DBConnector;
+
+#[tokio::main]
+async fn main() -> Result<(), Error> {
+    tracing::init_default_subscriber();
+    let handler = service_fn(|event| async {
+        let connector = DBConnector::default();
+        post_authentication(event, &connector).await
+    });
+    lambda_runtime::run(handler).await
+}
+
+/// Cognito post authentication trigger
+/// * Logging back in cancels a pending account deletion
+/// * Never blocks the login, the event is always returned unchanged
+async fn post_authentication(event: LambdaEvent<Value>, connector: &DBConnector) -> Result<Value, Error> {
+    let sub = event.payload
+        .pointer("/request/userAttributes/sub")
+        .and_then(Value::as_str)
+        .and_then(|sub| Uuid::parse_str(sub).ok());
+
+    match sub {
+        Some(user_id) => match cancel_user_deletion(user_id, connector).await {
+            Ok(0) => (),
+            Ok(_) => info!("Cancelled account deletion of {}", user_id),
+            Err(error) => error!("INTERNAL SERVER ERROR: {}", error),
+        },
+        None => warn!("Post authentication event without a valid sub"),
+    }
+    Ok(event.payload)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+    use serde_json::json;
+    use lambda_http::Context;
+    use salamandra_server::lib::utils::tests::{pg_container, insert_helper, Items};
+    use salamandra_server::lib::db::users_db::{lookup_user, schedule_user_deletion};
+
+    // TEST CASES
+    // * Login cancels pending deletion
+    // * Event without sub is returned unchanged
+
+    fn cognito_event(sub: &str) -> Value {
+        json!({
+            "version": "1",
+            "triggerSource": "PostAuthentication_Authentication",
+            "userName": "pato",
+            "request": {"userAttributes": {"sub": sub, "email_verified": "true"}, "newDeviceUsed": false},
+            "response": {}
+        })
+    }
+
+    #[tokio::test]
+    async fn test_post_authentication_cancels_deletion() {
+        let (connector, _container) = pg_container().await;
+        let user_id = insert_helper(1, Items::Users, &connector, None).await[0];
+        let _ = schedule_user_deletion(user_id, Duration::days(30), &connector).await;
+
+        let event = cognito_event(&user_id.to_string());
+        let resp = post_authentication(LambdaEvent::new(event.clone(), Context::default()), &connector).await;
+        assert_eq!(resp.unwrap(), event);
+        assert!(lookup_user(user_id, &connector).await.unwrap().deletion_scheduled_for.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_post_authentication_invalid_sub() {
+        let (connector, _container) = pg_container().await;
+
+        let event = cognito_event("not-a-uuid");
+        let resp = post_authentication(LambdaEvent::new(event.clone(), Context::default()), &connector).await;
+        assert_eq!(resp.unwrap(), event);
+    }
+}
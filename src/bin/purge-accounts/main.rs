@@ -0,0 +1,59 @@
+use chrono::Utc;
+use lambda_http::{service_fn, Error, LambdaEvent, tracing};
+use lambda_http::lambda_runtime;
+use serde_json::{json, Value};
+use tracing::{error, info};
+
+use salamandra_server::lib::db::users_db::purge_deleted_users;
+use salamandra_server::lib::db::DBConnector;
+
+#[tokio::main]
+async fn main() -> Result<(), Error> {
+    tracing::init_default_subscriber();
+    let handler = service_fn(|event| async {
+        let connector = DBConnector::default();
+        purge_accounts(event, &connector).await
+    });
+    lambda_runtime::run(handler).await
+}
+
+/// Scheduled job removing accounts whose deletion grace period is over
+/// * Everything owned by the users is removed with them
+async fn purge_accounts(_event: LambdaEvent<Value>, connector: &DBConnector) -> Result<Value, Error> {
+    match purge_deleted_users(Utc::now().naive_utc(), connector).await {
+        Ok(purged) => {
+            info!("Purged {} accounts", purged);
+            Ok(json!({ "purged": purged }))
+        }
+        Err(mes) => {
+            error!("INTERNAL SERVER ERROR: {}", mes);
+            Err(mes.to_string().into())
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+    use lambda_http::Context;
+    use salamandra_server::lib::utils::tests::{pg_container, insert_helper, Items};
+    use salamandra_server::lib::db::users_db::{lookup_user, schedule_user_deletion};
+
+    // TEST CASES
+    // * Purges expired accounts only
+
+    #[tokio::test]
+    async fn test_purge_accounts() {
+        let (connector, _container) = pg_container().await;
+        let user_ids = insert_helper(2, Items::Users, &connector, None).await;
+        let _ = schedule_user_deletion(user_ids[0], Duration::zero(), &connector).await;
+        let _ = schedule_user_deletion(user_ids[1], Duration::days(30), &connector).await;
+
+        let resp = purge_accounts(LambdaEvent::new(json!({}), Context::default()), &connector).await;
+        assert_eq!(resp.unwrap(), json!({ "purged": 1 }));
+        assert!(lookup_user(user_ids[0], &connector).await.is_err());
+        assert!(lookup_user(user_ids[1], &connector).await.is_ok());
+    }
+}
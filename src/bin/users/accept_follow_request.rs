@@ -0,0 +1,81 @@
+use lambda_http::{Error, Request, Response, Body, RequestExt};
+use lambda_http::http::StatusCode;
+use tracing::error;
+use uuid::Uuid;
+
+use salamandra_server::lib::db::follows_db::accept_follow_request as accept_request;
+use salamandra_server::lib::utils::handlers::{build_resp, extract_sub};
+use salamandra_server::lib::db::DBConnector;
+use salamandra_server::lib::errors::DBError;
+
+/// Approve a pending request to follow the user making the call
+pub async fn accept_follow_request(event: Request, connector: &DBConnector) -> Result<Response<Body>, Error> {
+
+    // Get path parameters
+    let user_id = Uuid::parse_str(event.path_parameters().first("user_id").unwrap()).unwrap();
+    let follower_id = Uuid::parse_str(event.path_parameters().first("follower_id").unwrap()).unwrap();
+
+    // Confirm user making call is the one being followed
+    match extract_sub(event.headers(), Some(user_id)) {
+        Ok(_) => (),
+        Err(resp) => return Ok(resp)
+    };
+
+    match accept_request(user_id, follower_id, connector).await {
+        Ok(follow) => Ok(build_resp(StatusCode::OK, follow)),
+        Err(DBError::ItemNotFound(mes)) => Ok(build_resp(StatusCode::NOT_FOUND, mes)),
+        Err(error) => {
+            error!("INTERNAL SERVER ERROR: {}", error);
+            Ok(build_resp(StatusCode::INTERNAL_SERVER_ERROR, ""))
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use lambda_http::http::header::{AUTHORIZATION, HeaderValue};
+    use salamandra_server::lib::utils::tests::{pg_container, insert_helper, test_jwt, Items};
+    use salamandra_server::lib::db::follows_db::{can_view_profile, insert_follow};
+    use salamandra_server::lib::db::users_db::update_user;
+    use salamandra_server::lib::models::user_models::UncompleteUser;
+
+    // TEST CASES
+    // * Accepting for someone else
+    // * Success, then nothing left to accept
+
+    fn accept_request_for(user_id: Uuid, follower_id: Uuid, sub: Uuid) -> Request {
+        let mut req = Request::default();
+        req.headers_mut().insert(AUTHORIZATION, HeaderValue::from_str(&test_jwt(sub)).unwrap());
+        req.with_path_parameters(HashMap::from([
+            ("user_id".to_string(), user_id.to_string()),
+            ("follower_id".to_string(), follower_id.to_string()),
+        ]))
+    }
+
+    #[tokio::test]
+    async fn test_accept_follow_request_forbidden() {
+        let (connector, _container) = pg_container().await;
+        let users = insert_helper(2, Items::Users, &connector, None).await;
+
+        let response = accept_follow_request(accept_request_for(users[0], users[1], users[1]), &connector).await.unwrap();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    }
+
+    #[tokio::test]
+    async fn test_accept_follow_request_success() {
+        let (connector, _container) = pg_container().await;
+        let users = insert_helper(2, Items::Users, &connector, None).await;
+        update_user(&users[0], &UncompleteUser { private_profile: Some(true), ..Default::default() }, &connector).await.unwrap();
+        insert_follow(users[1], users[0], &connector).await.unwrap();
+
+        let response = accept_follow_request(accept_request_for(users[0], users[1], users[0]), &connector).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(can_view_profile(users[1], users[0], &connector).await.unwrap());
+
+        let response = accept_follow_request(accept_request_for(users[0], users[1], users[0]), &connector).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+}
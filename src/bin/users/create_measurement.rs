@@ -0,0 +1,119 @@
+use lambda_http::{Error, Request, Response, Body, RequestExt};
+use lambda_http::http::StatusCode;
+use tracing::error;
+use uuid::Uuid;
+
+use salamandra_server::lib::db::body_measurements_db::insert_body_measurement;
+use salamandra_server::lib::models::body_measurement_models::UncompleteBodyMeasurement;
+use salamandra_server::lib::models::unit_models::InUnits;
+use salamandra_server::lib::utils::handlers::{build_resp, extract_sub, request_units};
+use salamandra_server::lib::db::DBConnector;
+use salamandra_server::lib::errors::DBError;
+
+/// Record body measurements of the user making the call
+/// * Dated today if no date is given, at most one entry per date
+/// * A weight becomes the user's current weight if it is the latest one
+/// * Values in the user's units, unless overridden with `units`
+pub async fn create_measurement(event: Request, connector: &DBConnector) -> Result<Response<Body>, Error> {
+
+    // Get path parameter
+    let user_id = Uuid::parse_str(event.path_parameters().first("user_id").unwrap()).unwrap();
+
+    // Confirm user making call owns resource
+    match extract_sub(event.headers(), Some(user_id)) {
+        Ok(_) => (),
+        Err(resp) => return Ok(resp)
+    };
+
+    let units = match request_units(&event, user_id, connector).await {
+        Ok(units) => units,
+        Err(resp) => return Ok(resp)
+    };
+
+    // Check and extract payload
+    let req: UncompleteBodyMeasurement = match event.into_body() {
+        Body::Text(body) => match serde_json::from_str(&body) {
+            Ok(req) => req,
+            Err(_) => return Ok(build_resp(StatusCode::BAD_REQUEST, "Invalid payload")),
+        },
+        _ => return Ok(build_resp(StatusCode::BAD_REQUEST, "Invalid payload: can't extract body")),
+    };
+    if req.is_empty() {
+        return Ok(build_resp(StatusCode::BAD_REQUEST, "Invalid payload: at least one measurement is needed"));
+    }
+    if let Err(mes) = req.validate() {
+        return Ok(build_resp(StatusCode::BAD_REQUEST, format!("Invalid payload: {}", mes)));
+    }
+
+    // Insert
+    match insert_body_measurement(&req.to_metric(&units).into_new(user_id), connector).await {
+        Ok(measurement) => Ok(build_resp(StatusCode::CREATED, measurement.in_units(&units))),
+        Err(DBError::UniqueViolation(mes)) => Ok(build_resp(StatusCode::CONFLICT, mes)),
+        Err(DBError::ItemNotFound(mes)) => Ok(build_resp(StatusCode::NOT_FOUND, mes)),
+        Err(error) => {
+            error!("INTERNAL SERVER ERROR: {}", error);
+            Ok(build_resp(StatusCode::INTERNAL_SERVER_ERROR, ""))
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use lambda_http::http::header::{AUTHORIZATION, HeaderValue};
+    use salamandra_server::lib::models::body_measurement_models::BodyMeasurement;
+    use salamandra_server::lib::utils::tests::{pg_container, insert_helper, test_jwt, Items};
+    use salamandra_server::lib::db::users_db::lookup_user;
+
+    // TEST CASES
+    // * Other user's measurements
+    // * Invalid payloads
+    // * Success, same date again conflicts
+
+    fn create_request(user_id: Uuid, sub: Uuid, body: &str) -> Request {
+        let mut req = Request::new(Body::Text(body.to_string()));
+        req.headers_mut().insert(AUTHORIZATION, HeaderValue::from_str(&test_jwt(sub)).unwrap());
+        req.with_path_parameters(HashMap::from([("user_id".to_string(), user_id.to_string())]))
+    }
+
+    #[tokio::test]
+    async fn test_create_measurement_forbidden() {
+        let (connector, _container) = pg_container().await;
+        let user_id = insert_helper(1, Items::Users, &connector, None).await[0];
+
+        let response = create_measurement(create_request(user_id, Uuid::new_v4(), r#"{"weight": 80}"#), &connector).await.unwrap();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    }
+
+    #[tokio::test]
+    async fn test_create_measurement_invalid_payload() {
+        let (connector, _container) = pg_container().await;
+        let user_id = insert_helper(1, Items::Users, &connector, None).await[0];
+
+        for body in ["", "{}", r#"{"date": "2024-09-01"}"#, r#"{"weight": -1}"#, r#"{"body_fat": 120}"#, r#"{"waist": 80, "date": "2999-01-01"}"#] {
+            let response = create_measurement(create_request(user_id, user_id, body), &connector).await.unwrap();
+            assert_eq!(response.status(), StatusCode::BAD_REQUEST, "{}", body);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_create_measurement_success() {
+        let (connector, _container) = pg_container().await;
+        let user_id = insert_helper(1, Items::Users, &connector, None).await[0];
+        let body = r#"{"date": "2024-09-01", "weight": 80.5, "body_fat": 18}"#;
+
+        let response = create_measurement(create_request(user_id, user_id, body), &connector).await.unwrap();
+        assert_eq!(response.status(), StatusCode::CREATED);
+        if let Body::Text(body) = response.into_body() {
+            let measurement: BodyMeasurement = serde_json::from_str(&body).unwrap();
+            assert_eq!(measurement.user_id, user_id);
+            assert_eq!(measurement.body_fat, Some(18.0));
+        }
+        assert_eq!(lookup_user(user_id, &connector).await.unwrap().weight, Some(80.5));
+
+        let response = create_measurement(create_request(user_id, user_id, body), &connector).await.unwrap();
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+    }
+}
@@ -0,0 +1,71 @@
+use lambda_http::{Error, Request, Response, Body, RequestExt};
+use lambda_http::http::StatusCode;
+use tracing::error;
+use uuid::Uuid;
+
+use salamandra_server::lib::db::body_measurements_db::delete_body_measurement;
+use salamandra_server::lib::utils::handlers::{build_resp, extract_sub};
+use salamandra_server::lib::db::DBConnector;
+
+/// Remove one of the user's measurements
+/// * The user's weight goes back to the latest remaining one
+pub async fn delete_measurement(event: Request, connector: &DBConnector) -> Result<Response<Body>, Error> {
+
+    // Get path parameters
+    let user_id = Uuid::parse_str(event.path_parameters().first("user_id").unwrap()).unwrap();
+    let measurement_id = Uuid::parse_str(event.path_parameters().first("measurement_id").unwrap()).unwrap();
+
+    // Confirm user making call owns resource
+    match extract_sub(event.headers(), Some(user_id)) {
+        Ok(_) => (),
+        Err(resp) => return Ok(resp)
+    };
+
+    match delete_body_measurement(user_id, measurement_id, connector).await {
+        Ok(0) => Ok(build_resp(StatusCode::NOT_FOUND, "")),
+        Ok(_) => Ok(build_resp(StatusCode::NO_CONTENT, "")),
+        Err(error) => {
+            error!("INTERNAL SERVER ERROR: {}", error);
+            Ok(build_resp(StatusCode::INTERNAL_SERVER_ERROR, ""))
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use lambda_http::http::header::{AUTHORIZATION, HeaderValue};
+    use salamandra_server::lib::db::body_measurements_db::{insert_body_measurement, select_body_measurements};
+    use salamandra_server::lib::models::body_measurement_models::NewBodyMeasurement;
+    use salamandra_server::lib::utils::tests::{pg_container, insert_helper, test_jwt, Items};
+
+    // TEST CASES
+    // * Other user's measurement
+    // * Success
+
+    fn delete_request(user_id: Uuid, measurement_id: Uuid) -> Request {
+        let mut req = Request::default();
+        req.headers_mut().insert(AUTHORIZATION, HeaderValue::from_str(&test_jwt(user_id)).unwrap());
+        req.with_path_parameters(HashMap::from([
+            ("user_id".to_string(), user_id.to_string()),
+            ("measurement_id".to_string(), measurement_id.to_string()),
+        ]))
+    }
+
+    #[tokio::test]
+    async fn test_delete_measurement() {
+        let (connector, _container) = pg_container().await;
+        let users = insert_helper(2, Items::Users, &connector, None).await;
+        let new_measurement = NewBodyMeasurement { user_id: users[0], weight: Some(75.0), ..Default::default() };
+        let measurement = insert_body_measurement(&new_measurement, &connector).await.unwrap();
+
+        let response = delete_measurement(delete_request(users[1], measurement.id), &connector).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+        let response = delete_measurement(delete_request(users[0], measurement.id), &connector).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+        assert!(select_body_measurements(users[0], None, None, &connector).await.unwrap().is_empty());
+    }
+}
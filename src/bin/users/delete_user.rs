@@ -0,0 +1,114 @@
+use std::env;
+use chrono::Duration;
+use lambda_http::{Error, Request, Response, Body, RequestExt};
+use lambda_http::http::StatusCode;
+use serde::{Deserialize, Serialize};
+use tracing::{error, warn};
+use uuid::Uuid;
+
+use salamandra_server::lib::db::users_db::schedule_user_deletion;
+use salamandra_server::lib::utils::handlers::{build_resp, extract_sub};
+use salamandra_server::lib::db::DBConnector;
+use salamandra_server::lib::errors::DBError;
+
+/// Days before an account is purged, unless `ACCOUNT_DELETION_GRACE_DAYS` is set
+const DEFAULT_GRACE_DAYS: i64 = 30;
+
+#[derive(Debug, Serialize, Deserialize)]
+struct DeletionScheduled {
+    deletion_scheduled_for: chrono::NaiveDateTime,
+}
+
+/// Schedule the deletion of the account of the user making the call
+/// * The account is hidden from searches during the grace period
+/// * Logging in again cancels the deletion
+pub async fn delete_user(event: Request, connector: &DBConnector) -> Result<Response<Body>, Error> {
+
+    // Get path parameter
+    let user_id = Uuid::parse_str(event.path_parameters().first("user_id").unwrap()).unwrap();
+
+    // Confirm user making call owns resource
+    match extract_sub(event.headers(), Some(user_id)) {
+        Ok(_) => (),
+        Err(resp) => return Ok(resp)
+    };
+
+    // Schedule in database
+    match schedule_user_deletion(user_id, grace_period(), connector).await {
+        Ok(scheduled) => Ok(build_resp(StatusCode::ACCEPTED, DeletionScheduled { deletion_scheduled_for: scheduled })),
+        Err(DBError::ItemNotFound(mes)) => Ok(build_resp(StatusCode::NOT_FOUND, mes)),
+        Err(error) => {
+            error!("INTERNAL SERVER ERROR: {}", error);
+            Ok(build_resp(StatusCode::INTERNAL_SERVER_ERROR, ""))
+        }
+    }
+}
+
+fn grace_period() -> Duration {
+    let days = match env::var("ACCOUNT_DELETION_GRACE_DAYS") {
+        Ok(value) => value.parse::<i64>().unwrap_or_else(|_| {
+            warn!("Invalid ACCOUNT_DELETION_GRACE_DAYS, using {} days", DEFAULT_GRACE_DAYS);
+            DEFAULT_GRACE_DAYS
+        }),
+        Err(_) => DEFAULT_GRACE_DAYS,
+    };
+    Duration::days(days.max(0))
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use lambda_http::http::header::{AUTHORIZATION, HeaderValue};
+    use salamandra_server::lib::utils::tests::{pg_container, insert_helper, test_jwt, Items};
+    use salamandra_server::lib::db::users_db::lookup_user;
+
+    // TEST CASES
+    // * Other user's account
+    // * Non existing user
+    // * Success, repeated request keeps the schedule
+
+    fn delete_request(user_id: Uuid, sub: Uuid) -> Request {
+        let mut req = Request::default();
+        req.headers_mut().insert(AUTHORIZATION, HeaderValue::from_str(&test_jwt(sub)).unwrap());
+        req.with_path_parameters(HashMap::from([("user_id".to_string(), user_id.to_string())]))
+    }
+
+    #[tokio::test]
+    async fn test_delete_user_forbidden() {
+        let (connector, _container) = pg_container().await;
+        let user_id = insert_helper(1, Items::Users, &connector, None).await[0];
+
+        let response = delete_user(delete_request(user_id, Uuid::new_v4()), &connector).await.unwrap();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+        assert!(lookup_user(user_id, &connector).await.unwrap().deletion_scheduled_for.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_delete_user_not_found() {
+        let (connector, _container) = pg_container().await;
+        let user_id = Uuid::new_v4();
+
+        let response = delete_user(delete_request(user_id, user_id), &connector).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_delete_user_success() {
+        let (connector, _container) = pg_container().await;
+        let user_id = insert_helper(1, Items::Users, &connector, None).await[0];
+
+        let response = delete_user(delete_request(user_id, user_id), &connector).await.unwrap();
+        assert_eq!(response.status(), StatusCode::ACCEPTED);
+        let scheduled = lookup_user(user_id, &connector).await.unwrap().deletion_scheduled_for;
+        assert!(scheduled.is_some());
+
+        let response = delete_user(delete_request(user_id, user_id), &connector).await.unwrap();
+        assert_eq!(response.status(), StatusCode::ACCEPTED);
+        if let Body::Text(body) = response.into_body() {
+            let res: DeletionScheduled = serde_json::from_str(&body).unwrap();
+            assert_eq!(Some(res.deletion_scheduled_for), scheduled);
+        }
+    }
+}
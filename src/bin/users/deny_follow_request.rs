@@ -0,0 +1,67 @@
+use lambda_http::{Error, Request, Response, Body, RequestExt};
+use lambda_http::http::StatusCode;
+use tracing::error;
+use uuid::Uuid;
+
+use salamandra_server::lib::db::follows_db::delete_follow_request;
+use salamandra_server::lib::utils::handlers::{build_resp, extract_sub};
+use salamandra_server::lib::db::DBConnector;
+
+/// Deny a pending request to follow the user making the call
+pub async fn deny_follow_request(event: Request, connector: &DBConnector) -> Result<Response<Body>, Error> {
+
+    // Get path parameters
+    let user_id = Uuid::parse_str(event.path_parameters().first("user_id").unwrap()).unwrap();
+    let follower_id = Uuid::parse_str(event.path_parameters().first("follower_id").unwrap()).unwrap();
+
+    // Confirm user making call is the one being followed
+    match extract_sub(event.headers(), Some(user_id)) {
+        Ok(_) => (),
+        Err(resp) => return Ok(resp)
+    };
+
+    match delete_follow_request(user_id, follower_id, connector).await {
+        Ok(0) => Ok(build_resp(StatusCode::NOT_FOUND, "")),
+        Ok(_) => Ok(build_resp(StatusCode::NO_CONTENT, "")),
+        Err(error) => {
+            error!("INTERNAL SERVER ERROR: {}", error);
+            Ok(build_resp(StatusCode::INTERNAL_SERVER_ERROR, ""))
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use lambda_http::http::header::{AUTHORIZATION, HeaderValue};
+    use salamandra_server::lib::utils::tests::{pg_container, insert_helper, test_jwt, Items};
+    use salamandra_server::lib::db::follows_db::insert_follow;
+    use salamandra_server::lib::db::users_db::update_user;
+    use salamandra_server::lib::models::user_models::UncompleteUser;
+
+    // TEST CASES
+    // * Success, then not found
+
+    #[tokio::test]
+    async fn test_deny_follow_request_success() {
+        let (connector, _container) = pg_container().await;
+        let users = insert_helper(2, Items::Users, &connector, None).await;
+        update_user(&users[0], &UncompleteUser { private_profile: Some(true), ..Default::default() }, &connector).await.unwrap();
+        insert_follow(users[1], users[0], &connector).await.unwrap();
+
+        let deny = || {
+            let mut req = Request::default();
+            req.headers_mut().insert(AUTHORIZATION, HeaderValue::from_str(&test_jwt(users[0])).unwrap());
+            req.with_path_parameters(HashMap::from([
+                ("user_id".to_string(), users[0].to_string()),
+                ("follower_id".to_string(), users[1].to_string()),
+            ]))
+        };
+        let response = deny_follow_request(deny(), &connector).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+        let response = deny_follow_request(deny(), &connector).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+}
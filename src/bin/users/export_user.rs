@@ -0,0 +1,223 @@
+use std::io::{Cursor, Write};
+use chrono::{NaiveDate, NaiveDateTime};
+use lambda_http::{Error, Request, Response, Body, RequestExt};
+use lambda_http::http::StatusCode;
+use serde::Serialize;
+use tracing::error;
+use uuid::Uuid;
+use zip::ZipWriter;
+use zip::write::SimpleFileOptions;
+
+use salamandra_server::lib::db::user_export_db::select_user_export;
+use salamandra_server::lib::models::user_export_models::UserExport;
+use salamandra_server::lib::models::schedule_models::ScheduledWorkout;
+use salamandra_server::lib::utils::handlers::{build_attachment_resp, build_resp, extract_sub};
+use salamandra_server::lib::db::DBConnector;
+use salamandra_server::lib::errors::DBError;
+
+/// Download everything stored about the user making the call
+/// * Zip archive with the whole export as `export.json` and one CSV per table
+/// * CSVs of empty tables are empty files
+/// * Measures are always in kg and cm, whatever units the user prefers
+pub async fn export_user(event: Request, connector: &DBConnector) -> Result<Response<Body>, Error> {
+
+    // Get path parameter
+    let user_id = Uuid::parse_str(event.path_parameters().first("user_id").unwrap()).unwrap();
+
+    // Confirm user making call owns resource
+    match extract_sub(event.headers(), Some(user_id)) {
+        Ok(_) => (),
+        Err(resp) => return Ok(resp)
+    };
+
+    // Fetch from database
+    let export = match select_user_export(user_id, connector).await {
+        Ok(export) => export,
+        Err(DBError::ItemNotFound(mes)) => return Ok(build_resp(StatusCode::NOT_FOUND, mes)),
+        Err(error) => {
+            error!("INTERNAL SERVER ERROR: {}", error);
+            return Ok(build_resp(StatusCode::INTERNAL_SERVER_ERROR, ""));
+        }
+    };
+
+    match build_archive(&export) {
+        Ok(archive) => Ok(build_attachment_resp(&format!("export-{}.zip", user_id), "application/zip", archive)),
+        Err(error) => {
+            error!("INTERNAL SERVER ERROR: {}", error);
+            Ok(build_resp(StatusCode::INTERNAL_SERVER_ERROR, ""))
+        }
+    }
+}
+
+fn build_archive(export: &UserExport) -> Result<Vec<u8>, Error> {
+    let mut archive = ZipWriter::new(Cursor::new(Vec::new()));
+    let options = SimpleFileOptions::default();
+
+    archive.start_file("export.json", options)?;
+    archive.write_all(&serde_json::to_vec_pretty(export)?)?;
+
+    write_csv(&mut archive, options, "user.csv", std::slice::from_ref(&export.user))?;
+    write_csv(&mut archive, options, "body_measurements.csv", &export.body_measurements)?;
+    write_csv(&mut archive, options, "follows.csv", &export.follows)?;
+    write_csv(&mut archive, options, "comments.csv", &export.comments)?;
+    write_csv(&mut archive, options, "reactions.csv", &export.reactions)?;
+    write_csv(&mut archive, options, "notifications.csv", &export.notifications)?;
+    write_csv(&mut archive, options, "challenges.csv", &export.challenges)?;
+    write_csv(&mut archive, options, "challenge_participants.csv", &export.challenge_participants)?;
+    write_csv(&mut archive, options, "workout_templates.csv", &export.workout_templates)?;
+    write_csv(&mut archive, options, "wk_template_elements.csv", &export.wk_template_elements)?;
+    write_csv(&mut archive, options, "progression_rules.csv", &export.progression_rules)?;
+    write_csv(&mut archive, options, "programs.csv", &export.programs)?;
+    write_csv(&mut archive, options, "program_slots.csv", &export.program_slots)?;
+    write_csv(&mut archive, options, "program_enrolments.csv", &export.program_enrolments)?;
+    write_csv(&mut archive, options, "workout_executions.csv", &export.workout_executions)?;
+    write_csv(&mut archive, options, "wk_execution_elements.csv", &export.wk_execution_elements)?;
+    write_csv(&mut archive, options, "workout_imports.csv", &export.workout_imports)?;
+    let scheduled_workouts: Vec<ScheduledWorkoutRow> = export.scheduled_workouts.iter().map(ScheduledWorkoutRow::from).collect();
+    write_csv(&mut archive, options, "scheduled_workouts.csv", &scheduled_workouts)?;
+    write_csv(&mut archive, options, "calendar_tokens.csv", &export.calendar_tokens)?;
+    write_csv(&mut archive, options, "exercises.csv", &export.exercises)?;
+
+    Ok(archive.finish()?.into_inner())
+}
+
+/// CSV cells can't hold lists, weekdays are joined with spaces
+#[derive(Serialize)]
+struct ScheduledWorkoutRow {
+    id: Uuid,
+    user_id: Uuid,
+    workout_template_id: Uuid,
+    start_date: NaiveDate,
+    end_date: Option<NaiveDate>,
+    weekdays: Option<String>,
+    date_created: NaiveDateTime,
+}
+
+impl From<&ScheduledWorkout> for ScheduledWorkoutRow {
+    fn from(scheduled: &ScheduledWorkout) -> Self {
+        ScheduledWorkoutRow {
+            id: scheduled.id,
+            user_id: scheduled.user_id,
+            workout_template_id: scheduled.workout_template_id,
+            start_date: scheduled.start_date,
+            end_date: scheduled.end_date,
+            weekdays: scheduled.weekdays.as_ref().map(|weekdays| weekdays.iter().map(|day| day.to_string()).collect::<Vec<_>>().join(" ")),
+            date_created: scheduled.date_created,
+        }
+    }
+}
+
+fn write_csv<T: Serialize>(archive: &mut ZipWriter<Cursor<Vec<u8>>>, options: SimpleFileOptions, name: &str, rows: &[T]) -> Result<(), Error> {
+    archive.start_file(name, options)?;
+    let mut writer = csv::Writer::from_writer(archive);
+    for row in rows {
+        writer.serialize(row)?;
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use std::io::Read;
+    use zip::ZipArchive;
+    use lambda_http::http::header::{AUTHORIZATION, CONTENT_TYPE, HeaderValue};
+    use salamandra_server::lib::utils::tests::{pg_container, insert_helper, test_jwt, Items};
+    use salamandra_server::lib::db::workout_templates_db::lookup_workout_template;
+    use salamandra_server::lib::db::schedules_db::insert_scheduled_workout;
+    use salamandra_server::lib::models::schedule_models::NewScheduledWorkout;
+
+    // TEST CASES
+    // * Other user's data
+    // * Non existing user
+    // * Success, archive holds the JSON and every table, lists flattened in CSVs
+
+    fn export_request(user_id: Uuid, sub: Uuid) -> Request {
+        let mut req = Request::default();
+        req.headers_mut().insert(AUTHORIZATION, HeaderValue::from_str(&test_jwt(sub)).unwrap());
+        req.with_path_parameters(HashMap::from([("user_id".to_string(), user_id.to_string())]))
+    }
+
+    #[tokio::test]
+    async fn test_export_user_forbidden() {
+        let (connector, _container) = pg_container().await;
+        let user_id = insert_helper(1, Items::Users, &connector, None).await[0];
+
+        let response = export_user(export_request(user_id, Uuid::new_v4()), &connector).await.unwrap();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    }
+
+    #[tokio::test]
+    async fn test_export_user_not_found() {
+        let (connector, _container) = pg_container().await;
+        let user_id = Uuid::new_v4();
+
+        let response = export_user(export_request(user_id, user_id), &connector).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_export_user_success() {
+        let (connector, _container) = pg_container().await;
+        let template_id = insert_helper(1, Items::WkTemplates, &connector, None).await[0];
+        let user_id = lookup_workout_template(template_id, &connector).await.unwrap().user_id;
+        let start_date = NaiveDate::from_ymd_opt(2024, 11, 4).unwrap();
+        insert_scheduled_workout(&NewScheduledWorkout { user_id, workout_template_id: template_id, start_date, end_date: None, weekdays: Some(vec![1, 4]) }, &connector).await.unwrap();
+
+        let response = export_user(export_request(user_id, user_id), &connector).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers().get(CONTENT_TYPE).unwrap(), "application/zip");
+
+        let body = match response.into_body() {
+            Body::Binary(body) => body,
+            _ => panic!("Expected a binary body"),
+        };
+        let mut archive = ZipArchive::new(Cursor::new(body)).unwrap();
+        let mut names: Vec<&str> = archive.file_names().collect();
+        names.sort();
+        assert_eq!(names, vec![
+            "body_measurements.csv",
+            "calendar_tokens.csv",
+            "challenge_participants.csv",
+            "challenges.csv",
+            "comments.csv",
+            "exercises.csv",
+            "export.json",
+            "follows.csv",
+            "notifications.csv",
+            "program_enrolments.csv",
+            "program_slots.csv",
+            "programs.csv",
+            "progression_rules.csv",
+            "reactions.csv",
+            "scheduled_workouts.csv",
+            "user.csv",
+            "wk_execution_elements.csv",
+            "wk_template_elements.csv",
+            "workout_executions.csv",
+            "workout_imports.csv",
+            "workout_templates.csv",
+        ]);
+
+        let mut json = String::new();
+        archive.by_name("export.json").unwrap().read_to_string(&mut json).unwrap();
+        let export: UserExport = serde_json::from_str(&json).unwrap();
+        assert_eq!(export.user.id, user_id);
+        assert_eq!(export.workout_templates[0].id, template_id);
+
+        let mut templates = String::new();
+        archive.by_name("workout_templates.csv").unwrap().read_to_string(&mut templates).unwrap();
+        let mut lines = templates.lines();
+        assert_eq!(lines.next(), Some("id,user_id,name,description,date_created"));
+        assert!(lines.next().unwrap().starts_with(&template_id.to_string()));
+
+        let mut scheduled = String::new();
+        archive.by_name("scheduled_workouts.csv").unwrap().read_to_string(&mut scheduled).unwrap();
+        let mut lines = scheduled.lines();
+        assert_eq!(lines.next(), Some("id,user_id,workout_template_id,start_date,end_date,weekdays,date_created"));
+        assert!(lines.next().unwrap().contains(",2024-11-04,,1 4,"));
+    }
+}
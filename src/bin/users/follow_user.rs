@@ -0,0 +1,107 @@
+use lambda_http::{Error, Request, Response, Body, RequestExt};
+use lambda_http::http::StatusCode;
+use tracing::error;
+use uuid::Uuid;
+
+use salamandra_server::lib::db::follows_db::insert_follow;
+use salamandra_server::lib::models::notification_models::{NewNotification, NEW_FOLLOWER};
+use salamandra_server::lib::utils::handlers::{build_resp, extract_sub, notify};
+use salamandra_server::lib::db::DBConnector;
+use salamandra_server::lib::errors::DBError;
+
+/// Follow another user
+/// * Private profiles get a pending request instead, until they accept it
+/// * The followed user is notified either way
+pub async fn follow_user(event: Request, connector: &DBConnector) -> Result<Response<Body>, Error> {
+
+    // Get path parameters
+    let user_id = Uuid::parse_str(event.path_parameters().first("user_id").unwrap()).unwrap();
+    let followed_id = Uuid::parse_str(event.path_parameters().first("followed_id").unwrap()).unwrap();
+
+    // Confirm user making call is the follower
+    match extract_sub(event.headers(), Some(user_id)) {
+        Ok(_) => (),
+        Err(resp) => return Ok(resp)
+    };
+
+    if user_id == followed_id {
+        return Ok(build_resp(StatusCode::BAD_REQUEST, "Users can't follow themselves"));
+    }
+
+    match insert_follow(user_id, followed_id, connector).await {
+        Ok(follow) => {
+            notify(NewNotification { user_id: followed_id, kind: NEW_FOLLOWER, actor_id: Some(user_id), ..Default::default() }, connector).await;
+            Ok(build_resp(StatusCode::CREATED, follow))
+        },
+        Err(DBError::ItemNotFound(mes)) => Ok(build_resp(StatusCode::NOT_FOUND, mes)),
+        Err(DBError::UniqueViolation(mes)) => Ok(build_resp(StatusCode::CONFLICT, mes)),
+        Err(error) => {
+            error!("INTERNAL SERVER ERROR: {}", error);
+            Ok(build_resp(StatusCode::INTERNAL_SERVER_ERROR, ""))
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use lambda_http::http::header::{AUTHORIZATION, HeaderValue};
+    use salamandra_server::lib::utils::tests::{pg_container, insert_helper, test_jwt, Items};
+    use salamandra_server::lib::models::follow_models::Follow;
+    use salamandra_server::lib::db::notifications_db::select_notifications;
+
+    // TEST CASES
+    // * Following in someone else's name
+    // * Following oneself and non existing user
+    // * Success, notifying the followed user, and duplicate
+
+    fn follow_request(user_id: Uuid, followed_id: Uuid, sub: Uuid) -> Request {
+        let mut req = Request::default();
+        req.headers_mut().insert(AUTHORIZATION, HeaderValue::from_str(&test_jwt(sub)).unwrap());
+        req.with_path_parameters(HashMap::from([
+            ("user_id".to_string(), user_id.to_string()),
+            ("followed_id".to_string(), followed_id.to_string()),
+        ]))
+    }
+
+    #[tokio::test]
+    async fn test_follow_user_forbidden() {
+        let (connector, _container) = pg_container().await;
+        let users = insert_helper(2, Items::Users, &connector, None).await;
+
+        let response = follow_user(follow_request(users[0], users[1], users[1]), &connector).await.unwrap();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    }
+
+    #[tokio::test]
+    async fn test_follow_user_invalid() {
+        let (connector, _container) = pg_container().await;
+        let user_id = insert_helper(1, Items::Users, &connector, None).await[0];
+
+        let response = follow_user(follow_request(user_id, user_id, user_id), &connector).await.unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        let response = follow_user(follow_request(user_id, Uuid::new_v4(), user_id), &connector).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_follow_user_success() {
+        let (connector, _container) = pg_container().await;
+        let users = insert_helper(2, Items::Users, &connector, None).await;
+
+        let response = follow_user(follow_request(users[0], users[1], users[0]), &connector).await.unwrap();
+        assert_eq!(response.status(), StatusCode::CREATED);
+        if let Body::Text(body) = response.into_body() {
+            let follow: Follow = serde_json::from_str(&body).unwrap();
+            assert!(follow.accepted);
+        }
+        let notifications = select_notifications(users[1], true, 10, 0, &connector).await.unwrap();
+        assert_eq!(notifications[0].notification.kind, NEW_FOLLOWER);
+        assert_eq!(notifications[0].actor.as_ref().unwrap().id, users[0]);
+
+        let response = follow_user(follow_request(users[0], users[1], users[0]), &connector).await.unwrap();
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+    }
+}
@@ -0,0 +1,114 @@
+use lambda_http::{Error, Request, Response, Body, RequestExt};
+use lambda_http::http::StatusCode;
+use tracing::error;
+use uuid::Uuid;
+
+use salamandra_server::lib::db::sync_db::select_changes;
+use salamandra_server::lib::models::sync_models::SyncToken;
+use salamandra_server::lib::models::unit_models::InUnits;
+use salamandra_server::lib::utils::handlers::{build_resp, extract_sub, request_units};
+use salamandra_server::lib::db::DBConnector;
+
+
+/// Templates, executions and their elements created, updated and deleted since `since`
+/// * `since` is the token of the previous changes, without it everything is returned
+/// * The response has the token to ask for the next changes with
+/// * Weights in the user's units, unless overridden with `units`
+pub async fn get_changes(event: Request, connector: &DBConnector) -> Result<Response<Body>, Error> {
+
+    // Get path parameter
+    let user_id = Uuid::parse_str(event.path_parameters().first("user_id").unwrap()).unwrap();
+
+    // Confirm user making call owns resource
+    match extract_sub(event.headers(), Some(user_id)) {
+        Ok(_) => (),
+        Err(resp) => return Ok(resp)
+    };
+
+    let units = match request_units(&event, user_id, connector).await {
+        Ok(units) => units,
+        Err(resp) => return Ok(resp)
+    };
+
+    let since: Option<SyncToken> = match event.query_string_parameters().first("since").map(str::parse).transpose() {
+        Ok(since) => since,
+        Err(mes) => return Ok(build_resp(StatusCode::BAD_REQUEST, mes))
+    };
+
+    match select_changes(user_id, since, connector).await {
+        Ok(feed) => Ok(build_resp(StatusCode::OK, feed.in_units(&units))),
+        Err(error) => {
+            error!("INTERNAL SERVER ERROR: {}", error);
+            Ok(build_resp(StatusCode::INTERNAL_SERVER_ERROR, ""))
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use lambda_http::http::header::{AUTHORIZATION, HeaderValue};
+    use salamandra_server::lib::db::wk_execution_elements_db::lookup_wk_execution_element;
+    use salamandra_server::lib::db::workout_executions_db::{delete_workout_execution, lookup_workout_execution};
+    use salamandra_server::lib::db::workout_templates_db::lookup_workout_template;
+    use salamandra_server::lib::models::sync_models::ChangeFeed;
+    use salamandra_server::lib::utils::tests::{pg_container, insert_helper, test_jwt, Items};
+
+    // TEST CASES
+    // * Another user's changes, invalid token
+    // * Everything in pounds, then the deletions since the token
+
+    fn changes_request(user_id: Uuid, sub: Uuid, params: &[(&str, &str)]) -> Request {
+        let mut req = Request::default();
+        req.headers_mut().insert(AUTHORIZATION, HeaderValue::from_str(&test_jwt(sub)).unwrap());
+        req.with_path_parameters(HashMap::from([("user_id".to_string(), user_id.to_string())]))
+            .with_query_string_parameters(params.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect::<HashMap<String, String>>())
+    }
+
+    fn feed(response: Response<Body>) -> ChangeFeed {
+        assert_eq!(response.status(), StatusCode::OK);
+        if let Body::Text(body) = response.into_body() {
+            serde_json::from_str(&body).unwrap()
+        } else {
+            panic!("Expected a text body");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_get_changes_invalid() {
+        let (connector, _container) = pg_container().await;
+        let users = insert_helper(2, Items::Users, &connector, None).await;
+
+        let response = get_changes(changes_request(users[0], users[1], &[]), &connector).await.unwrap();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+        let response = get_changes(changes_request(users[0], users[0], &[("since", "not a token")]), &connector).await.unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_get_changes_success() {
+        let (connector, _container) = pg_container().await;
+        let element_id = insert_helper(1, Items::WkExecutionElements, &connector, None).await[0];
+        let element = lookup_wk_execution_element(element_id, &connector).await.unwrap();
+        let execution_id = element.workout_execution_id;
+        let template_id = lookup_workout_execution(execution_id, &connector).await.unwrap().workout_template_id;
+        let user_id = lookup_workout_template(template_id, &connector).await.unwrap().user_id;
+
+        let full = feed(get_changes(changes_request(user_id, user_id, &[("units", "imperial")]), &connector).await.unwrap());
+        assert_eq!(full.workout_templates.created.len(), 1);
+        assert_eq!(full.workout_executions.created[0].id, execution_id);
+        let synced = &full.wk_execution_elements.created[0];
+        assert_eq!(synced.id, element_id);
+        if let (Some(kg), Some(lb)) = (element.weight, synced.weight) {
+            assert!((lb - kg / 0.453_592_37).abs() < 0.01);
+        }
+
+        delete_workout_execution(execution_id, &connector).await.unwrap();
+        let changes = feed(get_changes(changes_request(user_id, user_id, &[("since", &full.token)]), &connector).await.unwrap());
+        assert_eq!(changes.workout_executions.deleted, vec![execution_id]);
+        assert_eq!(changes.wk_execution_elements.deleted, vec![element_id]);
+        assert!(changes.workout_templates.created.is_empty() && changes.workout_templates.updated.is_empty());
+    }
+}
@@ -0,0 +1,85 @@
+use lambda_http::{Error, Request, Response, Body, RequestExt};
+use lambda_http::http::StatusCode;
+use tracing::error;
+use uuid::Uuid;
+
+use salamandra_server::lib::db::follows_db::select_followers;
+use salamandra_server::lib::models::follow_models::FollowList;
+use salamandra_server::lib::utils::handlers::{build_resp, extract_sub, pagination};
+use salamandra_server::lib::db::DBConnector;
+
+/// Pending requests to follow the user making the call, most recent first
+/// * Optional `limit` and `offset`
+pub async fn get_follow_requests(event: Request, connector: &DBConnector) -> Result<Response<Body>, Error> {
+
+    // Get path parameter
+    let user_id = Uuid::parse_str(event.path_parameters().first("user_id").unwrap()).unwrap();
+
+    // Confirm user making call owns resource
+    match extract_sub(event.headers(), Some(user_id)) {
+        Ok(_) => (),
+        Err(resp) => return Ok(resp)
+    };
+
+    let (limit, offset) = match pagination(&event) {
+        Ok(page) => page,
+        Err(resp) => return Ok(resp)
+    };
+
+    match select_followers(user_id, false, limit + 1, offset, connector).await {
+        Ok(users) => Ok(build_resp(StatusCode::OK, FollowList::page(users, limit, offset))),
+        Err(error) => {
+            error!("INTERNAL SERVER ERROR: {}", error);
+            Ok(build_resp(StatusCode::INTERNAL_SERVER_ERROR, ""))
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use lambda_http::http::header::{AUTHORIZATION, HeaderValue};
+    use salamandra_server::lib::utils::tests::{pg_container, insert_helper, test_jwt, Items};
+    use salamandra_server::lib::db::follows_db::insert_follow;
+    use salamandra_server::lib::db::users_db::update_user;
+    use salamandra_server::lib::models::user_models::UncompleteUser;
+
+    // TEST CASES
+    // * Other user's requests
+    // * Success
+
+    fn requests_request(user_id: Uuid, sub: Uuid) -> Request {
+        let mut req = Request::default();
+        req.headers_mut().insert(AUTHORIZATION, HeaderValue::from_str(&test_jwt(sub)).unwrap());
+        req.with_path_parameters(HashMap::from([("user_id".to_string(), user_id.to_string())]))
+    }
+
+    #[tokio::test]
+    async fn test_get_follow_requests_forbidden() {
+        let (connector, _container) = pg_container().await;
+        let users = insert_helper(2, Items::Users, &connector, None).await;
+
+        let response = get_follow_requests(requests_request(users[0], users[1]), &connector).await.unwrap();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    }
+
+    #[tokio::test]
+    async fn test_get_follow_requests_success() {
+        let (connector, _container) = pg_container().await;
+        let users = insert_helper(3, Items::Users, &connector, None).await;
+        // Accepted follow before going private, pending request after
+        insert_follow(users[1], users[0], &connector).await.unwrap();
+        update_user(&users[0], &UncompleteUser { private_profile: Some(true), ..Default::default() }, &connector).await.unwrap();
+        insert_follow(users[2], users[0], &connector).await.unwrap();
+
+        let response = get_follow_requests(requests_request(users[0], users[0]), &connector).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let page: FollowList = match response.into_body() {
+            Body::Text(body) => serde_json::from_str(&body).unwrap(),
+            _ => panic!("Expected a text body"),
+        };
+        assert_eq!(page.users.iter().map(|user| user.id).collect::<Vec<Uuid>>(), vec![users[2]]);
+    }
+}
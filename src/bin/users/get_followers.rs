@@ -0,0 +1,123 @@
+use lambda_http::{Error, Request, Response, Body, RequestExt};
+use lambda_http::http::StatusCode;
+use tracing::error;
+use uuid::Uuid;
+
+use salamandra_server::lib::db::follows_db::{can_view_profile, select_followers};
+use salamandra_server::lib::models::follow_models::FollowList;
+use salamandra_server::lib::utils::handlers::{build_resp, extract_sub, pagination};
+use salamandra_server::lib::db::DBConnector;
+use salamandra_server::lib::errors::DBError;
+
+/// Users following the given one, most recent first
+/// * Optional `limit` and `offset`
+/// * Followers of private profiles are only shown to the owner and their followers
+pub async fn get_followers(event: Request, connector: &DBConnector) -> Result<Response<Body>, Error> {
+
+    // Get path parameter
+    let user_id = Uuid::parse_str(event.path_parameters().first("user_id").unwrap()).unwrap();
+
+    // Any authenticated user, visibility is checked below
+    let viewer_id = match extract_sub(event.headers(), None) {
+        Ok(sub) => sub,
+        Err(resp) => return Ok(resp)
+    };
+
+    let (limit, offset) = match pagination(&event) {
+        Ok(page) => page,
+        Err(resp) => return Ok(resp)
+    };
+
+    match can_view_profile(viewer_id, user_id, connector).await {
+        Ok(true) => (),
+        Ok(false) => return Ok(build_resp(StatusCode::FORBIDDEN, "This profile is private")),
+        Err(DBError::ItemNotFound(mes)) => return Ok(build_resp(StatusCode::NOT_FOUND, mes)),
+        Err(error) => {
+            error!("INTERNAL SERVER ERROR: {}", error);
+            return Ok(build_resp(StatusCode::INTERNAL_SERVER_ERROR, ""))
+        }
+    };
+
+    match select_followers(user_id, true, limit + 1, offset, connector).await {
+        Ok(users) => Ok(build_resp(StatusCode::OK, FollowList::page(users, limit, offset))),
+        Err(error) => {
+            error!("INTERNAL SERVER ERROR: {}", error);
+            Ok(build_resp(StatusCode::INTERNAL_SERVER_ERROR, ""))
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use lambda_http::http::header::{AUTHORIZATION, HeaderValue};
+    use salamandra_server::lib::utils::tests::{pg_container, insert_helper, test_jwt, Items};
+    use salamandra_server::lib::db::follows_db::insert_follow;
+    use salamandra_server::lib::db::users_db::update_user;
+    use salamandra_server::lib::models::user_models::UncompleteUser;
+
+    // TEST CASES
+    // * Invalid pagination
+    // * Private profile, hidden to non followers
+    // * Success, paginated
+
+    fn followers_request(user_id: Uuid, sub: Uuid, query: &[(&str, &str)]) -> Request {
+        let mut req = Request::default();
+        req.headers_mut().insert(AUTHORIZATION, HeaderValue::from_str(&test_jwt(sub)).unwrap());
+        req.with_path_parameters(HashMap::from([("user_id".to_string(), user_id.to_string())]))
+            .with_query_string_parameters(query.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect::<HashMap<String, String>>())
+    }
+
+    #[tokio::test]
+    async fn test_get_followers_invalid_pagination() {
+        let (connector, _container) = pg_container().await;
+        let user_id = insert_helper(1, Items::Users, &connector, None).await[0];
+
+        for query in [[("limit", "0")], [("limit", "1000")], [("offset", "-1")], [("limit", "ten")]] {
+            let response = get_followers(followers_request(user_id, user_id, &query), &connector).await.unwrap();
+            assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_get_followers_private() {
+        let (connector, _container) = pg_container().await;
+        let users = insert_helper(2, Items::Users, &connector, None).await;
+        update_user(&users[0], &UncompleteUser { private_profile: Some(true), ..Default::default() }, &connector).await.unwrap();
+
+        let response = get_followers(followers_request(users[0], users[1], &[]), &connector).await.unwrap();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+        let response = get_followers(followers_request(users[0], users[0], &[]), &connector).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let response = get_followers(followers_request(Uuid::new_v4(), users[0], &[]), &connector).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_get_followers_success() {
+        let (connector, _container) = pg_container().await;
+        let users = insert_helper(4, Items::Users, &connector, None).await;
+        for follower in &users[1..] {
+            insert_follow(*follower, users[0], &connector).await.unwrap();
+        }
+
+        let response = get_followers(followers_request(users[0], users[1], &[("limit", "2")]), &connector).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let page: FollowList = match response.into_body() {
+            Body::Text(body) => serde_json::from_str(&body).unwrap(),
+            _ => panic!("Expected a text body"),
+        };
+        assert_eq!(page.users.len(), 2);
+        assert_eq!(page.next_offset, Some(2));
+
+        let response = get_followers(followers_request(users[0], users[1], &[("limit", "2"), ("offset", "2")]), &connector).await.unwrap();
+        let page: FollowList = match response.into_body() {
+            Body::Text(body) => serde_json::from_str(&body).unwrap(),
+            _ => panic!("Expected a text body"),
+        };
+        assert_eq!(page.users.len(), 1);
+        assert_eq!(page.next_offset, None);
+    }
+}
@@ -0,0 +1,81 @@
+use lambda_http::{Error, Request, Response, Body, RequestExt};
+use lambda_http::http::StatusCode;
+use tracing::error;
+use uuid::Uuid;
+
+use salamandra_server::lib::db::follows_db::{can_view_profile, select_following};
+use salamandra_server::lib::models::follow_models::FollowList;
+use salamandra_server::lib::utils::handlers::{build_resp, extract_sub, pagination};
+use salamandra_server::lib::db::DBConnector;
+use salamandra_server::lib::errors::DBError;
+
+/// Users the given one follows, most recent first
+/// * Optional `limit` and `offset`
+/// * Private profiles only show it to the owner and their followers
+pub async fn get_following(event: Request, connector: &DBConnector) -> Result<Response<Body>, Error> {
+
+    // Get path parameter
+    let user_id = Uuid::parse_str(event.path_parameters().first("user_id").unwrap()).unwrap();
+
+    // Any authenticated user, visibility is checked below
+    let viewer_id = match extract_sub(event.headers(), None) {
+        Ok(sub) => sub,
+        Err(resp) => return Ok(resp)
+    };
+
+    let (limit, offset) = match pagination(&event) {
+        Ok(page) => page,
+        Err(resp) => return Ok(resp)
+    };
+
+    match can_view_profile(viewer_id, user_id, connector).await {
+        Ok(true) => (),
+        Ok(false) => return Ok(build_resp(StatusCode::FORBIDDEN, "This profile is private")),
+        Err(DBError::ItemNotFound(mes)) => return Ok(build_resp(StatusCode::NOT_FOUND, mes)),
+        Err(error) => {
+            error!("INTERNAL SERVER ERROR: {}", error);
+            return Ok(build_resp(StatusCode::INTERNAL_SERVER_ERROR, ""))
+        }
+    };
+
+    match select_following(user_id, limit + 1, offset, connector).await {
+        Ok(users) => Ok(build_resp(StatusCode::OK, FollowList::page(users, limit, offset))),
+        Err(error) => {
+            error!("INTERNAL SERVER ERROR: {}", error);
+            Ok(build_resp(StatusCode::INTERNAL_SERVER_ERROR, ""))
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use lambda_http::http::header::{AUTHORIZATION, HeaderValue};
+    use salamandra_server::lib::utils::tests::{pg_container, insert_helper, test_jwt, Items};
+    use salamandra_server::lib::db::follows_db::insert_follow;
+
+    // TEST CASES
+    // * Success
+
+    #[tokio::test]
+    async fn test_get_following_success() {
+        let (connector, _container) = pg_container().await;
+        let users = insert_helper(2, Items::Users, &connector, None).await;
+        insert_follow(users[0], users[1], &connector).await.unwrap();
+
+        let mut req = Request::default();
+        req.headers_mut().insert(AUTHORIZATION, HeaderValue::from_str(&test_jwt(users[1])).unwrap());
+        let req = req.with_path_parameters(HashMap::from([("user_id".to_string(), users[0].to_string())]));
+
+        let response = get_following(req, &connector).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let page: FollowList = match response.into_body() {
+            Body::Text(body) => serde_json::from_str(&body).unwrap(),
+            _ => panic!("Expected a text body"),
+        };
+        assert_eq!(page.users.iter().map(|user| user.id).collect::<Vec<Uuid>>(), vec![users[1]]);
+        assert_eq!(page.next_offset, None);
+    }
+}
@@ -0,0 +1,159 @@
+use chrono::NaiveDate;
+use lambda_http::{Error, Request, Response, Body, RequestExt};
+use lambda_http::http::StatusCode;
+use serde::{Deserialize, Serialize};
+use tracing::error;
+use uuid::Uuid;
+
+use salamandra_server::lib::db::body_measurements_db::select_body_measurements;
+use salamandra_server::lib::models::body_measurement_models::Metric;
+use salamandra_server::lib::models::unit_models::InUnits;
+use salamandra_server::lib::utils::handlers::{build_resp, date_range, extract_sub, request_units};
+use salamandra_server::lib::db::DBConnector;
+
+
+/// Weight of each new day in the moving average
+const SMOOTHING: f32 = 0.1;
+
+#[derive(Debug, Serialize, Deserialize)]
+struct MeasurementTrend {
+    metric: Metric,
+    trend: Vec<TrendPoint>,
+}
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+struct TrendPoint {
+    date: NaiveDate,
+    value: f32,
+    trend: f32,
+}
+
+/// Smoothed trend of one of the user's measurements, oldest first
+/// * `metric` is a measurement field name, weight by default
+/// * Optional `from` and `to` dates (YYYY-MM-DD), earlier values still shape the trend
+/// * Values in the user's units, unless overridden with `units`
+pub async fn get_measurement_trend(event: Request, connector: &DBConnector) -> Result<Response<Body>, Error> {
+
+    // Get path parameter
+    let user_id = Uuid::parse_str(event.path_parameters().first("user_id").unwrap()).unwrap();
+
+    // Confirm user making call owns resource
+    match extract_sub(event.headers(), Some(user_id)) {
+        Ok(_) => (),
+        Err(resp) => return Ok(resp)
+    };
+
+    let units = match request_units(&event, user_id, connector).await {
+        Ok(units) => units,
+        Err(resp) => return Ok(resp)
+    };
+
+    let metric: Metric = match event.query_string_parameters().first("metric").map(str::parse).unwrap_or(Ok(Metric::Weight)) {
+        Ok(metric) => metric,
+        Err(mes) => return Ok(build_resp(StatusCode::BAD_REQUEST, mes))
+    };
+    let (from, to) = match date_range(&event) {
+        Ok(range) => range,
+        Err(resp) => return Ok(resp)
+    };
+
+    let measurements = match select_body_measurements(user_id, None, to, connector).await {
+        Ok(measurements) => measurements.in_units(&units),
+        Err(error) => {
+            error!("INTERNAL SERVER ERROR: {}", error);
+            return Ok(build_resp(StatusCode::INTERNAL_SERVER_ERROR, ""))
+        }
+    };
+    let points: Vec<(NaiveDate, f32)> = measurements.iter()
+        .filter_map(|measurement| metric.value(measurement).map(|value| (measurement.date, value)))
+        .collect();
+    let trend = smoothed_trend(&points).into_iter()
+        .filter(|point| from.is_none_or(|from| point.date >= from))
+        .collect();
+    Ok(build_resp(StatusCode::OK, MeasurementTrend { metric, trend }))
+}
+
+/// Exponential moving average of the values, sorted by date
+/// * Days without a measurement count as if the trend was measured,
+///   so a value after a long gap weighs more than a daily one
+fn smoothed_trend(points: &[(NaiveDate, f32)]) -> Vec<TrendPoint> {
+    let mut previous: Option<(NaiveDate, f32)> = None;
+    points.iter().map(|&(date, value)| {
+        let trend = match previous {
+            Some((last_date, last_trend)) => {
+                let days = (date - last_date).num_days().max(1) as i32;
+                let weight = 1.0 - (1.0 - SMOOTHING).powi(days);
+                last_trend + weight * (value - last_trend)
+            }
+            None => value,
+        };
+        previous = Some((date, trend));
+        TrendPoint { date, value, trend }
+    }).collect()
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use lambda_http::http::header::{AUTHORIZATION, HeaderValue};
+    use salamandra_server::lib::db::body_measurements_db::insert_body_measurement;
+    use salamandra_server::lib::models::body_measurement_models::NewBodyMeasurement;
+    use salamandra_server::lib::utils::tests::{pg_container, insert_helper, test_jwt, Items};
+
+    // TEST CASES
+    // * Smoothing with daily values and gaps
+    // * Unknown metric
+    // * Success, range keeps the earlier values in the trend
+
+    fn day(n: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2024, 9, n).unwrap()
+    }
+
+    fn trend_request(user_id: Uuid, params: &[(&str, &str)]) -> Request {
+        let mut req = Request::default();
+        req.headers_mut().insert(AUTHORIZATION, HeaderValue::from_str(&test_jwt(user_id)).unwrap());
+        req.with_path_parameters(HashMap::from([("user_id".to_string(), user_id.to_string())]))
+            .with_query_string_parameters(params.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect::<HashMap<String, String>>())
+    }
+
+    #[test]
+    fn test_smoothed_trend() {
+        let trend = smoothed_trend(&[(day(1), 80.0), (day(2), 90.0), (day(4), 70.0)]);
+        assert_eq!(trend[0].trend, 80.0);
+        assert!((trend[1].trend - 81.0).abs() < 1e-4);
+        // Two days since the last value: 1 - 0.9^2 = 0.19
+        assert!((trend[2].trend - (81.0 - 0.19 * 11.0)).abs() < 1e-4);
+        assert_eq!(trend[2].value, 70.0);
+        assert!(smoothed_trend(&[]).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_get_measurement_trend_unknown_metric() {
+        let (connector, _container) = pg_container().await;
+        let user_id = insert_helper(1, Items::Users, &connector, None).await[0];
+
+        let response = get_measurement_trend(trend_request(user_id, &[("metric", "height")]), &connector).await.unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_get_measurement_trend_success() {
+        let (connector, _container) = pg_container().await;
+        let user_id = insert_helper(1, Items::Users, &connector, None).await[0];
+        for (n, waist) in [(1, Some(90.0)), (2, None), (3, Some(80.0))] {
+            let new_measurement = NewBodyMeasurement { user_id, date: day(n), weight: Some(80.0), waist, ..Default::default() };
+            insert_body_measurement(&new_measurement, &connector).await.unwrap();
+        }
+
+        let response = get_measurement_trend(trend_request(user_id, &[("metric", "waist"), ("from", "2024-09-02")]), &connector).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        if let Body::Text(body) = response.into_body() {
+            let res: MeasurementTrend = serde_json::from_str(&body).unwrap();
+            assert_eq!(res.metric, Metric::Waist);
+            assert_eq!(res.trend.len(), 1);
+            assert_eq!(res.trend[0].date, day(3));
+            assert!((res.trend[0].trend - (90.0 - 0.19 * 10.0)).abs() < 1e-4);
+        }
+    }
+}
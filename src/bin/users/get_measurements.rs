@@ -0,0 +1,114 @@
+use lambda_http::{Error, Request, Response, Body, RequestExt};
+use lambda_http::http::StatusCode;
+use serde::{Deserialize, Serialize};
+use tracing::error;
+use uuid::Uuid;
+
+use salamandra_server::lib::db::body_measurements_db::select_body_measurements;
+use salamandra_server::lib::models::body_measurement_models::BodyMeasurement;
+use salamandra_server::lib::models::unit_models::InUnits;
+use salamandra_server::lib::utils::handlers::{build_resp, date_range, extract_sub, request_units};
+use salamandra_server::lib::db::DBConnector;
+
+#[derive(Debug, Serialize, Deserialize)]
+struct MeasurementHistory {
+    measurements: Vec<BodyMeasurement>,
+}
+
+/// Body measurement history of the user making the call, oldest first
+/// * Optional `from` and `to` dates (YYYY-MM-DD), both included
+/// * Values in the user's units, unless overridden with `units`
+pub async fn get_measurements(event: Request, connector: &DBConnector) -> Result<Response<Body>, Error> {
+
+    // Get path parameter
+    let user_id = Uuid::parse_str(event.path_parameters().first("user_id").unwrap()).unwrap();
+
+    // Confirm user making call owns resource
+    match extract_sub(event.headers(), Some(user_id)) {
+        Ok(_) => (),
+        Err(resp) => return Ok(resp)
+    };
+
+    let units = match request_units(&event, user_id, connector).await {
+        Ok(units) => units,
+        Err(resp) => return Ok(resp)
+    };
+
+    let (from, to) = match date_range(&event) {
+        Ok(range) => range,
+        Err(resp) => return Ok(resp)
+    };
+
+    match select_body_measurements(user_id, from, to, connector).await {
+        Ok(measurements) => Ok(build_resp(StatusCode::OK, MeasurementHistory { measurements: measurements.in_units(&units) })),
+        Err(error) => {
+            error!("INTERNAL SERVER ERROR: {}", error);
+            Ok(build_resp(StatusCode::INTERNAL_SERVER_ERROR, ""))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use chrono::NaiveDate;
+    use lambda_http::http::header::{AUTHORIZATION, HeaderValue};
+    use salamandra_server::lib::db::body_measurements_db::insert_body_measurement;
+    use salamandra_server::lib::models::body_measurement_models::NewBodyMeasurement;
+    use salamandra_server::lib::utils::tests::{pg_container, insert_helper, test_jwt, Items};
+
+    // TEST CASES
+    // * Other user's measurements
+    // * Invalid dates
+    // * Success with and without range
+
+    fn get_request(user_id: Uuid, sub: Uuid, params: &[(&str, &str)]) -> Request {
+        let mut req = Request::default();
+        req.headers_mut().insert(AUTHORIZATION, HeaderValue::from_str(&test_jwt(sub)).unwrap());
+        req.with_path_parameters(HashMap::from([("user_id".to_string(), user_id.to_string())]))
+            .with_query_string_parameters(params.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect::<HashMap<String, String>>())
+    }
+
+    #[tokio::test]
+    async fn test_get_measurements_forbidden() {
+        let (connector, _container) = pg_container().await;
+        let user_id = insert_helper(1, Items::Users, &connector, None).await[0];
+
+        let response = get_measurements(get_request(user_id, Uuid::new_v4(), &[]), &connector).await.unwrap();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    }
+
+    #[tokio::test]
+    async fn test_get_measurements_invalid_dates() {
+        let (connector, _container) = pg_container().await;
+        let user_id = insert_helper(1, Items::Users, &connector, None).await[0];
+
+        for params in [vec![("from", "yesterday")], vec![("to", "2024-13-01")], vec![("from", "2024-09-02"), ("to", "2024-09-01")]] {
+            let response = get_measurements(get_request(user_id, user_id, &params), &connector).await.unwrap();
+            assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_get_measurements_success() {
+        let (connector, _container) = pg_container().await;
+        let user_id = insert_helper(1, Items::Users, &connector, None).await[0];
+        for day in ["2024-09-01", "2024-09-08", "2024-09-15"] {
+            let new_measurement = NewBodyMeasurement {
+                user_id, date: NaiveDate::parse_from_str(day, "%Y-%m-%d").unwrap(), weight: Some(80.0), ..Default::default()
+            };
+            insert_body_measurement(&new_measurement, &connector).await.unwrap();
+        }
+
+        let cases = [(vec![], 3), (vec![("from", "2024-09-08")], 2), (vec![("from", "2024-09-02"), ("to", "2024-09-08")], 1)];
+        for (params, expected) in cases {
+            let response = get_measurements(get_request(user_id, user_id, &params), &connector).await.unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+            if let Body::Text(body) = response.into_body() {
+                let history: MeasurementHistory = serde_json::from_str(&body).unwrap();
+                assert_eq!(history.measurements.len(), expected);
+            }
+        }
+    }
+}
@@ -0,0 +1,107 @@
+use lambda_http::{Error, Request, Response, Body, RequestExt};
+use lambda_http::http::StatusCode;
+use tracing::error;
+use uuid::Uuid;
+
+use salamandra_server::lib::db::notifications_db::{count_unread, select_notifications};
+use salamandra_server::lib::models::notification_models::NotificationInbox;
+use salamandra_server::lib::utils::handlers::{build_resp, extract_sub, pagination};
+use salamandra_server::lib::db::DBConnector;
+
+/// Notifications of the caller, newest first, with unread counts
+/// * Optional `limit` and `offset`, and `unread=true` to leave read ones out
+pub async fn get_notifications(event: Request, connector: &DBConnector) -> Result<Response<Body>, Error> {
+
+    // Get path parameter
+    let user_id = Uuid::parse_str(event.path_parameters().first("user_id").unwrap()).unwrap();
+
+    // Confirm user making call owns resource
+    match extract_sub(event.headers(), Some(user_id)) {
+        Ok(_) => (),
+        Err(resp) => return Ok(resp)
+    };
+
+    let (limit, offset) = match pagination(&event) {
+        Ok(page) => page,
+        Err(resp) => return Ok(resp)
+    };
+    let unread_only = event.query_string_parameters().first("unread") == Some("true");
+
+    let notifications = match select_notifications(user_id, unread_only, limit + 1, offset, connector).await {
+        Ok(notifications) => notifications,
+        Err(error) => {
+            error!("INTERNAL SERVER ERROR: {}", error);
+            return Ok(build_resp(StatusCode::INTERNAL_SERVER_ERROR, ""))
+        }
+    };
+    match count_unread(user_id, connector).await {
+        Ok(unread) => Ok(build_resp(StatusCode::OK, NotificationInbox::page(notifications, unread, limit, offset))),
+        Err(error) => {
+            error!("INTERNAL SERVER ERROR: {}", error);
+            Ok(build_resp(StatusCode::INTERNAL_SERVER_ERROR, ""))
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use lambda_http::http::header::{AUTHORIZATION, HeaderValue};
+    use salamandra_server::lib::utils::tests::{pg_container, insert_helper, test_jwt, Items};
+    use salamandra_server::lib::db::follows_db::insert_follow;
+    use salamandra_server::lib::db::notifications_db::{insert_notification, mark_notifications_read};
+    use salamandra_server::lib::models::notification_models::{NewNotification, UnreadCount, NEW_FOLLOWER};
+
+    // TEST CASES
+    // * Other user's notifications
+    // * Success, paginated, unread only
+
+    fn inbox_request(user_id: Uuid, sub: Uuid, query: &[(&str, &str)]) -> Request {
+        let mut req = Request::default();
+        req.headers_mut().insert(AUTHORIZATION, HeaderValue::from_str(&test_jwt(sub)).unwrap());
+        req.with_path_parameters(HashMap::from([("user_id".to_string(), user_id.to_string())]))
+            .with_query_string_parameters(query.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect::<HashMap<String, String>>())
+    }
+
+    fn inbox_from(response: Response<Body>) -> NotificationInbox {
+        match response.into_body() {
+            Body::Text(body) => serde_json::from_str(&body).unwrap(),
+            _ => panic!("Expected a text body"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_get_notifications_forbidden() {
+        let (connector, _container) = pg_container().await;
+        let users = insert_helper(2, Items::Users, &connector, None).await;
+
+        let response = get_notifications(inbox_request(users[0], users[1], &[]), &connector).await.unwrap();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    }
+
+    #[tokio::test]
+    async fn test_get_notifications_success() {
+        let (connector, _container) = pg_container().await;
+        let users = insert_helper(3, Items::Users, &connector, None).await;
+        for follower in &users[1..] {
+            insert_follow(*follower, users[0], &connector).await.unwrap();
+            let new_notification = NewNotification { user_id: users[0], kind: NEW_FOLLOWER, actor_id: Some(*follower), ..Default::default() };
+            insert_notification(&new_notification, &connector).await.unwrap();
+        }
+
+        let inbox = inbox_from(get_notifications(inbox_request(users[0], users[0], &[("limit", "1")]), &connector).await.unwrap());
+        assert_eq!(inbox.notifications.len(), 1);
+        assert_eq!(inbox.next_offset, Some(1));
+        assert_eq!(inbox.unread, 2);
+        assert_eq!(inbox.unread_by_kind, vec![UnreadCount { kind: NEW_FOLLOWER, count: 2 }]);
+
+        let read = inbox.notifications[0].notification.id;
+        mark_notifications_read(users[0], Some(&[read]), &connector).await.unwrap();
+        let inbox = inbox_from(get_notifications(inbox_request(users[0], users[0], &[("unread", "true")]), &connector).await.unwrap());
+        assert_eq!(inbox.notifications.len(), 1);
+        assert_ne!(inbox.notifications[0].notification.id, read);
+        assert_eq!(inbox.unread, 1);
+    }
+}
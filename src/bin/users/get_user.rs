@@ -3,21 +3,56 @@ use lambda_http::http::StatusCode;
 use tracing::error;
 use uuid::Uuid;
 
+use salamandra_server::lib::db::follows_db::can_view_profile;
 use salamandra_server::lib::db::users_db::lookup_user;
-use salamandra_server::lib::utils::handlers::build_resp;
+use salamandra_server::lib::models::unit_models::InUnits;
+use salamandra_server::lib::models::user_models::UserInfo;
+use salamandra_server::lib::utils::handlers::{build_resp, extract_sub, request_units};
 use salamandra_server::lib::db::DBConnector;
 use salamandra_server::lib::errors::DBError;
 
 /// Fetch user
-/// * Assumes user id has been previously checked
+/// * The whole profile only for the user themselves, with height and weight in their units unless overridden with `units`
+/// * Anyone else gets the public projection, forbidden if the profile is private to them
 pub async fn get_user(event: Request, connector: &DBConnector) -> Result<Response<Body>, Error> {
 
     // Get path parameter
     let user_id = Uuid::parse_str(event.path_parameters().first("user_id").unwrap()).unwrap();
 
+    // Identify user making call
+    let viewer_id = match extract_sub(event.headers(), None) {
+        Ok(viewer_id) => viewer_id,
+        Err(resp) => return Ok(resp)
+    };
+
+    if viewer_id != user_id {
+        match can_view_profile(viewer_id, user_id, connector).await {
+            Ok(true) => (),
+            Ok(false) => return Ok(build_resp(StatusCode::FORBIDDEN, "This profile is private")),
+            Err(DBError::ItemNotFound(mes)) => return Ok(build_resp(StatusCode::NOT_FOUND, mes)),
+            Err(error) => {
+                error!("INTERNAL SERVER ERROR: {}", error);
+                return Ok(build_resp(StatusCode::INTERNAL_SERVER_ERROR, ""))
+            }
+        }
+        return match lookup_user(user_id, connector).await {
+            Ok(user) => Ok(build_resp(StatusCode::OK, UserInfo { username: user.username, display_name: user.display_name, id: user.id })),
+            Err(DBError::ItemNotFound(mes)) => Ok(build_resp(StatusCode::NOT_FOUND, mes)),
+            Err(error) => {
+                error!("INTERNAL SERVER ERROR: {}", error);
+                Ok(build_resp(StatusCode::INTERNAL_SERVER_ERROR, ""))
+            }
+        };
+    }
+
+    let units = match request_units(&event, user_id, connector).await {
+        Ok(units) => units,
+        Err(resp) => return Ok(resp)
+    };
+
     // Fetch from database
     match lookup_user(user_id, connector).await {
-        Ok(user) => Ok(build_resp(StatusCode::OK, user)),
+        Ok(user) => Ok(build_resp(StatusCode::OK, user.in_units(&units))),
         Err(DBError::ItemNotFound(mes)) => Ok(build_resp(StatusCode::NOT_FOUND, mes)),
         Err(error) => {
             error!("INTERNAL SERVER ERROR: {}", error);
@@ -33,35 +68,46 @@ mod tests {
     use uuid::Uuid;
     use std::collections::HashMap;
     use lambda_http::http::StatusCode;
-    use salamandra_server::lib::utils::tests::pg_container;
+    use lambda_http::http::header::{AUTHORIZATION, HeaderValue};
+    use salamandra_server::lib::utils::tests::{pg_container, test_jwt};
     use salamandra_server::lib::models::user_models::User;
+    use salamandra_server::lib::models::unit_models::{LB, IN};
     use salamandra_server::lib::db::users_db::insert_user;
+    use salamandra_server::lib::db::follows_db::{accept_follow_request, insert_follow};
 
     // TEST CASES
     // * Non existing user
     // * Existing user
+    // * Measures in the user's units and overridden
+    // * Missing token
+    // * Other viewers: public projection, private profile, accepted follower
+
+    fn user_request(user_id: Uuid, sub: Uuid) -> Request {
+        let mut req = Request::default();
+        req.headers_mut().insert(AUTHORIZATION, HeaderValue::from_str(&test_jwt(sub)).unwrap());
+        req.with_path_parameters(HashMap::from([("user_id".to_string(), user_id.to_string())]))
+    }
 
     #[tokio::test]
     async fn test_get_user_not_found() {
         let (connector, _container) = pg_container().await;
-        let user_id = Uuid::new_v4().to_string();
-        let req = Request::default();
-        let req = req.clone().with_path_parameters(HashMap::from([("user_id".to_string(), user_id)]));
-        
-        let resp = get_user(req, &connector).await;
+        let user_id = Uuid::new_v4();
+
+        let resp = get_user(user_request(user_id, user_id), &connector).await;
         assert!(resp.is_ok());
         let response = resp.unwrap();
         assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+        let response = get_user(user_request(user_id, Uuid::new_v4()), &connector).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
     }
 
     #[tokio::test]
     async fn test_get_user_ok() {
         let (connector, _container) = pg_container().await;
         let user = User::default();
-        let user_id = user.id.to_string();
-        let req = Request::default();
-        let req = req.clone().with_path_parameters(HashMap::from([("user_id".to_string(), user_id)]));
-       
+        let req = user_request(user.id, user.id);
+
         let _ = insert_user(&user, &connector).await;
         let resp = get_user(req, &connector).await;
         assert!(resp.is_ok());
@@ -73,4 +119,69 @@ mod tests {
             assert_eq!(user, fetched_user.unwrap());
         }
     }
+
+    #[tokio::test]
+    async fn test_get_user_units() {
+        let (connector, _container) = pg_container().await;
+        let user = User { weight: Some(80.0), height: Some(180), weight_unit: LB, length_unit: IN, ..Default::default() };
+        insert_user(&user, &connector).await.unwrap();
+        let request = |units: Option<&str>| {
+            let req = user_request(user.id, user.id);
+            match units {
+                Some(units) => req.with_query_string_parameters(HashMap::from([("units".to_string(), units.to_string())])),
+                None => req,
+            }
+        };
+        let fetch = |response: Response<Body>| match response.into_body() {
+            Body::Text(body) => serde_json::from_str::<User>(&body).unwrap(),
+            _ => panic!("Expected a text body"),
+        };
+
+        let fetched = fetch(get_user(request(None), &connector).await.unwrap());
+        assert_eq!((fetched.weight, fetched.height), (Some(176.4), Some(71)));
+        let fetched = fetch(get_user(request(Some("metric")), &connector).await.unwrap());
+        assert_eq!((fetched.weight, fetched.height), (Some(80.0), Some(180)));
+
+        let response = get_user(request(Some("stone")), &connector).await.unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_get_user_unauthorized() {
+        let (connector, _container) = pg_container().await;
+        let user = User::default();
+        insert_user(&user, &connector).await.unwrap();
+
+        let req = Request::default().with_path_parameters(HashMap::from([("user_id".to_string(), user.id.to_string())]));
+        let response = get_user(req, &connector).await.unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    }
+
+    #[tokio::test]
+    async fn test_get_user_other_viewers() {
+        let (connector, _container) = pg_container().await;
+        let public = User { username: "public".to_string(), ..Default::default() };
+        let private = User { id: Uuid::new_v4(), username: "private".to_string(), private_profile: true, ..Default::default() };
+        let viewer = User { id: Uuid::new_v4(), username: "viewer".to_string(), ..Default::default() };
+        for user in [&public, &private, &viewer] {
+            insert_user(user, &connector).await.unwrap();
+        }
+        let fetch = |response: Response<Body>| match response.into_body() {
+            Body::Text(body) => serde_json::from_str::<UserInfo>(&body).unwrap(),
+            _ => panic!("Expected a text body"),
+        };
+
+        let response = get_user(user_request(public.id, viewer.id), &connector).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(fetch(response), UserInfo { username: public.username.clone(), display_name: public.display_name.clone(), id: public.id });
+
+        let response = get_user(user_request(private.id, viewer.id), &connector).await.unwrap();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+        insert_follow(viewer.id, private.id, &connector).await.unwrap();
+        accept_follow_request(private.id, viewer.id, &connector).await.unwrap();
+        let response = get_user(user_request(private.id, viewer.id), &connector).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(fetch(response).id, private.id);
+    }
 }
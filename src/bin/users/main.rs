@@ -2,11 +2,47 @@ mod create_user;
 mod get_user;
 mod search_users;
 mod patch_user;
+mod delete_user;
+mod export_user;
+mod create_measurement;
+mod get_measurements;
+mod get_measurement_trend;
+mod patch_measurement;
+mod delete_measurement;
+mod follow_user;
+mod unfollow_user;
+mod get_followers;
+mod get_following;
+mod get_follow_requests;
+mod accept_follow_request;
+mod deny_follow_request;
+mod get_notifications;
+mod mark_notification_read;
+mod mark_notifications_read;
+mod get_changes;
 
 use get_user::get_user;
 use create_user::create_user;
-use search_users::search_users;
+use search_users::search_users_;
 use patch_user::patch_user;
+use delete_user::delete_user;
+use export_user::export_user;
+use create_measurement::create_measurement;
+use get_measurements::get_measurements;
+use get_measurement_trend::get_measurement_trend;
+use patch_measurement::patch_measurement;
+use delete_measurement::delete_measurement;
+use follow_user::follow_user;
+use unfollow_user::unfollow_user;
+use get_followers::get_followers;
+use get_following::get_following;
+use get_follow_requests::get_follow_requests;
+use accept_follow_request::accept_follow_request;
+use deny_follow_request::deny_follow_request;
+use get_notifications::get_notifications;
+use mark_notification_read::mark_notification_read;
+use mark_notifications_read::mark_notifications_read;
+use get_changes::get_changes;
 use salamandra_server::lib::utils::handlers::{not_found, UUID_PATTERN};
 use salamandra_server::lib::db::DBConnector;
 
@@ -25,11 +61,42 @@ async fn router(event: Request) -> Result<Response<Body>, Error> {
     let path = event.uri().path();
     let connector = DBConnector::default();
     let specific_user = Regex::new(&format!(r"^/users/{}$", UUID_PATTERN));
+    let user_export = Regex::new(&format!(r"^/users/{}/export$", UUID_PATTERN));
+    let measurements = Regex::new(&format!(r"^/users/{}/measurements$", UUID_PATTERN));
+    let measurement_trend = Regex::new(&format!(r"^/users/{}/measurements/trend$", UUID_PATTERN));
+    let specific_measurement = Regex::new(&format!(r"^/users/{}/measurements/{}$", UUID_PATTERN, UUID_PATTERN));
+    let followers = Regex::new(&format!(r"^/users/{}/followers$", UUID_PATTERN));
+    let following = Regex::new(&format!(r"^/users/{}/following$", UUID_PATTERN));
+    let specific_following = Regex::new(&format!(r"^/users/{}/following/{}$", UUID_PATTERN, UUID_PATTERN));
+    let follow_requests = Regex::new(&format!(r"^/users/{}/follow-requests$", UUID_PATTERN));
+    let specific_follow_request = Regex::new(&format!(r"^/users/{}/follow-requests/{}$", UUID_PATTERN, UUID_PATTERN));
+    let notifications = Regex::new(&format!(r"^/users/{}/notifications$", UUID_PATTERN));
+    let notifications_read = Regex::new(&format!(r"^/users/{}/notifications/read$", UUID_PATTERN));
+    let specific_notification_read = Regex::new(&format!(r"^/users/{}/notifications/{}/read$", UUID_PATTERN, UUID_PATTERN));
+    let changes = Regex::new(&format!(r"^/users/{}/changes$", UUID_PATTERN));
     let response = match (event.method(), path) {
         (&Method::POST, "/users") => create_user(event, &connector).await,
         (&Method::GET, _) if specific_user.clone().unwrap().is_match(path) => get_user(event, &connector).await,
-        (&Method::PATCH, _) if specific_user.unwrap().is_match(path) => patch_user(event, &connector).await,
-        (&Method::GET, "/users") => search_users(event, &connector).await,
+        (&Method::PATCH, _) if specific_user.clone().unwrap().is_match(path) => patch_user(event, &connector).await,
+        (&Method::DELETE, _) if specific_user.unwrap().is_match(path) => delete_user(event, &connector).await,
+        (&Method::GET, _) if user_export.unwrap().is_match(path) => export_user(event, &connector).await,
+        (&Method::POST, _) if measurements.clone().unwrap().is_match(path) => create_measurement(event, &connector).await,
+        (&Method::GET, _) if measurements.unwrap().is_match(path) => get_measurements(event, &connector).await,
+        (&Method::GET, _) if measurement_trend.unwrap().is_match(path) => get_measurement_trend(event, &connector).await,
+        (&Method::PATCH, _) if specific_measurement.clone().unwrap().is_match(path) => patch_measurement(event, &connector).await,
+        (&Method::DELETE, _) if specific_measurement.unwrap().is_match(path) => delete_measurement(event, &connector).await,
+        (&Method::GET, _) if followers.unwrap().is_match(path) => get_followers(event, &connector).await,
+        (&Method::GET, _) if following.unwrap().is_match(path) => get_following(event, &connector).await,
+        (&Method::POST, _) if specific_following.clone().unwrap().is_match(path) => follow_user(event, &connector).await,
+        (&Method::DELETE, _) if specific_following.unwrap().is_match(path) => unfollow_user(event, &connector).await,
+        (&Method::GET, _) if follow_requests.unwrap().is_match(path) => get_follow_requests(event, &connector).await,
+        (&Method::POST, _) if specific_follow_request.clone().unwrap().is_match(path) => accept_follow_request(event, &connector).await,
+        (&Method::DELETE, _) if specific_follow_request.unwrap().is_match(path) => deny_follow_request(event, &connector).await,
+        (&Method::GET, _) if notifications.unwrap().is_match(path) => get_notifications(event, &connector).await,
+        (&Method::POST, _) if notifications_read.unwrap().is_match(path) => mark_notifications_read(event, &connector).await,
+        (&Method::POST, _) if specific_notification_read.unwrap().is_match(path) => mark_notification_read(event, &connector).await,
+        (&Method::GET, _) if changes.unwrap().is_match(path) => get_changes(event, &connector).await,
+        (&Method::GET, "/users") => search_users_(event, &connector).await,
         _ => not_found()
     };
     response
@@ -0,0 +1,72 @@
+use lambda_http::{Error, Request, Response, Body, RequestExt};
+use lambda_http::http::StatusCode;
+use tracing::error;
+use uuid::Uuid;
+
+use salamandra_server::lib::db::notifications_db::mark_notifications_read;
+use salamandra_server::lib::utils::handlers::{build_resp, extract_sub};
+use salamandra_server::lib::db::DBConnector;
+
+/// Mark one of the caller's notifications as read
+pub async fn mark_notification_read(event: Request, connector: &DBConnector) -> Result<Response<Body>, Error> {
+
+    // Get path parameters
+    let user_id = Uuid::parse_str(event.path_parameters().first("user_id").unwrap()).unwrap();
+    let notification_id = Uuid::parse_str(event.path_parameters().first("notification_id").unwrap()).unwrap();
+
+    // Confirm user making call owns resource
+    match extract_sub(event.headers(), Some(user_id)) {
+        Ok(_) => (),
+        Err(resp) => return Ok(resp)
+    };
+
+    match mark_notifications_read(user_id, Some(&[notification_id]), connector).await {
+        Ok(0) => Ok(build_resp(StatusCode::NOT_FOUND, "")),
+        Ok(_) => Ok(build_resp(StatusCode::NO_CONTENT, "")),
+        Err(error) => {
+            error!("INTERNAL SERVER ERROR: {}", error);
+            Ok(build_resp(StatusCode::INTERNAL_SERVER_ERROR, ""))
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use lambda_http::http::header::{AUTHORIZATION, HeaderValue};
+    use salamandra_server::lib::utils::tests::{pg_container, insert_helper, test_jwt, Items};
+    use salamandra_server::lib::db::notifications_db::{count_unread, insert_notification};
+    use salamandra_server::lib::models::notification_models::{NewNotification, NEW_FOLLOWER};
+
+    // TEST CASES
+    // * Success
+    // * Someone else's or non existing notification
+
+    fn read_request(user_id: Uuid, notification_id: Uuid) -> Request {
+        let mut req = Request::default();
+        req.headers_mut().insert(AUTHORIZATION, HeaderValue::from_str(&test_jwt(user_id)).unwrap());
+        req.with_path_parameters(HashMap::from([
+            ("user_id".to_string(), user_id.to_string()),
+            ("notification_id".to_string(), notification_id.to_string()),
+        ]))
+    }
+
+    #[tokio::test]
+    async fn test_mark_notification_read() {
+        let (connector, _container) = pg_container().await;
+        let users = insert_helper(2, Items::Users, &connector, None).await;
+        let new_notification = NewNotification { user_id: users[0], kind: NEW_FOLLOWER, actor_id: Some(users[1]), ..Default::default() };
+        let notification = insert_notification(&new_notification, &connector).await.unwrap().unwrap();
+
+        let response = mark_notification_read(read_request(users[1], notification.id), &connector).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        let response = mark_notification_read(read_request(users[0], Uuid::new_v4()), &connector).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+        let response = mark_notification_read(read_request(users[0], notification.id), &connector).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+        assert!(count_unread(users[0], &connector).await.unwrap().is_empty());
+    }
+}
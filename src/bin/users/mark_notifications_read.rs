@@ -0,0 +1,90 @@
+use lambda_http::{Error, Request, Response, Body, RequestExt};
+use lambda_http::http::StatusCode;
+use serde::Serialize;
+use tracing::error;
+use uuid::Uuid;
+
+use salamandra_server::lib::db::notifications_db::mark_notifications_read as mark_read;
+use salamandra_server::lib::models::notification_models::MarkRead;
+use salamandra_server::lib::utils::handlers::{build_resp, extract_sub};
+use salamandra_server::lib::db::DBConnector;
+
+#[derive(Serialize)]
+struct MarkedRead {
+    marked: usize,
+}
+
+/// Mark several of the caller's notifications as read
+/// * All of them unless the payload lists their `ids`
+pub async fn mark_notifications_read(event: Request, connector: &DBConnector) -> Result<Response<Body>, Error> {
+
+    // Get path parameter
+    let user_id = Uuid::parse_str(event.path_parameters().first("user_id").unwrap()).unwrap();
+
+    // Confirm user making call owns resource
+    match extract_sub(event.headers(), Some(user_id)) {
+        Ok(_) => (),
+        Err(resp) => return Ok(resp)
+    };
+
+    // Check and extract optional payload
+    let req: MarkRead = match event.into_body() {
+        Body::Text(body) if !body.trim().is_empty() => match serde_json::from_str(&body) {
+            Ok(req) => req,
+            Err(_) => return Ok(build_resp(StatusCode::BAD_REQUEST, "Invalid payload")),
+        },
+        _ => MarkRead::default(),
+    };
+
+    match mark_read(user_id, req.ids.as_deref(), connector).await {
+        Ok(marked) => Ok(build_resp(StatusCode::OK, MarkedRead { marked })),
+        Err(error) => {
+            error!("INTERNAL SERVER ERROR: {}", error);
+            Ok(build_resp(StatusCode::INTERNAL_SERVER_ERROR, ""))
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use lambda_http::http::header::{AUTHORIZATION, HeaderValue};
+    use salamandra_server::lib::utils::tests::{pg_container, insert_helper, test_jwt, Items};
+    use salamandra_server::lib::db::notifications_db::{count_unread, insert_notification};
+    use salamandra_server::lib::models::notification_models::{NewNotification, NEW_FOLLOWER};
+
+    // TEST CASES
+    // * Invalid payload
+    // * Given ids, then all of them
+
+    fn read_request(user_id: Uuid, body: &str) -> Request {
+        let mut req = Request::new(Body::Text(body.to_string()));
+        req.headers_mut().insert(AUTHORIZATION, HeaderValue::from_str(&test_jwt(user_id)).unwrap());
+        req.with_path_parameters(HashMap::from([("user_id".to_string(), user_id.to_string())]))
+    }
+
+    #[tokio::test]
+    async fn test_mark_notifications_read() {
+        let (connector, _container) = pg_container().await;
+        let users = insert_helper(4, Items::Users, &connector, None).await;
+        let mut ids = Vec::new();
+        for actor in &users[1..] {
+            let new_notification = NewNotification { user_id: users[0], kind: NEW_FOLLOWER, actor_id: Some(*actor), ..Default::default() };
+            ids.push(insert_notification(&new_notification, &connector).await.unwrap().unwrap().id);
+        }
+
+        let response = mark_notifications_read(read_request(users[0], r#"{"ids": "all"}"#), &connector).await.unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        let body = serde_json::json!({ "ids": [ids[0]] }).to_string();
+        let response = mark_notifications_read(read_request(users[0], &body), &connector).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(count_unread(users[0], &connector).await.unwrap()[0].count, 2);
+
+        let response = mark_notifications_read(read_request(users[0], ""), &connector).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(count_unread(users[0], &connector).await.unwrap().is_empty());
+    }
+}
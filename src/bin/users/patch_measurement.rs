@@ -0,0 +1,127 @@
+use lambda_http::{Error, Request, Response, Body, RequestExt};
+use lambda_http::http::StatusCode;
+use tracing::error;
+use uuid::Uuid;
+
+use salamandra_server::lib::db::body_measurements_db::update_body_measurement;
+use salamandra_server::lib::models::body_measurement_models::UncompleteBodyMeasurement;
+use salamandra_server::lib::models::unit_models::InUnits;
+use salamandra_server::lib::utils::handlers::{build_resp, extract_sub, request_units};
+use salamandra_server::lib::db::DBConnector;
+use salamandra_server::lib::errors::DBError;
+
+/// Update given values of one of the user's measurements
+/// * Values in the user's units, unless overridden with `units`
+pub async fn patch_measurement(event: Request, connector: &DBConnector) -> Result<Response<Body>, Error> {
+
+    // Get path parameters
+    let user_id = Uuid::parse_str(event.path_parameters().first("user_id").unwrap()).unwrap();
+    let measurement_id = Uuid::parse_str(event.path_parameters().first("measurement_id").unwrap()).unwrap();
+
+    // Confirm user making call owns resource
+    match extract_sub(event.headers(), Some(user_id)) {
+        Ok(_) => (),
+        Err(resp) => return Ok(resp)
+    };
+
+    let units = match request_units(&event, user_id, connector).await {
+        Ok(units) => units,
+        Err(resp) => return Ok(resp)
+    };
+
+    // Check and extract payload
+    let req: UncompleteBodyMeasurement = match event.into_body() {
+        Body::Text(body) => match serde_json::from_str(&body) {
+            Ok(req) => req,
+            Err(_) => return Ok(build_resp(StatusCode::BAD_REQUEST, "Invalid payload")),
+        },
+        _ => return Ok(build_resp(StatusCode::BAD_REQUEST, "Invalid payload: can't extract body")),
+    };
+    if let Err(mes) = req.validate() {
+        return Ok(build_resp(StatusCode::BAD_REQUEST, format!("Invalid payload: {}", mes)));
+    }
+
+    match update_body_measurement(user_id, measurement_id, &req.to_metric(&units), connector).await {
+        Ok(measurement) => Ok(build_resp(StatusCode::OK, measurement.in_units(&units))),
+        Err(DBError::ItemNotFound(mes)) => Ok(build_resp(StatusCode::NOT_FOUND, mes)),
+        Err(DBError::UniqueViolation(mes)) => Ok(build_resp(StatusCode::CONFLICT, mes)),
+        Err(DBError::QueryError(_)) => Ok(build_resp(StatusCode::NOT_MODIFIED, "")),
+        Err(error) => {
+            error!("INTERNAL SERVER ERROR: {}", error);
+            Ok(build_resp(StatusCode::INTERNAL_SERVER_ERROR, ""))
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use chrono::NaiveDate;
+    use lambda_http::http::header::{AUTHORIZATION, HeaderValue};
+    use salamandra_server::lib::db::body_measurements_db::insert_body_measurement;
+    use salamandra_server::lib::models::body_measurement_models::{BodyMeasurement, NewBodyMeasurement};
+    use salamandra_server::lib::utils::tests::{pg_container, insert_helper, test_jwt, Items};
+
+    // TEST CASES
+    // * Other user's measurement
+    // * Invalid payload and nothing to update
+    // * Success, moving to a date already taken conflicts
+
+    fn patch_request(user_id: Uuid, measurement_id: Uuid, body: &str) -> Request {
+        let mut req = Request::new(Body::Text(body.to_string()));
+        req.headers_mut().insert(AUTHORIZATION, HeaderValue::from_str(&test_jwt(user_id)).unwrap());
+        req.with_path_parameters(HashMap::from([
+            ("user_id".to_string(), user_id.to_string()),
+            ("measurement_id".to_string(), measurement_id.to_string()),
+        ]))
+    }
+
+    async fn insert_on(user_id: Uuid, day: u32, connector: &DBConnector) -> BodyMeasurement {
+        let new_measurement = NewBodyMeasurement {
+            user_id, date: NaiveDate::from_ymd_opt(2024, 9, day).unwrap(), arms: Some(35.0), ..Default::default()
+        };
+        insert_body_measurement(&new_measurement, connector).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_patch_measurement_not_found() {
+        let (connector, _container) = pg_container().await;
+        let users = insert_helper(2, Items::Users, &connector, None).await;
+        let measurement = insert_on(users[0], 1, &connector).await;
+
+        let response = patch_measurement(patch_request(users[1], measurement.id, r#"{"arms": 36}"#), &connector).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_patch_measurement_invalid_payload() {
+        let (connector, _container) = pg_container().await;
+        let user_id = insert_helper(1, Items::Users, &connector, None).await[0];
+        let measurement = insert_on(user_id, 1, &connector).await;
+
+        let response = patch_measurement(patch_request(user_id, measurement.id, r#"{"arms": 0}"#), &connector).await.unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        let response = patch_measurement(patch_request(user_id, measurement.id, "{}"), &connector).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+    }
+
+    #[tokio::test]
+    async fn test_patch_measurement_success() {
+        let (connector, _container) = pg_container().await;
+        let user_id = insert_helper(1, Items::Users, &connector, None).await[0];
+        let measurement = insert_on(user_id, 1, &connector).await;
+        insert_on(user_id, 2, &connector).await;
+
+        let response = patch_measurement(patch_request(user_id, measurement.id, r#"{"arms": 36, "hips": 95}"#), &connector).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        if let Body::Text(body) = response.into_body() {
+            let updated: BodyMeasurement = serde_json::from_str(&body).unwrap();
+            assert_eq!((updated.arms, updated.hips), (Some(36.0), Some(95.0)));
+        }
+
+        let response = patch_measurement(patch_request(user_id, measurement.id, r#"{"date": "2024-09-02"}"#), &connector).await.unwrap();
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+    }
+}
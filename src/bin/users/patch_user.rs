@@ -6,11 +6,13 @@ use uuid::Uuid;
 use salamandra_server::lib::db::users_db::update_user;
 use salamandra_server::lib::models::user_models::UncompleteUser;
 use salamandra_server::lib::errors::DBError;
-use salamandra_server::lib::utils::handlers::{build_resp, extract_sub};
+use salamandra_server::lib::models::unit_models::InUnits;
+use salamandra_server::lib::utils::handlers::{build_resp, extract_sub, request_units};
 use salamandra_server::lib::db::DBConnector;
 
 /// Update user
 /// * Update certain values of an existing user
+/// * Height and weight are read in the units being set, or the user's ones unless overridden with `units`
 pub async fn patch_user(event: Request, connector: &DBConnector) -> Result<Response<Body>, Error> {
 
     if let Body::Text(body) = event.clone().into_body() {
@@ -22,8 +24,17 @@ pub async fn patch_user(event: Request, connector: &DBConnector) -> Result<Respo
                 Err(resp) => return Ok(resp)
             };
 
-            let res = match update_user(&user_id, &req, connector).await {
-                Ok(updated_user) => build_resp(StatusCode::OK, updated_user),
+            let units = match request_units(&event, user_id, connector).await {
+                Ok(units) if event.query_string_parameters().first("units").is_some() => units,
+                Ok(units) => req.units(units),
+                Err(resp) => return Ok(resp)
+            };
+            if !req.units(units).is_valid() {
+                return Ok(build_resp(StatusCode::BAD_REQUEST, "Invalid units"));
+            }
+
+            let res = match update_user(&user_id, &req.to_metric(&units), connector).await {
+                Ok(updated_user) => build_resp(StatusCode::OK, updated_user.in_units(&units)),
                 Err(DBError::ItemNotFound(mes)) => {
                     warn!("Tried to update non-existing user");
                     build_resp(StatusCode::NOT_FOUND, mes)
@@ -52,12 +63,16 @@ mod tests {
     use lambda_http::http::{header::AUTHORIZATION, HeaderValue};
     use serde_json::to_string;
     use salamandra_server::lib::utils::tests::{insert_helper, pg_container, test_jwt, Items};
+    use salamandra_server::lib::db::users_db::lookup_user;
+    use salamandra_server::lib::models::user_models::User;
+    use salamandra_server::lib::models::unit_models::LB;
 
 
     // TEST CASES
     // * Invalid payload
     // * Try to update new user
     // * Success
+    // * Values read in the units being set, invalid units
     
     #[tokio::test]
     async fn test_patch_user_invalid_payload() {
@@ -112,4 +127,28 @@ mod tests {
         let response = resp.unwrap();
         assert_eq!(response.status(), StatusCode::OK);
     }
+
+    #[tokio::test]
+    async fn test_patch_user_units() {
+        let (connector, _container) = pg_container().await;
+        let id = insert_helper(1, Items::Users, &connector, None).await[0];
+        let request = |payload: &str| {
+            let mut req = Request::new(Body::Text(payload.to_string()));
+            req.headers_mut().insert(AUTHORIZATION, HeaderValue::from_str(&test_jwt(id)).unwrap());
+            req.with_path_parameters(HashMap::from([("user_id".to_string(), id.to_string())]))
+        };
+
+        let response = patch_user(request(r#"{"weight": 176.4, "weight_unit": 1}"#), &connector).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        if let Body::Text(body) = response.into_body() {
+            let user: User = serde_json::from_str(&body).unwrap();
+            assert_eq!(user.weight, Some(176.4));
+        }
+        let stored = lookup_user(id, &connector).await.unwrap();
+        assert_eq!(stored.weight_unit, LB);
+        assert!((stored.weight.unwrap() - 80.0).abs() < 0.05);
+
+        let response = patch_user(request(r#"{"weight_unit": 3}"#), &connector).await.unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
 }
@@ -2,26 +2,21 @@ use lambda_http::{Error, Request, Response, Body, RequestExt};
 use lambda_http::http::StatusCode;
 use serde::{Deserialize, Serialize};
 use tracing::error;
-use uuid::Uuid;
 
-use salamandra_server::lib::db::users_db::search_username;
+use salamandra_server::lib::db::users_db::search_users;
+use salamandra_server::lib::models::user_models::UserInfo;
 use salamandra_server::lib::utils::handlers::build_resp;
 use salamandra_server::lib::db::DBConnector;
 
-#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
-struct UserInfo {
-    username: String,
-    display_name: String,
-    id: Uuid,
-}
-
 #[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
 struct UserSearchResult {
     users: Vec<UserInfo>,
 }
 
-/// Return all users with username that matches the given prefix
-pub async fn search_users(event: Request, connector: &DBConnector) -> Result<Response<Body>, Error> {
+/// Return users whose username or display name matches the given prefix
+/// * Case-insensitive, exact matches first
+/// * Users that opted out of discoverability are hidden
+pub async fn search_users_(event: Request, connector: &DBConnector) -> Result<Response<Body>, Error> {
 
     // Check query paramater
     let username = match event.query_string_parameters().first("username") {
@@ -30,7 +25,7 @@ pub async fn search_users(event: Request, connector: &DBConnector) -> Result<Res
     };
 
     // Search in database
-    let search_result = match search_username(&username, connector).await {
+    let search_result = match search_users(&username, connector).await {
         Ok(vec) => vec,
         Err(error) => {
             error!("INTERNAL SERVER ERROR: {}", error);
@@ -39,14 +34,7 @@ pub async fn search_users(event: Request, connector: &DBConnector) -> Result<Res
     };
     
     // Format and return results
-    let user_info: Vec<UserInfo> = search_result.into_iter()
-        .map(|user| UserInfo {
-            username: user.username,
-            display_name: user.display_name,
-            id: user.id,
-        })
-    .collect();
-    let result = UserSearchResult { users: user_info };
+    let result = UserSearchResult { users: search_result };
     Ok(build_resp(StatusCode::OK, result))
     
 }
@@ -55,6 +43,7 @@ pub async fn search_users(event: Request, connector: &DBConnector) -> Result<Res
 #[cfg(test)]
 mod tests {
     use super::*;
+    use uuid::Uuid;
     use std::collections::HashMap;
     use salamandra_server::lib::utils::tests::{pg_container, insert_helper, Items};
 
@@ -70,7 +59,7 @@ mod tests {
         { // ------ No query parameters
             let req_ = Request::default();
 
-            let resp = search_users(req_, &connector).await;
+            let resp = search_users_(req_, &connector).await;
             assert!(resp.is_ok());
             let response = resp.unwrap();
             assert_eq!(response.status(), StatusCode::BAD_REQUEST);
@@ -82,7 +71,7 @@ mod tests {
             query_params.insert("not_username".to_string(), "Test".to_string());
             let req = req_.with_query_string_parameters(query_params);
 
-            let resp = search_users(req, &connector).await;
+            let resp = search_users_(req, &connector).await;
             assert!(resp.is_ok());
             let response = resp.unwrap();
             assert_eq!(response.status(), StatusCode::BAD_REQUEST);
@@ -99,14 +88,17 @@ mod tests {
         let req = req_.with_query_string_parameters(query_params);
     
         let user_ids = insert_helper(5, Items::Users, &connector, Some("Test".into())).await;
-        let resp = search_users(req, &connector).await;
+        let resp = search_users_(req, &connector).await;
         assert!(resp.is_ok());
         let response = resp.unwrap();
         assert_eq!(response.status(), StatusCode::OK);
         if let Body::Text(body) = response.into_body() {
             let res: Result<UserSearchResult, _> = serde_json::from_str(&body);
             assert!(res.is_ok());
-            let id_vec: Vec<Uuid> = res.unwrap().users.iter().map(|ex| ex.id.clone()).collect();
+            let mut id_vec: Vec<Uuid> = res.unwrap().users.iter().map(|ex| ex.id).collect();
+            let mut user_ids = user_ids;
+            id_vec.sort();
+            user_ids.sort();
             assert_eq!(user_ids, id_vec);
         }
     }
@@ -0,0 +1,76 @@
+use lambda_http::{Error, Request, Response, Body, RequestExt};
+use lambda_http::http::StatusCode;
+use tracing::error;
+use uuid::Uuid;
+
+use salamandra_server::lib::db::follows_db::delete_follow;
+use salamandra_server::lib::utils::handlers::{build_resp, extract_sub};
+use salamandra_server::lib::db::DBConnector;
+
+/// Stop following a user, or cancel a pending follow request
+pub async fn unfollow_user(event: Request, connector: &DBConnector) -> Result<Response<Body>, Error> {
+
+    // Get path parameters
+    let user_id = Uuid::parse_str(event.path_parameters().first("user_id").unwrap()).unwrap();
+    let followed_id = Uuid::parse_str(event.path_parameters().first("followed_id").unwrap()).unwrap();
+
+    // Confirm user making call is the follower
+    match extract_sub(event.headers(), Some(user_id)) {
+        Ok(_) => (),
+        Err(resp) => return Ok(resp)
+    };
+
+    match delete_follow(user_id, followed_id, connector).await {
+        Ok(0) => Ok(build_resp(StatusCode::NOT_FOUND, "")),
+        Ok(_) => Ok(build_resp(StatusCode::NO_CONTENT, "")),
+        Err(error) => {
+            error!("INTERNAL SERVER ERROR: {}", error);
+            Ok(build_resp(StatusCode::INTERNAL_SERVER_ERROR, ""))
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use lambda_http::http::header::{AUTHORIZATION, HeaderValue};
+    use salamandra_server::lib::utils::tests::{pg_container, insert_helper, test_jwt, Items};
+    use salamandra_server::lib::db::follows_db::insert_follow;
+
+    // TEST CASES
+    // * Unfollowing in someone else's name
+    // * Success, then not found
+
+    fn unfollow_request(user_id: Uuid, followed_id: Uuid, sub: Uuid) -> Request {
+        let mut req = Request::default();
+        req.headers_mut().insert(AUTHORIZATION, HeaderValue::from_str(&test_jwt(sub)).unwrap());
+        req.with_path_parameters(HashMap::from([
+            ("user_id".to_string(), user_id.to_string()),
+            ("followed_id".to_string(), followed_id.to_string()),
+        ]))
+    }
+
+    #[tokio::test]
+    async fn test_unfollow_user_forbidden() {
+        let (connector, _container) = pg_container().await;
+        let users = insert_helper(2, Items::Users, &connector, None).await;
+        insert_follow(users[0], users[1], &connector).await.unwrap();
+
+        let response = unfollow_user(unfollow_request(users[0], users[1], users[1]), &connector).await.unwrap();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    }
+
+    #[tokio::test]
+    async fn test_unfollow_user_success() {
+        let (connector, _container) = pg_container().await;
+        let users = insert_helper(2, Items::Users, &connector, None).await;
+        insert_follow(users[0], users[1], &connector).await.unwrap();
+
+        let response = unfollow_user(unfollow_request(users[0], users[1], users[0]), &connector).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+        let response = unfollow_user(unfollow_request(users[0], users[1], users[0]), &connector).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+}
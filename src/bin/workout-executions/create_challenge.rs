@@ -0,0 +1,149 @@
+use lambda_http::{Error, Request, Response, Body, RequestExt};
+use lambda_http::http::StatusCode;
+use tracing::error;
+use uuid::Uuid;
+
+use salamandra_server::lib::db::challenges_db::insert_challenge;
+use salamandra_server::lib::db::exercises_db::validate_exercises;
+use salamandra_server::lib::models::challenge_models::CreateChallengeRequest;
+use salamandra_server::lib::utils::handlers::{build_resp, extract_sub};
+use salamandra_server::lib::db::DBConnector;
+use salamandra_server::lib::errors::DBError;
+
+/// Create a challenge and invite users to it, the creator takes part from the start
+/// * The exercise of exercise reps challenges can't be retired or another user's private one
+pub async fn create_challenge(event: Request, connector: &DBConnector) -> Result<Response<Body>, Error> {
+
+    // Get path parameter
+    let user_id = Uuid::parse_str(event.path_parameters().first("user_id").unwrap()).unwrap();
+
+    // Confirm user making call owns resource
+    match extract_sub(event.headers(), Some(user_id)) {
+        Ok(_) => (),
+        Err(resp) => return Ok(resp)
+    };
+
+    // Check and extract payload
+    let req: CreateChallengeRequest = match event.into_body() {
+        Body::Text(body) => match serde_json::from_str(&body) {
+            Ok(req) => req,
+            Err(_) => return Ok(build_resp(StatusCode::BAD_REQUEST, "Invalid payload")),
+        },
+        _ => return Ok(build_resp(StatusCode::BAD_REQUEST, "Invalid payload: can't extract body")),
+    };
+    if let Err(mes) = req.validate() {
+        return Ok(build_resp(StatusCode::BAD_REQUEST, format!("Invalid payload: {}", mes)));
+    }
+
+    if let Some(exercise_id) = req.exercise_id {
+        match validate_exercises(vec![exercise_id], user_id, false, connector).await {
+            Ok(true) => (),
+            Ok(false) => return Ok(build_resp(StatusCode::NOT_FOUND, "No exercise exists with the corresponding id")),
+            Err(error) => {
+                error!("INTERNAL SERVER ERROR: {}", error);
+                return Ok(build_resp(StatusCode::INTERNAL_SERVER_ERROR, ""));
+            }
+        };
+    }
+
+    match insert_challenge(&req.to_new_challenge(user_id), &req.invitees(user_id), connector).await {
+        Ok(challenge) => Ok(build_resp(StatusCode::CREATED, challenge)),
+        Err(DBError::ItemNotFound(mes)) => Ok(build_resp(StatusCode::NOT_FOUND, mes)),
+        Err(error) => {
+            error!("INTERNAL SERVER ERROR: {}", error);
+            Ok(build_resp(StatusCode::INTERNAL_SERVER_ERROR, ""))
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use lambda_http::http::header::{AUTHORIZATION, HeaderValue};
+    use salamandra_server::lib::utils::tests::{pg_container, insert_helper, test_jwt, Items};
+    use salamandra_server::lib::models::challenge_models::{ChallengeWithParticipants, EXERCISE_REPS};
+    use salamandra_server::lib::db::exercises_db::{insert_exercise, retire_exercise};
+    use salamandra_server::lib::models::exercise_models::NewExercise;
+
+    // TEST CASES
+    // * Invalid payloads
+    // * Invited user doesn't exist
+    // * Exercise retired or private to another user
+    // * Success, duplicated invites and the creator ignored
+
+    fn create_request(user_id: Uuid, body: &str) -> Request {
+        let mut req = Request::new(Body::Text(body.to_string()));
+        req.headers_mut().insert(AUTHORIZATION, HeaderValue::from_str(&test_jwt(user_id)).unwrap());
+        req.with_path_parameters(HashMap::from([("user_id".to_string(), user_id.to_string())]))
+    }
+
+    fn challenge_body(metric: i16, exercise_id: Option<Uuid>, end_date: &str, invited: &[Uuid]) -> String {
+        serde_json::json!({
+            "name": "November",
+            "metric": metric,
+            "exercise_id": exercise_id,
+            "start_date": "2024-11-01",
+            "end_date": end_date,
+            "invited": invited,
+        }).to_string()
+    }
+
+    #[tokio::test]
+    async fn test_create_challenge_invalid() {
+        let (connector, _container) = pg_container().await;
+        let user_id = insert_helper(1, Items::Users, &connector, None).await[0];
+
+        for body in [
+            challenge_body(7, None, "2024-11-30", &[]),
+            challenge_body(2, None, "2024-11-30", &[]),
+            challenge_body(0, Some(Uuid::new_v4()), "2024-11-30", &[]),
+            challenge_body(0, None, "2024-10-30", &[]),
+            "{}".to_string(),
+        ] {
+            let response = create_challenge(create_request(user_id, &body), &connector).await.unwrap();
+            assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        }
+
+        let response = create_challenge(create_request(user_id, &challenge_body(1, None, "2024-11-30", &[Uuid::new_v4()])), &connector).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_create_challenge_exercise_not_available() {
+        let (connector, _container) = pg_container().await;
+        let users = insert_helper(2, Items::Users, &connector, None).await;
+        let private = insert_exercise(&NewExercise { name: "Own".to_string(), owner_id: Some(users[1]), ..Default::default() }, &connector).await.unwrap();
+        let retired = insert_helper(1, Items::Exercises, &connector, None).await[0];
+        retire_exercise(retired, &connector).await.unwrap();
+
+        for exercise_id in [private.id, retired] {
+            let body = challenge_body(EXERCISE_REPS, Some(exercise_id), "2024-11-30", &[]);
+            let response = create_challenge(create_request(users[0], &body), &connector).await.unwrap();
+            assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        }
+
+        // The owner can build one on their own exercise
+        let body = challenge_body(EXERCISE_REPS, Some(private.id), "2024-11-30", &[]);
+        let response = create_challenge(create_request(users[1], &body), &connector).await.unwrap();
+        assert_eq!(response.status(), StatusCode::CREATED);
+    }
+
+    #[tokio::test]
+    async fn test_create_challenge_success() {
+        let (connector, _container) = pg_container().await;
+        let users = insert_helper(2, Items::Users, &connector, None).await;
+
+        let body = challenge_body(1, None, "2024-11-30", &[users[1], users[1], users[0]]);
+        let response = create_challenge(create_request(users[0], &body), &connector).await.unwrap();
+        assert_eq!(response.status(), StatusCode::CREATED);
+        if let Body::Text(body) = response.into_body() {
+            let created: ChallengeWithParticipants = serde_json::from_str(&body).unwrap();
+            assert_eq!(created.challenge.creator_id, users[0]);
+            assert_eq!(created.participants.len(), 2);
+        } else {
+            panic!("Expected a text body");
+        }
+    }
+}
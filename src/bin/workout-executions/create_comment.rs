@@ -0,0 +1,157 @@
+use lambda_http::{Error, Request, Response, Body, RequestExt};
+use lambda_http::http::StatusCode;
+use tracing::error;
+use uuid::Uuid;
+
+use salamandra_server::lib::db::comments_db::insert_comment;
+use salamandra_server::lib::models::comment_models::{CommentContent, NewComment, MAX_COMMENT_LENGTH};
+use salamandra_server::lib::models::notification_models::{NewNotification, COMMENT};
+use salamandra_server::lib::utils::handlers::{build_resp, check_execution_visible, extract_sub, notify};
+use salamandra_server::lib::db::DBConnector;
+use salamandra_server::lib::errors::DBError;
+
+/// Comment on a workout execution the caller can see, notifying its owner
+pub async fn create_comment(event: Request, connector: &DBConnector) -> Result<Response<Body>, Error> {
+
+    // Get path parameters
+    let user_id = Uuid::parse_str(event.path_parameters().first("user_id").unwrap()).unwrap();
+    let execution_id = Uuid::parse_str(event.path_parameters().first("workout_execution_id").unwrap()).unwrap();
+
+    // Any authenticated user, visibility is checked below
+    let viewer_id = match extract_sub(event.headers(), None) {
+        Ok(sub) => sub,
+        Err(resp) => return Ok(resp)
+    };
+
+    if let Err(resp) = check_execution_visible(viewer_id, user_id, execution_id, connector).await {
+        return Ok(resp);
+    }
+
+    // Check and extract payload
+    let req: CommentContent = match event.into_body() {
+        Body::Text(body) => match serde_json::from_str(&body) {
+            Ok(req) => req,
+            Err(_) => return Ok(build_resp(StatusCode::BAD_REQUEST, "Invalid payload")),
+        },
+        _ => return Ok(build_resp(StatusCode::BAD_REQUEST, "Invalid payload: can't extract body")),
+    };
+    if !req.is_valid() {
+        return Ok(build_resp(StatusCode::BAD_REQUEST, format!("Invalid payload: comments must have between 1 and {} characters", MAX_COMMENT_LENGTH)));
+    }
+
+    let new_comment = NewComment { workout_execution_id: execution_id, user_id: viewer_id, content: req.content };
+    match insert_comment(&new_comment, connector).await {
+        Ok(comment) => {
+            let new_notification = NewNotification {
+                user_id,
+                kind: COMMENT,
+                actor_id: Some(viewer_id),
+                workout_execution_id: Some(execution_id),
+                comment_id: Some(comment.id),
+                ..Default::default()
+            };
+            notify(new_notification, connector).await;
+            Ok(build_resp(StatusCode::CREATED, comment))
+        },
+        Err(DBError::ItemNotFound(mes)) => Ok(build_resp(StatusCode::NOT_FOUND, mes)),
+        Err(error) => {
+            error!("INTERNAL SERVER ERROR: {}", error);
+            Ok(build_resp(StatusCode::INTERNAL_SERVER_ERROR, ""))
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use lambda_http::http::header::{AUTHORIZATION, HeaderValue};
+    use salamandra_server::lib::utils::tests::{pg_container, insert_helper, test_jwt, Items};
+    use salamandra_server::lib::db::users_db::update_user;
+    use salamandra_server::lib::db::workout_executions_db::lookup_workout_execution;
+    use salamandra_server::lib::db::workout_templates_db::lookup_workout_template;
+    use salamandra_server::lib::db::workout_sessions_db::insert_workout_session;
+    use salamandra_server::lib::models::workout_execution_models::NewWorkoutSession;
+    use chrono::Utc;
+    use salamandra_server::lib::models::comment_models::Comment;
+    use salamandra_server::lib::db::notifications_db::select_notifications;
+    use salamandra_server::lib::models::user_models::UncompleteUser;
+
+    // TEST CASES
+    // * Execution of another user, private profile, workout still in progress
+    // * Invalid payload
+    // * Success, notifying the owner
+
+    fn comment_request(user_id: Uuid, execution_id: Uuid, sub: Uuid, body: &str) -> Request {
+        let mut req = Request::new(Body::Text(body.to_string()));
+        req.headers_mut().insert(AUTHORIZATION, HeaderValue::from_str(&test_jwt(sub)).unwrap());
+        req.with_path_parameters(HashMap::from([
+            ("user_id".to_string(), user_id.to_string()),
+            ("workout_execution_id".to_string(), execution_id.to_string()),
+        ]))
+    }
+
+    async fn execution_owner(execution_id: Uuid, connector: &DBConnector) -> Uuid {
+        let execution = lookup_workout_execution(execution_id, connector).await.unwrap();
+        lookup_workout_template(execution.workout_template_id, connector).await.unwrap().user_id
+    }
+
+    #[tokio::test]
+    async fn test_create_comment_not_visible() {
+        let (connector, _container) = pg_container().await;
+        let viewer = insert_helper(1, Items::Users, &connector, None).await[0];
+        let execution = insert_helper(1, Items::WkExecutions, &connector, None).await[0];
+        let owner = execution_owner(execution, &connector).await;
+
+        let response = create_comment(comment_request(viewer, execution, viewer, r#"{"content": "Nice"}"#), &connector).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+        // Workouts in progress are only visible to their owner
+        let template_id = lookup_workout_execution(execution, &connector).await.unwrap().workout_template_id;
+        let now = Utc::now().naive_utc();
+        let session = insert_workout_session(&NewWorkoutSession::new(template_id, now.date(), None, now), owner, &connector).await.unwrap();
+        let response = create_comment(comment_request(owner, session.id, viewer, r#"{"content": "Nice"}"#), &connector).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        let response = create_comment(comment_request(owner, session.id, owner, r#"{"content": "Nice"}"#), &connector).await.unwrap();
+        assert_eq!(response.status(), StatusCode::CREATED);
+
+        update_user(&owner, &UncompleteUser { private_profile: Some(true), ..Default::default() }, &connector).await.unwrap();
+        let response = create_comment(comment_request(owner, execution, viewer, r#"{"content": "Nice"}"#), &connector).await.unwrap();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    }
+
+    #[tokio::test]
+    async fn test_create_comment_invalid_payload() {
+        let (connector, _container) = pg_container().await;
+        let execution = insert_helper(1, Items::WkExecutions, &connector, None).await[0];
+        let owner = execution_owner(execution, &connector).await;
+
+        let too_long = format!(r#"{{"content": "{}"}}"#, "a".repeat(MAX_COMMENT_LENGTH + 1));
+        for body in [r#"{"content": "  "}"#, r#"{"text": "Nice"}"#, too_long.as_str()] {
+            let response = create_comment(comment_request(owner, execution, owner, body), &connector).await.unwrap();
+            assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_create_comment_success() {
+        let (connector, _container) = pg_container().await;
+        let viewer = insert_helper(1, Items::Users, &connector, None).await[0];
+        let execution = insert_helper(1, Items::WkExecutions, &connector, None).await[0];
+        let owner = execution_owner(execution, &connector).await;
+
+        let response = create_comment(comment_request(owner, execution, viewer, r#"{"content": "Nice"}"#), &connector).await.unwrap();
+        assert_eq!(response.status(), StatusCode::CREATED);
+        if let Body::Text(body) = response.into_body() {
+            let comment: Comment = serde_json::from_str(&body).unwrap();
+            assert_eq!((comment.user_id, comment.workout_execution_id), (viewer, execution));
+            assert_eq!(comment.content, "Nice");
+            let notifications = select_notifications(owner, true, 10, 0, &connector).await.unwrap();
+            assert_eq!(notifications[0].notification.kind, COMMENT);
+            assert_eq!(notifications[0].notification.comment_id, Some(comment.id));
+        } else {
+            panic!("Expected a text body");
+        }
+    }
+}
@@ -8,12 +8,16 @@ use chrono::NaiveDate;
 use std::collections::{HashMap, HashSet};
 
 use salamandra_server::lib::db::exercises_db::validate_exercises;
+use salamandra_server::lib::db::feed_db::count_prs;
+use salamandra_server::lib::db::programs_db::lookup_program_slot_validated;
 use salamandra_server::lib::db::DBConnector;
 use salamandra_server::lib::db::wk_execution_elements_db::insert_batch_wk_execution_elements;
 use salamandra_server::lib::db::workout_executions_db::{delete_workout_execution, insert_workout_execution};
-use salamandra_server::lib::models::wk_execution_elements_models::NewWkExecutionElement;
+use salamandra_server::lib::models::wk_execution_elements_models::{NewWkExecutionElement, WkExecutionElement};
 use salamandra_server::lib::models::workout_execution_models::{NewWorkoutExecution, WkExecutionWithElements, WorkoutExecution};
-use salamandra_server::lib::utils::handlers::{build_resp, extract_sub};
+use salamandra_server::lib::models::notification_models::{NewNotification, PR_ACHIEVED};
+use salamandra_server::lib::models::unit_models::{InUnits, Units};
+use salamandra_server::lib::utils::handlers::{build_resp, extract_sub, notify, request_units};
 use salamandra_server::lib::errors::DBError;
 
 const BASE_ERROR: &str = "Invalid payload. ";
@@ -21,13 +25,19 @@ const DOC_LINK: &str = ". See https://github.com/SalamandraApp/salamandra-server
 
 #[derive(Serialize, Deserialize)]
 struct CreateWkExecutionRequest {
+    #[serde(default)]
+    id: Option<Uuid>,
     workout_template_id: Uuid,
     date: NaiveDate,
     survey: i16,
+    #[serde(default)]
+    program_slot_id: Option<Uuid>,
     elements: Vec<WkExecutionElementRequest>,
 }
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 struct WkExecutionElementRequest {
+    #[serde(default)]
+    id: Option<Uuid>,
     exercise_id: Uuid,
     position: i16,
     exercise_number: i16,
@@ -42,33 +52,56 @@ struct WkExecutionElementRequest {
 impl CreateWkExecutionRequest {
     fn to_new_execution(&self) -> NewWorkoutExecution {
         NewWorkoutExecution {
+            id: self.id,
             workout_template_id: self.workout_template_id,
             survey: self.survey as i16,
             date: self.date,
+            program_slot_id: self.program_slot_id,
         }
     }
 }
 impl WkExecutionElementRequest {
-    fn to_new_element(&self, workout_execution_id: Uuid) -> NewWkExecutionElement {
+    fn to_new_element(&self, workout_execution_id: Uuid, units: &Units) -> NewWkExecutionElement {
         NewWkExecutionElement {
+            id: self.id,
             workout_execution_id,
             exercise_id: self.exercise_id,
             position: self.position,
             exercise_number: self.exercise_number,
             reps: self.reps,
             set_number: self.set_number,
-            weight: self.weight,
+            weight: self.weight.map(|weight| units.weight_to_kg(weight)),
             rest: self.rest,
             super_set: self.super_set,
             time: self.time,
         }    
     }
 }
+impl From<&WkExecutionElement> for WkExecutionElementRequest {
+    fn from(element: &WkExecutionElement) -> Self {
+        WkExecutionElementRequest {
+            id: Some(element.id),
+            exercise_id: element.exercise_id,
+            position: element.position,
+            exercise_number: element.exercise_number,
+            reps: element.reps,
+            set_number: element.set_number,
+            weight: element.weight,
+            rest: element.rest,
+            super_set: element.super_set,
+            time: element.time,
+        }
+    }
+}
 
 
 /// Validate and insert execution
 /// * Assumes path parameters have been checked previously
 /// * Check all payload values
+/// * Weights in the user's units, unless overridden with `units`
+/// * Notifies the user if they beat any of their PRs
+/// * `program_slot_id` links the execution to a slot of one of the user's programs, on the same template
+/// * Ids can be given for the execution and its elements, so ones created offline keep theirs
 pub async fn create_workout_execution(event: Request, connector: &DBConnector) -> Result<Response<Body>, Error> {
    
     // Get path parameter
@@ -80,6 +113,11 @@ pub async fn create_workout_execution(event: Request, connector: &DBConnector) -
         Err(resp) => return Ok(resp)
     };
 
+    let units = match request_units(&event, user_id, connector).await {
+        Ok(units) => units,
+        Err(resp) => return Ok(resp)
+    };
+
     // Check and extract payload
     let body = match event.into_body() {
         Body::Text(body) => body,
@@ -102,8 +140,9 @@ pub async fn create_workout_execution(event: Request, connector: &DBConnector) -
     }
 
     // Validate exercise ids before inserting
+    // * Retired exercises are allowed, they may still be in the user's templates
     let exercise_ids: HashSet<Uuid> = req.elements.iter().map(|element| element.exercise_id).collect();
-    match validate_exercises(exercise_ids.into_iter().collect(), connector).await {
+    match validate_exercises(exercise_ids.into_iter().collect(), user_id, true, connector).await {
         Ok(valid) => {
             if !valid {
                 return Ok(build_resp(StatusCode::NOT_FOUND, "One or more exercise IDs do not reference existing exercises"));
@@ -115,16 +154,28 @@ pub async fn create_workout_execution(event: Request, connector: &DBConnector) -
         }
     }
 
+    // The slot must be of a program of the user and planned on the executed template
+    if let Some(program_slot_id) = req.program_slot_id {
+        match lookup_program_slot_validated(program_slot_id, user_id, connector).await {
+            Ok(slot) if slot.workout_template_id != req.workout_template_id => {
+                return Ok(build_resp(StatusCode::BAD_REQUEST, "Invalid payload: the program slot is planned on another template"))
+            },
+            Ok(_) => (),
+            Err(DBError::ItemNotFound(mes)) => return Ok(build_resp(StatusCode::NOT_FOUND, mes)),
+            Err(error) => {
+                error!("INTERNAL SERVER ERROR: {}", error);
+                return Ok(build_resp(StatusCode::INTERNAL_SERVER_ERROR, ""))
+            }
+        }
+    }
+
     // Construct and insert execution without elements
     let new_workout_execution = req.to_new_execution();
 
     let workout_execution: WorkoutExecution = match insert_workout_execution(&new_workout_execution, connector).await {
         Ok(execution) => execution,
-        Err(DBError::UniqueViolation(mes)) => {
-            // should never trigger since the primary key is only the UUID
-            warn!("Tried to insert already exisiting workout-execution");
-            return Ok(build_resp(StatusCode::CONFLICT, mes))
-        }
+        // Only with ids given by the client, most likely a retry of one created offline
+        Err(DBError::UniqueViolation(mes)) => return Ok(build_resp(StatusCode::CONFLICT, mes)),
         Err(mes) => {
             error!("INTERNAL SERVER ERROR: {}", mes);
             return Ok(build_resp(StatusCode::INTERNAL_SERVER_ERROR, ""))
@@ -134,22 +185,37 @@ pub async fn create_workout_execution(event: Request, connector: &DBConnector) -
     // Create execution elements
     let new_elements: Vec<NewWkExecutionElement> = req.elements
         .iter()
-        .map(|elem| elem.to_new_element(workout_execution.id))
+        .map(|elem| elem.to_new_element(workout_execution.id, &units))
         .collect();
 
     // Insert template elements
     match insert_batch_wk_execution_elements(&new_elements, connector).await {
         Ok(elements) => {
+            // Let the user know about new PRs, the execution is saved whatever happens here
+            match count_prs(&[workout_execution.id], connector).await {
+                Ok(prs) if prs.contains_key(&workout_execution.id) => {
+                    notify(NewNotification { user_id, kind: PR_ACHIEVED, workout_execution_id: Some(workout_execution.id), ..Default::default() }, connector).await;
+                },
+                Ok(_) => (),
+                Err(error) => warn!("Could not count PRs of the new execution: {}", error),
+            }
             let response = WkExecutionWithElements {
                 workout_execution,
                 elements,
+                reactions: Vec::new(),
             };
-            Ok(build_resp(StatusCode::CREATED, response))
+            Ok(build_resp(StatusCode::CREATED, response.in_units(&units)))
         },
         Err(DBError::ConnectionError(mes)) => {
             error!("INTERNAL SERVER ERROR: {}", mes);
             Ok(build_resp(StatusCode::INTERNAL_SERVER_ERROR, ""))
                         }
+        Err(DBError::UniqueViolation(mes)) => {
+            if delete_workout_execution(workout_execution.id, connector).await.is_err() {
+                warn!("Could not delete workout-execution triggered by error inserting templates");
+            }
+            Ok(build_resp(StatusCode::CONFLICT, mes))
+        }
         Err(error) => {
             // Should never trigger because all values are checked before hand
             warn!("Could not insert workout-execution element: {}", error);
@@ -162,6 +228,12 @@ pub async fn create_workout_execution(event: Request, connector: &DBConnector) -
     }
 }
 
+/// Same checks on the sets logged during a session, once it is finished
+pub fn validate_execution_elements(elements: &[WkExecutionElement]) -> Result<(), String> {
+    let items: Vec<WkExecutionElementRequest> = elements.iter().map(WkExecutionElementRequest::from).collect();
+    validate_execution(&items)
+}
+
 /// Check format
 fn validate_execution(items: &[WkExecutionElementRequest]) -> Result<(), String> {
     if items.is_empty() {
@@ -173,6 +245,12 @@ fn validate_execution(items: &[WkExecutionElementRequest]) -> Result<(), String>
         return Err(format!("{}All reps and time values must be at least 1. No values can't be negative{}", BASE_ERROR, DOC_LINK));
     }
 
+    // Ids given by the client
+    let ids: Vec<Uuid> = items.iter().filter_map(|item| item.id).collect();
+    if ids.iter().collect::<HashSet<&Uuid>>().len() != ids.len() {
+        return Err(format!("{}The element's ids must be unique{}", BASE_ERROR, DOC_LINK));
+    }
+
     // POSITION
     let mut positions: Vec<i16> = items.iter().map(|item| item.position).collect();
     positions.sort_unstable();
@@ -271,10 +349,16 @@ mod tests {
     use serde_json::to_string;
     use lambda_http::http::header::{AUTHORIZATION, HeaderValue};
     use salamandra_server::lib::{db::workout_templates_db::lookup_workout_template, utils::tests::{insert_helper, pg_container, test_jwt, Items}};
+    use salamandra_server::lib::db::programs_db::insert_program;
+    use salamandra_server::lib::db::workout_templates_db::insert_workout_template;
+    use salamandra_server::lib::models::program_models::{NewProgram, ProgramSlotRequest};
+    use salamandra_server::lib::models::workout_templates_models::NewWorkoutTemplate;
 
     // TEST CASES
     // * Create a template
+    // * Ids given by the client, created again
     // * Invalid ids
+    // * Linked to a program slot
     // * Invalid payload
     //      * Set number
     //      * Exercise number (and negative values)
@@ -286,6 +370,7 @@ mod tests {
         let user_id = lookup_workout_template(workout_template_id, connector).await.unwrap().user_id;
         
         let base_element = WkExecutionElementRequest {
+            id: None,
             exercise_id,
             position: 0,
             set_number: 0,
@@ -305,9 +390,11 @@ mod tests {
         };
 
         let execution = CreateWkExecutionRequest {
+            id: None,
             workout_template_id,
             date: chrono::Utc::now().date_naive(),
             survey: 0,
+            program_slot_id: None,
             elements,
         };
         (user_id, execution)
@@ -366,6 +453,43 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_create_workout_execution_client_ids() {
+        let (connector, _container) = pg_container().await;
+        let (user_id, mut payload) = setup_execution(&connector, 2).await;
+        payload.elements[1].set_number = 1;
+        let execution_id = Uuid::new_v4();
+        let element_id = Uuid::new_v4();
+        payload.id = Some(execution_id);
+        payload.elements[0].id = Some(element_id);
+        let create_request = |payload: &CreateWkExecutionRequest| {
+            let mut req = Request::new(Body::from(to_string(payload).unwrap()));
+            req.headers_mut().insert(AUTHORIZATION, HeaderValue::from_str(&test_jwt(user_id)).unwrap());
+            req.with_path_parameters(HashMap::from([("user_id".to_string(), user_id.to_string())]))
+        };
+
+        payload.elements[1].id = Some(element_id);
+        let response = create_workout_execution(create_request(&payload), &connector).await.unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        payload.elements[1].id = None;
+
+        let response = create_workout_execution(create_request(&payload), &connector).await.unwrap();
+        assert_eq!(response.status(), StatusCode::CREATED);
+        if let Body::Text(body) = response.into_body() {
+            let execution: WkExecutionWithElements = serde_json::from_str(&body).unwrap();
+            assert_eq!(execution.workout_execution.id, execution_id);
+            assert!(execution.elements.iter().any(|element| element.id == element_id));
+        } else {
+            panic!("Expected a text body");
+        }
+
+        let response = create_workout_execution(create_request(&payload), &connector).await.unwrap();
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+        payload.id = None;
+        let response = create_workout_execution(create_request(&payload), &connector).await.unwrap();
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+    }
+
     #[tokio::test]
     async fn test_create_workout_execution_invalid_ids() {
         let (connector, _container) = pg_container().await;
@@ -388,6 +512,43 @@ mod tests {
         assert_eq!(response.status(), StatusCode::NOT_FOUND);
     }
 
+    #[tokio::test]
+    async fn test_create_workout_execution_program_slot() {
+        let (connector, _container) = pg_container().await;
+        let (user_id, mut payload) = setup_execution(&connector, 1).await;
+        let other_template = insert_workout_template(&NewWorkoutTemplate { user_id, ..Default::default() }, &connector).await.unwrap().id;
+        let slots = [
+            ProgramSlotRequest { workout_template_id: payload.workout_template_id, week: 1, day: 1, reps: None, intensity: None },
+            ProgramSlotRequest { workout_template_id: other_template, week: 1, day: 2, reps: None, intensity: None },
+        ];
+        let new_program = NewProgram { user_id, name: "Block".to_string(), description: None, weeks: 1 };
+        let program = insert_program(&new_program, &slots, &connector).await.unwrap();
+
+        let request = |payload: &CreateWkExecutionRequest| {
+            let mut req = Request::default();
+            req.headers_mut().insert(AUTHORIZATION, HeaderValue::from_str(&test_jwt(user_id)).unwrap());
+            *req.body_mut() = Body::from(to_string(payload).expect("Error"));
+            req.with_path_parameters(HashMap::from([("user_id".to_string(), user_id.to_string())]))
+        };
+
+        payload.program_slot_id = Some(Uuid::new_v4());
+        let response = create_workout_execution(request(&payload), &connector).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        payload.program_slot_id = Some(program.slots[1].id);
+        let response = create_workout_execution(request(&payload), &connector).await.unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        payload.program_slot_id = Some(program.slots[0].id);
+        let response = create_workout_execution(request(&payload), &connector).await.unwrap();
+        assert_eq!(response.status(), StatusCode::CREATED);
+        if let Body::Text(body) = response.into_body() {
+            let execution: WkExecutionWithElements = serde_json::from_str(&body).unwrap();
+            assert_eq!(execution.workout_execution.program_slot_id, Some(program.slots[0].id));
+        } else {
+            panic!("Expected a text body");
+        }
+    }
+
     #[tokio::test]
     async fn test_create_workout_execution_invalid_set_number() {
         let (connector, _container) = pg_container().await; 
@@ -0,0 +1,91 @@
+use lambda_http::{Error, Request, Response, Body, RequestExt};
+use lambda_http::http::StatusCode;
+use tracing::error;
+use uuid::Uuid;
+
+use salamandra_server::lib::db::comments_db::delete_comment as remove_comment;
+use salamandra_server::lib::utils::handlers::{build_resp, check_execution_visible, extract_sub};
+use salamandra_server::lib::db::DBConnector;
+
+/// Delete a comment of a workout execution
+/// * Authors can delete their own comments, the execution owner any of them
+pub async fn delete_comment(event: Request, connector: &DBConnector) -> Result<Response<Body>, Error> {
+
+    // Get path parameters
+    let user_id = Uuid::parse_str(event.path_parameters().first("user_id").unwrap()).unwrap();
+    let execution_id = Uuid::parse_str(event.path_parameters().first("workout_execution_id").unwrap()).unwrap();
+    let comment_id = Uuid::parse_str(event.path_parameters().first("comment_id").unwrap()).unwrap();
+
+    // Any authenticated user, moderation rights are checked below
+    let viewer_id = match extract_sub(event.headers(), None) {
+        Ok(sub) => sub,
+        Err(resp) => return Ok(resp)
+    };
+
+    if let Err(resp) = check_execution_visible(viewer_id, user_id, execution_id, connector).await {
+        return Ok(resp);
+    }
+
+    let author_id = (viewer_id != user_id).then_some(viewer_id);
+    match remove_comment(comment_id, execution_id, author_id, connector).await {
+        Ok(0) => Ok(build_resp(StatusCode::NOT_FOUND, "")),
+        Ok(_) => Ok(build_resp(StatusCode::NO_CONTENT, "")),
+        Err(error) => {
+            error!("INTERNAL SERVER ERROR: {}", error);
+            Ok(build_resp(StatusCode::INTERNAL_SERVER_ERROR, ""))
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use lambda_http::http::header::{AUTHORIZATION, HeaderValue};
+    use salamandra_server::lib::utils::tests::{pg_container, insert_helper, test_jwt, Items};
+    use salamandra_server::lib::db::comments_db::insert_comment;
+    use salamandra_server::lib::db::workout_executions_db::lookup_workout_execution;
+    use salamandra_server::lib::db::workout_templates_db::lookup_workout_template;
+    use salamandra_server::lib::models::comment_models::NewComment;
+
+    // TEST CASES
+    // * Someone else's comment
+    // * Author deletes, then not found
+    // * Owner moderates
+
+    fn delete_request(user_id: Uuid, execution_id: Uuid, comment_id: Uuid, sub: Uuid) -> Request {
+        let mut req = Request::default();
+        req.headers_mut().insert(AUTHORIZATION, HeaderValue::from_str(&test_jwt(sub)).unwrap());
+        req.with_path_parameters(HashMap::from([
+            ("user_id".to_string(), user_id.to_string()),
+            ("workout_execution_id".to_string(), execution_id.to_string()),
+            ("comment_id".to_string(), comment_id.to_string()),
+        ]))
+    }
+
+    #[tokio::test]
+    async fn test_delete_comment() {
+        let (connector, _container) = pg_container().await;
+        let users = insert_helper(2, Items::Users, &connector, None).await;
+        let execution_id = insert_helper(1, Items::WkExecutions, &connector, None).await[0];
+        let execution = lookup_workout_execution(execution_id, &connector).await.unwrap();
+        let owner = lookup_workout_template(execution.workout_template_id, &connector).await.unwrap().user_id;
+        let mut comments = Vec::new();
+        for _ in 0..2 {
+            let new_comment = NewComment { workout_execution_id: execution_id, user_id: users[0], content: "Nice".to_string() };
+            comments.push(insert_comment(&new_comment, &connector).await.unwrap().id);
+        }
+
+        let response = delete_comment(delete_request(owner, execution_id, comments[0], users[1]), &connector).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+        let response = delete_comment(delete_request(owner, execution_id, comments[0], users[0]), &connector).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+        let response = delete_comment(delete_request(owner, execution_id, comments[0], users[0]), &connector).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+        let response = delete_comment(delete_request(owner, execution_id, comments[1], owner), &connector).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+    }
+}
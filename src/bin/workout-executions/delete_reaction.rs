@@ -0,0 +1,75 @@
+use lambda_http::{Error, Request, Response, Body, RequestExt};
+use lambda_http::http::StatusCode;
+use tracing::error;
+use uuid::Uuid;
+
+use salamandra_server::lib::db::reactions_db::delete_reaction as remove_reaction;
+use salamandra_server::lib::utils::handlers::{build_resp, check_execution_visible, extract_sub};
+use salamandra_server::lib::db::DBConnector;
+
+/// Remove the caller's reaction to a workout execution
+pub async fn delete_reaction(event: Request, connector: &DBConnector) -> Result<Response<Body>, Error> {
+
+    // Get path parameters
+    let user_id = Uuid::parse_str(event.path_parameters().first("user_id").unwrap()).unwrap();
+    let execution_id = Uuid::parse_str(event.path_parameters().first("workout_execution_id").unwrap()).unwrap();
+
+    // Any authenticated user, visibility is checked below
+    let viewer_id = match extract_sub(event.headers(), None) {
+        Ok(sub) => sub,
+        Err(resp) => return Ok(resp)
+    };
+
+    if let Err(resp) = check_execution_visible(viewer_id, user_id, execution_id, connector).await {
+        return Ok(resp);
+    }
+
+    match remove_reaction(execution_id, viewer_id, connector).await {
+        Ok(0) => Ok(build_resp(StatusCode::NOT_FOUND, "")),
+        Ok(_) => Ok(build_resp(StatusCode::NO_CONTENT, "")),
+        Err(error) => {
+            error!("INTERNAL SERVER ERROR: {}", error);
+            Ok(build_resp(StatusCode::INTERNAL_SERVER_ERROR, ""))
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use lambda_http::http::header::{AUTHORIZATION, HeaderValue};
+    use salamandra_server::lib::utils::tests::{pg_container, insert_helper, test_jwt, Items};
+    use salamandra_server::lib::db::reactions_db::upsert_reaction;
+    use salamandra_server::lib::db::workout_executions_db::lookup_workout_execution;
+    use salamandra_server::lib::db::workout_templates_db::lookup_workout_template;
+    use salamandra_server::lib::models::reaction_models::{NewReaction, LIKE};
+
+    // TEST CASES
+    // * Success, then not found
+
+    fn delete_request(user_id: Uuid, execution_id: Uuid, sub: Uuid) -> Request {
+        let mut req = Request::default();
+        req.headers_mut().insert(AUTHORIZATION, HeaderValue::from_str(&test_jwt(sub)).unwrap());
+        req.with_path_parameters(HashMap::from([
+            ("user_id".to_string(), user_id.to_string()),
+            ("workout_execution_id".to_string(), execution_id.to_string()),
+        ]))
+    }
+
+    #[tokio::test]
+    async fn test_delete_reaction() {
+        let (connector, _container) = pg_container().await;
+        let viewer = insert_helper(1, Items::Users, &connector, None).await[0];
+        let execution_id = insert_helper(1, Items::WkExecutions, &connector, None).await[0];
+        let execution = lookup_workout_execution(execution_id, &connector).await.unwrap();
+        let owner = lookup_workout_template(execution.workout_template_id, &connector).await.unwrap().user_id;
+        upsert_reaction(&NewReaction { workout_execution_id: execution_id, user_id: viewer, reaction: LIKE }, &connector).await.unwrap();
+
+        let response = delete_reaction(delete_request(owner, execution_id, viewer), &connector).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+        let response = delete_reaction(delete_request(owner, execution_id, viewer), &connector).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+}
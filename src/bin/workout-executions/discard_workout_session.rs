@@ -0,0 +1,81 @@
+use lambda_http::{Error, Request, Response, Body, RequestExt};
+use lambda_http::http::StatusCode;
+use tracing::error;
+use uuid::Uuid;
+
+use salamandra_server::lib::db::workout_sessions_db::delete_workout_session;
+use salamandra_server::lib::utils::handlers::{build_resp, extract_sub};
+use salamandra_server::lib::db::DBConnector;
+
+/// Throw away a workout in progress, or one that was abandoned, with its sets
+/// * Completed executions stay, they aren't sessions anymore
+pub async fn discard_workout_session(event: Request, connector: &DBConnector) -> Result<Response<Body>, Error> {
+
+    // Get path parameters
+    let user_id = Uuid::parse_str(event.path_parameters().first("user_id").unwrap()).unwrap();
+    let execution_id = Uuid::parse_str(event.path_parameters().first("workout_execution_id").unwrap()).unwrap();
+
+    // Check user in claim
+    match extract_sub(event.headers(), Some(user_id)) {
+        Ok(_) => (),
+        Err(resp) => return Ok(resp)
+    };
+
+    match delete_workout_session(execution_id, user_id, connector).await {
+        Ok(0) => Ok(build_resp(StatusCode::NOT_FOUND, "No workout in progress with the corresponding id")),
+        Ok(_) => Ok(build_resp(StatusCode::NO_CONTENT, "")),
+        Err(error) => {
+            error!("INTERNAL SERVER ERROR: {}", error);
+            Ok(build_resp(StatusCode::INTERNAL_SERVER_ERROR, ""))
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use chrono::Utc;
+    use lambda_http::http::header::{AUTHORIZATION, HeaderValue};
+    use salamandra_server::lib::utils::tests::{pg_container, insert_helper, test_jwt, Items};
+    use salamandra_server::lib::db::workout_executions_db::lookup_workout_execution;
+    use salamandra_server::lib::db::workout_sessions_db::insert_workout_session;
+    use salamandra_server::lib::db::workout_templates_db::lookup_workout_template;
+    use salamandra_server::lib::models::workout_execution_models::NewWorkoutSession;
+
+    // TEST CASES
+    // * Completed execution
+    // * Session of another user
+    // * Success
+
+    fn discard_request(user_id: Uuid, execution_id: Uuid) -> Request {
+        let mut req = Request::default();
+        req.headers_mut().insert(AUTHORIZATION, HeaderValue::from_str(&test_jwt(user_id)).unwrap());
+        req.with_path_parameters(HashMap::from([
+            ("user_id".to_string(), user_id.to_string()),
+            ("workout_execution_id".to_string(), execution_id.to_string()),
+        ]))
+    }
+
+    #[tokio::test]
+    async fn test_discard_workout_session() {
+        let (connector, _container) = pg_container().await;
+        let completed_id = insert_helper(1, Items::WkExecutions, &connector, None).await[0];
+        let template_id = lookup_workout_execution(completed_id, &connector).await.unwrap().workout_template_id;
+        let user_id = lookup_workout_template(template_id, &connector).await.unwrap().user_id;
+        let stranger = insert_helper(1, Items::Users, &connector, None).await[0];
+        let now = Utc::now().naive_utc();
+        let session_id = insert_workout_session(&NewWorkoutSession::new(template_id, now.date(), None, now), user_id, &connector).await.unwrap().id;
+
+        let response = discard_workout_session(discard_request(user_id, completed_id), &connector).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        let response = discard_workout_session(discard_request(stranger, session_id), &connector).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+        let response = discard_workout_session(discard_request(user_id, session_id), &connector).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+        assert!(lookup_workout_execution(session_id, &connector).await.is_err());
+        assert!(lookup_workout_execution(completed_id, &connector).await.is_ok());
+    }
+}
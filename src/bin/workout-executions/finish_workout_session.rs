@@ -0,0 +1,162 @@
+use lambda_http::{Error, Request, Response, Body, RequestExt};
+use lambda_http::http::StatusCode;
+use serde::Deserialize;
+use tracing::{error, warn};
+use uuid::Uuid;
+
+use salamandra_server::lib::db::feed_db::count_prs;
+use salamandra_server::lib::db::workout_sessions_db::{complete_workout_session, select_workout_session};
+use salamandra_server::lib::models::notification_models::{NewNotification, PR_ACHIEVED};
+use salamandra_server::lib::models::workout_execution_models::{WkExecutionWithElements, WorkoutExecution, COMPLETED};
+use salamandra_server::lib::models::unit_models::InUnits;
+use salamandra_server::lib::utils::handlers::{build_resp, extract_sub, notify, request_units};
+use salamandra_server::lib::db::DBConnector;
+use salamandra_server::lib::errors::DBError;
+
+use crate::create_workout_execution::validate_execution_elements;
+
+#[derive(Deserialize)]
+struct FinishSessionRequest {
+    survey: i16,
+}
+
+/// Finish a workout in progress, it becomes a regular execution
+/// * The logged sets go through the same checks as a workout created at once
+/// * Weights in the user's units, unless overridden with `units`
+/// * Notifies the user if they beat any of their PRs
+pub async fn finish_workout_session(event: Request, connector: &DBConnector) -> Result<Response<Body>, Error> {
+
+    // Get path parameters
+    let user_id = Uuid::parse_str(event.path_parameters().first("user_id").unwrap()).unwrap();
+    let execution_id = Uuid::parse_str(event.path_parameters().first("workout_execution_id").unwrap()).unwrap();
+
+    // Check user in claim
+    match extract_sub(event.headers(), Some(user_id)) {
+        Ok(_) => (),
+        Err(resp) => return Ok(resp)
+    };
+
+    let units = match request_units(&event, user_id, connector).await {
+        Ok(units) => units,
+        Err(resp) => return Ok(resp)
+    };
+
+    let req: FinishSessionRequest = match event.into_body() {
+        Body::Text(body) => match serde_json::from_str(&body) {
+            Ok(req) => req,
+            Err(error) => return Ok(build_resp(StatusCode::BAD_REQUEST, format!("Invalid payload: {}", error))),
+        },
+        _ => return Ok(build_resp(StatusCode::BAD_REQUEST, "Invalid payload: can't extract body")),
+    };
+    if req.survey < 0 {
+        return Ok(build_resp(StatusCode::BAD_REQUEST, "Invalid payload: survey can't be negative"));
+    }
+
+    let (session, elements) = match select_workout_session(execution_id, user_id, connector).await {
+        Ok(session) => session,
+        Err(DBError::ItemNotFound(mes)) => return Ok(build_resp(StatusCode::NOT_FOUND, mes)),
+        Err(error) => {
+            error!("INTERNAL SERVER ERROR: {}", error);
+            return Ok(build_resp(StatusCode::INTERNAL_SERVER_ERROR, ""))
+        }
+    };
+    if let Err(mes) = validate_execution_elements(&elements) {
+        return Ok(build_resp(StatusCode::BAD_REQUEST, mes));
+    }
+
+    // Sessions always have some activity, they are started with it
+    let last_activity = session.last_activity.unwrap_or_default();
+    match complete_workout_session(execution_id, user_id, req.survey, last_activity, connector).await {
+        Ok(0) => return Ok(build_resp(StatusCode::CONFLICT, "The workout changed while it was being finished, try again")),
+        Ok(_) => (),
+        Err(error) => {
+            error!("INTERNAL SERVER ERROR: {}", error);
+            return Ok(build_resp(StatusCode::INTERNAL_SERVER_ERROR, ""))
+        }
+    }
+
+    // Let the user know about new PRs, the execution is saved whatever happens here
+    match count_prs(&[execution_id], connector).await {
+        Ok(prs) if prs.contains_key(&execution_id) => {
+            notify(NewNotification { user_id, kind: PR_ACHIEVED, workout_execution_id: Some(execution_id), ..Default::default() }, connector).await;
+        },
+        Ok(_) => (),
+        Err(error) => warn!("Could not count PRs of the finished execution: {}", error),
+    }
+
+    let response = WkExecutionWithElements {
+        workout_execution: WorkoutExecution { status: COMPLETED, survey: req.survey, ..session },
+        elements,
+        reactions: Vec::new(),
+    };
+    Ok(build_resp(StatusCode::OK, response.in_units(&units)))
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use chrono::Utc;
+    use lambda_http::http::header::{AUTHORIZATION, HeaderValue};
+    use salamandra_server::lib::utils::tests::{pg_container, insert_helper, test_jwt, Items};
+    use salamandra_server::lib::db::workout_executions_db::lookup_workout_execution;
+    use salamandra_server::lib::db::workout_sessions_db::{insert_workout_session, upsert_session_set};
+    use salamandra_server::lib::db::workout_templates_db::lookup_workout_template;
+    use salamandra_server::lib::models::wk_execution_elements_models::NewWkExecutionElement;
+    use salamandra_server::lib::models::workout_execution_models::NewWorkoutSession;
+
+    // TEST CASES
+    // * Session of another user
+    // * Invalid logged sets
+    // * Success, then it's no longer in progress
+
+    fn finish_request(user_id: Uuid, execution_id: Uuid) -> Request {
+        let mut req = Request::new(Body::Text(r#"{"survey": 4}"#.to_string()));
+        req.headers_mut().insert(AUTHORIZATION, HeaderValue::from_str(&test_jwt(user_id)).unwrap());
+        req.with_path_parameters(HashMap::from([
+            ("user_id".to_string(), user_id.to_string()),
+            ("workout_execution_id".to_string(), execution_id.to_string()),
+        ]))
+    }
+
+    #[tokio::test]
+    async fn test_finish_workout_session() {
+        let (connector, _container) = pg_container().await;
+        let template_id = insert_helper(1, Items::WkTemplates, &connector, None).await[0];
+        let user_id = lookup_workout_template(template_id, &connector).await.unwrap().user_id;
+        let stranger = insert_helper(1, Items::Users, &connector, None).await[0];
+        let exercise_id = insert_helper(1, Items::Exercises, &connector, None).await[0];
+        let now = Utc::now().naive_utc();
+        let session_id = insert_workout_session(&NewWorkoutSession::new(template_id, now.date(), None, now), user_id, &connector).await.unwrap().id;
+
+        let response = finish_workout_session(finish_request(stranger, session_id), &connector).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+        // Nothing logged yet, then the second set skipping one
+        let response = finish_workout_session(finish_request(user_id, session_id), &connector).await.unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        for (position, set_number) in [(0, 0), (1, 2)] {
+            let new_set = NewWkExecutionElement { workout_execution_id: session_id, exercise_id, position, set_number, reps: 5, time: 30, ..Default::default() };
+            upsert_session_set(&new_set, user_id, Utc::now().naive_utc(), &connector).await.unwrap();
+        }
+        let response = finish_workout_session(finish_request(user_id, session_id), &connector).await.unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        let fixed = NewWkExecutionElement { workout_execution_id: session_id, exercise_id, position: 1, set_number: 1, reps: 5, time: 30, ..Default::default() };
+        upsert_session_set(&fixed, user_id, Utc::now().naive_utc(), &connector).await.unwrap();
+        let response = finish_workout_session(finish_request(user_id, session_id), &connector).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        if let Body::Text(body) = response.into_body() {
+            let execution: WkExecutionWithElements = serde_json::from_str(&body).unwrap();
+            assert_eq!((execution.workout_execution.status, execution.workout_execution.survey), (COMPLETED, 4));
+            assert_eq!(execution.elements.len(), 2);
+        } else {
+            panic!("Expected a text body");
+        }
+        assert_eq!(lookup_workout_execution(session_id, &connector).await.unwrap().status, COMPLETED);
+
+        let response = finish_workout_session(finish_request(user_id, session_id), &connector).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+}
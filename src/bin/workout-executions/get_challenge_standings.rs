@@ -0,0 +1,146 @@
+use chrono::Utc;
+use lambda_http::{Error, Request, Response, Body, RequestExt};
+use lambda_http::http::StatusCode;
+use tracing::error;
+use uuid::Uuid;
+
+use salamandra_server::lib::db::challenges_db::{lookup_challenge_validated, select_challenge_progress};
+use salamandra_server::lib::models::challenge_models::ChallengeStandings;
+use salamandra_server::lib::models::unit_models::InUnits;
+use salamandra_server::lib::utils::handlers::{build_resp, extract_sub, request_units};
+use salamandra_server::lib::db::DBConnector;
+use salamandra_server::lib::errors::DBError;
+
+/// Current standings of a challenge the caller was invited to
+/// * Volume in the caller's units, unless overridden with `units`
+pub async fn get_challenge_standings(event: Request, connector: &DBConnector) -> Result<Response<Body>, Error> {
+    standings(event, false, connector).await
+}
+
+/// Final results of a challenge the caller was invited to, once it has ended
+/// * Volume in the caller's units, unless overridden with `units`
+pub async fn get_challenge_results(event: Request, connector: &DBConnector) -> Result<Response<Body>, Error> {
+    standings(event, true, connector).await
+}
+
+async fn standings(event: Request, final_results: bool, connector: &DBConnector) -> Result<Response<Body>, Error> {
+
+    // Get path parameters
+    let user_id = Uuid::parse_str(event.path_parameters().first("user_id").unwrap()).unwrap();
+    let challenge_id = Uuid::parse_str(event.path_parameters().first("challenge_id").unwrap()).unwrap();
+
+    // Confirm user making call owns resource
+    match extract_sub(event.headers(), Some(user_id)) {
+        Ok(_) => (),
+        Err(resp) => return Ok(resp)
+    };
+
+    let units = match request_units(&event, user_id, connector).await {
+        Ok(units) => units,
+        Err(resp) => return Ok(resp)
+    };
+
+    let challenge = match lookup_challenge_validated(challenge_id, user_id, connector).await {
+        Ok(challenge) => challenge,
+        Err(DBError::ItemNotFound(mes)) => return Ok(build_resp(StatusCode::NOT_FOUND, mes)),
+        Err(error) => {
+            error!("INTERNAL SERVER ERROR: {}", error);
+            return Ok(build_resp(StatusCode::INTERNAL_SERVER_ERROR, ""))
+        }
+    };
+    let finished = challenge.has_ended(Utc::now().naive_utc().date());
+    if final_results && !finished {
+        return Ok(build_resp(StatusCode::CONFLICT, "The challenge hasn't ended yet"));
+    }
+
+    match select_challenge_progress(&challenge, connector).await {
+        Ok(progress) => Ok(build_resp(StatusCode::OK, ChallengeStandings::rank(challenge, finished, progress).in_units(&units))),
+        Err(error) => {
+            error!("INTERNAL SERVER ERROR: {}", error);
+            Ok(build_resp(StatusCode::INTERNAL_SERVER_ERROR, ""))
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use chrono::{Duration, NaiveDate};
+    use lambda_http::http::header::{AUTHORIZATION, HeaderValue};
+    use salamandra_server::lib::utils::tests::{pg_container, insert_helper, test_jwt, Items};
+    use salamandra_server::lib::db::challenges_db::{insert_challenge, update_participation};
+    use salamandra_server::lib::db::workout_executions_db::insert_workout_execution;
+    use salamandra_server::lib::db::workout_templates_db::insert_workout_template;
+    use salamandra_server::lib::models::challenge_models::{NewChallenge, WORKOUTS};
+    use salamandra_server::lib::models::workout_execution_models::NewWorkoutExecution;
+    use salamandra_server::lib::models::workout_templates_models::NewWorkoutTemplate;
+
+    // TEST CASES
+    // * Not invited
+    // * Standings of an ongoing challenge, results not available yet
+    // * Final results
+
+    fn standings_request(user_id: Uuid, challenge_id: Uuid) -> Request {
+        let mut req = Request::default();
+        req.headers_mut().insert(AUTHORIZATION, HeaderValue::from_str(&test_jwt(user_id)).unwrap());
+        req.with_path_parameters(HashMap::from([
+            ("user_id".to_string(), user_id.to_string()),
+            ("challenge_id".to_string(), challenge_id.to_string()),
+        ]))
+    }
+
+    fn standings_from(response: Response<Body>) -> ChallengeStandings {
+        match response.into_body() {
+            Body::Text(body) => serde_json::from_str(&body).unwrap(),
+            _ => panic!("Expected a text body"),
+        }
+    }
+
+    async fn workout_on(user_id: Uuid, date: NaiveDate, connector: &DBConnector) {
+        let template = insert_workout_template(&NewWorkoutTemplate { user_id, ..Default::default() }, connector).await.unwrap();
+        insert_workout_execution(&NewWorkoutExecution { workout_template_id: template.id, date, ..Default::default() }, connector).await.unwrap();
+    }
+
+    async fn challenge_between(users: &[Uuid], start_date: NaiveDate, end_date: NaiveDate, connector: &DBConnector) -> Uuid {
+        let new_challenge = NewChallenge { creator_id: users[0], name: "Week".to_string(), metric: WORKOUTS, exercise_id: None, start_date, end_date };
+        let challenge = insert_challenge(&new_challenge, &users[1..], connector).await.unwrap().challenge;
+        update_participation(challenge.id, users[1], true, connector).await.unwrap();
+        challenge.id
+    }
+
+    #[tokio::test]
+    async fn test_get_challenge_standings() {
+        let (connector, _container) = pg_container().await;
+        let users = insert_helper(3, Items::Users, &connector, None).await;
+        let today = Utc::now().naive_utc().date();
+        let challenge_id = challenge_between(&users[..2], today - Duration::days(3), today + Duration::days(3), &connector).await;
+        workout_on(users[1], today, &connector).await;
+
+        let response = get_challenge_standings(standings_request(users[2], challenge_id), &connector).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        let response = get_challenge_results(standings_request(users[0], challenge_id), &connector).await.unwrap();
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+
+        let standings = standings_from(get_challenge_standings(standings_request(users[0], challenge_id), &connector).await.unwrap());
+        assert!(!standings.finished);
+        let ranks: Vec<(i64, Uuid, f32)> = standings.standings.iter().map(|entry| (entry.rank, entry.user.id, entry.score)).collect();
+        assert_eq!(ranks, vec![(1, users[1], 1.0), (2, users[0], 0.0)]);
+    }
+
+    #[tokio::test]
+    async fn test_get_challenge_results() {
+        let (connector, _container) = pg_container().await;
+        let users = insert_helper(2, Items::Users, &connector, None).await;
+        let today = Utc::now().naive_utc().date();
+        let challenge_id = challenge_between(&users, today - Duration::days(10), today - Duration::days(3), &connector).await;
+        workout_on(users[0], today - Duration::days(5), &connector).await;
+        workout_on(users[1], today, &connector).await;
+
+        let results = standings_from(get_challenge_results(standings_request(users[1], challenge_id), &connector).await.unwrap());
+        assert!(results.finished);
+        assert_eq!(results.standings[0].user.id, users[0]);
+        assert_eq!(results.standings[1].score, 0.0);
+    }
+}
@@ -0,0 +1,80 @@
+use lambda_http::{Error, Request, Response, Body, RequestExt};
+use lambda_http::http::StatusCode;
+use tracing::error;
+use uuid::Uuid;
+
+use salamandra_server::lib::db::challenges_db::select_challenges;
+use salamandra_server::lib::models::challenge_models::ChallengeList;
+use salamandra_server::lib::utils::handlers::{build_resp, extract_sub, pagination};
+use salamandra_server::lib::db::DBConnector;
+
+/// Challenges the caller created or was invited to, latest first
+/// * Optional `limit` and `offset`
+pub async fn get_challenges(event: Request, connector: &DBConnector) -> Result<Response<Body>, Error> {
+
+    // Get path parameter
+    let user_id = Uuid::parse_str(event.path_parameters().first("user_id").unwrap()).unwrap();
+
+    // Confirm user making call owns resource
+    match extract_sub(event.headers(), Some(user_id)) {
+        Ok(_) => (),
+        Err(resp) => return Ok(resp)
+    };
+
+    let (limit, offset) = match pagination(&event) {
+        Ok(page) => page,
+        Err(resp) => return Ok(resp)
+    };
+
+    match select_challenges(user_id, limit + 1, offset, connector).await {
+        Ok(challenges) => Ok(build_resp(StatusCode::OK, ChallengeList::page(challenges, limit, offset))),
+        Err(error) => {
+            error!("INTERNAL SERVER ERROR: {}", error);
+            Ok(build_resp(StatusCode::INTERNAL_SERVER_ERROR, ""))
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use chrono::NaiveDate;
+    use lambda_http::http::header::{AUTHORIZATION, HeaderValue};
+    use salamandra_server::lib::utils::tests::{pg_container, insert_helper, test_jwt, Items};
+    use salamandra_server::lib::db::challenges_db::insert_challenge;
+    use salamandra_server::lib::models::challenge_models::{NewChallenge, WORKOUTS};
+
+    // TEST CASES
+    // * Success, paginated
+
+    fn list_request(user_id: Uuid, query: &[(&str, &str)]) -> Request {
+        let mut req = Request::default();
+        req.headers_mut().insert(AUTHORIZATION, HeaderValue::from_str(&test_jwt(user_id)).unwrap());
+        req.with_path_parameters(HashMap::from([("user_id".to_string(), user_id.to_string())]))
+            .with_query_string_parameters(query.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect::<HashMap<String, String>>())
+    }
+
+    #[tokio::test]
+    async fn test_get_challenges() {
+        let (connector, _container) = pg_container().await;
+        let users = insert_helper(2, Items::Users, &connector, None).await;
+        for month in 1..=3 {
+            let date = NaiveDate::from_ymd_opt(2024, month, 1).unwrap();
+            let new_challenge = NewChallenge { creator_id: users[0], name: "Monthly".to_string(), metric: WORKOUTS, exercise_id: None, start_date: date, end_date: date };
+            insert_challenge(&new_challenge, &users[1..], &connector).await.unwrap();
+        }
+
+        let response = get_challenges(list_request(users[1], &[("limit", "2")]), &connector).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        if let Body::Text(body) = response.into_body() {
+            let list: ChallengeList = serde_json::from_str(&body).unwrap();
+            assert_eq!(list.challenges.len(), 2);
+            assert_eq!(list.challenges[0].start_date, NaiveDate::from_ymd_opt(2024, 3, 1).unwrap());
+            assert_eq!(list.next_offset, Some(2));
+        } else {
+            panic!("Expected a text body");
+        }
+    }
+}
@@ -0,0 +1,106 @@
+use lambda_http::{Error, Request, Response, Body, RequestExt};
+use lambda_http::http::StatusCode;
+use tracing::error;
+use uuid::Uuid;
+
+use salamandra_server::lib::db::comments_db::select_comments;
+use salamandra_server::lib::models::comment_models::CommentList;
+use salamandra_server::lib::utils::handlers::{build_resp, check_execution_visible, extract_sub, pagination};
+use salamandra_server::lib::db::DBConnector;
+
+/// Comments of a workout execution the caller can see, oldest first
+/// * Optional `limit` and `offset`
+pub async fn get_comments(event: Request, connector: &DBConnector) -> Result<Response<Body>, Error> {
+
+    // Get path parameters
+    let user_id = Uuid::parse_str(event.path_parameters().first("user_id").unwrap()).unwrap();
+    let execution_id = Uuid::parse_str(event.path_parameters().first("workout_execution_id").unwrap()).unwrap();
+
+    // Any authenticated user, visibility is checked below
+    let viewer_id = match extract_sub(event.headers(), None) {
+        Ok(sub) => sub,
+        Err(resp) => return Ok(resp)
+    };
+
+    let (limit, offset) = match pagination(&event) {
+        Ok(page) => page,
+        Err(resp) => return Ok(resp)
+    };
+
+    if let Err(resp) = check_execution_visible(viewer_id, user_id, execution_id, connector).await {
+        return Ok(resp);
+    }
+
+    match select_comments(execution_id, limit + 1, offset, connector).await {
+        Ok(comments) => Ok(build_resp(StatusCode::OK, CommentList::page(comments, limit, offset))),
+        Err(error) => {
+            error!("INTERNAL SERVER ERROR: {}", error);
+            Ok(build_resp(StatusCode::INTERNAL_SERVER_ERROR, ""))
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use lambda_http::http::header::{AUTHORIZATION, HeaderValue};
+    use salamandra_server::lib::utils::tests::{pg_container, insert_helper, test_jwt, Items};
+    use salamandra_server::lib::db::comments_db::insert_comment;
+    use salamandra_server::lib::db::workout_executions_db::lookup_workout_execution;
+    use salamandra_server::lib::db::workout_templates_db::lookup_workout_template;
+    use salamandra_server::lib::models::comment_models::NewComment;
+
+    // TEST CASES
+    // * Invalid pagination
+    // * Success, paginated
+
+    fn comments_request(user_id: Uuid, execution_id: Uuid, sub: Uuid, query: &[(&str, &str)]) -> Request {
+        let mut req = Request::default();
+        req.headers_mut().insert(AUTHORIZATION, HeaderValue::from_str(&test_jwt(sub)).unwrap());
+        req.with_path_parameters(HashMap::from([
+            ("user_id".to_string(), user_id.to_string()),
+            ("workout_execution_id".to_string(), execution_id.to_string()),
+        ]))
+            .with_query_string_parameters(query.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect::<HashMap<String, String>>())
+    }
+
+    fn list_from(response: Response<Body>) -> CommentList {
+        match response.into_body() {
+            Body::Text(body) => serde_json::from_str(&body).unwrap(),
+            _ => panic!("Expected a text body"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_get_comments_invalid_pagination() {
+        let (connector, _container) = pg_container().await;
+        let user_id = insert_helper(1, Items::Users, &connector, None).await[0];
+
+        let response = get_comments(comments_request(user_id, Uuid::new_v4(), user_id, &[("offset", "-1")]), &connector).await.unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_get_comments_success() {
+        let (connector, _container) = pg_container().await;
+        let viewer = insert_helper(1, Items::Users, &connector, None).await[0];
+        let execution_id = insert_helper(1, Items::WkExecutions, &connector, None).await[0];
+        let execution = lookup_workout_execution(execution_id, &connector).await.unwrap();
+        let owner = lookup_workout_template(execution.workout_template_id, &connector).await.unwrap().user_id;
+        for n in 0..3 {
+            let new_comment = NewComment { workout_execution_id: execution_id, user_id: viewer, content: format!("Comment {}", n) };
+            insert_comment(&new_comment, &connector).await.unwrap();
+        }
+
+        let list = list_from(get_comments(comments_request(owner, execution_id, viewer, &[("limit", "2")]), &connector).await.unwrap());
+        assert_eq!(list.comments.len(), 2);
+        assert_eq!(list.comments[0].comment.content, "Comment 0");
+        assert_eq!(list.next_offset, Some(2));
+
+        let list = list_from(get_comments(comments_request(owner, execution_id, viewer, &[("limit", "2"), ("offset", "2")]), &connector).await.unwrap());
+        assert_eq!(list.comments.len(), 1);
+        assert_eq!(list.next_offset, None);
+    }
+}
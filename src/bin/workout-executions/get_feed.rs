@@ -0,0 +1,127 @@
+use lambda_http::{Error, Request, Response, Body, RequestExt};
+use lambda_http::http::StatusCode;
+use tracing::error;
+use uuid::Uuid;
+
+use salamandra_server::lib::db::feed_db::select_feed;
+use salamandra_server::lib::models::feed_models::{Feed, FeedCursor};
+use salamandra_server::lib::models::unit_models::InUnits;
+use salamandra_server::lib::utils::handlers::{build_resp, extract_sub, request_units, DEFAULT_PAGE_SIZE, MAX_PAGE_SIZE};
+use salamandra_server::lib::db::DBConnector;
+
+/// Workout executions of the users the caller follows, newest first
+/// * Optional `limit`, and `before` with the `next_cursor` of the previous page
+/// * Volume in the caller's units, unless overridden with `units`
+pub async fn get_feed(event: Request, connector: &DBConnector) -> Result<Response<Body>, Error> {
+
+    // Get path parameter
+    let user_id = Uuid::parse_str(event.path_parameters().first("user_id").unwrap()).unwrap();
+
+    // Confirm user making call owns resource
+    match extract_sub(event.headers(), Some(user_id)) {
+        Ok(_) => (),
+        Err(resp) => return Ok(resp)
+    };
+
+    // Get query parameters
+    let params = event.query_string_parameters();
+    let limit = match params.first("limit").map(str::parse::<i64>).unwrap_or(Ok(DEFAULT_PAGE_SIZE)) {
+        Ok(limit) if (1..=MAX_PAGE_SIZE).contains(&limit) => limit,
+        _ => return Ok(build_resp(StatusCode::BAD_REQUEST, format!("'limit' must be between 1 and {}", MAX_PAGE_SIZE)))
+    };
+    let before = match params.first("before").map(str::parse::<FeedCursor>).transpose() {
+        Ok(before) => before,
+        Err(mes) => return Ok(build_resp(StatusCode::BAD_REQUEST, mes))
+    };
+
+    let units = match request_units(&event, user_id, connector).await {
+        Ok(units) => units,
+        Err(resp) => return Ok(resp)
+    };
+
+    // Fetch one more than needed to know if there is a next page
+    let mut items = match select_feed(user_id, before, limit + 1, connector).await {
+        Ok(items) => items,
+        Err(error) => {
+            error!("INTERNAL SERVER ERROR: {}", error);
+            return Ok(build_resp(StatusCode::INTERNAL_SERVER_ERROR, ""))
+        }
+    };
+    let next_cursor = match items.len() as i64 > limit {
+        true => {
+            items.truncate(limit as usize);
+            items.last().map(|item| FeedCursor { date: item.workout_execution.date, id: item.workout_execution.id }.to_string())
+        }
+        false => None,
+    };
+    Ok(build_resp(StatusCode::OK, Feed { items: items.in_units(&units), next_cursor }))
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use lambda_http::http::header::{AUTHORIZATION, HeaderValue};
+    use salamandra_server::lib::utils::tests::{pg_container, insert_helper, test_jwt, Items};
+    use salamandra_server::lib::db::follows_db::insert_follow;
+    use salamandra_server::lib::db::workout_templates_db::lookup_workout_template;
+    use salamandra_server::lib::db::workout_executions_db::lookup_workout_execution;
+
+    // TEST CASES
+    // * Other user's feed
+    // * Invalid limit and cursor
+    // * Success, paginated with the cursor
+
+    fn feed_request(user_id: Uuid, sub: Uuid, query: &[(&str, &str)]) -> Request {
+        let mut req = Request::default();
+        req.headers_mut().insert(AUTHORIZATION, HeaderValue::from_str(&test_jwt(sub)).unwrap());
+        req.with_path_parameters(HashMap::from([("user_id".to_string(), user_id.to_string())]))
+            .with_query_string_parameters(query.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect::<HashMap<String, String>>())
+    }
+
+    fn feed_from(response: Response<Body>) -> Feed {
+        match response.into_body() {
+            Body::Text(body) => serde_json::from_str(&body).unwrap(),
+            _ => panic!("Expected a text body"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_get_feed_forbidden() {
+        let (connector, _container) = pg_container().await;
+        let users = insert_helper(2, Items::Users, &connector, None).await;
+
+        let response = get_feed(feed_request(users[0], users[1], &[]), &connector).await.unwrap();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    }
+
+    #[tokio::test]
+    async fn test_get_feed_invalid_params() {
+        let (connector, _container) = pg_container().await;
+        let user_id = insert_helper(1, Items::Users, &connector, None).await[0];
+
+        for query in [[("limit", "0")], [("limit", "500")], [("before", "yesterday")], [("before", "2024-09-01_nope")]] {
+            let response = get_feed(feed_request(user_id, user_id, &query), &connector).await.unwrap();
+            assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_get_feed_success() {
+        let (connector, _container) = pg_container().await;
+        let user_id = insert_helper(1, Items::Users, &connector, None).await[0];
+        let executions = insert_helper(3, Items::WkExecutions, &connector, None).await;
+        let execution = lookup_workout_execution(executions[0], &connector).await.unwrap();
+        let author = lookup_workout_template(execution.workout_template_id, &connector).await.unwrap().user_id;
+        insert_follow(user_id, author, &connector).await.unwrap();
+
+        let feed = feed_from(get_feed(feed_request(user_id, user_id, &[("limit", "2")]), &connector).await.unwrap());
+        assert_eq!(feed.items.len(), 2);
+        let cursor = feed.next_cursor.unwrap();
+
+        let feed = feed_from(get_feed(feed_request(user_id, user_id, &[("limit", "2"), ("before", &cursor)]), &connector).await.unwrap());
+        assert_eq!(feed.items.len(), 1);
+        assert_eq!(feed.next_cursor, None);
+    }
+}
@@ -0,0 +1,150 @@
+use chrono::{NaiveDate, Utc};
+use lambda_http::{Error, Request, Response, Body, RequestExt};
+use lambda_http::http::StatusCode;
+use tracing::error;
+use uuid::Uuid;
+
+use salamandra_server::lib::db::leaderboards_db::select_leaderboard_scores;
+use salamandra_server::lib::models::leaderboard_models::{week_start, Leaderboard, LeaderboardMetric};
+use salamandra_server::lib::models::unit_models::InUnits;
+use salamandra_server::lib::utils::handlers::{build_resp, extract_sub, request_units};
+use salamandra_server::lib::db::DBConnector;
+
+/// Ranking of the caller and the users they follow
+/// * `metric` is one of `one_rep_max`, which needs an `exercise_id`, `weekly_volume` or `weekly_workouts`
+/// * Weekly metrics are for the week of `week`, a date, the current one by default
+/// * `normalize=true` divides weights by each user's body weight
+/// * Weights in the caller's units, unless overridden with `units`
+pub async fn get_leaderboard(event: Request, connector: &DBConnector) -> Result<Response<Body>, Error> {
+
+    // Get path parameter
+    let user_id = Uuid::parse_str(event.path_parameters().first("user_id").unwrap()).unwrap();
+
+    // Confirm user making call owns resource
+    match extract_sub(event.headers(), Some(user_id)) {
+        Ok(_) => (),
+        Err(resp) => return Ok(resp)
+    };
+
+    // Get query parameters
+    let params = event.query_string_parameters();
+    let metric = match params.first("metric").map(str::parse::<LeaderboardMetric>) {
+        Some(Ok(metric)) => metric,
+        Some(Err(mes)) => return Ok(build_resp(StatusCode::BAD_REQUEST, mes)),
+        None => return Ok(build_resp(StatusCode::BAD_REQUEST, "Missing 'metric'")),
+    };
+    let exercise_id = match (metric, params.first("exercise_id").map(Uuid::parse_str)) {
+        (LeaderboardMetric::OneRepMax, Some(Ok(exercise_id))) => Some(exercise_id),
+        (LeaderboardMetric::OneRepMax, _) => return Ok(build_resp(StatusCode::BAD_REQUEST, "'one_rep_max' needs a valid 'exercise_id'")),
+        _ => None,
+    };
+    let week = match params.first("week").map(|date| NaiveDate::parse_from_str(date, "%Y-%m-%d")) {
+        Some(Ok(date)) => week_start(date),
+        Some(Err(_)) => return Ok(build_resp(StatusCode::BAD_REQUEST, "Invalid 'week', expected a date as YYYY-MM-DD")),
+        None => week_start(Utc::now().naive_utc().date()),
+    };
+    let normalized = params.first("normalize") == Some("true");
+    if normalized && !metric.is_weight() {
+        return Ok(build_resp(StatusCode::BAD_REQUEST, "Only weight metrics can be normalized"));
+    }
+
+    let units = match request_units(&event, user_id, connector).await {
+        Ok(units) => units,
+        Err(resp) => return Ok(resp)
+    };
+
+    match select_leaderboard_scores(user_id, metric, exercise_id, week, connector).await {
+        Ok(scores) => {
+            let week = (metric != LeaderboardMetric::OneRepMax).then_some(week);
+            let leaderboard = Leaderboard::rank(metric, exercise_id, week, normalized, scores);
+            Ok(build_resp(StatusCode::OK, leaderboard.in_units(&units)))
+        },
+        Err(error) => {
+            error!("INTERNAL SERVER ERROR: {}", error);
+            Ok(build_resp(StatusCode::INTERNAL_SERVER_ERROR, ""))
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use lambda_http::http::header::{AUTHORIZATION, HeaderValue};
+    use salamandra_server::lib::utils::tests::{pg_container, insert_helper, test_jwt, Items};
+    use salamandra_server::lib::db::follows_db::insert_follow;
+    use salamandra_server::lib::db::users_db::update_user;
+    use salamandra_server::lib::db::wk_execution_elements_db::insert_batch_wk_execution_elements;
+    use salamandra_server::lib::db::workout_templates_db::lookup_workout_template;
+    use salamandra_server::lib::db::workout_executions_db::lookup_workout_execution;
+    use salamandra_server::lib::models::user_models::UncompleteUser;
+    use salamandra_server::lib::models::wk_execution_elements_models::NewWkExecutionElement;
+
+    // TEST CASES
+    // * Other user's leaderboard
+    // * Invalid metric, missing exercise, invalid week, normalized count
+    // * Success, normalized by body weight
+
+    fn leaderboard_request(user_id: Uuid, sub: Uuid, query: &[(&str, &str)]) -> Request {
+        let mut req = Request::default();
+        req.headers_mut().insert(AUTHORIZATION, HeaderValue::from_str(&test_jwt(sub)).unwrap());
+        req.with_path_parameters(HashMap::from([("user_id".to_string(), user_id.to_string())]))
+            .with_query_string_parameters(query.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect::<HashMap<String, String>>())
+    }
+
+    #[tokio::test]
+    async fn test_get_leaderboard_forbidden() {
+        let (connector, _container) = pg_container().await;
+        let users = insert_helper(2, Items::Users, &connector, None).await;
+
+        let response = get_leaderboard(leaderboard_request(users[0], users[1], &[("metric", "weekly_workouts")]), &connector).await.unwrap();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    }
+
+    #[tokio::test]
+    async fn test_get_leaderboard_invalid_params() {
+        let (connector, _container) = pg_container().await;
+        let user_id = insert_helper(1, Items::Users, &connector, None).await[0];
+
+        let queries: [&[(&str, &str)]; 5] = [
+            &[],
+            &[("metric", "fastest")],
+            &[("metric", "one_rep_max")],
+            &[("metric", "weekly_volume"), ("week", "monday")],
+            &[("metric", "weekly_workouts"), ("normalize", "true")],
+        ];
+        for query in queries {
+            let response = get_leaderboard(leaderboard_request(user_id, user_id, query), &connector).await.unwrap();
+            assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_get_leaderboard_success() {
+        let (connector, _container) = pg_container().await;
+        let user_id = insert_helper(1, Items::Users, &connector, None).await[0];
+        let exercise_id = insert_helper(1, Items::Exercises, &connector, None).await[0];
+        let execution_id = insert_helper(1, Items::WkExecutions, &connector, None).await[0];
+        let execution = lookup_workout_execution(execution_id, &connector).await.unwrap();
+        let author = lookup_workout_template(execution.workout_template_id, &connector).await.unwrap().user_id;
+        let new_element = NewWkExecutionElement { workout_execution_id: execution_id, exercise_id, reps: 1, weight: Some(100.0), ..Default::default() };
+        insert_batch_wk_execution_elements(&vec![new_element], &connector).await.unwrap();
+        insert_follow(user_id, author, &connector).await.unwrap();
+        update_user(&author, &UncompleteUser { weight: Some(80.0), ..Default::default() }, &connector).await.unwrap();
+
+        let exercise_id = exercise_id.to_string();
+        let query = [("metric", "one_rep_max"), ("exercise_id", exercise_id.as_str()), ("normalize", "true")];
+        let response = get_leaderboard(leaderboard_request(user_id, user_id, &query), &connector).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        if let Body::Text(body) = response.into_body() {
+            let leaderboard: Leaderboard = serde_json::from_str(&body).unwrap();
+            assert_eq!(leaderboard.week_start, None);
+            assert_eq!(leaderboard.entries.len(), 1);
+            assert_eq!((leaderboard.entries[0].user.id, leaderboard.entries[0].rank), (author, 1));
+            assert_eq!(leaderboard.entries[0].score, 1.25);
+        } else {
+            panic!("Expected a text body");
+        }
+    }
+}
@@ -1,3 +1,4 @@
+use std::collections::HashMap;
 use lambda_http::{Error, Request, Response, Body, RequestExt};
 use lambda_http::http::StatusCode;
 use salamandra_server::lib::models::workout_execution_models::{WkExecutionWithElements, WorkoutExecutionFull};
@@ -6,12 +7,18 @@ use uuid::Uuid;
 
 use salamandra_server::lib::db::workout_executions_db::lookup_workout_execution_validated;
 use salamandra_server::lib::db::wk_execution_elements_db::{select_wk_execution_element_by_execution, select_wk_execution_element_by_execution_full};
-use salamandra_server::lib::utils::handlers::{build_resp, extract_sub};
+use salamandra_server::lib::db::exercise_aliases_db::select_localized_names;
+use salamandra_server::lib::db::exercise_content_db::select_exercise_contents;
+use salamandra_server::lib::db::reactions_db::count_reactions;
+use salamandra_server::lib::models::exercise_content_models::WithExerciseContent;
+use salamandra_server::lib::models::unit_models::InUnits;
+use salamandra_server::lib::utils::handlers::{accept_languages, build_resp, extract_sub, request_units};
 use salamandra_server::lib::db::DBConnector;
 use salamandra_server::lib::errors::DBError;
 
 
 /// Fetch execution with specified verbosity
+/// * Weights in the user's units, unless overridden with `units`
 /// * Assumes path parameters have been checked previously
 pub async fn get_workout_execution(event: Request, connector: &DBConnector) -> Result<Response<Body>, Error> {
     
@@ -24,12 +31,21 @@ pub async fn get_workout_execution(event: Request, connector: &DBConnector) -> R
         Ok(_) => (),
         Err(resp) => return Ok(resp)
     };
+
+    let units = match request_units(&event, user_id, connector).await {
+        Ok(units) => units,
+        Err(resp) => return Ok(resp)
+    };
     
     // Check verbosity of response
     let full: bool = match event.query_string_parameters().first("full") {
         Some(val) => val == "true",
         None => false
     };
+    let include_content: bool = match event.query_string_parameters().first("include_content") {
+        Some(val) => val == "true",
+        None => false
+    };
 
     // Get user from execution id
     let workout_execution = match lookup_workout_execution_validated(execution_id, user_id, connector).await  {
@@ -41,11 +57,20 @@ pub async fn get_workout_execution(event: Request, connector: &DBConnector) -> R
             return Ok(build_resp(StatusCode::INTERNAL_SERVER_ERROR, ""))
         }
     };
+
+    // Reaction counts embedded in either verbosity
+    let reactions = match count_reactions(&[execution_id], connector).await {
+        Ok(mut counts) => counts.remove(&execution_id).unwrap_or_default(),
+        Err(mes) => {
+            error!("INTERNAL SERVER ERROR: {}", mes);
+            return Ok(build_resp(StatusCode::INTERNAL_SERVER_ERROR, ""))
+        }
+    };
    
     // Fetch full/normal elements and build response
     match full {
         true => {
-        let full_elements = match select_wk_execution_element_by_execution_full(execution_id, connector).await {
+        let mut full_elements = match select_wk_execution_element_by_execution_full(execution_id, connector).await {
             Ok(vector) => vector,
             Err(mes) => {
                 error!("INTERNAL SERVER ERROR: {}", mes);
@@ -53,11 +78,45 @@ pub async fn get_workout_execution(event: Request, connector: &DBConnector) -> R
             }
         };
 
+        // Localize exercise names
+        let exercise_ids: Vec<Uuid> = full_elements.iter().map(|element| element.exercise_id).collect();
+        let localized_names = match select_localized_names(&exercise_ids, &accept_languages(event.headers()), connector).await {
+            Ok(names) => names,
+            Err(mes) => {
+                error!("INTERNAL SERVER ERROR: {}", mes);
+                return Ok(build_resp(StatusCode::INTERNAL_SERVER_ERROR, ""))
+            }
+        };
+        for element in full_elements.iter_mut() {
+            if let Some(localized) = localized_names.get(&element.exercise_id) {
+                element.exercise_name = localized.clone();
+            }
+        }
+
+        // Embed instructional content if requested
+        let mut contents = HashMap::new();
+        if include_content {
+            contents = match select_exercise_contents(&exercise_ids, connector).await {
+                Ok(contents) => contents,
+                Err(mes) => {
+                    error!("INTERNAL SERVER ERROR: {}", mes);
+                    return Ok(build_resp(StatusCode::INTERNAL_SERVER_ERROR, ""))
+                }
+            };
+        }
+        let full_elements = full_elements.into_iter()
+            .map(|element| WithExerciseContent {
+                exercise_content: include_content.then(|| contents.get(&element.exercise_id).cloned().unwrap_or_default()),
+                element,
+            })
+            .collect();
+
         let execution = WorkoutExecutionFull {
             workout_execution,
             elements: full_elements,
+            reactions,
         };
-        Ok(build_resp(StatusCode::OK, execution))
+        Ok(build_resp(StatusCode::OK, execution.in_units(&units)))
     },
         false => {
             let elements = match select_wk_execution_element_by_execution(execution_id, connector).await {
@@ -71,8 +130,9 @@ pub async fn get_workout_execution(event: Request, connector: &DBConnector) -> R
             let execution = WkExecutionWithElements {
                 workout_execution,
                 elements,
+                reactions,
             };
-            Ok(build_resp(StatusCode::OK, execution))
+            Ok(build_resp(StatusCode::OK, execution.in_units(&units)))
         },
     }
 }
@@ -81,16 +141,18 @@ pub async fn get_workout_execution(event: Request, connector: &DBConnector) -> R
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::collections::HashMap;
     use lambda_http::http::header::{AUTHORIZATION, HeaderValue};
     use salamandra_server::lib::db::workout_templates_db::lookup_workout_template;
     use salamandra_server::lib::utils::tests::{pg_container, test_jwt, insert_helper, Items};
     use salamandra_server::lib::db::wk_execution_elements_db::lookup_wk_execution_element;
     use salamandra_server::lib::db::workout_executions_db::lookup_workout_execution;
+    use salamandra_server::lib::db::exercise_content_db::replace_exercise_content;
+    use salamandra_server::lib::models::exercise_content_models::ExerciseContent;
 
     // TEST CASES
     // * Get non exisiting execution
     // * Success full and non full
+    // * Full with instructional content
 
     #[tokio::test]
     async fn test_get_workout_execution_not_found() {
@@ -139,10 +201,11 @@ mod tests {
         assert_eq!(response.status(), StatusCode::OK);
 
         if let Body::Text(body) = response.into_body() {
-            let execution: Result<WorkoutExecutionFull, _> = serde_json::from_str(&body);
-            let id_vec: Vec<Uuid> = execution.unwrap().elements.iter().map(|wkt| wkt.id.clone()).collect();
+            let execution: WorkoutExecutionFull = serde_json::from_str(&body).unwrap();
+            let id_vec: Vec<Uuid> = execution.elements.iter().map(|wkt| wkt.element.id).collect();
             assert_eq!(id_vec.len(), 5);
             assert_eq!(id_vec, element_vector);
+            assert!(execution.elements.iter().all(|wkt| wkt.exercise_content.is_none()));
         }
     }
     
@@ -176,4 +239,29 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_get_workout_execution_full_with_content() {
+        let (connector, _container) = pg_container().await;
+
+        let element_vector = insert_helper(2, Items::WkExecutionElements, &connector, None).await;
+        let first_element = lookup_wk_execution_element(element_vector[0], &connector).await.unwrap();
+        let execution_id = first_element.workout_execution_id;
+        let template_id = lookup_workout_execution(execution_id, &connector).await.unwrap().workout_template_id;
+        let user_id = lookup_workout_template(template_id, &connector).await.unwrap().user_id;
+        let content = ExerciseContent {description: Some("Keep a straight line".to_string()), cues: vec!["Brace".to_string()], ..Default::default()};
+        let _ = replace_exercise_content(first_element.exercise_id, &content, &connector).await;
+
+        let query_params = HashMap::from([("full".to_string(), "true".to_string()), ("include_content".to_string(), "true".to_string())]);
+        let mut req = Request::default().with_query_string_parameters(query_params);
+        req.headers_mut().insert(AUTHORIZATION, HeaderValue::from_str(&test_jwt(user_id)).unwrap());
+        let req = req.with_path_parameters(HashMap::from([("user_id".to_string(), user_id.to_string()), ("workout_execution_id".into(), execution_id.to_string())]));
+
+        let response = get_workout_execution(req, &connector).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        if let Body::Text(body) = response.into_body() {
+            let execution: WorkoutExecutionFull = serde_json::from_str(&body).unwrap();
+            assert_eq!(execution.elements[0].exercise_content, Some(content));
+            assert_eq!(execution.elements[1].exercise_content, Some(ExerciseContent::default()));
+        }
+    }
 }
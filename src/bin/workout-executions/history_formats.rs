@@ -0,0 +1,278 @@
+use std::collections::HashMap;
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+
+use salamandra_server::lib::models::unit_models::{Units, KG_PER_LB};
+
+const DATETIME_FORMATS: [&str; 5] = ["%Y-%m-%d %H:%M:%S", "%Y-%m-%d %H:%M", "%Y-%m-%dT%H:%M:%S", "%d %b %Y, %H:%M", "%d %b %Y %H:%M"];
+
+/// Apps whose CSV exports can be imported, detected from the header
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HistorySource {
+    Strong,
+    Hevy,
+}
+
+impl HistorySource {
+    pub fn label(&self) -> &'static str {
+        match self {
+            HistorySource::Strong => "Strong",
+            HistorySource::Hevy => "Hevy",
+        }
+    }
+}
+
+/// Single set, weights always in kg and time in seconds
+#[derive(Debug, PartialEq)]
+pub struct HistorySet {
+    pub exercise_name: String,
+    pub reps: i16,
+    pub weight: Option<f32>,
+    pub time: i32,
+    /// Hevy superset id, only meaningful within its workout
+    pub super_set: Option<String>,
+}
+
+/// Sets performed in one session, in the order they were exported
+#[derive(Debug, PartialEq)]
+pub struct HistoryWorkout {
+    pub name: String,
+    pub started_at: NaiveDateTime,
+    pub sets: Vec<HistorySet>,
+}
+
+impl HistoryWorkout {
+    /// Identifies the workout across uploads of the same history
+    pub fn import_key(&self, source: HistorySource) -> String {
+        format!("{}|{}|{}", source.label().to_lowercase(), self.started_at.format("%Y-%m-%dT%H:%M:%S"), self.name)
+    }
+}
+
+/// Columns of each export, by name since both apps have changed their order over time
+struct Columns {
+    workout: &'static str,
+    started_at: &'static str,
+    exercise: &'static str,
+    reps: &'static str,
+    seconds: &'static str,
+    super_set: Option<&'static str>,
+}
+
+const STRONG_COLUMNS: Columns = Columns {
+    workout: "Workout Name",
+    started_at: "Date",
+    exercise: "Exercise Name",
+    reps: "Reps",
+    seconds: "Seconds",
+    super_set: None,
+};
+
+const HEVY_COLUMNS: Columns = Columns {
+    workout: "title",
+    started_at: "start_time",
+    exercise: "exercise_title",
+    reps: "reps",
+    seconds: "duration_seconds",
+    super_set: Some("superset_id"),
+};
+
+/// Parse a Strong or Hevy CSV export into workouts sorted by start time
+/// * Strong exports may use `;` as delimiter and have an optional `Weight Unit` column,
+///   without it weights are taken to be in `units`
+/// * Hevy exports have weights in either a `weight_kg` or a `weight_lbs` column
+/// * Rest timers and rows without reps nor time (notes) are ignored
+pub fn parse_history(content: &str, units: &Units) -> Result<(HistorySource, Vec<HistoryWorkout>), String> {
+    let content = content.trim_start_matches('\u{feff}');
+    let header = content.lines().next().unwrap_or_default();
+    let delimiter = if header.matches(';').count() > header.matches(',').count() { b';' } else { b',' };
+
+    let mut reader = csv::ReaderBuilder::new()
+        .delimiter(delimiter)
+        .flexible(true)
+        .trim(csv::Trim::All)
+        .from_reader(content.as_bytes());
+    let headers: HashMap<String, usize> = reader.headers()
+        .map_err(|e| e.to_string())?
+        .iter()
+        .enumerate()
+        .map(|(i, column)| (column.to_string(), i))
+        .collect();
+
+    let (source, columns) = if headers.contains_key(STRONG_COLUMNS.exercise) {
+        (HistorySource::Strong, STRONG_COLUMNS)
+    } else if headers.contains_key(HEVY_COLUMNS.exercise) {
+        (HistorySource::Hevy, HEVY_COLUMNS)
+    } else {
+        return Err("Unrecognized file, expected a CSV export from Strong or Hevy".to_string());
+    };
+    for column in [columns.workout, columns.started_at, columns.exercise, columns.reps] {
+        if !headers.contains_key(column) {
+            return Err(format!("Missing column '{}' in {} export", column, source.label()));
+        }
+    }
+
+    let mut workouts: Vec<HistoryWorkout> = Vec::new();
+    let mut index: HashMap<(NaiveDateTime, String), usize> = HashMap::new();
+    for (row, record) in reader.records().enumerate() {
+        let line = row + 2;
+        let record = record.map_err(|e| e.to_string())?;
+        let field = |column: &str| headers.get(column).and_then(|&i| record.get(i)).unwrap_or_default();
+
+        if source == HistorySource::Strong && field("Set Order").eq_ignore_ascii_case("Rest Timer") {
+            continue;
+        }
+        let reps = parse_number(field(columns.reps), columns.reps, line)?;
+        let seconds = parse_number(field(columns.seconds), columns.seconds, line)?;
+        if reps == 0.0 && seconds == 0.0 {
+            continue;
+        }
+        if reps > i16::MAX as f32 || seconds > i32::MAX as f32 {
+            return Err(format!("Line {}: value out of range", line));
+        }
+
+        let weight = match source {
+            HistorySource::Strong => {
+                let weight = parse_number(field("Weight"), "Weight", line)?;
+                match headers.contains_key("Weight Unit") {
+                    true if field("Weight Unit").eq_ignore_ascii_case("lbs") => weight * KG_PER_LB,
+                    true => weight,
+                    false => units.weight_to_kg(weight),
+                }
+            }
+            HistorySource::Hevy => match headers.contains_key("weight_lbs") {
+                true => parse_number(field("weight_lbs"), "weight_lbs", line)? * KG_PER_LB,
+                false => parse_number(field("weight_kg"), "weight_kg", line)?,
+            },
+        };
+
+        let exercise_name = field(columns.exercise);
+        if exercise_name.is_empty() {
+            return Err(format!("Line {}: missing exercise name", line));
+        }
+        let started_at = parse_datetime(field(columns.started_at))
+            .ok_or_else(|| format!("Line {}: invalid date '{}'", line, field(columns.started_at)))?;
+        let name = field(columns.workout).to_string();
+
+        let set = HistorySet {
+            exercise_name: exercise_name.to_string(),
+            reps: reps.round() as i16,
+            weight: (weight > 0.0).then_some(weight),
+            time: seconds.round() as i32,
+            super_set: columns.super_set.map(field).filter(|id| !id.is_empty()).map(str::to_string),
+        };
+        let i = *index.entry((started_at, name.clone())).or_insert_with(|| {
+            workouts.push(HistoryWorkout { name, started_at, sets: Vec::new() });
+            workouts.len() - 1
+        });
+        workouts[i].sets.push(set);
+    }
+
+    workouts.sort_by_key(|workout| workout.started_at);
+    Ok((source, workouts))
+}
+
+/// Empty cells are 0, decimals are accepted for every column
+fn parse_number(value: &str, column: &str, line: usize) -> Result<f32, String> {
+    if value.is_empty() {
+        return Ok(0.0);
+    }
+    match value.parse::<f32>() {
+        Ok(number) if number >= 0.0 && number.is_finite() => Ok(number),
+        _ => Err(format!("Line {}: invalid {} '{}'", line, column, value)),
+    }
+}
+
+fn parse_datetime(value: &str) -> Option<NaiveDateTime> {
+    DATETIME_FORMATS.iter().find_map(|format| NaiveDateTime::parse_from_str(value, format).ok())
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    // TEST CASES
+    // * Strong export, comma and semicolon delimited, lbs converted, in the user's units without a unit column
+    // * Hevy export with supersets
+    // * Unknown format and invalid values
+
+    fn datetime(day: u32, hour: u32, minute: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2023, 1, day).unwrap().and_hms_opt(hour, minute, 0).unwrap()
+    }
+
+    #[test]
+    fn test_parse_history_strong() {
+        let content = "\u{feff}Date,Workout Name,Duration,Exercise Name,Set Order,Weight,Reps,Distance,Seconds,Notes,Workout Notes,RPE
+2023-01-17 18:00:00,Legs,1h,Squat (Barbell),1,100,5,0,0,,,
+2023-01-15 08:30:00,Push Day,1h 2m,Bench Press (Barbell),1,60,8,0,0,,,
+2023-01-15 08:30:00,Push Day,1h 2m,Bench Press (Barbell),Rest Timer,0,0,0,90,,,
+2023-01-15 08:30:00,Push Day,1h 2m,Bench Press (Barbell),2,62.5,6,0,0,,,
+2023-01-15 08:30:00,Push Day,1h 2m,Plank,1,0,0,0,60,,,
+";
+        let (source, workouts) = parse_history(content, &Units::METRIC).unwrap();
+        assert_eq!(source, HistorySource::Strong);
+        assert_eq!(workouts.len(), 2);
+        assert_eq!(workouts[0].name, "Push Day");
+        assert_eq!(workouts[0].started_at, datetime(15, 8, 30));
+        assert_eq!(workouts[0].sets.len(), 3);
+        assert_eq!(workouts[0].sets[1].weight, Some(62.5));
+        assert_eq!(workouts[0].sets[2], HistorySet {
+            exercise_name: "Plank".to_string(), reps: 0, weight: None, time: 60, super_set: None,
+        });
+        assert_eq!(workouts[0].import_key(source), "strong|2023-01-15T08:30:00|Push Day");
+        assert_eq!(workouts[1].name, "Legs");
+
+        let content = "Date;Workout Name;Exercise Name;Set Order;Weight;Weight Unit;Reps;RPE;Distance;Distance Unit;Seconds;Notes;Workout Notes;Workout Duration
+2023-01-15 08:30:00;Push Day;Bench Press (Barbell);1;135;lbs;8;;;;0;;;1h
+";
+        let (source, workouts) = parse_history(content, &Units::METRIC).unwrap();
+        assert_eq!(source, HistorySource::Strong);
+        assert!((workouts[0].sets[0].weight.unwrap() - 61.23).abs() < 0.01);
+        // The column wins over the user's units
+        let (_, workouts) = parse_history(&content.replace(";lbs;", ";kg;"), &Units::IMPERIAL).unwrap();
+        assert_eq!(workouts[0].sets[0].weight, Some(135.0));
+
+        let content = "Date,Workout Name,Exercise Name,Set Order,Weight,Reps,Seconds
+2023-01-15 08:30:00,Push Day,Bench Press (Barbell),1,135,8,0
+";
+        let (_, workouts) = parse_history(content, &Units::IMPERIAL).unwrap();
+        assert!((workouts[0].sets[0].weight.unwrap() - 61.23).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_parse_history_hevy() {
+        let content = r#""title","start_time","end_time","description","exercise_title","superset_id","exercise_notes","set_index","set_type","weight_kg","reps","distance_km","duration_seconds","rpe"
+"Upper","15 Jan 2023, 08:30","15 Jan 2023, 09:30","","Bench Press (Barbell)","0","","0","normal","60","8","","",""
+"Upper","15 Jan 2023, 08:30","15 Jan 2023, 09:30","","Bent Over Row (Barbell)","0","","0","normal","50","10","","",""
+"Upper","15 Jan 2023, 08:30","15 Jan 2023, 09:30","","Pull Up","","","0","normal","","12","","",""
+"#;
+        let (source, workouts) = parse_history(content, &Units::METRIC).unwrap();
+        assert_eq!(source, HistorySource::Hevy);
+        assert_eq!(workouts.len(), 1);
+        assert_eq!(workouts[0].started_at, datetime(15, 8, 30));
+        let sets = &workouts[0].sets;
+        assert_eq!(sets.len(), 3);
+        assert_eq!(sets[0].super_set, Some("0".to_string()));
+        assert_eq!(sets[1].weight, Some(50.0));
+        assert_eq!(sets[2], HistorySet {
+            exercise_name: "Pull Up".to_string(), reps: 12, weight: None, time: 0, super_set: None,
+        });
+    }
+
+    #[test]
+    fn test_parse_history_invalid() {
+        assert!(parse_history("name,sets\nSquat,3\n", &Units::METRIC).is_err());
+        assert!(parse_history("", &Units::METRIC).is_err());
+
+        let missing_column = "Date,Exercise Name,Reps\n2023-01-15 08:30:00,Squat,5\n";
+        assert_eq!(parse_history(missing_column, &Units::METRIC).unwrap_err(), "Missing column 'Workout Name' in Strong export");
+
+        let invalid_reps = "Date,Workout Name,Exercise Name,Weight,Reps,Seconds\n2023-01-15 08:30:00,Legs,Squat,100,five,0\n";
+        assert_eq!(parse_history(invalid_reps, &Units::METRIC).unwrap_err(), "Line 2: invalid Reps 'five'");
+
+        let invalid_date = "Date,Workout Name,Exercise Name,Weight,Reps,Seconds\nyesterday,Legs,Squat,100,5,0\n";
+        assert_eq!(parse_history(invalid_date, &Units::METRIC).unwrap_err(), "Line 2: invalid date 'yesterday'");
+    }
+}
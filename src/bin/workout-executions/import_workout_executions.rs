@@ -0,0 +1,361 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use lambda_http::{Error, Request, Response, Body, RequestExt};
+use lambda_http::http::StatusCode;
+use serde::{Deserialize, Serialize};
+use tracing::error;
+use uuid::Uuid;
+
+use salamandra_server::lib::db::exercises_db::match_exercise_names;
+use salamandra_server::lib::db::workout_imports_db::{insert_imported_workouts, select_imported_keys};
+use salamandra_server::lib::db::DBConnector;
+use salamandra_server::lib::models::wk_execution_elements_models::NewWkExecutionElement;
+use salamandra_server::lib::models::workout_import_models::ImportedWorkout;
+use salamandra_server::lib::utils::handlers::{build_resp, extract_sub, request_units};
+use salamandra_server::lib::errors::DBError;
+
+use crate::history_formats::{parse_history, HistorySet, HistorySource};
+
+// Length of the exercise name column
+const MAX_NAME_LENGTH: usize = 255;
+
+#[derive(Debug, Serialize, Deserialize)]
+struct ImportReport {
+    source: HistorySource,
+    dry_run: bool,
+    workouts: usize,
+    already_imported: usize,
+    imported: usize,
+    // Exercise names in the file and what they were mapped to
+    matched: BTreeMap<String, MatchedExercise>,
+    unknown: Vec<String>,
+    created: Vec<String>,
+}
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+struct MatchedExercise {
+    id: Uuid,
+    name: String,
+}
+
+/// Import workout history from a Strong or Hevy CSV export sent as the body
+/// * Assumes path parameters have been checked previously
+/// * Exercise names are mapped onto the catalog and the user's private exercises
+/// * Unknown names are reported and nothing is imported, unless `create_missing=true`,
+///   in which case they become private exercises
+/// * Exercise names longer than 255 characters are rejected
+/// * `dry_run=true` only returns the report
+/// * Strong files without a weight unit column are in the user's units, unless overridden with `units`
+/// * Workouts that were already imported are skipped, so the same file can be uploaded again
+pub async fn import_workout_executions(event: Request, connector: &DBConnector) -> Result<Response<Body>, Error> {
+
+    // Get path parameter
+    let user_id = Uuid::parse_str(event.path_parameters().first("user_id").unwrap()).unwrap();
+
+    // Check path user id with sub in claim
+    match extract_sub(event.headers(), Some(user_id)) {
+        Ok(_) => (),
+        Err(resp) => return Ok(resp)
+    };
+
+    let units = match request_units(&event, user_id, connector).await {
+        Ok(units) => units,
+        Err(resp) => return Ok(resp)
+    };
+
+    let params = event.query_string_parameters();
+    let dry_run = params.first("dry_run") == Some("true");
+    let create_missing = params.first("create_missing") == Some("true");
+
+    // Parse file
+    let content = match event.into_body() {
+        Body::Text(body) => body,
+        Body::Binary(body) => match String::from_utf8(body) {
+            Ok(body) => body,
+            Err(_) => return Ok(build_resp(StatusCode::BAD_REQUEST, "Invalid payload: file must be UTF-8 text")),
+        },
+        Body::Empty => return Ok(build_resp(StatusCode::BAD_REQUEST, "Invalid payload: can't extract body")),
+    };
+    let (source, history) = match parse_history(&content, &units) {
+        Ok((_, history)) if history.is_empty() => return Ok(build_resp(StatusCode::BAD_REQUEST, "Invalid payload: no workouts in file")),
+        Ok(parsed) => parsed,
+        Err(mes) => return Ok(build_resp(StatusCode::BAD_REQUEST, format!("Invalid payload: {}", mes))),
+    };
+
+    // Map exercise names
+    let names: Vec<String> = history.iter()
+        .flat_map(|workout| workout.sets.iter().map(|set| set.exercise_name.clone()))
+        .collect::<BTreeSet<String>>()
+        .into_iter()
+        .collect();
+    if names.iter().any(|term| term.chars().count() > MAX_NAME_LENGTH) {
+        return Ok(build_resp(StatusCode::BAD_REQUEST, format!("Invalid payload: exercise names can't be longer than {} characters", MAX_NAME_LENGTH)));
+    }
+    let mut exercise_ids: HashMap<String, Uuid> = HashMap::new();
+    let mut matched = BTreeMap::new();
+    match match_exercise_names(&names, user_id, connector).await {
+        Ok(matches) => for (term, exercise) in matches {
+            exercise_ids.insert(term.clone(), exercise.id);
+            matched.insert(term, MatchedExercise { id: exercise.id, name: exercise.name });
+        },
+        Err(mes) => {
+            error!("INTERNAL SERVER ERROR: {}", mes);
+            return Ok(build_resp(StatusCode::INTERNAL_SERVER_ERROR, ""))
+        }
+    }
+    let unknown: Vec<String> = names.into_iter().filter(|term| !exercise_ids.contains_key(term)).collect();
+
+    // Find workouts imported by previous uploads
+    let keys: Vec<String> = history.iter().map(|workout| workout.import_key(source)).collect();
+    let already_imported = match select_imported_keys(user_id, &keys, connector).await {
+        Ok(imported) => imported.len(),
+        Err(mes) => {
+            error!("INTERNAL SERVER ERROR: {}", mes);
+            return Ok(build_resp(StatusCode::INTERNAL_SERVER_ERROR, ""))
+        }
+    };
+
+    let mut report = ImportReport {
+        source,
+        dry_run,
+        workouts: history.len(),
+        already_imported,
+        imported: 0,
+        matched,
+        unknown,
+        created: Vec::new(),
+    };
+    if dry_run {
+        return Ok(build_resp(StatusCode::OK, report));
+    }
+    if !report.unknown.is_empty() && !create_missing {
+        return Ok(build_resp(StatusCode::UNPROCESSABLE_ENTITY, report));
+    }
+
+    // Unknown names become private exercises, created along with the workouts
+    let mut new_exercises = HashMap::new();
+    for exercise_name in std::mem::take(&mut report.unknown) {
+        let id = Uuid::new_v4();
+        new_exercises.insert(exercise_name.clone(), id);
+        exercise_ids.insert(exercise_name.clone(), id);
+        report.matched.insert(exercise_name.clone(), MatchedExercise { id, name: exercise_name.clone() });
+        report.created.push(exercise_name);
+    }
+
+    // Insert
+    let workouts: Vec<ImportedWorkout> = history.into_iter()
+        .zip(keys)
+        .map(|(workout, import_key)| ImportedWorkout {
+            import_key,
+            template_name: workout.name,
+            date: workout.started_at.date(),
+            elements: to_elements(&workout.sets, &exercise_ids),
+        })
+        .collect();
+    match insert_imported_workouts(user_id, source.label(), &new_exercises, workouts, connector).await {
+        Ok(imported) => {
+            report.imported = imported;
+            report.already_imported = report.workouts - imported;
+            let status = if imported > 0 { StatusCode::CREATED } else { StatusCode::OK };
+            Ok(build_resp(status, report))
+        }
+        Err(DBError::UniqueViolation(mes)) => Ok(build_resp(StatusCode::CONFLICT, mes)),
+        Err(mes) => {
+            error!("INTERNAL SERVER ERROR: {}", mes);
+            Ok(build_resp(StatusCode::INTERNAL_SERVER_ERROR, ""))
+        }
+    }
+}
+
+/// Number the sets the same way the app does
+/// * Consecutive sets of an exercise share its exercise number, set numbers start from 0
+/// * Sets in a superset keep the number of their exercise within that superset
+/// * Supersets are numbered from 0 by order of appearance
+fn to_elements(sets: &[HistorySet], exercise_ids: &HashMap<String, Uuid>) -> Vec<NewWkExecutionElement> {
+    let mut super_sets: HashMap<&str, i16> = HashMap::new();
+    let mut super_set_exercises: HashMap<(i16, &str), i16> = HashMap::new();
+    let mut set_numbers: HashMap<i16, i16> = HashMap::new();
+    let mut next_exercise_number = 0;
+    let mut previous: Option<(&str, Option<i16>, i16)> = None;
+
+    sets.iter().enumerate().map(|(position, set)| {
+        let super_set = set.super_set.as_deref().map(|id| {
+            let next = super_sets.len() as i16;
+            *super_sets.entry(id).or_insert(next)
+        });
+        let exercise_number = match (super_set, previous) {
+            (Some(group), _) => *super_set_exercises.entry((group, &set.exercise_name)).or_insert_with(|| {
+                next_exercise_number += 1;
+                next_exercise_number - 1
+            }),
+            (None, Some((name, None, number))) if name == set.exercise_name => number,
+            (None, _) => {
+                next_exercise_number += 1;
+                next_exercise_number - 1
+            }
+        };
+        previous = Some((&set.exercise_name, super_set, exercise_number));
+
+        let set_number = set_numbers.entry(exercise_number).or_insert(0);
+        *set_number += 1;
+        NewWkExecutionElement {
+            exercise_id: exercise_ids[&set.exercise_name],
+            position: position as i16,
+            exercise_number,
+            reps: set.reps,
+            set_number: *set_number - 1,
+            weight: set.weight,
+            rest: 0,
+            super_set,
+            time: set.time,
+            ..Default::default()
+        }
+    }).collect()
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lambda_http::http::header::{AUTHORIZATION, HeaderValue};
+    use salamandra_server::lib::utils::tests::{pg_container, insert_helper, test_jwt, Items};
+    use salamandra_server::lib::db::exercises_db::insert_exercise;
+    use salamandra_server::lib::db::user_export_db::select_user_export;
+    use salamandra_server::lib::db::users_db::update_user;
+    use salamandra_server::lib::models::exercise_models::NewExercise;
+    use salamandra_server::lib::models::user_models::UncompleteUser;
+    use salamandra_server::lib::models::unit_models::{KG_PER_LB, LB};
+
+    // TEST CASES
+    // * Other user's history
+    // * Invalid file, exercise name too long
+    // * Elements numbering with supersets
+    // * Unknown exercises reported, dry run, created and imported, imported again
+    // * Weights of a file without units in pounds for an imperial user
+
+    const STRONG_EXPORT: &str = "Date,Workout Name,Duration,Exercise Name,Set Order,Weight,Reps,Distance,Seconds,Notes,Workout Notes,RPE
+2023-01-15 08:30:00,Push Day,1h,Bench Press (Barbell),1,60,8,0,0,,,
+2023-01-15 08:30:00,Push Day,1h,Bench Press (Barbell),2,62.5,6,0,0,,,
+2023-01-15 08:30:00,Push Day,1h,Cable Lateral Raise,1,10,12,0,0,,,
+2023-01-17 18:00:00,Push Day,1h,Bench Press (Barbell),1,65,5,0,0,,,
+";
+
+    fn import_request(user_id: Uuid, sub: Uuid, body: &str, params: &[(&str, &str)]) -> Request {
+        let mut req = Request::new(Body::Text(body.to_string()));
+        req.headers_mut().insert(AUTHORIZATION, HeaderValue::from_str(&test_jwt(sub)).unwrap());
+        req.with_path_parameters(HashMap::from([("user_id".to_string(), user_id.to_string())]))
+            .with_query_string_parameters(params.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect::<HashMap<String, String>>())
+    }
+
+    fn report(response: Response<Body>) -> ImportReport {
+        match response.into_body() {
+            Body::Text(body) => serde_json::from_str(&body).unwrap(),
+            _ => panic!("Expected a text body"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_import_workout_executions_forbidden() {
+        let (connector, _container) = pg_container().await;
+        let user_id = insert_helper(1, Items::Users, &connector, None).await[0];
+
+        let response = import_workout_executions(import_request(user_id, Uuid::new_v4(), STRONG_EXPORT, &[]), &connector).await.unwrap();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    }
+
+    #[tokio::test]
+    async fn test_import_workout_executions_invalid_file() {
+        let (connector, _container) = pg_container().await;
+        let user_id = insert_helper(1, Items::Users, &connector, None).await[0];
+
+        for body in ["", "name,sets\nSquat,3\n", "Date,Workout Name,Exercise Name,Reps,Seconds\n"] {
+            let response = import_workout_executions(import_request(user_id, user_id, body, &[]), &connector).await.unwrap();
+            assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        }
+
+        let long_name = STRONG_EXPORT.replace("Cable Lateral Raise", &"a".repeat(MAX_NAME_LENGTH + 1));
+        let params = [("create_missing", "true")];
+        let response = import_workout_executions(import_request(user_id, user_id, &long_name, &params), &connector).await.unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        assert!(select_user_export(user_id, &connector).await.unwrap().workout_executions.is_empty());
+    }
+
+    #[test]
+    fn test_to_elements() {
+        let ids: HashMap<String, Uuid> = ["A", "B", "C"].iter().map(|name| (name.to_string(), Uuid::new_v4())).collect();
+        let set = |name: &str, super_set: Option<&str>| HistorySet {
+            exercise_name: name.to_string(), reps: 5, weight: None, time: 0, super_set: super_set.map(str::to_string),
+        };
+        let sets = vec![
+            set("A", None), set("A", None),
+            set("B", Some("7")), set("C", Some("7")), set("B", Some("7")), set("C", Some("7")),
+            set("A", None),
+        ];
+        let numbering: Vec<(i16, i16, Option<i16>)> = to_elements(&sets, &ids).iter()
+            .map(|element| (element.exercise_number, element.set_number, element.super_set))
+            .collect();
+        assert_eq!(numbering, vec![
+            (0, 0, None), (0, 1, None),
+            (1, 0, Some(0)), (2, 0, Some(0)), (1, 1, Some(0)), (2, 1, Some(0)),
+            (3, 0, None),
+        ]);
+    }
+
+    #[tokio::test]
+    async fn test_import_workout_executions_success() {
+        let (connector, _container) = pg_container().await;
+        let user_id = insert_helper(1, Items::Users, &connector, None).await[0];
+        insert_exercise(&NewExercise { name: "Bench Press".to_string(), ..Default::default() }, &connector).await.unwrap();
+
+        // Unknown exercise
+        let response = import_workout_executions(import_request(user_id, user_id, STRONG_EXPORT, &[]), &connector).await.unwrap();
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+        let res = report(response);
+        assert_eq!(res.unknown, vec!["Cable Lateral Raise"]);
+        assert_eq!(res.matched["Bench Press (Barbell)"].name, "Bench Press");
+
+        // Dry run doesn't create anything
+        let params = [("dry_run", "true"), ("create_missing", "true")];
+        let response = import_workout_executions(import_request(user_id, user_id, STRONG_EXPORT, &params), &connector).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(report(response).workouts, 2);
+        assert!(select_user_export(user_id, &connector).await.unwrap().exercises.is_empty());
+
+        let params = [("create_missing", "true")];
+        let response = import_workout_executions(import_request(user_id, user_id, STRONG_EXPORT, &params), &connector).await.unwrap();
+        assert_eq!(response.status(), StatusCode::CREATED);
+        let res = report(response);
+        assert_eq!((res.imported, res.already_imported), (2, 0));
+        assert_eq!(res.created, vec!["Cable Lateral Raise"]);
+
+        let export = select_user_export(user_id, &connector).await.unwrap();
+        assert_eq!(export.workout_templates.len(), 1);
+        assert_eq!(export.workout_templates[0].name, "Push Day");
+        assert_eq!(export.workout_executions.len(), 2);
+        assert_eq!(export.wk_execution_elements.len(), 4);
+        assert!(export.exercises.iter().any(|exercise| exercise.name == "Cable Lateral Raise" && exercise.owner_id == Some(user_id)));
+
+        // Uploading again maps onto the new private exercise and imports nothing
+        let response = import_workout_executions(import_request(user_id, user_id, STRONG_EXPORT, &[]), &connector).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let res = report(response);
+        assert_eq!((res.imported, res.already_imported), (0, 2));
+        assert!(res.unknown.is_empty());
+        assert_eq!(select_user_export(user_id, &connector).await.unwrap().workout_executions.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_import_workout_executions_imperial() {
+        let (connector, _container) = pg_container().await;
+        let user_id = insert_helper(1, Items::Users, &connector, None).await[0];
+        update_user(&user_id, &UncompleteUser { weight_unit: Some(LB), ..Default::default() }, &connector).await.unwrap();
+
+        let params = [("create_missing", "true")];
+        let response = import_workout_executions(import_request(user_id, user_id, STRONG_EXPORT, &params), &connector).await.unwrap();
+        assert_eq!(response.status(), StatusCode::CREATED);
+        let export = select_user_export(user_id, &connector).await.unwrap();
+        let mut weights: Vec<f32> = export.wk_execution_elements.iter().filter_map(|element| element.weight).collect();
+        weights.sort_by(f32::total_cmp);
+        assert_eq!(weights.len(), 4);
+        let expected: Vec<f32> = [10.0, 60.0, 62.5, 65.0].iter().map(|lb| lb * KG_PER_LB).collect();
+        assert!(weights.iter().zip(&expected).all(|(weight, kg)| (weight - kg).abs() < 0.01));
+    }
+}
@@ -0,0 +1,99 @@
+use chrono::Utc;
+use lambda_http::{Error, Request, Response, Body, RequestExt};
+use lambda_http::http::StatusCode;
+use tracing::error;
+use uuid::Uuid;
+
+use salamandra_server::lib::db::challenges_db::{lookup_challenge_validated, update_participation};
+use salamandra_server::lib::utils::handlers::{build_resp, extract_sub};
+use salamandra_server::lib::db::DBConnector;
+use salamandra_server::lib::errors::DBError;
+
+/// Join a challenge the caller was invited to, until it ends
+pub async fn join_challenge(event: Request, connector: &DBConnector) -> Result<Response<Body>, Error> {
+
+    // Get path parameters
+    let user_id = Uuid::parse_str(event.path_parameters().first("user_id").unwrap()).unwrap();
+    let challenge_id = Uuid::parse_str(event.path_parameters().first("challenge_id").unwrap()).unwrap();
+
+    // Confirm user making call owns resource
+    match extract_sub(event.headers(), Some(user_id)) {
+        Ok(_) => (),
+        Err(resp) => return Ok(resp)
+    };
+
+    match lookup_challenge_validated(challenge_id, user_id, connector).await {
+        Ok(challenge) if challenge.has_ended(Utc::now().naive_utc().date()) => {
+            return Ok(build_resp(StatusCode::CONFLICT, "The challenge has already ended"))
+        },
+        Ok(_) => (),
+        Err(DBError::ItemNotFound(mes)) => return Ok(build_resp(StatusCode::NOT_FOUND, mes)),
+        Err(error) => {
+            error!("INTERNAL SERVER ERROR: {}", error);
+            return Ok(build_resp(StatusCode::INTERNAL_SERVER_ERROR, ""))
+        }
+    };
+
+    match update_participation(challenge_id, user_id, true, connector).await {
+        Ok(participant) => Ok(build_resp(StatusCode::OK, participant)),
+        Err(DBError::ItemNotFound(mes)) => Ok(build_resp(StatusCode::NOT_FOUND, mes)),
+        Err(error) => {
+            error!("INTERNAL SERVER ERROR: {}", error);
+            Ok(build_resp(StatusCode::INTERNAL_SERVER_ERROR, ""))
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use chrono::{Duration, NaiveDate};
+    use lambda_http::http::header::{AUTHORIZATION, HeaderValue};
+    use salamandra_server::lib::utils::tests::{pg_container, insert_helper, test_jwt, Items};
+    use salamandra_server::lib::db::challenges_db::insert_challenge;
+    use salamandra_server::lib::models::challenge_models::{ChallengeParticipant, NewChallenge, WORKOUTS};
+
+    // TEST CASES
+    // * Not invited
+    // * Ended challenge
+    // * Success
+
+    fn join_request(user_id: Uuid, challenge_id: Uuid) -> Request {
+        let mut req = Request::default();
+        req.headers_mut().insert(AUTHORIZATION, HeaderValue::from_str(&test_jwt(user_id)).unwrap());
+        req.with_path_parameters(HashMap::from([
+            ("user_id".to_string(), user_id.to_string()),
+            ("challenge_id".to_string(), challenge_id.to_string()),
+        ]))
+    }
+
+    async fn challenge_until(creator_id: Uuid, end_date: NaiveDate, invited: &[Uuid], connector: &DBConnector) -> Uuid {
+        let new_challenge = NewChallenge { creator_id, name: "Week".to_string(), metric: WORKOUTS, exercise_id: None, start_date: end_date - Duration::days(7), end_date };
+        insert_challenge(&new_challenge, invited, connector).await.unwrap().challenge.id
+    }
+
+    #[tokio::test]
+    async fn test_join_challenge() {
+        let (connector, _container) = pg_container().await;
+        let users = insert_helper(3, Items::Users, &connector, None).await;
+        let today = Utc::now().naive_utc().date();
+        let ongoing = challenge_until(users[0], today, &users[1..2], &connector).await;
+        let ended = challenge_until(users[0], today - Duration::days(1), &users[1..2], &connector).await;
+
+        let response = join_challenge(join_request(users[2], ongoing), &connector).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        let response = join_challenge(join_request(users[1], ended), &connector).await.unwrap();
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+
+        let response = join_challenge(join_request(users[1], ongoing), &connector).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        if let Body::Text(body) = response.into_body() {
+            let participant: ChallengeParticipant = serde_json::from_str(&body).unwrap();
+            assert!(participant.joined);
+        } else {
+            panic!("Expected a text body");
+        }
+    }
+}
@@ -0,0 +1,87 @@
+use chrono::Utc;
+use lambda_http::{Error, Request, Response, Body, RequestExt};
+use lambda_http::http::StatusCode;
+use tracing::error;
+use uuid::Uuid;
+
+use salamandra_server::lib::db::challenges_db::{lookup_challenge_validated, update_participation};
+use salamandra_server::lib::utils::handlers::{build_resp, extract_sub};
+use salamandra_server::lib::db::DBConnector;
+use salamandra_server::lib::errors::DBError;
+
+/// Leave a challenge before it ends, the invite stays so the caller can join again
+pub async fn leave_challenge(event: Request, connector: &DBConnector) -> Result<Response<Body>, Error> {
+
+    // Get path parameters
+    let user_id = Uuid::parse_str(event.path_parameters().first("user_id").unwrap()).unwrap();
+    let challenge_id = Uuid::parse_str(event.path_parameters().first("challenge_id").unwrap()).unwrap();
+
+    // Confirm user making call owns resource
+    match extract_sub(event.headers(), Some(user_id)) {
+        Ok(_) => (),
+        Err(resp) => return Ok(resp)
+    };
+
+    // Final results don't change once the challenge is over
+    match lookup_challenge_validated(challenge_id, user_id, connector).await {
+        Ok(challenge) if challenge.has_ended(Utc::now().naive_utc().date()) => {
+            return Ok(build_resp(StatusCode::CONFLICT, "The challenge has already ended"))
+        },
+        Ok(_) => (),
+        Err(DBError::ItemNotFound(mes)) => return Ok(build_resp(StatusCode::NOT_FOUND, mes)),
+        Err(error) => {
+            error!("INTERNAL SERVER ERROR: {}", error);
+            return Ok(build_resp(StatusCode::INTERNAL_SERVER_ERROR, ""))
+        }
+    };
+
+    match update_participation(challenge_id, user_id, false, connector).await {
+        Ok(_) => Ok(build_resp(StatusCode::NO_CONTENT, "")),
+        Err(DBError::ItemNotFound(mes)) => Ok(build_resp(StatusCode::NOT_FOUND, mes)),
+        Err(error) => {
+            error!("INTERNAL SERVER ERROR: {}", error);
+            Ok(build_resp(StatusCode::INTERNAL_SERVER_ERROR, ""))
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use chrono::Duration;
+    use lambda_http::http::header::{AUTHORIZATION, HeaderValue};
+    use salamandra_server::lib::utils::tests::{pg_container, insert_helper, test_jwt, Items};
+    use salamandra_server::lib::db::challenges_db::{insert_challenge, select_challenge_progress};
+    use salamandra_server::lib::models::challenge_models::{NewChallenge, WORKOUTS};
+
+    // TEST CASES
+    // * Not invited
+    // * Success, left out of the standings
+
+    fn leave_request(user_id: Uuid, challenge_id: Uuid) -> Request {
+        let mut req = Request::default();
+        req.headers_mut().insert(AUTHORIZATION, HeaderValue::from_str(&test_jwt(user_id)).unwrap());
+        req.with_path_parameters(HashMap::from([
+            ("user_id".to_string(), user_id.to_string()),
+            ("challenge_id".to_string(), challenge_id.to_string()),
+        ]))
+    }
+
+    #[tokio::test]
+    async fn test_leave_challenge() {
+        let (connector, _container) = pg_container().await;
+        let users = insert_helper(2, Items::Users, &connector, None).await;
+        let today = Utc::now().naive_utc().date();
+        let new_challenge = NewChallenge { creator_id: users[0], name: "Week".to_string(), metric: WORKOUTS, exercise_id: None, start_date: today, end_date: today + Duration::days(7) };
+        let challenge = insert_challenge(&new_challenge, &[], &connector).await.unwrap().challenge;
+
+        let response = leave_challenge(leave_request(users[1], challenge.id), &connector).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+        let response = leave_challenge(leave_request(users[0], challenge.id), &connector).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+        assert!(select_challenge_progress(&challenge, &connector).await.unwrap().is_empty());
+    }
+}
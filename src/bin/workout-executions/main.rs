@@ -1,8 +1,45 @@
 mod get_workout_execution;
 mod create_workout_execution;
+mod import_workout_executions;
+mod history_formats;
+mod get_feed;
+mod get_leaderboard;
+mod create_challenge;
+mod get_challenges;
+mod join_challenge;
+mod leave_challenge;
+mod get_challenge_standings;
+mod create_comment;
+mod get_comments;
+mod patch_comment;
+mod delete_comment;
+mod put_reaction;
+mod delete_reaction;
+mod start_workout_session;
+mod put_session_set;
+mod finish_workout_session;
+mod discard_workout_session;
 
 use create_workout_execution::create_workout_execution;
 use get_workout_execution::get_workout_execution;
+use import_workout_executions::import_workout_executions;
+use get_feed::get_feed;
+use get_leaderboard::get_leaderboard;
+use create_challenge::create_challenge;
+use get_challenges::get_challenges;
+use join_challenge::join_challenge;
+use leave_challenge::leave_challenge;
+use get_challenge_standings::{get_challenge_standings, get_challenge_results};
+use create_comment::create_comment;
+use get_comments::get_comments;
+use patch_comment::patch_comment;
+use delete_comment::delete_comment;
+use put_reaction::put_reaction;
+use delete_reaction::delete_reaction;
+use start_workout_session::start_workout_session;
+use put_session_set::put_session_set;
+use finish_workout_session::finish_workout_session;
+use discard_workout_session::discard_workout_session;
 
 use salamandra_server::lib::db::DBConnector;
 
@@ -23,7 +60,26 @@ async fn router(event: Request) -> Result<Response<Body>, Error> {
     let connector = DBConnector::default();
     let response = match (event.method(), path) {
         (&Method::POST, _) if Regex::new(&format!(r"^/users/{}/workout-executions$", UUID_PATTERN)).unwrap().is_match(path) => create_workout_execution(event, &connector).await,
+        (&Method::POST, _) if Regex::new(&format!(r"^/users/{}/workout-executions/import$", UUID_PATTERN)).unwrap().is_match(path) => import_workout_executions(event, &connector).await,
         (&Method::GET, _) if Regex::new(&format!(r"^/users/{}/workout-executions/{}$", UUID_PATTERN, UUID_PATTERN)).unwrap().is_match(path) => get_workout_execution(event, &connector).await,
+        (&Method::GET, _) if Regex::new(&format!(r"^/users/{}/feed$", UUID_PATTERN)).unwrap().is_match(path) => get_feed(event, &connector).await,
+        (&Method::GET, _) if Regex::new(&format!(r"^/users/{}/leaderboard$", UUID_PATTERN)).unwrap().is_match(path) => get_leaderboard(event, &connector).await,
+        (&Method::POST, _) if Regex::new(&format!(r"^/users/{}/challenges$", UUID_PATTERN)).unwrap().is_match(path) => create_challenge(event, &connector).await,
+        (&Method::GET, _) if Regex::new(&format!(r"^/users/{}/challenges$", UUID_PATTERN)).unwrap().is_match(path) => get_challenges(event, &connector).await,
+        (&Method::POST, _) if Regex::new(&format!(r"^/users/{}/challenges/{}$", UUID_PATTERN, UUID_PATTERN)).unwrap().is_match(path) => join_challenge(event, &connector).await,
+        (&Method::DELETE, _) if Regex::new(&format!(r"^/users/{}/challenges/{}$", UUID_PATTERN, UUID_PATTERN)).unwrap().is_match(path) => leave_challenge(event, &connector).await,
+        (&Method::GET, _) if Regex::new(&format!(r"^/users/{}/challenges/{}/standings$", UUID_PATTERN, UUID_PATTERN)).unwrap().is_match(path) => get_challenge_standings(event, &connector).await,
+        (&Method::GET, _) if Regex::new(&format!(r"^/users/{}/challenges/{}/results$", UUID_PATTERN, UUID_PATTERN)).unwrap().is_match(path) => get_challenge_results(event, &connector).await,
+        (&Method::POST, _) if Regex::new(&format!(r"^/users/{}/workout-executions/{}/comments$", UUID_PATTERN, UUID_PATTERN)).unwrap().is_match(path) => create_comment(event, &connector).await,
+        (&Method::GET, _) if Regex::new(&format!(r"^/users/{}/workout-executions/{}/comments$", UUID_PATTERN, UUID_PATTERN)).unwrap().is_match(path) => get_comments(event, &connector).await,
+        (&Method::PATCH, _) if Regex::new(&format!(r"^/users/{}/workout-executions/{}/comments/{}$", UUID_PATTERN, UUID_PATTERN, UUID_PATTERN)).unwrap().is_match(path) => patch_comment(event, &connector).await,
+        (&Method::DELETE, _) if Regex::new(&format!(r"^/users/{}/workout-executions/{}/comments/{}$", UUID_PATTERN, UUID_PATTERN, UUID_PATTERN)).unwrap().is_match(path) => delete_comment(event, &connector).await,
+        (&Method::PUT, _) if Regex::new(&format!(r"^/users/{}/workout-executions/{}/reactions$", UUID_PATTERN, UUID_PATTERN)).unwrap().is_match(path) => put_reaction(event, &connector).await,
+        (&Method::DELETE, _) if Regex::new(&format!(r"^/users/{}/workout-executions/{}/reactions$", UUID_PATTERN, UUID_PATTERN)).unwrap().is_match(path) => delete_reaction(event, &connector).await,
+        (&Method::POST, _) if Regex::new(&format!(r"^/users/{}/workout-sessions$", UUID_PATTERN)).unwrap().is_match(path) => start_workout_session(event, &connector).await,
+        (&Method::PUT, _) if Regex::new(&format!(r"^/users/{}/workout-sessions/{}/sets/[0-9]+$", UUID_PATTERN, UUID_PATTERN)).unwrap().is_match(path) => put_session_set(event, &connector).await,
+        (&Method::POST, _) if Regex::new(&format!(r"^/users/{}/workout-sessions/{}/finish$", UUID_PATTERN, UUID_PATTERN)).unwrap().is_match(path) => finish_workout_session(event, &connector).await,
+        (&Method::DELETE, _) if Regex::new(&format!(r"^/users/{}/workout-sessions/{}$", UUID_PATTERN, UUID_PATTERN)).unwrap().is_match(path) => discard_workout_session(event, &connector).await,
         _ => not_found()
     };
     response
@@ -0,0 +1,101 @@
+use lambda_http::{Error, Request, Response, Body, RequestExt};
+use lambda_http::http::StatusCode;
+use tracing::error;
+use uuid::Uuid;
+
+use salamandra_server::lib::db::comments_db::update_comment;
+use salamandra_server::lib::models::comment_models::{CommentContent, MAX_COMMENT_LENGTH};
+use salamandra_server::lib::utils::handlers::{build_resp, check_execution_visible, extract_sub};
+use salamandra_server::lib::db::DBConnector;
+use salamandra_server::lib::errors::DBError;
+
+/// Edit the content of one of the caller's comments
+pub async fn patch_comment(event: Request, connector: &DBConnector) -> Result<Response<Body>, Error> {
+
+    // Get path parameters
+    let user_id = Uuid::parse_str(event.path_parameters().first("user_id").unwrap()).unwrap();
+    let execution_id = Uuid::parse_str(event.path_parameters().first("workout_execution_id").unwrap()).unwrap();
+    let comment_id = Uuid::parse_str(event.path_parameters().first("comment_id").unwrap()).unwrap();
+
+    // Any authenticated user, only the author's comments are matched below
+    let viewer_id = match extract_sub(event.headers(), None) {
+        Ok(sub) => sub,
+        Err(resp) => return Ok(resp)
+    };
+
+    if let Err(resp) = check_execution_visible(viewer_id, user_id, execution_id, connector).await {
+        return Ok(resp);
+    }
+
+    // Check and extract payload
+    let req: CommentContent = match event.into_body() {
+        Body::Text(body) => match serde_json::from_str(&body) {
+            Ok(req) => req,
+            Err(_) => return Ok(build_resp(StatusCode::BAD_REQUEST, "Invalid payload")),
+        },
+        _ => return Ok(build_resp(StatusCode::BAD_REQUEST, "Invalid payload: can't extract body")),
+    };
+    if !req.is_valid() {
+        return Ok(build_resp(StatusCode::BAD_REQUEST, format!("Invalid payload: comments must have between 1 and {} characters", MAX_COMMENT_LENGTH)));
+    }
+
+    match update_comment(comment_id, execution_id, viewer_id, &req.content, connector).await {
+        Ok(comment) => Ok(build_resp(StatusCode::OK, comment)),
+        Err(DBError::ItemNotFound(mes)) => Ok(build_resp(StatusCode::NOT_FOUND, mes)),
+        Err(error) => {
+            error!("INTERNAL SERVER ERROR: {}", error);
+            Ok(build_resp(StatusCode::INTERNAL_SERVER_ERROR, ""))
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use lambda_http::http::header::{AUTHORIZATION, HeaderValue};
+    use salamandra_server::lib::utils::tests::{pg_container, insert_helper, test_jwt, Items};
+    use salamandra_server::lib::db::comments_db::insert_comment;
+    use salamandra_server::lib::db::workout_executions_db::lookup_workout_execution;
+    use salamandra_server::lib::db::workout_templates_db::lookup_workout_template;
+    use salamandra_server::lib::models::comment_models::{Comment, NewComment};
+
+    // TEST CASES
+    // * Someone else's comment, even as execution owner
+    // * Success
+
+    fn patch_request(user_id: Uuid, execution_id: Uuid, comment_id: Uuid, sub: Uuid, body: &str) -> Request {
+        let mut req = Request::new(Body::Text(body.to_string()));
+        req.headers_mut().insert(AUTHORIZATION, HeaderValue::from_str(&test_jwt(sub)).unwrap());
+        req.with_path_parameters(HashMap::from([
+            ("user_id".to_string(), user_id.to_string()),
+            ("workout_execution_id".to_string(), execution_id.to_string()),
+            ("comment_id".to_string(), comment_id.to_string()),
+        ]))
+    }
+
+    #[tokio::test]
+    async fn test_patch_comment() {
+        let (connector, _container) = pg_container().await;
+        let author = insert_helper(1, Items::Users, &connector, None).await[0];
+        let execution_id = insert_helper(1, Items::WkExecutions, &connector, None).await[0];
+        let execution = lookup_workout_execution(execution_id, &connector).await.unwrap();
+        let owner = lookup_workout_template(execution.workout_template_id, &connector).await.unwrap().user_id;
+        let new_comment = NewComment { workout_execution_id: execution_id, user_id: author, content: "Nice".to_string() };
+        let comment = insert_comment(&new_comment, &connector).await.unwrap();
+
+        let response = patch_comment(patch_request(owner, execution_id, comment.id, owner, r#"{"content": "Edited"}"#), &connector).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+        let response = patch_comment(patch_request(owner, execution_id, comment.id, author, r#"{"content": "Edited"}"#), &connector).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        if let Body::Text(body) = response.into_body() {
+            let comment: Comment = serde_json::from_str(&body).unwrap();
+            assert_eq!(comment.content, "Edited");
+            assert!(comment.date_edited.is_some());
+        } else {
+            panic!("Expected a text body");
+        }
+    }
+}
@@ -0,0 +1,107 @@
+use lambda_http::{Error, Request, Response, Body, RequestExt};
+use lambda_http::http::StatusCode;
+use tracing::error;
+use uuid::Uuid;
+
+use salamandra_server::lib::db::reactions_db::upsert_reaction;
+use salamandra_server::lib::models::reaction_models::{NewReaction, ReactionKind};
+use salamandra_server::lib::models::notification_models::{NewNotification, REACTION};
+use salamandra_server::lib::utils::handlers::{build_resp, check_execution_visible, extract_sub, notify};
+use salamandra_server::lib::db::DBConnector;
+use salamandra_server::lib::errors::DBError;
+
+/// React to a workout execution the caller can see, replacing their previous reaction
+/// * The owner is notified of the first reaction of each user only
+pub async fn put_reaction(event: Request, connector: &DBConnector) -> Result<Response<Body>, Error> {
+
+    // Get path parameters
+    let user_id = Uuid::parse_str(event.path_parameters().first("user_id").unwrap()).unwrap();
+    let execution_id = Uuid::parse_str(event.path_parameters().first("workout_execution_id").unwrap()).unwrap();
+
+    // Any authenticated user, visibility is checked below
+    let viewer_id = match extract_sub(event.headers(), None) {
+        Ok(sub) => sub,
+        Err(resp) => return Ok(resp)
+    };
+
+    if let Err(resp) = check_execution_visible(viewer_id, user_id, execution_id, connector).await {
+        return Ok(resp);
+    }
+
+    // Check and extract payload
+    let req: ReactionKind = match event.into_body() {
+        Body::Text(body) => match serde_json::from_str(&body) {
+            Ok(req) => req,
+            Err(_) => return Ok(build_resp(StatusCode::BAD_REQUEST, "Invalid payload")),
+        },
+        _ => return Ok(build_resp(StatusCode::BAD_REQUEST, "Invalid payload: can't extract body")),
+    };
+    if !req.is_valid() {
+        return Ok(build_resp(StatusCode::BAD_REQUEST, "Invalid payload: unknown reaction"));
+    }
+
+    let new_reaction = NewReaction { workout_execution_id: execution_id, user_id: viewer_id, reaction: req.reaction };
+    match upsert_reaction(&new_reaction, connector).await {
+        Ok(reaction) => {
+            let new_notification = NewNotification {
+                user_id,
+                kind: REACTION,
+                actor_id: Some(viewer_id),
+                workout_execution_id: Some(execution_id),
+                ..Default::default()
+            };
+            notify(new_notification, connector).await;
+            Ok(build_resp(StatusCode::OK, reaction))
+        },
+        Err(DBError::ItemNotFound(mes)) => Ok(build_resp(StatusCode::NOT_FOUND, mes)),
+        Err(error) => {
+            error!("INTERNAL SERVER ERROR: {}", error);
+            Ok(build_resp(StatusCode::INTERNAL_SERVER_ERROR, ""))
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use lambda_http::http::header::{AUTHORIZATION, HeaderValue};
+    use salamandra_server::lib::utils::tests::{pg_container, insert_helper, test_jwt, Items};
+    use salamandra_server::lib::db::reactions_db::count_reactions;
+    use salamandra_server::lib::db::workout_executions_db::lookup_workout_execution;
+    use salamandra_server::lib::db::workout_templates_db::lookup_workout_template;
+    use salamandra_server::lib::models::reaction_models::{ReactionCount, FIRE};
+
+    // TEST CASES
+    // * Unknown reaction
+    // * Success, replacing the previous reaction
+
+    fn reaction_request(user_id: Uuid, execution_id: Uuid, sub: Uuid, body: &str) -> Request {
+        let mut req = Request::new(Body::Text(body.to_string()));
+        req.headers_mut().insert(AUTHORIZATION, HeaderValue::from_str(&test_jwt(sub)).unwrap());
+        req.with_path_parameters(HashMap::from([
+            ("user_id".to_string(), user_id.to_string()),
+            ("workout_execution_id".to_string(), execution_id.to_string()),
+        ]))
+    }
+
+    #[tokio::test]
+    async fn test_put_reaction() {
+        let (connector, _container) = pg_container().await;
+        let viewer = insert_helper(1, Items::Users, &connector, None).await[0];
+        let execution_id = insert_helper(1, Items::WkExecutions, &connector, None).await[0];
+        let execution = lookup_workout_execution(execution_id, &connector).await.unwrap();
+        let owner = lookup_workout_template(execution.workout_template_id, &connector).await.unwrap().user_id;
+
+        let response = put_reaction(reaction_request(owner, execution_id, viewer, r#"{"reaction": 9}"#), &connector).await.unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        for body in [r#"{"reaction": 0}"#, r#"{"reaction": 1}"#] {
+            let response = put_reaction(reaction_request(owner, execution_id, viewer, body), &connector).await.unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+        }
+        let counts = count_reactions(&[execution_id], &connector).await.unwrap();
+        assert_eq!(counts[&execution_id], vec![ReactionCount { reaction: FIRE, count: 1 }]);
+    }
+}
@@ -0,0 +1,173 @@
+use chrono::Utc;
+use lambda_http::{Error, Request, Response, Body, RequestExt};
+use lambda_http::http::StatusCode;
+use serde::Deserialize;
+use tracing::error;
+use uuid::Uuid;
+
+use salamandra_server::lib::db::exercises_db::validate_exercises;
+use salamandra_server::lib::db::workout_sessions_db::upsert_session_set;
+use salamandra_server::lib::models::wk_execution_elements_models::NewWkExecutionElement;
+use salamandra_server::lib::models::unit_models::{InUnits, Units};
+use salamandra_server::lib::utils::handlers::{build_resp, extract_sub, request_units};
+use salamandra_server::lib::db::DBConnector;
+use salamandra_server::lib::errors::DBError;
+
+#[derive(Deserialize)]
+struct SessionSetRequest {
+    exercise_id: Uuid,
+    exercise_number: i16,
+    reps: i16,
+    set_number: i16,
+    weight: Option<f32>,
+    rest: i16,
+    #[serde(default)]
+    super_set: Option<i16>,
+    time: i32,
+}
+
+impl SessionSetRequest {
+    fn to_new_element(&self, workout_execution_id: Uuid, position: i16, units: &Units) -> NewWkExecutionElement {
+        NewWkExecutionElement {
+            id: None,
+            workout_execution_id,
+            exercise_id: self.exercise_id,
+            position,
+            exercise_number: self.exercise_number,
+            reps: self.reps,
+            set_number: self.set_number,
+            weight: self.weight.map(|weight| units.weight_to_kg(weight)),
+            rest: self.rest,
+            super_set: self.super_set,
+            time: self.time,
+        }
+    }
+}
+
+/// Log the set at `position` of a workout in progress, replacing the one there if any
+/// * Weights in the user's units, unless overridden with `units`
+/// * Only the values of the set are checked, the whole workout is when it's finished
+pub async fn put_session_set(event: Request, connector: &DBConnector) -> Result<Response<Body>, Error> {
+
+    // Get path parameters
+    let user_id = Uuid::parse_str(event.path_parameters().first("user_id").unwrap()).unwrap();
+    let execution_id = Uuid::parse_str(event.path_parameters().first("workout_execution_id").unwrap()).unwrap();
+    let position = match event.path_parameters().first("position").unwrap().parse::<i16>() {
+        Ok(position) => position,
+        Err(_) => return Ok(build_resp(StatusCode::BAD_REQUEST, "Invalid position")),
+    };
+
+    // Check user in claim
+    match extract_sub(event.headers(), Some(user_id)) {
+        Ok(_) => (),
+        Err(resp) => return Ok(resp)
+    };
+
+    let units = match request_units(&event, user_id, connector).await {
+        Ok(units) => units,
+        Err(resp) => return Ok(resp)
+    };
+
+    let req: SessionSetRequest = match event.into_body() {
+        Body::Text(body) => match serde_json::from_str(&body) {
+            Ok(req) => req,
+            Err(error) => return Ok(build_resp(StatusCode::BAD_REQUEST, format!("Invalid payload: {}", error))),
+        },
+        _ => return Ok(build_resp(StatusCode::BAD_REQUEST, "Invalid payload: can't extract body")),
+    };
+    if req.reps <= 0 || req.time <= 0 || req.exercise_number < 0 || req.set_number < 0 || req.weight.is_some_and(|w| w < 0.0) || req.rest < 0 || req.super_set.is_some_and(|s| s < 0) {
+        return Ok(build_resp(StatusCode::BAD_REQUEST, "Invalid payload: reps and time must be at least 1 and no value can be negative"));
+    }
+
+    // Retired exercises are allowed, they may still be in the user's templates
+    match validate_exercises(vec![req.exercise_id], user_id, true, connector).await {
+        Ok(true) => (),
+        Ok(false) => return Ok(build_resp(StatusCode::NOT_FOUND, "No exercise exists with the corresponding id")),
+        Err(error) => {
+            error!("INTERNAL SERVER ERROR: {}", error);
+            return Ok(build_resp(StatusCode::INTERNAL_SERVER_ERROR, ""))
+        }
+    }
+
+    let new_set = req.to_new_element(execution_id, position, &units);
+    match upsert_session_set(&new_set, user_id, Utc::now().naive_utc(), connector).await {
+        Ok(set) => Ok(build_resp(StatusCode::OK, set.in_units(&units))),
+        Err(DBError::ItemNotFound(mes)) => Ok(build_resp(StatusCode::NOT_FOUND, mes)),
+        Err(error) => {
+            error!("INTERNAL SERVER ERROR: {}", error);
+            Ok(build_resp(StatusCode::INTERNAL_SERVER_ERROR, ""))
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use lambda_http::http::header::{AUTHORIZATION, HeaderValue};
+    use salamandra_server::lib::utils::tests::{pg_container, insert_helper, test_jwt, Items};
+    use salamandra_server::lib::db::workout_sessions_db::{insert_workout_session, select_workout_session};
+    use salamandra_server::lib::db::workout_templates_db::lookup_workout_template;
+    use salamandra_server::lib::models::wk_execution_elements_models::WkExecutionElement;
+    use salamandra_server::lib::models::workout_execution_models::NewWorkoutSession;
+
+    // TEST CASES
+    // * Invalid position and payload
+    // * Session of another user, unknown exercise
+    // * Log and replace a set in pounds
+
+    fn set_request(user_id: Uuid, execution_id: Uuid, position: &str, body: String) -> Request {
+        let mut req = Request::new(Body::Text(body));
+        req.headers_mut().insert(AUTHORIZATION, HeaderValue::from_str(&test_jwt(user_id)).unwrap());
+        req.with_path_parameters(HashMap::from([
+            ("user_id".to_string(), user_id.to_string()),
+            ("workout_execution_id".to_string(), execution_id.to_string()),
+            ("position".to_string(), position.to_string()),
+        ]))
+        .with_query_string_parameters(HashMap::from([("units".to_string(), "imperial".to_string())]))
+    }
+
+    fn set_body(exercise_id: Uuid, reps: i16) -> String {
+        serde_json::json!({
+            "exercise_id": exercise_id, "exercise_number": 0, "reps": reps, "set_number": 0, "weight": 100.0, "rest": 60, "time": 30
+        }).to_string()
+    }
+
+    #[tokio::test]
+    async fn test_put_session_set() {
+        let (connector, _container) = pg_container().await;
+        let template_id = insert_helper(1, Items::WkTemplates, &connector, None).await[0];
+        let user_id = lookup_workout_template(template_id, &connector).await.unwrap().user_id;
+        let stranger = insert_helper(1, Items::Users, &connector, None).await[0];
+        let exercise_id = insert_helper(1, Items::Exercises, &connector, None).await[0];
+        let new_session = NewWorkoutSession::new(template_id, Utc::now().naive_utc().date(), None, Utc::now().naive_utc());
+        let session_id = insert_workout_session(&new_session, user_id, &connector).await.unwrap().id;
+
+        let response = put_session_set(set_request(user_id, session_id, "99999", set_body(exercise_id, 5)), &connector).await.unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        let response = put_session_set(set_request(user_id, session_id, "0", set_body(exercise_id, 0)), &connector).await.unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        let response = put_session_set(set_request(stranger, session_id, "0", set_body(exercise_id, 5)), &connector).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        let response = put_session_set(set_request(user_id, session_id, "0", set_body(Uuid::new_v4(), 5)), &connector).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+        let response = put_session_set(set_request(user_id, session_id, "0", set_body(exercise_id, 5)), &connector).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        if let Body::Text(body) = response.into_body() {
+            let set: WkExecutionElement = serde_json::from_str(&body).unwrap();
+            assert_eq!((set.position, set.reps, set.weight), (0, 5, Some(100.0)));
+        } else {
+            panic!("Expected a text body");
+        }
+        let response = put_session_set(set_request(user_id, session_id, "0", set_body(exercise_id, 4)), &connector).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let (_, sets) = select_workout_session(session_id, user_id, &connector).await.unwrap();
+        assert_eq!(sets.len(), 1);
+        assert_eq!(sets[0].reps, 4);
+        assert!((sets[0].weight.unwrap() - 45.359_237).abs() < 0.01);
+    }
+}
@@ -0,0 +1,128 @@
+use chrono::{NaiveDate, Utc};
+use lambda_http::{Error, Request, Response, Body, RequestExt};
+use lambda_http::http::StatusCode;
+use serde::Deserialize;
+use tracing::error;
+use uuid::Uuid;
+
+use salamandra_server::lib::db::programs_db::lookup_program_slot_validated;
+use salamandra_server::lib::db::workout_sessions_db::insert_workout_session;
+use salamandra_server::lib::models::workout_execution_models::NewWorkoutSession;
+use salamandra_server::lib::utils::handlers::{build_resp, extract_sub};
+use salamandra_server::lib::db::DBConnector;
+use salamandra_server::lib::errors::DBError;
+
+#[derive(Deserialize)]
+struct StartSessionRequest {
+    workout_template_id: Uuid,
+    #[serde(default)]
+    date: Option<NaiveDate>,
+    #[serde(default)]
+    program_slot_id: Option<Uuid>,
+}
+
+/// Start a workout on one of the user's templates, its sets are logged one by one as they are done
+/// * Today unless another `date` is given, never in the future
+/// * `program_slot_id` links it to a slot of one of the user's programs, on the same template
+/// * It's abandoned if no set is logged for a while
+pub async fn start_workout_session(event: Request, connector: &DBConnector) -> Result<Response<Body>, Error> {
+
+    // Get path parameter
+    let user_id = Uuid::parse_str(event.path_parameters().first("user_id").unwrap()).unwrap();
+
+    // Check user in claim
+    match extract_sub(event.headers(), Some(user_id)) {
+        Ok(_) => (),
+        Err(resp) => return Ok(resp)
+    };
+
+    let req: StartSessionRequest = match event.into_body() {
+        Body::Text(body) => match serde_json::from_str(&body) {
+            Ok(req) => req,
+            Err(error) => return Ok(build_resp(StatusCode::BAD_REQUEST, format!("Invalid payload: {}", error))),
+        },
+        _ => return Ok(build_resp(StatusCode::BAD_REQUEST, "Invalid payload: can't extract body")),
+    };
+
+    let now = Utc::now().naive_utc();
+    let date = req.date.unwrap_or(now.date());
+    if now.date() < date {
+        return Ok(build_resp(StatusCode::BAD_REQUEST, "Invalid payload: date can't be in the future"));
+    }
+
+    if let Some(program_slot_id) = req.program_slot_id {
+        match lookup_program_slot_validated(program_slot_id, user_id, connector).await {
+            Ok(slot) if slot.workout_template_id != req.workout_template_id => {
+                return Ok(build_resp(StatusCode::BAD_REQUEST, "Invalid payload: the program slot is planned on another template"))
+            },
+            Ok(_) => (),
+            Err(DBError::ItemNotFound(mes)) => return Ok(build_resp(StatusCode::NOT_FOUND, mes)),
+            Err(error) => {
+                error!("INTERNAL SERVER ERROR: {}", error);
+                return Ok(build_resp(StatusCode::INTERNAL_SERVER_ERROR, ""))
+            }
+        }
+    }
+
+    let new_session = NewWorkoutSession::new(req.workout_template_id, date, req.program_slot_id, now);
+    match insert_workout_session(&new_session, user_id, connector).await {
+        Ok(session) => Ok(build_resp(StatusCode::CREATED, session)),
+        Err(DBError::ItemNotFound(mes)) => Ok(build_resp(StatusCode::NOT_FOUND, mes)),
+        Err(error) => {
+            error!("INTERNAL SERVER ERROR: {}", error);
+            Ok(build_resp(StatusCode::INTERNAL_SERVER_ERROR, ""))
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use chrono::Duration;
+    use lambda_http::http::header::{AUTHORIZATION, HeaderValue};
+    use salamandra_server::lib::utils::tests::{pg_container, insert_helper, test_jwt, Items};
+    use salamandra_server::lib::db::workout_templates_db::lookup_workout_template;
+    use salamandra_server::lib::models::workout_execution_models::{WorkoutExecution, IN_PROGRESS};
+
+    // TEST CASES
+    // * Invalid payload, date in the future
+    // * Template of another user
+    // * Success
+
+    fn start_request(user_id: Uuid, body: String) -> Request {
+        let mut req = Request::new(Body::Text(body));
+        req.headers_mut().insert(AUTHORIZATION, HeaderValue::from_str(&test_jwt(user_id)).unwrap());
+        req.with_path_parameters(HashMap::from([("user_id".to_string(), user_id.to_string())]))
+    }
+
+    #[tokio::test]
+    async fn test_start_workout_session() {
+        let (connector, _container) = pg_container().await;
+        let template_id = insert_helper(1, Items::WkTemplates, &connector, None).await[0];
+        let user_id = lookup_workout_template(template_id, &connector).await.unwrap().user_id;
+        let stranger = insert_helper(1, Items::Users, &connector, None).await[0];
+
+        let response = start_workout_session(start_request(user_id, "{}".to_string()), &connector).await.unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        let tomorrow = Utc::now().naive_utc().date() + Duration::days(1);
+        let body = serde_json::json!({ "workout_template_id": template_id, "date": tomorrow }).to_string();
+        let response = start_workout_session(start_request(user_id, body), &connector).await.unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        let body = serde_json::json!({ "workout_template_id": template_id }).to_string();
+        let response = start_workout_session(start_request(stranger, body.clone()), &connector).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+        let response = start_workout_session(start_request(user_id, body), &connector).await.unwrap();
+        assert_eq!(response.status(), StatusCode::CREATED);
+        if let Body::Text(body) = response.into_body() {
+            let session: WorkoutExecution = serde_json::from_str(&body).unwrap();
+            assert_eq!((session.workout_template_id, session.status), (template_id, IN_PROGRESS));
+            assert!(session.last_activity.is_some());
+        } else {
+            panic!("Expected a text body");
+        }
+    }
+}
@@ -0,0 +1,68 @@
+use lambda_http::{Error, Request, Response, Body, RequestExt};
+use lambda_http::http::StatusCode;
+use tracing::error;
+use uuid::Uuid;
+
+use salamandra_server::lib::db::schedules_db::rotate_calendar_token;
+use salamandra_server::lib::models::schedule_models::CalendarFeedLink;
+use salamandra_server::lib::utils::handlers::{build_resp, extract_sub};
+use salamandra_server::lib::db::DBConnector;
+use salamandra_server::lib::errors::DBError;
+
+/// Create the secret link of the user's iCal feed, replacing the previous one
+pub async fn create_calendar_token(event: Request, connector: &DBConnector) -> Result<Response<Body>, Error> {
+
+    // Get path parameter
+    let user_id = Uuid::parse_str(event.path_parameters().first("user_id").unwrap()).unwrap();
+
+    // Check user in claim
+    match extract_sub(event.headers(), Some(user_id)) {
+        Ok(_) => (),
+        Err(resp) => return Ok(resp)
+    };
+
+    match rotate_calendar_token(user_id, connector).await {
+        Ok(calendar_token) => Ok(build_resp(StatusCode::CREATED, CalendarFeedLink::from(calendar_token))),
+        Err(DBError::ItemNotFound(mes)) => Ok(build_resp(StatusCode::NOT_FOUND, mes)),
+        Err(error) => {
+            error!("INTERNAL SERVER ERROR: {}", error);
+            Ok(build_resp(StatusCode::INTERNAL_SERVER_ERROR, ""))
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use lambda_http::http::header::{AUTHORIZATION, HeaderValue};
+    use salamandra_server::lib::utils::tests::{pg_container, insert_helper, test_jwt, Items};
+    use salamandra_server::lib::db::schedules_db::lookup_calendar_token;
+
+    // TEST CASES
+    // * Success, a second call replaces the token
+
+    #[tokio::test]
+    async fn test_create_calendar_token() {
+        let (connector, _container) = pg_container().await;
+        let user_id = insert_helper(1, Items::Users, &connector, None).await[0];
+
+        let mut links = Vec::new();
+        for _ in 0..2 {
+            let mut req = Request::default();
+            req.headers_mut().insert(AUTHORIZATION, HeaderValue::from_str(&test_jwt(user_id)).unwrap());
+            let req = req.with_path_parameters(HashMap::from([("user_id".to_string(), user_id.to_string())]));
+            let response = create_calendar_token(req, &connector).await.unwrap();
+            assert_eq!(response.status(), StatusCode::CREATED);
+            if let Body::Text(body) = response.into_body() {
+                links.push(serde_json::from_str::<CalendarFeedLink>(&body).unwrap());
+            } else {
+                panic!("Expected a text body");
+            }
+        }
+        assert_eq!(links[1].feed_path, format!("/calendar/{}.ics", links[1].token));
+        assert!(lookup_calendar_token(links[0].token, &connector).await.is_err());
+        assert_eq!(lookup_calendar_token(links[1].token, &connector).await.unwrap(), user_id);
+    }
+}
@@ -0,0 +1,102 @@
+use lambda_http::{Error, Request, Response, Body, RequestExt};
+use lambda_http::http::StatusCode;
+use tracing::error;
+use uuid::Uuid;
+
+use salamandra_server::lib::db::programs_db::insert_program;
+use salamandra_server::lib::models::program_models::CreateProgramRequest;
+use salamandra_server::lib::utils::handlers::{build_resp, extract_sub};
+use salamandra_server::lib::db::DBConnector;
+use salamandra_server::lib::errors::DBError;
+
+/// Create a program out of the user's templates
+/// * Each slot puts a template on a day of a week, optionally overriding its reps and intensity
+pub async fn create_program(event: Request, connector: &DBConnector) -> Result<Response<Body>, Error> {
+
+    // Get path parameter
+    let user_id = Uuid::parse_str(event.path_parameters().first("user_id").unwrap()).unwrap();
+
+    // Check user in claim
+    match extract_sub(event.headers(), Some(user_id)) {
+        Ok(_) => (),
+        Err(resp) => return Ok(resp)
+    };
+
+    // Check and extract payload
+    let req: CreateProgramRequest = match event.into_body() {
+        Body::Text(body) => match serde_json::from_str(&body) {
+            Ok(req) => req,
+            Err(_) => return Ok(build_resp(StatusCode::BAD_REQUEST, "Invalid payload")),
+        },
+        _ => return Ok(build_resp(StatusCode::BAD_REQUEST, "Invalid payload: can't extract body")),
+    };
+    if let Err(mes) = req.validate() {
+        return Ok(build_resp(StatusCode::BAD_REQUEST, format!("Invalid payload: {}", mes)));
+    }
+
+    match insert_program(&req.to_new_program(user_id), &req.slots, connector).await {
+        Ok(program) => Ok(build_resp(StatusCode::CREATED, program)),
+        Err(DBError::ItemNotFound(mes)) => Ok(build_resp(StatusCode::NOT_FOUND, mes)),
+        Err(error) => {
+            error!("INTERNAL SERVER ERROR: {}", error);
+            Ok(build_resp(StatusCode::INTERNAL_SERVER_ERROR, ""))
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use lambda_http::http::header::{AUTHORIZATION, HeaderValue};
+    use salamandra_server::lib::utils::tests::{pg_container, insert_helper, test_jwt, Items};
+    use salamandra_server::lib::db::workout_templates_db::lookup_workout_template;
+    use salamandra_server::lib::models::program_models::ProgramWithSlots;
+
+    // TEST CASES
+    // * Invalid payload
+    // * Template of another user
+    // * Success
+
+    fn create_request(user_id: Uuid, body: serde_json::Value) -> Request {
+        let mut req = Request::new(Body::Text(body.to_string()));
+        req.headers_mut().insert(AUTHORIZATION, HeaderValue::from_str(&test_jwt(user_id)).unwrap());
+        req.with_path_parameters(HashMap::from([("user_id".to_string(), user_id.to_string())]))
+    }
+
+    fn program_body(workout_template_id: Uuid, week: i16) -> serde_json::Value {
+        serde_json::json!({
+            "name": "5/3/1",
+            "weeks": 3,
+            "slots": [
+                {"workout_template_id": workout_template_id, "week": week, "day": 1, "reps": 5, "intensity": 85},
+                {"workout_template_id": workout_template_id, "week": 1, "day": 4},
+            ]
+        })
+    }
+
+    #[tokio::test]
+    async fn test_create_program() {
+        let (connector, _container) = pg_container().await;
+        let template_id = insert_helper(1, Items::WkTemplates, &connector, None).await[0];
+        let user_id = lookup_workout_template(template_id, &connector).await.unwrap().user_id;
+        let stranger = insert_helper(1, Items::Users, &connector, None).await[0];
+
+        let response = create_program(create_request(user_id, program_body(template_id, 4)), &connector).await.unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        let response = create_program(create_request(stranger, program_body(template_id, 2)), &connector).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+        let response = create_program(create_request(user_id, program_body(template_id, 2)), &connector).await.unwrap();
+        assert_eq!(response.status(), StatusCode::CREATED);
+        if let Body::Text(body) = response.into_body() {
+            let program: ProgramWithSlots = serde_json::from_str(&body).unwrap();
+            assert_eq!(program.program.weeks, 3);
+            assert_eq!(program.slots.len(), 2);
+            assert_eq!((program.slots[1].reps, program.slots[1].intensity), (Some(5), Some(85)));
+        } else {
+            panic!("Expected a text body");
+        }
+    }
+}
@@ -0,0 +1,92 @@
+use lambda_http::{Error, Request, Response, Body, RequestExt};
+use lambda_http::http::StatusCode;
+use tracing::error;
+use uuid::Uuid;
+
+use salamandra_server::lib::db::schedules_db::insert_scheduled_workout;
+use salamandra_server::lib::models::schedule_models::ScheduleRequest;
+use salamandra_server::lib::utils::handlers::{build_resp, extract_sub};
+use salamandra_server::lib::db::DBConnector;
+use salamandra_server::lib::errors::DBError;
+
+/// Plan one of the user's templates on a day, or every week on the given `weekdays`
+pub async fn create_scheduled_workout(event: Request, connector: &DBConnector) -> Result<Response<Body>, Error> {
+
+    // Get path parameter
+    let user_id = Uuid::parse_str(event.path_parameters().first("user_id").unwrap()).unwrap();
+
+    // Check user in claim
+    match extract_sub(event.headers(), Some(user_id)) {
+        Ok(_) => (),
+        Err(resp) => return Ok(resp)
+    };
+
+    // Check and extract payload
+    let req: ScheduleRequest = match event.into_body() {
+        Body::Text(body) => match serde_json::from_str(&body) {
+            Ok(req) => req,
+            Err(_) => return Ok(build_resp(StatusCode::BAD_REQUEST, "Invalid payload")),
+        },
+        _ => return Ok(build_resp(StatusCode::BAD_REQUEST, "Invalid payload: can't extract body")),
+    };
+    if let Err(mes) = req.validate() {
+        return Ok(build_resp(StatusCode::BAD_REQUEST, format!("Invalid payload: {}", mes)));
+    }
+
+    match insert_scheduled_workout(&req.to_new_scheduled_workout(user_id), connector).await {
+        Ok(scheduled) => Ok(build_resp(StatusCode::CREATED, scheduled)),
+        Err(DBError::ItemNotFound(mes)) => Ok(build_resp(StatusCode::NOT_FOUND, mes)),
+        Err(error) => {
+            error!("INTERNAL SERVER ERROR: {}", error);
+            Ok(build_resp(StatusCode::INTERNAL_SERVER_ERROR, ""))
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use lambda_http::http::header::{AUTHORIZATION, HeaderValue};
+    use salamandra_server::lib::utils::tests::{pg_container, insert_helper, test_jwt, Items};
+    use salamandra_server::lib::db::workout_templates_db::lookup_workout_template;
+    use salamandra_server::lib::models::schedule_models::ScheduledWorkout;
+
+    // TEST CASES
+    // * Invalid payload
+    // * Template of another user
+    // * Success
+
+    fn create_request(user_id: Uuid, body: serde_json::Value) -> Request {
+        let mut req = Request::new(Body::Text(body.to_string()));
+        req.headers_mut().insert(AUTHORIZATION, HeaderValue::from_str(&test_jwt(user_id)).unwrap());
+        req.with_path_parameters(HashMap::from([("user_id".to_string(), user_id.to_string())]))
+    }
+
+    #[tokio::test]
+    async fn test_create_scheduled_workout() {
+        let (connector, _container) = pg_container().await;
+        let template_id = insert_helper(1, Items::WkTemplates, &connector, None).await[0];
+        let user_id = lookup_workout_template(template_id, &connector).await.unwrap().user_id;
+        let stranger = insert_helper(1, Items::Users, &connector, None).await[0];
+
+        let body = serde_json::json!({"workout_template_id": template_id, "start_date": "2024-11-04", "end_date": "2024-11-01", "weekdays": [1]});
+        let response = create_scheduled_workout(create_request(user_id, body), &connector).await.unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        let body = serde_json::json!({"workout_template_id": template_id, "start_date": "2024-11-04", "weekdays": [5, 1]});
+        let response = create_scheduled_workout(create_request(stranger, body.clone()), &connector).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+        let response = create_scheduled_workout(create_request(user_id, body), &connector).await.unwrap();
+        assert_eq!(response.status(), StatusCode::CREATED);
+        if let Body::Text(body) = response.into_body() {
+            let scheduled: ScheduledWorkout = serde_json::from_str(&body).unwrap();
+            assert_eq!(scheduled.weekdays, Some(vec![1, 5]));
+            assert_eq!(scheduled.end_date, None);
+        } else {
+            panic!("Expected a text body");
+        }
+    }
+}
@@ -11,7 +11,8 @@ use salamandra_server::lib::db::wk_template_elements_db::insert_batch_wk_templat
 use salamandra_server::lib::db::DBConnector;
 use salamandra_server::lib::models::workout_templates_models::{NewWorkoutTemplate, WkTemplateWithElements, WorkoutTemplate};
 use salamandra_server::lib::models::wk_template_elements_models::NewWkTemplateElement;
-use salamandra_server::lib::utils::handlers::{build_resp, extract_sub};
+use salamandra_server::lib::models::unit_models::{InUnits, Units};
+use salamandra_server::lib::utils::handlers::{build_resp, extract_sub, request_units};
 use salamandra_server::lib::errors::DBError;
 
 const BASE_ERROR: &str = "Invalid payload. ";
@@ -19,6 +20,8 @@ const DOC_LINK: &str = ". See https://github.com/SalamandraApp/salamandra-server
 
 #[derive(Serialize, Deserialize)]
 struct CreateWkTemplateRequest {
+    #[serde(default)]
+    id: Option<Uuid>,
     name: String,
     description: Option<String>,
     date_created: chrono::NaiveDate,
@@ -26,6 +29,8 @@ struct CreateWkTemplateRequest {
 }
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 struct WkTemplateElementRequest {
+    #[serde(default)]
+    id: Option<Uuid>,
     exercise_id: Uuid,
     position: i16,
     reps: i16,
@@ -38,6 +43,7 @@ struct WkTemplateElementRequest {
 impl CreateWkTemplateRequest {
     fn to_new_template(&self, user_id: Uuid) -> NewWorkoutTemplate {
         NewWorkoutTemplate {
+            id: self.id,
             user_id,
             name: self.name.clone(),
             description: self.description.clone(),
@@ -46,14 +52,15 @@ impl CreateWkTemplateRequest {
     }
 }
 impl WkTemplateElementRequest {
-    fn to_new_element(&self, workout_template_id: Uuid) -> NewWkTemplateElement {
+    fn to_new_element(&self, workout_template_id: Uuid, units: &Units) -> NewWkTemplateElement {
         NewWkTemplateElement {
+            id: self.id,
             workout_template_id,
             exercise_id: self.exercise_id,
             position: self.position,
             reps: self.reps,
             sets: self.sets,
-            weight: self.weight,
+            weight: self.weight.map(|weight| units.weight_to_kg(weight)),
             rest: self.rest,
             super_set: self.super_set,
         }
@@ -61,6 +68,8 @@ impl WkTemplateElementRequest {
 }
 
 /// Insert new workout template and its elements
+/// * Weights in the user's units, unless overridden with `units`
+/// * Ids can be given for the template and its elements, so ones created offline keep theirs
 pub async fn create_workout_template(event: Request, connector: &DBConnector) -> Result<Response<Body>, Error> {
 
     // Get path parameter
@@ -72,6 +81,11 @@ pub async fn create_workout_template(event: Request, connector: &DBConnector) ->
         Err(resp) => return Ok(resp)
     };
 
+    let units = match request_units(&event, user_id, connector).await {
+        Ok(units) => units,
+        Err(resp) => return Ok(resp)
+    };
+
     // Check and extract payload
     let body = match event.into_body() {
         Body::Text(body) => body,
@@ -95,7 +109,7 @@ pub async fn create_workout_template(event: Request, connector: &DBConnector) ->
 
     // Validate that the ids exist
     let exercise_ids: HashSet<Uuid> = req.elements.iter().map(|element| element.exercise_id).collect();
-    match validate_exercises(exercise_ids.into_iter().collect(), connector).await {
+    match validate_exercises(exercise_ids.into_iter().collect(), user_id, false, connector).await {
         Ok(valid) => {
             if !valid {
                 return Ok(build_resp(StatusCode::NOT_FOUND, "One or more exercise IDs do not reference existing exercises"));
@@ -113,11 +127,8 @@ pub async fn create_workout_template(event: Request, connector: &DBConnector) ->
     // Insert template
     let workout_template: WorkoutTemplate = match insert_workout_template(&new_workout_template, connector).await {
         Ok(template) => template,
-        Err(DBError::UniqueViolation(mes)) => {
-            // should never trigger since the primary key is only the UUID
-            warn!("Tried to insert already exisiting workout-template");
-            return Ok(build_resp(StatusCode::CONFLICT, mes))
-        }
+        // Only with ids given by the client, most likely a retry of one created offline
+        Err(DBError::UniqueViolation(mes)) => return Ok(build_resp(StatusCode::CONFLICT, mes)),
         Err(mes) => {
             error!("INTERNAL SERVER ERROR: {}", mes);
             return Ok(build_resp(StatusCode::INTERNAL_SERVER_ERROR, ""))
@@ -127,7 +138,7 @@ pub async fn create_workout_template(event: Request, connector: &DBConnector) ->
     // Create template elements
     let new_elements: Vec<NewWkTemplateElement> = req.elements
         .iter()
-        .map(|elem| elem.to_new_element(workout_template.id))
+        .map(|elem| elem.to_new_element(workout_template.id, &units))
         .collect();
 
     // Insert template elements
@@ -137,12 +148,18 @@ pub async fn create_workout_template(event: Request, connector: &DBConnector) ->
                 workout_template,
                 elements,
             };
-            Ok(build_resp(StatusCode::CREATED, response))
+            Ok(build_resp(StatusCode::CREATED, response.in_units(&units)))
         },
         Err(DBError::ConnectionError(mes)) => {
             error!("INTERNAL SERVER ERROR: {}", mes);
             Ok(build_resp(StatusCode::INTERNAL_SERVER_ERROR, ""))
         }
+        Err(DBError::UniqueViolation(mes)) => {
+            if delete_workout_template(user_id, workout_template.id, connector).await.is_err() {
+                warn!("Could not delete workout-template triggered by error inserting templates");
+            }
+            Ok(build_resp(StatusCode::CONFLICT, mes))
+        }
         Err(error) => {
             // Should never trigger because all values are checked before hand
             warn!("Could not insert workout-template element: {}", error);
@@ -171,6 +188,12 @@ fn validate_template(items: &[WkTemplateElementRequest]) -> Result<(), String> {
         return Err(format!("{}All sets and reps must be at least 1. No values can't be negative{}", BASE_ERROR, DOC_LINK));
     }
 
+    // Ids given by the client
+    let ids: Vec<Uuid> = items.iter().filter_map(|item| item.id).collect();
+    if ids.iter().collect::<HashSet<&Uuid>>().len() != ids.len() {
+        return Err(format!("{}The element's ids must be unique{}", BASE_ERROR, DOC_LINK));
+    }
+
     // Sequential position 
     let mut positions: Vec<i16> = items.iter().map(|item| item.position).collect();
     positions.sort_unstable();
@@ -229,17 +252,22 @@ mod tests {
     use serde_json::to_string;
     use lambda_http::http::header::{AUTHORIZATION, HeaderValue};
     use salamandra_server::lib::utils::tests::{insert_helper, pg_container, test_jwt, Items};
+    use salamandra_server::lib::db::wk_template_elements_db::select_wk_template_element_by_template;
+    use salamandra_server::lib::db::workout_templates_db::select_workout_template_by_user;
 
     // TEST CASES
     // * Create a template
+    // * Ids given by the client, created again
     // * Invalid ids
     // * Invalid payload
+    // * Weights given in pounds
 
     async fn setup_template(connector: &DBConnector) -> (Uuid, CreateWkTemplateRequest) {
         let user_id = insert_helper(1, Items::Users, connector, None).await[0];
         let exercise_id = insert_helper(1, Items::Exercises, connector, None).await[0];
 
         let base_element = WkTemplateElementRequest {
+            id: None,
             exercise_id,
             position: 0,  
             reps: 1,
@@ -257,6 +285,7 @@ mod tests {
         }
 
         let template = CreateWkTemplateRequest {
+            id: None,
             name: "Placeholder".to_string(),
             description: None,
             date_created: chrono::Utc::now().date_naive(),
@@ -296,6 +325,46 @@ mod tests {
 
     }
 
+    #[tokio::test]
+    async fn test_create_workout_template_client_ids() {
+        let (connector, _container) = pg_container().await;
+        let (user_id, mut payload) = setup_template(&connector).await;
+        let template_id = Uuid::new_v4();
+        let element_id = Uuid::new_v4();
+        payload.id = Some(template_id);
+        payload.elements[0].id = Some(element_id);
+        let create_request = |payload: &CreateWkTemplateRequest| {
+            let mut req = Request::new(Body::from(to_string(payload).unwrap()));
+            req.headers_mut().insert(AUTHORIZATION, HeaderValue::from_str(&test_jwt(user_id)).unwrap());
+            req.with_path_parameters(HashMap::from([("user_id".to_string(), user_id.to_string())]))
+        };
+
+        payload.elements[1].id = Some(element_id);
+        let response = create_workout_template(create_request(&payload), &connector).await.unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        payload.elements[1].id = None;
+
+        let response = create_workout_template(create_request(&payload), &connector).await.unwrap();
+        assert_eq!(response.status(), StatusCode::CREATED);
+        if let Body::Text(body) = response.into_body() {
+            let template: WkTemplateWithElements = serde_json::from_str(&body).unwrap();
+            assert_eq!(template.workout_template.id, template_id);
+            assert!(template.elements.iter().any(|element| element.id == element_id));
+        } else {
+            panic!("Expected a text body");
+        }
+
+        let response = create_workout_template(create_request(&payload), &connector).await.unwrap();
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+
+        // Only an element that already exists, the new template is rolled back
+        payload.id = None;
+        let response = create_workout_template(create_request(&payload), &connector).await.unwrap();
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+        let templates = select_workout_template_by_user(user_id, &connector).await.unwrap();
+        assert_eq!(templates.len(), 1);
+    }
+
     #[tokio::test]
     async fn test_create_workout_template_invalid_user_ids() {
         let (connector, _container) = pg_container().await;
@@ -530,4 +599,30 @@ mod tests {
 
         }
     }
+
+    #[tokio::test]
+    async fn test_create_workout_template_imperial() {
+        let (connector, _container) = pg_container().await;
+        let (user_id, mut payload) = setup_template(&connector).await;
+        payload.elements[0].weight = Some(135.0);
+
+        let mut req = Request::new(Body::Text(to_string(&payload).unwrap()));
+        req.headers_mut().insert(AUTHORIZATION, HeaderValue::from_str(&test_jwt(user_id)).unwrap());
+        let req = req
+            .with_path_parameters(HashMap::from([("user_id".to_string(), user_id.to_string())]))
+            .with_query_string_parameters(HashMap::from([("units".to_string(), "imperial".to_string())]));
+
+        let response = create_workout_template(req, &connector).await.unwrap();
+        assert_eq!(response.status(), StatusCode::CREATED);
+        let template: WkTemplateWithElements = match response.into_body() {
+            Body::Text(body) => serde_json::from_str(&body).unwrap(),
+            _ => panic!("Expected a text body"),
+        };
+        let element = template.elements.iter().find(|element| element.position == 0).unwrap();
+        assert_eq!(element.weight, Some(135.0));
+
+        let stored = select_wk_template_element_by_template(template.workout_template.id, &connector).await.unwrap();
+        let stored = stored.iter().find(|element| element.position == 0).unwrap();
+        assert!((stored.weight.unwrap() - 61.235).abs() < 0.01);
+    }
 }
@@ -0,0 +1,73 @@
+use lambda_http::{Error, Request, Response, Body, RequestExt};
+use lambda_http::http::StatusCode;
+use tracing::error;
+use uuid::Uuid;
+
+use salamandra_server::lib::db::progression_db::delete_progression_rule as remove_progression_rule;
+use salamandra_server::lib::utils::handlers::{build_resp, extract_sub};
+use salamandra_server::lib::db::DBConnector;
+
+/// Remove the progression rule of an element, its targets go back to the template's
+pub async fn delete_progression_rule(event: Request, connector: &DBConnector) -> Result<Response<Body>, Error> {
+
+    // Get path parameters
+    let user_id = Uuid::parse_str(event.path_parameters().first("user_id").unwrap()).unwrap();
+    let workout_template_id = Uuid::parse_str(event.path_parameters().first("workout_template_id").unwrap()).unwrap();
+    let element_id = Uuid::parse_str(event.path_parameters().first("wk_template_element_id").unwrap()).unwrap();
+
+    // Check user in claim
+    match extract_sub(event.headers(), Some(user_id)) {
+        Ok(_) => (),
+        Err(resp) => return Ok(resp)
+    };
+
+    match remove_progression_rule(element_id, workout_template_id, user_id, connector).await {
+        Ok(0) => Ok(build_resp(StatusCode::NOT_FOUND, "The element has no progression rule")),
+        Ok(_) => Ok(build_resp(StatusCode::NO_CONTENT, "")),
+        Err(error) => {
+            error!("INTERNAL SERVER ERROR: {}", error);
+            Ok(build_resp(StatusCode::INTERNAL_SERVER_ERROR, ""))
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use lambda_http::http::header::{AUTHORIZATION, HeaderValue};
+    use salamandra_server::lib::utils::tests::{pg_container, insert_helper, test_jwt, Items};
+    use salamandra_server::lib::db::progression_db::upsert_progression_rule;
+    use salamandra_server::lib::db::wk_template_elements_db::insert_batch_wk_template_elements;
+    use salamandra_server::lib::db::workout_templates_db::lookup_workout_template;
+    use salamandra_server::lib::models::progression_models::{NewProgressionRule, LINEAR};
+    use salamandra_server::lib::models::wk_template_elements_models::NewWkTemplateElement;
+
+    // TEST CASES
+    // * Success, then not found
+
+    #[tokio::test]
+    async fn test_delete_progression_rule() {
+        let (connector, _container) = pg_container().await;
+        let template_id = insert_helper(1, Items::WkTemplates, &connector, None).await[0];
+        let user_id = lookup_workout_template(template_id, &connector).await.unwrap().user_id;
+        let exercise_id = insert_helper(1, Items::Exercises, &connector, None).await[0];
+        let new_element = NewWkTemplateElement { workout_template_id: template_id, exercise_id, reps: 5, sets: 5, ..Default::default() };
+        let element_id = insert_batch_wk_template_elements(&vec![new_element], &connector).await.unwrap()[0].id;
+        let new_rule = NewProgressionRule { wk_template_element_id: element_id, kind: LINEAR, weight_increment: 2.5, min_reps: None, max_reps: None, deload_after: None, deload_percent: 10 };
+        upsert_progression_rule(template_id, user_id, &new_rule, &connector).await.unwrap();
+
+        for expected in [StatusCode::NO_CONTENT, StatusCode::NOT_FOUND] {
+            let mut req = Request::default();
+            req.headers_mut().insert(AUTHORIZATION, HeaderValue::from_str(&test_jwt(user_id)).unwrap());
+            let req = req.with_path_parameters(HashMap::from([
+                ("user_id".to_string(), user_id.to_string()),
+                ("workout_template_id".to_string(), template_id.to_string()),
+                ("wk_template_element_id".to_string(), element_id.to_string()),
+            ]));
+            let response = delete_progression_rule(req, &connector).await.unwrap();
+            assert_eq!(response.status(), expected);
+        }
+    }
+}
@@ -0,0 +1,67 @@
+use lambda_http::{Error, Request, Response, Body, RequestExt};
+use lambda_http::http::StatusCode;
+use tracing::error;
+use uuid::Uuid;
+
+use salamandra_server::lib::db::schedules_db::delete_scheduled_workout as remove_scheduled_workout;
+use salamandra_server::lib::utils::handlers::{build_resp, extract_sub};
+use salamandra_server::lib::db::DBConnector;
+
+/// Remove a scheduled workout of the user, past executions are kept
+pub async fn delete_scheduled_workout(event: Request, connector: &DBConnector) -> Result<Response<Body>, Error> {
+
+    // Get path parameters
+    let user_id = Uuid::parse_str(event.path_parameters().first("user_id").unwrap()).unwrap();
+    let scheduled_id = Uuid::parse_str(event.path_parameters().first("scheduled_workout_id").unwrap()).unwrap();
+
+    // Check user in claim
+    match extract_sub(event.headers(), Some(user_id)) {
+        Ok(_) => (),
+        Err(resp) => return Ok(resp)
+    };
+
+    match remove_scheduled_workout(scheduled_id, user_id, connector).await {
+        Ok(0) => Ok(build_resp(StatusCode::NOT_FOUND, "No scheduled workout exists with the corresponding id")),
+        Ok(_) => Ok(build_resp(StatusCode::NO_CONTENT, "")),
+        Err(error) => {
+            error!("INTERNAL SERVER ERROR: {}", error);
+            Ok(build_resp(StatusCode::INTERNAL_SERVER_ERROR, ""))
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use chrono::NaiveDate;
+    use lambda_http::http::header::{AUTHORIZATION, HeaderValue};
+    use salamandra_server::lib::utils::tests::{pg_container, insert_helper, test_jwt, Items};
+    use salamandra_server::lib::db::schedules_db::insert_scheduled_workout;
+    use salamandra_server::lib::db::workout_templates_db::lookup_workout_template;
+    use salamandra_server::lib::models::schedule_models::NewScheduledWorkout;
+
+    // TEST CASES
+    // * Success, then not found
+
+    #[tokio::test]
+    async fn test_delete_scheduled_workout() {
+        let (connector, _container) = pg_container().await;
+        let template_id = insert_helper(1, Items::WkTemplates, &connector, None).await[0];
+        let user_id = lookup_workout_template(template_id, &connector).await.unwrap().user_id;
+        let start_date = NaiveDate::from_ymd_opt(2024, 11, 4).unwrap();
+        let scheduled = insert_scheduled_workout(&NewScheduledWorkout { user_id, workout_template_id: template_id, start_date, end_date: None, weekdays: None }, &connector).await.unwrap();
+
+        for expected in [StatusCode::NO_CONTENT, StatusCode::NOT_FOUND] {
+            let mut req = Request::default();
+            req.headers_mut().insert(AUTHORIZATION, HeaderValue::from_str(&test_jwt(user_id)).unwrap());
+            let req = req.with_path_parameters(HashMap::from([
+                ("user_id".to_string(), user_id.to_string()),
+                ("scheduled_workout_id".to_string(), scheduled.id.to_string()),
+            ]));
+            let response = delete_scheduled_workout(req, &connector).await.unwrap();
+            assert_eq!(response.status(), expected);
+        }
+    }
+}
@@ -0,0 +1,99 @@
+use chrono::Utc;
+use lambda_http::{Error, Request, Response, Body, RequestExt};
+use lambda_http::http::StatusCode;
+use tracing::error;
+use uuid::Uuid;
+
+use salamandra_server::lib::db::programs_db::insert_enrolment;
+use salamandra_server::lib::models::program_models::EnrolRequest;
+use salamandra_server::lib::utils::handlers::{build_resp, extract_sub};
+use salamandra_server::lib::db::DBConnector;
+use salamandra_server::lib::errors::DBError;
+
+/// Start following a program of the user, today unless a `start_date` is given
+/// * Only one program can be followed at a time
+pub async fn enrol_program(event: Request, connector: &DBConnector) -> Result<Response<Body>, Error> {
+
+    // Get path parameters
+    let user_id = Uuid::parse_str(event.path_parameters().first("user_id").unwrap()).unwrap();
+    let program_id = Uuid::parse_str(event.path_parameters().first("program_id").unwrap()).unwrap();
+
+    // Check user in claim
+    match extract_sub(event.headers(), Some(user_id)) {
+        Ok(_) => (),
+        Err(resp) => return Ok(resp)
+    };
+
+    // The payload is optional
+    let req: EnrolRequest = match event.into_body() {
+        Body::Empty => EnrolRequest::default(),
+        Body::Text(body) if body.trim().is_empty() => EnrolRequest::default(),
+        Body::Text(body) => match serde_json::from_str(&body) {
+            Ok(req) => req,
+            Err(_) => return Ok(build_resp(StatusCode::BAD_REQUEST, "Invalid payload")),
+        },
+        _ => return Ok(build_resp(StatusCode::BAD_REQUEST, "Invalid payload: can't extract body")),
+    };
+    let start_date = req.start_date.unwrap_or(Utc::now().naive_utc().date());
+
+    match insert_enrolment(program_id, user_id, start_date, connector).await {
+        Ok(enrolment) => Ok(build_resp(StatusCode::CREATED, enrolment)),
+        Err(DBError::ItemNotFound(mes)) => Ok(build_resp(StatusCode::NOT_FOUND, mes)),
+        Err(DBError::UniqueViolation(mes)) => Ok(build_resp(StatusCode::CONFLICT, mes)),
+        Err(error) => {
+            error!("INTERNAL SERVER ERROR: {}", error);
+            Ok(build_resp(StatusCode::INTERNAL_SERVER_ERROR, ""))
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use lambda_http::http::header::{AUTHORIZATION, HeaderValue};
+    use salamandra_server::lib::utils::tests::{pg_container, insert_helper, test_jwt, Items};
+    use salamandra_server::lib::db::programs_db::insert_program;
+    use salamandra_server::lib::db::workout_templates_db::lookup_workout_template;
+    use salamandra_server::lib::models::program_models::{NewProgram, ProgramEnrolment, ProgramSlotRequest};
+
+    // TEST CASES
+    // * Program of another user
+    // * Success with a start date, then already enrolled
+
+    fn enrol_request(user_id: Uuid, program_id: Uuid, body: Body) -> Request {
+        let mut req = Request::new(body);
+        req.headers_mut().insert(AUTHORIZATION, HeaderValue::from_str(&test_jwt(user_id)).unwrap());
+        req.with_path_parameters(HashMap::from([
+            ("user_id".to_string(), user_id.to_string()),
+            ("program_id".to_string(), program_id.to_string()),
+        ]))
+    }
+
+    #[tokio::test]
+    async fn test_enrol_program() {
+        let (connector, _container) = pg_container().await;
+        let template_id = insert_helper(1, Items::WkTemplates, &connector, None).await[0];
+        let user_id = lookup_workout_template(template_id, &connector).await.unwrap().user_id;
+        let stranger = insert_helper(1, Items::Users, &connector, None).await[0];
+        let slots = [ProgramSlotRequest { workout_template_id: template_id, week: 1, day: 1, reps: None, intensity: None }];
+        let program_id = insert_program(&NewProgram { user_id, name: "Block".to_string(), description: None, weeks: 1 }, &slots, &connector).await.unwrap().program.id;
+
+        let response = enrol_program(enrol_request(stranger, program_id, Body::Empty), &connector).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+        let body = Body::Text(r#"{"start_date": "2024-11-04"}"#.to_string());
+        let response = enrol_program(enrol_request(user_id, program_id, body), &connector).await.unwrap();
+        assert_eq!(response.status(), StatusCode::CREATED);
+        if let Body::Text(body) = response.into_body() {
+            let enrolment: ProgramEnrolment = serde_json::from_str(&body).unwrap();
+            assert_eq!(enrolment.start_date.to_string(), "2024-11-04");
+        } else {
+            panic!("Expected a text body");
+        }
+
+        let response = enrol_program(enrol_request(user_id, program_id, Body::Empty), &connector).await.unwrap();
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+    }
+}
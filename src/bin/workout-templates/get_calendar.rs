@@ -0,0 +1,103 @@
+use chrono::{Duration, Utc};
+use lambda_http::{Error, Request, Response, Body, RequestExt};
+use lambda_http::http::StatusCode;
+use tracing::error;
+use uuid::Uuid;
+
+use salamandra_server::lib::db::schedules_db::select_calendar;
+use salamandra_server::lib::models::schedule_models::MAX_CALENDAR_DAYS;
+use salamandra_server::lib::utils::handlers::{build_resp, date_range, extract_sub};
+use salamandra_server::lib::db::DBConnector;
+
+/// Days shown before and after today when no range is given
+const DEFAULT_DAYS_BEFORE: i64 = 28;
+const DEFAULT_DAYS_AFTER: i64 = 7;
+
+/// Planned workouts of the user next to what was actually done, with the adherence so far
+/// * Optional `from` and `to` dates (YYYY-MM-DD), the last four weeks and the next one by default
+pub async fn get_calendar(event: Request, connector: &DBConnector) -> Result<Response<Body>, Error> {
+
+    // Get path parameter
+    let user_id = Uuid::parse_str(event.path_parameters().first("user_id").unwrap()).unwrap();
+
+    // Check user in claim
+    match extract_sub(event.headers(), Some(user_id)) {
+        Ok(_) => (),
+        Err(resp) => return Ok(resp)
+    };
+
+    let today = Utc::now().naive_utc().date();
+    let (from, to) = match date_range(&event) {
+        Ok((from, to)) => (
+            from.unwrap_or(today - Duration::days(DEFAULT_DAYS_BEFORE)),
+            to.unwrap_or(today + Duration::days(DEFAULT_DAYS_AFTER)),
+        ),
+        Err(resp) => return Ok(resp)
+    };
+    if from > to || (to - from).num_days() >= MAX_CALENDAR_DAYS {
+        return Ok(build_resp(StatusCode::BAD_REQUEST, format!("'from' must be before 'to' and at most {} days apart", MAX_CALENDAR_DAYS)));
+    }
+
+    match select_calendar(user_id, from, to, today, connector).await {
+        Ok(calendar) => Ok(build_resp(StatusCode::OK, calendar)),
+        Err(error) => {
+            error!("INTERNAL SERVER ERROR: {}", error);
+            Ok(build_resp(StatusCode::INTERNAL_SERVER_ERROR, ""))
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use lambda_http::http::header::{AUTHORIZATION, HeaderValue};
+    use salamandra_server::lib::utils::tests::{pg_container, insert_helper, test_jwt, Items};
+    use salamandra_server::lib::db::schedules_db::insert_scheduled_workout;
+    use salamandra_server::lib::db::workout_executions_db::insert_workout_execution;
+    use salamandra_server::lib::db::workout_templates_db::lookup_workout_template;
+    use salamandra_server::lib::models::schedule_models::{Calendar, NewScheduledWorkout};
+    use salamandra_server::lib::models::workout_execution_models::NewWorkoutExecution;
+
+    // TEST CASES
+    // * Range too long
+    // * Success with the default range
+
+    fn calendar_request(user_id: Uuid, query: HashMap<String, String>) -> Request {
+        let mut req = Request::default();
+        req.headers_mut().insert(AUTHORIZATION, HeaderValue::from_str(&test_jwt(user_id)).unwrap());
+        req.with_path_parameters(HashMap::from([("user_id".to_string(), user_id.to_string())]))
+            .with_query_string_parameters(query)
+    }
+
+    #[tokio::test]
+    async fn test_get_calendar() {
+        let (connector, _container) = pg_container().await;
+        let template_id = insert_helper(1, Items::WkTemplates, &connector, None).await[0];
+        let user_id = lookup_workout_template(template_id, &connector).await.unwrap().user_id;
+        let today = Utc::now().naive_utc().date();
+
+        let query = HashMap::from([("from".to_string(), "2023-01-01".to_string()), ("to".to_string(), "2024-12-31".to_string())]);
+        let response = get_calendar(calendar_request(user_id, query), &connector).await.unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        let new_scheduled = |start_date| NewScheduledWorkout { user_id, workout_template_id: template_id, start_date, end_date: None, weekdays: None };
+        insert_scheduled_workout(&new_scheduled(today - Duration::days(3)), &connector).await.unwrap();
+        insert_scheduled_workout(&new_scheduled(today + Duration::days(2)), &connector).await.unwrap();
+        insert_scheduled_workout(&new_scheduled(today + Duration::days(30)), &connector).await.unwrap();
+        insert_workout_execution(&NewWorkoutExecution { workout_template_id: template_id, date: today - Duration::days(3), ..Default::default() }, &connector).await.unwrap();
+        insert_workout_execution(&NewWorkoutExecution { workout_template_id: template_id, date: today - Duration::days(1), ..Default::default() }, &connector).await.unwrap();
+
+        let response = get_calendar(calendar_request(user_id, HashMap::new()), &connector).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        if let Body::Text(body) = response.into_body() {
+            let calendar: Calendar = serde_json::from_str(&body).unwrap();
+            assert_eq!(calendar.planned.len(), 2);
+            assert_eq!(calendar.unplanned.len(), 1);
+            assert_eq!((calendar.due, calendar.completed, calendar.adherence), (1, 1, Some(1.0)));
+        } else {
+            panic!("Expected a text body");
+        }
+    }
+}
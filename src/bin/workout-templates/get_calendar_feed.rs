@@ -0,0 +1,87 @@
+use chrono::Utc;
+use lambda_http::{Error, Request, Response, Body, RequestExt};
+use lambda_http::http::StatusCode;
+use tracing::error;
+use uuid::Uuid;
+
+use salamandra_server::lib::db::schedules_db::{lookup_calendar_token, select_schedule_with_names};
+use salamandra_server::lib::utils::handlers::{build_attachment_resp, build_resp};
+use salamandra_server::lib::db::DBConnector;
+use salamandra_server::lib::errors::DBError;
+
+use crate::ical::calendar_feed;
+
+/// iCal feed of a user's scheduled workouts, for calendar apps to subscribe to
+/// * Not authenticated, the token in the path is the secret
+pub async fn get_calendar_feed(event: Request, connector: &DBConnector) -> Result<Response<Body>, Error> {
+
+    // Get path parameter
+    let token = match Uuid::parse_str(event.path_parameters().first("token").unwrap_or_default()) {
+        Ok(token) => token,
+        Err(_) => return Ok(build_resp(StatusCode::NOT_FOUND, "No calendar exists with the corresponding token")),
+    };
+
+    let user_id = match lookup_calendar_token(token, connector).await {
+        Ok(user_id) => user_id,
+        Err(DBError::ItemNotFound(mes)) => return Ok(build_resp(StatusCode::NOT_FOUND, mes)),
+        Err(error) => {
+            error!("INTERNAL SERVER ERROR: {}", error);
+            return Ok(build_resp(StatusCode::INTERNAL_SERVER_ERROR, ""))
+        }
+    };
+
+    match select_schedule_with_names(user_id, connector).await {
+        Ok(schedule) => {
+            let feed = calendar_feed(&schedule, Utc::now().naive_utc());
+            Ok(build_attachment_resp("workouts.ics", "text/calendar; charset=utf-8", feed.into_bytes()))
+        },
+        Err(error) => {
+            error!("INTERNAL SERVER ERROR: {}", error);
+            Ok(build_resp(StatusCode::INTERNAL_SERVER_ERROR, ""))
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use chrono::NaiveDate;
+    use salamandra_server::lib::utils::tests::{pg_container, insert_helper, Items};
+    use salamandra_server::lib::db::schedules_db::{delete_calendar_token, insert_scheduled_workout, rotate_calendar_token};
+    use salamandra_server::lib::db::workout_templates_db::lookup_workout_template;
+    use salamandra_server::lib::models::schedule_models::NewScheduledWorkout;
+
+    // TEST CASES
+    // * Success without authentication
+    // * Revoked token
+
+    fn feed_request(token: Uuid) -> Request {
+        Request::default().with_path_parameters(HashMap::from([("token".to_string(), token.to_string())]))
+    }
+
+    #[tokio::test]
+    async fn test_get_calendar_feed() {
+        let (connector, _container) = pg_container().await;
+        let template_id = insert_helper(1, Items::WkTemplates, &connector, None).await[0];
+        let user_id = lookup_workout_template(template_id, &connector).await.unwrap().user_id;
+        let start_date = NaiveDate::from_ymd_opt(2024, 11, 4).unwrap();
+        insert_scheduled_workout(&NewScheduledWorkout { user_id, workout_template_id: template_id, start_date, end_date: None, weekdays: Some(vec![1, 4]) }, &connector).await.unwrap();
+        let token = rotate_calendar_token(user_id, &connector).await.unwrap().token;
+
+        let response = get_calendar_feed(feed_request(token), &connector).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()["Content-Type"], "text/calendar; charset=utf-8");
+        if let Body::Binary(data) = response.into_body() {
+            let feed = String::from_utf8(data).unwrap();
+            assert!(feed.contains("SUMMARY:Placeholder\r\nRRULE:FREQ=WEEKLY;BYDAY=MO,TH\r\n"));
+        } else {
+            panic!("Expected a binary body");
+        }
+
+        delete_calendar_token(user_id, &connector).await.unwrap();
+        let response = get_calendar_feed(feed_request(token), &connector).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+}
@@ -0,0 +1,79 @@
+use lambda_http::{Error, Request, Response, Body, RequestExt};
+use lambda_http::http::StatusCode;
+use tracing::error;
+use uuid::Uuid;
+
+use salamandra_server::lib::db::programs_db::lookup_program_validated;
+use salamandra_server::lib::utils::handlers::{build_resp, extract_sub};
+use salamandra_server::lib::db::DBConnector;
+use salamandra_server::lib::errors::DBError;
+
+/// Fetch a program of the user with its slots, sorted by week and day
+pub async fn get_program(event: Request, connector: &DBConnector) -> Result<Response<Body>, Error> {
+
+    // Get path parameters
+    let user_id = Uuid::parse_str(event.path_parameters().first("user_id").unwrap()).unwrap();
+    let program_id = Uuid::parse_str(event.path_parameters().first("program_id").unwrap()).unwrap();
+
+    // Check user in claim
+    match extract_sub(event.headers(), Some(user_id)) {
+        Ok(_) => (),
+        Err(resp) => return Ok(resp)
+    };
+
+    match lookup_program_validated(program_id, user_id, connector).await {
+        Ok(program) => Ok(build_resp(StatusCode::OK, program)),
+        Err(DBError::ItemNotFound(mes)) => Ok(build_resp(StatusCode::NOT_FOUND, mes)),
+        Err(error) => {
+            error!("INTERNAL SERVER ERROR: {}", error);
+            Ok(build_resp(StatusCode::INTERNAL_SERVER_ERROR, ""))
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use lambda_http::http::header::{AUTHORIZATION, HeaderValue};
+    use salamandra_server::lib::utils::tests::{pg_container, insert_helper, test_jwt, Items};
+    use salamandra_server::lib::db::programs_db::insert_program;
+    use salamandra_server::lib::db::workout_templates_db::lookup_workout_template;
+    use salamandra_server::lib::models::program_models::{NewProgram, ProgramSlotRequest, ProgramWithSlots};
+
+    // TEST CASES
+    // * Program of another user
+    // * Success
+
+    fn program_request(user_id: Uuid, program_id: Uuid) -> Request {
+        let mut req = Request::default();
+        req.headers_mut().insert(AUTHORIZATION, HeaderValue::from_str(&test_jwt(user_id)).unwrap());
+        req.with_path_parameters(HashMap::from([
+            ("user_id".to_string(), user_id.to_string()),
+            ("program_id".to_string(), program_id.to_string()),
+        ]))
+    }
+
+    #[tokio::test]
+    async fn test_get_program() {
+        let (connector, _container) = pg_container().await;
+        let template_id = insert_helper(1, Items::WkTemplates, &connector, None).await[0];
+        let user_id = lookup_workout_template(template_id, &connector).await.unwrap().user_id;
+        let stranger = insert_helper(1, Items::Users, &connector, None).await[0];
+        let slots = [ProgramSlotRequest { workout_template_id: template_id, week: 1, day: 1, reps: None, intensity: None }];
+        let created = insert_program(&NewProgram { user_id, name: "Block".to_string(), description: None, weeks: 1 }, &slots, &connector).await.unwrap();
+
+        let response = get_program(program_request(stranger, created.program.id), &connector).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+        let response = get_program(program_request(user_id, created.program.id), &connector).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        if let Body::Text(body) = response.into_body() {
+            let program: ProgramWithSlots = serde_json::from_str(&body).unwrap();
+            assert_eq!(program, created);
+        } else {
+            panic!("Expected a text body");
+        }
+    }
+}
@@ -0,0 +1,88 @@
+use lambda_http::{Error, Request, Response, Body, RequestExt};
+use lambda_http::http::StatusCode;
+use tracing::error;
+use uuid::Uuid;
+
+use salamandra_server::lib::db::programs_db::select_program_progress;
+use salamandra_server::lib::utils::handlers::{build_resp, extract_sub};
+use salamandra_server::lib::db::DBConnector;
+use salamandra_server::lib::errors::DBError;
+
+/// Completion of the latest enrolment in a program: every slot with the execution that did it, if any
+pub async fn get_program_progress(event: Request, connector: &DBConnector) -> Result<Response<Body>, Error> {
+
+    // Get path parameters
+    let user_id = Uuid::parse_str(event.path_parameters().first("user_id").unwrap()).unwrap();
+    let program_id = Uuid::parse_str(event.path_parameters().first("program_id").unwrap()).unwrap();
+
+    // Check user in claim
+    match extract_sub(event.headers(), Some(user_id)) {
+        Ok(_) => (),
+        Err(resp) => return Ok(resp)
+    };
+
+    match select_program_progress(program_id, user_id, connector).await {
+        Ok(progress) => Ok(build_resp(StatusCode::OK, progress)),
+        Err(DBError::ItemNotFound(mes)) => Ok(build_resp(StatusCode::NOT_FOUND, mes)),
+        Err(error) => {
+            error!("INTERNAL SERVER ERROR: {}", error);
+            Ok(build_resp(StatusCode::INTERNAL_SERVER_ERROR, ""))
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use chrono::Utc;
+    use lambda_http::http::header::{AUTHORIZATION, HeaderValue};
+    use salamandra_server::lib::utils::tests::{pg_container, insert_helper, test_jwt, Items};
+    use salamandra_server::lib::db::programs_db::{insert_enrolment, insert_program};
+    use salamandra_server::lib::db::workout_executions_db::insert_workout_execution;
+    use salamandra_server::lib::db::workout_templates_db::lookup_workout_template;
+    use salamandra_server::lib::models::program_models::{NewProgram, ProgramProgress, ProgramSlotRequest};
+    use salamandra_server::lib::models::workout_execution_models::NewWorkoutExecution;
+
+    // TEST CASES
+    // * Never enrolled
+    // * Success, one of two slots done
+
+    #[tokio::test]
+    async fn test_get_program_progress() {
+        let (connector, _container) = pg_container().await;
+        let template_id = insert_helper(1, Items::WkTemplates, &connector, None).await[0];
+        let user_id = lookup_workout_template(template_id, &connector).await.unwrap().user_id;
+        let slots = [
+            ProgramSlotRequest { workout_template_id: template_id, week: 1, day: 1, reps: None, intensity: None },
+            ProgramSlotRequest { workout_template_id: template_id, week: 1, day: 3, reps: None, intensity: None },
+        ];
+        let program = insert_program(&NewProgram { user_id, name: "Block".to_string(), description: None, weeks: 1 }, &slots, &connector).await.unwrap();
+
+        let request = || {
+            let mut req = Request::default();
+            req.headers_mut().insert(AUTHORIZATION, HeaderValue::from_str(&test_jwt(user_id)).unwrap());
+            req.with_path_parameters(HashMap::from([
+                ("user_id".to_string(), user_id.to_string()),
+                ("program_id".to_string(), program.program.id.to_string()),
+            ]))
+        };
+        let response = get_program_progress(request(), &connector).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+        insert_enrolment(program.program.id, user_id, Utc::now().naive_utc().date(), &connector).await.unwrap();
+        let new_execution = NewWorkoutExecution { workout_template_id: template_id, program_slot_id: Some(program.slots[0].id), ..Default::default() };
+        insert_workout_execution(&new_execution, &connector).await.unwrap();
+
+        let response = get_program_progress(request(), &connector).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        if let Body::Text(body) = response.into_body() {
+            let progress: ProgramProgress = serde_json::from_str(&body).unwrap();
+            assert_eq!((progress.completed, progress.total), (1, 2));
+            assert!(progress.slots[1].workout_execution_id.is_none());
+        } else {
+            panic!("Expected a text body");
+        }
+    }
+}
@@ -0,0 +1,78 @@
+use lambda_http::{Error, Request, Response, Body, RequestExt};
+use lambda_http::http::StatusCode;
+use tracing::error;
+use uuid::Uuid;
+
+use salamandra_server::lib::db::schedules_db::select_scheduled_workouts;
+use salamandra_server::lib::models::schedule_models::ScheduleList;
+use salamandra_server::lib::utils::handlers::{build_resp, extract_sub, pagination};
+use salamandra_server::lib::db::DBConnector;
+
+/// Scheduled workouts of the user, latest first
+/// * Optional `limit` and `offset`
+pub async fn get_schedule(event: Request, connector: &DBConnector) -> Result<Response<Body>, Error> {
+
+    // Get path parameter
+    let user_id = Uuid::parse_str(event.path_parameters().first("user_id").unwrap()).unwrap();
+
+    // Check user in claim
+    match extract_sub(event.headers(), Some(user_id)) {
+        Ok(_) => (),
+        Err(resp) => return Ok(resp)
+    };
+
+    let (limit, offset) = match pagination(&event) {
+        Ok(page) => page,
+        Err(resp) => return Ok(resp)
+    };
+
+    match select_scheduled_workouts(user_id, limit + 1, offset, connector).await {
+        Ok(scheduled_workouts) => Ok(build_resp(StatusCode::OK, ScheduleList::page(scheduled_workouts, limit, offset))),
+        Err(error) => {
+            error!("INTERNAL SERVER ERROR: {}", error);
+            Ok(build_resp(StatusCode::INTERNAL_SERVER_ERROR, ""))
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use chrono::NaiveDate;
+    use lambda_http::http::header::{AUTHORIZATION, HeaderValue};
+    use salamandra_server::lib::utils::tests::{pg_container, insert_helper, test_jwt, Items};
+    use salamandra_server::lib::db::schedules_db::insert_scheduled_workout;
+    use salamandra_server::lib::db::workout_templates_db::lookup_workout_template;
+    use salamandra_server::lib::models::schedule_models::NewScheduledWorkout;
+
+    // TEST CASES
+    // * Success, paginated
+
+    #[tokio::test]
+    async fn test_get_schedule() {
+        let (connector, _container) = pg_container().await;
+        let template_id = insert_helper(1, Items::WkTemplates, &connector, None).await[0];
+        let user_id = lookup_workout_template(template_id, &connector).await.unwrap().user_id;
+        for n in 1..=3 {
+            let start_date = NaiveDate::from_ymd_opt(2024, 11, n).unwrap();
+            insert_scheduled_workout(&NewScheduledWorkout { user_id, workout_template_id: template_id, start_date, end_date: None, weekdays: None }, &connector).await.unwrap();
+        }
+
+        let mut req = Request::default();
+        req.headers_mut().insert(AUTHORIZATION, HeaderValue::from_str(&test_jwt(user_id)).unwrap());
+        let req = req.with_path_parameters(HashMap::from([("user_id".to_string(), user_id.to_string())]))
+            .with_query_string_parameters(HashMap::from([("limit".to_string(), "2".to_string())]));
+
+        let response = get_schedule(req, &connector).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        if let Body::Text(body) = response.into_body() {
+            let list: ScheduleList = serde_json::from_str(&body).unwrap();
+            assert_eq!(list.scheduled_workouts.len(), 2);
+            assert_eq!(list.next_offset, Some(2));
+        } else {
+            panic!("Expected a text body");
+        }
+    }
+}
@@ -0,0 +1,101 @@
+use lambda_http::{Error, Request, Response, Body, RequestExt};
+use lambda_http::http::StatusCode;
+use tracing::error;
+use uuid::Uuid;
+
+use salamandra_server::lib::db::progression_db::select_template_progression;
+use salamandra_server::lib::models::unit_models::InUnits;
+use salamandra_server::lib::utils::handlers::{build_resp, extract_sub, request_units};
+use salamandra_server::lib::db::DBConnector;
+use salamandra_server::lib::errors::DBError;
+
+/// Targets of each element of the user's template for the next session
+/// * Elements with a progression rule are evaluated against the latest executions of the template
+/// * Weights in the user's units, unless overridden with `units`
+pub async fn get_template_progression(event: Request, connector: &DBConnector) -> Result<Response<Body>, Error> {
+
+    // Get path parameters
+    let user_id = Uuid::parse_str(event.path_parameters().first("user_id").unwrap()).unwrap();
+    let workout_template_id = Uuid::parse_str(event.path_parameters().first("workout_template_id").unwrap()).unwrap();
+
+    // Check user in claim
+    match extract_sub(event.headers(), Some(user_id)) {
+        Ok(_) => (),
+        Err(resp) => return Ok(resp)
+    };
+
+    let units = match request_units(&event, user_id, connector).await {
+        Ok(units) => units,
+        Err(resp) => return Ok(resp)
+    };
+
+    match select_template_progression(workout_template_id, user_id, connector).await {
+        Ok(progression) => Ok(build_resp(StatusCode::OK, progression.in_units(&units))),
+        Err(DBError::ItemNotFound(mes)) => Ok(build_resp(StatusCode::NOT_FOUND, mes)),
+        Err(error) => {
+            error!("INTERNAL SERVER ERROR: {}", error);
+            Ok(build_resp(StatusCode::INTERNAL_SERVER_ERROR, ""))
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use lambda_http::http::header::{AUTHORIZATION, HeaderValue};
+    use salamandra_server::lib::utils::tests::{pg_container, insert_helper, test_jwt, Items};
+    use salamandra_server::lib::db::progression_db::upsert_progression_rule;
+    use salamandra_server::lib::db::wk_execution_elements_db::insert_batch_wk_execution_elements;
+    use salamandra_server::lib::db::wk_template_elements_db::insert_batch_wk_template_elements;
+    use salamandra_server::lib::db::workout_executions_db::insert_workout_execution;
+    use salamandra_server::lib::db::workout_templates_db::lookup_workout_template;
+    use salamandra_server::lib::models::progression_models::{NewProgressionRule, Outcome, TemplateProgression, LINEAR};
+    use salamandra_server::lib::models::wk_execution_elements_models::NewWkExecutionElement;
+    use salamandra_server::lib::models::wk_template_elements_models::NewWkTemplateElement;
+    use salamandra_server::lib::models::workout_execution_models::NewWorkoutExecution;
+
+    // TEST CASES
+    // * Template of another user
+    // * Success after a completed session
+
+    fn progression_request(user_id: Uuid, workout_template_id: Uuid) -> Request {
+        let mut req = Request::default();
+        req.headers_mut().insert(AUTHORIZATION, HeaderValue::from_str(&test_jwt(user_id)).unwrap());
+        req.with_path_parameters(HashMap::from([
+            ("user_id".to_string(), user_id.to_string()),
+            ("workout_template_id".to_string(), workout_template_id.to_string()),
+        ]))
+        .with_query_string_parameters(HashMap::from([("units".to_string(), "metric".to_string())]))
+    }
+
+    #[tokio::test]
+    async fn test_get_template_progression() {
+        let (connector, _container) = pg_container().await;
+        let template_id = insert_helper(1, Items::WkTemplates, &connector, None).await[0];
+        let user_id = lookup_workout_template(template_id, &connector).await.unwrap().user_id;
+        let stranger = insert_helper(1, Items::Users, &connector, None).await[0];
+        let exercise_id = insert_helper(1, Items::Exercises, &connector, None).await[0];
+        let new_element = NewWkTemplateElement { workout_template_id: template_id, exercise_id, reps: 5, sets: 1, weight: Some(100.0), ..Default::default() };
+        let element_id = insert_batch_wk_template_elements(&vec![new_element], &connector).await.unwrap()[0].id;
+        let new_rule = NewProgressionRule { wk_template_element_id: element_id, kind: LINEAR, weight_increment: 5.0, min_reps: None, max_reps: None, deload_after: None, deload_percent: 10 };
+        upsert_progression_rule(template_id, user_id, &new_rule, &connector).await.unwrap();
+        let execution = insert_workout_execution(&NewWorkoutExecution { workout_template_id: template_id, ..Default::default() }, &connector).await.unwrap();
+        let set = NewWkExecutionElement { workout_execution_id: execution.id, exercise_id, reps: 5, weight: Some(100.0), time: 30, ..Default::default() };
+        insert_batch_wk_execution_elements(&vec![set], &connector).await.unwrap();
+
+        let response = get_template_progression(progression_request(stranger, template_id), &connector).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+        let response = get_template_progression(progression_request(user_id, template_id), &connector).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        if let Body::Text(body) = response.into_body() {
+            let progression: TemplateProgression = serde_json::from_str(&body).unwrap();
+            assert_eq!(progression.workout_execution_id, Some(execution.id));
+            assert_eq!((progression.targets[0].outcome, progression.targets[0].weight), (Outcome::Increase, Some(105.0)));
+        } else {
+            panic!("Expected a text body");
+        }
+    }
+}
@@ -0,0 +1,88 @@
+use chrono::Utc;
+use lambda_http::{Error, Request, Response, Body, RequestExt};
+use lambda_http::http::StatusCode;
+use tracing::error;
+use uuid::Uuid;
+
+use salamandra_server::lib::db::programs_db::select_todays_workout;
+use salamandra_server::lib::models::unit_models::InUnits;
+use salamandra_server::lib::utils::handlers::{build_resp, extract_sub, request_units};
+use salamandra_server::lib::db::DBConnector;
+use salamandra_server::lib::errors::DBError;
+
+/// Workout the active program has planned for today, with the week's reps and intensity applied
+/// * Weights in the user's units, unless overridden with `units`
+pub async fn get_todays_workout(event: Request, connector: &DBConnector) -> Result<Response<Body>, Error> {
+
+    // Get path parameter
+    let user_id = Uuid::parse_str(event.path_parameters().first("user_id").unwrap()).unwrap();
+
+    // Check user in claim
+    match extract_sub(event.headers(), Some(user_id)) {
+        Ok(_) => (),
+        Err(resp) => return Ok(resp)
+    };
+
+    let units = match request_units(&event, user_id, connector).await {
+        Ok(units) => units,
+        Err(resp) => return Ok(resp)
+    };
+
+    match select_todays_workout(user_id, Utc::now().naive_utc().date(), connector).await {
+        Ok(todays) => Ok(build_resp(StatusCode::OK, todays.in_units(&units))),
+        Err(DBError::ItemNotFound(mes)) => Ok(build_resp(StatusCode::NOT_FOUND, mes)),
+        Err(error) => {
+            error!("INTERNAL SERVER ERROR: {}", error);
+            Ok(build_resp(StatusCode::INTERNAL_SERVER_ERROR, ""))
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use lambda_http::http::header::{AUTHORIZATION, HeaderValue};
+    use salamandra_server::lib::utils::tests::{pg_container, insert_helper, test_jwt, Items};
+    use salamandra_server::lib::db::programs_db::{insert_enrolment, insert_program};
+    use salamandra_server::lib::db::wk_template_elements_db::lookup_wk_template_element;
+    use salamandra_server::lib::db::workout_templates_db::lookup_workout_template;
+    use salamandra_server::lib::models::program_models::{NewProgram, ProgramSlotRequest, TodaysWorkout};
+
+    // TEST CASES
+    // * Not enrolled
+    // * Success, template with the overrides of the slot
+
+    #[tokio::test]
+    async fn test_get_todays_workout() {
+        let (connector, _container) = pg_container().await;
+        let element_id = insert_helper(1, Items::WkTemplateElements, &connector, None).await[0];
+        let template_id = lookup_wk_template_element(element_id, &connector).await.unwrap().workout_template_id;
+        let user_id = lookup_workout_template(template_id, &connector).await.unwrap().user_id;
+
+        let request = || {
+            let mut req = Request::default();
+            req.headers_mut().insert(AUTHORIZATION, HeaderValue::from_str(&test_jwt(user_id)).unwrap());
+            req.with_path_parameters(HashMap::from([("user_id".to_string(), user_id.to_string())]))
+        };
+        let response = get_todays_workout(request(), &connector).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+        let slots = [ProgramSlotRequest { workout_template_id: template_id, week: 1, day: 1, reps: Some(8), intensity: None }];
+        let program_id = insert_program(&NewProgram { user_id, name: "Block".to_string(), description: None, weeks: 1 }, &slots, &connector).await.unwrap().program.id;
+        insert_enrolment(program_id, user_id, Utc::now().naive_utc().date(), &connector).await.unwrap();
+
+        let response = get_todays_workout(request(), &connector).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        if let Body::Text(body) = response.into_body() {
+            let todays: TodaysWorkout = serde_json::from_str(&body).unwrap();
+            assert_eq!((todays.week, todays.day), (Some(1), Some(1)));
+            let template = todays.workout_template.unwrap();
+            assert_eq!(template.workout_template.id, template_id);
+            assert!(template.elements.iter().all(|element| element.reps == 8));
+        } else {
+            panic!("Expected a text body");
+        }
+    }
+}
@@ -1,3 +1,4 @@
+use std::collections::HashMap;
 use lambda_http::{Error, Request, Response, Body, RequestExt};
 use lambda_http::http::StatusCode;
 use salamandra_server::lib::models::workout_templates_models::{WkTemplateWithElements, WorkoutTemplateFull};
@@ -6,12 +7,17 @@ use uuid::Uuid;
 
 use salamandra_server::lib::db::workout_templates_db::lookup_workout_template;
 use salamandra_server::lib::db::wk_template_elements_db::{select_wk_template_element_by_template, select_wk_template_element_by_template_full};
-use salamandra_server::lib::utils::handlers::{build_resp, extract_sub};
+use salamandra_server::lib::db::exercise_aliases_db::select_localized_names;
+use salamandra_server::lib::db::exercise_content_db::select_exercise_contents;
+use salamandra_server::lib::models::exercise_content_models::WithExerciseContent;
+use salamandra_server::lib::models::unit_models::InUnits;
+use salamandra_server::lib::utils::handlers::{accept_languages, build_resp, extract_sub, request_units};
 use salamandra_server::lib::db::DBConnector;
 use salamandra_server::lib::errors::DBError;
 
 
 /// Fetch template from a given user
+/// * Weights in the user's units, unless overridden with `units`
 pub async fn get_workout_template(event: Request, connector: &DBConnector) -> Result<Response<Body>, Error> {
 
     // Get path parameter
@@ -23,12 +29,21 @@ pub async fn get_workout_template(event: Request, connector: &DBConnector) -> Re
         Ok(_) => (),
         Err(resp) => return Ok(resp)
     };
+
+    let units = match request_units(&event, user_id, connector).await {
+        Ok(units) => units,
+        Err(resp) => return Ok(resp)
+    };
    
     // Get query parameter
     let full: bool = match event.query_string_parameters().first("full") {
         Some(val) => val == "true",
         None => false
     };
+    let include_content: bool = match event.query_string_parameters().first("include_content") {
+        Some(val) => val == "true",
+        None => false
+    };
 
     let workout_template = match lookup_workout_template(workout_template_id, connector).await  {
         Ok(template) => {
@@ -47,7 +62,7 @@ pub async fn get_workout_template(event: Request, connector: &DBConnector) -> Re
     // Select template and build response
     match full {
         true => {
-        let full_elements = match select_wk_template_element_by_template_full(workout_template_id, connector).await {
+        let mut full_elements = match select_wk_template_element_by_template_full(workout_template_id, connector).await {
             Ok(vector) => vector,
             Err(mes) => {
                 error!("INTERNAL SERVER ERROR: {}", mes);
@@ -55,12 +70,45 @@ pub async fn get_workout_template(event: Request, connector: &DBConnector) -> Re
             }
         };
 
+        // Localize exercise names
+        let exercise_ids: Vec<Uuid> = full_elements.iter().map(|element| element.exercise_id).collect();
+        let localized_names = match select_localized_names(&exercise_ids, &accept_languages(event.headers()), connector).await {
+            Ok(names) => names,
+            Err(mes) => {
+                error!("INTERNAL SERVER ERROR: {}", mes);
+                return Ok(build_resp(StatusCode::INTERNAL_SERVER_ERROR, ""))
+            }
+        };
+        for element in full_elements.iter_mut() {
+            if let Some(localized) = localized_names.get(&element.exercise_id) {
+                element.exercise_name = localized.clone();
+            }
+        }
+
+        // Embed instructional content if requested
+        let mut contents = HashMap::new();
+        if include_content {
+            contents = match select_exercise_contents(&exercise_ids, connector).await {
+                Ok(contents) => contents,
+                Err(mes) => {
+                    error!("INTERNAL SERVER ERROR: {}", mes);
+                    return Ok(build_resp(StatusCode::INTERNAL_SERVER_ERROR, ""))
+                }
+            };
+        }
+        let full_elements = full_elements.into_iter()
+            .map(|element| WithExerciseContent {
+                exercise_content: include_content.then(|| contents.get(&element.exercise_id).cloned().unwrap_or_default()),
+                element,
+            })
+            .collect();
+
         // Create models 
         let template = WorkoutTemplateFull {
             workout_template,
             elements: full_elements,
         };
-        Ok(build_resp(StatusCode::OK, template))
+        Ok(build_resp(StatusCode::OK, template.in_units(&units)))
     },
         false => {
             let elements = match select_wk_template_element_by_template(workout_template_id, connector).await {
@@ -76,7 +124,7 @@ pub async fn get_workout_template(event: Request, connector: &DBConnector) -> Re
                 workout_template,
                 elements,
             };
-            Ok(build_resp(StatusCode::OK, template))
+            Ok(build_resp(StatusCode::OK, template.in_units(&units)))
         },
     }
 }
@@ -85,15 +133,17 @@ pub async fn get_workout_template(event: Request, connector: &DBConnector) -> Re
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::collections::HashMap;
-    use lambda_http::http::header::{AUTHORIZATION, HeaderValue};
+    use lambda_http::http::header::{ACCEPT_LANGUAGE, AUTHORIZATION, HeaderValue};
     use salamandra_server::lib::utils::tests::{pg_container, test_jwt, insert_helper, Items};
+    use salamandra_server::lib::models::exercise_alias_models::NewExerciseAlias;
+    use salamandra_server::lib::db::exercise_aliases_db::insert_exercise_alias;
     use salamandra_server::lib::db::wk_template_elements_db::lookup_wk_template_element;
     use salamandra_server::lib::db::workout_templates_db::lookup_workout_template;
 
     // TEST CASES
     // * Non existing template
     // * Success full and not full
+    // * Full view with localized exercise names
     
     #[tokio::test]
     async fn test_get_workout_template_not_found() {
@@ -119,8 +169,11 @@ mod tests {
         let (connector, _container) = pg_container().await;
        
         let element_vector = insert_helper(5, Items::WkTemplateElements, &connector, None).await;
-        let template_id = lookup_wk_template_element(element_vector[0], &connector).await.unwrap().workout_template_id;
+        let first_element = lookup_wk_template_element(element_vector[0], &connector).await.unwrap();
+        let template_id = first_element.workout_template_id;
         let user_id = lookup_workout_template(template_id, &connector).await.unwrap().user_id;
+        let new_alias = NewExerciseAlias {exercise_id: first_element.exercise_id, locale: "es".to_string(), name: "Flexiones".to_string(), preferred: true};
+        let _ = insert_exercise_alias(&new_alias, &connector).await;
 
         let user_id_string = user_id.to_string();
         let jwt = test_jwt(user_id);
@@ -132,6 +185,7 @@ mod tests {
         let mut req = req.with_query_string_parameters(query_params);
 
         req.headers_mut().insert(AUTHORIZATION, HeaderValue::from_str(&jwt).unwrap());
+        req.headers_mut().insert(ACCEPT_LANGUAGE, HeaderValue::from_static("es-ES"));
         let req = req.with_path_parameters(HashMap::from([("user_id".to_string(), user_id_string), ("workout_template_id".into(), template_id.to_string())]));
 
 
@@ -141,10 +195,12 @@ mod tests {
         assert_eq!(response.status(), StatusCode::OK);
 
         if let Body::Text(body) = response.into_body() {
-            let template: Result<WorkoutTemplateFull, _> = serde_json::from_str(&body);
-            let id_vec: Vec<Uuid> = template.unwrap().elements.iter().map(|wkt| wkt.id.clone()).collect();
+            let template: WorkoutTemplateFull = serde_json::from_str(&body).unwrap();
+            let id_vec: Vec<Uuid> = template.elements.iter().map(|wkt| wkt.element.id).collect();
             assert_eq!(id_vec.len(), 5);
             assert_eq!(id_vec, element_vector);
+            assert_eq!(template.elements[0].element.exercise_name, "Flexiones");
+            assert!(template.elements[1].element.exercise_name.starts_with("Push-up"));
         }
     }
     
@@ -0,0 +1,119 @@
+use chrono::{Duration, NaiveDateTime};
+
+use salamandra_server::lib::models::schedule_models::ScheduledWorkout;
+
+/// Lines longer than this are folded, as RFC 5545 asks
+const MAX_LINE_OCTETS: usize = 75;
+const WEEKDAYS: [&str; 7] = ["MO", "TU", "WE", "TH", "FR", "SA", "SU"];
+
+/// iCalendar feed with one all-day event per scheduled workout, recurring ones as weekly rules
+pub fn calendar_feed(schedule: &[(ScheduledWorkout, String)], now: NaiveDateTime) -> String {
+    let mut lines = vec![
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        "PRODID:-//Salamandra//Workouts//EN".to_string(),
+        "CALSCALE:GREGORIAN".to_string(),
+        "X-WR-CALNAME:Workouts".to_string(),
+    ];
+    for (scheduled, template_name) in schedule {
+        // Recurrences that never hit one of their weekdays have nothing to show
+        let Some(first) = scheduled.first_occurrence() else { continue };
+        lines.push("BEGIN:VEVENT".to_string());
+        lines.push(format!("UID:{}@salamandra", scheduled.id));
+        lines.push(format!("DTSTAMP:{}", now.format("%Y%m%dT%H%M%SZ")));
+        lines.push(format!("DTSTART;VALUE=DATE:{}", first.format("%Y%m%d")));
+        lines.push(format!("DTEND;VALUE=DATE:{}", (first + Duration::days(1)).format("%Y%m%d")));
+        lines.push(format!("SUMMARY:{}", escape(template_name)));
+        if let Some(weekdays) = &scheduled.weekdays {
+            let by_day: Vec<&str> = weekdays.iter().map(|day| WEEKDAYS[(*day - 1) as usize]).collect();
+            let until = scheduled.end_date.map(|end_date| format!(";UNTIL={}", end_date.format("%Y%m%d"))).unwrap_or_default();
+            lines.push(format!("RRULE:FREQ=WEEKLY;BYDAY={}{}", by_day.join(","), until));
+        }
+        lines.push("END:VEVENT".to_string());
+    }
+    lines.push("END:VCALENDAR".to_string());
+
+    lines.iter().map(|line| fold(line)).collect::<Vec<String>>().join("\r\n") + "\r\n"
+}
+
+/// Line breaks of any kind become escaped newlines, a bare CR would end the line
+fn escape(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace("\r\n", "\\n")
+        .replace(['\r', '\n'], "\\n")
+}
+
+/// Splits a line in chunks of at most 75 octets, without cutting characters, continuation lines start with a space
+fn fold(line: &str) -> String {
+    let mut folded = String::with_capacity(line.len());
+    let mut octets = 0;
+    for c in line.chars() {
+        if octets + c.len_utf8() > MAX_LINE_OCTETS {
+            folded.push_str("\r\n ");
+            octets = 1;
+        }
+        folded.push(c);
+        octets += c.len_utf8();
+    }
+    folded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+    use uuid::Uuid;
+
+    // TEST CASES
+    // * One-off and recurring events
+    // * Escaping and folding
+
+    fn day(n: u32) -> NaiveDate {
+        // 2024-11-04 is a Monday
+        NaiveDate::from_ymd_opt(2024, 11, n).unwrap()
+    }
+
+    fn scheduled(start_date: NaiveDate, end_date: Option<NaiveDate>, weekdays: Option<Vec<i16>>) -> ScheduledWorkout {
+        ScheduledWorkout {
+            id: Uuid::new_v4(),
+            user_id: Uuid::new_v4(),
+            workout_template_id: Uuid::new_v4(),
+            start_date,
+            end_date,
+            weekdays,
+            date_created: day(1).and_hms_opt(0, 0, 0).unwrap(),
+        }
+    }
+
+    #[test]
+    fn test_calendar_feed() {
+        let now = day(1).and_hms_opt(8, 30, 0).unwrap();
+        let one_off = scheduled(day(6), None, None);
+        let recurring = scheduled(day(5), Some(day(30)), Some(vec![1, 3, 5]));
+        let never = scheduled(day(5), Some(day(6)), Some(vec![7]));
+        let feed = calendar_feed(&[(one_off.clone(), "Legs".to_string()), (recurring, "Push".to_string()), (never, "Pull".to_string())], now);
+
+        assert!(feed.starts_with("BEGIN:VCALENDAR\r\n") && feed.ends_with("END:VCALENDAR\r\n"));
+        assert_eq!(feed.matches("BEGIN:VEVENT").count(), 2);
+        assert!(feed.contains(&format!("UID:{}@salamandra\r\nDTSTAMP:20241101T083000Z\r\nDTSTART;VALUE=DATE:20241106\r\nDTEND;VALUE=DATE:20241107\r\nSUMMARY:Legs\r\nEND:VEVENT", one_off.id)));
+        assert!(feed.contains("DTSTART;VALUE=DATE:20241106\r\nDTEND;VALUE=DATE:20241107\r\nSUMMARY:Push\r\nRRULE:FREQ=WEEKLY;BYDAY=MO,WE,FR;UNTIL=20241130\r\n"));
+        assert!(!feed.contains("Pull"));
+    }
+
+    #[test]
+    fn test_escape_and_fold() {
+        assert_eq!(escape("Push; chest, triceps\\arms\nday"), "Push\\; chest\\, triceps\\\\arms\\nday");
+        assert_eq!(escape("Legs\r\nday\rRRULE:FREQ=DAILY"), "Legs\\nday\\nRRULE:FREQ=DAILY");
+
+        let line = format!("SUMMARY:{}", "é".repeat(50));
+        let folded = fold(&line);
+        let parts: Vec<&str> = folded.split("\r\n").collect();
+        assert!(parts.len() > 1);
+        assert!(parts.iter().all(|part| part.len() <= MAX_LINE_OCTETS));
+        assert!(parts[1..].iter().all(|part| part.starts_with(' ')));
+        assert_eq!(folded.replace("\r\n ", ""), line);
+        assert_eq!(fold("SHORT"), "SHORT");
+    }
+}
@@ -2,11 +2,52 @@ mod create_workout_template;
 mod delete_workout_template;
 mod get_all_workout_templates;
 mod get_workout_template;
+mod create_program;
+mod get_programs;
+mod get_program;
+mod delete_program;
+mod enrol_program;
+mod unenrol_program;
+mod get_program_progress;
+mod get_todays_workout;
+mod create_scheduled_workout;
+mod get_schedule;
+mod delete_scheduled_workout;
+mod get_calendar;
+mod create_calendar_token;
+mod revoke_calendar_token;
+mod get_calendar_feed;
+mod ical;
+mod put_progression_rule;
+mod delete_progression_rule;
+mod get_template_progression;
+mod start_workout;
+mod save_workout_template;
 
 use create_workout_template::create_workout_template;
 use delete_workout_template::delete_workout_template_;
 use get_all_workout_templates::get_all_workout_templates;
 use get_workout_template::get_workout_template;
+use create_program::create_program;
+use get_programs::get_programs;
+use get_program::get_program;
+use delete_program::delete_program;
+use enrol_program::enrol_program;
+use unenrol_program::unenrol_program;
+use get_program_progress::get_program_progress;
+use get_todays_workout::get_todays_workout;
+use create_scheduled_workout::create_scheduled_workout;
+use get_schedule::get_schedule;
+use delete_scheduled_workout::delete_scheduled_workout;
+use get_calendar::get_calendar;
+use create_calendar_token::create_calendar_token;
+use revoke_calendar_token::revoke_calendar_token;
+use get_calendar_feed::get_calendar_feed;
+use put_progression_rule::put_progression_rule;
+use delete_progression_rule::delete_progression_rule;
+use get_template_progression::get_template_progression;
+use start_workout::start_workout;
+use save_workout_template::save_workout_template;
 use salamandra_server::lib::db::DBConnector;
 
 use lambda_http::{run, service_fn, Error, Request, Response, Body, tracing};
@@ -26,11 +67,45 @@ async fn router(event: Request) -> Result<Response<Body>, Error> {
     let connector = DBConnector::default();
     let specific_template = Regex::new(&format!(r"^/users/{}/workout-templates/{}$", UUID_PATTERN, UUID_PATTERN));
     let not_specific = Regex::new(&format!(r"^/users/{}/workout-templates$", UUID_PATTERN));
+    let progression = Regex::new(&format!(r"^/users/{}/workout-templates/{}/progression$", UUID_PATTERN, UUID_PATTERN));
+    let element_progression = Regex::new(&format!(r"^/users/{}/workout-templates/{}/elements/{}/progression$", UUID_PATTERN, UUID_PATTERN, UUID_PATTERN));
+    let start = Regex::new(&format!(r"^/users/{}/workout-templates/{}/start$", UUID_PATTERN, UUID_PATTERN));
+    let save = Regex::new(&format!(r"^/users/{}/workout-templates/{}/save$", UUID_PATTERN, UUID_PATTERN));
+    let programs = Regex::new(&format!(r"^/users/{}/programs$", UUID_PATTERN));
+    let specific_program = Regex::new(&format!(r"^/users/{}/programs/{}$", UUID_PATTERN, UUID_PATTERN));
+    let enrolment = Regex::new(&format!(r"^/users/{}/programs/{}/enrolment$", UUID_PATTERN, UUID_PATTERN));
+    let progress = Regex::new(&format!(r"^/users/{}/programs/{}/progress$", UUID_PATTERN, UUID_PATTERN));
+    let today = Regex::new(&format!(r"^/users/{}/programs/today$", UUID_PATTERN));
+    let schedule = Regex::new(&format!(r"^/users/{}/schedule$", UUID_PATTERN));
+    let specific_schedule = Regex::new(&format!(r"^/users/{}/schedule/{}$", UUID_PATTERN, UUID_PATTERN));
+    let calendar = Regex::new(&format!(r"^/users/{}/calendar$", UUID_PATTERN));
+    let calendar_token = Regex::new(&format!(r"^/users/{}/calendar/token$", UUID_PATTERN));
+    let calendar_feed = Regex::new(&format!(r"^/calendar/{}\.ics$", UUID_PATTERN));
     let response = match (event.method(), path) {
         (&Method::POST, _) if not_specific.clone().unwrap().is_match(path) => create_workout_template(event, &connector).await,
         (&Method::DELETE, _) if specific_template.clone().unwrap().is_match(path) => delete_workout_template_(event, &connector).await,
         (&Method::GET, _) if not_specific.unwrap().is_match(path) => get_all_workout_templates(event, &connector).await,
         (&Method::GET, _) if specific_template.unwrap().is_match(path) => get_workout_template(event, &connector).await,
+        (&Method::GET, _) if progression.unwrap().is_match(path) => get_template_progression(event, &connector).await,
+        (&Method::PUT, _) if element_progression.clone().unwrap().is_match(path) => put_progression_rule(event, &connector).await,
+        (&Method::DELETE, _) if element_progression.unwrap().is_match(path) => delete_progression_rule(event, &connector).await,
+        (&Method::POST, _) if start.unwrap().is_match(path) => start_workout(event, &connector).await,
+        (&Method::POST, _) if save.unwrap().is_match(path) => save_workout_template(event, &connector).await,
+        (&Method::POST, _) if programs.clone().unwrap().is_match(path) => create_program(event, &connector).await,
+        (&Method::GET, _) if programs.unwrap().is_match(path) => get_programs(event, &connector).await,
+        (&Method::GET, _) if today.unwrap().is_match(path) => get_todays_workout(event, &connector).await,
+        (&Method::GET, _) if specific_program.clone().unwrap().is_match(path) => get_program(event, &connector).await,
+        (&Method::DELETE, _) if specific_program.unwrap().is_match(path) => delete_program(event, &connector).await,
+        (&Method::POST, _) if enrolment.clone().unwrap().is_match(path) => enrol_program(event, &connector).await,
+        (&Method::DELETE, _) if enrolment.unwrap().is_match(path) => unenrol_program(event, &connector).await,
+        (&Method::GET, _) if progress.unwrap().is_match(path) => get_program_progress(event, &connector).await,
+        (&Method::POST, _) if schedule.clone().unwrap().is_match(path) => create_scheduled_workout(event, &connector).await,
+        (&Method::GET, _) if schedule.unwrap().is_match(path) => get_schedule(event, &connector).await,
+        (&Method::DELETE, _) if specific_schedule.unwrap().is_match(path) => delete_scheduled_workout(event, &connector).await,
+        (&Method::GET, _) if calendar.unwrap().is_match(path) => get_calendar(event, &connector).await,
+        (&Method::POST, _) if calendar_token.clone().unwrap().is_match(path) => create_calendar_token(event, &connector).await,
+        (&Method::DELETE, _) if calendar_token.unwrap().is_match(path) => revoke_calendar_token(event, &connector).await,
+        (&Method::GET, _) if calendar_feed.unwrap().is_match(path) => get_calendar_feed(event, &connector).await,
         _ => not_found()
     };
 
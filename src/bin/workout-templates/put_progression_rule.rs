@@ -0,0 +1,109 @@
+use lambda_http::{Error, Request, Response, Body, RequestExt};
+use lambda_http::http::StatusCode;
+use tracing::error;
+use uuid::Uuid;
+
+use salamandra_server::lib::db::progression_db::upsert_progression_rule;
+use salamandra_server::lib::models::progression_models::ProgressionRuleRequest;
+use salamandra_server::lib::models::unit_models::InUnits;
+use salamandra_server::lib::utils::handlers::{build_resp, extract_sub, request_units};
+use salamandra_server::lib::db::DBConnector;
+use salamandra_server::lib::errors::DBError;
+
+/// Set how an element of the user's template progresses, replacing its previous rule
+/// * `weight_increment` in the user's units, unless overridden with `units`
+pub async fn put_progression_rule(event: Request, connector: &DBConnector) -> Result<Response<Body>, Error> {
+
+    // Get path parameters
+    let user_id = Uuid::parse_str(event.path_parameters().first("user_id").unwrap()).unwrap();
+    let workout_template_id = Uuid::parse_str(event.path_parameters().first("workout_template_id").unwrap()).unwrap();
+    let element_id = Uuid::parse_str(event.path_parameters().first("wk_template_element_id").unwrap()).unwrap();
+
+    // Check user in claim
+    match extract_sub(event.headers(), Some(user_id)) {
+        Ok(_) => (),
+        Err(resp) => return Ok(resp)
+    };
+
+    let units = match request_units(&event, user_id, connector).await {
+        Ok(units) => units,
+        Err(resp) => return Ok(resp)
+    };
+
+    // Check and extract payload
+    let req: ProgressionRuleRequest = match event.into_body() {
+        Body::Text(body) => match serde_json::from_str(&body) {
+            Ok(req) => req,
+            Err(_) => return Ok(build_resp(StatusCode::BAD_REQUEST, "Invalid payload")),
+        },
+        _ => return Ok(build_resp(StatusCode::BAD_REQUEST, "Invalid payload: can't extract body")),
+    };
+    if let Err(mes) = req.validate() {
+        return Ok(build_resp(StatusCode::BAD_REQUEST, format!("Invalid payload: {}", mes)));
+    }
+
+    match upsert_progression_rule(workout_template_id, user_id, &req.to_new_rule(element_id, &units), connector).await {
+        Ok(rule) => Ok(build_resp(StatusCode::OK, rule.in_units(&units))),
+        Err(DBError::ItemNotFound(mes)) => Ok(build_resp(StatusCode::NOT_FOUND, mes)),
+        Err(error) => {
+            error!("INTERNAL SERVER ERROR: {}", error);
+            Ok(build_resp(StatusCode::INTERNAL_SERVER_ERROR, ""))
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use lambda_http::http::header::{AUTHORIZATION, HeaderValue};
+    use salamandra_server::lib::utils::tests::{pg_container, insert_helper, test_jwt, Items};
+    use salamandra_server::lib::db::wk_template_elements_db::insert_batch_wk_template_elements;
+    use salamandra_server::lib::db::workout_templates_db::lookup_workout_template;
+    use salamandra_server::lib::models::progression_models::{ProgressionRule, DOUBLE_PROGRESSION};
+    use salamandra_server::lib::models::wk_template_elements_models::NewWkTemplateElement;
+
+    // TEST CASES
+    // * Invalid payload
+    // * Element of another template
+    // * Success in pounds
+
+    fn put_request(user_id: Uuid, workout_template_id: Uuid, element_id: Uuid, body: serde_json::Value) -> Request {
+        let mut req = Request::new(Body::Text(body.to_string()));
+        req.headers_mut().insert(AUTHORIZATION, HeaderValue::from_str(&test_jwt(user_id)).unwrap());
+        req.with_path_parameters(HashMap::from([
+            ("user_id".to_string(), user_id.to_string()),
+            ("workout_template_id".to_string(), workout_template_id.to_string()),
+            ("wk_template_element_id".to_string(), element_id.to_string()),
+        ]))
+        .with_query_string_parameters(HashMap::from([("units".to_string(), "imperial".to_string())]))
+    }
+
+    #[tokio::test]
+    async fn test_put_progression_rule() {
+        let (connector, _container) = pg_container().await;
+        let template_ids = insert_helper(2, Items::WkTemplates, &connector, None).await;
+        let user_id = lookup_workout_template(template_ids[0], &connector).await.unwrap().user_id;
+        let exercise_id = insert_helper(1, Items::Exercises, &connector, None).await[0];
+        let new_element = NewWkTemplateElement { workout_template_id: template_ids[0], exercise_id, reps: 8, sets: 3, ..Default::default() };
+        let element_id = insert_batch_wk_template_elements(&vec![new_element], &connector).await.unwrap()[0].id;
+
+        let invalid = serde_json::json!({"kind": DOUBLE_PROGRESSION, "weight_increment": 5.0, "min_reps": 12, "max_reps": 8});
+        let response = put_progression_rule(put_request(user_id, template_ids[0], element_id, invalid), &connector).await.unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        let body = serde_json::json!({"kind": DOUBLE_PROGRESSION, "weight_increment": 5.0, "min_reps": 8, "max_reps": 12, "deload_after": 3});
+        let response = put_progression_rule(put_request(user_id, template_ids[1], element_id, body.clone()), &connector).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+        let response = put_progression_rule(put_request(user_id, template_ids[0], element_id, body), &connector).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        if let Body::Text(body) = response.into_body() {
+            let rule: ProgressionRule = serde_json::from_str(&body).unwrap();
+            assert_eq!((rule.weight_increment, rule.min_reps, rule.deload_percent), (5.0, Some(8), 10));
+        } else {
+            panic!("Expected a text body");
+        }
+    }
+}
@@ -0,0 +1,184 @@
+use lambda_http::{Error, Request, Response, Body, RequestExt};
+use lambda_http::http::StatusCode;
+use tracing::error;
+use uuid::Uuid;
+
+use salamandra_server::lib::db::exercises_db::validate_exercises;
+use salamandra_server::lib::db::follows_db::can_view_profile;
+use salamandra_server::lib::db::workout_templates_db::{copy_workout_template, lookup_workout_template};
+use salamandra_server::lib::db::wk_template_elements_db::select_wk_template_element_by_template;
+use salamandra_server::lib::models::notification_models::{NewNotification, TEMPLATE_SAVED};
+use salamandra_server::lib::models::unit_models::InUnits;
+use salamandra_server::lib::utils::handlers::{build_resp, extract_sub, notify, request_units};
+use salamandra_server::lib::db::DBConnector;
+use salamandra_server::lib::errors::DBError;
+
+/// Save a copy of another user's template into the caller's templates
+/// * Only templates of profiles the caller can see, and without their owner's private exercises
+/// * The owner is notified unless they opted out
+/// * Weights in the caller's units, unless overridden with `units`
+pub async fn save_workout_template(event: Request, connector: &DBConnector) -> Result<Response<Body>, Error> {
+
+    // Get path parameters
+    let user_id = Uuid::parse_str(event.path_parameters().first("user_id").unwrap()).unwrap();
+    let workout_template_id = Uuid::parse_str(event.path_parameters().first("workout_template_id").unwrap()).unwrap();
+
+    // Any authenticated user, visibility is checked below
+    let viewer_id = match extract_sub(event.headers(), None) {
+        Ok(sub) => sub,
+        Err(resp) => return Ok(resp)
+    };
+
+    match can_view_profile(viewer_id, user_id, connector).await {
+        Ok(true) => (),
+        Ok(false) => return Ok(build_resp(StatusCode::FORBIDDEN, "This profile is private")),
+        Err(DBError::ItemNotFound(mes)) => return Ok(build_resp(StatusCode::NOT_FOUND, mes)),
+        Err(error) => {
+            error!("INTERNAL SERVER ERROR: {}", error);
+            return Ok(build_resp(StatusCode::INTERNAL_SERVER_ERROR, ""));
+        }
+    };
+
+    match lookup_workout_template(workout_template_id, connector).await {
+        Ok(template) if template.user_id == user_id => (),
+        Ok(_) => return Ok(build_resp(StatusCode::NOT_FOUND, "No template exists with the corresponding id")),
+        Err(DBError::ItemNotFound(mes)) => return Ok(build_resp(StatusCode::NOT_FOUND, mes)),
+        Err(error) => {
+            error!("INTERNAL SERVER ERROR: {}", error);
+            return Ok(build_resp(StatusCode::INTERNAL_SERVER_ERROR, ""));
+        }
+    };
+
+    // Retired exercises are allowed, the owner's private ones are not
+    let exercise_ids = match select_wk_template_element_by_template(workout_template_id, connector).await {
+        Ok(elements) => elements.iter().map(|element| element.exercise_id).collect(),
+        Err(error) => {
+            error!("INTERNAL SERVER ERROR: {}", error);
+            return Ok(build_resp(StatusCode::INTERNAL_SERVER_ERROR, ""));
+        }
+    };
+    match validate_exercises(exercise_ids, viewer_id, true, connector).await {
+        Ok(true) => (),
+        Ok(false) => return Ok(build_resp(StatusCode::BAD_REQUEST, "The template uses private exercises of its owner")),
+        Err(error) => {
+            error!("INTERNAL SERVER ERROR: {}", error);
+            return Ok(build_resp(StatusCode::INTERNAL_SERVER_ERROR, ""));
+        }
+    };
+
+    let units = match request_units(&event, viewer_id, connector).await {
+        Ok(units) => units,
+        Err(resp) => return Ok(resp)
+    };
+
+    match copy_workout_template(workout_template_id, user_id, viewer_id, connector).await {
+        Ok(copy) => {
+            let new_notification = NewNotification {
+                user_id,
+                kind: TEMPLATE_SAVED,
+                actor_id: Some(viewer_id),
+                workout_template_id: Some(workout_template_id),
+                ..Default::default()
+            };
+            notify(new_notification, connector).await;
+            Ok(build_resp(StatusCode::CREATED, copy.in_units(&units)))
+        },
+        Err(DBError::ItemNotFound(mes)) => Ok(build_resp(StatusCode::NOT_FOUND, mes)),
+        Err(error) => {
+            error!("INTERNAL SERVER ERROR: {}", error);
+            Ok(build_resp(StatusCode::INTERNAL_SERVER_ERROR, ""))
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use lambda_http::http::header::{AUTHORIZATION, HeaderValue};
+    use salamandra_server::lib::utils::tests::{pg_container, insert_helper, test_jwt, Items};
+    use salamandra_server::lib::db::exercises_db::insert_exercise;
+    use salamandra_server::lib::db::notifications_db::select_notifications;
+    use salamandra_server::lib::db::users_db::update_user;
+    use salamandra_server::lib::db::wk_template_elements_db::{insert_batch_wk_template_elements, lookup_wk_template_element};
+    use salamandra_server::lib::db::workout_templates_db::select_workout_template_by_user;
+    use salamandra_server::lib::models::exercise_models::NewExercise;
+    use salamandra_server::lib::models::user_models::UncompleteUser;
+    use salamandra_server::lib::models::wk_template_elements_models::NewWkTemplateElement;
+    use salamandra_server::lib::models::workout_templates_models::WkTemplateWithElements;
+
+    // TEST CASES
+    // * Private profile, template of someone else
+    // * Template with the owner's private exercises
+    // * Success, notifying the owner unless they opted out
+
+    fn save_request(user_id: Uuid, workout_template_id: Uuid, sub: Uuid) -> Request {
+        let mut req = Request::default();
+        req.headers_mut().insert(AUTHORIZATION, HeaderValue::from_str(&test_jwt(sub)).unwrap());
+        req.with_path_parameters(HashMap::from([
+            ("user_id".to_string(), user_id.to_string()),
+            ("workout_template_id".to_string(), workout_template_id.to_string()),
+        ]))
+    }
+
+    async fn setup(connector: &DBConnector) -> (Uuid, Uuid, Uuid) {
+        let element_id = insert_helper(1, Items::WkTemplateElements, connector, None).await[0];
+        let template_id = lookup_wk_template_element(element_id, connector).await.unwrap().workout_template_id;
+        let owner = lookup_workout_template(template_id, connector).await.unwrap().user_id;
+        let viewer = insert_helper(1, Items::Users, connector, None).await[0];
+        (owner, template_id, viewer)
+    }
+
+    #[tokio::test]
+    async fn test_save_workout_template_not_visible() {
+        let (connector, _container) = pg_container().await;
+        let (owner, template_id, viewer) = setup(&connector).await;
+
+        let response = save_workout_template(save_request(viewer, template_id, viewer), &connector).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+        update_user(&owner, &UncompleteUser { private_profile: Some(true), ..Default::default() }, &connector).await.unwrap();
+        let response = save_workout_template(save_request(owner, template_id, viewer), &connector).await.unwrap();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    }
+
+    #[tokio::test]
+    async fn test_save_workout_template_private_exercise() {
+        let (connector, _container) = pg_container().await;
+        let (owner, template_id, viewer) = setup(&connector).await;
+        let private = insert_exercise(&NewExercise { name: "Own".to_string(), owner_id: Some(owner), ..Default::default() }, &connector).await.unwrap();
+        let new_element = NewWkTemplateElement { workout_template_id: template_id, exercise_id: private.id, position: 1, ..Default::default() };
+        insert_batch_wk_template_elements(&vec![new_element], &connector).await.unwrap();
+
+        let response = save_workout_template(save_request(owner, template_id, viewer), &connector).await.unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        assert!(select_workout_template_by_user(viewer, &connector).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_save_workout_template_success() {
+        let (connector, _container) = pg_container().await;
+        let (owner, template_id, viewer) = setup(&connector).await;
+
+        let response = save_workout_template(save_request(owner, template_id, viewer), &connector).await.unwrap();
+        assert_eq!(response.status(), StatusCode::CREATED);
+        if let Body::Text(body) = response.into_body() {
+            let copy: WkTemplateWithElements = serde_json::from_str(&body).unwrap();
+            assert_eq!(copy.workout_template.user_id, viewer);
+            assert_eq!(copy.elements.len(), 1);
+        } else {
+            panic!("Expected a text body");
+        }
+        let notifications = select_notifications(owner, true, 10, 0, &connector).await.unwrap();
+        assert_eq!(notifications.len(), 1);
+        assert_eq!(notifications[0].notification.kind, TEMPLATE_SAVED);
+        assert_eq!(notifications[0].notification.workout_template_id, Some(template_id));
+
+        update_user(&owner, &UncompleteUser { notify_template_saves: Some(false), ..Default::default() }, &connector).await.unwrap();
+        let response = save_workout_template(save_request(owner, template_id, viewer), &connector).await.unwrap();
+        assert_eq!(response.status(), StatusCode::CREATED);
+        assert_eq!(select_notifications(owner, true, 10, 0, &connector).await.unwrap().len(), 1);
+        assert_eq!(select_workout_template_by_user(viewer, &connector).await.unwrap().len(), 2);
+    }
+}
@@ -0,0 +1,94 @@
+use chrono::Utc;
+use lambda_http::{Error, Request, Response, Body, RequestExt};
+use lambda_http::http::StatusCode;
+use tracing::error;
+use uuid::Uuid;
+
+use salamandra_server::lib::db::workout_drafts_db::select_workout_draft;
+use salamandra_server::lib::models::unit_models::InUnits;
+use salamandra_server::lib::utils::handlers::{build_resp, extract_sub, request_units};
+use salamandra_server::lib::db::DBConnector;
+use salamandra_server::lib::errors::DBError;
+
+/// Draft execution of one of the user's templates for today, nothing is stored
+/// * One row per set, ready to be posted as a workout execution once filled in
+/// * Reps, weights and times from the user's latest sets of each exercise, the template's otherwise
+/// * Weights in the user's units, unless overridden with `units`
+pub async fn start_workout(event: Request, connector: &DBConnector) -> Result<Response<Body>, Error> {
+
+    // Get path parameters
+    let user_id = Uuid::parse_str(event.path_parameters().first("user_id").unwrap()).unwrap();
+    let workout_template_id = Uuid::parse_str(event.path_parameters().first("workout_template_id").unwrap()).unwrap();
+
+    // Check user in claim
+    match extract_sub(event.headers(), Some(user_id)) {
+        Ok(_) => (),
+        Err(resp) => return Ok(resp)
+    };
+
+    let units = match request_units(&event, user_id, connector).await {
+        Ok(units) => units,
+        Err(resp) => return Ok(resp)
+    };
+
+    match select_workout_draft(workout_template_id, user_id, Utc::now().naive_utc().date(), connector).await {
+        Ok(draft) => Ok(build_resp(StatusCode::OK, draft.in_units(&units))),
+        Err(DBError::ItemNotFound(mes)) => Ok(build_resp(StatusCode::NOT_FOUND, mes)),
+        Err(error) => {
+            error!("INTERNAL SERVER ERROR: {}", error);
+            Ok(build_resp(StatusCode::INTERNAL_SERVER_ERROR, ""))
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use lambda_http::http::header::{AUTHORIZATION, HeaderValue};
+    use salamandra_server::lib::utils::tests::{pg_container, insert_helper, test_jwt, Items};
+    use salamandra_server::lib::db::wk_template_elements_db::insert_batch_wk_template_elements;
+    use salamandra_server::lib::db::workout_templates_db::lookup_workout_template;
+    use salamandra_server::lib::models::wk_template_elements_models::NewWkTemplateElement;
+    use salamandra_server::lib::models::workout_draft_models::WorkoutDraft;
+
+    // TEST CASES
+    // * Template of another user
+    // * Success in pounds
+
+    fn start_request(user_id: Uuid, workout_template_id: Uuid) -> Request {
+        let mut req = Request::default();
+        req.headers_mut().insert(AUTHORIZATION, HeaderValue::from_str(&test_jwt(user_id)).unwrap());
+        req.with_path_parameters(HashMap::from([
+            ("user_id".to_string(), user_id.to_string()),
+            ("workout_template_id".to_string(), workout_template_id.to_string()),
+        ]))
+        .with_query_string_parameters(HashMap::from([("units".to_string(), "imperial".to_string())]))
+    }
+
+    #[tokio::test]
+    async fn test_start_workout() {
+        let (connector, _container) = pg_container().await;
+        let template_id = insert_helper(1, Items::WkTemplates, &connector, None).await[0];
+        let user_id = lookup_workout_template(template_id, &connector).await.unwrap().user_id;
+        let stranger = insert_helper(1, Items::Users, &connector, None).await[0];
+        let exercise_id = insert_helper(1, Items::Exercises, &connector, None).await[0];
+        let new_element = NewWkTemplateElement { workout_template_id: template_id, exercise_id, reps: 8, sets: 3, weight: Some(45.359_237), rest: 90, ..Default::default() };
+        insert_batch_wk_template_elements(&vec![new_element], &connector).await.unwrap();
+
+        let response = start_workout(start_request(stranger, template_id), &connector).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+        let response = start_workout(start_request(user_id, template_id), &connector).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        if let Body::Text(body) = response.into_body() {
+            let draft: WorkoutDraft = serde_json::from_str(&body).unwrap();
+            assert_eq!(draft.elements.len(), 3);
+            assert!(draft.elements.iter().all(|row| row.weight == Some(100.0) && row.reps == 8));
+            assert_eq!(draft.elements.iter().map(|row| row.set_number).collect::<Vec<i16>>(), vec![0, 1, 2]);
+        } else {
+            panic!("Expected a text body");
+        }
+    }
+}
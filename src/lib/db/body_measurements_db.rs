@@ -0,0 +1,210 @@
+use diesel::prelude::*;
+use diesel::result::{Error, DatabaseErrorKind};
+use diesel_async::{AsyncConnection, AsyncPgConnection, RunQueryDsl};
+use diesel_async::scoped_futures::ScopedFutureExt;
+use chrono::NaiveDate;
+use uuid::Uuid;
+
+use crate::schema::bodymeasurements::dsl::*;
+use crate::schema::users;
+use crate::lib::models::body_measurement_models::{BodyMeasurement, NewBodyMeasurement, UncompleteBodyMeasurement};
+use crate::lib::errors::DBError;
+
+use super::DBConnector;
+
+/// Inserts a measurement and mirrors the latest weight into the user's profile.
+pub async fn insert_body_measurement(new_measurement: &NewBodyMeasurement, connector: &DBConnector) -> Result<BodyMeasurement, DBError> {
+
+    let mut conn = connector.rds_connection().await?;
+    conn.transaction::<BodyMeasurement, Error, _>(|conn| async move {
+        let measurement = diesel::insert_into(bodymeasurements)
+            .values(new_measurement)
+            .returning(BodyMeasurement::as_returning())
+            .get_result(conn)
+            .await?;
+        mirror_latest_weight(measurement.user_id, conn).await?;
+        Ok(measurement)
+    }.scope_boxed())
+    .await
+    .map_err(|error| match error {
+        Error::DatabaseError(DatabaseErrorKind::UniqueViolation, _) => {
+            DBError::UniqueViolation("There is already a measurement for that date".to_string())
+        },
+        Error::DatabaseError(DatabaseErrorKind::ForeignKeyViolation, _) => {
+            DBError::ItemNotFound("No user exists with the corresponding id".to_string())
+        },
+        _ => DBError::OperationError(error.to_string()),
+    })
+}
+
+/// Returns the user's measurements between both dates, included, oldest first.
+pub async fn select_body_measurements(user_uuid: Uuid, from: Option<NaiveDate>, to: Option<NaiveDate>, connector: &DBConnector) -> Result<Vec<BodyMeasurement>, DBError> {
+
+    let mut conn = connector.rds_connection().await?;
+    let mut query = bodymeasurements
+        .filter(user_id.eq(user_uuid))
+        .into_boxed();
+    if let Some(from) = from {
+        query = query.filter(date.ge(from));
+    }
+    if let Some(to) = to {
+        query = query.filter(date.le(to));
+    }
+    query
+        .order(date.asc())
+        .select(BodyMeasurement::as_select())
+        .load::<BodyMeasurement>(&mut conn)
+        .await
+        .map_err(|error| DBError::OperationError(error.to_string()))
+}
+
+/// Updates given values of a user's measurement and mirrors the latest weight into their profile.
+pub async fn update_body_measurement(user_uuid: Uuid, measurement_id: Uuid, changes: &UncompleteBodyMeasurement, connector: &DBConnector) -> Result<BodyMeasurement, DBError> {
+
+    let mut conn = connector.rds_connection().await?;
+    conn.transaction::<BodyMeasurement, Error, _>(|conn| async move {
+        let measurement = diesel::update(bodymeasurements.filter(id.eq(measurement_id)).filter(user_id.eq(user_uuid)))
+            .set(changes)
+            .returning(BodyMeasurement::as_returning())
+            .get_result(conn)
+            .await?;
+        mirror_latest_weight(user_uuid, conn).await?;
+        Ok(measurement)
+    }.scope_boxed())
+    .await
+    .map_err(|error| match error {
+        Error::NotFound => DBError::ItemNotFound("No measurement exists with the corresponding id".to_string()),
+        Error::QueryBuilderError(_) => DBError::QueryError(error.to_string()),
+        Error::DatabaseError(DatabaseErrorKind::UniqueViolation, _) => {
+            DBError::UniqueViolation("There is already a measurement for that date".to_string())
+        },
+        _ => DBError::OperationError(error.to_string()),
+    })
+}
+
+/// Removes a user's measurement and mirrors the latest remaining weight into their profile.
+/// * If no weight is left the profile keeps its current one
+pub async fn delete_body_measurement(user_uuid: Uuid, measurement_id: Uuid, connector: &DBConnector) -> Result<usize, DBError> {
+
+    let mut conn = connector.rds_connection().await?;
+    conn.transaction::<usize, Error, _>(|conn| async move {
+        let deleted = diesel::delete(bodymeasurements.filter(id.eq(measurement_id)).filter(user_id.eq(user_uuid)))
+            .execute(conn)
+            .await?;
+        mirror_latest_weight(user_uuid, conn).await?;
+        Ok(deleted)
+    }.scope_boxed())
+    .await
+    .map_err(|error| DBError::OperationError(error.to_string()))
+}
+
+/// Copies the most recent measured weight into `users.weight`, kept for older clients
+pub(crate) async fn mirror_latest_weight(user_uuid: Uuid, conn: &mut AsyncPgConnection) -> Result<(), Error> {
+    let latest = bodymeasurements
+        .filter(user_id.eq(user_uuid))
+        .filter(weight.is_not_null())
+        .order(date.desc())
+        .select(weight)
+        .first::<Option<f32>>(conn)
+        .await
+        .optional()?
+        .flatten();
+    if let Some(latest) = latest {
+        diesel::update(users::table.find(user_uuid))
+            .set(users::weight.eq(latest))
+            .execute(conn)
+            .await?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lib::utils::tests::{pg_container, insert_helper, Items};
+    use crate::lib::db::users_db::lookup_user;
+
+    // TEST CASES
+    // * Insert, duplicate date and non existing user
+    // * Select by date range, only the user's measurements
+    // * Update own and other user's measurement
+    // * Latest weight mirrored on insert, update and delete
+
+    fn day(n: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2024, 9, n).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_insert_body_measurement() {
+        let (connector, _container) = pg_container().await;
+        let user = insert_helper(1, Items::Users, &connector, None).await[0];
+
+        let new_measurement = NewBodyMeasurement { user_id: user, date: day(1), waist: Some(80.0), ..Default::default() };
+        let measurement = insert_body_measurement(&new_measurement, &connector).await.unwrap();
+        assert_eq!(measurement.waist, Some(80.0));
+        assert_eq!(measurement.weight, None);
+
+        let res = insert_body_measurement(&new_measurement, &connector).await;
+        assert!(matches!(res, Err(DBError::UniqueViolation(_))));
+
+        let new_measurement = NewBodyMeasurement { user_id: Uuid::new_v4(), ..Default::default() };
+        let res = insert_body_measurement(&new_measurement, &connector).await;
+        assert!(matches!(res, Err(DBError::ItemNotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn test_select_body_measurements() {
+        let (connector, _container) = pg_container().await;
+        let users = insert_helper(2, Items::Users, &connector, None).await;
+        for (user, n) in [(users[0], 3), (users[0], 1), (users[0], 2), (users[1], 2)] {
+            let new_measurement = NewBodyMeasurement { user_id: user, date: day(n), weight: Some(80.0), ..Default::default() };
+            insert_body_measurement(&new_measurement, &connector).await.unwrap();
+        }
+
+        let dates = |list: Vec<BodyMeasurement>| list.into_iter().map(|m| m.date).collect::<Vec<NaiveDate>>();
+        let all = select_body_measurements(users[0], None, None, &connector).await.unwrap();
+        assert_eq!(dates(all), vec![day(1), day(2), day(3)]);
+        let range = select_body_measurements(users[0], Some(day(2)), Some(day(3)), &connector).await.unwrap();
+        assert_eq!(dates(range), vec![day(2), day(3)]);
+        let until = select_body_measurements(users[0], None, Some(day(1)), &connector).await.unwrap();
+        assert_eq!(dates(until), vec![day(1)]);
+    }
+
+    #[tokio::test]
+    async fn test_update_body_measurement() {
+        let (connector, _container) = pg_container().await;
+        let user = insert_helper(1, Items::Users, &connector, None).await[0];
+        let new_measurement = NewBodyMeasurement { user_id: user, date: day(1), chest: Some(100.0), ..Default::default() };
+        let measurement = insert_body_measurement(&new_measurement, &connector).await.unwrap();
+
+        let changes = UncompleteBodyMeasurement { chest: Some(98.5), ..Default::default() };
+        let res = update_body_measurement(Uuid::new_v4(), measurement.id, &changes, &connector).await;
+        assert!(matches!(res, Err(DBError::ItemNotFound(_))));
+
+        let updated = update_body_measurement(user, measurement.id, &changes, &connector).await.unwrap();
+        assert_eq!(updated.chest, Some(98.5));
+        assert_eq!(updated.date, day(1));
+
+        let res = update_body_measurement(user, measurement.id, &UncompleteBodyMeasurement::default(), &connector).await;
+        assert!(matches!(res, Err(DBError::QueryError(_))));
+    }
+
+    #[tokio::test]
+    async fn test_mirror_latest_weight() {
+        let (connector, _container) = pg_container().await;
+        let user = insert_helper(1, Items::Users, &connector, None).await[0];
+        let weight_on = |n: u32, kg: f32| NewBodyMeasurement { user_id: user, date: day(n), weight: Some(kg), ..Default::default() };
+
+        let latest = insert_body_measurement(&weight_on(5, 80.0), &connector).await.unwrap();
+        insert_body_measurement(&weight_on(2, 82.0), &connector).await.unwrap();
+        assert_eq!(lookup_user(user, &connector).await.unwrap().weight, Some(80.0));
+
+        let changes = UncompleteBodyMeasurement { weight: Some(79.5), ..Default::default() };
+        update_body_measurement(user, latest.id, &changes, &connector).await.unwrap();
+        assert_eq!(lookup_user(user, &connector).await.unwrap().weight, Some(79.5));
+
+        assert_eq!(delete_body_measurement(user, latest.id, &connector).await.unwrap(), 1);
+        assert_eq!(lookup_user(user, &connector).await.unwrap().weight, Some(82.0));
+        assert_eq!(delete_body_measurement(user, latest.id, &connector).await.unwrap(), 0);
+    }
+}
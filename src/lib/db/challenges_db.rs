@@ -0,0 +1,242 @@
+use diesel::prelude::*;
+use diesel::result::{Error, DatabaseErrorKind};
+use diesel::sql_types::{Date, Nullable, Uuid as SqlUuid};
+use diesel_async::{AsyncConnection, RunQueryDsl};
+use diesel_async::scoped_futures::ScopedFutureExt;
+use uuid::Uuid;
+
+use crate::schema::{challengeparticipants, challenges};
+use crate::lib::models::challenge_models::{
+    Challenge, ChallengeParticipant, ChallengeProgress, ChallengeWithParticipants, NewChallenge, NewChallengeParticipant,
+    EXERCISE_REPS, TOTAL_VOLUME,
+};
+use crate::lib::errors::DBError;
+
+use super::DBConnector;
+
+/// Progress of every joined participant within `$2` and `$3`, both included, zero if they did nothing
+/// * `$4` is the progress of a set: its volume, 1 or its reps if it is of the exercise
+const PROGRESS_QUERY: &str = "
+SELECT u.id, u.username, u.display_name, COALESCE(SUM(done.progress), 0)::float4 AS progress
+FROM ChallengeParticipants cp
+JOIN Users u ON u.id = cp.user_id
+LEFT JOIN (
+    SELECT wt.user_id, {progress} AS progress
+    FROM WorkoutTemplates wt
+    JOIN WorkoutExecutions we ON we.workout_template_id = wt.id
+    {elements}
+    WHERE we.date >= $2 AND we.date <= $3 AND we.status = 0
+) done ON done.user_id = cp.user_id
+WHERE cp.challenge_id = $1 AND cp.joined AND u.deletion_scheduled_for IS NULL
+GROUP BY u.id, u.username, u.display_name";
+
+/// Create a challenge, its creator joined and the invited users pending
+pub async fn insert_challenge(new_challenge: &NewChallenge, invited: &[Uuid], connector: &DBConnector) -> Result<ChallengeWithParticipants, DBError> {
+
+    let mut conn = connector.rds_connection().await?;
+    conn.transaction::<ChallengeWithParticipants, Error, _>(|conn| async move {
+        let challenge = diesel::insert_into(challenges::table)
+            .values(new_challenge)
+            .returning(Challenge::as_returning())
+            .get_result(conn)
+            .await?;
+        let new_participants: Vec<NewChallengeParticipant> = std::iter::once((new_challenge.creator_id, true))
+            .chain(invited.iter().map(|user_id| (*user_id, false)))
+            .map(|(user_id, joined)| NewChallengeParticipant { challenge_id: challenge.id, user_id, joined })
+            .collect();
+        let participants = diesel::insert_into(challengeparticipants::table)
+            .values(&new_participants)
+            .returning(ChallengeParticipant::as_returning())
+            .get_results(conn)
+            .await?;
+        Ok(ChallengeWithParticipants { challenge, participants })
+    }.scope_boxed())
+    .await
+    .map_err(|error| match error {
+        Error::DatabaseError(DatabaseErrorKind::ForeignKeyViolation, _) => {
+            DBError::ItemNotFound("No user or exercise exists with the corresponding id".to_string())
+        },
+        Error::DatabaseError(DatabaseErrorKind::UniqueViolation, _) => {
+            DBError::UniqueViolation("Users can only be invited once".to_string())
+        },
+        _ => DBError::OperationError(error.to_string()),
+    })
+}
+
+/// Fetch a challenge the user created or was invited to
+pub async fn lookup_challenge_validated(challenge_id: Uuid, user_id: Uuid, connector: &DBConnector) -> Result<Challenge, DBError> {
+
+    let mut conn = connector.rds_connection().await?;
+    challenges::table
+        .inner_join(challengeparticipants::table)
+        .filter(challenges::id.eq(challenge_id))
+        .filter(challengeparticipants::user_id.eq(user_id))
+        .select(Challenge::as_select())
+        .first::<Challenge>(&mut conn)
+        .await
+        .map_err(|error| match error {
+            Error::NotFound => DBError::ItemNotFound("No challenge you are invited to exists with the corresponding id".to_string()),
+            _ => DBError::OperationError(error.to_string()),
+        })
+}
+
+/// Challenges the user created or was invited to, latest first
+pub async fn select_challenges(user_id: Uuid, limit: i64, offset: i64, connector: &DBConnector) -> Result<Vec<Challenge>, DBError> {
+
+    let mut conn = connector.rds_connection().await?;
+    challenges::table
+        .inner_join(challengeparticipants::table)
+        .filter(challengeparticipants::user_id.eq(user_id))
+        .order((challenges::start_date.desc(), challenges::id))
+        .limit(limit)
+        .offset(offset)
+        .select(Challenge::as_select())
+        .load::<Challenge>(&mut conn)
+        .await
+        .map_err(|error| DBError::OperationError(error.to_string()))
+}
+
+/// Join or leave a challenge the user is invited to
+pub async fn update_participation(challenge_id: Uuid, user_id: Uuid, joined: bool, connector: &DBConnector) -> Result<ChallengeParticipant, DBError> {
+
+    let mut conn = connector.rds_connection().await?;
+    diesel::update(challengeparticipants::table.find((challenge_id, user_id)))
+        .set(challengeparticipants::joined.eq(joined))
+        .returning(ChallengeParticipant::as_returning())
+        .get_result(&mut conn)
+        .await
+        .map_err(|error| match error {
+            Error::NotFound => DBError::ItemNotFound("No challenge you are invited to exists with the corresponding id".to_string()),
+            _ => DBError::OperationError(error.to_string()),
+        })
+}
+
+/// Progress of each joined participant from their executions within the challenge window
+pub async fn select_challenge_progress(challenge: &Challenge, connector: &DBConnector) -> Result<Vec<ChallengeProgress>, DBError> {
+
+    let mut conn = connector.rds_connection().await?;
+    let (progress, elements) = match challenge.metric {
+        TOTAL_VOLUME => ("el.reps * COALESCE(el.weight, 0)", "JOIN WkExecutionElements el ON el.workout_execution_id = we.id"),
+        EXERCISE_REPS => ("el.reps", "JOIN WkExecutionElements el ON el.workout_execution_id = we.id AND el.exercise_id = $4"),
+        _ => ("1", ""),
+    };
+    diesel::sql_query(PROGRESS_QUERY.replace("{progress}", progress).replace("{elements}", elements))
+        .bind::<SqlUuid, _>(challenge.id)
+        .bind::<Date, _>(challenge.start_date)
+        .bind::<Date, _>(challenge.end_date)
+        .bind::<Nullable<SqlUuid>, _>(challenge.exercise_id)
+        .load::<ChallengeProgress>(&mut conn)
+        .await
+        .map_err(|error| DBError::OperationError(error.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+    use crate::lib::utils::tests::{pg_container, insert_helper, Items};
+    use crate::lib::db::wk_execution_elements_db::insert_batch_wk_execution_elements;
+    use crate::lib::db::workout_executions_db::insert_workout_execution;
+    use crate::lib::db::workout_templates_db::insert_workout_template;
+    use crate::lib::db::exercises_db::{lookup_exercise, retire_exercise};
+    use crate::lib::models::challenge_models::{EXERCISE_REPS, WORKOUTS};
+    use crate::lib::models::wk_execution_elements_models::NewWkExecutionElement;
+    use crate::lib::models::workout_execution_models::NewWorkoutExecution;
+    use crate::lib::models::workout_templates_models::NewWorkoutTemplate;
+
+    // TEST CASES
+    // * Insert with invites, non existing user, lookup only for participants
+    // * Join, leave and list
+    // * Progress per metric within the window, joined participants only
+    // * Retiring the exercise of a challenge keeps both
+
+    fn day(n: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2024, 11, n).unwrap()
+    }
+
+    fn new_challenge(creator_id: Uuid, metric: i16, exercise_id: Option<Uuid>) -> NewChallenge {
+        NewChallenge { creator_id, name: "November".to_string(), metric, exercise_id, start_date: day(1), end_date: day(30) }
+    }
+
+    async fn execution_on(user_id: Uuid, date: NaiveDate, sets: &[(Uuid, i16, f32)], connector: &DBConnector) {
+        let template = insert_workout_template(&NewWorkoutTemplate { user_id, ..Default::default() }, connector).await.unwrap();
+        let execution = insert_workout_execution(&NewWorkoutExecution { workout_template_id: template.id, date, ..Default::default() }, connector).await.unwrap();
+        let elements: Vec<NewWkExecutionElement> = sets.iter().enumerate().map(|(n, (exercise_id, reps, weight))| NewWkExecutionElement {
+            workout_execution_id: execution.id,
+            exercise_id: *exercise_id,
+            set_number: n as i16,
+            reps: *reps,
+            weight: Some(*weight),
+            ..Default::default()
+        }).collect();
+        insert_batch_wk_execution_elements(&elements, connector).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_insert_challenge() {
+        let (connector, _container) = pg_container().await;
+        let users = insert_helper(3, Items::Users, &connector, None).await;
+
+        let created = insert_challenge(&new_challenge(users[0], WORKOUTS, None), &users[1..2], &connector).await.unwrap();
+        let joined: Vec<(Uuid, bool)> = created.participants.iter().map(|participant| (participant.user_id, participant.joined)).collect();
+        assert_eq!(joined, vec![(users[0], true), (users[1], false)]);
+
+        let res = insert_challenge(&new_challenge(users[0], WORKOUTS, None), &[Uuid::new_v4()], &connector).await;
+        assert!(matches!(res, Err(DBError::ItemNotFound(_))));
+
+        assert!(lookup_challenge_validated(created.challenge.id, users[1], &connector).await.is_ok());
+        let res = lookup_challenge_validated(created.challenge.id, users[2], &connector).await;
+        assert!(matches!(res, Err(DBError::ItemNotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn test_update_participation() {
+        let (connector, _container) = pg_container().await;
+        let users = insert_helper(3, Items::Users, &connector, None).await;
+        let challenge = insert_challenge(&new_challenge(users[0], WORKOUTS, None), &users[1..2], &connector).await.unwrap().challenge;
+
+        assert!(update_participation(challenge.id, users[1], true, &connector).await.unwrap().joined);
+        assert!(!update_participation(challenge.id, users[1], false, &connector).await.unwrap().joined);
+        let res = update_participation(challenge.id, users[2], true, &connector).await;
+        assert!(matches!(res, Err(DBError::ItemNotFound(_))));
+
+        assert_eq!(select_challenges(users[1], 10, 0, &connector).await.unwrap(), vec![challenge]);
+        assert!(select_challenges(users[2], 10, 0, &connector).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_select_challenge_progress() {
+        let (connector, _container) = pg_container().await;
+        let users = insert_helper(3, Items::Users, &connector, None).await;
+        let exercises = insert_helper(2, Items::Exercises, &connector, None).await;
+        execution_on(users[0], day(2), &[(exercises[0], 10, 50.0), (exercises[1], 5, 100.0)], &connector).await;
+        execution_on(users[0], day(30), &[(exercises[0], 10, 50.0)], &connector).await;
+        execution_on(users[0], NaiveDate::from_ymd_opt(2024, 12, 1).unwrap(), &[(exercises[0], 10, 50.0)], &connector).await;
+        execution_on(users[2], day(3), &[(exercises[0], 10, 50.0)], &connector).await;
+
+        let progress_of = |rows: Vec<ChallengeProgress>| {
+            let mut rows: Vec<(Uuid, f32)> = rows.into_iter().map(|row| (row.id, row.progress)).collect();
+            rows.sort_by(|a, b| b.1.total_cmp(&a.1));
+            rows
+        };
+        // The invited user who didn't join is left out, the one who did shows up with nothing done
+        for (metric, exercise_id, expected) in [(TOTAL_VOLUME, None, 1500.0), (WORKOUTS, None, 2.0), (EXERCISE_REPS, Some(exercises[0]), 20.0)] {
+            let challenge = insert_challenge(&new_challenge(users[0], metric, exercise_id), &users[1..], &connector).await.unwrap().challenge;
+            update_participation(challenge.id, users[1], true, &connector).await.unwrap();
+            let progress = select_challenge_progress(&challenge, &connector).await.unwrap();
+            assert_eq!(progress_of(progress), vec![(users[0], expected), (users[1], 0.0)]);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_retire_challenge_exercise() {
+        let (connector, _container) = pg_container().await;
+        let user_id = insert_helper(1, Items::Users, &connector, None).await[0];
+        let exercise_id = insert_helper(1, Items::Exercises, &connector, None).await[0];
+        let challenge = insert_challenge(&new_challenge(user_id, EXERCISE_REPS, Some(exercise_id)), &[], &connector).await.unwrap().challenge;
+
+        assert_eq!(retire_exercise(exercise_id, &connector).await.unwrap(), 1);
+        assert!(lookup_exercise(exercise_id, user_id, &connector).await.unwrap().retired);
+        assert_eq!(lookup_challenge_validated(challenge.id, user_id, &connector).await.unwrap(), challenge);
+    }
+}
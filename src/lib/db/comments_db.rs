@@ -0,0 +1,135 @@
+use diesel::prelude::*;
+use diesel::result::{Error, DatabaseErrorKind};
+use diesel_async::RunQueryDsl;
+use uuid::Uuid;
+
+use crate::schema::{comments, users};
+use crate::lib::models::comment_models::{Comment, CommentWithAuthor, NewComment};
+use crate::lib::models::user_models::UserInfo;
+use crate::lib::errors::DBError;
+
+use super::DBConnector;
+
+/// Insert a comment on an execution
+pub async fn insert_comment(new_comment: &NewComment, connector: &DBConnector) -> Result<Comment, DBError> {
+
+    let mut conn = connector.rds_connection().await?;
+    diesel::insert_into(comments::table)
+        .values(new_comment)
+        .returning(Comment::as_returning())
+        .get_result(&mut conn)
+        .await
+        .map_err(|error| match error {
+            Error::DatabaseError(DatabaseErrorKind::ForeignKeyViolation, _) => {
+                DBError::ItemNotFound("No execution exists with the corresponding id".to_string())
+            },
+            _ => DBError::OperationError(error.to_string()),
+        })
+}
+
+/// Comments of an execution with their authors, oldest first
+/// * Comments of users pending deletion are left out
+pub async fn select_comments(execution_id: Uuid, limit: i64, offset: i64, connector: &DBConnector) -> Result<Vec<CommentWithAuthor>, DBError> {
+
+    let mut conn = connector.rds_connection().await?;
+    comments::table
+        .inner_join(users::table)
+        .filter(comments::workout_execution_id.eq(execution_id))
+        .filter(users::deletion_scheduled_for.is_null())
+        .order((comments::date_created, comments::id))
+        .limit(limit)
+        .offset(offset)
+        .select((Comment::as_select(), UserInfo::as_select()))
+        .load::<(Comment, UserInfo)>(&mut conn)
+        .await
+        .map(|rows| rows.into_iter().map(|(comment, author)| CommentWithAuthor { comment, author }).collect())
+        .map_err(|error| DBError::OperationError(error.to_string()))
+}
+
+/// Change the content of a comment, only its author can
+pub async fn update_comment(comment_id: Uuid, execution_id: Uuid, user_id: Uuid, content: &str, connector: &DBConnector) -> Result<Comment, DBError> {
+
+    let mut conn = connector.rds_connection().await?;
+    diesel::update(comments::table.find(comment_id))
+        .filter(comments::workout_execution_id.eq(execution_id))
+        .filter(comments::user_id.eq(user_id))
+        .set((comments::content.eq(content), comments::date_edited.eq(diesel::dsl::now)))
+        .returning(Comment::as_returning())
+        .get_result(&mut conn)
+        .await
+        .map_err(|error| match error {
+            Error::NotFound => DBError::ItemNotFound("No comment of yours exists with the corresponding id".to_string()),
+            _ => DBError::OperationError(error.to_string()),
+        })
+}
+
+/// Delete a comment of an execution
+/// * Only if written by `author_id` when given, the execution owner moderates by passing `None`
+pub async fn delete_comment(comment_id: Uuid, execution_id: Uuid, author_id: Option<Uuid>, connector: &DBConnector) -> Result<usize, DBError> {
+
+    let mut conn = connector.rds_connection().await?;
+    let mut query = diesel::delete(comments::table.find(comment_id))
+        .filter(comments::workout_execution_id.eq(execution_id))
+        .into_boxed();
+    if let Some(author_id) = author_id {
+        query = query.filter(comments::user_id.eq(author_id));
+    }
+    query
+        .execute(&mut conn)
+        .await
+        .map_err(|error| DBError::OperationError(error.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lib::utils::tests::{pg_container, insert_helper, Items};
+
+    // TEST CASES
+    // * Insert on existing and non existing execution
+    // * Select paginated, oldest first
+    // * Update only by the author
+    // * Delete by the author or the moderator
+
+    fn new_comment(workout_execution_id: Uuid, user_id: Uuid, content: &str) -> NewComment {
+        NewComment { workout_execution_id, user_id, content: content.to_string() }
+    }
+
+    #[tokio::test]
+    async fn test_insert_and_select_comments() {
+        let (connector, _container) = pg_container().await;
+        let users = insert_helper(2, Items::Users, &connector, None).await;
+        let execution = insert_helper(1, Items::WkExecutions, &connector, None).await[0];
+
+        for n in 0..3 {
+            insert_comment(&new_comment(execution, users[n % 2], &format!("Comment {}", n)), &connector).await.unwrap();
+        }
+        let res = insert_comment(&new_comment(Uuid::new_v4(), users[0], "Lost"), &connector).await;
+        assert!(matches!(res, Err(DBError::ItemNotFound(_))));
+
+        let first = select_comments(execution, 2, 0, &connector).await.unwrap();
+        let second = select_comments(execution, 2, 2, &connector).await.unwrap();
+        assert_eq!(first.iter().map(|c| c.comment.content.as_str()).collect::<Vec<&str>>(), vec!["Comment 0", "Comment 1"]);
+        assert_eq!(second.len(), 1);
+        assert_eq!(first[1].author.id, users[1]);
+    }
+
+    #[tokio::test]
+    async fn test_update_and_delete_comment() {
+        let (connector, _container) = pg_container().await;
+        let users = insert_helper(2, Items::Users, &connector, None).await;
+        let execution = insert_helper(1, Items::WkExecutions, &connector, None).await[0];
+        let comment = insert_comment(&new_comment(execution, users[0], "Nice"), &connector).await.unwrap();
+        assert_eq!(comment.date_edited, None);
+
+        let res = update_comment(comment.id, execution, users[1], "Mine now", &connector).await;
+        assert!(matches!(res, Err(DBError::ItemNotFound(_))));
+        let updated = update_comment(comment.id, execution, users[0], "Very nice", &connector).await.unwrap();
+        assert_eq!(updated.content, "Very nice");
+        assert!(updated.date_edited.is_some());
+
+        assert_eq!(delete_comment(comment.id, execution, Some(users[1]), &connector).await.unwrap(), 0);
+        assert_eq!(delete_comment(comment.id, Uuid::new_v4(), None, &connector).await.unwrap(), 0);
+        assert_eq!(delete_comment(comment.id, execution, None, &connector).await.unwrap(), 1);
+    }
+}
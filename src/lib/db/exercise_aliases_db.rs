@@ -0,0 +1,133 @@
+use std::collections::HashMap;
+
+use diesel::prelude::*;
+use diesel::result::{Error, DatabaseErrorKind};
+use diesel_async::RunQueryDsl;
+use uuid::Uuid;
+
+use crate::schema::exercisealiases::dsl::*;
+use crate::lib::models::exercise_alias_models::{ExerciseAlias, NewExerciseAlias};
+use crate::lib::errors::DBError;
+
+use super::DBConnector;
+
+/// Inserts a new alias for an exercise and returns the inserted alias.
+///
+/// Locales are stored lowercased so they can be matched against `Accept-Language`.
+pub async fn insert_exercise_alias(new_alias: &NewExerciseAlias, connector: &DBConnector) -> Result<ExerciseAlias, DBError> {
+
+    let mut conn = connector.rds_connection().await?;
+    diesel::insert_into(exercisealiases)
+        .values((
+            exercise_id.eq(new_alias.exercise_id),
+            locale.eq(new_alias.locale.to_lowercase()),
+            name.eq(&new_alias.name),
+            preferred.eq(new_alias.preferred),
+        ))
+        .returning(ExerciseAlias::as_returning())
+        .get_result(&mut conn)
+        .await
+        .map_err(|error| match error {
+            Error::DatabaseError(DatabaseErrorKind::UniqueViolation, _) => {
+                DBError::UniqueViolation("Alias already exists".to_string())
+            },
+            Error::DatabaseError(DatabaseErrorKind::ForeignKeyViolation, _) => {
+                DBError::ItemNotFound("No exercise exists with the corresponding id".to_string())
+            },
+            _ => DBError::OperationError(error.to_string()),
+        })
+}
+
+/// Returns the localized name of each exercise for the first matching locale.
+///
+/// Locales are tried in the given order of preference, exercises without
+/// a preferred alias in any of them are left out of the map.
+pub async fn select_localized_names(exercise_ids: &[Uuid], locales: &[String], connector: &DBConnector) -> Result<HashMap<Uuid, String>, DBError> {
+
+    if exercise_ids.is_empty() || locales.is_empty() {
+        return Ok(HashMap::new());
+    }
+
+    let mut conn = connector.rds_connection().await?;
+    let aliases = exercisealiases
+        .filter(exercise_id.eq_any(exercise_ids))
+        .filter(locale.eq_any(locales))
+        .filter(preferred.eq(true))
+        .select((exercise_id, locale, name))
+        .load::<(Uuid, String, String)>(&mut conn)
+        .await
+        .map_err(|error| DBError::OperationError(error.to_string()))?;
+
+    let rank = |alias_locale: &String| locales.iter().position(|l| l == alias_locale);
+    let mut best: HashMap<Uuid, (usize, String)> = HashMap::new();
+    for (alias_exercise_id, alias_locale, alias_name) in aliases {
+        let Some(position) = rank(&alias_locale) else { continue };
+        match best.get(&alias_exercise_id) {
+            Some((current, _)) if *current <= position => (),
+            _ => { best.insert(alias_exercise_id, (position, alias_name)); }
+        }
+    }
+    Ok(best.into_iter().map(|(key, (_, value))| (key, value)).collect())
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lib::utils::tests::{pg_container, insert_helper, Items};
+
+    // TEST CASES
+    // * Insert alias and duplicate
+    // * Insert alias for non existing exercise
+    // * Localized names follow locale preference and ignore non preferred aliases
+
+    #[tokio::test]
+    async fn test_insert_exercise_alias_duplicate() {
+        let (connector, _container) = pg_container().await;
+        let new_exercise_id = insert_helper(1, Items::Exercises, &connector, None).await[0];
+
+        let new_alias = NewExerciseAlias { exercise_id: new_exercise_id, locale: "ES".to_string(), name: "Press banca".to_string(), preferred: true };
+        let insert_res = insert_exercise_alias(&new_alias, &connector).await;
+        assert!(insert_res.is_ok());
+        assert_eq!(insert_res.unwrap().locale, "es");
+
+        let insert_res = insert_exercise_alias(&new_alias, &connector).await;
+        assert!(matches!(insert_res, Err(DBError::UniqueViolation(_))));
+    }
+
+    #[tokio::test]
+    async fn test_insert_exercise_alias_non_existing() {
+        let (connector, _container) = pg_container().await;
+
+        let new_alias = NewExerciseAlias { exercise_id: Uuid::new_v4(), locale: "es".to_string(), name: "Press banca".to_string(), preferred: true };
+        let insert_res = insert_exercise_alias(&new_alias, &connector).await;
+        assert!(matches!(insert_res, Err(DBError::ItemNotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn test_select_localized_names() {
+        let (connector, _container) = pg_container().await;
+        let ids = insert_helper(2, Items::Exercises, &connector, None).await;
+
+        let aliases = [
+            (ids[0], "es", "Press banca", true),
+            (ids[0], "es", "Banca", false),
+            (ids[0], "es-mx", "Press de pecho", true),
+            (ids[1], "es", "Sentadilla", false),
+        ];
+        for (alias_exercise_id, alias_locale, alias_name, alias_preferred) in aliases {
+            let new_alias = NewExerciseAlias { exercise_id: alias_exercise_id, locale: alias_locale.to_string(), name: alias_name.to_string(), preferred: alias_preferred };
+            assert!(insert_exercise_alias(&new_alias, &connector).await.is_ok());
+        }
+
+        let names = select_localized_names(&ids, &["es-mx".to_string(), "es".to_string()], &connector).await.unwrap();
+        assert_eq!(names.get(&ids[0]).unwrap(), "Press de pecho");
+        assert!(!names.contains_key(&ids[1]));
+
+        let names = select_localized_names(&ids, &["es".to_string()], &connector).await.unwrap();
+        assert_eq!(names.get(&ids[0]).unwrap(), "Press banca");
+
+        let names = select_localized_names(&ids, &[], &connector).await.unwrap();
+        assert!(names.is_empty());
+    }
+}
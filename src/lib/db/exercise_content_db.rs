@@ -0,0 +1,176 @@
+use std::collections::HashMap;
+
+use diesel::prelude::*;
+use diesel::result::{Error, DatabaseErrorKind};
+use diesel::upsert::excluded;
+use diesel_async::{AsyncConnection, RunQueryDsl};
+use diesel_async::scoped_futures::ScopedFutureExt;
+use uuid::Uuid;
+
+use crate::schema::{exerciseinstructions, exercisemedia};
+use crate::lib::models::exercise_content_models::{ExerciseContent, ExerciseInstructions, ExerciseMedia, NewExerciseMedia};
+use crate::lib::errors::DBError;
+
+use super::DBConnector;
+
+/// Returns the instructional content of each exercise.
+///
+/// Exercises without any content are left out of the map.
+pub async fn select_exercise_contents(exercise_ids: &[Uuid], connector: &DBConnector) -> Result<HashMap<Uuid, ExerciseContent>, DBError> {
+
+    if exercise_ids.is_empty() {
+        return Ok(HashMap::new());
+    }
+
+    let mut conn = connector.rds_connection().await?;
+    let instructions = exerciseinstructions::table
+        .filter(exerciseinstructions::exercise_id.eq_any(exercise_ids))
+        .select(ExerciseInstructions::as_select())
+        .load::<ExerciseInstructions>(&mut conn)
+        .await
+        .map_err(|error| DBError::OperationError(error.to_string()))?;
+    let media = exercisemedia::table
+        .filter(exercisemedia::exercise_id.eq_any(exercise_ids))
+        .order((exercisemedia::exercise_id, exercisemedia::position))
+        .select((exercisemedia::exercise_id, ExerciseMedia::as_select()))
+        .load::<(Uuid, ExerciseMedia)>(&mut conn)
+        .await
+        .map_err(|error| DBError::OperationError(error.to_string()))?;
+
+    let mut contents: HashMap<Uuid, ExerciseContent> = HashMap::new();
+    for instruction in instructions {
+        contents.insert(instruction.exercise_id, ExerciseContent {
+            description: instruction.description,
+            steps: instruction.steps,
+            cues: instruction.cues,
+            common_mistakes: instruction.common_mistakes,
+            media: Vec::new(),
+        });
+    }
+    for (media_exercise_id, item) in media {
+        contents.entry(media_exercise_id).or_default().media.push(item);
+    }
+    Ok(contents)
+}
+
+/// Replaces the instructional content of an exercise.
+///
+/// Instructions are upserted and media is replaced in the given order,
+/// all in a single transaction.
+pub async fn replace_exercise_content(exercise_id: Uuid, content: &ExerciseContent, connector: &DBConnector) -> Result<(), DBError> {
+
+    let instructions = ExerciseInstructions {
+        exercise_id,
+        description: content.description.clone(),
+        steps: content.steps.clone(),
+        cues: content.cues.clone(),
+        common_mistakes: content.common_mistakes.clone(),
+    };
+    let media: Vec<NewExerciseMedia> = content.media.iter().enumerate()
+        .map(|(position, item)| NewExerciseMedia {
+            exercise_id,
+            position: position as i16,
+            media_type: item.media_type,
+            url: &item.url,
+            thumbnail_url: item.thumbnail_url.as_deref(),
+            width: item.width,
+            height: item.height,
+        })
+        .collect();
+
+    let mut conn = connector.rds_connection().await?;
+    conn.transaction::<(), Error, _>(|conn| async move {
+        diesel::insert_into(exerciseinstructions::table)
+            .values(&instructions)
+            .on_conflict(exerciseinstructions::exercise_id)
+            .do_update()
+            .set((
+                exerciseinstructions::description.eq(excluded(exerciseinstructions::description)),
+                exerciseinstructions::steps.eq(excluded(exerciseinstructions::steps)),
+                exerciseinstructions::cues.eq(excluded(exerciseinstructions::cues)),
+                exerciseinstructions::common_mistakes.eq(excluded(exerciseinstructions::common_mistakes)),
+            ))
+            .execute(conn)
+            .await?;
+        diesel::delete(exercisemedia::table.filter(exercisemedia::exercise_id.eq(exercise_id)))
+            .execute(conn)
+            .await?;
+        diesel::insert_into(exercisemedia::table)
+            .values(&media)
+            .execute(conn)
+            .await?;
+        Ok(())
+    }.scope_boxed())
+    .await
+    .map_err(|error| match error {
+        Error::DatabaseError(DatabaseErrorKind::ForeignKeyViolation, _) => {
+            DBError::ItemNotFound("No exercise exists with the corresponding id".to_string())
+        },
+        Error::DatabaseError(DatabaseErrorKind::CheckViolation, _) => {
+            DBError::QueryError("Media dimensions must be positive".to_string())
+        },
+        _ => DBError::OperationError(error.to_string()),
+    })
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lib::utils::tests::{pg_container, insert_helper, Items};
+
+    // TEST CASES
+    // * Replace and select content, replacing again drops old media
+    // * Select exercises without content
+    // * Replace content of non existing exercise
+
+    fn content(n_media: usize) -> ExerciseContent {
+        ExerciseContent {
+            description: Some("Lie on a bench".to_string()),
+            steps: vec!["Unrack".to_string(), "Lower to the chest".to_string(), "Press".to_string()],
+            cues: vec!["Elbows tucked".to_string()],
+            common_mistakes: vec!["Bouncing".to_string()],
+            media: (0..n_media).map(|i| ExerciseMedia {
+                media_type: 1,
+                url: format!("https://cdn.example.com/{}.mp4", i),
+                thumbnail_url: Some(format!("https://cdn.example.com/{}.jpg", i)),
+                width: Some(1280),
+                height: Some(720),
+            }).collect(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_replace_select_exercise_content() {
+        let (connector, _container) = pg_container().await;
+        let ids = insert_helper(2, Items::Exercises, &connector, None).await;
+
+        assert!(replace_exercise_content(ids[0], &content(3), &connector).await.is_ok());
+        let mut contents = select_exercise_contents(&ids, &connector).await.unwrap();
+        assert_eq!(contents.remove(&ids[0]).unwrap(), content(3));
+        assert!(contents.is_empty());
+
+        let mut updated = content(1);
+        updated.description = None;
+        assert!(replace_exercise_content(ids[0], &updated, &connector).await.is_ok());
+        let mut contents = select_exercise_contents(&ids, &connector).await.unwrap();
+        assert_eq!(contents.remove(&ids[0]).unwrap(), updated);
+    }
+
+    #[tokio::test]
+    async fn test_select_exercise_contents_none() {
+        let (connector, _container) = pg_container().await;
+        let ids = insert_helper(2, Items::Exercises, &connector, None).await;
+
+        assert!(select_exercise_contents(&ids, &connector).await.unwrap().is_empty());
+        assert!(select_exercise_contents(&[], &connector).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_replace_exercise_content_non_existing() {
+        let (connector, _container) = pg_container().await;
+
+        let replace_res = replace_exercise_content(Uuid::new_v4(), &content(1), &connector).await;
+        assert!(matches!(replace_res, Err(DBError::ItemNotFound(_))));
+    }
+}
@@ -1,13 +1,19 @@
+use std::collections::HashMap;
 use diesel::prelude::*;
+use diesel::dsl::{case_when, exists};
 use diesel::result::{Error, DatabaseErrorKind};
-use diesel_async::RunQueryDsl;
+use diesel::sql_types::{Array, Float4, Integer, Text, Uuid as SqlUuid};
+use diesel_async::{AsyncConnection, RunQueryDsl};
+use diesel_async::scoped_futures::ScopedFutureExt;
 use uuid::Uuid;
 
 use crate::schema::exercises::dsl::*;
-use crate::lib::models::exercise_models::{Exercise, NewExercise};
+use crate::schema::exercisealiases;
+use crate::lib::models::exercise_models::{CatalogEntry, Exercise, ExerciseFilters, ExerciseMatch, NewExercise, UncompleteExercise};
 use crate::lib::errors::DBError;
 
 use super::DBConnector;
+use super::sql_functions::{greatest, lower, word_similar, word_similarity};
 
 /// Inserts a new exercise into the database and returns the inserted user.
 ///
@@ -33,11 +39,12 @@ pub async fn insert_exercise(new_exercise: &NewExercise, connector: &DBConnector
 ///
 /// This function performs a lookup for a exercise by its primary key (UUID).
 /// If the exercise is found, it is returned. Otherwise, an appropriate error
-/// is returned.
-pub async fn lookup_exercise(exercise_id: Uuid, connector: &DBConnector) -> Result<Exercise, DBError> {
+/// is returned. Private exercises are only found by their owner.
+pub async fn lookup_exercise(exercise_id: Uuid, user_id: Uuid, connector: &DBConnector) -> Result<Exercise, DBError> {
 
     let mut conn = connector.rds_connection().await?;
     let exercise = exercises.find(exercise_id)
+        .filter(owner_id.is_null().or(owner_id.eq(user_id)))
         .first::<Exercise>(&mut conn)
         .await
         .map_err(|error| {
@@ -51,30 +58,215 @@ pub async fn lookup_exercise(exercise_id: Uuid, connector: &DBConnector) -> Resu
 }
 
 
-/// Searches for exercises with names starting with the given term.
+/// Update given values for an already existing exercise
+pub async fn update_exercise(exercise_id: Uuid, exercise: &UncompleteExercise, connector: &DBConnector) -> Result<Exercise, DBError> {
+
+    let mut conn = connector.rds_connection().await?;
+    diesel::update(exercises.find(exercise_id))
+        .set(exercise)
+        .returning(Exercise::as_returning())
+        .get_result(&mut conn)
+        .await
+        .map_err(|error| match error {
+            Error::NotFound => {
+                DBError::ItemNotFound("No exercise exists with the corresponding id".to_string())
+            },
+            Error::DatabaseError(DatabaseErrorKind::UniqueViolation, _) => {
+                DBError::UniqueViolation("Exercise already exists".to_string())
+            },
+            Error::QueryBuilderError(_) => {
+                DBError::QueryError(error.to_string())
+            },
+            _ => DBError::OperationError(error.to_string()),
+        })
+}
+
+/// Removes an exercise from the catalog, returning the number of affected rows.
 ///
-/// Case-insensitive search in the `exercises` table,
-/// returning all exercises whose names begin with the specified term
-pub async fn search_exercises(term: &str, connector: &DBConnector) -> Result<Vec<Exercise>, DBError> {
+/// Exercises referenced by template or execution elements, or by challenges, can't be
+/// deleted without losing the users' history, so they are marked as retired instead.
+/// Retired exercises are hidden from searches and can't be used in new templates.
+pub async fn retire_exercise(exercise_id: Uuid, connector: &DBConnector) -> Result<usize, DBError> {
 
     let mut conn = connector.rds_connection().await?;
-    let pattern = format!("{}%", term);
-    exercises.filter(name.like(pattern))
+    match diesel::delete(exercises.find(exercise_id)).execute(&mut conn).await {
+        Ok(deleted) => Ok(deleted),
+        Err(Error::DatabaseError(DatabaseErrorKind::ForeignKeyViolation, _)) => {
+            diesel::update(exercises.find(exercise_id))
+                .set(retired.eq(true))
+                .execute(&mut conn)
+                .await
+                .map_err(|error| DBError::OperationError(error.to_string()))
+        },
+        Err(error) => Err(DBError::OperationError(error.to_string())),
+    }
+}
+
+
+/// Maximum number of exercises returned by a search
+pub const EXERCISE_SEARCH_LIMIT: i64 = 50;
+
+/// Searches for exercises whose names match the given term.
+///
+/// Case-insensitive search in the `exercises` table, returning all non retired
+/// exercises whose names, or any of their aliases, contain the term or have a
+/// word similar to it (pg_trgm). Results are ranked by relevance: exact matches,
+/// then prefixes, then similarity, up to `EXERCISE_SEARCH_LIMIT` of them.
+/// Includes the catalog and the private exercises of the given user.
+pub async fn search_exercises(term: &str, filters: &ExerciseFilters, user_id: Uuid, connector: &DBConnector) -> Result<Vec<Exercise>, DBError> {
+
+    let mut conn = connector.rds_connection().await?;
+    // Wildcards in the term are matched literally
+    let escaped = term.replace('\\', "\\\\").replace('%', "\\%").replace('_', "\\_");
+    let contains = format!("%{}%", escaped);
+    let prefix = format!("{}%", escaped);
+    let aliases = exercisealiases::table.filter(exercisealiases::exercise_id.eq(id));
+    let alias_matches = aliases
+        .filter(exercisealiases::name.ilike(contains.clone()).or(word_similar(term, exercisealiases::name)));
+    let alias_exact = aliases.filter(lower(exercisealiases::name).eq(lower(term)));
+    let alias_prefix = aliases.filter(exercisealiases::name.ilike(prefix.clone()));
+    let alias_similarity = aliases
+        .select(diesel::dsl::max(word_similarity(term, exercisealiases::name)))
+        .single_value();
+
+    let mut query = exercises
+        .filter(name.ilike(contains).or(word_similar(term, name)).or(exists(alias_matches)))
+        .filter(retired.eq(false))
+        .filter(owner_id.is_null().or(owner_id.eq(user_id)))
+        .into_boxed();
+
+    if let Some(group) = filters.main_muscle_group {
+        query = query.filter(main_muscle_group.eq(group));
+    }
+    if let Some(equipment) = filters.necessary_equipment {
+        query = query.filter(necessary_equipment.eq(equipment));
+    }
+    if let Some(kind) = filters.exercise_type {
+        query = query.filter(exercise_type.eq(kind));
+    }
+
+    query
+        .order((
+            lower(name).eq(lower(term)).or(exists(alias_exact)).desc(),
+            name.ilike(prefix).or(exists(alias_prefix)).desc(),
+            greatest(word_similarity(term, name).nullable(), alias_similarity).desc(),
+            name.asc(),
+        ))
+        .limit(EXERCISE_SEARCH_LIMIT)
         .load::<Exercise>(&mut conn)
         .await
         .map_err(|error| DBError::OperationError(error.to_string()))
 }
 
 
+/// Returns exercises that can replace the given one, most similar first.
+///
+/// Candidates share a muscle group with the exercise and are ranked by a
+/// score: same main muscle group (4), same type (2), same secondary muscle
+/// group or groups swapped (1 each), then by name. Retired exercises and other
+/// users' private exercises are excluded. If `equipment` is given, only exercises
+/// needing one of those codes or no equipment at all are returned.
+pub async fn select_exercise_alternatives(exercise: &Exercise, user_id: Uuid, equipment: Option<&[i16]>, limit: i64, offset: i64, connector: &DBConnector) -> Result<Vec<Exercise>, DBError> {
+
+    let groups: Vec<i16> = [exercise.main_muscle_group, exercise.secondary_muscle_group].into_iter().flatten().collect();
+    if groups.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    // Comparisons with a missing value are NULL, so they score nothing
+    let points = |value: i32| value.into_sql::<Integer>();
+    let score = case_when(main_muscle_group.eq(exercise.main_muscle_group), points(4)).otherwise(points(0))
+        + case_when(exercise_type.eq(exercise.exercise_type), points(2)).otherwise(points(0))
+        + case_when(secondary_muscle_group.eq(exercise.secondary_muscle_group), points(1)).otherwise(points(0))
+        + case_when(main_muscle_group.eq(exercise.secondary_muscle_group), points(1)).otherwise(points(0))
+        + case_when(secondary_muscle_group.eq(exercise.main_muscle_group), points(1)).otherwise(points(0));
+
+    let mut conn = connector.rds_connection().await?;
+    let mut query = exercises
+        .filter(id.ne(exercise.id))
+        .filter(main_muscle_group.eq_any(&groups).or(secondary_muscle_group.eq_any(&groups)))
+        .filter(retired.eq(false))
+        .filter(owner_id.is_null().or(owner_id.eq(user_id)))
+        .into_boxed();
+    if let Some(codes) = equipment {
+        query = query.filter(necessary_equipment.is_null().or(necessary_equipment.eq_any(codes)));
+    }
+    query
+        .order((score.desc(), name.asc()))
+        .limit(limit)
+        .offset(offset)
+        .load::<Exercise>(&mut conn)
+        .await
+        .map_err(|error| DBError::OperationError(error.to_string()))
+}
+
+/// Minimum trigram similarity for an exercise name from another app to be mapped
+pub const EXERCISE_MATCH_THRESHOLD: f32 = 0.5;
+
+const MATCH_QUERY: &str = "
+SELECT t.term, e.*
+FROM unnest($1::text[]) AS t(term)
+CROSS JOIN LATERAL (
+    SELECT ex.id, ex.name, ex.main_muscle_group, ex.secondary_muscle_group,
+        ex.necessary_equipment, ex.exercise_type, ex.retired, ex.owner_id
+    FROM Exercises ex
+    WHERE (ex.owner_id IS NULL OR ex.owner_id = $2)
+    AND (
+        similarity(t.term, ex.name) >= $3
+        OR EXISTS (SELECT 1 FROM ExerciseAliases al WHERE al.exercise_id = ex.id AND similarity(t.term, al.name) >= $3)
+    )
+    ORDER BY
+        (lower(ex.name) = lower(t.term)
+            OR EXISTS (SELECT 1 FROM ExerciseAliases al WHERE al.exercise_id = ex.id AND lower(al.name) = lower(t.term))) DESC,
+        greatest(
+            similarity(t.term, ex.name),
+            (SELECT MAX(similarity(t.term, al.name)) FROM ExerciseAliases al WHERE al.exercise_id = ex.id)
+        ) DESC,
+        ex.retired ASC,
+        ex.name ASC
+    LIMIT 1
+) e";
+
+/// Maps exercise names from other apps onto existing exercises.
+///
+/// Each name is matched to the exercise whose name or alias is the most similar
+/// one (pg_trgm), as long as the similarity reaches `EXERCISE_MATCH_THRESHOLD`.
+/// Exact matches, ignoring case, always win. Retired exercises can be matched,
+/// since they may appear in old workouts, but active ones are preferred on ties.
+/// Names without a match are left out of the returned map.
+pub async fn match_exercise_names(names: &[String], user_id: Uuid, connector: &DBConnector) -> Result<HashMap<String, Exercise>, DBError> {
+
+    let mut conn = connector.rds_connection().await?;
+    Ok(diesel::sql_query(MATCH_QUERY)
+        .bind::<Array<Text>, _>(names)
+        .bind::<SqlUuid, _>(user_id)
+        .bind::<Float4, _>(EXERCISE_MATCH_THRESHOLD)
+        .load::<ExerciseMatch>(&mut conn)
+        .await
+        .map_err(|error| DBError::OperationError(error.to_string()))?
+        .into_iter()
+        .map(|row| (row.term, row.exercise))
+        .collect())
+}
+
 /// Checks if all provided UUIDs are valid references to existing exercises.
 ///
 /// This function verifies whether all given UUIDs correspond to existing exercises
 /// in the database. It returns `true` if all UUIDs are valid, and `false` otherwise.
-pub async fn validate_exercises(exercise_ids: Vec<Uuid>, connector: &DBConnector) -> Result<bool, DBError> {
+/// Another user's private exercises are never valid, retired exercises
+/// are only considered valid if `include_retired` is set.
+pub async fn validate_exercises(exercise_ids: Vec<Uuid>, user_id: Uuid, include_retired: bool, connector: &DBConnector) -> Result<bool, DBError> {
     
     let mut conn = connector.rds_connection().await?;
     let n = exercise_ids.len();
-    let found_uuids = exercises.filter(id.eq_any(&exercise_ids))
+    let mut query = exercises
+        .filter(id.eq_any(&exercise_ids))
+        .filter(owner_id.is_null().or(owner_id.eq(user_id)))
+        .into_boxed();
+    if !include_retired {
+        query = query.filter(retired.eq(false));
+    }
+    let found_uuids = query
             .select(id)
             .load::<Uuid>(&mut conn)
             .await
@@ -84,18 +276,71 @@ pub async fn validate_exercises(exercise_ids: Vec<Uuid>, connector: &DBConnector
 }
 
 
+/// Returns every catalog exercise, retired ones included, ordered by name.
+pub async fn select_catalog_exercises(connector: &DBConnector) -> Result<Vec<Exercise>, DBError> {
+
+    let mut conn = connector.rds_connection().await?;
+    exercises
+        .filter(owner_id.is_null())
+        .order(name.asc())
+        .load::<Exercise>(&mut conn)
+        .await
+        .map_err(|error| DBError::OperationError(error.to_string()))
+}
+
+/// Inserts new catalog exercises and updates existing ones matched by name.
+///
+/// Everything is applied in a single transaction, so a failing entry
+/// leaves the catalog untouched. Returns the number of affected rows.
+pub async fn upsert_catalog_exercises(additions: &[CatalogEntry], changes: &[CatalogEntry], connector: &DBConnector) -> Result<usize, DBError> {
+
+    let mut conn = connector.rds_connection().await?;
+    conn.transaction::<usize, Error, _>(|conn| async move {
+        let mut affected = diesel::insert_into(exercises)
+            .values(additions)
+            .execute(conn)
+            .await?;
+        for entry in changes {
+            affected += diesel::update(exercises)
+                .filter(owner_id.is_null())
+                .filter(name.eq(&entry.name))
+                .set(entry)
+                .execute(conn)
+                .await?;
+        }
+        Ok(affected)
+    }.scope_boxed())
+    .await
+    .map_err(|error| match error {
+        Error::DatabaseError(DatabaseErrorKind::UniqueViolation, _) => {
+            DBError::UniqueViolation("Exercise already exists".to_string())
+        },
+        _ => DBError::OperationError(error.to_string()),
+    })
+}
+
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::lib::utils::tests::{pg_container, insert_helper, Items};
+    use crate::lib::db::exercise_aliases_db::insert_exercise_alias;
+    use crate::lib::models::exercise_alias_models::NewExerciseAlias;
 
     // TEST CASES
     // * Insert and lookup inserted exercise
     // * Insert with duplicate PK
     // * Lookup non exisiting
-    // * Search multiple and empty
+    // * Search multiple, empty and capped
+    // * Search with LIKE wildcards in the term
+    // * Search case-insensitive with filters
     // * Validate all valid, no valid, some valid
+    // * Update existing and non existing
+    // * Retire unreferenced, referenced and non existing
+    // * Private exercises: unique name per owner, only visible and valid for the owner
+    // * Upsert catalog and select it back, failed upsert is rolled back
+    // * Alternatives ranked by score, paginated, filtered by equipment and visibility
+    // * Match names from other apps: exact, similar, alias, private and unknown
 
     #[tokio::test]
     async fn test_insert_lookup_exercise() {
@@ -111,7 +356,7 @@ mod tests {
 
         let new_exercise_id = insert_res.unwrap().id;
 
-        let read_res = lookup_exercise(new_exercise_id, &connector).await;
+        let read_res = lookup_exercise(new_exercise_id, Uuid::new_v4(), &connector).await;
         assert!(read_res.is_ok());
     }
     #[tokio::test]
@@ -126,7 +371,7 @@ mod tests {
         assert!(insert_res1.is_ok());
         let new_exercise_id = insert_res1.unwrap().id;
 
-        let read_res = lookup_exercise(new_exercise_id, &connector).await;
+        let read_res = lookup_exercise(new_exercise_id, Uuid::new_v4(), &connector).await;
         assert!(matches!(read_res, Ok(_new_exercise1)));
 
         let insert_res2 = insert_exercise(&new_exercise2, &connector).await;
@@ -137,7 +382,7 @@ mod tests {
         let (connector, _container) = pg_container().await;
 
         // Look up non existing exercise
-        let read_res = lookup_exercise(Uuid::new_v4(), &connector).await;
+        let read_res = lookup_exercise(Uuid::new_v4(), Uuid::new_v4(), &connector).await;
         assert!(read_res.is_err());
     }
 
@@ -148,7 +393,7 @@ mod tests {
         let exercise_ids = insert_helper(5, Items::Exercises, &connector, Some("TEST".into())).await;
 
         let pattern = "TEST";
-        let search_res = search_exercises(pattern, &connector).await;
+        let search_res = search_exercises(pattern, &ExerciseFilters::default(), Uuid::new_v4(), &connector).await;
         assert!(search_res.is_ok());
 
         let exercise_vec = search_res.unwrap();
@@ -157,25 +402,70 @@ mod tests {
         assert_eq!(id_vec, exercise_ids);
     }
 
+    #[tokio::test]
+    async fn test_search_exercises_limit() {
+        let (connector, _container) = pg_container().await;
+        let _ = insert_helper(EXERCISE_SEARCH_LIMIT as usize + 5, Items::Exercises, &connector, Some("many".into())).await;
+
+        let search_res = search_exercises("m", &ExerciseFilters::default(), Uuid::new_v4(), &connector).await;
+        assert_eq!(search_res.unwrap().len(), EXERCISE_SEARCH_LIMIT as usize);
+    }
+
     #[tokio::test]
     async fn test_search_exercises_success_none() {
         let (connector, _container) = pg_container().await;
 
         let pattern = "Testing";
-        let search_res = search_exercises(pattern, &connector).await;
+        let search_res = search_exercises(pattern, &ExerciseFilters::default(), Uuid::new_v4(), &connector).await;
         assert!(search_res.is_ok());
 
         let vec = search_res.unwrap();
         assert_eq!(vec.len(), 0, "Should have been 0 exercises");
     }
 
+    #[tokio::test]
+    async fn test_search_exercises_wildcards() {
+        let (connector, _container) = pg_container().await;
+        let mut underscored = insert_helper(3, Items::Exercises, &connector, Some("TEST".into())).await;
+        let percent = insert_exercise(&NewExercise {name: "100% Squat".to_string(), ..Default::default()}, &connector).await.unwrap();
+
+        let filters = ExerciseFilters::default();
+        let search = |pattern: &'static str| search_exercises(pattern, &filters, Uuid::new_v4(), &connector);
+        let found: Vec<Uuid> = search("%").await.unwrap().iter().map(|ex| ex.id).collect();
+        assert_eq!(found, vec![percent.id]);
+        let mut found: Vec<Uuid> = search("_").await.unwrap().iter().map(|ex| ex.id).collect();
+        found.sort();
+        underscored.sort();
+        assert_eq!(found, underscored);
+        assert!(search("\\").await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_search_exercises_case_insensitive_filtered() {
+        let (connector, _container) = pg_container().await;
+
+        let bench = NewExercise {name: "Bench Press".to_string(), exercise_type: Some(1), ..Default::default()};
+        let dumbbell = NewExercise {name: "Dumbbell Bench Press".to_string(), exercise_type: Some(2), ..Default::default()};
+        let bench_id = insert_exercise(&bench, &connector).await.unwrap().id;
+        let _ = insert_exercise(&dumbbell, &connector).await.unwrap();
+
+        let search_res = search_exercises("bench press", &ExerciseFilters::default(), Uuid::new_v4(), &connector).await;
+        let found: Vec<Uuid> = search_res.unwrap().iter().map(|ex| ex.id).collect();
+        assert_eq!(found.len(), 2);
+        assert_eq!(found[0], bench_id);
+
+        let filters = ExerciseFilters {exercise_type: Some(2), ..Default::default()};
+        let search_res = search_exercises("PRESS", &filters, Uuid::new_v4(), &connector).await;
+        assert_eq!(search_res.unwrap().len(), 1);
+    }
+
     #[tokio::test]
     async fn test_validate_exercises_none() {
         let (connector, _container) = pg_container().await;
 
         let uuids: Vec<Uuid> = Vec::new();
 
-        let validate_res = validate_exercises(uuids, &connector).await;
+        let validate_res = validate_exercises(uuids, Uuid::new_v4(), false, &connector).await;
         assert!(validate_res.is_ok());
         assert_eq!(validate_res.unwrap(), true);
     }
@@ -186,7 +476,7 @@ mod tests {
 
         let exercise_ids = insert_helper(5, Items::Exercises, &connector, Some("TEST".into())).await;
 
-        let validate_res = validate_exercises(exercise_ids, &connector).await;
+        let validate_res = validate_exercises(exercise_ids, Uuid::new_v4(), false, &connector).await;
         assert!(validate_res.is_ok());
         assert_eq!(validate_res.unwrap(), true);
     }
@@ -198,8 +488,215 @@ mod tests {
         let mut exercise_ids = insert_helper(5, Items::Exercises, &connector, Some("TEST".into())).await;
         exercise_ids.push(Uuid::new_v4());
 
-        let validate_res = validate_exercises(exercise_ids, &connector).await;
+        let validate_res = validate_exercises(exercise_ids, Uuid::new_v4(), false, &connector).await;
         assert!(validate_res.is_ok());
         assert_eq!(validate_res.unwrap(), false);
     }
+
+    #[tokio::test]
+    async fn test_update_exercise_success() {
+        let (connector, _container) = pg_container().await;
+
+        let exercise_id = insert_helper(1, Items::Exercises, &connector, None).await[0];
+        let changes = UncompleteExercise {
+            name: Some("Bench Press".to_string()),
+            main_muscle_group: Some(1),
+            ..Default::default()
+        };
+
+        let update_res = update_exercise(exercise_id, &changes, &connector).await;
+        assert!(update_res.is_ok());
+        let updated = update_res.unwrap();
+        assert_eq!(updated.name, "Bench Press");
+        assert_eq!(updated.main_muscle_group, Some(1));
+    }
+
+    #[tokio::test]
+    async fn test_update_exercise_non_existing() {
+        let (connector, _container) = pg_container().await;
+
+        let changes = UncompleteExercise {name: Some("Bench Press".to_string()), ..Default::default()};
+        let update_res = update_exercise(Uuid::new_v4(), &changes, &connector).await;
+        assert!(matches!(update_res, Err(DBError::ItemNotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn test_retire_exercise_unreferenced() {
+        let (connector, _container) = pg_container().await;
+
+        let exercise_id = insert_helper(1, Items::Exercises, &connector, None).await[0];
+
+        let retire_res = retire_exercise(exercise_id, &connector).await;
+        assert!(retire_res.is_ok());
+        assert_eq!(retire_res.unwrap(), 1);
+
+        // Not referenced, so it is deleted
+        let read_res = lookup_exercise(exercise_id, Uuid::new_v4(), &connector).await;
+        assert!(matches!(read_res, Err(DBError::ItemNotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn test_retire_exercise_referenced() {
+        use crate::lib::db::wk_template_elements_db::lookup_wk_template_element;
+
+        let (connector, _container) = pg_container().await;
+
+        let element_id = insert_helper(1, Items::WkTemplateElements, &connector, None).await[0];
+        let exercise_id = lookup_wk_template_element(element_id, &connector).await.unwrap().exercise_id;
+
+        let retire_res = retire_exercise(exercise_id, &connector).await;
+        assert!(retire_res.is_ok());
+        assert_eq!(retire_res.unwrap(), 1);
+
+        // Still exists, hidden from search and new templates
+        let read_res = lookup_exercise(exercise_id, Uuid::new_v4(), &connector).await;
+        assert!(read_res.unwrap().retired);
+        let search_res = search_exercises("Push-up", &ExerciseFilters::default(), Uuid::new_v4(), &connector).await;
+        assert_eq!(search_res.unwrap().len(), 0);
+        assert!(!validate_exercises(vec![exercise_id], Uuid::new_v4(), false, &connector).await.unwrap());
+        assert!(validate_exercises(vec![exercise_id], Uuid::new_v4(), true, &connector).await.unwrap());
+
+        // Element history is kept
+        assert!(lookup_wk_template_element(element_id, &connector).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_retire_exercise_non_existing() {
+        let (connector, _container) = pg_container().await;
+
+        let retire_res = retire_exercise(Uuid::new_v4(), &connector).await;
+        assert!(retire_res.is_ok());
+        assert_eq!(retire_res.unwrap(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_private_exercise_name_per_owner() {
+        let (connector, _container) = pg_container().await;
+        let user_ids = insert_helper(2, Items::Users, &connector, None).await;
+
+        let catalog = NewExercise {..Default::default()};
+        let private1 = NewExercise {owner_id: Some(user_ids[0]), ..Default::default()};
+        let private2 = NewExercise {owner_id: Some(user_ids[1]), ..Default::default()};
+
+        assert!(insert_exercise(&catalog, &connector).await.is_ok());
+        assert!(insert_exercise(&private1, &connector).await.is_ok());
+        assert!(insert_exercise(&private2, &connector).await.is_ok());
+        assert!(matches!(insert_exercise(&private1, &connector).await, Err(DBError::UniqueViolation(_))));
+    }
+
+    #[tokio::test]
+    async fn test_private_exercise_visibility() {
+        let (connector, _container) = pg_container().await;
+        let user_ids = insert_helper(2, Items::Users, &connector, None).await;
+        let (owner, other) = (user_ids[0], user_ids[1]);
+
+        let private = NewExercise {name: "TEST private".to_string(), owner_id: Some(owner), ..Default::default()};
+        let exercise_id = insert_exercise(&private, &connector).await.unwrap().id;
+
+        assert!(lookup_exercise(exercise_id, owner, &connector).await.is_ok());
+        assert!(matches!(lookup_exercise(exercise_id, other, &connector).await, Err(DBError::ItemNotFound(_))));
+
+        assert_eq!(search_exercises("TEST", &ExerciseFilters::default(), owner, &connector).await.unwrap().len(), 1);
+        assert_eq!(search_exercises("TEST", &ExerciseFilters::default(), other, &connector).await.unwrap().len(), 0);
+
+        assert!(validate_exercises(vec![exercise_id], owner, false, &connector).await.unwrap());
+        assert!(!validate_exercises(vec![exercise_id], other, true, &connector).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_upsert_select_catalog_exercises() {
+        let (connector, _container) = pg_container().await;
+        let existing = NewExercise {name: "Squat".to_string(), exercise_type: Some(1), ..Default::default()};
+        let _ = insert_exercise(&existing, &connector).await;
+        let owner = insert_helper(1, Items::Users, &connector, None).await[0];
+        let private = NewExercise {name: "Private".to_string(), owner_id: Some(owner), ..Default::default()};
+        let _ = insert_exercise(&private, &connector).await;
+
+        let entry = |entry_name: &str, kind: Option<i16>| CatalogEntry {
+            name: entry_name.to_string(), main_muscle_group: None, secondary_muscle_group: None, necessary_equipment: None, exercise_type: kind,
+        };
+        let upsert_res = upsert_catalog_exercises(&[entry("Bench Press", Some(2))], &[entry("Squat", None)], &connector).await;
+        assert_eq!(upsert_res.unwrap(), 2);
+
+        let catalog = select_catalog_exercises(&connector).await.unwrap();
+        let catalog: Vec<(String, Option<i16>)> = catalog.into_iter().map(|ex| (ex.name, ex.exercise_type)).collect();
+        assert_eq!(catalog, vec![("Bench Press".to_string(), Some(2)), ("Squat".to_string(), None)]);
+
+        // Duplicate addition rolls back the whole batch
+        let upsert_res = upsert_catalog_exercises(&[entry("Deadlift", None), entry("Squat", None)], &[], &connector).await;
+        assert!(matches!(upsert_res, Err(DBError::UniqueViolation(_))));
+        assert_eq!(select_catalog_exercises(&connector).await.unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_select_exercise_alternatives() {
+        use crate::lib::db::wk_template_elements_db::insert_batch_wk_template_elements;
+        use crate::lib::models::wk_template_elements_models::NewWkTemplateElement;
+
+        let (connector, _container) = pg_container().await;
+        let user = insert_helper(1, Items::Users, &connector, None).await[0];
+        let catalog = [
+            ("Bench Press", Some(1), Some(2), Some(1), Some(1), None),
+            ("Dumbbell Press", Some(1), Some(2), Some(2), Some(1), None),
+            ("Push Up", Some(1), Some(2), None, Some(2), None),
+            ("Dips", Some(2), Some(1), Some(3), Some(1), None),
+            ("Squat", Some(4), None, Some(1), Some(1), None),
+            ("My Press", Some(1), None, Some(1), Some(1), Some(user)),
+            ("Old Press", Some(1), Some(2), Some(1), Some(1), None),
+        ];
+        let mut inserted = Vec::new();
+        for (exercise_name, main, secondary, equipment, kind, owner) in catalog {
+            let new_exercise = NewExercise {
+                name: exercise_name.to_string(), main_muscle_group: main, secondary_muscle_group: secondary,
+                necessary_equipment: equipment, exercise_type: kind, owner_id: owner,
+            };
+            inserted.push(insert_exercise(&new_exercise, &connector).await.unwrap());
+        }
+        // Used in a template, so it is kept as retired
+        let template_id = insert_helper(1, Items::WkTemplates, &connector, None).await[0];
+        let new_element = NewWkTemplateElement { workout_template_id: template_id, exercise_id: inserted[6].id, ..Default::default() };
+        insert_batch_wk_template_elements(&vec![new_element], &connector).await.unwrap();
+        assert_eq!(retire_exercise(inserted[6].id, &connector).await.unwrap(), 1);
+        assert!(lookup_exercise(inserted[6].id, user, &connector).await.unwrap().retired);
+        let reference = &inserted[0];
+
+        let names = |list: Vec<Exercise>| list.into_iter().map(|ex| ex.name).collect::<Vec<String>>();
+
+        // Old Press would rank first if it wasn't retired
+        let alternatives = select_exercise_alternatives(reference, user, None, 10, 0, &connector).await.unwrap();
+        assert_eq!(names(alternatives), vec!["Dumbbell Press", "My Press", "Push Up", "Dips"]);
+
+        let alternatives = select_exercise_alternatives(reference, user, None, 2, 1, &connector).await.unwrap();
+        assert_eq!(names(alternatives), vec!["My Press", "Push Up"]);
+
+        let alternatives = select_exercise_alternatives(reference, Uuid::new_v4(), Some(&[3]), 10, 0, &connector).await.unwrap();
+        assert_eq!(names(alternatives), vec!["Push Up", "Dips"]);
+    }
+
+    #[tokio::test]
+    async fn test_match_exercise_names() {
+        let (connector, _container) = pg_container().await;
+        let user = insert_helper(1, Items::Users, &connector, None).await[0];
+        let mut inserted = Vec::new();
+        for (exercise_name, owner) in [("Bench Press", None), ("Deadlift", None), ("Squat", None), ("Zercher Carry", Some(user))] {
+            let new_exercise = NewExercise { name: exercise_name.to_string(), owner_id: owner, ..Default::default() };
+            inserted.push(insert_exercise(&new_exercise, &connector).await.unwrap());
+        }
+        let alias = NewExerciseAlias { exercise_id: inserted[2].id, locale: "en".to_string(), name: "Back Squat (Barbell)".to_string(), preferred: false };
+        insert_exercise_alias(&alias, &connector).await.unwrap();
+
+        let terms: Vec<String> = ["bench press", "Bench Press (Barbell)", "Back Squat (Barbell)", "Zercher Carry", "Bicep Curl"]
+            .iter().map(|term| term.to_string()).collect();
+
+        let matches = match_exercise_names(&terms, user, &connector).await.unwrap();
+        assert_eq!(matches.len(), 4);
+        assert_eq!(matches["bench press"].id, inserted[0].id);
+        assert_eq!(matches["Bench Press (Barbell)"].id, inserted[0].id);
+        assert_eq!(matches["Back Squat (Barbell)"].id, inserted[2].id);
+        assert_eq!(matches["Zercher Carry"].id, inserted[3].id);
+
+        // Private exercises of other users are never matched
+        let matches = match_exercise_names(&terms, Uuid::new_v4(), &connector).await.unwrap();
+        assert!(!matches.contains_key("Zercher Carry"));
+    }
 }
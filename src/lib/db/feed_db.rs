@@ -0,0 +1,218 @@
+use std::collections::HashMap;
+use diesel::prelude::*;
+use diesel::result::Error;
+use diesel::sql_types::{Array, Uuid as SqlUuid};
+use diesel_async::{AsyncPgConnection, RunQueryDsl};
+use diesel_async::scoped_futures::ScopedFutureExt;
+use uuid::Uuid;
+
+use crate::schema::{follows, users, wkexecutionelements, workoutexecutions, workouttemplates};
+use crate::lib::models::feed_models::{ExecutionPrs, ExecutionSummary, FeedCursor, FeedItem};
+use crate::lib::models::user_models::UserInfo;
+use crate::lib::models::workout_execution_models::{WorkoutExecution, COMPLETED};
+use crate::lib::errors::DBError;
+
+use super::DBConnector;
+use super::reactions_db::reaction_counts;
+
+/// Exercises of each execution lifted heavier than in any earlier day by the same author
+const PRS_QUERY: &str = "
+SELECT page.workout_execution_id, COUNT(*) AS prs
+FROM (
+    SELECT el.workout_execution_id, wt.user_id, we.date, el.exercise_id, MAX(el.weight) AS best
+    FROM WkExecutionElements el
+    JOIN WorkoutExecutions we ON we.id = el.workout_execution_id
+    JOIN WorkoutTemplates wt ON wt.id = we.workout_template_id
+    WHERE el.workout_execution_id = ANY($1) AND el.weight IS NOT NULL
+    GROUP BY el.workout_execution_id, wt.user_id, we.date, el.exercise_id
+) page
+WHERE page.best > (
+    SELECT MAX(prev.weight)
+    FROM WorkoutTemplates prev_wt
+    JOIN WorkoutExecutions prev_we ON prev_we.workout_template_id = prev_wt.id
+    JOIN WkExecutionElements prev ON prev.workout_execution_id = prev_we.id
+    WHERE prev_wt.user_id = page.user_id AND prev.exercise_id = page.exercise_id AND prev_we.date < page.date AND prev_we.status = 0
+)
+GROUP BY page.workout_execution_id";
+
+/// Executions of the users the viewer follows, newest first, with a summary of each.
+///
+/// Only accepted follows count, so private profiles only show up once they approved the viewer.
+/// Pagination is by cursor, `before` being the last execution of the previous page, so pages
+/// stay stable while new workouts come in and deep pages don't get slower.
+pub async fn select_feed(viewer_id: Uuid, before: Option<FeedCursor>, limit: i64, connector: &DBConnector) -> Result<Vec<FeedItem>, DBError> {
+
+    let mut conn = connector.rds_connection().await?;
+    conn.build_transaction()
+        .read_only()
+        .repeatable_read()
+        .run::<Vec<FeedItem>, Error, _>(|conn| async move {
+            let mut query = workoutexecutions::table
+                .inner_join(workouttemplates::table)
+                .inner_join(follows::table.on(follows::followed_id.eq(workouttemplates::user_id)))
+                .inner_join(users::table.on(users::id.eq(workouttemplates::user_id)))
+                .filter(follows::follower_id.eq(viewer_id))
+                .filter(follows::accepted.eq(true))
+                .filter(workoutexecutions::status.eq(COMPLETED))
+                .filter(users::deletion_scheduled_for.is_null())
+                .into_boxed();
+            if let Some(cursor) = before {
+                query = query.filter(
+                    workoutexecutions::date.lt(cursor.date)
+                        .or(workoutexecutions::date.eq(cursor.date).and(workoutexecutions::id.lt(cursor.id)))
+                );
+            }
+            let page = query
+                .order((workoutexecutions::date.desc(), workoutexecutions::id.desc()))
+                .limit(limit)
+                .select((WorkoutExecution::as_select(), workouttemplates::name, UserInfo::as_select()))
+                .load::<(WorkoutExecution, String, UserInfo)>(conn)
+                .await?;
+            let execution_ids: Vec<Uuid> = page.iter().map(|(execution, _, _)| execution.id).collect();
+
+            // Sets, duration and volume of each execution
+            let elements = wkexecutionelements::table
+                .filter(wkexecutionelements::workout_execution_id.eq_any(&execution_ids))
+                .select((
+                    wkexecutionelements::workout_execution_id,
+                    wkexecutionelements::reps,
+                    wkexecutionelements::weight,
+                    wkexecutionelements::time,
+                    wkexecutionelements::rest,
+                ))
+                .load::<(Uuid, i16, Option<f32>, i32, i16)>(conn)
+                .await?;
+            let mut summaries: HashMap<Uuid, ExecutionSummary> = HashMap::new();
+            for (execution_id, reps, weight, time, rest) in elements {
+                let summary = summaries.entry(execution_id).or_default();
+                summary.sets += 1;
+                summary.duration += time as i64 + rest as i64;
+                summary.volume += reps as f32 * weight.unwrap_or(0.0);
+            }
+
+            let prs = execution_prs(&execution_ids, conn).await?;
+            let mut reactions = reaction_counts(&execution_ids, conn).await?;
+
+            Ok(page.into_iter().map(|(workout_execution, template_name, author)| {
+                let summary = ExecutionSummary {
+                    template_name,
+                    prs: prs.get(&workout_execution.id).copied().unwrap_or(0),
+                    ..summaries.remove(&workout_execution.id).unwrap_or_default()
+                };
+                let reactions = reactions.remove(&workout_execution.id).unwrap_or_default();
+                FeedItem { author, workout_execution, summary, reactions }
+            }).collect())
+        }.scope_boxed())
+        .await
+        .map_err(|error| DBError::OperationError(error.to_string()))
+}
+
+/// PRs in each execution, executions without any are left out
+pub async fn count_prs(execution_ids: &[Uuid], connector: &DBConnector) -> Result<HashMap<Uuid, i64>, DBError> {
+
+    let mut conn = connector.rds_connection().await?;
+    execution_prs(execution_ids, &mut conn)
+        .await
+        .map_err(|error| DBError::OperationError(error.to_string()))
+}
+
+async fn execution_prs(execution_ids: &[Uuid], conn: &mut AsyncPgConnection) -> QueryResult<HashMap<Uuid, i64>> {
+    Ok(diesel::sql_query(PRS_QUERY)
+        .bind::<Array<SqlUuid>, _>(execution_ids)
+        .load::<ExecutionPrs>(conn)
+        .await?
+        .into_iter()
+        .map(|row| (row.workout_execution_id, row.prs))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+    use crate::lib::utils::tests::{pg_container, insert_helper, Items};
+    use crate::lib::db::follows_db::insert_follow;
+    use crate::lib::db::users_db::update_user;
+    use crate::lib::db::wk_execution_elements_db::insert_batch_wk_execution_elements;
+    use crate::lib::db::workout_executions_db::insert_workout_execution;
+    use crate::lib::db::workout_templates_db::insert_workout_template;
+    use crate::lib::models::user_models::UncompleteUser;
+    use crate::lib::models::wk_execution_elements_models::NewWkExecutionElement;
+    use crate::lib::models::workout_execution_models::NewWorkoutExecution;
+    use crate::lib::models::workout_templates_models::NewWorkoutTemplate;
+
+    // TEST CASES
+    // * Only accepted follows, newest first, paginated by cursor
+    // * Summary with sets, duration, volume and PRs
+
+    fn day(n: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2024, 9, n).unwrap()
+    }
+
+    async fn execution_on(user_id: Uuid, date: NaiveDate, weights: &[(Uuid, f32)], connector: &DBConnector) -> Uuid {
+        let template = insert_workout_template(&NewWorkoutTemplate { user_id, ..Default::default() }, connector).await.unwrap();
+        let execution = insert_workout_execution(&NewWorkoutExecution { workout_template_id: template.id, date, ..Default::default() }, connector).await.unwrap();
+        let elements: Vec<NewWkExecutionElement> = weights.iter().enumerate().map(|(n, (exercise_id, weight))| NewWkExecutionElement {
+            workout_execution_id: execution.id,
+            exercise_id: *exercise_id,
+            set_number: n as i16,
+            reps: 5,
+            weight: Some(*weight),
+            time: 30,
+            rest: 90,
+            ..Default::default()
+        }).collect();
+        insert_batch_wk_execution_elements(&elements, connector).await.unwrap();
+        execution.id
+    }
+
+    #[tokio::test]
+    async fn test_select_feed_follows_and_pages() {
+        let (connector, _container) = pg_container().await;
+        let users = insert_helper(4, Items::Users, &connector, None).await;
+        let (viewer, followed, private, stranger) = (users[0], users[1], users[2], users[3]);
+        let exercise = insert_helper(1, Items::Exercises, &connector, None).await[0];
+        update_user(&private, &UncompleteUser { private_profile: Some(true), ..Default::default() }, &connector).await.unwrap();
+        insert_follow(viewer, followed, &connector).await.unwrap();
+        insert_follow(viewer, private, &connector).await.unwrap();
+
+        let mut expected = Vec::new();
+        for n in 1..=3 {
+            expected.push(execution_on(followed, day(n), &[(exercise, 50.0)], &connector).await);
+        }
+        execution_on(private, day(2), &[(exercise, 50.0)], &connector).await;
+        execution_on(stranger, day(2), &[(exercise, 50.0)], &connector).await;
+        execution_on(viewer, day(2), &[(exercise, 50.0)], &connector).await;
+        expected.reverse();
+
+        let ids = |items: &[FeedItem]| items.iter().map(|item| item.workout_execution.id).collect::<Vec<Uuid>>();
+        let first = select_feed(viewer, None, 2, &connector).await.unwrap();
+        assert_eq!(ids(&first), expected[..2]);
+        let cursor = FeedCursor { date: first[1].workout_execution.date, id: first[1].workout_execution.id };
+        let second = select_feed(viewer, Some(cursor), 2, &connector).await.unwrap();
+        assert_eq!(ids(&second), expected[2..]);
+        assert!(second.iter().all(|item| item.author.id == followed));
+    }
+
+    #[tokio::test]
+    async fn test_select_feed_summary() {
+        let (connector, _container) = pg_container().await;
+        let users = insert_helper(2, Items::Users, &connector, None).await;
+        let exercises = insert_helper(3, Items::Exercises, &connector, None).await;
+        insert_follow(users[0], users[1], &connector).await.unwrap();
+
+        execution_on(users[1], day(1), &[(exercises[0], 100.0), (exercises[1], 60.0)], &connector).await;
+        // Heavier bench, lighter row and a first time exercise: one PR
+        let latest = execution_on(users[1], day(2), &[(exercises[0], 105.0), (exercises[0], 100.0), (exercises[1], 50.0), (exercises[2], 20.0)], &connector).await;
+
+        let feed = select_feed(users[0], None, 1, &connector).await.unwrap();
+        assert_eq!(feed[0].workout_execution.id, latest);
+        let summary = &feed[0].summary;
+        assert_eq!(summary.template_name, "Placeholder");
+        assert_eq!(summary.sets, 4);
+        assert_eq!(summary.duration, 4 * 120);
+        assert_eq!(summary.volume, 5.0 * (105.0 + 100.0 + 50.0 + 20.0));
+        assert_eq!(summary.prs, 1);
+        assert_eq!(count_prs(&[latest], &connector).await.unwrap()[&latest], 1);
+    }
+}
@@ -0,0 +1,236 @@
+use diesel::prelude::*;
+use diesel::result::{Error, DatabaseErrorKind};
+use diesel_async::{AsyncConnection, RunQueryDsl};
+use diesel_async::scoped_futures::ScopedFutureExt;
+use uuid::Uuid;
+
+use crate::schema::{follows, users};
+use crate::lib::models::follow_models::{Follow, NewFollow};
+use crate::lib::models::user_models::UserInfo;
+use crate::lib::errors::DBError;
+
+use super::DBConnector;
+
+/// Follows a user, accepted right away unless their profile is private.
+pub async fn insert_follow(follower_id: Uuid, followed_id: Uuid, connector: &DBConnector) -> Result<Follow, DBError> {
+
+    let mut conn = connector.rds_connection().await?;
+    conn.transaction::<Follow, Error, _>(|conn| async move {
+        let private_profile = users::table.find(followed_id)
+            .select(users::private_profile)
+            .first::<bool>(conn)
+            .await?;
+        diesel::insert_into(follows::table)
+            .values(&NewFollow { follower_id, followed_id, accepted: !private_profile })
+            .returning(Follow::as_returning())
+            .get_result(conn)
+            .await
+    }.scope_boxed())
+    .await
+    .map_err(|error| match error {
+        Error::NotFound | Error::DatabaseError(DatabaseErrorKind::ForeignKeyViolation, _) => {
+            DBError::ItemNotFound("No user exists with the corresponding id".to_string())
+        },
+        Error::DatabaseError(DatabaseErrorKind::UniqueViolation, _) => {
+            DBError::UniqueViolation("Already following or requested to follow this user".to_string())
+        },
+        _ => DBError::OperationError(error.to_string()),
+    })
+}
+
+/// Stops following a user, or cancels the request if still pending.
+pub async fn delete_follow(follower_id: Uuid, followed_id: Uuid, connector: &DBConnector) -> Result<usize, DBError> {
+
+    let mut conn = connector.rds_connection().await?;
+    diesel::delete(follows::table.find((follower_id, followed_id)))
+        .execute(&mut conn)
+        .await
+        .map_err(|error| DBError::OperationError(error.to_string()))
+}
+
+/// Accepts a pending request to follow the user.
+pub async fn accept_follow_request(followed_id: Uuid, follower_id: Uuid, connector: &DBConnector) -> Result<Follow, DBError> {
+
+    let mut conn = connector.rds_connection().await?;
+    diesel::update(follows::table.find((follower_id, followed_id)).filter(follows::accepted.eq(false)))
+        .set(follows::accepted.eq(true))
+        .returning(Follow::as_returning())
+        .get_result(&mut conn)
+        .await
+        .map_err(|error| match error {
+            Error::NotFound => DBError::ItemNotFound("No pending follow request from that user".to_string()),
+            _ => DBError::OperationError(error.to_string()),
+        })
+}
+
+/// Denies a pending request to follow the user, accepted follows are left untouched.
+pub async fn delete_follow_request(followed_id: Uuid, follower_id: Uuid, connector: &DBConnector) -> Result<usize, DBError> {
+
+    let mut conn = connector.rds_connection().await?;
+    diesel::delete(follows::table.find((follower_id, followed_id)).filter(follows::accepted.eq(false)))
+        .execute(&mut conn)
+        .await
+        .map_err(|error| DBError::OperationError(error.to_string()))
+}
+
+/// Returns the users following the given one, or requesting to if not `accepted`, most recent first.
+/// * Users pending deletion are left out
+pub async fn select_followers(user_id: Uuid, accepted: bool, limit: i64, offset: i64, connector: &DBConnector) -> Result<Vec<UserInfo>, DBError> {
+
+    let mut conn = connector.rds_connection().await?;
+    follows::table
+        .inner_join(users::table.on(users::id.eq(follows::follower_id)))
+        .filter(follows::followed_id.eq(user_id))
+        .filter(follows::accepted.eq(accepted))
+        .filter(users::deletion_scheduled_for.is_null())
+        .order((follows::requested_at.desc(), users::id))
+        .limit(limit)
+        .offset(offset)
+        .select(UserInfo::as_select())
+        .load::<UserInfo>(&mut conn)
+        .await
+        .map_err(|error| DBError::OperationError(error.to_string()))
+}
+
+/// Returns the users the given one follows, most recent first.
+/// * Pending requests and users pending deletion are left out
+pub async fn select_following(user_id: Uuid, limit: i64, offset: i64, connector: &DBConnector) -> Result<Vec<UserInfo>, DBError> {
+
+    let mut conn = connector.rds_connection().await?;
+    follows::table
+        .inner_join(users::table.on(users::id.eq(follows::followed_id)))
+        .filter(follows::follower_id.eq(user_id))
+        .filter(follows::accepted.eq(true))
+        .filter(users::deletion_scheduled_for.is_null())
+        .order((follows::requested_at.desc(), users::id))
+        .limit(limit)
+        .offset(offset)
+        .select(UserInfo::as_select())
+        .load::<UserInfo>(&mut conn)
+        .await
+        .map_err(|error| DBError::OperationError(error.to_string()))
+}
+
+/// True if the viewer can see the owner's social data: it is their own, the profile is public
+/// or they are an accepted follower.
+pub async fn can_view_profile(viewer_id: Uuid, owner_id: Uuid, connector: &DBConnector) -> Result<bool, DBError> {
+
+    let mut conn = connector.rds_connection().await?;
+    let private_profile = users::table.find(owner_id)
+        .select(users::private_profile)
+        .first::<bool>(&mut conn)
+        .await
+        .map_err(|error| match error {
+            Error::NotFound => DBError::ItemNotFound("No user exists with the corresponding id".to_string()),
+            _ => DBError::OperationError(error.to_string()),
+        })?;
+    if viewer_id == owner_id || !private_profile {
+        return Ok(true);
+    }
+    diesel::select(diesel::dsl::exists(
+        follows::table
+            .find((viewer_id, owner_id))
+            .filter(follows::accepted.eq(true))
+    ))
+    .get_result::<bool>(&mut conn)
+    .await
+    .map_err(|error| DBError::OperationError(error.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lib::utils::tests::{pg_container, insert_helper, Items};
+    use crate::lib::db::users_db::insert_user;
+    use crate::lib::models::user_models::User;
+
+    // TEST CASES
+    // * Follow public profile, duplicate, non existing user
+    // * Follow private profile, accept and deny requests
+    // * List followers, following and requests with pagination
+    // * Profile visibility
+
+    async fn private_user(connector: &DBConnector) -> Uuid {
+        insert_user(&User { username: Uuid::new_v4().to_string(), private_profile: true, ..Default::default() }, connector).await.unwrap().id
+    }
+
+    #[tokio::test]
+    async fn test_insert_follow_public() {
+        let (connector, _container) = pg_container().await;
+        let users = insert_helper(2, Items::Users, &connector, None).await;
+
+        let follow = insert_follow(users[0], users[1], &connector).await.unwrap();
+        assert!(follow.accepted);
+
+        let res = insert_follow(users[0], users[1], &connector).await;
+        assert!(matches!(res, Err(DBError::UniqueViolation(_))));
+        let res = insert_follow(users[0], Uuid::new_v4(), &connector).await;
+        assert!(matches!(res, Err(DBError::ItemNotFound(_))));
+        let res = insert_follow(users[0], users[0], &connector).await;
+        assert!(matches!(res, Err(DBError::OperationError(_))));
+
+        assert_eq!(delete_follow(users[0], users[1], &connector).await.unwrap(), 1);
+        assert_eq!(delete_follow(users[0], users[1], &connector).await.unwrap(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_follow_requests() {
+        let (connector, _container) = pg_container().await;
+        let followers = insert_helper(2, Items::Users, &connector, None).await;
+        let owner = private_user(&connector).await;
+
+        for follower in &followers {
+            let follow = insert_follow(*follower, owner, &connector).await.unwrap();
+            assert!(!follow.accepted);
+        }
+        assert_eq!(select_followers(owner, false, 10, 0, &connector).await.unwrap().len(), 2);
+
+        let follow = accept_follow_request(owner, followers[0], &connector).await.unwrap();
+        assert!(follow.accepted);
+        let res = accept_follow_request(owner, followers[0], &connector).await;
+        assert!(matches!(res, Err(DBError::ItemNotFound(_))));
+
+        // Accepted follows can't be denied, only pending ones
+        assert_eq!(delete_follow_request(owner, followers[0], &connector).await.unwrap(), 0);
+        assert_eq!(delete_follow_request(owner, followers[1], &connector).await.unwrap(), 1);
+
+        let ids = |list: Vec<UserInfo>| list.into_iter().map(|user| user.id).collect::<Vec<Uuid>>();
+        assert_eq!(ids(select_followers(owner, true, 10, 0, &connector).await.unwrap()), vec![followers[0]]);
+        assert!(select_followers(owner, false, 10, 0, &connector).await.unwrap().is_empty());
+        assert_eq!(ids(select_following(followers[0], 10, 0, &connector).await.unwrap()), vec![owner]);
+    }
+
+    #[tokio::test]
+    async fn test_select_followers_paginated() {
+        let (connector, _container) = pg_container().await;
+        let users = insert_helper(6, Items::Users, &connector, None).await;
+        for follower in &users[1..] {
+            insert_follow(*follower, users[0], &connector).await.unwrap();
+        }
+
+        let first = select_followers(users[0], true, 3, 0, &connector).await.unwrap();
+        let second = select_followers(users[0], true, 3, 3, &connector).await.unwrap();
+        assert_eq!((first.len(), second.len()), (3, 2));
+        assert!(first.iter().all(|user| !second.contains(user)));
+        assert!(select_following(users[0], 3, 0, &connector).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_can_view_profile() {
+        let (connector, _container) = pg_container().await;
+        let users = insert_helper(2, Items::Users, &connector, None).await;
+        let owner = private_user(&connector).await;
+
+        assert!(can_view_profile(users[0], users[1], &connector).await.unwrap());
+        assert!(can_view_profile(owner, owner, &connector).await.unwrap());
+        assert!(!can_view_profile(users[0], owner, &connector).await.unwrap());
+
+        insert_follow(users[0], owner, &connector).await.unwrap();
+        assert!(!can_view_profile(users[0], owner, &connector).await.unwrap());
+        accept_follow_request(owner, users[0], &connector).await.unwrap();
+        assert!(can_view_profile(users[0], owner, &connector).await.unwrap());
+
+        let res = can_view_profile(users[0], Uuid::new_v4(), &connector).await;
+        assert!(matches!(res, Err(DBError::ItemNotFound(_))));
+    }
+}
@@ -0,0 +1,161 @@
+use chrono::{Duration, NaiveDate};
+use diesel::sql_types::{Date, Nullable, Uuid as SqlUuid};
+use diesel_async::RunQueryDsl;
+use uuid::Uuid;
+
+use crate::lib::models::leaderboard_models::{LeaderboardMetric, LeaderboardScore};
+use crate::lib::errors::DBError;
+
+use super::DBConnector;
+
+/// The viewer and the users they follow, minus those who opted out of rankings or are pending deletion
+const PARTICIPANTS: &str = "
+WITH participants AS (
+    SELECT u.id, u.username, u.display_name, u.weight
+    FROM Users u
+    WHERE (u.id = $1 OR u.id IN (SELECT followed_id FROM Follows WHERE follower_id = $1 AND accepted))
+        AND u.show_in_leaderboards AND u.deletion_scheduled_for IS NULL
+)";
+
+/// Best estimated 1RM of the exercise, a single rep counts as is
+const ONE_REP_MAX_QUERY: &str = "
+SELECT p.id, p.username, p.display_name, p.weight AS body_weight,
+    MAX(CASE WHEN el.reps = 1 THEN el.weight ELSE el.weight * (1 + el.reps / 30.0::float4) END)::float4 AS score
+FROM participants p
+JOIN WorkoutTemplates wt ON wt.user_id = p.id
+JOIN WorkoutExecutions we ON we.workout_template_id = wt.id
+JOIN WkExecutionElements el ON el.workout_execution_id = we.id
+WHERE el.exercise_id = $2 AND el.weight IS NOT NULL AND el.reps > 0 AND we.status = 0
+GROUP BY p.id, p.username, p.display_name, p.weight";
+
+/// Reps times weight over the week, `$2` inclusive to `$3` exclusive
+const WEEKLY_VOLUME_QUERY: &str = "
+SELECT p.id, p.username, p.display_name, p.weight AS body_weight,
+    SUM(el.reps * COALESCE(el.weight, 0))::float4 AS score
+FROM participants p
+JOIN WorkoutTemplates wt ON wt.user_id = p.id
+JOIN WorkoutExecutions we ON we.workout_template_id = wt.id
+JOIN WkExecutionElements el ON el.workout_execution_id = we.id
+WHERE we.date >= $2 AND we.date < $3 AND we.status = 0
+GROUP BY p.id, p.username, p.display_name, p.weight";
+
+/// Executions over the week, `$2` inclusive to `$3` exclusive
+const WEEKLY_WORKOUTS_QUERY: &str = "
+SELECT p.id, p.username, p.display_name, p.weight AS body_weight,
+    COUNT(we.id)::float4 AS score
+FROM participants p
+JOIN WorkoutTemplates wt ON wt.user_id = p.id
+JOIN WorkoutExecutions we ON we.workout_template_id = wt.id
+WHERE we.date >= $2 AND we.date < $3 AND we.status = 0
+GROUP BY p.id, p.username, p.display_name, p.weight";
+
+/// Scores of the viewer and the people they follow, users without any are left out
+/// * `exercise_id` is only used for the 1RM, nobody has one without it, and `week_start` for the weekly metrics
+pub async fn select_leaderboard_scores(viewer_id: Uuid, metric: LeaderboardMetric, exercise_id: Option<Uuid>, week_start: NaiveDate, connector: &DBConnector) -> Result<Vec<LeaderboardScore>, DBError> {
+
+    let mut conn = connector.rds_connection().await?;
+    let week_end = week_start + Duration::days(7);
+    let res = match metric {
+        LeaderboardMetric::OneRepMax => {
+            diesel::sql_query(format!("{}{}", PARTICIPANTS, ONE_REP_MAX_QUERY))
+                .bind::<SqlUuid, _>(viewer_id)
+                .bind::<Nullable<SqlUuid>, _>(exercise_id)
+                .load::<LeaderboardScore>(&mut conn)
+                .await
+        },
+        LeaderboardMetric::WeeklyVolume | LeaderboardMetric::WeeklyWorkouts => {
+            let query = match metric {
+                LeaderboardMetric::WeeklyVolume => WEEKLY_VOLUME_QUERY,
+                _ => WEEKLY_WORKOUTS_QUERY,
+            };
+            diesel::sql_query(format!("{}{}", PARTICIPANTS, query))
+                .bind::<SqlUuid, _>(viewer_id)
+                .bind::<Date, _>(week_start)
+                .bind::<Date, _>(week_end)
+                .load::<LeaderboardScore>(&mut conn)
+                .await
+        },
+    };
+    res.map_err(|error| DBError::OperationError(error.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lib::utils::tests::{pg_container, insert_helper, Items};
+    use crate::lib::db::follows_db::insert_follow;
+    use crate::lib::db::users_db::update_user;
+    use crate::lib::db::wk_execution_elements_db::insert_batch_wk_execution_elements;
+    use crate::lib::db::workout_executions_db::insert_workout_execution;
+    use crate::lib::db::workout_templates_db::insert_workout_template;
+    use crate::lib::models::user_models::UncompleteUser;
+    use crate::lib::models::wk_execution_elements_models::NewWkExecutionElement;
+    use crate::lib::models::workout_execution_models::NewWorkoutExecution;
+    use crate::lib::models::workout_templates_models::NewWorkoutTemplate;
+
+    // TEST CASES
+    // * Estimated 1RM among followed users, opted out and strangers left out
+    // * Weekly volume and workouts within the week only
+
+    fn day(n: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2024, 10, n).unwrap()
+    }
+
+    async fn execution_on(user_id: Uuid, date: NaiveDate, sets: &[(Uuid, i16, f32)], connector: &DBConnector) {
+        let template = insert_workout_template(&NewWorkoutTemplate { user_id, ..Default::default() }, connector).await.unwrap();
+        let execution = insert_workout_execution(&NewWorkoutExecution { workout_template_id: template.id, date, ..Default::default() }, connector).await.unwrap();
+        let elements: Vec<NewWkExecutionElement> = sets.iter().enumerate().map(|(n, (exercise_id, reps, weight))| NewWkExecutionElement {
+            workout_execution_id: execution.id,
+            exercise_id: *exercise_id,
+            set_number: n as i16,
+            reps: *reps,
+            weight: Some(*weight),
+            ..Default::default()
+        }).collect();
+        insert_batch_wk_execution_elements(&elements, connector).await.unwrap();
+    }
+
+    fn scores_of(scores: Vec<LeaderboardScore>) -> Vec<(Uuid, f32)> {
+        let mut scores: Vec<(Uuid, f32)> = scores.into_iter().map(|row| (row.id, row.score)).collect();
+        scores.sort_by(|a, b| b.1.total_cmp(&a.1));
+        scores
+    }
+
+    #[tokio::test]
+    async fn test_one_rep_max() {
+        let (connector, _container) = pg_container().await;
+        let users = insert_helper(4, Items::Users, &connector, None).await;
+        let (viewer, followed, opted_out, stranger) = (users[0], users[1], users[2], users[3]);
+        let exercise = insert_helper(1, Items::Exercises, &connector, None).await[0];
+        insert_follow(viewer, followed, &connector).await.unwrap();
+        insert_follow(viewer, opted_out, &connector).await.unwrap();
+        update_user(&opted_out, &UncompleteUser { show_in_leaderboards: Some(false), ..Default::default() }, &connector).await.unwrap();
+
+        execution_on(viewer, day(1), &[(exercise, 1, 100.0), (exercise, 5, 80.0)], &connector).await;
+        execution_on(followed, day(2), &[(exercise, 10, 90.0)], &connector).await;
+        execution_on(opted_out, day(2), &[(exercise, 1, 200.0)], &connector).await;
+        execution_on(stranger, day(2), &[(exercise, 1, 200.0)], &connector).await;
+
+        let scores = select_leaderboard_scores(viewer, LeaderboardMetric::OneRepMax, Some(exercise), day(21), &connector).await.unwrap();
+        assert_eq!(scores_of(scores), vec![(followed, 120.0), (viewer, 100.0)]);
+    }
+
+    #[tokio::test]
+    async fn test_weekly_metrics() {
+        let (connector, _container) = pg_container().await;
+        let users = insert_helper(2, Items::Users, &connector, None).await;
+        let exercise = insert_helper(1, Items::Exercises, &connector, None).await[0];
+        insert_follow(users[0], users[1], &connector).await.unwrap();
+
+        execution_on(users[0], day(21), &[(exercise, 10, 50.0)], &connector).await;
+        execution_on(users[0], day(27), &[(exercise, 10, 50.0)], &connector).await;
+        execution_on(users[0], day(28), &[(exercise, 10, 50.0)], &connector).await;
+        execution_on(users[1], day(20), &[(exercise, 10, 50.0)], &connector).await;
+        execution_on(users[1], day(22), &[(exercise, 5, 100.0), (exercise, 5, 100.0), (exercise, 5, 100.0)], &connector).await;
+
+        let scores = select_leaderboard_scores(users[0], LeaderboardMetric::WeeklyVolume, None, day(21), &connector).await.unwrap();
+        assert_eq!(scores_of(scores), vec![(users[1], 1500.0), (users[0], 1000.0)]);
+        let scores = select_leaderboard_scores(users[0], LeaderboardMetric::WeeklyWorkouts, None, day(21), &connector).await.unwrap();
+        assert_eq!(scores_of(scores), vec![(users[0], 2.0), (users[1], 1.0)]);
+    }
+}
@@ -1,9 +1,28 @@
 pub mod users_db;
+pub mod user_export_db;
+pub mod body_measurements_db;
+pub mod follows_db;
+pub mod notifications_db;
 pub mod exercises_db;
+pub mod exercise_aliases_db;
+pub mod exercise_content_db;
 pub mod workout_templates_db;
 pub mod wk_template_elements_db;
+pub mod progression_db;
+pub mod programs_db;
+pub mod schedules_db;
 pub mod wk_execution_elements_db;
 pub mod workout_executions_db;
+pub mod workout_drafts_db;
+pub mod workout_sessions_db;
+pub mod sync_db;
+pub mod workout_imports_db;
+pub mod feed_db;
+pub mod leaderboards_db;
+pub mod challenges_db;
+pub mod comments_db;
+pub mod reactions_db;
+pub mod sql_functions;
 
 
 use std::env;
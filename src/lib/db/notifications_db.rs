@@ -0,0 +1,157 @@
+use diesel::prelude::*;
+use diesel::result::Error;
+use diesel_async::{AsyncConnection, RunQueryDsl};
+use diesel_async::scoped_futures::ScopedFutureExt;
+use uuid::Uuid;
+
+use crate::schema::{notifications, users};
+use crate::lib::models::notification_models::{NewNotification, Notification, NotificationSettings, NotificationWithActor, UnreadCount};
+use crate::lib::models::user_models::UserInfo;
+use crate::lib::errors::DBError;
+
+use super::DBConnector;
+
+/// Write a notification unless the recipient opted out of its kind
+/// * Nothing is written for users acting on their own things, nor twice for the same reaction
+pub async fn insert_notification(new_notification: &NewNotification, connector: &DBConnector) -> Result<Option<Notification>, DBError> {
+
+    let mut conn = connector.rds_connection().await?;
+    conn.transaction::<Option<Notification>, Error, _>(|conn| async move {
+        let settings = users::table.find(new_notification.user_id)
+            .select(NotificationSettings::as_select())
+            .first::<NotificationSettings>(conn)
+            .await?;
+        if !settings.allows(new_notification.kind) || new_notification.actor_id == Some(new_notification.user_id) {
+            return Ok(None);
+        }
+        diesel::insert_into(notifications::table)
+            .values(new_notification)
+            .on_conflict_do_nothing()
+            .returning(Notification::as_returning())
+            .get_result(conn)
+            .await
+            .optional()
+    }.scope_boxed())
+    .await
+    .map_err(|error| match error {
+        Error::NotFound => DBError::ItemNotFound("No user exists with the corresponding id".to_string()),
+        _ => DBError::OperationError(error.to_string()),
+    })
+}
+
+/// Notifications of a user with whoever caused them, newest first
+pub async fn select_notifications(user_id: Uuid, unread_only: bool, limit: i64, offset: i64, connector: &DBConnector) -> Result<Vec<NotificationWithActor>, DBError> {
+
+    let mut conn = connector.rds_connection().await?;
+    let mut query = notifications::table
+        .left_join(users::table.on(users::id.nullable().eq(notifications::actor_id)))
+        .filter(notifications::user_id.eq(user_id))
+        .into_boxed();
+    if unread_only {
+        query = query.filter(notifications::read.eq(false));
+    }
+    query
+        .order((notifications::date_created.desc(), notifications::id))
+        .limit(limit)
+        .offset(offset)
+        .select((Notification::as_select(), Option::<UserInfo>::as_select()))
+        .load::<(Notification, Option<UserInfo>)>(&mut conn)
+        .await
+        .map(|rows| rows.into_iter().map(|(notification, actor)| NotificationWithActor { notification, actor }).collect())
+        .map_err(|error| DBError::OperationError(error.to_string()))
+}
+
+/// Unread notifications of a user per kind, kinds without any are left out
+pub async fn count_unread(user_id: Uuid, connector: &DBConnector) -> Result<Vec<UnreadCount>, DBError> {
+
+    let mut conn = connector.rds_connection().await?;
+    notifications::table
+        .filter(notifications::user_id.eq(user_id))
+        .filter(notifications::read.eq(false))
+        .group_by(notifications::kind)
+        .order(notifications::kind)
+        .select((notifications::kind, diesel::dsl::count_star()))
+        .load::<(i16, i64)>(&mut conn)
+        .await
+        .map(|rows| rows.into_iter().map(|(kind, count)| UnreadCount { kind, count }).collect())
+        .map_err(|error| DBError::OperationError(error.to_string()))
+}
+
+/// Mark notifications of a user as read, the given ones or all of them
+pub async fn mark_notifications_read(user_id: Uuid, notification_ids: Option<&[Uuid]>, connector: &DBConnector) -> Result<usize, DBError> {
+
+    let mut conn = connector.rds_connection().await?;
+    let mut query = diesel::update(notifications::table)
+        .filter(notifications::user_id.eq(user_id))
+        .into_boxed();
+    if let Some(notification_ids) = notification_ids {
+        query = query.filter(notifications::id.eq_any(notification_ids));
+    }
+    query
+        .set(notifications::read.eq(true))
+        .execute(&mut conn)
+        .await
+        .map_err(|error| DBError::OperationError(error.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lib::utils::tests::{pg_container, insert_helper, Items};
+    use crate::lib::db::users_db::update_user;
+    use crate::lib::models::notification_models::{COMMENT, NEW_FOLLOWER, PR_ACHIEVED, REACTION};
+    use crate::lib::models::user_models::UncompleteUser;
+
+    // TEST CASES
+    // * Insert, skipped for opt outs, own actions and repeated reactions
+    // * Select with actor, unread only, counts
+    // * Mark single and all as read
+
+    #[tokio::test]
+    async fn test_insert_notification() {
+        let (connector, _container) = pg_container().await;
+        let users = insert_helper(2, Items::Users, &connector, None).await;
+        let execution = insert_helper(1, Items::WkExecutions, &connector, None).await[0];
+        let reaction = NewNotification { user_id: users[0], kind: REACTION, actor_id: Some(users[1]), workout_execution_id: Some(execution), ..Default::default() };
+
+        assert!(insert_notification(&reaction, &connector).await.unwrap().is_some());
+        assert!(insert_notification(&reaction, &connector).await.unwrap().is_none());
+
+        let own = NewNotification { user_id: users[0], kind: NEW_FOLLOWER, actor_id: Some(users[0]), ..Default::default() };
+        assert!(insert_notification(&own, &connector).await.unwrap().is_none());
+
+        update_user(&users[0], &UncompleteUser { notify_prs: Some(false), ..Default::default() }, &connector).await.unwrap();
+        let pr = NewNotification { user_id: users[0], kind: PR_ACHIEVED, workout_execution_id: Some(execution), ..Default::default() };
+        assert!(insert_notification(&pr, &connector).await.unwrap().is_none());
+
+        let res = insert_notification(&NewNotification { user_id: Uuid::new_v4(), ..Default::default() }, &connector).await;
+        assert!(matches!(res, Err(DBError::ItemNotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn test_select_and_mark_notifications() {
+        let (connector, _container) = pg_container().await;
+        let users = insert_helper(2, Items::Users, &connector, None).await;
+        let execution = insert_helper(1, Items::WkExecutions, &connector, None).await[0];
+        let mut ids = Vec::new();
+        for kind in [NEW_FOLLOWER, COMMENT, COMMENT] {
+            let new_notification = NewNotification { user_id: users[0], kind, actor_id: Some(users[1]), workout_execution_id: Some(execution), ..Default::default() };
+            ids.push(insert_notification(&new_notification, &connector).await.unwrap().unwrap().id);
+        }
+
+        let inbox = select_notifications(users[0], false, 10, 0, &connector).await.unwrap();
+        assert_eq!(inbox.len(), 3);
+        assert_eq!(inbox[0].actor.as_ref().unwrap().id, users[1]);
+        assert_eq!(count_unread(users[0], &connector).await.unwrap(), vec![
+            UnreadCount { kind: NEW_FOLLOWER, count: 1 },
+            UnreadCount { kind: COMMENT, count: 2 },
+        ]);
+
+        assert_eq!(mark_notifications_read(users[0], Some(&ids[..1]), &connector).await.unwrap(), 1);
+        assert_eq!(mark_notifications_read(users[1], Some(&ids[1..]), &connector).await.unwrap(), 0);
+        assert_eq!(select_notifications(users[0], true, 10, 0, &connector).await.unwrap().len(), 2);
+
+        assert_eq!(mark_notifications_read(users[0], None, &connector).await.unwrap(), 3);
+        assert!(count_unread(users[0], &connector).await.unwrap().is_empty());
+    }
+}
@@ -0,0 +1,412 @@
+use std::collections::{HashMap, HashSet};
+use chrono::NaiveDate;
+use diesel::prelude::*;
+use diesel::result::{Error, DatabaseErrorKind};
+use diesel_async::{AsyncConnection, AsyncPgConnection, RunQueryDsl};
+use diesel_async::scoped_futures::ScopedFutureExt;
+use uuid::Uuid;
+
+use crate::schema::{programenrolments, programs, programslots, wktemplateelements, workoutexecutions, workouttemplates};
+use crate::lib::models::program_models::{
+    NewProgram, NewProgramEnrolment, Program, ProgramEnrolment, ProgramProgress, ProgramSlot, ProgramSlotRequest,
+    ProgramWithSlots, SlotProgress, TodaysWorkout,
+};
+use crate::lib::models::wk_template_elements_models::WkTemplateElement;
+use crate::lib::models::workout_execution_models::COMPLETED;
+use crate::lib::models::workout_templates_models::{WkTemplateWithElements, WorkoutTemplate};
+use crate::lib::errors::DBError;
+
+use super::DBConnector;
+
+const PROGRAM_NOT_FOUND: &str = "No program exists with the corresponding id";
+
+/// Create a program with its slots, all of them on templates of the same user
+pub async fn insert_program(new_program: &NewProgram, slots: &[ProgramSlotRequest], connector: &DBConnector) -> Result<ProgramWithSlots, DBError> {
+
+    let mut conn = connector.rds_connection().await?;
+    conn.transaction::<ProgramWithSlots, Error, _>(|conn| async move {
+        let template_ids: HashSet<Uuid> = slots.iter().map(|slot| slot.workout_template_id).collect();
+        let owned = workouttemplates::table
+            .filter(workouttemplates::id.eq_any(&template_ids))
+            .filter(workouttemplates::user_id.eq(new_program.user_id))
+            .count()
+            .get_result::<i64>(conn)
+            .await?;
+        if owned != template_ids.len() as i64 {
+            return Err(Error::NotFound);
+        }
+
+        let program = diesel::insert_into(programs::table)
+            .values(new_program)
+            .returning(Program::as_returning())
+            .get_result(conn)
+            .await?;
+        let new_slots: Vec<_> = slots.iter().map(|slot| slot.to_new_slot(program.id)).collect();
+        let mut slots = diesel::insert_into(programslots::table)
+            .values(&new_slots)
+            .returning(ProgramSlot::as_returning())
+            .get_results(conn)
+            .await?;
+        slots.sort_by_key(|slot| (slot.week, slot.day));
+        Ok(ProgramWithSlots { program, slots })
+    }.scope_boxed())
+    .await
+    .map_err(|error| match error {
+        Error::NotFound | Error::DatabaseError(DatabaseErrorKind::ForeignKeyViolation, _) => {
+            DBError::ItemNotFound("No template exists with the corresponding id".to_string())
+        },
+        _ => DBError::OperationError(error.to_string()),
+    })
+}
+
+/// Programs of a user, latest first
+pub async fn select_programs(user_id: Uuid, limit: i64, offset: i64, connector: &DBConnector) -> Result<Vec<Program>, DBError> {
+
+    let mut conn = connector.rds_connection().await?;
+    programs::table
+        .filter(programs::user_id.eq(user_id))
+        .order((programs::date_created.desc(), programs::id))
+        .limit(limit)
+        .offset(offset)
+        .select(Program::as_select())
+        .load::<Program>(&mut conn)
+        .await
+        .map_err(|error| DBError::OperationError(error.to_string()))
+}
+
+/// Program with its slots, only if it belongs to the user
+pub async fn lookup_program_validated(program_id: Uuid, user_id: Uuid, connector: &DBConnector) -> Result<ProgramWithSlots, DBError> {
+
+    let mut conn = connector.rds_connection().await?;
+    let program = owned_program(program_id, user_id, &mut conn)
+        .await
+        .map_err(|error| match error {
+            Error::NotFound => DBError::ItemNotFound(PROGRAM_NOT_FOUND.to_string()),
+            _ => DBError::OperationError(error.to_string()),
+        })?;
+    let slots = program_slots(program_id, &mut conn)
+        .await
+        .map_err(|error| DBError::OperationError(error.to_string()))?;
+    Ok(ProgramWithSlots { program, slots })
+}
+
+/// Slot of a program of the user, to link executions to it
+pub async fn lookup_program_slot_validated(slot_id: Uuid, user_id: Uuid, connector: &DBConnector) -> Result<ProgramSlot, DBError> {
+
+    let mut conn = connector.rds_connection().await?;
+    programslots::table
+        .inner_join(programs::table)
+        .filter(programslots::id.eq(slot_id))
+        .filter(programs::user_id.eq(user_id))
+        .select(ProgramSlot::as_select())
+        .first::<ProgramSlot>(&mut conn)
+        .await
+        .map_err(|error| match error {
+            Error::NotFound => DBError::ItemNotFound("No program slot exists with the corresponding id".to_string()),
+            _ => DBError::OperationError(error.to_string()),
+        })
+}
+
+/// Delete a program of the user, executions done on it are kept without their slot
+pub async fn delete_program(program_id: Uuid, user_id: Uuid, connector: &DBConnector) -> Result<usize, DBError> {
+
+    let mut conn = connector.rds_connection().await?;
+    diesel::delete(programs::table.filter(programs::id.eq(program_id)).filter(programs::user_id.eq(user_id)))
+        .execute(&mut conn)
+        .await
+        .map_err(|error| DBError::OperationError(error.to_string()))
+}
+
+/// Start following a program of the user on `start_date`
+/// * Fails with UniqueViolation if the user is already enrolled in a program
+pub async fn insert_enrolment(program_id: Uuid, user_id: Uuid, start_date: NaiveDate, connector: &DBConnector) -> Result<ProgramEnrolment, DBError> {
+
+    let mut conn = connector.rds_connection().await?;
+    conn.transaction::<ProgramEnrolment, Error, _>(|conn| async move {
+        owned_program(program_id, user_id, conn).await?;
+        diesel::insert_into(programenrolments::table)
+            .values(&NewProgramEnrolment { program_id, user_id, start_date })
+            .returning(ProgramEnrolment::as_returning())
+            .get_result(conn)
+            .await
+    }.scope_boxed())
+    .await
+    .map_err(|error| match error {
+        Error::NotFound => DBError::ItemNotFound(PROGRAM_NOT_FOUND.to_string()),
+        Error::DatabaseError(DatabaseErrorKind::UniqueViolation, _) => {
+            DBError::UniqueViolation("Already enrolled in a program".to_string())
+        },
+        _ => DBError::OperationError(error.to_string()),
+    })
+}
+
+/// End the active enrolment of the user in the program, returns the amount of enrolments ended
+pub async fn end_enrolment(program_id: Uuid, user_id: Uuid, date_ended: NaiveDate, connector: &DBConnector) -> Result<usize, DBError> {
+
+    let mut conn = connector.rds_connection().await?;
+    diesel::update(
+        programenrolments::table
+            .filter(programenrolments::program_id.eq(program_id))
+            .filter(programenrolments::user_id.eq(user_id))
+            .filter(programenrolments::date_ended.is_null())
+    )
+    .set(programenrolments::date_ended.eq(date_ended))
+    .execute(&mut conn)
+    .await
+    .map_err(|error| DBError::OperationError(error.to_string()))
+}
+
+/// What the active program of the user has planned for `today`, with the slot overrides applied
+/// * ItemNotFound if the user isn't enrolled in any program
+pub async fn select_todays_workout(user_id: Uuid, today: NaiveDate, connector: &DBConnector) -> Result<TodaysWorkout, DBError> {
+
+    let mut conn = connector.rds_connection().await?;
+    conn.build_transaction()
+        .read_only()
+        .repeatable_read()
+        .run::<TodaysWorkout, Error, _>(|conn| async move {
+            let (enrolment, program) = programenrolments::table
+                .inner_join(programs::table)
+                .filter(programenrolments::user_id.eq(user_id))
+                .filter(programenrolments::date_ended.is_null())
+                .select((ProgramEnrolment::as_select(), Program::as_select()))
+                .first::<(ProgramEnrolment, Program)>(conn)
+                .await?;
+
+            let position = enrolment.position(today);
+            let finished = position.is_some_and(|(week, _)| week > program.weeks);
+            let slot = match position {
+                Some((week, day)) if !finished => programslots::table
+                    .filter(programslots::program_id.eq(program.id))
+                    .filter(programslots::week.eq(week))
+                    .filter(programslots::day.eq(day))
+                    .select(ProgramSlot::as_select())
+                    .first::<ProgramSlot>(conn)
+                    .await
+                    .optional()?,
+                _ => None,
+            };
+
+            let (mut workout_template, mut workout_execution_id) = (None, None);
+            if let Some(slot) = &slot {
+                let template = workouttemplates::table
+                    .find(slot.workout_template_id)
+                    .select(WorkoutTemplate::as_select())
+                    .first::<WorkoutTemplate>(conn)
+                    .await?;
+                let elements = wktemplateelements::table
+                    .filter(wktemplateelements::workout_template_id.eq(slot.workout_template_id))
+                    .order(wktemplateelements::position)
+                    .load::<WkTemplateElement>(conn)
+                    .await?;
+                workout_template = Some(WkTemplateWithElements { workout_template: template, elements: slot.apply(elements) });
+                workout_execution_id = slot_executions(&[slot.id], &enrolment, conn).await?.remove(&slot.id);
+            }
+
+            Ok(TodaysWorkout {
+                week: position.filter(|_| !finished).map(|(week, _)| week),
+                day: position.filter(|_| !finished).map(|(_, day)| day),
+                program,
+                enrolment,
+                finished,
+                slot,
+                workout_template,
+                workout_execution_id,
+            })
+        }.scope_boxed())
+        .await
+        .map_err(|error| match error {
+            Error::NotFound => DBError::ItemNotFound("Not enrolled in any program".to_string()),
+            _ => DBError::OperationError(error.to_string()),
+        })
+}
+
+/// Slots done in the latest enrolment of the user in the program
+/// * ItemNotFound if the program isn't the user's or they never enrolled in it
+pub async fn select_program_progress(program_id: Uuid, user_id: Uuid, connector: &DBConnector) -> Result<ProgramProgress, DBError> {
+
+    let mut conn = connector.rds_connection().await?;
+    conn.build_transaction()
+        .read_only()
+        .repeatable_read()
+        .run::<ProgramProgress, Error, _>(|conn| async move {
+            let enrolment = programenrolments::table
+                .filter(programenrolments::program_id.eq(program_id))
+                .filter(programenrolments::user_id.eq(user_id))
+                .order((programenrolments::start_date.desc(), programenrolments::date_created.desc()))
+                .select(ProgramEnrolment::as_select())
+                .first::<ProgramEnrolment>(conn)
+                .await?;
+            let slots = program_slots(program_id, conn).await?;
+            let slot_ids: Vec<Uuid> = slots.iter().map(|slot| slot.id).collect();
+            let mut executions = slot_executions(&slot_ids, &enrolment, conn).await?;
+            let slots = slots.into_iter()
+                .map(|slot| SlotProgress { workout_execution_id: executions.remove(&slot.id), slot })
+                .collect();
+            Ok(ProgramProgress::new(enrolment, slots))
+        }.scope_boxed())
+        .await
+        .map_err(|error| match error {
+            Error::NotFound => DBError::ItemNotFound("Not enrolled in the program".to_string()),
+            _ => DBError::OperationError(error.to_string()),
+        })
+}
+
+async fn owned_program(program_id: Uuid, user_id: Uuid, conn: &mut AsyncPgConnection) -> QueryResult<Program> {
+    programs::table
+        .filter(programs::id.eq(program_id))
+        .filter(programs::user_id.eq(user_id))
+        .select(Program::as_select())
+        .first::<Program>(conn)
+        .await
+}
+
+async fn program_slots(program_id: Uuid, conn: &mut AsyncPgConnection) -> QueryResult<Vec<ProgramSlot>> {
+    programslots::table
+        .filter(programslots::program_id.eq(program_id))
+        .order((programslots::week, programslots::day))
+        .select(ProgramSlot::as_select())
+        .load::<ProgramSlot>(conn)
+        .await
+}
+
+/// First execution of each slot within the enrolment, slots not done yet are left out
+async fn slot_executions(slot_ids: &[Uuid], enrolment: &ProgramEnrolment, conn: &mut AsyncPgConnection) -> QueryResult<HashMap<Uuid, Uuid>> {
+    let mut query = workoutexecutions::table
+        .filter(workoutexecutions::program_slot_id.eq_any(slot_ids))
+        .filter(workoutexecutions::date.ge(enrolment.start_date))
+        .filter(workoutexecutions::status.eq(COMPLETED))
+        .into_boxed();
+    if let Some(date_ended) = enrolment.date_ended {
+        query = query.filter(workoutexecutions::date.le(date_ended));
+    }
+    let executions = query
+        .order((workoutexecutions::date.desc(), workoutexecutions::id.desc()))
+        .select((workoutexecutions::program_slot_id.assume_not_null(), workoutexecutions::id))
+        .load::<(Uuid, Uuid)>(conn)
+        .await?;
+    // Latest first, so the earliest one of each slot is inserted last
+    Ok(executions.into_iter().collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+    use crate::lib::utils::tests::{pg_container, insert_helper, Items};
+    use crate::lib::db::workout_executions_db::insert_workout_execution;
+    use crate::lib::db::workout_templates_db::lookup_workout_template;
+    use crate::lib::db::wk_template_elements_db::lookup_wk_template_element;
+    use crate::lib::models::workout_execution_models::NewWorkoutExecution;
+
+    // TEST CASES
+    // * Create with templates of another user, list, delete
+    // * Enrol once at a time, end enrolment
+    // * Today's workout with overrides, rest days and finished programs
+    // * Progress of the latest enrolment
+
+    fn slot(workout_template_id: Uuid, week: i16, day: i16) -> ProgramSlotRequest {
+        ProgramSlotRequest { workout_template_id, week, day, reps: None, intensity: None }
+    }
+
+    fn new_program(user_id: Uuid, weeks: i16) -> NewProgram {
+        NewProgram { user_id, name: "Block".to_string(), description: None, weeks }
+    }
+
+    #[tokio::test]
+    async fn test_insert_program() {
+        let (connector, _container) = pg_container().await;
+        let template_id = insert_helper(1, Items::WkTemplates, &connector, None).await[0];
+        let user_id = lookup_workout_template(template_id, &connector).await.unwrap().user_id;
+        let stranger = insert_helper(1, Items::Users, &connector, None).await[0];
+
+        let res = insert_program(&new_program(stranger, 1), &[slot(template_id, 1, 1)], &connector).await;
+        assert!(matches!(res, Err(DBError::ItemNotFound(_))));
+
+        let created = insert_program(&new_program(user_id, 2), &[slot(template_id, 2, 1), slot(template_id, 1, 3)], &connector).await.unwrap();
+        let order: Vec<(i16, i16)> = created.slots.iter().map(|slot| (slot.week, slot.day)).collect();
+        assert_eq!(order, vec![(1, 3), (2, 1)]);
+        assert_eq!(lookup_program_validated(created.program.id, user_id, &connector).await.unwrap(), created);
+        assert!(matches!(lookup_program_validated(created.program.id, stranger, &connector).await, Err(DBError::ItemNotFound(_))));
+        assert_eq!(select_programs(user_id, 10, 0, &connector).await.unwrap().len(), 1);
+
+        assert_eq!(delete_program(created.program.id, stranger, &connector).await.unwrap(), 0);
+        assert_eq!(delete_program(created.program.id, user_id, &connector).await.unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_enrolments() {
+        let (connector, _container) = pg_container().await;
+        let template_id = insert_helper(1, Items::WkTemplates, &connector, None).await[0];
+        let user_id = lookup_workout_template(template_id, &connector).await.unwrap().user_id;
+        let first = insert_program(&new_program(user_id, 1), &[slot(template_id, 1, 1)], &connector).await.unwrap().program.id;
+        let second = insert_program(&new_program(user_id, 1), &[slot(template_id, 1, 1)], &connector).await.unwrap().program.id;
+        let today = chrono::Utc::now().date_naive();
+
+        insert_enrolment(first, user_id, today, &connector).await.unwrap();
+        let res = insert_enrolment(second, user_id, today, &connector).await;
+        assert!(matches!(res, Err(DBError::UniqueViolation(_))));
+        let res = insert_enrolment(Uuid::new_v4(), user_id, today, &connector).await;
+        assert!(matches!(res, Err(DBError::ItemNotFound(_))));
+
+        assert_eq!(end_enrolment(second, user_id, today, &connector).await.unwrap(), 0);
+        assert_eq!(end_enrolment(first, user_id, today, &connector).await.unwrap(), 1);
+        insert_enrolment(second, user_id, today, &connector).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_select_todays_workout() {
+        let (connector, _container) = pg_container().await;
+        let element_id = insert_helper(1, Items::WkTemplateElements, &connector, None).await[0];
+        let template_id = lookup_wk_template_element(element_id, &connector).await.unwrap().workout_template_id;
+        let user_id = lookup_workout_template(template_id, &connector).await.unwrap().user_id;
+        let today = chrono::Utc::now().date_naive();
+
+        let res = select_todays_workout(user_id, today, &connector).await;
+        assert!(matches!(res, Err(DBError::ItemNotFound(_))));
+
+        // Week 2 day 1 is today, with 3 reps
+        let slots = [ProgramSlotRequest { reps: Some(3), intensity: Some(90), ..slot(template_id, 2, 1) }, slot(template_id, 1, 1)];
+        let program = insert_program(&new_program(user_id, 2), &slots, &connector).await.unwrap();
+        insert_enrolment(program.program.id, user_id, today - Duration::days(7), &connector).await.unwrap();
+
+        let todays = select_todays_workout(user_id, today, &connector).await.unwrap();
+        assert_eq!((todays.week, todays.day, todays.finished), (Some(2), Some(1), false));
+        let slot_id = todays.slot.unwrap().id;
+        assert_eq!(todays.workout_template.unwrap().elements[0].reps, 3);
+        assert_eq!(todays.workout_execution_id, None);
+
+        let execution = insert_workout_execution(&NewWorkoutExecution { workout_template_id: template_id, program_slot_id: Some(slot_id), ..Default::default() }, &connector).await.unwrap();
+        assert_eq!(select_todays_workout(user_id, today, &connector).await.unwrap().workout_execution_id, Some(execution.id));
+
+        let rest_day = select_todays_workout(user_id, today + Duration::days(1), &connector).await.unwrap();
+        assert!(rest_day.slot.is_none() && !rest_day.finished);
+        let finished = select_todays_workout(user_id, today + Duration::days(7), &connector).await.unwrap();
+        assert!(finished.finished && finished.week.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_select_program_progress() {
+        let (connector, _container) = pg_container().await;
+        let template_id = insert_helper(1, Items::WkTemplates, &connector, None).await[0];
+        let user_id = lookup_workout_template(template_id, &connector).await.unwrap().user_id;
+        let program = insert_program(&new_program(user_id, 1), &[slot(template_id, 1, 1), slot(template_id, 1, 3)], &connector).await.unwrap();
+        let today = chrono::Utc::now().date_naive();
+
+        let res = select_program_progress(program.program.id, user_id, &connector).await;
+        assert!(matches!(res, Err(DBError::ItemNotFound(_))));
+
+        // Done before the enrolment, it doesn't count
+        let slot_id = program.slots[0].id;
+        insert_workout_execution(&NewWorkoutExecution { workout_template_id: template_id, date: today - Duration::days(1), program_slot_id: Some(slot_id), ..Default::default() }, &connector).await.unwrap();
+        insert_enrolment(program.program.id, user_id, today, &connector).await.unwrap();
+        let progress = select_program_progress(program.program.id, user_id, &connector).await.unwrap();
+        assert_eq!((progress.completed, progress.total), (0, 2));
+
+        let execution = insert_workout_execution(&NewWorkoutExecution { workout_template_id: template_id, program_slot_id: Some(slot_id), ..Default::default() }, &connector).await.unwrap();
+        let progress = select_program_progress(program.program.id, user_id, &connector).await.unwrap();
+        assert_eq!((progress.completed, progress.total), (1, 2));
+        assert_eq!(progress.slots[0].workout_execution_id, Some(execution.id));
+        assert_eq!(lookup_program_slot_validated(slot_id, user_id, &connector).await.unwrap().id, slot_id);
+    }
+}
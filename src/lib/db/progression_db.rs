@@ -0,0 +1,231 @@
+use std::collections::HashMap;
+use diesel::prelude::*;
+use diesel::result::{Error, DatabaseErrorKind};
+use diesel_async::{AsyncConnection, RunQueryDsl};
+use diesel_async::scoped_futures::ScopedFutureExt;
+use itertools::Itertools;
+use uuid::Uuid;
+
+use crate::schema::{progressionrules, wkexecutionelements, wktemplateelements, workoutexecutions, workouttemplates};
+use crate::lib::models::progression_models::{match_elements, NewProgressionRule, NextTarget, ProgressionRule, TemplateProgression};
+use crate::lib::models::wk_execution_elements_models::WkExecutionElement;
+use crate::lib::models::wk_template_elements_models::WkTemplateElement;
+use crate::lib::models::workout_execution_models::COMPLETED;
+use crate::lib::errors::DBError;
+
+use super::DBConnector;
+
+const ELEMENT_NOT_FOUND: &str = "No template element exists with the corresponding id";
+
+/// Set the progression rule of an element of a template of the user, replacing the previous one
+pub async fn upsert_progression_rule(workout_template_id: Uuid, user_id: Uuid, new_rule: &NewProgressionRule, connector: &DBConnector) -> Result<ProgressionRule, DBError> {
+
+    let mut conn = connector.rds_connection().await?;
+    conn.transaction::<ProgressionRule, Error, _>(|conn| async move {
+        wktemplateelements::table
+            .inner_join(workouttemplates::table)
+            .filter(wktemplateelements::id.eq(new_rule.wk_template_element_id))
+            .filter(workouttemplates::id.eq(workout_template_id))
+            .filter(workouttemplates::user_id.eq(user_id))
+            .select(wktemplateelements::id)
+            .first::<Uuid>(conn)
+            .await?;
+        diesel::insert_into(progressionrules::table)
+            .values(new_rule)
+            .on_conflict(progressionrules::wk_template_element_id)
+            .do_update()
+            .set(new_rule)
+            .returning(ProgressionRule::as_returning())
+            .get_result(conn)
+            .await
+    }.scope_boxed())
+    .await
+    .map_err(|error| match error {
+        Error::NotFound | Error::DatabaseError(DatabaseErrorKind::ForeignKeyViolation, _) => {
+            DBError::ItemNotFound(ELEMENT_NOT_FOUND.to_string())
+        },
+        _ => DBError::OperationError(error.to_string()),
+    })
+}
+
+pub async fn delete_progression_rule(element_id: Uuid, workout_template_id: Uuid, user_id: Uuid, connector: &DBConnector) -> Result<usize, DBError> {
+
+    let mut conn = connector.rds_connection().await?;
+    let owned_elements = wktemplateelements::table
+        .inner_join(workouttemplates::table)
+        .filter(workouttemplates::id.eq(workout_template_id))
+        .filter(workouttemplates::user_id.eq(user_id))
+        .select(wktemplateelements::id);
+    diesel::delete(
+        progressionrules::table
+            .filter(progressionrules::wk_template_element_id.eq(element_id))
+            .filter(progressionrules::wk_template_element_id.eq_any(owned_elements))
+    )
+    .execute(&mut conn)
+    .await
+    .map_err(|error| DBError::OperationError(error.to_string()))
+}
+
+/// Next targets of each element of a template of the user, from its latest executions
+pub async fn select_template_progression(workout_template_id: Uuid, user_id: Uuid, connector: &DBConnector) -> Result<TemplateProgression, DBError> {
+
+    let mut conn = connector.rds_connection().await?;
+    conn.build_transaction()
+        .read_only()
+        .repeatable_read()
+        .run::<TemplateProgression, Error, _>(|conn| async move {
+            workouttemplates::table
+                .filter(workouttemplates::id.eq(workout_template_id))
+                .filter(workouttemplates::user_id.eq(user_id))
+                .select(workouttemplates::id)
+                .first::<Uuid>(conn)
+                .await?;
+            let elements = wktemplateelements::table
+                .filter(wktemplateelements::workout_template_id.eq(workout_template_id))
+                .order(wktemplateelements::position)
+                .select(wktemplateelements::all_columns)
+                .load::<WkTemplateElement>(conn)
+                .await?;
+            let element_ids: Vec<Uuid> = elements.iter().map(|element| element.id).collect();
+            let mut rules: HashMap<Uuid, ProgressionRule> = progressionrules::table
+                .filter(progressionrules::wk_template_element_id.eq_any(&element_ids))
+                .select(ProgressionRule::as_select())
+                .load::<ProgressionRule>(conn)
+                .await?
+                .into_iter()
+                .map(|rule| (rule.wk_template_element_id, rule))
+                .collect();
+
+            // Only as many executions as the longest wait for a deload
+            let depth = rules.values().filter_map(|rule| rule.deload_after).max().unwrap_or(1).max(1);
+            let execution_ids = workoutexecutions::table
+                .filter(workoutexecutions::workout_template_id.eq(workout_template_id))
+                .filter(workoutexecutions::status.eq(COMPLETED))
+                .order((workoutexecutions::date.desc(), workoutexecutions::id.desc()))
+                .limit(depth as i64)
+                .select(workoutexecutions::id)
+                .load::<Uuid>(conn)
+                .await?;
+            let mut rows: HashMap<Uuid, Vec<WkExecutionElement>> = wkexecutionelements::table
+                .filter(wkexecutionelements::workout_execution_id.eq_any(&execution_ids))
+                .select(wkexecutionelements::all_columns)
+                .load::<WkExecutionElement>(conn)
+                .await?
+                .into_iter()
+                .into_group_map_by(|row| row.workout_execution_id);
+
+            // Sets of each element in each execution, most recent first
+            let mut history: HashMap<Uuid, Vec<Vec<WkExecutionElement>>> = HashMap::new();
+            for execution_id in &execution_ids {
+                let matched = match_elements(&elements, rows.remove(execution_id).unwrap_or_default());
+                for (element_id, sets) in matched {
+                    history.entry(element_id).or_default().push(sets);
+                }
+            }
+
+            let targets = elements.iter()
+                .map(|element| NextTarget::evaluate(element, rules.remove(&element.id), history.get(&element.id).map_or(&[], Vec::as_slice)))
+                .collect();
+            Ok(TemplateProgression { workout_template_id, workout_execution_id: execution_ids.as_slice().first().copied(), targets })
+        }.scope_boxed())
+        .await
+        .map_err(|error| match error {
+            Error::NotFound => DBError::ItemNotFound("No template exists with the corresponding id".to_string()),
+            _ => DBError::OperationError(error.to_string()),
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+    use crate::lib::utils::tests::{pg_container, insert_helper, Items};
+    use crate::lib::db::wk_execution_elements_db::insert_batch_wk_execution_elements;
+    use crate::lib::db::wk_template_elements_db::insert_batch_wk_template_elements;
+    use crate::lib::db::workout_executions_db::insert_workout_execution;
+    use crate::lib::db::workout_templates_db::lookup_workout_template;
+    use crate::lib::models::progression_models::{Outcome, LINEAR};
+    use crate::lib::models::wk_execution_elements_models::NewWkExecutionElement;
+    use crate::lib::models::wk_template_elements_models::NewWkTemplateElement;
+    use crate::lib::models::workout_execution_models::NewWorkoutExecution;
+
+    // TEST CASES
+    // * Set, replace and delete rules, only on the user's templates
+    // * Next targets after the latest execution
+
+    /// Template with a weighted and a bodyweight element, and its owner
+    async fn template_with_elements(connector: &DBConnector) -> (Uuid, Uuid, Vec<WkTemplateElement>) {
+        let template_id = insert_helper(1, Items::WkTemplates, connector, None).await[0];
+        let user_id = lookup_workout_template(template_id, connector).await.unwrap().user_id;
+        let exercises = insert_helper(2, Items::Exercises, connector, None).await;
+        let elements = vec![
+            NewWkTemplateElement { workout_template_id: template_id, exercise_id: exercises[0], position: 0, reps: 5, sets: 3, weight: Some(50.0), ..Default::default() },
+            NewWkTemplateElement { workout_template_id: template_id, exercise_id: exercises[1], position: 1, reps: 8, sets: 2, ..Default::default() },
+        ];
+        (template_id, user_id, insert_batch_wk_template_elements(&elements, connector).await.unwrap())
+    }
+
+    fn linear_rule(wk_template_element_id: Uuid, deload_after: Option<i16>) -> NewProgressionRule {
+        NewProgressionRule { wk_template_element_id, kind: LINEAR, weight_increment: 2.5, min_reps: None, max_reps: None, deload_after, deload_percent: 10 }
+    }
+
+    #[tokio::test]
+    async fn test_upsert_delete_progression_rule() {
+        let (connector, _container) = pg_container().await;
+        let (template_id, user_id, elements) = template_with_elements(&connector).await;
+        let element_id = elements[0].id;
+        let stranger = insert_helper(1, Items::Users, &connector, None).await[0];
+
+        let res = upsert_progression_rule(template_id, stranger, &linear_rule(element_id, None), &connector).await;
+        assert!(matches!(res, Err(DBError::ItemNotFound(_))));
+        let res = upsert_progression_rule(template_id, user_id, &linear_rule(Uuid::new_v4(), None), &connector).await;
+        assert!(matches!(res, Err(DBError::ItemNotFound(_))));
+
+        let rule = upsert_progression_rule(template_id, user_id, &linear_rule(element_id, Some(3)), &connector).await.unwrap();
+        assert_eq!(rule.deload_after, Some(3));
+        let replaced = upsert_progression_rule(template_id, user_id, &linear_rule(element_id, None), &connector).await.unwrap();
+        assert_eq!(replaced.deload_after, None);
+
+        assert_eq!(delete_progression_rule(element_id, template_id, stranger, &connector).await.unwrap(), 0);
+        assert_eq!(delete_progression_rule(element_id, template_id, user_id, &connector).await.unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_select_template_progression() {
+        let (connector, _container) = pg_container().await;
+        let (template_id, user_id, elements) = template_with_elements(&connector).await;
+        let element = &elements[0];
+        upsert_progression_rule(template_id, user_id, &linear_rule(element.id, Some(2)), &connector).await.unwrap();
+
+        let progression = select_template_progression(template_id, user_id, &connector).await.unwrap();
+        assert_eq!(progression.workout_execution_id, None);
+        let outcomes: Vec<Outcome> = progression.targets.iter().map(|target| target.outcome).collect();
+        assert_eq!(outcomes, vec![Outcome::NoHistory, Outcome::NoRule]);
+
+        let mut latest = None;
+        for day in 1..=2 {
+            let date = NaiveDate::from_ymd_opt(2024, 11, day).unwrap();
+            let execution = insert_workout_execution(&NewWorkoutExecution { workout_template_id: template_id, date, ..Default::default() }, &connector).await.unwrap();
+            let sets: Vec<NewWkExecutionElement> = (0..element.sets).map(|set_number| NewWkExecutionElement {
+                workout_execution_id: execution.id,
+                exercise_id: element.exercise_id,
+                position: set_number,
+                set_number,
+                reps: element.reps,
+                weight: Some(50.0 + day as f32),
+                time: 30,
+                ..Default::default()
+            }).collect();
+            insert_batch_wk_execution_elements(&sets, &connector).await.unwrap();
+            latest = Some(execution.id);
+        }
+
+        let progression = select_template_progression(template_id, user_id, &connector).await.unwrap();
+        assert_eq!(progression.workout_execution_id, latest);
+        let target = &progression.targets[0];
+        assert_eq!((target.outcome, target.reps, target.weight), (Outcome::Increase, 5, Some(54.5)));
+
+        let res = select_template_progression(template_id, Uuid::new_v4(), &connector).await;
+        assert!(matches!(res, Err(DBError::ItemNotFound(_))));
+    }
+}
@@ -0,0 +1,112 @@
+use std::collections::HashMap;
+use diesel::prelude::*;
+use diesel::upsert::excluded;
+use diesel::result::{Error, DatabaseErrorKind};
+use diesel_async::{AsyncPgConnection, RunQueryDsl};
+use uuid::Uuid;
+
+use crate::schema::reactions;
+use crate::lib::models::reaction_models::{NewReaction, Reaction, ReactionCount};
+use crate::lib::errors::DBError;
+
+use super::DBConnector;
+
+/// React to an execution, replacing the user's previous reaction to it if any
+pub async fn upsert_reaction(new_reaction: &NewReaction, connector: &DBConnector) -> Result<Reaction, DBError> {
+
+    let mut conn = connector.rds_connection().await?;
+    diesel::insert_into(reactions::table)
+        .values(new_reaction)
+        .on_conflict((reactions::workout_execution_id, reactions::user_id))
+        .do_update()
+        .set(reactions::reaction.eq(excluded(reactions::reaction)))
+        .returning(Reaction::as_returning())
+        .get_result(&mut conn)
+        .await
+        .map_err(|error| match error {
+            Error::DatabaseError(DatabaseErrorKind::ForeignKeyViolation, _) => {
+                DBError::ItemNotFound("No execution exists with the corresponding id".to_string())
+            },
+            _ => DBError::OperationError(error.to_string()),
+        })
+}
+
+/// Remove the user's reaction to an execution
+pub async fn delete_reaction(execution_id: Uuid, user_id: Uuid, connector: &DBConnector) -> Result<usize, DBError> {
+
+    let mut conn = connector.rds_connection().await?;
+    diesel::delete(reactions::table.find((execution_id, user_id)))
+        .execute(&mut conn)
+        .await
+        .map_err(|error| DBError::OperationError(error.to_string()))
+}
+
+/// Reaction counts of each execution, executions without reactions are left out
+pub async fn count_reactions(execution_ids: &[Uuid], connector: &DBConnector) -> Result<HashMap<Uuid, Vec<ReactionCount>>, DBError> {
+
+    let mut conn = connector.rds_connection().await?;
+    reaction_counts(execution_ids, &mut conn)
+        .await
+        .map_err(|error| DBError::OperationError(error.to_string()))
+}
+
+/// Same as `count_reactions`, on a connection already open, e.g. within a transaction
+pub(crate) async fn reaction_counts(execution_ids: &[Uuid], conn: &mut AsyncPgConnection) -> QueryResult<HashMap<Uuid, Vec<ReactionCount>>> {
+    let rows = reactions::table
+        .filter(reactions::workout_execution_id.eq_any(execution_ids))
+        .group_by((reactions::workout_execution_id, reactions::reaction))
+        .order((reactions::workout_execution_id, reactions::reaction))
+        .select((reactions::workout_execution_id, reactions::reaction, diesel::dsl::count_star()))
+        .load::<(Uuid, i16, i64)>(conn)
+        .await?;
+    let mut counts: HashMap<Uuid, Vec<ReactionCount>> = HashMap::new();
+    for (execution_id, reaction, count) in rows {
+        counts.entry(execution_id).or_default().push(ReactionCount { reaction, count });
+    }
+    Ok(counts)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lib::utils::tests::{pg_container, insert_helper, Items};
+    use crate::lib::models::reaction_models::{FIRE, LIKE};
+
+    // TEST CASES
+    // * Upsert replaces the previous reaction, non existing execution
+    // * Count per execution and kind, delete
+
+    #[tokio::test]
+    async fn test_upsert_reaction() {
+        let (connector, _container) = pg_container().await;
+        let user_id = insert_helper(1, Items::Users, &connector, None).await[0];
+        let execution = insert_helper(1, Items::WkExecutions, &connector, None).await[0];
+
+        upsert_reaction(&NewReaction { workout_execution_id: execution, user_id, reaction: LIKE }, &connector).await.unwrap();
+        let reaction = upsert_reaction(&NewReaction { workout_execution_id: execution, user_id, reaction: FIRE }, &connector).await.unwrap();
+        assert_eq!(reaction.reaction, FIRE);
+
+        let res = upsert_reaction(&NewReaction { workout_execution_id: Uuid::new_v4(), user_id, reaction: LIKE }, &connector).await;
+        assert!(matches!(res, Err(DBError::ItemNotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn test_count_and_delete_reactions() {
+        let (connector, _container) = pg_container().await;
+        let users = insert_helper(3, Items::Users, &connector, None).await;
+        let executions = insert_helper(2, Items::WkExecutions, &connector, None).await;
+
+        for (user_id, reaction) in [(users[0], LIKE), (users[1], LIKE), (users[2], FIRE)] {
+            upsert_reaction(&NewReaction { workout_execution_id: executions[0], user_id, reaction }, &connector).await.unwrap();
+        }
+
+        let counts = count_reactions(&executions, &connector).await.unwrap();
+        assert_eq!(counts[&executions[0]], vec![ReactionCount { reaction: LIKE, count: 2 }, ReactionCount { reaction: FIRE, count: 1 }]);
+        assert!(!counts.contains_key(&executions[1]));
+
+        assert_eq!(delete_reaction(executions[0], users[2], &connector).await.unwrap(), 1);
+        assert_eq!(delete_reaction(executions[0], users[2], &connector).await.unwrap(), 0);
+        let counts = count_reactions(&executions, &connector).await.unwrap();
+        assert_eq!(counts[&executions[0]].len(), 1);
+    }
+}
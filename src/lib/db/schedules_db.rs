@@ -0,0 +1,228 @@
+use chrono::NaiveDate;
+use diesel::prelude::*;
+use diesel::result::{Error, DatabaseErrorKind};
+use diesel::upsert::excluded;
+use diesel_async::{AsyncConnection, RunQueryDsl};
+use diesel_async::scoped_futures::ScopedFutureExt;
+use uuid::Uuid;
+
+use crate::schema::{calendartokens, scheduledworkouts, workoutexecutions, workouttemplates};
+use crate::lib::models::schedule_models::{Calendar, CalendarToken, NewCalendarToken, NewScheduledWorkout, ScheduledWorkout};
+use crate::lib::models::workout_execution_models::{WorkoutExecution, COMPLETED};
+use crate::lib::errors::DBError;
+
+use super::DBConnector;
+
+/// Schedule a template of the user
+pub async fn insert_scheduled_workout(new_scheduled: &NewScheduledWorkout, connector: &DBConnector) -> Result<ScheduledWorkout, DBError> {
+
+    let mut conn = connector.rds_connection().await?;
+    conn.transaction::<ScheduledWorkout, Error, _>(|conn| async move {
+        workouttemplates::table
+            .filter(workouttemplates::id.eq(new_scheduled.workout_template_id))
+            .filter(workouttemplates::user_id.eq(new_scheduled.user_id))
+            .select(workouttemplates::id)
+            .first::<Uuid>(conn)
+            .await?;
+        diesel::insert_into(scheduledworkouts::table)
+            .values(new_scheduled)
+            .returning(ScheduledWorkout::as_returning())
+            .get_result(conn)
+            .await
+    }.scope_boxed())
+    .await
+    .map_err(|error| match error {
+        Error::NotFound | Error::DatabaseError(DatabaseErrorKind::ForeignKeyViolation, _) => {
+            DBError::ItemNotFound("No template exists with the corresponding id".to_string())
+        },
+        _ => DBError::OperationError(error.to_string()),
+    })
+}
+
+/// Scheduled workouts of a user, latest first
+pub async fn select_scheduled_workouts(user_id: Uuid, limit: i64, offset: i64, connector: &DBConnector) -> Result<Vec<ScheduledWorkout>, DBError> {
+
+    let mut conn = connector.rds_connection().await?;
+    scheduledworkouts::table
+        .filter(scheduledworkouts::user_id.eq(user_id))
+        .order((scheduledworkouts::date_created.desc(), scheduledworkouts::id))
+        .limit(limit)
+        .offset(offset)
+        .select(ScheduledWorkout::as_select())
+        .load::<ScheduledWorkout>(&mut conn)
+        .await
+        .map_err(|error| DBError::OperationError(error.to_string()))
+}
+
+/// Every scheduled workout of a user with the name of its template, for the iCal feed
+pub async fn select_schedule_with_names(user_id: Uuid, connector: &DBConnector) -> Result<Vec<(ScheduledWorkout, String)>, DBError> {
+
+    let mut conn = connector.rds_connection().await?;
+    scheduledworkouts::table
+        .inner_join(workouttemplates::table)
+        .filter(scheduledworkouts::user_id.eq(user_id))
+        .order((scheduledworkouts::start_date, scheduledworkouts::id))
+        .select((ScheduledWorkout::as_select(), workouttemplates::name))
+        .load::<(ScheduledWorkout, String)>(&mut conn)
+        .await
+        .map_err(|error| DBError::OperationError(error.to_string()))
+}
+
+pub async fn delete_scheduled_workout(scheduled_id: Uuid, user_id: Uuid, connector: &DBConnector) -> Result<usize, DBError> {
+
+    let mut conn = connector.rds_connection().await?;
+    diesel::delete(
+        scheduledworkouts::table
+            .filter(scheduledworkouts::id.eq(scheduled_id))
+            .filter(scheduledworkouts::user_id.eq(user_id))
+    )
+    .execute(&mut conn)
+    .await
+    .map_err(|error| DBError::OperationError(error.to_string()))
+}
+
+/// Planned workouts between `from` and `to` merged with the executions the user did on those days
+pub async fn select_calendar(user_id: Uuid, from: NaiveDate, to: NaiveDate, today: NaiveDate, connector: &DBConnector) -> Result<Calendar, DBError> {
+
+    let mut conn = connector.rds_connection().await?;
+    conn.build_transaction()
+        .read_only()
+        .repeatable_read()
+        .run::<Calendar, Error, _>(|conn| async move {
+            let schedule = scheduledworkouts::table
+                .inner_join(workouttemplates::table)
+                .filter(scheduledworkouts::user_id.eq(user_id))
+                .filter(scheduledworkouts::start_date.le(to))
+                .filter(scheduledworkouts::end_date.is_null().or(scheduledworkouts::end_date.ge(from)))
+                .select((ScheduledWorkout::as_select(), workouttemplates::name))
+                .load::<(ScheduledWorkout, String)>(conn)
+                .await?;
+            let executions = workoutexecutions::table
+                .inner_join(workouttemplates::table)
+                .filter(workouttemplates::user_id.eq(user_id))
+                .filter(workoutexecutions::date.between(from, to))
+                .filter(workoutexecutions::status.eq(COMPLETED))
+                .select((WorkoutExecution::as_select(), workouttemplates::name))
+                .load::<(WorkoutExecution, String)>(conn)
+                .await?;
+            Ok(Calendar::merge(from, to, today, schedule, executions))
+        }.scope_boxed())
+        .await
+        .map_err(|error| DBError::OperationError(error.to_string()))
+}
+
+/// Give the user a new iCal feed token, the previous one stops working
+pub async fn rotate_calendar_token(user_id: Uuid, connector: &DBConnector) -> Result<CalendarToken, DBError> {
+
+    let mut conn = connector.rds_connection().await?;
+    diesel::insert_into(calendartokens::table)
+        .values(&NewCalendarToken { user_id, token: Uuid::new_v4() })
+        .on_conflict(calendartokens::user_id)
+        .do_update()
+        .set((calendartokens::token.eq(excluded(calendartokens::token)), calendartokens::date_created.eq(diesel::dsl::now)))
+        .returning(CalendarToken::as_returning())
+        .get_result(&mut conn)
+        .await
+        .map_err(|error| match error {
+            Error::DatabaseError(DatabaseErrorKind::ForeignKeyViolation, _) => {
+                DBError::ItemNotFound("No user exists with the corresponding id".to_string())
+            },
+            _ => DBError::OperationError(error.to_string()),
+        })
+}
+
+pub async fn delete_calendar_token(user_id: Uuid, connector: &DBConnector) -> Result<usize, DBError> {
+
+    let mut conn = connector.rds_connection().await?;
+    diesel::delete(calendartokens::table.find(user_id))
+        .execute(&mut conn)
+        .await
+        .map_err(|error| DBError::OperationError(error.to_string()))
+}
+
+/// Owner of a feed token, ItemNotFound once revoked
+pub async fn lookup_calendar_token(token: Uuid, connector: &DBConnector) -> Result<Uuid, DBError> {
+
+    let mut conn = connector.rds_connection().await?;
+    calendartokens::table
+        .filter(calendartokens::token.eq(token))
+        .select(calendartokens::user_id)
+        .first::<Uuid>(&mut conn)
+        .await
+        .map_err(|error| match error {
+            Error::NotFound => DBError::ItemNotFound("No calendar exists with the corresponding token".to_string()),
+            _ => DBError::OperationError(error.to_string()),
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+    use crate::lib::utils::tests::{pg_container, insert_helper, Items};
+    use crate::lib::db::workout_executions_db::insert_workout_execution;
+    use crate::lib::db::workout_templates_db::lookup_workout_template;
+    use crate::lib::models::workout_execution_models::NewWorkoutExecution;
+
+    // TEST CASES
+    // * Schedule own and other user's templates, list and delete
+    // * Calendar merging planned workouts and executions
+    // * Rotate, look up and revoke feed tokens
+
+    fn new_scheduled(user_id: Uuid, workout_template_id: Uuid, start_date: NaiveDate, weekdays: Option<Vec<i16>>) -> NewScheduledWorkout {
+        NewScheduledWorkout { user_id, workout_template_id, start_date, end_date: None, weekdays }
+    }
+
+    #[tokio::test]
+    async fn test_insert_scheduled_workout() {
+        let (connector, _container) = pg_container().await;
+        let template_id = insert_helper(1, Items::WkTemplates, &connector, None).await[0];
+        let user_id = lookup_workout_template(template_id, &connector).await.unwrap().user_id;
+        let stranger = insert_helper(1, Items::Users, &connector, None).await[0];
+        let today = chrono::Utc::now().date_naive();
+
+        let res = insert_scheduled_workout(&new_scheduled(stranger, template_id, today, None), &connector).await;
+        assert!(matches!(res, Err(DBError::ItemNotFound(_))));
+
+        let scheduled = insert_scheduled_workout(&new_scheduled(user_id, template_id, today, Some(vec![1, 3, 5])), &connector).await.unwrap();
+        assert_eq!(scheduled.weekdays, Some(vec![1, 3, 5]));
+        assert_eq!(select_scheduled_workouts(user_id, 10, 0, &connector).await.unwrap(), vec![scheduled.clone()]);
+        assert_eq!(select_schedule_with_names(user_id, &connector).await.unwrap()[0].1, "Placeholder");
+
+        assert_eq!(delete_scheduled_workout(scheduled.id, stranger, &connector).await.unwrap(), 0);
+        assert_eq!(delete_scheduled_workout(scheduled.id, user_id, &connector).await.unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_select_calendar() {
+        let (connector, _container) = pg_container().await;
+        let template_id = insert_helper(1, Items::WkTemplates, &connector, None).await[0];
+        let user_id = lookup_workout_template(template_id, &connector).await.unwrap().user_id;
+        let today = chrono::Utc::now().date_naive();
+        let every_day = Some(vec![1, 2, 3, 4, 5, 6, 7]);
+        insert_scheduled_workout(&new_scheduled(user_id, template_id, today - Duration::days(2), every_day), &connector).await.unwrap();
+        let execution = insert_workout_execution(&NewWorkoutExecution { workout_template_id: template_id, date: today - Duration::days(1), ..Default::default() }, &connector).await.unwrap();
+
+        let calendar = select_calendar(user_id, today - Duration::days(7), today + Duration::days(1), today, &connector).await.unwrap();
+        assert_eq!(calendar.planned.len(), 4);
+        assert_eq!(calendar.planned[1].workout_execution_id, Some(execution.id));
+        assert_eq!((calendar.due, calendar.completed), (3, 1));
+        assert!(calendar.unplanned.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_calendar_tokens() {
+        let (connector, _container) = pg_container().await;
+        let user_id = insert_helper(1, Items::Users, &connector, None).await[0];
+
+        let first = rotate_calendar_token(user_id, &connector).await.unwrap();
+        assert_eq!(lookup_calendar_token(first.token, &connector).await.unwrap(), user_id);
+        let second = rotate_calendar_token(user_id, &connector).await.unwrap();
+        assert_ne!(first.token, second.token);
+        assert!(matches!(lookup_calendar_token(first.token, &connector).await, Err(DBError::ItemNotFound(_))));
+
+        assert_eq!(delete_calendar_token(user_id, &connector).await.unwrap(), 1);
+        assert!(matches!(lookup_calendar_token(second.token, &connector).await, Err(DBError::ItemNotFound(_))));
+        assert!(matches!(rotate_calendar_token(Uuid::new_v4(), &connector).await, Err(DBError::ItemNotFound(_))));
+    }
+}
@@ -0,0 +1,34 @@
+use diesel::expression::AsExpression;
+use diesel::sql_types::{Float4, Nullable, Text};
+use diesel::define_sql_function;
+
+define_sql_function! {
+    /// Lowercase version of a string
+    fn lower(x: Text) -> Text;
+}
+
+define_sql_function! {
+    /// Largest of the two values, NULLs are ignored
+    fn greatest(x: Nullable<Float4>, y: Nullable<Float4>) -> Nullable<Float4>;
+}
+
+define_sql_function! {
+    /// Trigram similarity between two strings, from 0 to 1 (pg_trgm)
+    fn similarity(x: Text, y: Text) -> Float4;
+}
+
+define_sql_function! {
+    /// Greatest trigram similarity between the first string and any word of the second (pg_trgm)
+    fn word_similarity(x: Text, y: Text) -> Float4;
+}
+
+diesel::infix_operator!(WordSimilar, " <% ", backend: diesel::pg::Pg);
+
+/// Word similarity over the threshold, can use trigram indexes (pg_trgm)
+pub fn word_similar<T, U>(left: T, right: U) -> WordSimilar<T::Expression, U::Expression>
+where
+    T: AsExpression<Text>,
+    U: AsExpression<Text>,
+{
+    WordSimilar::new(left.as_expression(), right.as_expression())
+}
@@ -0,0 +1,193 @@
+use diesel::dsl::sql;
+use diesel::prelude::*;
+use diesel::result::Error;
+use diesel::sql_types::BigInt;
+use diesel_async::RunQueryDsl;
+use diesel_async::scoped_futures::ScopedFutureExt;
+use itertools::Itertools;
+use uuid::Uuid;
+
+use crate::schema::{syncchanges, wkexecutionelements, wktemplateelements, workoutexecutions, workouttemplates};
+use crate::lib::models::sync_models::{
+    ChangeFeed, Changes, SortedChanges, SyncChange, SyncToken,
+    WK_EXECUTION_ELEMENT, WK_TEMPLATE_ELEMENT, WORKOUT_EXECUTION, WORKOUT_TEMPLATE,
+};
+use crate::lib::models::wk_execution_elements_models::WkExecutionElement;
+use crate::lib::models::wk_template_elements_models::WkTemplateElement;
+use crate::lib::models::workout_execution_models::WorkoutExecution;
+use crate::lib::models::workout_templates_models::WorkoutTemplate;
+use crate::lib::errors::DBError;
+
+use super::DBConnector;
+
+/// Templates, executions and their elements of the user changed since `since`, all of them without it
+pub async fn select_changes(user_id: Uuid, since: Option<SyncToken>, connector: &DBConnector) -> Result<ChangeFeed, DBError> {
+
+    let mut conn = connector.rds_connection().await?;
+    conn.build_transaction()
+        .read_only()
+        .repeatable_read()
+        .run::<ChangeFeed, Error, _>(|conn| async move {
+            // First, so it is taken with the snapshot everything else is read from
+            let token = diesel::select(sql::<BigInt>("txid_snapshot_xmin(txid_current_snapshot())"))
+                .get_result::<i64>(conn)
+                .await?;
+
+            let mut query = syncchanges::table
+                .filter(syncchanges::user_id.eq(user_id))
+                .into_boxed();
+            query = match since {
+                Some(SyncToken(since)) => query.filter(syncchanges::updated.ge(since)),
+                None => query.filter(syncchanges::deleted.eq(false)),
+            };
+            let mut changes = query
+                .select(SyncChange::as_select())
+                .load::<SyncChange>(conn)
+                .await?
+                .into_iter()
+                .into_group_map_by(|change| change.kind);
+            let mut sorted = |kind: i16| SortedChanges::new(changes.remove(&kind).unwrap_or_default(), since);
+
+            let templates = sorted(WORKOUT_TEMPLATE);
+            let loaded = workouttemplates::table
+                .filter(workouttemplates::id.eq_any(templates.live()))
+                .select(WorkoutTemplate::as_select())
+                .load::<WorkoutTemplate>(conn)
+                .await?;
+            let workout_templates = Changes::new(loaded, templates, |template| template.id);
+
+            let template_elements = sorted(WK_TEMPLATE_ELEMENT);
+            let loaded = wktemplateelements::table
+                .filter(wktemplateelements::id.eq_any(template_elements.live()))
+                .select(wktemplateelements::all_columns)
+                .load::<WkTemplateElement>(conn)
+                .await?;
+            let wk_template_elements = Changes::new(loaded, template_elements, |element| element.id);
+
+            let executions = sorted(WORKOUT_EXECUTION);
+            let loaded = workoutexecutions::table
+                .filter(workoutexecutions::id.eq_any(executions.live()))
+                .select(WorkoutExecution::as_select())
+                .load::<WorkoutExecution>(conn)
+                .await?;
+            let workout_executions = Changes::new(loaded, executions, |execution| execution.id);
+
+            let execution_elements = sorted(WK_EXECUTION_ELEMENT);
+            let loaded = wkexecutionelements::table
+                .filter(wkexecutionelements::id.eq_any(execution_elements.live()))
+                .select(wkexecutionelements::all_columns)
+                .load::<WkExecutionElement>(conn)
+                .await?;
+            let wk_execution_elements = Changes::new(loaded, execution_elements, |element| element.id);
+
+            Ok(ChangeFeed {
+                token: SyncToken(token).to_string(),
+                workout_templates,
+                wk_template_elements,
+                workout_executions,
+                wk_execution_elements,
+            })
+        }.scope_boxed())
+        .await
+        .map_err(|error| DBError::OperationError(error.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lib::utils::tests::{pg_container, insert_helper, Items};
+    use crate::lib::db::wk_execution_elements_db::{insert_batch_wk_execution_elements, lookup_wk_execution_element};
+    use crate::lib::db::wk_template_elements_db::insert_batch_wk_template_elements;
+    use crate::lib::db::workout_executions_db::{delete_workout_execution, lookup_workout_execution};
+    use crate::lib::db::workout_sessions_db::upsert_session_set;
+    use crate::lib::db::workout_templates_db::{delete_workout_template, insert_workout_template, lookup_workout_template};
+    use crate::lib::models::wk_execution_elements_models::NewWkExecutionElement;
+    use crate::lib::models::wk_template_elements_models::NewWkTemplateElement;
+    use crate::lib::models::workout_templates_models::NewWorkoutTemplate;
+
+    // TEST CASES
+    // * Everything without a token, only the user's
+    // * Created, updated and deleted records since a token, with the elements deleted with their parent
+    // * Nothing since the latest token
+    // * Ids of deleted records reused by their owner, rejected for anyone else
+
+    fn ids<T>(records: &[T], id: impl Fn(&T) -> Uuid) -> Vec<Uuid> {
+        records.iter().map(id).sorted().collect()
+    }
+
+    #[tokio::test]
+    async fn test_select_changes() {
+        let (connector, _container) = pg_container().await;
+        let element_id = insert_helper(1, Items::WkExecutionElements, &connector, None).await[0];
+        let element = lookup_wk_execution_element(element_id, &connector).await.unwrap();
+        let execution = lookup_workout_execution(element.workout_execution_id, &connector).await.unwrap();
+        let user_id = lookup_workout_template(execution.workout_template_id, &connector).await.unwrap().user_id;
+        insert_helper(1, Items::WkExecutionElements, &connector, None).await;
+
+        let full = select_changes(user_id, None, &connector).await.unwrap();
+        assert_eq!(ids(&full.workout_templates.created, |template| template.id), vec![execution.workout_template_id]);
+        assert_eq!(ids(&full.workout_executions.created, |execution| execution.id), vec![execution.id]);
+        assert_eq!(ids(&full.wk_execution_elements.created, |element| element.id), vec![element_id]);
+        assert!(full.wk_execution_elements.updated.is_empty() && full.wk_execution_elements.deleted.is_empty());
+        let since: SyncToken = full.token.parse().unwrap();
+
+        // A new template with an element and an updated set
+        let template = insert_workout_template(&NewWorkoutTemplate { user_id, ..Default::default() }, &connector).await.unwrap();
+        let new_element = NewWkTemplateElement { workout_template_id: template.id, exercise_id: element.exercise_id, ..Default::default() };
+        let template_element = insert_batch_wk_template_elements(&vec![new_element], &connector).await.unwrap().remove(0);
+        diesel::update(wkexecutionelements::table.find(element_id))
+            .set(wkexecutionelements::reps.eq(12))
+            .execute(&mut connector.rds_connection().await.unwrap())
+            .await
+            .unwrap();
+
+        let changes = select_changes(user_id, Some(since), &connector).await.unwrap();
+        assert_eq!(ids(&changes.workout_templates.created, |template| template.id), vec![template.id]);
+        assert_eq!(ids(&changes.wk_template_elements.created, |element| element.id), vec![template_element.id]);
+        assert_eq!(ids(&changes.wk_execution_elements.updated, |element| element.id), vec![element_id]);
+        assert!(changes.workout_executions.created.is_empty() && changes.workout_executions.updated.is_empty());
+
+        let since: SyncToken = changes.token.parse().unwrap();
+        assert_eq!(delete_workout_execution(execution.id, &connector).await.unwrap(), 1);
+        assert_eq!(delete_workout_template(user_id, template.id, &connector).await.unwrap(), 1);
+        let changes = select_changes(user_id, Some(since), &connector).await.unwrap();
+        assert_eq!(changes.workout_executions.deleted, vec![execution.id]);
+        assert_eq!(changes.wk_execution_elements.deleted, vec![element_id]);
+        assert_eq!(changes.workout_templates.deleted, vec![template.id]);
+        assert_eq!(changes.wk_template_elements.deleted, vec![template_element.id]);
+
+        // Writes that failed leave no trace
+        let orphan = NewWkExecutionElement { workout_execution_id: execution.id, exercise_id: element.exercise_id, ..Default::default() };
+        assert!(upsert_session_set(&orphan, user_id, chrono::Utc::now().naive_utc(), &connector).await.is_err());
+        assert!(insert_batch_wk_execution_elements(&vec![orphan], &connector).await.is_err());
+
+        let since: SyncToken = changes.token.parse().unwrap();
+        let changes = select_changes(user_id, Some(since), &connector).await.unwrap();
+        assert!(changes.workout_templates.deleted.is_empty() && changes.wk_execution_elements.deleted.is_empty());
+        assert!(changes.workout_templates.created.is_empty() && changes.workout_templates.updated.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_reused_ids() {
+        let (connector, _container) = pg_container().await;
+        let user_ids = insert_helper(2, Items::Users, &connector, None).await;
+        let template_id = Uuid::new_v4();
+        let new_template = |user_id| NewWorkoutTemplate { id: Some(template_id), user_id, ..Default::default() };
+
+        insert_workout_template(&new_template(user_ids[0]), &connector).await.unwrap();
+        let since: SyncToken = select_changes(user_ids[0], None, &connector).await.unwrap().token.parse().unwrap();
+        assert_eq!(delete_workout_template(user_ids[0], template_id, &connector).await.unwrap(), 1);
+
+        // The tombstone stays with its owner
+        let res = insert_workout_template(&new_template(user_ids[1]), &connector).await;
+        assert!(matches!(res, Err(DBError::UniqueViolation(_))));
+        assert!(select_changes(user_ids[1], None, &connector).await.unwrap().workout_templates.created.is_empty());
+        let changes = select_changes(user_ids[0], Some(since), &connector).await.unwrap();
+        assert_eq!(changes.workout_templates.deleted, vec![template_id]);
+
+        insert_workout_template(&new_template(user_ids[0]), &connector).await.unwrap();
+        let changes = select_changes(user_ids[0], Some(since), &connector).await.unwrap();
+        assert_eq!(ids(&changes.workout_templates.created, |template| template.id), vec![template_id]);
+        assert!(changes.workout_templates.deleted.is_empty());
+    }
+}
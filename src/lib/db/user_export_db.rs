@@ -0,0 +1,346 @@
+use diesel::prelude::*;
+use diesel::result::Error;
+use diesel_async::RunQueryDsl;
+use diesel_async::scoped_futures::ScopedFutureExt;
+use uuid::Uuid;
+
+use crate::schema::{bodymeasurements, challengeparticipants, challenges, comments, exercises, follows, notifications, programenrolments, programs, programslots, progressionrules, reactions, scheduledworkouts, calendartokens, users, workouttemplates, wktemplateelements, workoutexecutions, wkexecutionelements, workoutimports};
+use crate::lib::models::user_export_models::UserExport;
+use crate::lib::models::user_models::User;
+use crate::lib::models::body_measurement_models::BodyMeasurement;
+use crate::lib::models::follow_models::Follow;
+use crate::lib::models::comment_models::Comment;
+use crate::lib::models::reaction_models::Reaction;
+use crate::lib::models::notification_models::Notification;
+use crate::lib::models::challenge_models::{Challenge, ChallengeParticipant};
+use crate::lib::models::exercise_models::Exercise;
+use crate::lib::models::program_models::{Program, ProgramEnrolment, ProgramSlot};
+use crate::lib::models::progression_models::ProgressionRule;
+use crate::lib::models::workout_templates_models::WorkoutTemplate;
+use crate::lib::models::wk_template_elements_models::WkTemplateElement;
+use crate::lib::models::workout_execution_models::WorkoutExecution;
+use crate::lib::models::wk_execution_elements_models::WkExecutionElement;
+use crate::lib::models::workout_import_models::WorkoutImport;
+use crate::lib::models::schedule_models::{CalendarToken, ScheduledWorkout};
+use crate::lib::errors::DBError;
+
+use super::DBConnector;
+
+/// Collects every row stored for a user, for data access requests.
+///
+/// All tables are read in a single read only, repeatable read transaction, so
+/// the export is a consistent snapshot even if the user keeps training meanwhile.
+/// Rows are ordered so that two exports of the same data are identical.
+pub async fn select_user_export(user_id: Uuid, connector: &DBConnector) -> Result<UserExport, DBError> {
+
+    let mut conn = connector.rds_connection().await?;
+    conn.build_transaction()
+        .read_only()
+        .repeatable_read()
+        .run::<UserExport, Error, _>(|conn| async move {
+            let user = users::table.find(user_id)
+                .first::<User>(conn)
+                .await?;
+
+            let body_measurements = bodymeasurements::table
+                .filter(bodymeasurements::user_id.eq(user_id))
+                .order(bodymeasurements::date)
+                .select(BodyMeasurement::as_select())
+                .load::<BodyMeasurement>(conn)
+                .await?;
+
+            let follows = follows::table
+                .filter(follows::follower_id.eq(user_id).or(follows::followed_id.eq(user_id)))
+                .order((follows::requested_at, follows::follower_id, follows::followed_id))
+                .select(Follow::as_select())
+                .load::<Follow>(conn)
+                .await?;
+
+            let comments = comments::table
+                .filter(comments::user_id.eq(user_id))
+                .order((comments::date_created, comments::id))
+                .select(Comment::as_select())
+                .load::<Comment>(conn)
+                .await?;
+
+            let reactions = reactions::table
+                .filter(reactions::user_id.eq(user_id))
+                .order((reactions::date_created, reactions::workout_execution_id))
+                .select(Reaction::as_select())
+                .load::<Reaction>(conn)
+                .await?;
+
+            let notifications = notifications::table
+                .filter(notifications::user_id.eq(user_id))
+                .order((notifications::date_created, notifications::id))
+                .select(Notification::as_select())
+                .load::<Notification>(conn)
+                .await?;
+
+            let challenges = challenges::table
+                .filter(challenges::creator_id.eq(user_id))
+                .order((challenges::date_created, challenges::id))
+                .select(Challenge::as_select())
+                .load::<Challenge>(conn)
+                .await?;
+
+            let challenge_participants = challengeparticipants::table
+                .filter(challengeparticipants::user_id.eq(user_id))
+                .order((challengeparticipants::invited_at, challengeparticipants::challenge_id))
+                .select(ChallengeParticipant::as_select())
+                .load::<ChallengeParticipant>(conn)
+                .await?;
+
+            let workout_templates = workouttemplates::table
+                .filter(workouttemplates::user_id.eq(user_id))
+                .order((workouttemplates::date_created, workouttemplates::id))
+                .load::<WorkoutTemplate>(conn)
+                .await?;
+            let template_ids: Vec<Uuid> = workout_templates.iter().map(|template| template.id).collect();
+
+            let wk_template_elements = wktemplateelements::table
+                .filter(wktemplateelements::workout_template_id.eq_any(&template_ids))
+                .order((wktemplateelements::workout_template_id, wktemplateelements::position, wktemplateelements::id))
+                .select((
+                    wktemplateelements::id,
+                    wktemplateelements::workout_template_id,
+                    wktemplateelements::exercise_id,
+                    wktemplateelements::position,
+                    wktemplateelements::reps,
+                    wktemplateelements::sets,
+                    wktemplateelements::weight,
+                    wktemplateelements::rest,
+                    wktemplateelements::super_set,
+                ))
+                .load::<WkTemplateElement>(conn)
+                .await?;
+            let element_ids: Vec<Uuid> = wk_template_elements.iter().map(|element| element.id).collect();
+
+            let progression_rules = progressionrules::table
+                .filter(progressionrules::wk_template_element_id.eq_any(&element_ids))
+                .order(progressionrules::wk_template_element_id)
+                .select(ProgressionRule::as_select())
+                .load::<ProgressionRule>(conn)
+                .await?;
+
+            let programs = programs::table
+                .filter(programs::user_id.eq(user_id))
+                .order((programs::date_created, programs::id))
+                .select(Program::as_select())
+                .load::<Program>(conn)
+                .await?;
+            let program_ids: Vec<Uuid> = programs.iter().map(|program| program.id).collect();
+
+            let program_slots = programslots::table
+                .filter(programslots::program_id.eq_any(&program_ids))
+                .order((programslots::program_id, programslots::week, programslots::day))
+                .select(ProgramSlot::as_select())
+                .load::<ProgramSlot>(conn)
+                .await?;
+
+            let program_enrolments = programenrolments::table
+                .filter(programenrolments::user_id.eq(user_id))
+                .order((programenrolments::date_created, programenrolments::id))
+                .select(ProgramEnrolment::as_select())
+                .load::<ProgramEnrolment>(conn)
+                .await?;
+
+            let workout_executions = workoutexecutions::table
+                .filter(workoutexecutions::workout_template_id.eq_any(&template_ids))
+                .order((workoutexecutions::date, workoutexecutions::id))
+                .select(WorkoutExecution::as_select())
+                .load::<WorkoutExecution>(conn)
+                .await?;
+            let execution_ids: Vec<Uuid> = workout_executions.iter().map(|execution| execution.id).collect();
+
+            let wk_execution_elements = wkexecutionelements::table
+                .filter(wkexecutionelements::workout_execution_id.eq_any(&execution_ids))
+                .order((wkexecutionelements::workout_execution_id, wkexecutionelements::position, wkexecutionelements::set_number, wkexecutionelements::id))
+                .select((
+                    wkexecutionelements::id,
+                    wkexecutionelements::workout_execution_id,
+                    wkexecutionelements::exercise_id,
+                    wkexecutionelements::position,
+                    wkexecutionelements::exercise_number,
+                    wkexecutionelements::reps,
+                    wkexecutionelements::set_number,
+                    wkexecutionelements::weight,
+                    wkexecutionelements::rest,
+                    wkexecutionelements::super_set,
+                    wkexecutionelements::time,
+                ))
+                .load::<WkExecutionElement>(conn)
+                .await?;
+
+            let workout_imports = workoutimports::table
+                .filter(workoutimports::user_id.eq(user_id))
+                .order(workoutimports::import_key)
+                .select(WorkoutImport::as_select())
+                .load::<WorkoutImport>(conn)
+                .await?;
+
+            let scheduled_workouts = scheduledworkouts::table
+                .filter(scheduledworkouts::user_id.eq(user_id))
+                .order((scheduledworkouts::date_created, scheduledworkouts::id))
+                .select(ScheduledWorkout::as_select())
+                .load::<ScheduledWorkout>(conn)
+                .await?;
+
+            let calendar_tokens = calendartokens::table
+                .filter(calendartokens::user_id.eq(user_id))
+                .select(CalendarToken::as_select())
+                .load::<CalendarToken>(conn)
+                .await?;
+
+            let referenced_ids: Vec<Uuid> = wk_template_elements.iter().map(|element| element.exercise_id)
+                .chain(wk_execution_elements.iter().map(|element| element.exercise_id))
+                .collect();
+            let exercises = exercises::table
+                .filter(exercises::id.eq_any(&referenced_ids).or(exercises::owner_id.eq(user_id)))
+                .order((exercises::name, exercises::id))
+                .select(Exercise::as_select())
+                .load::<Exercise>(conn)
+                .await?;
+
+            Ok(UserExport {
+                user,
+                body_measurements,
+                follows,
+                comments,
+                reactions,
+                notifications,
+                challenges,
+                challenge_participants,
+                workout_templates,
+                wk_template_elements,
+                progression_rules,
+                programs,
+                program_slots,
+                program_enrolments,
+                workout_executions,
+                wk_execution_elements,
+                workout_imports,
+                scheduled_workouts,
+                calendar_tokens,
+                exercises,
+            })
+        }.scope_boxed())
+        .await
+        .map_err(|error| match error {
+            Error::NotFound => DBError::ItemNotFound("No user exists with the corresponding id".to_string()),
+            _ => DBError::OperationError(error.to_string())
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lib::utils::tests::{pg_container, insert_helper, Items};
+    use crate::lib::db::exercises_db::insert_exercise;
+    use crate::lib::db::challenges_db::insert_challenge;
+    use crate::lib::db::schedules_db::{insert_scheduled_workout, rotate_calendar_token};
+    use crate::lib::models::schedule_models::NewScheduledWorkout;
+    use crate::lib::models::challenge_models::{NewChallenge, WORKOUTS};
+    use crate::lib::db::wk_template_elements_db::insert_batch_wk_template_elements;
+    use crate::lib::db::wk_execution_elements_db::insert_batch_wk_execution_elements;
+    use crate::lib::db::workout_executions_db::insert_workout_execution;
+    use crate::lib::db::workout_templates_db::insert_workout_template;
+    use crate::lib::models::exercise_models::NewExercise;
+    use crate::lib::models::wk_template_elements_models::NewWkTemplateElement;
+    use crate::lib::models::wk_execution_elements_models::NewWkExecutionElement;
+    use crate::lib::models::workout_execution_models::NewWorkoutExecution;
+    use crate::lib::models::workout_templates_models::NewWorkoutTemplate;
+
+    // TEST CASES
+    // * Non existing user
+    // * User without data
+    // * User with templates, executions, an import, challenges, a schedule and a custom exercise, alongside another user's data
+
+    #[tokio::test]
+    async fn test_select_user_export_not_found() {
+        let (connector, _container) = pg_container().await;
+        let res = select_user_export(Uuid::new_v4(), &connector).await;
+        assert!(matches!(res, Err(DBError::ItemNotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn test_select_user_export_empty() {
+        let (connector, _container) = pg_container().await;
+        let user_id = insert_helper(1, Items::Users, &connector, None).await[0];
+
+        let export = select_user_export(user_id, &connector).await.unwrap();
+        assert_eq!(export.user.id, user_id);
+        assert!(export.body_measurements.is_empty());
+        assert!(export.follows.is_empty());
+        assert!(export.comments.is_empty());
+        assert!(export.reactions.is_empty());
+        assert!(export.notifications.is_empty());
+        assert!(export.challenges.is_empty());
+        assert!(export.challenge_participants.is_empty());
+        assert!(export.workout_templates.is_empty());
+        assert!(export.wk_template_elements.is_empty());
+        assert!(export.workout_executions.is_empty());
+        assert!(export.wk_execution_elements.is_empty());
+        assert!(export.workout_imports.is_empty());
+        assert!(export.scheduled_workouts.is_empty());
+        assert!(export.calendar_tokens.is_empty());
+        assert!(export.exercises.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_select_user_export_ok() {
+        let (connector, _container) = pg_container().await;
+        let user_id = insert_helper(1, Items::Users, &connector, None).await[0];
+        let exercise_ids = insert_helper(2, Items::Exercises, &connector, None).await;
+        // Unreferenced catalog exercise and another user's data
+        insert_helper(1, Items::Exercises, &connector, None).await;
+        insert_helper(2, Items::WkExecutionElements, &connector, None).await;
+
+        let custom = insert_exercise(&NewExercise { name: "Custom".to_string(), owner_id: Some(user_id), ..Default::default() }, &connector).await.unwrap();
+        let template = insert_workout_template(&NewWorkoutTemplate { user_id, ..Default::default() }, &connector).await.unwrap();
+        insert_batch_wk_template_elements(&vec![
+            NewWkTemplateElement { workout_template_id: template.id, exercise_id: exercise_ids[0], position: 0, ..Default::default() },
+            NewWkTemplateElement { workout_template_id: template.id, exercise_id: exercise_ids[1], position: 1, ..Default::default() },
+        ], &connector).await.unwrap();
+        let execution = insert_workout_execution(&NewWorkoutExecution { workout_template_id: template.id, ..Default::default() }, &connector).await.unwrap();
+        insert_batch_wk_execution_elements(&vec![
+            NewWkExecutionElement { workout_execution_id: execution.id, exercise_id: exercise_ids[0], ..Default::default() },
+        ], &connector).await.unwrap();
+        let import = WorkoutImport { user_id, import_key: "hevy:1".to_string(), workout_execution_id: execution.id };
+        diesel::insert_into(workoutimports::table)
+            .values(&import)
+            .execute(&mut connector.rds_connection().await.unwrap())
+            .await
+            .unwrap();
+
+        // Own challenge, and an invitation to another user's
+        let other_id = insert_helper(1, Items::Users, &connector, None).await[0];
+        let challenge = |creator_id| NewChallenge { creator_id, name: "November".to_string(), metric: WORKOUTS, exercise_id: None, start_date: execution.date, end_date: execution.date };
+        let own = insert_challenge(&challenge(user_id), &[], &connector).await.unwrap().challenge;
+        let invited = insert_challenge(&challenge(other_id), &[user_id], &connector).await.unwrap().challenge;
+
+        let scheduled = insert_scheduled_workout(&NewScheduledWorkout { user_id, workout_template_id: template.id, start_date: execution.date, end_date: None, weekdays: Some(vec![1, 4]) }, &connector).await.unwrap();
+        let token = rotate_calendar_token(user_id, &connector).await.unwrap();
+
+        let export = select_user_export(user_id, &connector).await.unwrap();
+        assert_eq!(export.scheduled_workouts, vec![scheduled]);
+        assert_eq!(export.calendar_tokens, vec![token]);
+        assert_eq!(export.challenges, vec![own.clone()]);
+        let participations: Vec<Uuid> = export.challenge_participants.iter().map(|participant| participant.challenge_id).collect();
+        assert_eq!(participations.len(), 2);
+        assert!(participations.contains(&own.id) && participations.contains(&invited.id));
+        assert_eq!(export.workout_templates.len(), 1);
+        assert_eq!(export.wk_template_elements.len(), 2);
+        assert_eq!(export.wk_template_elements[0].exercise_id, exercise_ids[0]);
+        assert_eq!(export.workout_executions.len(), 1);
+        assert_eq!(export.wk_execution_elements.len(), 1);
+        assert!(export.wk_execution_elements.iter().all(|element| element.workout_execution_id == execution.id));
+        assert_eq!(export.workout_imports, vec![import]);
+
+        let mut exported_exercises: Vec<Uuid> = export.exercises.iter().map(|exercise| exercise.id).collect();
+        let mut expected = vec![exercise_ids[0], exercise_ids[1], custom.id];
+        exported_exercises.sort();
+        expected.sort();
+        assert_eq!(exported_exercises, expected);
+    }
+}
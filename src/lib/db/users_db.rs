@@ -1,13 +1,20 @@
 use diesel::prelude::*;
 use diesel::result::{Error, DatabaseErrorKind};
-use diesel_async::RunQueryDsl;
+use diesel_async::{AsyncConnection, RunQueryDsl};
+use diesel_async::scoped_futures::ScopedFutureExt;
+use chrono::{Duration, NaiveDateTime, Utc};
 use uuid::Uuid;
 
 use crate::schema::users::dsl::*;
-use crate::lib::models::user_models::{UncompleteUser, User};
+use crate::schema::{challenges, exercises, wkexecutionelements, wktemplateelements, workouttemplates};
+use crate::schema::bodymeasurements;
+use crate::schema::follows;
+use crate::lib::models::user_models::{UncompleteUser, User, UserInfo};
+use crate::lib::models::unit_models::Units;
 use crate::lib::errors::DBError;
 
 use super::DBConnector;
+use super::sql_functions::lower;
 
 /// Inserts a new user into the database and returns the inserted user.
 pub async fn insert_user(new_user: &User, connector: &DBConnector) -> Result<User, DBError> {
@@ -26,12 +33,37 @@ pub async fn insert_user(new_user: &User, connector: &DBConnector) -> Result<Use
 }
 
 /// Update given values for an already existing user
+/// * A new weight is also recorded as today's body measurement
+/// * Making the profile public accepts its pending follow requests
 pub async fn update_user(user_id: &Uuid, user: &UncompleteUser, connector: &DBConnector) -> Result<User, DBError> {
 
     let mut conn = connector.rds_connection().await?;
-    let user = diesel::update(users.filter(id.eq(user_id)))
-        .set(user)
-        .get_result(&mut conn)
+    let user = conn.transaction::<User, Error, _>(|conn| async move {
+        let updated = diesel::update(users.filter(id.eq(user_id)))
+            .set(user)
+            .get_result::<User>(conn)
+            .await?;
+        if let Some(kg) = user.weight.filter(|kg| *kg > 0.0) {
+            diesel::insert_into(bodymeasurements::table)
+                .values((
+                    bodymeasurements::user_id.eq(user_id),
+                    bodymeasurements::date.eq(Utc::now().date_naive()),
+                    bodymeasurements::weight.eq(kg),
+                ))
+                .on_conflict((bodymeasurements::user_id, bodymeasurements::date))
+                .do_update()
+                .set(bodymeasurements::weight.eq(kg))
+                .execute(conn)
+                .await?;
+        }
+        if user.private_profile == Some(false) {
+            diesel::update(follows::table.filter(follows::followed_id.eq(user_id)))
+                .set(follows::accepted.eq(true))
+                .execute(conn)
+                .await?;
+        }
+        Ok(updated)
+    }.scope_boxed())
         .await
         .map_err(|error| match error {
             Error::NotFound => {
@@ -63,31 +95,159 @@ pub async fn lookup_user(user_id: Uuid, connector: &DBConnector) -> Result<User,
     Ok(user)
 }
 
+/// Returns the units the user prefers, or an error if not found.
+pub async fn lookup_units(user_id: Uuid, connector: &DBConnector) -> Result<Units, DBError> {
+
+    let mut conn = connector.rds_connection().await?;
+    users.find(user_id)
+        .select(Units::as_select())
+        .first::<Units>(&mut conn)
+        .await
+        .map_err(|error| match error {
+            Error::NotFound => DBError::ItemNotFound("No user exists with the corresponding id".to_string()),
+            _ => DBError::OperationError(error.to_string())
+        })
+}
+
+
+/// Maximum number of users returned by a search
+pub const USER_SEARCH_LIMIT: i64 = 25;
 
 /// Searches for users with names starting with the given term.
 ///
 /// This function performs a case-insensitive search in the `users` table,
-/// returning all users whose names begin with the specified t
-pub async fn search_username(term: &str, connector: &DBConnector) -> Result<Vec<User>, DBError> {
+/// returning users whose username or any word of their display name begin
+/// with the specified term. Exact matches come first, then username prefixes.
+/// Users that opted out of discoverability or are pending deletion are never returned.
+pub async fn search_users(term: &str, connector: &DBConnector) -> Result<Vec<UserInfo>, DBError> {
     
     let mut conn = connector.rds_connection().await?;
-    let pattern = format!("{}%", term);
-    users.filter(username.like(pattern))
-        .load::<User>(&mut conn)
+    let prefix = format!("{}%", term);
+    let word_prefix = format!("% {}%", term);
+    users
+        .filter(discoverable.eq(true))
+        .filter(deletion_scheduled_for.is_null())
+        .filter(username.ilike(&prefix).or(display_name.ilike(&prefix)).or(display_name.ilike(word_prefix)))
+        .order((
+            lower(username).eq(lower(term)).or(lower(display_name).eq(lower(term))).desc(),
+            username.ilike(&prefix).desc(),
+            username.asc(),
+        ))
+        .limit(USER_SEARCH_LIMIT)
+        .select(UserInfo::as_select())
+        .load::<UserInfo>(&mut conn)
+        .await
+        .map_err(|error| DBError::OperationError(error.to_string()))
+}
+
+/// Schedules the deletion of a user, returning when it will be purged.
+///
+/// Requesting it again keeps the original schedule.
+pub async fn schedule_user_deletion(user_id: Uuid, grace_period: Duration, connector: &DBConnector) -> Result<NaiveDateTime, DBError> {
+
+    let mut conn = connector.rds_connection().await?;
+    let scheduled_for = Utc::now().naive_utc() + grace_period;
+    diesel::update(users.find(user_id))
+        .filter(deletion_scheduled_for.is_null())
+        .set(deletion_scheduled_for.eq(scheduled_for))
+        .execute(&mut conn)
+        .await
+        .map_err(|error| DBError::OperationError(error.to_string()))?;
+
+    let scheduled = users.find(user_id)
+        .select(deletion_scheduled_for)
+        .first::<Option<NaiveDateTime>>(&mut conn)
+        .await
+        .map_err(|error| match error {
+            Error::NotFound => DBError::ItemNotFound("No user exists with the corresponding id".to_string()),
+            _ => DBError::OperationError(error.to_string())
+        })?;
+    scheduled.ok_or_else(|| DBError::OperationError("Deletion couldn't be scheduled".to_string()))
+}
+
+/// Cancels a pending deletion, returning the number of affected users.
+pub async fn cancel_user_deletion(user_id: Uuid, connector: &DBConnector) -> Result<usize, DBError> {
+
+    let mut conn = connector.rds_connection().await?;
+    diesel::update(users.find(user_id))
+        .filter(deletion_scheduled_for.is_not_null())
+        .set(deletion_scheduled_for.eq(None::<NaiveDateTime>))
+        .execute(&mut conn)
         .await
         .map_err(|error| DBError::OperationError(error.to_string()))
 }
 
+/// Removes every user whose deletion was scheduled before the given time.
+///
+/// Elements and challenges using the users' private exercises are removed first, as they would
+/// keep the exercises from cascading with the users, then the templates with everything under them.
+/// Returns the number of purged users.
+pub async fn purge_deleted_users(until: NaiveDateTime, connector: &DBConnector) -> Result<usize, DBError> {
+
+    let mut conn = connector.rds_connection().await?;
+    conn.transaction::<usize, Error, _>(|conn| async move {
+        let purged_ids = users
+            .filter(deletion_scheduled_for.le(until))
+            .select(id)
+            .for_update()
+            .load::<Uuid>(conn)
+            .await?;
+        let private_ids = exercises::table
+            .filter(exercises::owner_id.eq_any(&purged_ids))
+            .select(exercises::id)
+            .load::<Uuid>(conn)
+            .await?;
+        diesel::delete(wkexecutionelements::table.filter(wkexecutionelements::exercise_id.eq_any(&private_ids)))
+            .execute(conn)
+            .await?;
+        diesel::delete(wktemplateelements::table.filter(wktemplateelements::exercise_id.eq_any(&private_ids)))
+            .execute(conn)
+            .await?;
+        diesel::delete(challenges::table.filter(challenges::exercise_id.eq_any(&private_ids)))
+            .execute(conn)
+            .await?;
+        diesel::delete(workouttemplates::table.filter(workouttemplates::user_id.eq_any(&purged_ids)))
+            .execute(conn)
+            .await?;
+        diesel::delete(users.filter(id.eq_any(&purged_ids)))
+            .execute(conn)
+            .await
+    }.scope_boxed())
+    .await
+    .map_err(|error| DBError::OperationError(error.to_string()))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::lib::utils::tests::{pg_container, insert_helper, Items};
+    use crate::lib::db::exercises_db::{insert_exercise, lookup_exercise};
+    use crate::lib::db::challenges_db::insert_challenge;
+    use crate::lib::models::challenge_models::{NewChallenge, EXERCISE_REPS};
+    use crate::lib::db::wk_execution_elements_db::insert_batch_wk_execution_elements;
+    use crate::lib::db::workout_executions_db::insert_workout_execution;
+    use crate::lib::db::body_measurements_db::select_body_measurements;
+    use crate::lib::db::wk_template_elements_db::{insert_batch_wk_template_elements, lookup_wk_template_element};
+    use crate::lib::db::workout_templates_db::lookup_workout_template;
+    use crate::lib::models::unit_models::{LB, CM};
+    use crate::lib::db::follows_db::{can_view_profile, insert_follow};
+    use crate::lib::models::exercise_models::NewExercise;
+    use crate::lib::models::wk_template_elements_models::NewWkTemplateElement;
+    use crate::lib::models::wk_execution_elements_models::NewWkExecutionElement;
+    use crate::lib::models::workout_execution_models::NewWorkoutExecution;
 
     // TEST CASES
     // * Insert and lookup user
     // * Insert with duplicate PK
     // * Lookup non existing
     // * Search multiple and none
+    // * Search case-insensitive on username and display name, exact first
+    // * Search hides undiscoverable users and is capped
+    // * Schedule deletion, hidden from search, reschedule keeps date, cancel
+    // * Purge only expired users along with their templates and private exercises
+    // * Update with weight records it as today's measurement
+    // * Lookup unit preferences
+    // * Making the profile public accepts pending follow requests
 
     #[tokio::test]
     async fn test_insert_lookup_user() {
@@ -124,6 +284,28 @@ mod tests {
         let insert_res2 = insert_user(&new_user2, &connector).await;
         assert!(insert_res2.is_err());
     }
+    #[tokio::test]
+    async fn test_lookup_units() {
+        let (connector, _container) = pg_container().await;
+        let user = insert_user(&User { weight_unit: LB, ..Default::default() }, &connector).await.unwrap();
+
+        let units = lookup_units(user.id, &connector).await.unwrap();
+        assert_eq!(units, Units { weight_unit: LB, length_unit: CM });
+        let res = lookup_units(Uuid::new_v4(), &connector).await;
+        assert!(matches!(res, Err(DBError::ItemNotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn test_update_user_public_accepts_requests() {
+        let (connector, _container) = pg_container().await;
+        let ids = insert_helper(2, Items::Users, &connector, None).await;
+        update_user(&ids[0], &UncompleteUser { private_profile: Some(true), ..Default::default() }, &connector).await.unwrap();
+        assert!(!insert_follow(ids[1], ids[0], &connector).await.unwrap().accepted);
+
+        update_user(&ids[0], &UncompleteUser { private_profile: Some(false), ..Default::default() }, &connector).await.unwrap();
+        assert!(can_view_profile(ids[1], ids[0], &connector).await.unwrap());
+    }
+
     #[tokio::test]
     async fn test_lookup_user_non_existing() {
         let (connector, _container) = pg_container().await;
@@ -134,27 +316,30 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn test_search_username_multiple() {
+    async fn test_search_users_multiple() {
         let (connector, _container) = pg_container().await;
 
         let user_ids = insert_helper(5, Items::Users, &connector, Some("TEST".into())).await;
 
         let pattern = "TEST";
-        let search_res = search_username(pattern, &connector).await;
+        let search_res = search_users(pattern, &connector).await;
         assert!(search_res.is_ok());
 
         let user_vec = search_res.unwrap();
-        let id_vec: Vec<Uuid> = user_vec.iter().map(|ex| ex.id.clone()).collect();
+        let mut id_vec: Vec<Uuid> = user_vec.iter().map(|ex| ex.id).collect();
+        let mut user_ids = user_ids;
+        id_vec.sort();
+        user_ids.sort();
         assert_eq!(id_vec.len(), 5);
         assert_eq!(id_vec, user_ids);
     }
 
     #[tokio::test]
-    async fn test_search_username_success_none() {
+    async fn test_search_users_success_none() {
         let (connector, _container) = pg_container().await;
 
         let pattern = "Testing";
-        let search_res = search_username(pattern, &connector).await;
+        let search_res = search_users(pattern, &connector).await;
         assert!(search_res.is_ok());
 
         let vec = search_res.unwrap();
@@ -192,4 +377,95 @@ mod tests {
         assert!(update_res.is_ok());
         assert_eq!(update_res.unwrap(), expected_user);
     }
+
+    #[tokio::test]
+    async fn test_update_user_weight_recorded() {
+        let (connector, _container) = pg_container().await;
+        let user_id = insert_helper(1, Items::Users, &connector, None).await[0];
+
+        for kg in [81.0, 80.5] {
+            let changes = UncompleteUser {weight: Some(kg), ..Default::default()};
+            update_user(&user_id, &changes, &connector).await.unwrap();
+        }
+        let history = select_body_measurements(user_id, None, None, &connector).await.unwrap();
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].weight, Some(80.5));
+    }
+
+    #[tokio::test]
+    async fn test_search_users_ranked() {
+        let (connector, _container) = pg_container().await;
+        for (user_name, display) in [("annabel", "Annabel Lee"), ("ann", "Ann"), ("bob", "Bob Annson"), ("carl", "Carl")] {
+            let new_user = User {username: user_name.to_string(), display_name: display.to_string(), ..Default::default()};
+            let _ = insert_user(&new_user, &connector).await;
+        }
+
+        let names: Vec<String> = search_users("ANN", &connector).await.unwrap().into_iter().map(|user| user.username).collect();
+        assert_eq!(names, vec!["ann", "annabel", "bob"]);
+
+        let names: Vec<String> = search_users("lee", &connector).await.unwrap().into_iter().map(|user| user.username).collect();
+        assert_eq!(names, vec!["annabel"]);
+    }
+
+    #[tokio::test]
+    async fn test_search_users_hidden_and_capped() {
+        let (connector, _container) = pg_container().await;
+        let hidden = User {username: "hidden".to_string(), discoverable: false, ..Default::default()};
+        let _ = insert_user(&hidden, &connector).await;
+        assert!(search_users("hidden", &connector).await.unwrap().is_empty());
+
+        let _ = insert_helper(USER_SEARCH_LIMIT as usize + 5, Items::Users, &connector, Some("many".into())).await;
+        assert_eq!(search_users("many", &connector).await.unwrap().len(), USER_SEARCH_LIMIT as usize);
+    }
+
+    #[tokio::test]
+    async fn test_schedule_cancel_user_deletion() {
+        let (connector, _container) = pg_container().await;
+        let user_id = insert_helper(1, Items::Users, &connector, Some("leaving".into())).await[0];
+
+        let scheduled = schedule_user_deletion(user_id, Duration::days(30), &connector).await.unwrap();
+        assert!(scheduled > Utc::now().naive_utc() + Duration::days(29));
+        assert!(search_users("leaving", &connector).await.unwrap().is_empty());
+
+        let rescheduled = schedule_user_deletion(user_id, Duration::days(1), &connector).await.unwrap();
+        assert_eq!(scheduled, rescheduled);
+
+        assert_eq!(cancel_user_deletion(user_id, &connector).await.unwrap(), 1);
+        assert_eq!(cancel_user_deletion(user_id, &connector).await.unwrap(), 0);
+        assert_eq!(search_users("leaving", &connector).await.unwrap().len(), 1);
+
+        let schedule_res = schedule_user_deletion(Uuid::new_v4(), Duration::days(1), &connector).await;
+        assert!(matches!(schedule_res, Err(DBError::ItemNotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn test_purge_deleted_users() {
+        let (connector, _container) = pg_container().await;
+
+        // User with a template that uses one of their private exercises, and a workout where it was logged
+        let element_id = insert_helper(1, Items::WkTemplateElements, &connector, None).await[0];
+        let template_id = lookup_wk_template_element(element_id, &connector).await.unwrap().workout_template_id;
+        let user_id = lookup_workout_template(template_id, &connector).await.unwrap().user_id;
+        let private = NewExercise {owner_id: Some(user_id), ..Default::default()};
+        let private_id = insert_exercise(&private, &connector).await.unwrap().id;
+        let new_element = NewWkTemplateElement {workout_template_id: template_id, exercise_id: private_id, position: 1, ..Default::default()};
+        assert!(insert_batch_wk_template_elements(&vec![new_element], &connector).await.is_ok());
+        let execution_id = insert_workout_execution(&NewWorkoutExecution {workout_template_id: template_id, ..Default::default()}, &connector).await.unwrap().id;
+        let logged = NewWkExecutionElement {workout_execution_id: execution_id, exercise_id: private_id, ..Default::default()};
+        assert!(insert_batch_wk_execution_elements(&vec![logged], &connector).await.is_ok());
+        let challenge = NewChallenge {creator_id: user_id, name: "Reps".to_string(), metric: EXERCISE_REPS, exercise_id: Some(private_id), start_date: Utc::now().date_naive(), end_date: Utc::now().date_naive()};
+        assert!(insert_challenge(&challenge, &[], &connector).await.is_ok());
+
+        let staying_id = insert_helper(1, Items::Users, &connector, None).await[0];
+        let later_id = insert_helper(1, Items::Users, &connector, None).await[0];
+        let _ = schedule_user_deletion(user_id, Duration::zero(), &connector).await;
+        let _ = schedule_user_deletion(later_id, Duration::days(30), &connector).await;
+
+        assert_eq!(purge_deleted_users(Utc::now().naive_utc(), &connector).await.unwrap(), 1);
+        assert!(lookup_user(user_id, &connector).await.is_err());
+        assert!(lookup_workout_template(template_id, &connector).await.is_err());
+        assert!(lookup_exercise(private_id, user_id, &connector).await.is_err());
+        assert!(lookup_user(staying_id, &connector).await.is_ok());
+        assert!(lookup_user(later_id, &connector).await.is_ok());
+    }
 }
@@ -0,0 +1,145 @@
+use std::collections::{HashMap, HashSet};
+use chrono::NaiveDate;
+use diesel::prelude::*;
+use diesel::result::Error;
+use diesel_async::{AsyncPgConnection, RunQueryDsl};
+use diesel_async::scoped_futures::ScopedFutureExt;
+use itertools::Itertools;
+use uuid::Uuid;
+
+use crate::schema::{wkexecutionelements, wktemplateelements, workoutexecutions, workouttemplates};
+use crate::lib::models::wk_execution_elements_models::WkExecutionElement;
+use crate::lib::models::wk_template_elements_models::WkTemplateElement;
+use crate::lib::models::workout_draft_models::WorkoutDraft;
+use crate::lib::models::workout_execution_models::COMPLETED;
+use crate::lib::errors::DBError;
+
+use super::DBConnector;
+
+/// Draft execution of a template of the user for `date`, prefilled from their latest sets of each exercise
+pub async fn select_workout_draft(workout_template_id: Uuid, user_id: Uuid, date: NaiveDate, connector: &DBConnector) -> Result<WorkoutDraft, DBError> {
+
+    let mut conn = connector.rds_connection().await?;
+    conn.build_transaction()
+        .read_only()
+        .repeatable_read()
+        .run::<WorkoutDraft, Error, _>(|conn| async move {
+            workouttemplates::table
+                .filter(workouttemplates::id.eq(workout_template_id))
+                .filter(workouttemplates::user_id.eq(user_id))
+                .select(workouttemplates::id)
+                .first::<Uuid>(conn)
+                .await?;
+            let elements = wktemplateelements::table
+                .filter(wktemplateelements::workout_template_id.eq(workout_template_id))
+                .select(wktemplateelements::all_columns)
+                .load::<WkTemplateElement>(conn)
+                .await?;
+            let exercise_ids: Vec<Uuid> = elements.iter().map(|element| element.exercise_id).unique().collect();
+            let last_sets = last_sets(user_id, &exercise_ids, conn).await?;
+            Ok(WorkoutDraft::new(workout_template_id, date, elements, &last_sets))
+        }.scope_boxed())
+        .await
+        .map_err(|error| match error {
+            Error::NotFound => DBError::ItemNotFound("No template exists with the corresponding id".to_string()),
+            _ => DBError::OperationError(error.to_string()),
+        })
+}
+
+/// Sets of each exercise in the latest execution of the user that has it, in the order they were done
+async fn last_sets(user_id: Uuid, exercise_ids: &[Uuid], conn: &mut AsyncPgConnection) -> QueryResult<HashMap<Uuid, Vec<WkExecutionElement>>> {
+    let latest: HashSet<(Uuid, Uuid)> = wkexecutionelements::table
+        .inner_join(workoutexecutions::table.inner_join(workouttemplates::table))
+        .filter(workouttemplates::user_id.eq(user_id))
+        .filter(workoutexecutions::status.eq(COMPLETED))
+        .filter(wkexecutionelements::exercise_id.eq_any(exercise_ids))
+        .distinct_on(wkexecutionelements::exercise_id)
+        .order((wkexecutionelements::exercise_id, workoutexecutions::date.desc(), workoutexecutions::id.desc()))
+        .select((wkexecutionelements::exercise_id, workoutexecutions::id))
+        .load::<(Uuid, Uuid)>(conn)
+        .await?
+        .into_iter()
+        .collect();
+    let execution_ids: Vec<Uuid> = latest.iter().map(|(_, execution_id)| *execution_id).unique().collect();
+    Ok(wkexecutionelements::table
+        .filter(wkexecutionelements::workout_execution_id.eq_any(&execution_ids))
+        .filter(wkexecutionelements::exercise_id.eq_any(exercise_ids))
+        .order((wkexecutionelements::exercise_number, wkexecutionelements::set_number))
+        .select(wkexecutionelements::all_columns)
+        .load::<WkExecutionElement>(conn)
+        .await?
+        .into_iter()
+        .filter(|set| latest.contains(&(set.exercise_id, set.workout_execution_id)))
+        .into_group_map_by(|set| set.exercise_id))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lib::utils::tests::{pg_container, insert_helper, Items};
+    use crate::lib::db::wk_execution_elements_db::insert_batch_wk_execution_elements;
+    use crate::lib::db::wk_template_elements_db::insert_batch_wk_template_elements;
+    use crate::lib::db::workout_executions_db::insert_workout_execution;
+    use crate::lib::db::workout_sessions_db::{insert_workout_session, upsert_session_set};
+    use crate::lib::db::workout_templates_db::{insert_workout_template, lookup_workout_template};
+    use crate::lib::models::wk_execution_elements_models::NewWkExecutionElement;
+    use crate::lib::models::wk_template_elements_models::NewWkTemplateElement;
+    use crate::lib::models::workout_execution_models::{NewWorkoutExecution, NewWorkoutSession};
+    use crate::lib::models::workout_templates_models::NewWorkoutTemplate;
+
+    // TEST CASES
+    // * Template of another user
+    // * Prefilled from the latest execution of each exercise, on any template
+    // * Sessions in progress don't count
+
+    fn day(n: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2024, 11, n).unwrap()
+    }
+
+    async fn execution_with(workout_template_id: Uuid, date: NaiveDate, exercise_id: Uuid, sets: &[(i16, f32)], connector: &DBConnector) {
+        let execution = insert_workout_execution(&NewWorkoutExecution { workout_template_id, date, ..Default::default() }, connector).await.unwrap();
+        let rows: Vec<NewWkExecutionElement> = sets.iter().enumerate().map(|(n, (reps, weight))| NewWkExecutionElement {
+            workout_execution_id: execution.id,
+            exercise_id,
+            position: n as i16,
+            set_number: n as i16,
+            reps: *reps,
+            weight: Some(*weight),
+            time: 40,
+            ..Default::default()
+        }).collect();
+        insert_batch_wk_execution_elements(&rows, connector).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_select_workout_draft() {
+        let (connector, _container) = pg_container().await;
+        let template_id = insert_helper(1, Items::WkTemplates, &connector, None).await[0];
+        let user_id = lookup_workout_template(template_id, &connector).await.unwrap().user_id;
+        let other_template = insert_workout_template(&NewWorkoutTemplate { user_id, ..Default::default() }, &connector).await.unwrap().id;
+        let exercises = insert_helper(2, Items::Exercises, &connector, None).await;
+        let elements = vec![
+            NewWkTemplateElement { workout_template_id: template_id, exercise_id: exercises[0], position: 0, reps: 5, sets: 2, weight: Some(60.0), rest: 90, ..Default::default() },
+            NewWkTemplateElement { workout_template_id: template_id, exercise_id: exercises[1], position: 1, reps: 12, sets: 1, weight: Some(20.0), rest: 60, ..Default::default() },
+        ];
+        insert_batch_wk_template_elements(&elements, &connector).await.unwrap();
+
+        execution_with(template_id, day(1), exercises[0], &[(5, 70.0), (5, 70.0)], &connector).await;
+        execution_with(other_template, day(4), exercises[0], &[(6, 72.5), (4, 72.5)], &connector).await;
+        execution_with(template_id, day(2), exercises[0], &[(5, 71.0)], &connector).await;
+        let now = chrono::Utc::now().naive_utc();
+        let session_id = insert_workout_session(&NewWorkoutSession::new(template_id, day(5), None, now), user_id, &connector).await.unwrap().id;
+        let session_set = NewWkExecutionElement { workout_execution_id: session_id, exercise_id: exercises[0], reps: 10, weight: Some(200.0), time: 40, ..Default::default() };
+        upsert_session_set(&session_set, user_id, now, &connector).await.unwrap();
+
+        let res = select_workout_draft(template_id, Uuid::new_v4(), day(5), &connector).await;
+        assert!(matches!(res, Err(DBError::ItemNotFound(_))));
+
+        let draft = select_workout_draft(template_id, user_id, day(5), &connector).await.unwrap();
+        assert_eq!((draft.workout_template_id, draft.date), (template_id, day(5)));
+        let rows: Vec<(i16, i16, i16, Option<f32>, i32)> = draft.elements.iter()
+            .map(|row| (row.exercise_number, row.set_number, row.reps, row.weight, row.time))
+            .collect();
+        assert_eq!(rows, vec![(0, 0, 6, Some(72.5), 40), (0, 1, 4, Some(72.5), 40), (1, 0, 12, Some(20.0), 30)]);
+    }
+}
@@ -0,0 +1,240 @@
+use std::collections::{HashMap, HashSet};
+use diesel::prelude::*;
+use diesel::result::{Error, DatabaseErrorKind};
+use diesel_async::{AsyncConnection, RunQueryDsl};
+use diesel_async::scoped_futures::ScopedFutureExt;
+use uuid::Uuid;
+
+use crate::schema::{exercises, workoutimports, workouttemplates, workoutexecutions, wkexecutionelements};
+use crate::lib::models::workout_import_models::{ImportedWorkout, WorkoutImport};
+use crate::lib::models::workout_execution_models::NewWorkoutExecution;
+use crate::lib::models::workout_templates_models::NewWorkoutTemplate;
+use crate::lib::errors::DBError;
+
+use super::DBConnector;
+
+/// Returns which of the given keys the user has already imported.
+pub async fn select_imported_keys(user_id: Uuid, keys: &[String], connector: &DBConnector) -> Result<HashSet<String>, DBError> {
+
+    let mut conn = connector.rds_connection().await?;
+    workoutimports::table
+        .filter(workoutimports::user_id.eq(user_id))
+        .filter(workoutimports::import_key.eq_any(keys))
+        .select(workoutimports::import_key)
+        .load::<String>(&mut conn)
+        .await
+        .map(|imported| imported.into_iter().collect())
+        .map_err(|error| DBError::OperationError(error.to_string()))
+}
+
+/// Inserts the workouts the user hasn't imported yet and returns how many were inserted.
+///
+/// Each workout becomes an execution of the user's template with the same name,
+/// templates that don't exist are created empty, described as imported from `source`.
+/// `new_exercises` are created as private exercises of the user first, with the ids
+/// the elements already use for them.
+/// Everything happens in a single transaction, a concurrent import of the same
+/// workouts fails with a unique violation instead of duplicating them.
+pub async fn insert_imported_workouts(user_id: Uuid, source: &str, new_exercises: &HashMap<String, Uuid>, workouts: Vec<ImportedWorkout>, connector: &DBConnector) -> Result<usize, DBError> {
+
+    let description = format!("Imported from {}", source);
+    let mut conn = connector.rds_connection().await?;
+    conn.transaction::<usize, Error, _>(|conn| async move {
+        if !new_exercises.is_empty() {
+            let values: Vec<_> = new_exercises.iter()
+                .map(|(name, &id)| (exercises::id.eq(id), exercises::name.eq(name), exercises::owner_id.eq(user_id)))
+                .collect();
+            diesel::insert_into(exercises::table)
+                .values(&values)
+                .execute(conn)
+                .await?;
+        }
+
+        let keys: Vec<&String> = workouts.iter().map(|workout| &workout.import_key).collect();
+        let imported: HashSet<String> = workoutimports::table
+            .filter(workoutimports::user_id.eq(user_id))
+            .filter(workoutimports::import_key.eq_any(keys))
+            .select(workoutimports::import_key)
+            .load::<String>(conn)
+            .await?
+            .into_iter()
+            .collect();
+        let pending: Vec<ImportedWorkout> = workouts.into_iter()
+            .filter(|workout| !imported.contains(&workout.import_key))
+            .collect();
+
+        // Existing templates are reused, the others are created dated on their first workout
+        let names: Vec<&String> = pending.iter().map(|workout| &workout.template_name).collect();
+        let mut templates: HashMap<String, Uuid> = workouttemplates::table
+            .filter(workouttemplates::user_id.eq(user_id))
+            .filter(workouttemplates::name.eq_any(names))
+            .order(workouttemplates::date_created.asc())
+            .select((workouttemplates::name, workouttemplates::id))
+            .load::<(String, Uuid)>(conn)
+            .await?
+            .into_iter()
+            .rev()
+            .collect();
+        let mut new_templates: HashMap<&String, NewWorkoutTemplate> = HashMap::new();
+        for workout in pending.iter().filter(|workout| !templates.contains_key(&workout.template_name)) {
+            let template = new_templates.entry(&workout.template_name).or_insert_with(|| NewWorkoutTemplate {
+                id: None,
+                user_id,
+                name: workout.template_name.clone(),
+                description: Some(description.clone()),
+                date_created: workout.date,
+            });
+            template.date_created = template.date_created.min(workout.date);
+        }
+        if !new_templates.is_empty() {
+            let new_templates: Vec<NewWorkoutTemplate> = new_templates.into_values().collect();
+            let created: Vec<(String, Uuid)> = diesel::insert_into(workouttemplates::table)
+                .values(&new_templates)
+                .returning((workouttemplates::name, workouttemplates::id))
+                .get_results(conn)
+                .await?;
+            templates.extend(created);
+        }
+
+        let n = pending.len();
+        for workout in pending {
+            let execution_id = diesel::insert_into(workoutexecutions::table)
+                .values(NewWorkoutExecution {
+                    id: None,
+                    workout_template_id: templates[&workout.template_name],
+                    date: workout.date,
+                    survey: 0,
+                    program_slot_id: None,
+                })
+                .returning(workoutexecutions::id)
+                .get_result::<Uuid>(conn)
+                .await?;
+
+            let mut elements = workout.elements;
+            for element in elements.iter_mut() {
+                element.workout_execution_id = execution_id;
+            }
+            diesel::insert_into(wkexecutionelements::table)
+                .values(&elements)
+                .execute(conn)
+                .await?;
+
+            diesel::insert_into(workoutimports::table)
+                .values(WorkoutImport { user_id, import_key: workout.import_key, workout_execution_id: execution_id })
+                .execute(conn)
+                .await?;
+        }
+        Ok(n)
+    }.scope_boxed())
+    .await
+    .map_err(|error| match error {
+        Error::DatabaseError(DatabaseErrorKind::UniqueViolation, _) => {
+            DBError::UniqueViolation("Workouts are already being imported".to_string())
+        },
+        Error::DatabaseError(DatabaseErrorKind::ForeignKeyViolation, _) => {
+            DBError::ItemNotFound("User or exercise doesn't exist".to_string())
+        },
+        _ => DBError::OperationError(error.to_string()),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+    use crate::lib::utils::tests::{pg_container, insert_helper, Items};
+    use crate::lib::db::exercises_db::lookup_exercise;
+    use crate::lib::db::user_export_db::select_user_export;
+    use crate::lib::db::workout_templates_db::insert_workout_template;
+    use crate::lib::models::wk_execution_elements_models::NewWkExecutionElement;
+
+    // TEST CASES
+    // * Import creates missing templates and reuses existing ones
+    // * Importing again skips the imported workouts
+    // * Non existing exercise rolls everything back, including the created exercises
+
+    fn imported_workout(key: &str, template_name: &str, day: u32, exercise_ids: &[Uuid]) -> ImportedWorkout {
+        ImportedWorkout {
+            import_key: key.to_string(),
+            template_name: template_name.to_string(),
+            date: NaiveDate::from_ymd_opt(2023, 1, day).unwrap(),
+            elements: exercise_ids.iter().enumerate().map(|(i, &exercise_id)| NewWkExecutionElement {
+                exercise_id,
+                position: i as i16,
+                exercise_number: i as i16,
+                reps: 5,
+                ..Default::default()
+            }).collect(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_insert_imported_workouts_templates() {
+        let (connector, _container) = pg_container().await;
+        let user_id = insert_helper(1, Items::Users, &connector, None).await[0];
+        let exercise_ids = insert_helper(2, Items::Exercises, &connector, None).await;
+        let existing = insert_workout_template(&NewWorkoutTemplate { user_id, name: "Legs".to_string(), ..Default::default() }, &connector).await.unwrap();
+
+        let workouts = vec![
+            imported_workout("a", "Push", 3, &exercise_ids),
+            imported_workout("b", "Push", 1, &exercise_ids[..1]),
+            imported_workout("c", "Legs", 2, &exercise_ids[1..]),
+        ];
+        let inserted = insert_imported_workouts(user_id, "Strong", &HashMap::new(), workouts, &connector).await.unwrap();
+        assert_eq!(inserted, 3);
+
+        let export = select_user_export(user_id, &connector).await.unwrap();
+        assert_eq!(export.workout_templates.len(), 2);
+        let push = export.workout_templates.iter().find(|template| template.name == "Push").unwrap();
+        assert_eq!(push.date_created, NaiveDate::from_ymd_opt(2023, 1, 1).unwrap());
+        assert_eq!(push.description, Some("Imported from Strong".to_string()));
+        assert_eq!(export.workout_executions.iter().filter(|execution| execution.workout_template_id == existing.id).count(), 1);
+        assert_eq!(export.workout_executions.len(), 3);
+        assert_eq!(export.wk_execution_elements.len(), 4);
+    }
+
+    #[tokio::test]
+    async fn test_insert_imported_workouts_idempotent() {
+        let (connector, _container) = pg_container().await;
+        let user_id = insert_helper(1, Items::Users, &connector, None).await[0];
+        let exercise_ids = insert_helper(1, Items::Exercises, &connector, None).await;
+
+        let inserted = insert_imported_workouts(user_id, "Hevy", &HashMap::new(), vec![imported_workout("a", "Push", 1, &exercise_ids)], &connector).await.unwrap();
+        assert_eq!(inserted, 1);
+        let workouts = vec![
+            imported_workout("a", "Push", 1, &exercise_ids),
+            imported_workout("b", "Push", 2, &exercise_ids),
+        ];
+        let inserted = insert_imported_workouts(user_id, "Hevy", &HashMap::new(), workouts, &connector).await.unwrap();
+        assert_eq!(inserted, 1);
+
+        let keys = vec!["a".to_string(), "b".to_string(), "c".to_string()];
+        let imported = select_imported_keys(user_id, &keys, &connector).await.unwrap();
+        assert_eq!(imported, HashSet::from(["a".to_string(), "b".to_string()]));
+        // Keys belong to each user
+        let other_id = insert_helper(1, Items::Users, &connector, None).await[0];
+        assert!(select_imported_keys(other_id, &keys, &connector).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_insert_imported_workouts_rollback() {
+        let (connector, _container) = pg_container().await;
+        let user_id = insert_helper(1, Items::Users, &connector, None).await[0];
+        let exercise_ids = insert_helper(1, Items::Exercises, &connector, None).await;
+
+        let new_exercises = HashMap::from([("Zercher Carry".to_string(), Uuid::new_v4())]);
+
+        let workouts = vec![
+            imported_workout("a", "Push", 1, &[exercise_ids[0], new_exercises["Zercher Carry"]]),
+            imported_workout("b", "Push", 2, &[Uuid::new_v4()]),
+        ];
+        let res = insert_imported_workouts(user_id, "Strong", &new_exercises, workouts, &connector).await;
+        assert!(matches!(res, Err(DBError::ItemNotFound(_))));
+        assert!(matches!(lookup_exercise(new_exercises["Zercher Carry"], user_id, &connector).await, Err(DBError::ItemNotFound(_))));
+
+        let export = select_user_export(user_id, &connector).await.unwrap();
+        assert!(export.workout_templates.is_empty());
+        assert!(export.workout_executions.is_empty());
+        assert!(select_imported_keys(user_id, &["a".to_string()], &connector).await.unwrap().is_empty());
+    }
+}
@@ -0,0 +1,261 @@
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+use diesel::result::{Error, DatabaseErrorKind};
+use diesel_async::{AsyncConnection, RunQueryDsl};
+use diesel_async::scoped_futures::ScopedFutureExt;
+use uuid::Uuid;
+
+use crate::schema::{wkexecutionelements, workoutexecutions, workouttemplates};
+use crate::lib::models::wk_execution_elements_models::{NewWkExecutionElement, WkExecutionElement};
+use crate::lib::models::workout_execution_models::{NewWorkoutSession, WorkoutExecution, ABANDONED, COMPLETED, IN_PROGRESS};
+use crate::lib::errors::DBError;
+
+use super::DBConnector;
+
+const SESSION_NOT_FOUND: &str = "No workout in progress with the corresponding id";
+
+/// Start a session on a template of the user
+pub async fn insert_workout_session(new_session: &NewWorkoutSession, user_id: Uuid, connector: &DBConnector) -> Result<WorkoutExecution, DBError> {
+
+    let mut conn = connector.rds_connection().await?;
+    conn.transaction::<WorkoutExecution, Error, _>(|conn| async move {
+        workouttemplates::table
+            .filter(workouttemplates::id.eq(new_session.workout_template_id))
+            .filter(workouttemplates::user_id.eq(user_id))
+            .select(workouttemplates::id)
+            .first::<Uuid>(conn)
+            .await?;
+        diesel::insert_into(workoutexecutions::table)
+            .values(new_session)
+            .returning(WorkoutExecution::as_returning())
+            .get_result(conn)
+            .await
+    }.scope_boxed())
+    .await
+    .map_err(|error| match error {
+        Error::NotFound | Error::DatabaseError(DatabaseErrorKind::ForeignKeyViolation, _) => {
+            DBError::ItemNotFound("No template exists with the corresponding id".to_string())
+        },
+        _ => DBError::OperationError(error.to_string()),
+    })
+}
+
+/// Log a set of a session of the user in progress, replacing whatever was logged at its position
+/// * Keeps the session alive until `now`
+pub async fn upsert_session_set(new_set: &NewWkExecutionElement, user_id: Uuid, now: NaiveDateTime, connector: &DBConnector) -> Result<WkExecutionElement, DBError> {
+
+    let mut conn = connector.rds_connection().await?;
+    conn.transaction::<WkExecutionElement, Error, _>(|conn| async move {
+        // Locked so sets logged at once don't both take the same position
+        let owned_templates = workouttemplates::table
+            .filter(workouttemplates::user_id.eq(user_id))
+            .select(workouttemplates::id);
+        workoutexecutions::table
+            .filter(workoutexecutions::id.eq(new_set.workout_execution_id))
+            .filter(workoutexecutions::status.eq(IN_PROGRESS))
+            .filter(workoutexecutions::workout_template_id.eq_any(owned_templates))
+            .select(workoutexecutions::id)
+            .for_update()
+            .first::<Uuid>(conn)
+            .await?;
+        diesel::update(workoutexecutions::table.find(new_set.workout_execution_id))
+            .set(workoutexecutions::last_activity.eq(now))
+            .execute(conn)
+            .await?;
+
+        let updated = diesel::update(
+            wkexecutionelements::table
+                .filter(wkexecutionelements::workout_execution_id.eq(new_set.workout_execution_id))
+                .filter(wkexecutionelements::position.eq(new_set.position))
+        )
+        .set(new_set)
+        .returning(wkexecutionelements::all_columns)
+        .get_result::<WkExecutionElement>(conn)
+        .await
+        .optional()?;
+        match updated {
+            Some(set) => Ok(set),
+            None => {
+                diesel::insert_into(wkexecutionelements::table)
+                    .values(new_set)
+                    .returning(wkexecutionelements::all_columns)
+                    .get_result::<WkExecutionElement>(conn)
+                    .await
+            }
+        }
+    }.scope_boxed())
+    .await
+    .map_err(|error| match error {
+        Error::NotFound => DBError::ItemNotFound(SESSION_NOT_FOUND.to_string()),
+        Error::DatabaseError(DatabaseErrorKind::ForeignKeyViolation, _) => {
+            DBError::ItemNotFound("No exercise exists with the corresponding id".to_string())
+        },
+        _ => DBError::OperationError(error.to_string()),
+    })
+}
+
+/// Session of the user in progress with its sets, in the order they were logged
+pub async fn select_workout_session(execution_id: Uuid, user_id: Uuid, connector: &DBConnector) -> Result<(WorkoutExecution, Vec<WkExecutionElement>), DBError> {
+
+    let mut conn = connector.rds_connection().await?;
+    conn.build_transaction()
+        .read_only()
+        .repeatable_read()
+        .run::<(WorkoutExecution, Vec<WkExecutionElement>), Error, _>(|conn| async move {
+            let session = workoutexecutions::table
+                .inner_join(workouttemplates::table)
+                .filter(workoutexecutions::id.eq(execution_id))
+                .filter(workoutexecutions::status.eq(IN_PROGRESS))
+                .filter(workouttemplates::user_id.eq(user_id))
+                .select(WorkoutExecution::as_select())
+                .first::<WorkoutExecution>(conn)
+                .await?;
+            let sets = wkexecutionelements::table
+                .filter(wkexecutionelements::workout_execution_id.eq(execution_id))
+                .order(wkexecutionelements::position)
+                .select(wkexecutionelements::all_columns)
+                .load::<WkExecutionElement>(conn)
+                .await?;
+            Ok((session, sets))
+        }.scope_boxed())
+        .await
+        .map_err(|error| match error {
+            Error::NotFound => DBError::ItemNotFound(SESSION_NOT_FOUND.to_string()),
+            _ => DBError::OperationError(error.to_string()),
+        })
+}
+
+/// Mark a session of the user as completed, as long as no set was logged since `last_activity`
+/// * Zero when it changed in the meantime, so what was checked is what gets saved
+pub async fn complete_workout_session(execution_id: Uuid, user_id: Uuid, survey: i16, last_activity: NaiveDateTime, connector: &DBConnector) -> Result<usize, DBError> {
+
+    let mut conn = connector.rds_connection().await?;
+    let owned_templates = workouttemplates::table
+        .filter(workouttemplates::user_id.eq(user_id))
+        .select(workouttemplates::id);
+    diesel::update(
+        workoutexecutions::table
+            .filter(workoutexecutions::id.eq(execution_id))
+            .filter(workoutexecutions::status.eq(IN_PROGRESS))
+            .filter(workoutexecutions::last_activity.eq(last_activity))
+            .filter(workoutexecutions::workout_template_id.eq_any(owned_templates))
+    )
+    .set((workoutexecutions::status.eq(COMPLETED), workoutexecutions::survey.eq(survey)))
+    .execute(&mut conn)
+    .await
+    .map_err(|error| DBError::OperationError(error.to_string()))
+}
+
+/// Discard a session of the user in progress or abandoned, with its sets
+pub async fn delete_workout_session(execution_id: Uuid, user_id: Uuid, connector: &DBConnector) -> Result<usize, DBError> {
+
+    let mut conn = connector.rds_connection().await?;
+    let owned_templates = workouttemplates::table
+        .filter(workouttemplates::user_id.eq(user_id))
+        .select(workouttemplates::id);
+    diesel::delete(
+        workoutexecutions::table
+            .filter(workoutexecutions::id.eq(execution_id))
+            .filter(workoutexecutions::status.ne(COMPLETED))
+            .filter(workoutexecutions::workout_template_id.eq_any(owned_templates))
+    )
+    .execute(&mut conn)
+    .await
+    .map_err(|error| DBError::OperationError(error.to_string()))
+}
+
+/// Give up on the sessions in progress without any set logged after `until`
+pub async fn abandon_stale_sessions(until: NaiveDateTime, connector: &DBConnector) -> Result<usize, DBError> {
+
+    let mut conn = connector.rds_connection().await?;
+    diesel::update(
+        workoutexecutions::table
+            .filter(workoutexecutions::status.eq(IN_PROGRESS))
+            .filter(workoutexecutions::last_activity.le(until))
+    )
+    .set(workoutexecutions::status.eq(ABANDONED))
+    .execute(&mut conn)
+    .await
+    .map_err(|error| DBError::OperationError(error.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, NaiveDate, Utc};
+    use crate::lib::utils::tests::{pg_container, insert_helper, Items};
+    use crate::lib::db::workout_executions_db::lookup_workout_execution;
+    use crate::lib::db::workout_templates_db::lookup_workout_template;
+
+    // TEST CASES
+    // * Start only on the user's templates
+    // * Log and replace sets, only while in progress
+    // * Complete unless a set was logged in the meantime
+    // * Abandon stale sessions, discard them
+
+    fn set(workout_execution_id: Uuid, exercise_id: Uuid, position: i16, reps: i16) -> NewWkExecutionElement {
+        NewWkExecutionElement { workout_execution_id, exercise_id, position, set_number: position, reps, weight: Some(60.0), time: 30, ..Default::default() }
+    }
+
+    fn date() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2024, 11, 11).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_workout_session_lifecycle() {
+        let (connector, _container) = pg_container().await;
+        let template_id = insert_helper(1, Items::WkTemplates, &connector, None).await[0];
+        let user_id = lookup_workout_template(template_id, &connector).await.unwrap().user_id;
+        let stranger = insert_helper(1, Items::Users, &connector, None).await[0];
+        let exercise_id = insert_helper(1, Items::Exercises, &connector, None).await[0];
+        let started = Utc::now().naive_utc();
+
+        let res = insert_workout_session(&NewWorkoutSession::new(template_id, date(), None, started), stranger, &connector).await;
+        assert!(matches!(res, Err(DBError::ItemNotFound(_))));
+        let session = insert_workout_session(&NewWorkoutSession::new(template_id, date(), None, started), user_id, &connector).await.unwrap();
+        assert_eq!(session.status, IN_PROGRESS);
+
+        let res = upsert_session_set(&set(session.id, exercise_id, 0, 5), stranger, started, &connector).await;
+        assert!(matches!(res, Err(DBError::ItemNotFound(_))));
+        let first = upsert_session_set(&set(session.id, exercise_id, 0, 5), user_id, started, &connector).await.unwrap();
+        let replaced = upsert_session_set(&set(session.id, exercise_id, 0, 6), user_id, started, &connector).await.unwrap();
+        assert_eq!((replaced.id, replaced.reps), (first.id, 6));
+        let res = upsert_session_set(&set(session.id, Uuid::new_v4(), 1, 5), user_id, started, &connector).await;
+        assert!(matches!(res, Err(DBError::ItemNotFound(_))));
+
+        let logged = started + Duration::minutes(3);
+        upsert_session_set(&set(session.id, exercise_id, 1, 5), user_id, logged, &connector).await.unwrap();
+        let (session, sets) = select_workout_session(session.id, user_id, &connector).await.unwrap();
+        assert_eq!(sets.iter().map(|set| (set.position, set.reps)).collect::<Vec<(i16, i16)>>(), vec![(0, 6), (1, 5)]);
+
+        assert_eq!(complete_workout_session(session.id, user_id, 3, started, &connector).await.unwrap(), 0);
+        let last_activity = session.last_activity.unwrap();
+        assert_eq!(complete_workout_session(session.id, stranger, 3, last_activity, &connector).await.unwrap(), 0);
+        assert_eq!(complete_workout_session(session.id, user_id, 3, last_activity, &connector).await.unwrap(), 1);
+        let completed = lookup_workout_execution(session.id, &connector).await.unwrap();
+        assert_eq!((completed.status, completed.survey), (COMPLETED, 3));
+
+        let res = upsert_session_set(&set(session.id, exercise_id, 2, 5), user_id, logged, &connector).await;
+        assert!(matches!(res, Err(DBError::ItemNotFound(_))));
+        assert!(matches!(select_workout_session(session.id, user_id, &connector).await, Err(DBError::ItemNotFound(_))));
+        assert_eq!(delete_workout_session(session.id, user_id, &connector).await.unwrap(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_abandon_stale_sessions() {
+        let (connector, _container) = pg_container().await;
+        let template_id = insert_helper(1, Items::WkTemplates, &connector, None).await[0];
+        let user_id = lookup_workout_template(template_id, &connector).await.unwrap().user_id;
+        let now = Utc::now().naive_utc();
+        let stale = insert_workout_session(&NewWorkoutSession::new(template_id, date(), None, now - Duration::hours(7)), user_id, &connector).await.unwrap();
+        let active = insert_workout_session(&NewWorkoutSession::new(template_id, date(), None, now), user_id, &connector).await.unwrap();
+
+        assert_eq!(abandon_stale_sessions(now - Duration::hours(6), &connector).await.unwrap(), 1);
+        assert_eq!(lookup_workout_execution(stale.id, &connector).await.unwrap().status, ABANDONED);
+        assert_eq!(lookup_workout_execution(active.id, &connector).await.unwrap().status, IN_PROGRESS);
+
+        assert_eq!(delete_workout_session(stale.id, Uuid::new_v4(), &connector).await.unwrap(), 0);
+        assert_eq!(delete_workout_session(stale.id, user_id, &connector).await.unwrap(), 1);
+        assert!(lookup_workout_execution(stale.id, &connector).await.is_err());
+    }
+}
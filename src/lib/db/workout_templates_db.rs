@@ -1,10 +1,13 @@
 use diesel::prelude::*;
 use diesel::result::{Error, DatabaseErrorKind};
-use diesel_async::RunQueryDsl;
+use diesel_async::{AsyncConnection, RunQueryDsl};
+use diesel_async::scoped_futures::ScopedFutureExt;
 use uuid::Uuid;
 
 use crate::schema::workouttemplates::dsl::*;
-use crate::lib::models::workout_templates_models::{NewWorkoutTemplate, WorkoutTemplate};
+use crate::schema::wktemplateelements;
+use crate::lib::models::workout_templates_models::{NewWorkoutTemplate, WkTemplateWithElements, WorkoutTemplate};
+use crate::lib::models::wk_template_elements_models::{NewWkTemplateElement, WkTemplateElement};
 use crate::lib::errors::DBError;
 
 use super::DBConnector;
@@ -64,11 +67,66 @@ pub async fn delete_workout_template(user_uuid: Uuid, template_id: uuid::Uuid, c
 }
 
 
+/// Copies a template of `owner_id` with its elements into the templates of `new_owner_id`.
+///
+/// The copy is dated today and gets new ids, progression rules stay with the original.
+pub async fn copy_workout_template(template_id: Uuid, owner_id: Uuid, new_owner_id: Uuid, connector: &DBConnector) -> Result<WkTemplateWithElements, DBError> {
+
+    let mut conn = connector.rds_connection().await?;
+    conn.transaction::<WkTemplateWithElements, Error, _>(|conn| async move {
+        let original = workouttemplates
+            .filter(id.eq(template_id))
+            .filter(user_id.eq(owner_id))
+            .first::<WorkoutTemplate>(conn)
+            .await?;
+        let elements = wktemplateelements::table
+            .filter(wktemplateelements::workout_template_id.eq(template_id))
+            .order(wktemplateelements::position)
+            .load::<WkTemplateElement>(conn)
+            .await?;
+
+        let new_template = NewWorkoutTemplate { user_id: new_owner_id, name: original.name, description: original.description, ..Default::default() };
+        let workout_template = diesel::insert_into(workouttemplates)
+            .values(&new_template)
+            .returning(WorkoutTemplate::as_returning())
+            .get_result(conn)
+            .await?;
+        if elements.is_empty() {
+            return Ok(WkTemplateWithElements { workout_template, elements });
+        }
+        let new_elements: Vec<NewWkTemplateElement> = elements.into_iter()
+            .map(|element| NewWkTemplateElement {
+                id: None,
+                workout_template_id: workout_template.id,
+                exercise_id: element.exercise_id,
+                position: element.position,
+                reps: element.reps,
+                sets: element.sets,
+                weight: element.weight,
+                rest: element.rest,
+                super_set: element.super_set,
+            })
+            .collect();
+        let elements = diesel::insert_into(wktemplateelements::table)
+            .values(&new_elements)
+            .get_results::<WkTemplateElement>(conn)
+            .await?;
+        Ok(WkTemplateWithElements { workout_template, elements })
+    }.scope_boxed())
+    .await
+    .map_err(|error| match error {
+        Error::NotFound => DBError::ItemNotFound("No template exists with the corresponding id".to_string()),
+        _ => DBError::OperationError(error.to_string()),
+    })
+}
+
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::lib::utils::tests::{pg_container, insert_helper, Items};
+    use crate::lib::db::wk_template_elements_db::{lookup_wk_template_element, select_wk_template_element_by_template};
+    use itertools::Itertools;
 
     // TEST CASES
     // * Insert wrong user id
@@ -76,6 +134,7 @@ mod tests {
     // * Lookup non existing
     // * Select by user none and multiple
     // * Delete success and non existing
+    // * Copy to another user, and from the wrong owner
 
     #[tokio::test]
     async fn test_insert_workout_template_wrong_user_id() {
@@ -184,4 +243,25 @@ mod tests {
         assert!(delete_res.is_ok());
         assert_eq!(delete_res.unwrap(), 0);
     }
+
+    #[tokio::test]
+    async fn test_copy_workout_template() {
+        let (connector, _container) = pg_container().await;
+        let element_ids = insert_helper(3, Items::WkTemplateElements, &connector, None).await;
+        let template_id = lookup_wk_template_element(element_ids[0], &connector).await.unwrap().workout_template_id;
+        let owner_id = lookup_workout_template(template_id, &connector).await.unwrap().user_id;
+        let new_owner_id = insert_helper(1, Items::Users, &connector, None).await[0];
+
+        let copy_res = copy_workout_template(template_id, new_owner_id, new_owner_id, &connector).await;
+        assert!(matches!(copy_res, Err(DBError::ItemNotFound(_))));
+
+        let copy = copy_workout_template(template_id, owner_id, new_owner_id, &connector).await.unwrap();
+        assert_ne!(copy.workout_template.id, template_id);
+        assert_eq!(copy.workout_template.user_id, new_owner_id);
+        assert_eq!(copy.elements.len(), 3);
+        assert!(copy.elements.iter().all(|element| element.workout_template_id == copy.workout_template.id && !element_ids.contains(&element.id)));
+        let original = select_wk_template_element_by_template(template_id, &connector).await.unwrap();
+        let exercises = |elements: &[WkTemplateElement]| elements.iter().map(|element| (element.position, element.exercise_id)).sorted().collect::<Vec<_>>();
+        assert_eq!(exercises(&copy.elements), exercises(&original));
+    }
 }
@@ -0,0 +1,171 @@
+use std::str::FromStr;
+use diesel::prelude::*;
+use uuid::Uuid;
+use chrono::{NaiveDate, Utc};
+use serde::{Serialize, Deserialize};
+use super::user_models::User;
+use super::unit_models::{InUnits, Units};
+use crate::schema::bodymeasurements;
+
+/// Body measurements of a user on a given day
+/// * Weight in kg, body fat in %, circumferences in cm, see `Units` for other units
+#[derive(Queryable, Identifiable, Associations, Selectable, Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[diesel(table_name = bodymeasurements)]
+#[diesel(belongs_to(User))]
+pub struct BodyMeasurement {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub date: NaiveDate,
+    pub weight: Option<f32>,
+    pub body_fat: Option<f32>,
+    pub waist: Option<f32>,
+    pub chest: Option<f32>,
+    pub arms: Option<f32>,
+    pub thighs: Option<f32>,
+    pub hips: Option<f32>,
+}
+
+impl InUnits for BodyMeasurement {
+    fn in_units(self, units: &Units) -> Self {
+        BodyMeasurement {
+            weight: self.weight.map(|kg| units.weight_from_kg(kg)),
+            waist: self.waist.map(|cm| units.length_from_cm(cm)),
+            chest: self.chest.map(|cm| units.length_from_cm(cm)),
+            arms: self.arms.map(|cm| units.length_from_cm(cm)),
+            thighs: self.thighs.map(|cm| units.length_from_cm(cm)),
+            hips: self.hips.map(|cm| units.length_from_cm(cm)),
+            ..self
+        }
+    }
+}
+
+#[derive(Insertable, Serialize, Deserialize, Debug)]
+#[diesel(table_name = bodymeasurements)]
+pub struct NewBodyMeasurement {
+    pub user_id: Uuid,
+    pub date: NaiveDate,
+    pub weight: Option<f32>,
+    pub body_fat: Option<f32>,
+    pub waist: Option<f32>,
+    pub chest: Option<f32>,
+    pub arms: Option<f32>,
+    pub thighs: Option<f32>,
+    pub hips: Option<f32>,
+}
+impl Default for NewBodyMeasurement {
+    fn default() -> Self {
+        NewBodyMeasurement {
+            user_id: Uuid::new_v4(),
+            date: Utc::now().date_naive(),
+            weight: None,
+            body_fat: None,
+            waist: None,
+            chest: None,
+            arms: None,
+            thighs: None,
+            hips: None,
+        }
+    }
+}
+
+#[derive(AsChangeset, Serialize, Deserialize, Default, Debug)]
+#[diesel(table_name = bodymeasurements)]
+pub struct UncompleteBodyMeasurement {
+    pub date: Option<NaiveDate>,
+    pub weight: Option<f32>,
+    pub body_fat: Option<f32>,
+    pub waist: Option<f32>,
+    pub chest: Option<f32>,
+    pub arms: Option<f32>,
+    pub thighs: Option<f32>,
+    pub hips: Option<f32>,
+}
+
+impl UncompleteBodyMeasurement {
+    /// True if no value was measured, the date doesn't count
+    pub fn is_empty(&self) -> bool {
+        self.values().iter().all(Option::is_none)
+    }
+
+    /// Values must be positive, body fat a percentage and the date can't be in the future
+    pub fn validate(&self) -> Result<(), String> {
+        if self.date.is_some_and(|date| date > Utc::now().date_naive()) {
+            return Err("Date can't be in the future".to_string());
+        }
+        if self.values().iter().flatten().any(|value| !value.is_finite() || *value <= 0.0) {
+            return Err("Measurements must be positive".to_string());
+        }
+        if self.body_fat.is_some_and(|body_fat| body_fat > 100.0) {
+            return Err("Body fat is a percentage, it can't be over 100".to_string());
+        }
+        Ok(())
+    }
+
+    /// Converts given values to kg and cm, as stored
+    pub fn to_metric(self, units: &Units) -> Self {
+        UncompleteBodyMeasurement {
+            weight: self.weight.map(|weight| units.weight_to_kg(weight)),
+            waist: self.waist.map(|length| units.length_to_cm(length)),
+            chest: self.chest.map(|length| units.length_to_cm(length)),
+            arms: self.arms.map(|length| units.length_to_cm(length)),
+            thighs: self.thighs.map(|length| units.length_to_cm(length)),
+            hips: self.hips.map(|length| units.length_to_cm(length)),
+            ..self
+        }
+    }
+
+    /// New measurement of the user, dated today unless a date was given
+    pub fn into_new(self, user_id: Uuid) -> NewBodyMeasurement {
+        NewBodyMeasurement {
+            user_id,
+            date: self.date.unwrap_or_else(|| Utc::now().date_naive()),
+            weight: self.weight,
+            body_fat: self.body_fat,
+            waist: self.waist,
+            chest: self.chest,
+            arms: self.arms,
+            thighs: self.thighs,
+            hips: self.hips,
+        }
+    }
+
+    fn values(&self) -> [Option<f32>; 7] {
+        [self.weight, self.body_fat, self.waist, self.chest, self.arms, self.thighs, self.hips]
+    }
+}
+
+/// Measured values, by their field name
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Metric {
+    Weight,
+    BodyFat,
+    Waist,
+    Chest,
+    Arms,
+    Thighs,
+    Hips,
+}
+
+impl Metric {
+    pub fn value(&self, measurement: &BodyMeasurement) -> Option<f32> {
+        match self {
+            Metric::Weight => measurement.weight,
+            Metric::BodyFat => measurement.body_fat,
+            Metric::Waist => measurement.waist,
+            Metric::Chest => measurement.chest,
+            Metric::Arms => measurement.arms,
+            Metric::Thighs => measurement.thighs,
+            Metric::Hips => measurement.hips,
+        }
+    }
+}
+
+impl FromStr for Metric {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        serde_json::from_value(serde_json::Value::String(s.to_string()))
+            .map_err(|_| format!("Unknown metric '{}'", s))
+    }
+}
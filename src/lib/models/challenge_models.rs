@@ -0,0 +1,184 @@
+use std::collections::HashSet;
+use chrono::{NaiveDate, NaiveDateTime};
+use diesel::prelude::*;
+use diesel::sql_types::{Float4, Uuid as SqlUuid, Varchar};
+use uuid::Uuid;
+use serde::{Serialize, Deserialize};
+use super::leaderboard_models::{rank_entries, LeaderboardEntry};
+use super::user_models::UserInfo;
+use super::unit_models::{InUnits, Units};
+use crate::schema::{challengeparticipants, challenges};
+
+// What participants compete on
+pub const TOTAL_VOLUME: i16 = 0;
+pub const WORKOUTS: i16 = 1;
+pub const EXERCISE_REPS: i16 = 2;
+
+pub const MAX_INVITED: usize = 100;
+
+/// Competition between invited users over a window of days, both ends included
+/// * `exercise_id` is only set for `EXERCISE_REPS`
+#[derive(Queryable, Identifiable, Selectable, Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[diesel(table_name = challenges)]
+pub struct Challenge {
+    pub id: Uuid,
+    pub creator_id: Uuid,
+    pub name: String,
+    pub metric: i16,
+    pub exercise_id: Option<Uuid>,
+    pub start_date: NaiveDate,
+    pub end_date: NaiveDate,
+    pub date_created: NaiveDateTime,
+}
+
+impl Challenge {
+    pub fn has_ended(&self, today: NaiveDate) -> bool {
+        self.end_date < today
+    }
+}
+
+#[derive(Insertable, Serialize, Deserialize, Debug)]
+#[diesel(table_name = challenges)]
+pub struct NewChallenge {
+    pub creator_id: Uuid,
+    pub name: String,
+    pub metric: i16,
+    pub exercise_id: Option<Uuid>,
+    pub start_date: NaiveDate,
+    pub end_date: NaiveDate,
+}
+
+/// Body of challenge creation requests
+#[derive(Serialize, Deserialize, Debug)]
+pub struct CreateChallengeRequest {
+    pub name: String,
+    pub metric: i16,
+    pub exercise_id: Option<Uuid>,
+    pub start_date: NaiveDate,
+    pub end_date: NaiveDate,
+    #[serde(default)]
+    pub invited: Vec<Uuid>,
+}
+
+impl CreateChallengeRequest {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.name.trim().is_empty() {
+            return Err("'name' can't be empty".to_string());
+        }
+        if ![TOTAL_VOLUME, WORKOUTS, EXERCISE_REPS].contains(&self.metric) {
+            return Err("Unknown 'metric'".to_string());
+        }
+        if (self.metric == EXERCISE_REPS) != self.exercise_id.is_some() {
+            return Err("'exercise_id' is needed for exercise reps challenges, and only for them".to_string());
+        }
+        if self.start_date > self.end_date {
+            return Err("'start_date' can't be after 'end_date'".to_string());
+        }
+        if self.invited.len() > MAX_INVITED {
+            return Err(format!("Can't invite more than {} users", MAX_INVITED));
+        }
+        Ok(())
+    }
+
+    /// Invited users without duplicates nor the creator, who takes part anyway
+    pub fn invitees(&self, creator_id: Uuid) -> Vec<Uuid> {
+        let mut seen = HashSet::from([creator_id]);
+        self.invited.iter().copied().filter(|user_id| seen.insert(*user_id)).collect()
+    }
+
+    pub fn to_new_challenge(&self, creator_id: Uuid) -> NewChallenge {
+        NewChallenge {
+            creator_id,
+            name: self.name.trim().to_string(),
+            metric: self.metric,
+            exercise_id: self.exercise_id,
+            start_date: self.start_date,
+            end_date: self.end_date,
+        }
+    }
+}
+
+/// Invited user, taking part once `joined`
+#[derive(Queryable, Selectable, Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[diesel(table_name = challengeparticipants)]
+pub struct ChallengeParticipant {
+    pub challenge_id: Uuid,
+    pub user_id: Uuid,
+    pub joined: bool,
+    pub invited_at: NaiveDateTime,
+}
+
+#[derive(Insertable, Serialize, Deserialize, Debug)]
+#[diesel(table_name = challengeparticipants)]
+pub struct NewChallengeParticipant {
+    pub challenge_id: Uuid,
+    pub user_id: Uuid,
+    pub joined: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct ChallengeWithParticipants {
+    #[serde(flatten)]
+    pub challenge: Challenge,
+    pub participants: Vec<ChallengeParticipant>,
+}
+
+/// Page of the challenges a user created or was invited to, latest first
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct ChallengeList {
+    pub challenges: Vec<Challenge>,
+    pub next_offset: Option<i64>,
+}
+
+impl ChallengeList {
+    /// Builds a page from up to `limit + 1` challenges, the extra one only tells there is a next page
+    pub fn page(mut challenges: Vec<Challenge>, limit: i64, offset: i64) -> ChallengeList {
+        let next_offset = (challenges.len() as i64 > limit).then_some(offset + limit);
+        challenges.truncate(limit as usize);
+        ChallengeList { challenges, next_offset }
+    }
+}
+
+/// Progress of a participant, as computed by the database
+#[derive(QueryableByName, Debug)]
+pub struct ChallengeProgress {
+    #[diesel(sql_type = SqlUuid)]
+    pub id: Uuid,
+    #[diesel(sql_type = Varchar)]
+    pub username: String,
+    #[diesel(sql_type = Varchar)]
+    pub display_name: String,
+    #[diesel(sql_type = Float4)]
+    pub progress: f32,
+}
+
+/// Ranking of the joined participants, best first
+/// * Once `finished` the standings are the final results
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct ChallengeStandings {
+    pub challenge: Challenge,
+    pub finished: bool,
+    pub standings: Vec<LeaderboardEntry>,
+}
+
+impl ChallengeStandings {
+    pub fn rank(challenge: Challenge, finished: bool, progress: Vec<ChallengeProgress>) -> ChallengeStandings {
+        let scored = progress.into_iter()
+            .map(|row| (UserInfo { id: row.id, username: row.username, display_name: row.display_name }, row.progress))
+            .collect();
+        ChallengeStandings { challenge, finished, standings: rank_entries(scored) }
+    }
+}
+
+/// Volume in the client's unit, counts stay as they are
+impl InUnits for ChallengeStandings {
+    fn in_units(self, units: &Units) -> Self {
+        if self.challenge.metric != TOTAL_VOLUME {
+            return self;
+        }
+        let standings = self.standings.into_iter()
+            .map(|entry| LeaderboardEntry { score: units.weight_from_kg(entry.score), ..entry })
+            .collect();
+        ChallengeStandings { standings, ..self }
+    }
+}
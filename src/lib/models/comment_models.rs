@@ -0,0 +1,65 @@
+use diesel::prelude::*;
+use uuid::Uuid;
+use chrono::NaiveDateTime;
+use serde::{Serialize, Deserialize};
+use super::user_models::UserInfo;
+use crate::schema::comments;
+
+pub const MAX_COMMENT_LENGTH: usize = 1000;
+
+/// Comment left on a workout execution
+/// * `date_edited` is only set once the author changes its content
+#[derive(Queryable, Identifiable, Selectable, Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[diesel(table_name = comments)]
+pub struct Comment {
+    pub id: Uuid,
+    pub workout_execution_id: Uuid,
+    pub user_id: Uuid,
+    pub content: String,
+    pub date_created: NaiveDateTime,
+    pub date_edited: Option<NaiveDateTime>,
+}
+
+#[derive(Insertable, Serialize, Deserialize, Debug)]
+#[diesel(table_name = comments)]
+pub struct NewComment {
+    pub workout_execution_id: Uuid,
+    pub user_id: Uuid,
+    pub content: String,
+}
+
+/// Body of comment creation and edition requests
+#[derive(Serialize, Deserialize, Debug)]
+pub struct CommentContent {
+    pub content: String,
+}
+
+impl CommentContent {
+    pub fn is_valid(&self) -> bool {
+        !self.content.trim().is_empty() && self.content.chars().count() <= MAX_COMMENT_LENGTH
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct CommentWithAuthor {
+    #[serde(flatten)]
+    pub comment: Comment,
+    pub author: UserInfo,
+}
+
+/// Page of the comments of an execution, oldest first
+/// * `next_offset` is only set if there are more comments after this page
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct CommentList {
+    pub comments: Vec<CommentWithAuthor>,
+    pub next_offset: Option<i64>,
+}
+
+impl CommentList {
+    /// Builds a page from up to `limit + 1` comments, the extra one only tells there is a next page
+    pub fn page(mut comments: Vec<CommentWithAuthor>, limit: i64, offset: i64) -> CommentList {
+        let next_offset = (comments.len() as i64 > limit).then_some(offset + limit);
+        comments.truncate(limit as usize);
+        CommentList { comments, next_offset }
+    }
+}
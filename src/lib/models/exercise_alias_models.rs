@@ -0,0 +1,25 @@
+use diesel::prelude::*;
+use uuid::Uuid;
+use serde::{Serialize, Deserialize};
+use crate::schema::exercisealiases;
+
+#[derive(Queryable, Identifiable, Selectable, Serialize, Deserialize, Debug)]
+#[diesel(table_name = exercisealiases)]
+pub struct ExerciseAlias {
+    pub id: Uuid,
+    pub exercise_id: Uuid,
+    pub locale: String,
+    pub name: String,
+    pub preferred: bool,
+}
+
+#[derive(Insertable, Serialize, Deserialize, Debug)]
+#[diesel(table_name = exercisealiases)]
+pub struct NewExerciseAlias {
+    // PREFERRED aliases are returned as the exercise name for their locale
+    pub exercise_id: Uuid,
+    pub locale: String,
+    pub name: String,
+    #[serde(default)]
+    pub preferred: bool,
+}
@@ -0,0 +1,78 @@
+use diesel::prelude::*;
+use uuid::Uuid;
+use serde::{Serialize, Deserialize};
+use crate::schema::{exerciseinstructions, exercisemedia};
+use super::exercise_models::Exercise;
+use super::unit_models::{InUnits, Units};
+
+#[derive(Queryable, Selectable, Insertable, AsChangeset, Serialize, Deserialize, Debug)]
+#[diesel(table_name = exerciseinstructions)]
+#[diesel(treat_none_as_null = true)]
+pub struct ExerciseInstructions {
+    pub exercise_id: Uuid,
+    pub description: Option<String>,
+    pub steps: Vec<String>,
+    pub cues: Vec<String>,
+    pub common_mistakes: Vec<String>,
+}
+
+/// Picture or video illustrating an exercise
+/// * MEDIA_TYPE: 0 image, 1 video
+#[derive(Queryable, Selectable, Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[diesel(table_name = exercisemedia)]
+pub struct ExerciseMedia {
+    pub media_type: i16,
+    pub url: String,
+    pub thumbnail_url: Option<String>,
+    pub width: Option<i32>,
+    pub height: Option<i32>,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = exercisemedia)]
+pub struct NewExerciseMedia<'a> {
+    pub exercise_id: Uuid,
+    pub position: i16,
+    pub media_type: i16,
+    pub url: &'a str,
+    pub thumbnail_url: Option<&'a str>,
+    pub width: Option<i32>,
+    pub height: Option<i32>,
+}
+
+/// Instructional content of an exercise, media in display order
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct ExerciseContent {
+    pub description: Option<String>,
+    #[serde(default)]
+    pub steps: Vec<String>,
+    #[serde(default)]
+    pub cues: Vec<String>,
+    #[serde(default)]
+    pub common_mistakes: Vec<String>,
+    #[serde(default)]
+    pub media: Vec<ExerciseMedia>,
+}
+
+/// Exercise with its instructional content
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ExerciseWithContent {
+    #[serde(flatten)]
+    pub exercise: Exercise,
+    pub content: ExerciseContent,
+}
+
+/// Full element optionally embedding the instructional content of its exercise
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct WithExerciseContent<T> {
+    #[serde(flatten)]
+    pub element: T,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub exercise_content: Option<ExerciseContent>,
+}
+
+impl<T: InUnits> InUnits for WithExerciseContent<T> {
+    fn in_units(self, units: &Units) -> Self {
+        WithExerciseContent { element: self.element.in_units(units), ..self }
+    }
+}
@@ -1,9 +1,10 @@
 use diesel::prelude::*;
+use diesel::sql_types::Text;
 use uuid::Uuid;
 use serde::{Serialize, Deserialize};
 use crate::schema::exercises;
 
-#[derive(Queryable, Identifiable, Selectable, Serialize, Deserialize, Debug)]
+#[derive(Queryable, QueryableByName, Identifiable, Selectable, Serialize, Deserialize, Debug)]
 #[diesel(table_name = exercises)]
 pub struct Exercise {
     pub id: Uuid,
@@ -12,10 +13,22 @@ pub struct Exercise {
     pub secondary_muscle_group: Option<i16>,
     pub necessary_equipment: Option<i16>,
     pub exercise_type: Option<i16>,
+    pub retired: bool,
+    pub owner_id: Option<Uuid>,
 }
 
 
-#[derive(Insertable, Deserialize)]
+/// Exercise matched to a name from another app, as computed by the database
+#[derive(QueryableByName, Debug)]
+pub struct ExerciseMatch {
+    #[diesel(sql_type = Text)]
+    pub term: String,
+    #[diesel(embed)]
+    pub exercise: Exercise,
+}
+
+
+#[derive(Insertable, Serialize, Deserialize)]
 #[diesel(table_name = exercises)]
 pub struct NewExercise {
     pub name: String,
@@ -23,6 +36,7 @@ pub struct NewExercise {
     pub secondary_muscle_group: Option<i16>,
     pub necessary_equipment: Option<i16>,
     pub exercise_type: Option<i16>,
+    pub owner_id: Option<Uuid>,
 }
 impl Default for NewExercise {
     fn default() -> Self {
@@ -31,7 +45,40 @@ impl Default for NewExercise {
             main_muscle_group: None,
             secondary_muscle_group: None,
             necessary_equipment: None,
-            exercise_type: None
+            exercise_type: None,
+            owner_id: None,
         }
     }
 }
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, AsChangeset)]
+#[diesel(table_name = exercises)]
+pub struct UncompleteExercise {
+    // RETIRED can only be changed by retiring the exercise
+    // OWNER cannot be changed
+    pub name: Option<String>,
+    pub main_muscle_group: Option<i16>,
+    pub secondary_muscle_group: Option<i16>,
+    pub necessary_equipment: Option<i16>,
+    pub exercise_type: Option<i16>,
+}
+
+/// Optional facets to narrow down exercise searches
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct ExerciseFilters {
+    pub main_muscle_group: Option<i16>,
+    pub necessary_equipment: Option<i16>,
+    pub exercise_type: Option<i16>,
+}
+
+/// Catalog exercise as kept in the import/export files, matched by name
+#[derive(Queryable, Selectable, Insertable, AsChangeset, Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[diesel(table_name = exercises)]
+#[diesel(treat_none_as_null = true)]
+pub struct CatalogEntry {
+    pub name: String,
+    pub main_muscle_group: Option<i16>,
+    pub secondary_muscle_group: Option<i16>,
+    pub necessary_equipment: Option<i16>,
+    pub exercise_type: Option<i16>,
+}
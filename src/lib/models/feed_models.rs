@@ -0,0 +1,81 @@
+use chrono::NaiveDate;
+use diesel::prelude::*;
+use diesel::sql_types::{BigInt, Uuid as SqlUuid};
+use uuid::Uuid;
+use serde::{Serialize, Deserialize};
+use super::user_models::UserInfo;
+use super::workout_execution_models::WorkoutExecution;
+use super::reaction_models::ReactionCount;
+use super::unit_models::{InUnits, Units};
+
+/// What a followed user did in a workout execution
+/// * Duration in seconds, sets and rest included
+/// * Volume is the sum of reps times weight, in kg
+/// * PRs are exercises lifted heavier than in any earlier execution of the author
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Default)]
+pub struct ExecutionSummary {
+    pub template_name: String,
+    pub duration: i64,
+    pub sets: i64,
+    pub volume: f32,
+    pub prs: i64,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct FeedItem {
+    pub author: UserInfo,
+    pub workout_execution: WorkoutExecution,
+    pub summary: ExecutionSummary,
+    pub reactions: Vec<ReactionCount>,
+}
+
+impl InUnits for FeedItem {
+    fn in_units(self, units: &Units) -> Self {
+        FeedItem {
+            summary: ExecutionSummary { volume: units.weight_from_kg(self.summary.volume), ..self.summary },
+            ..self
+        }
+    }
+}
+
+/// Page of the feed, newest first
+/// * `next_cursor` is only set if there are older executions, pass it as `before` to get them
+#[derive(Serialize, Deserialize)]
+pub struct Feed {
+    pub items: Vec<FeedItem>,
+    pub next_cursor: Option<String>,
+}
+
+/// Position in the feed, the date and id of the last execution seen
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FeedCursor {
+    pub date: NaiveDate,
+    pub id: Uuid,
+}
+
+impl std::fmt::Display for FeedCursor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}_{}", self.date, self.id)
+    }
+}
+
+impl std::str::FromStr for FeedCursor {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (date, id) = s.split_once('_').ok_or_else(|| format!("Invalid cursor '{}'", s))?;
+        Ok(FeedCursor {
+            date: NaiveDate::parse_from_str(date, "%Y-%m-%d").map_err(|_| format!("Invalid cursor '{}'", s))?,
+            id: Uuid::parse_str(id).map_err(|_| format!("Invalid cursor '{}'", s))?,
+        })
+    }
+}
+
+/// Number of PRs of an execution, as computed by the database
+#[derive(QueryableByName, Debug)]
+pub struct ExecutionPrs {
+    #[diesel(sql_type = SqlUuid)]
+    pub workout_execution_id: Uuid,
+    #[diesel(sql_type = BigInt)]
+    pub prs: i64,
+}
@@ -0,0 +1,42 @@
+use diesel::prelude::*;
+use uuid::Uuid;
+use chrono::NaiveDateTime;
+use serde::{Serialize, Deserialize};
+use super::user_models::UserInfo;
+use crate::schema::follows;
+
+/// A user following another one
+/// * Not accepted while the followed profile is private and hasn't approved it
+#[derive(Queryable, Selectable, Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[diesel(table_name = follows)]
+pub struct Follow {
+    pub follower_id: Uuid,
+    pub followed_id: Uuid,
+    pub accepted: bool,
+    pub requested_at: NaiveDateTime,
+}
+
+#[derive(Insertable, Serialize, Deserialize, Debug)]
+#[diesel(table_name = follows)]
+pub struct NewFollow {
+    pub follower_id: Uuid,
+    pub followed_id: Uuid,
+    pub accepted: bool,
+}
+
+/// Page of followers, followed users or follow requests, most recent first
+/// * `next_offset` is only set if there are more users after this page
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct FollowList {
+    pub users: Vec<UserInfo>,
+    pub next_offset: Option<i64>,
+}
+
+impl FollowList {
+    /// Builds a page from up to `limit + 1` users, the extra one only tells there is a next page
+    pub fn page(mut users: Vec<UserInfo>, limit: i64, offset: i64) -> FollowList {
+        let next_offset = (users.len() as i64 > limit).then_some(offset + limit);
+        users.truncate(limit as usize);
+        FollowList { users, next_offset }
+    }
+}
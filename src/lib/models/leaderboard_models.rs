@@ -0,0 +1,162 @@
+use std::str::FromStr;
+use chrono::{Datelike, Duration, NaiveDate};
+use diesel::prelude::*;
+use diesel::sql_types::{Float4, Nullable, Uuid as SqlUuid, Varchar};
+use uuid::Uuid;
+use serde::{Serialize, Deserialize};
+use super::user_models::UserInfo;
+use super::unit_models::{InUnits, Units};
+
+/// What users are ranked by
+/// * Estimated 1RM uses the Epley formula on the best set ever of the exercise
+/// * Weekly metrics count executions dated within the week, Monday to Sunday
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum LeaderboardMetric {
+    OneRepMax,
+    WeeklyVolume,
+    WeeklyWorkouts,
+}
+
+impl LeaderboardMetric {
+    /// Whether scores are weights, in kg as stored
+    pub fn is_weight(&self) -> bool {
+        matches!(self, LeaderboardMetric::OneRepMax | LeaderboardMetric::WeeklyVolume)
+    }
+}
+
+/// Value of the `metric` query parameter
+impl FromStr for LeaderboardMetric {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "one_rep_max" => Ok(LeaderboardMetric::OneRepMax),
+            "weekly_volume" => Ok(LeaderboardMetric::WeeklyVolume),
+            "weekly_workouts" => Ok(LeaderboardMetric::WeeklyWorkouts),
+            _ => Err(format!("Unknown metric '{}', expected 'one_rep_max', 'weekly_volume' or 'weekly_workouts'", s)),
+        }
+    }
+}
+
+/// Monday of the week the date falls in
+pub fn week_start(date: NaiveDate) -> NaiveDate {
+    date - Duration::days(date.weekday().num_days_from_monday() as i64)
+}
+
+/// Score of a user, as computed by the database
+#[derive(QueryableByName, Debug)]
+pub struct LeaderboardScore {
+    #[diesel(sql_type = SqlUuid)]
+    pub id: Uuid,
+    #[diesel(sql_type = Varchar)]
+    pub username: String,
+    #[diesel(sql_type = Varchar)]
+    pub display_name: String,
+    #[diesel(sql_type = Nullable<Float4>)]
+    pub body_weight: Option<f32>,
+    #[diesel(sql_type = Float4)]
+    pub score: f32,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct LeaderboardEntry {
+    pub rank: i64,
+    pub user: UserInfo,
+    pub score: f32,
+}
+
+/// Ranking of a user and the people they follow, best first
+/// * Normalized scores are divided by each user's body weight, users without one are left out
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct Leaderboard {
+    pub metric: LeaderboardMetric,
+    pub exercise_id: Option<Uuid>,
+    pub week_start: Option<NaiveDate>,
+    pub normalized: bool,
+    pub entries: Vec<LeaderboardEntry>,
+}
+
+impl Leaderboard {
+    /// Ranks the scores, normalizing them first if asked to
+    pub fn rank(metric: LeaderboardMetric, exercise_id: Option<Uuid>, week_start: Option<NaiveDate>, normalized: bool, scores: Vec<LeaderboardScore>) -> Leaderboard {
+        let scored: Vec<(UserInfo, f32)> = scores.into_iter()
+            .filter_map(|row| {
+                let score = match normalized {
+                    true => row.score / row.body_weight.filter(|weight| *weight > 0.0)?,
+                    false => row.score,
+                };
+                Some((UserInfo { id: row.id, username: row.username, display_name: row.display_name }, score))
+            })
+            .collect();
+        Leaderboard { metric, exercise_id, week_start, normalized, entries: rank_entries(scored) }
+    }
+}
+
+/// Sorts users by score, best first, tied users share a rank and the next one skips as many
+pub fn rank_entries(mut scored: Vec<(UserInfo, f32)>) -> Vec<LeaderboardEntry> {
+    scored.sort_by(|(a_user, a), (b_user, b)| b.total_cmp(a).then_with(|| a_user.username.cmp(&b_user.username)));
+
+    let mut entries: Vec<LeaderboardEntry> = Vec::with_capacity(scored.len());
+    for (position, (user, score)) in scored.into_iter().enumerate() {
+        let rank = match entries.last() {
+            Some(previous) if previous.score == score => previous.rank,
+            _ => position as i64 + 1,
+        };
+        entries.push(LeaderboardEntry { rank, user, score });
+    }
+    entries
+}
+
+/// Weights in the client's unit, ratios to body weight stay as they are
+impl InUnits for Leaderboard {
+    fn in_units(self, units: &Units) -> Self {
+        if self.normalized || !self.metric.is_weight() {
+            return self;
+        }
+        let entries = self.entries.into_iter()
+            .map(|entry| LeaderboardEntry { score: units.weight_from_kg(entry.score), ..entry })
+            .collect();
+        Leaderboard { entries, ..self }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // TEST CASES
+    // * Parse metric
+    // * Week start
+    // * Ranking with ties and normalization
+
+    fn score(username: &str, body_weight: Option<f32>, score: f32) -> LeaderboardScore {
+        LeaderboardScore { id: Uuid::new_v4(), username: username.to_string(), display_name: username.to_string(), body_weight, score }
+    }
+
+    #[test]
+    fn test_metric_from_str() {
+        assert_eq!("weekly_volume".parse::<LeaderboardMetric>(), Ok(LeaderboardMetric::WeeklyVolume));
+        assert!("volume".parse::<LeaderboardMetric>().is_err());
+    }
+
+    #[test]
+    fn test_week_start() {
+        let monday = NaiveDate::from_ymd_opt(2024, 10, 21).unwrap();
+        assert_eq!(week_start(monday), monday);
+        assert_eq!(week_start(NaiveDate::from_ymd_opt(2024, 10, 27).unwrap()), monday);
+    }
+
+    #[test]
+    fn test_rank() {
+        let scores = vec![score("c", Some(100.0), 150.0), score("a", Some(50.0), 200.0), score("b", None, 150.0)];
+        let board = Leaderboard::rank(LeaderboardMetric::OneRepMax, None, None, false, scores);
+        let ranks: Vec<(i64, &str)> = board.entries.iter().map(|entry| (entry.rank, entry.user.username.as_str())).collect();
+        assert_eq!(ranks, vec![(1, "a"), (2, "b"), (2, "c")]);
+
+        let scores = vec![score("c", Some(100.0), 150.0), score("a", Some(50.0), 200.0), score("b", None, 150.0)];
+        let board = Leaderboard::rank(LeaderboardMetric::OneRepMax, None, None, true, scores);
+        let ranks: Vec<(i64, &str, f32)> = board.entries.iter().map(|entry| (entry.rank, entry.user.username.as_str(), entry.score)).collect();
+        assert_eq!(ranks, vec![(1, "a", 4.0), (2, "c", 1.5)]);
+    }
+}
@@ -1,9 +1,27 @@
 pub mod user_models;
+pub mod unit_models;
+pub mod user_export_models;
+pub mod body_measurement_models;
+pub mod follow_models;
+pub mod notification_models;
 
 pub mod exercise_models;
+pub mod exercise_alias_models;
+pub mod exercise_content_models;
 
 pub mod workout_templates_models;
 pub mod wk_template_elements_models;
+pub mod progression_models;
+pub mod program_models;
+pub mod schedule_models;
 
 pub mod workout_execution_models;
 pub mod wk_execution_elements_models;
+pub mod workout_draft_models;
+pub mod sync_models;
+pub mod workout_import_models;
+pub mod feed_models;
+pub mod leaderboard_models;
+pub mod challenge_models;
+pub mod comment_models;
+pub mod reaction_models;
@@ -0,0 +1,108 @@
+use diesel::prelude::*;
+use uuid::Uuid;
+use chrono::NaiveDateTime;
+use serde::{Serialize, Deserialize};
+use super::user_models::UserInfo;
+use crate::schema::{notifications, users};
+
+// Kinds of notification
+pub const NEW_FOLLOWER: i16 = 0;
+pub const COMMENT: i16 = 1;
+pub const REACTION: i16 = 2;
+pub const PR_ACHIEVED: i16 = 3;
+pub const TEMPLATE_SAVED: i16 = 4;
+
+/// Event shown in a user's inbox
+/// * `actor_id` is who caused it, none for the user's own achievements
+/// * Ids of what it is about depend on the kind, the rest stay empty
+#[derive(Queryable, Identifiable, Selectable, Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[diesel(table_name = notifications)]
+pub struct Notification {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub kind: i16,
+    pub actor_id: Option<Uuid>,
+    pub workout_execution_id: Option<Uuid>,
+    pub workout_template_id: Option<Uuid>,
+    pub comment_id: Option<Uuid>,
+    pub read: bool,
+    pub date_created: NaiveDateTime,
+}
+
+#[derive(Insertable, Serialize, Deserialize, Debug, Default)]
+#[diesel(table_name = notifications)]
+pub struct NewNotification {
+    pub user_id: Uuid,
+    pub kind: i16,
+    pub actor_id: Option<Uuid>,
+    pub workout_execution_id: Option<Uuid>,
+    pub workout_template_id: Option<Uuid>,
+    pub comment_id: Option<Uuid>,
+}
+
+/// Kinds of notification the user still wants, the rest are never written
+#[derive(Queryable, Selectable, Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[diesel(table_name = users)]
+pub struct NotificationSettings {
+    pub notify_new_followers: bool,
+    pub notify_comments: bool,
+    pub notify_reactions: bool,
+    pub notify_prs: bool,
+    pub notify_template_saves: bool,
+}
+
+impl NotificationSettings {
+    pub fn allows(&self, kind: i16) -> bool {
+        match kind {
+            NEW_FOLLOWER => self.notify_new_followers,
+            COMMENT => self.notify_comments,
+            REACTION => self.notify_reactions,
+            PR_ACHIEVED => self.notify_prs,
+            TEMPLATE_SAVED => self.notify_template_saves,
+            _ => false,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct NotificationWithActor {
+    #[serde(flatten)]
+    pub notification: Notification,
+    pub actor: Option<UserInfo>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct UnreadCount {
+    pub kind: i16,
+    pub count: i64,
+}
+
+/// Page of the inbox, newest first, with the unread counts of the whole inbox
+/// * `next_offset` is only set if there are more notifications after this page
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct NotificationInbox {
+    pub notifications: Vec<NotificationWithActor>,
+    pub unread: i64,
+    pub unread_by_kind: Vec<UnreadCount>,
+    pub next_offset: Option<i64>,
+}
+
+impl NotificationInbox {
+    /// Builds a page from up to `limit + 1` notifications, the extra one only tells there is a next page
+    pub fn page(mut notifications: Vec<NotificationWithActor>, unread_by_kind: Vec<UnreadCount>, limit: i64, offset: i64) -> NotificationInbox {
+        let next_offset = (notifications.len() as i64 > limit).then_some(offset + limit);
+        notifications.truncate(limit as usize);
+        NotificationInbox {
+            notifications,
+            unread: unread_by_kind.iter().map(|unread| unread.count).sum(),
+            unread_by_kind,
+            next_offset,
+        }
+    }
+}
+
+/// Body of bulk mark-as-read requests, every unread notification if no ids are given
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct MarkRead {
+    pub ids: Option<Vec<Uuid>>,
+}
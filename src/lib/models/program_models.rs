@@ -0,0 +1,323 @@
+use std::collections::HashSet;
+use chrono::{NaiveDate, NaiveDateTime};
+use diesel::prelude::*;
+use uuid::Uuid;
+use serde::{Serialize, Deserialize};
+use super::workout_templates_models::WkTemplateWithElements;
+use super::wk_template_elements_models::WkTemplateElement;
+use super::unit_models::{InUnits, Units};
+use crate::schema::{programenrolments, programs, programslots};
+
+pub const MAX_WEEKS: i16 = 52;
+pub const DAYS_PER_WEEK: i16 = 7;
+/// Intensity is a percentage of the template weights
+pub const MAX_INTENSITY: i16 = 200;
+
+/// Templates ordered across the weeks and days of a training block
+#[derive(Queryable, Identifiable, Selectable, Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[diesel(table_name = programs)]
+pub struct Program {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub name: String,
+    pub description: Option<String>,
+    pub weeks: i16,
+    pub date_created: NaiveDate,
+}
+
+#[derive(Insertable, Serialize, Deserialize, Debug)]
+#[diesel(table_name = programs)]
+pub struct NewProgram {
+    pub user_id: Uuid,
+    pub name: String,
+    pub description: Option<String>,
+    pub weeks: i16,
+}
+
+/// Template done on a `day` of a `week`, both starting at 1
+/// * `reps` and `intensity` override the template elements that week
+#[derive(Queryable, Identifiable, Selectable, Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[diesel(table_name = programslots)]
+pub struct ProgramSlot {
+    pub id: Uuid,
+    pub program_id: Uuid,
+    pub workout_template_id: Uuid,
+    pub week: i16,
+    pub day: i16,
+    pub reps: Option<i16>,
+    pub intensity: Option<i16>,
+}
+
+impl ProgramSlot {
+    /// Template elements with the overrides of the slot applied, weights still in kg
+    pub fn apply(&self, elements: Vec<WkTemplateElement>) -> Vec<WkTemplateElement> {
+        elements.into_iter().map(|element| WkTemplateElement {
+            reps: self.reps.unwrap_or(element.reps),
+            weight: match self.intensity {
+                Some(intensity) => element.weight.map(|kg| kg * intensity as f32 / 100.0),
+                None => element.weight,
+            },
+            ..element
+        }).collect()
+    }
+}
+
+#[derive(Insertable, Serialize, Deserialize, Debug)]
+#[diesel(table_name = programslots)]
+pub struct NewProgramSlot {
+    pub program_id: Uuid,
+    pub workout_template_id: Uuid,
+    pub week: i16,
+    pub day: i16,
+    pub reps: Option<i16>,
+    pub intensity: Option<i16>,
+}
+
+/// Body of program creation requests
+#[derive(Serialize, Deserialize, Debug)]
+pub struct CreateProgramRequest {
+    pub name: String,
+    pub description: Option<String>,
+    pub weeks: i16,
+    pub slots: Vec<ProgramSlotRequest>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ProgramSlotRequest {
+    pub workout_template_id: Uuid,
+    pub week: i16,
+    pub day: i16,
+    pub reps: Option<i16>,
+    pub intensity: Option<i16>,
+}
+
+impl CreateProgramRequest {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.name.trim().is_empty() || self.name.len() > 255 {
+            return Err("'name' must have between 1 and 255 characters".to_string());
+        }
+        if !(1..=MAX_WEEKS).contains(&self.weeks) {
+            return Err(format!("'weeks' must be between 1 and {}", MAX_WEEKS));
+        }
+        if self.slots.is_empty() {
+            return Err("There must be at least one slot in the program".to_string());
+        }
+        let mut days = HashSet::new();
+        for slot in &self.slots {
+            if !(1..=self.weeks).contains(&slot.week) || !(1..=DAYS_PER_WEEK).contains(&slot.day) {
+                return Err(format!("Slot weeks go from 1 to 'weeks' and days from 1 to {}", DAYS_PER_WEEK));
+            }
+            if !days.insert((slot.week, slot.day)) {
+                return Err("There can only be one slot per day".to_string());
+            }
+            if slot.reps.is_some_and(|reps| reps <= 0) || slot.intensity.is_some_and(|intensity| !(1..=MAX_INTENSITY).contains(&intensity)) {
+                return Err(format!("'reps' must be at least 1 and 'intensity' between 1 and {}", MAX_INTENSITY));
+            }
+        }
+        Ok(())
+    }
+
+    pub fn to_new_program(&self, user_id: Uuid) -> NewProgram {
+        NewProgram {
+            user_id,
+            name: self.name.trim().to_string(),
+            description: self.description.clone(),
+            weeks: self.weeks,
+        }
+    }
+}
+
+impl ProgramSlotRequest {
+    pub fn to_new_slot(&self, program_id: Uuid) -> NewProgramSlot {
+        NewProgramSlot {
+            program_id,
+            workout_template_id: self.workout_template_id,
+            week: self.week,
+            day: self.day,
+            reps: self.reps,
+            intensity: self.intensity,
+        }
+    }
+}
+
+/// Program with its slots, sorted by week and day
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct ProgramWithSlots {
+    #[serde(flatten)]
+    pub program: Program,
+    pub slots: Vec<ProgramSlot>,
+}
+
+/// Page of the programs of a user, latest first
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct ProgramList {
+    pub programs: Vec<Program>,
+    pub next_offset: Option<i64>,
+}
+
+impl ProgramList {
+    /// Builds a page from up to `limit + 1` programs, the extra one only tells there is a next page
+    pub fn page(mut programs: Vec<Program>, limit: i64, offset: i64) -> ProgramList {
+        let next_offset = (programs.len() as i64 > limit).then_some(offset + limit);
+        programs.truncate(limit as usize);
+        ProgramList { programs, next_offset }
+    }
+}
+
+/// Run of a program starting on `start_date`, active until `date_ended` is set
+#[derive(Queryable, Identifiable, Selectable, Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[diesel(table_name = programenrolments)]
+pub struct ProgramEnrolment {
+    pub id: Uuid,
+    pub program_id: Uuid,
+    pub user_id: Uuid,
+    pub start_date: NaiveDate,
+    pub date_ended: Option<NaiveDate>,
+    pub date_created: NaiveDateTime,
+}
+
+impl ProgramEnrolment {
+    /// Week and day of the program on the given date, None before it starts
+    pub fn position(&self, date: NaiveDate) -> Option<(i16, i16)> {
+        let days = (date - self.start_date).num_days();
+        if days < 0 {
+            return None;
+        }
+        let week = days / DAYS_PER_WEEK as i64 + 1;
+        let day = days % DAYS_PER_WEEK as i64 + 1;
+        Some((week.min(i16::MAX as i64) as i16, day as i16))
+    }
+}
+
+#[derive(Insertable, Serialize, Deserialize, Debug)]
+#[diesel(table_name = programenrolments)]
+pub struct NewProgramEnrolment {
+    pub program_id: Uuid,
+    pub user_id: Uuid,
+    pub start_date: NaiveDate,
+}
+
+/// Body of enrolment requests, starting today if no date is given
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct EnrolRequest {
+    pub start_date: Option<NaiveDate>,
+}
+
+/// What the active program has planned for a day
+/// * No slot on rest days, before the start or once all weeks are done
+/// * `workout_execution_id` is set once the slot has been done
+#[derive(Serialize, Deserialize)]
+pub struct TodaysWorkout {
+    pub program: Program,
+    pub enrolment: ProgramEnrolment,
+    pub week: Option<i16>,
+    pub day: Option<i16>,
+    pub finished: bool,
+    pub slot: Option<ProgramSlot>,
+    pub workout_template: Option<WkTemplateWithElements>,
+    pub workout_execution_id: Option<Uuid>,
+}
+
+impl InUnits for TodaysWorkout {
+    fn in_units(self, units: &Units) -> Self {
+        TodaysWorkout { workout_template: self.workout_template.map(|template| template.in_units(units)), ..self }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct SlotProgress {
+    #[serde(flatten)]
+    pub slot: ProgramSlot,
+    pub workout_execution_id: Option<Uuid>,
+}
+
+/// Slots of the latest enrolment in a program and which of them were done
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct ProgramProgress {
+    pub enrolment: ProgramEnrolment,
+    pub completed: usize,
+    pub total: usize,
+    pub slots: Vec<SlotProgress>,
+}
+
+impl ProgramProgress {
+    pub fn new(enrolment: ProgramEnrolment, slots: Vec<SlotProgress>) -> ProgramProgress {
+        let completed = slots.iter().filter(|slot| slot.workout_execution_id.is_some()).count();
+        ProgramProgress { enrolment, completed, total: slots.len(), slots }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // TEST CASES
+    // * Request validation
+    // * Week and day of a date
+    // * Overrides applied to template elements
+
+    fn slot(week: i16, day: i16) -> ProgramSlotRequest {
+        ProgramSlotRequest { workout_template_id: Uuid::new_v4(), week, day, reps: None, intensity: None }
+    }
+
+    fn valid_base() -> CreateProgramRequest {
+        CreateProgramRequest { name: "PPL".to_string(), description: None, weeks: 4, slots: vec![] }
+    }
+
+    #[test]
+    fn test_validate() {
+        let valid = CreateProgramRequest { slots: vec![slot(1, 1), slot(4, 7)], ..valid_base() };
+        assert!(valid.validate().is_ok());
+
+        let invalid = [
+            CreateProgramRequest { weeks: 0, slots: vec![slot(1, 1)], ..valid_base() },
+            CreateProgramRequest { slots: vec![], ..valid_base() },
+            CreateProgramRequest { slots: vec![slot(5, 1)], ..valid_base() },
+            CreateProgramRequest { slots: vec![slot(1, 8)], ..valid_base() },
+            CreateProgramRequest { slots: vec![slot(1, 1), slot(1, 1)], ..valid_base() },
+            CreateProgramRequest { slots: vec![ProgramSlotRequest { intensity: Some(0), ..slot(1, 1) }], ..valid_base() },
+            CreateProgramRequest { name: " ".to_string(), slots: vec![slot(1, 1)], ..valid_base() },
+        ];
+        for request in invalid {
+            assert!(request.validate().is_err());
+        }
+    }
+
+    #[test]
+    fn test_position() {
+        let start_date = NaiveDate::from_ymd_opt(2024, 11, 4).unwrap();
+        let enrolment = ProgramEnrolment {
+            id: Uuid::new_v4(),
+            program_id: Uuid::new_v4(),
+            user_id: Uuid::new_v4(),
+            start_date,
+            date_ended: None,
+            date_created: start_date.and_hms_opt(0, 0, 0).unwrap(),
+        };
+        assert_eq!(enrolment.position(start_date.pred_opt().unwrap()), None);
+        assert_eq!(enrolment.position(start_date), Some((1, 1)));
+        assert_eq!(enrolment.position(NaiveDate::from_ymd_opt(2024, 11, 10).unwrap()), Some((1, 7)));
+        assert_eq!(enrolment.position(NaiveDate::from_ymd_opt(2024, 11, 13).unwrap()), Some((2, 3)));
+    }
+
+    #[test]
+    fn test_apply_overrides() {
+        let element = WkTemplateElement {
+            id: Uuid::new_v4(),
+            workout_template_id: Uuid::new_v4(),
+            exercise_id: Uuid::new_v4(),
+            position: 0,
+            reps: 5,
+            sets: 3,
+            weight: Some(100.0),
+            rest: 120,
+            super_set: None,
+        };
+        let slot = ProgramSlot { id: Uuid::new_v4(), program_id: Uuid::new_v4(), workout_template_id: element.workout_template_id, week: 2, day: 1, reps: Some(3), intensity: Some(90) };
+        let applied = slot.apply(vec![element]);
+        assert_eq!((applied[0].reps, applied[0].weight, applied[0].sets), (3, Some(90.0), 3));
+
+        let untouched = ProgramSlot { reps: None, intensity: None, ..slot }.apply(applied);
+        assert_eq!((untouched[0].reps, untouched[0].weight), (3, Some(90.0)));
+    }
+}
@@ -0,0 +1,366 @@
+use std::collections::HashMap;
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+use itertools::Itertools;
+use uuid::Uuid;
+use serde::{Serialize, Deserialize};
+use super::wk_template_elements_models::WkTemplateElement;
+use super::wk_execution_elements_models::WkExecutionElement;
+use super::unit_models::{InUnits, Units};
+use crate::schema::progressionrules;
+
+// Kinds of progression
+pub const LINEAR: i16 = 0;
+pub const DOUBLE_PROGRESSION: i16 = 1;
+
+pub const DEFAULT_DELOAD_PERCENT: i16 = 10;
+pub const MAX_DELOAD_PERCENT: i16 = 50;
+/// Most failed sessions in a row a deload can wait for, and so the most executions looked at
+pub const MAX_DELOAD_AFTER: i16 = 10;
+
+/// How the targets of a template element move from one session to the next
+/// * `weight_increment` in kg
+/// * `min_reps` and `max_reps` are the rep range of double progression, unset for linear
+/// * After `deload_after` failed sessions in a row the weight drops by `deload_percent`, never if unset
+#[derive(Queryable, Identifiable, Selectable, Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[diesel(table_name = progressionrules)]
+#[diesel(primary_key(wk_template_element_id))]
+pub struct ProgressionRule {
+    pub wk_template_element_id: Uuid,
+    pub kind: i16,
+    pub weight_increment: f32,
+    pub min_reps: Option<i16>,
+    pub max_reps: Option<i16>,
+    pub deload_after: Option<i16>,
+    pub deload_percent: i16,
+    pub date_created: NaiveDateTime,
+}
+
+/// Also the changeset when a rule replaces the previous one, unset fields are cleared
+#[derive(Insertable, AsChangeset, Serialize, Deserialize, Debug)]
+#[diesel(table_name = progressionrules)]
+#[diesel(treat_none_as_null = true)]
+pub struct NewProgressionRule {
+    pub wk_template_element_id: Uuid,
+    pub kind: i16,
+    pub weight_increment: f32,
+    pub min_reps: Option<i16>,
+    pub max_reps: Option<i16>,
+    pub deload_after: Option<i16>,
+    pub deload_percent: i16,
+}
+
+/// Body of progression rule requests, `weight_increment` in the user's units
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ProgressionRuleRequest {
+    pub kind: i16,
+    pub weight_increment: f32,
+    pub min_reps: Option<i16>,
+    pub max_reps: Option<i16>,
+    pub deload_after: Option<i16>,
+    pub deload_percent: Option<i16>,
+}
+
+impl ProgressionRuleRequest {
+    pub fn validate(&self) -> Result<(), String> {
+        match (self.kind, self.min_reps, self.max_reps) {
+            (LINEAR, None, None) => (),
+            (LINEAR, _, _) => return Err("'min_reps' and 'max_reps' are only for double progression".to_string()),
+            (DOUBLE_PROGRESSION, Some(min_reps), Some(max_reps)) if 0 < min_reps && min_reps < max_reps => (),
+            (DOUBLE_PROGRESSION, _, _) => return Err("Double progression needs 'min_reps' at least 1 and below 'max_reps'".to_string()),
+            _ => return Err(format!("'kind' must be {} (linear) or {} (double progression)", LINEAR, DOUBLE_PROGRESSION)),
+        }
+        if self.weight_increment.is_nan() || self.weight_increment <= 0.0 {
+            return Err("'weight_increment' must be over 0".to_string());
+        }
+        if self.deload_after.is_some_and(|after| !(1..=MAX_DELOAD_AFTER).contains(&after)) {
+            return Err(format!("'deload_after' must be between 1 and {}", MAX_DELOAD_AFTER));
+        }
+        if self.deload_percent.is_some_and(|percent| !(1..=MAX_DELOAD_PERCENT).contains(&percent)) {
+            return Err(format!("'deload_percent' must be between 1 and {}", MAX_DELOAD_PERCENT));
+        }
+        Ok(())
+    }
+
+    pub fn to_new_rule(&self, wk_template_element_id: Uuid, units: &Units) -> NewProgressionRule {
+        NewProgressionRule {
+            wk_template_element_id,
+            kind: self.kind,
+            weight_increment: units.weight_to_kg(self.weight_increment),
+            min_reps: self.min_reps,
+            max_reps: self.max_reps,
+            deload_after: self.deload_after,
+            deload_percent: self.deload_percent.unwrap_or(DEFAULT_DELOAD_PERCENT),
+        }
+    }
+}
+
+impl InUnits for ProgressionRule {
+    fn in_units(self, units: &Units) -> Self {
+        ProgressionRule { weight_increment: units.weight_from_kg(self.weight_increment), ..self }
+    }
+}
+
+/// Why the next targets of an element are what they are
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Outcome {
+    /// The element has no rule, targets are the template's
+    NoRule,
+    /// The element wasn't done in any execution of the template yet
+    NoHistory,
+    Increase,
+    Hold,
+    Deload,
+}
+
+/// Targets of a template element for its next session
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct NextTarget {
+    pub wk_template_element_id: Uuid,
+    pub exercise_id: Uuid,
+    pub sets: i16,
+    pub reps: i16,
+    pub weight: Option<f32>,
+    pub outcome: Outcome,
+    pub failed_sessions: usize,
+    pub rule: Option<ProgressionRule>,
+}
+
+impl InUnits for NextTarget {
+    fn in_units(self, units: &Units) -> Self {
+        NextTarget {
+            weight: self.weight.map(|kg| units.load_from_kg(kg)),
+            rule: self.rule.map(|rule| rule.in_units(units)),
+            ..self
+        }
+    }
+}
+
+impl NextTarget {
+    /// Evaluates the rule of an element against its sets in past executions, most recent first.
+    ///
+    /// The weight of a session is the lightest one of its sets, so a single heavier top set
+    /// doesn't move the targets on its own. A session fails when it has fewer sets than the element
+    /// or any set short of the reps, those of the element for linear and `min_reps` for double
+    /// progression. Failures only count since the weight last dropped, so a deload isn't repeated
+    /// right after the session it was applied to.
+    pub fn evaluate(element: &WkTemplateElement, rule: Option<ProgressionRule>, history: &[Vec<WkExecutionElement>]) -> NextTarget {
+        let target = NextTarget {
+            wk_template_element_id: element.id,
+            exercise_id: element.exercise_id,
+            sets: element.sets,
+            reps: element.reps,
+            weight: element.weight,
+            outcome: Outcome::NoRule,
+            failed_sessions: 0,
+            rule: None,
+        };
+        let Some(rule) = rule else { return target };
+        let (floor, top) = match (rule.min_reps, rule.max_reps) {
+            (Some(min_reps), Some(max_reps)) if rule.kind == DOUBLE_PROGRESSION => (min_reps, max_reps),
+            _ => (element.reps, element.reps),
+        };
+        let Some(last) = history.first() else {
+            return NextTarget { reps: floor, outcome: Outcome::NoHistory, rule: Some(rule), ..target };
+        };
+
+        let failed = |sets: &Vec<WkExecutionElement>| sets.len() < element.sets as usize || sets.iter().any(|set| set.reps < floor);
+        let session_weight = |sets: &Vec<WkExecutionElement>| sets.iter().filter_map(|set| set.weight).reduce(f32::min);
+        let since_drop = history.windows(2)
+            .position(|pair| session_weight(&pair[0]).zip(session_weight(&pair[1])).is_some_and(|(newer, older)| newer < older))
+            .map_or(history.len(), |index| index + 1);
+        let failed_sessions = history[..since_drop].iter().take_while(|sets| failed(sets)).count();
+        let weight = session_weight(last).or(element.weight);
+        let lowest_reps = last.iter().map(|set| set.reps).min().unwrap_or(0);
+
+        let (outcome, reps, weight) = if rule.deload_after.is_some_and(|after| failed_sessions >= after as usize) {
+            (Outcome::Deload, floor, weight.map(|kg| kg * (100 - rule.deload_percent) as f32 / 100.0))
+        } else if failed_sessions == 0 && lowest_reps >= top {
+            (Outcome::Increase, floor, weight.map(|kg| kg + rule.weight_increment))
+        } else if rule.kind == DOUBLE_PROGRESSION && failed_sessions == 0 {
+            (Outcome::Hold, (lowest_reps + 1).clamp(floor, top), weight)
+        } else {
+            (Outcome::Hold, floor, weight)
+        };
+        NextTarget { reps, weight, outcome, failed_sessions, rule: Some(rule), ..target }
+    }
+}
+
+/// Next targets of every element of a template, after its latest execution if any
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct TemplateProgression {
+    pub workout_template_id: Uuid,
+    pub workout_execution_id: Option<Uuid>,
+    pub targets: Vec<NextTarget>,
+}
+
+impl InUnits for TemplateProgression {
+    fn in_units(self, units: &Units) -> Self {
+        TemplateProgression { targets: self.targets.in_units(units), ..self }
+    }
+}
+
+/// Sets of an execution done for each template element.
+///
+/// Each exercise of the execution goes to the first element, by position, doing the same
+/// exercise that hasn't been matched yet, so a template can repeat an exercise.
+pub fn match_elements(elements: &[WkTemplateElement], rows: Vec<WkExecutionElement>) -> HashMap<Uuid, Vec<WkExecutionElement>> {
+    let mut exercises: Vec<(i16, Vec<WkExecutionElement>)> = rows.into_iter()
+        .sorted_by_key(|row| (row.exercise_number, row.position))
+        .chunk_by(|row| row.exercise_number)
+        .into_iter()
+        .map(|(exercise_number, sets)| (exercise_number, sets.collect()))
+        .collect();
+    let mut matched = HashMap::new();
+    for element in elements.iter().sorted_by_key(|element| element.position) {
+        if let Some(index) = exercises.iter().position(|(_, sets)| sets[0].exercise_id == element.exercise_id) {
+            matched.insert(element.id, exercises.remove(index).1);
+        }
+    }
+    matched
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // TEST CASES
+    // * Request validation
+    // * Linear increase, hold and deload, failures before a deload left out
+    // * Increments in the user's units
+    // * Double progression through the rep range
+    // * Execution sets matched to elements
+
+    fn element(reps: i16, sets: i16, weight: Option<f32>) -> WkTemplateElement {
+        WkTemplateElement {
+            id: Uuid::new_v4(),
+            workout_template_id: Uuid::new_v4(),
+            exercise_id: Uuid::new_v4(),
+            position: 0,
+            reps,
+            sets,
+            weight,
+            rest: 90,
+            super_set: None,
+        }
+    }
+
+    fn rule(element: &WkTemplateElement, kind: i16, range: Option<(i16, i16)>, deload_after: Option<i16>) -> ProgressionRule {
+        ProgressionRule {
+            wk_template_element_id: element.id,
+            kind,
+            weight_increment: 2.5,
+            min_reps: range.map(|(min_reps, _)| min_reps),
+            max_reps: range.map(|(_, max_reps)| max_reps),
+            deload_after,
+            deload_percent: DEFAULT_DELOAD_PERCENT,
+            date_created: chrono::Utc::now().naive_utc(),
+        }
+    }
+
+    fn session(exercise_id: Uuid, exercise_number: i16, sets: &[(i16, f32)]) -> Vec<WkExecutionElement> {
+        sets.iter().enumerate().map(|(n, (reps, weight))| WkExecutionElement {
+            id: Uuid::new_v4(),
+            workout_execution_id: Uuid::new_v4(),
+            exercise_id,
+            position: n as i16,
+            exercise_number,
+            reps: *reps,
+            set_number: n as i16,
+            weight: Some(*weight),
+            rest: 90,
+            super_set: None,
+            time: 30,
+        }).collect()
+    }
+
+    #[test]
+    fn test_validate() {
+        let request = |kind, min_reps, max_reps| ProgressionRuleRequest { kind, weight_increment: 2.5, min_reps, max_reps, deload_after: Some(3), deload_percent: None };
+        assert!(request(LINEAR, None, None).validate().is_ok());
+        assert!(request(DOUBLE_PROGRESSION, Some(8), Some(12)).validate().is_ok());
+        assert!(request(LINEAR, Some(8), Some(12)).validate().is_err());
+        assert!(request(DOUBLE_PROGRESSION, Some(12), Some(8)).validate().is_err());
+        assert!(request(DOUBLE_PROGRESSION, None, Some(8)).validate().is_err());
+        assert!(request(2, None, None).validate().is_err());
+        assert!(ProgressionRuleRequest { weight_increment: 0.0, ..request(LINEAR, None, None) }.validate().is_err());
+        assert!(ProgressionRuleRequest { deload_after: Some(0), ..request(LINEAR, None, None) }.validate().is_err());
+        assert!(ProgressionRuleRequest { deload_percent: Some(80), ..request(LINEAR, None, None) }.validate().is_err());
+
+        let new = request(LINEAR, None, None).to_new_rule(Uuid::new_v4(), &Units::IMPERIAL);
+        assert!((new.weight_increment - 2.5 * 0.453_592_37).abs() < 1e-6);
+        assert_eq!(new.deload_percent, DEFAULT_DELOAD_PERCENT);
+    }
+
+    #[test]
+    fn test_linear() {
+        let squat = element(5, 3, Some(100.0));
+        let linear = rule(&squat, LINEAR, None, Some(2));
+        let done = session(squat.exercise_id, 0, &[(5, 100.0), (5, 100.0), (5, 100.0)]);
+        let short = session(squat.exercise_id, 0, &[(5, 102.5), (5, 102.5), (3, 102.5)]);
+
+        let target = NextTarget::evaluate(&squat, None, std::slice::from_ref(&done));
+        assert_eq!((target.outcome, target.weight), (Outcome::NoRule, Some(100.0)));
+        let target = NextTarget::evaluate(&squat, Some(linear.clone()), &[]);
+        assert_eq!((target.outcome, target.weight), (Outcome::NoHistory, Some(100.0)));
+
+        let target = NextTarget::evaluate(&squat, Some(linear.clone()), std::slice::from_ref(&done));
+        assert_eq!((target.outcome, target.reps, target.weight), (Outcome::Increase, 5, Some(102.5)));
+
+        let target = NextTarget::evaluate(&squat, Some(linear.clone()), &[short.clone(), done]);
+        assert_eq!((target.outcome, target.weight, target.failed_sessions), (Outcome::Hold, Some(102.5), 1));
+
+        let target = NextTarget::evaluate(&squat, Some(linear.clone()), &[short.clone(), short.clone()]);
+        assert_eq!(target.outcome, Outcome::Deload);
+        assert!((target.weight.unwrap() - 92.25).abs() < 1e-4);
+
+        // Failures before the deload don't count towards the next one
+        let deloaded = session(squat.exercise_id, 0, &[(5, 92.5), (5, 92.5), (4, 92.5)]);
+        let target = NextTarget::evaluate(&squat, Some(linear.clone()), &[deloaded.clone(), short.clone(), short.clone()]);
+        assert_eq!((target.outcome, target.weight, target.failed_sessions), (Outcome::Hold, Some(92.5), 1));
+        let target = NextTarget::evaluate(&squat, Some(linear), &[deloaded.clone(), deloaded, short.clone(), short]);
+        assert_eq!((target.outcome, target.failed_sessions), (Outcome::Deload, 2));
+    }
+
+    #[test]
+    fn test_double_progression() {
+        let curl = element(8, 2, Some(20.0));
+        let double = rule(&curl, DOUBLE_PROGRESSION, Some((8, 12)), None);
+
+        let target = NextTarget::evaluate(&curl, Some(double.clone()), &[session(curl.exercise_id, 0, &[(10, 20.0), (9, 20.0)])]);
+        assert_eq!((target.outcome, target.reps, target.weight), (Outcome::Hold, 10, Some(20.0)));
+
+        let target = NextTarget::evaluate(&curl, Some(double.clone()), &[session(curl.exercise_id, 0, &[(12, 20.0), (12, 20.0)])]);
+        assert_eq!((target.outcome, target.reps, target.weight), (Outcome::Increase, 8, Some(22.5)));
+
+        // Failed sessions restart at the bottom of the range, without a deload they never drop the weight
+        let target = NextTarget::evaluate(&curl, Some(double), &[session(curl.exercise_id, 0, &[(7, 22.5), (6, 22.5)])]);
+        assert_eq!((target.outcome, target.reps, target.weight, target.failed_sessions), (Outcome::Hold, 8, Some(22.5), 1));
+    }
+
+    #[test]
+    fn test_rule_in_units() {
+        let squat = element(5, 3, Some(100.0));
+        let linear = ProgressionRule { weight_increment: 1.0, ..rule(&squat, LINEAR, None, None) };
+        // Increments aren't loads, they keep their decimals instead of snapping to plates
+        assert_eq!(linear.clone().in_units(&Units::IMPERIAL).weight_increment, 2.2);
+        assert_eq!(linear.in_units(&Units::METRIC).weight_increment, 1.0);
+    }
+
+    #[test]
+    fn test_match_elements() {
+        let bench = element(5, 2, Some(80.0));
+        let row = WkTemplateElement { position: 1, ..element(8, 2, Some(60.0)) };
+        let backoff = WkTemplateElement { position: 2, exercise_id: bench.exercise_id, ..element(10, 1, Some(60.0)) };
+        let mut rows = session(bench.exercise_id, 0, &[(5, 80.0), (5, 80.0)]);
+        rows.extend(session(bench.exercise_id, 1, &[(10, 60.0)]));
+
+        let (bench_id, row_id, backoff_id) = (bench.id, row.id, backoff.id);
+
+        let matched = match_elements(&[backoff, bench, row], rows);
+        assert_eq!(matched[&bench_id].len(), 2);
+        assert_eq!(matched[&backoff_id][0].reps, 10);
+        assert!(!matched.contains_key(&row_id));
+    }
+}
@@ -0,0 +1,48 @@
+use diesel::prelude::*;
+use uuid::Uuid;
+use chrono::NaiveDateTime;
+use serde::{Serialize, Deserialize};
+use crate::schema::reactions;
+
+// Kinds of reaction, a user has at most one per execution
+pub const LIKE: i16 = 0;
+pub const FIRE: i16 = 1;
+pub const STRONG: i16 = 2;
+pub const CLAP: i16 = 3;
+pub const WOW: i16 = 4;
+
+#[derive(Queryable, Selectable, Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[diesel(table_name = reactions)]
+pub struct Reaction {
+    pub workout_execution_id: Uuid,
+    pub user_id: Uuid,
+    pub reaction: i16,
+    pub date_created: NaiveDateTime,
+}
+
+#[derive(Insertable, AsChangeset, Serialize, Deserialize, Debug)]
+#[diesel(table_name = reactions)]
+pub struct NewReaction {
+    pub workout_execution_id: Uuid,
+    pub user_id: Uuid,
+    pub reaction: i16,
+}
+
+/// Body of reaction requests
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ReactionKind {
+    pub reaction: i16,
+}
+
+impl ReactionKind {
+    pub fn is_valid(&self) -> bool {
+        (LIKE..=WOW).contains(&self.reaction)
+    }
+}
+
+/// How many users reacted to an execution with each kind of reaction
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct ReactionCount {
+    pub reaction: i16,
+    pub count: i64,
+}
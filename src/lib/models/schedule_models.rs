@@ -0,0 +1,305 @@
+use std::collections::HashSet;
+use chrono::{Datelike, NaiveDate, NaiveDateTime};
+use diesel::prelude::*;
+use uuid::Uuid;
+use serde::{Serialize, Deserialize};
+use super::workout_execution_models::WorkoutExecution;
+use crate::schema::{calendartokens, scheduledworkouts};
+
+/// Longest range the calendar can be asked for
+pub const MAX_CALENDAR_DAYS: i64 = 366;
+
+/// Template planned once on `start_date` or, with `weekdays`, on those ISO weekdays (1 Monday to 7 Sunday)
+/// from `start_date` until `end_date`, or indefinitely
+#[derive(Queryable, Identifiable, Selectable, Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[diesel(table_name = scheduledworkouts)]
+pub struct ScheduledWorkout {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub workout_template_id: Uuid,
+    pub start_date: NaiveDate,
+    pub end_date: Option<NaiveDate>,
+    pub weekdays: Option<Vec<i16>>,
+    pub date_created: NaiveDateTime,
+}
+
+impl ScheduledWorkout {
+    /// Dates the workout is planned on between `from` and `to`, both included
+    pub fn occurrences(&self, from: NaiveDate, to: NaiveDate) -> Vec<NaiveDate> {
+        let last = match (&self.weekdays, self.end_date) {
+            (None, _) => self.start_date,
+            (Some(_), Some(end_date)) => end_date.min(to),
+            (Some(_), None) => to,
+        };
+        self.start_date.max(from)
+            .iter_days()
+            .take_while(|date| *date <= last.min(to))
+            .filter(|date| self.falls_on(*date))
+            .collect()
+    }
+
+    /// First date the workout is planned on, None if the recurrence never hits one of its weekdays
+    pub fn first_occurrence(&self) -> Option<NaiveDate> {
+        let week_later = self.start_date + chrono::Duration::days(6);
+        self.occurrences(self.start_date, week_later).first().copied()
+    }
+
+    fn falls_on(&self, date: NaiveDate) -> bool {
+        match &self.weekdays {
+            Some(weekdays) => weekdays.contains(&(date.weekday().number_from_monday() as i16)),
+            None => date == self.start_date,
+        }
+    }
+}
+
+#[derive(Insertable, Serialize, Deserialize, Debug)]
+#[diesel(table_name = scheduledworkouts)]
+pub struct NewScheduledWorkout {
+    pub user_id: Uuid,
+    pub workout_template_id: Uuid,
+    pub start_date: NaiveDate,
+    pub end_date: Option<NaiveDate>,
+    pub weekdays: Option<Vec<i16>>,
+}
+
+/// Body of schedule requests, recurring if `weekdays` are given
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ScheduleRequest {
+    pub workout_template_id: Uuid,
+    pub start_date: NaiveDate,
+    pub end_date: Option<NaiveDate>,
+    pub weekdays: Option<Vec<i16>>,
+}
+
+impl ScheduleRequest {
+    pub fn validate(&self) -> Result<(), String> {
+        match &self.weekdays {
+            Some(weekdays) if weekdays.is_empty() || weekdays.iter().any(|day| !(1..=7).contains(day)) => {
+                return Err("'weekdays' must be ISO weekdays, from 1 (Monday) to 7 (Sunday)".to_string())
+            },
+            None if self.end_date.is_some() => {
+                return Err("'end_date' is only for recurring workouts, with 'weekdays'".to_string())
+            },
+            _ => (),
+        }
+        if self.end_date.is_some_and(|end_date| end_date < self.start_date) {
+            return Err("'start_date' can't be after 'end_date'".to_string());
+        }
+        Ok(())
+    }
+
+    pub fn to_new_scheduled_workout(&self, user_id: Uuid) -> NewScheduledWorkout {
+        let weekdays = self.weekdays.as_ref().map(|weekdays| {
+            let mut weekdays: Vec<i16> = weekdays.iter().copied().collect::<HashSet<i16>>().into_iter().collect();
+            weekdays.sort_unstable();
+            weekdays
+        });
+        NewScheduledWorkout {
+            user_id,
+            workout_template_id: self.workout_template_id,
+            start_date: self.start_date,
+            end_date: self.end_date,
+            weekdays,
+        }
+    }
+}
+
+/// Page of the scheduled workouts of a user, latest first
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct ScheduleList {
+    pub scheduled_workouts: Vec<ScheduledWorkout>,
+    pub next_offset: Option<i64>,
+}
+
+impl ScheduleList {
+    /// Builds a page from up to `limit + 1` scheduled workouts, the extra one only tells there is a next page
+    pub fn page(mut scheduled_workouts: Vec<ScheduledWorkout>, limit: i64, offset: i64) -> ScheduleList {
+        let next_offset = (scheduled_workouts.len() as i64 > limit).then_some(offset + limit);
+        scheduled_workouts.truncate(limit as usize);
+        ScheduleList { scheduled_workouts, next_offset }
+    }
+}
+
+/// Occurrence of a scheduled workout, with the execution that did it if any
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct PlannedWorkout {
+    pub scheduled_workout_id: Uuid,
+    pub workout_template_id: Uuid,
+    pub template_name: String,
+    pub date: NaiveDate,
+    pub workout_execution_id: Option<Uuid>,
+}
+
+/// Execution that didn't match any planned workout
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct UnplannedWorkout {
+    pub workout_execution_id: Uuid,
+    pub workout_template_id: Uuid,
+    pub template_name: String,
+    pub date: NaiveDate,
+}
+
+/// Planned workouts and executions between `from` and `to`, both included
+/// * `due` are the planned workouts up to today, `adherence` the share of them that was done
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct Calendar {
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+    pub planned: Vec<PlannedWorkout>,
+    pub unplanned: Vec<UnplannedWorkout>,
+    pub due: usize,
+    pub completed: usize,
+    pub adherence: Option<f32>,
+}
+
+impl Calendar {
+    /// Matches each execution to a planned workout of the same template on the same day, if there is one left
+    pub fn merge(
+        from: NaiveDate,
+        to: NaiveDate,
+        today: NaiveDate,
+        schedule: Vec<(ScheduledWorkout, String)>,
+        mut executions: Vec<(WorkoutExecution, String)>,
+    ) -> Calendar {
+        let mut planned: Vec<PlannedWorkout> = schedule.into_iter()
+            .flat_map(|(scheduled, template_name)| scheduled.occurrences(from, to).into_iter().map(move |date| PlannedWorkout {
+                scheduled_workout_id: scheduled.id,
+                workout_template_id: scheduled.workout_template_id,
+                template_name: template_name.clone(),
+                date,
+                workout_execution_id: None,
+            }))
+            .collect();
+        planned.sort_by(|a, b| (a.date, &a.template_name, a.scheduled_workout_id).cmp(&(b.date, &b.template_name, b.scheduled_workout_id)));
+
+        executions.sort_by_key(|(execution, _)| (execution.date, execution.id));
+        let mut unplanned = Vec::new();
+        for (execution, template_name) in executions {
+            let slot = planned.iter_mut().find(|planned| {
+                planned.workout_execution_id.is_none() && planned.date == execution.date && planned.workout_template_id == execution.workout_template_id
+            });
+            match slot {
+                Some(planned) => planned.workout_execution_id = Some(execution.id),
+                None => unplanned.push(UnplannedWorkout {
+                    workout_execution_id: execution.id,
+                    workout_template_id: execution.workout_template_id,
+                    template_name,
+                    date: execution.date,
+                }),
+            }
+        }
+
+        let due = planned.iter().filter(|planned| planned.date <= today).count();
+        let completed = planned.iter().filter(|planned| planned.workout_execution_id.is_some()).count();
+        let adherence = (due > 0).then(|| completed as f32 / due as f32);
+        Calendar { from, to, planned, unplanned, due, completed, adherence }
+    }
+}
+
+/// Secret giving read access to the iCal feed of a user
+#[derive(Queryable, Identifiable, Selectable, Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[diesel(table_name = calendartokens)]
+#[diesel(primary_key(user_id))]
+pub struct CalendarToken {
+    pub user_id: Uuid,
+    pub token: Uuid,
+    pub date_created: NaiveDateTime,
+}
+
+#[derive(Insertable, Serialize, Deserialize, Debug)]
+#[diesel(table_name = calendartokens)]
+pub struct NewCalendarToken {
+    pub user_id: Uuid,
+    pub token: Uuid,
+}
+
+/// Token and path of the iCal feed, to subscribe to from a calendar app
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct CalendarFeedLink {
+    pub token: Uuid,
+    pub feed_path: String,
+}
+
+impl From<CalendarToken> for CalendarFeedLink {
+    fn from(calendar_token: CalendarToken) -> Self {
+        CalendarFeedLink { token: calendar_token.token, feed_path: format!("/calendar/{}.ics", calendar_token.token) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lib::models::workout_execution_models::COMPLETED;
+
+    // TEST CASES
+    // * Request validation
+    // * Occurrences of one-off and recurring workouts
+    // * Executions matched to planned workouts
+
+    fn day(n: u32) -> NaiveDate {
+        // 2024-11-04 is a Monday
+        NaiveDate::from_ymd_opt(2024, 11, n).unwrap()
+    }
+
+    fn scheduled(workout_template_id: Uuid, start_date: NaiveDate, end_date: Option<NaiveDate>, weekdays: Option<Vec<i16>>) -> ScheduledWorkout {
+        ScheduledWorkout {
+            id: Uuid::new_v4(),
+            user_id: Uuid::new_v4(),
+            workout_template_id,
+            start_date,
+            end_date,
+            weekdays,
+            date_created: day(1).and_hms_opt(0, 0, 0).unwrap(),
+        }
+    }
+
+    #[test]
+    fn test_validate() {
+        let request = |end_date, weekdays| ScheduleRequest { workout_template_id: Uuid::new_v4(), start_date: day(4), end_date, weekdays };
+        assert!(request(None, None).validate().is_ok());
+        assert!(request(Some(day(30)), Some(vec![1, 3, 5])).validate().is_ok());
+        assert!(request(Some(day(30)), None).validate().is_err());
+        assert!(request(None, Some(vec![])).validate().is_err());
+        assert!(request(None, Some(vec![0])).validate().is_err());
+        assert!(request(Some(day(1)), Some(vec![1])).validate().is_err());
+
+        let new = request(None, Some(vec![5, 1, 5])).to_new_scheduled_workout(Uuid::new_v4());
+        assert_eq!(new.weekdays, Some(vec![1, 5]));
+    }
+
+    #[test]
+    fn test_occurrences() {
+        let template_id = Uuid::new_v4();
+        let one_off = scheduled(template_id, day(6), None, None);
+        assert_eq!(one_off.occurrences(day(1), day(30)), vec![day(6)]);
+        assert!(one_off.occurrences(day(7), day(30)).is_empty());
+
+        let recurring = scheduled(template_id, day(5), Some(day(15)), Some(vec![1, 3, 5]));
+        assert_eq!(recurring.occurrences(day(1), day(30)), vec![day(6), day(8), day(11), day(13), day(15)]);
+        assert_eq!(recurring.occurrences(day(9), day(12)), vec![day(11)]);
+        assert_eq!(recurring.first_occurrence(), Some(day(6)));
+
+        let never = scheduled(template_id, day(5), Some(day(6)), Some(vec![7]));
+        assert_eq!(never.first_occurrence(), None);
+    }
+
+    #[test]
+    fn test_merge() {
+        let (push, pull) = (Uuid::new_v4(), Uuid::new_v4());
+        let schedule = vec![
+            (scheduled(push, day(4), None, Some(vec![1, 3])), "Push".to_string()),
+            (scheduled(pull, day(5), None, None), "Pull".to_string()),
+        ];
+        let execution = |workout_template_id, date| WorkoutExecution { id: Uuid::new_v4(), workout_template_id, date, survey: 0, program_slot_id: None, status: COMPLETED, last_activity: None };
+        let done = execution(push, day(4));
+        let moved = execution(pull, day(6));
+        let executions = vec![(done.clone(), "Push".to_string()), (moved.clone(), "Pull".to_string())];
+
+        let calendar = Calendar::merge(day(4), day(10), day(7), schedule, executions);
+        let planned: Vec<(NaiveDate, Option<Uuid>)> = calendar.planned.iter().map(|planned| (planned.date, planned.workout_execution_id)).collect();
+        assert_eq!(planned, vec![(day(4), Some(done.id)), (day(5), None), (day(6), None)]);
+        assert_eq!(calendar.unplanned[0].workout_execution_id, moved.id);
+        assert_eq!((calendar.due, calendar.completed), (3, 1));
+        assert!((calendar.adherence.unwrap() - 1.0 / 3.0).abs() < 1e-6);
+    }
+}
@@ -0,0 +1,162 @@
+use std::collections::HashSet;
+use base64::prelude::*;
+use diesel::prelude::*;
+use uuid::Uuid;
+use serde::{Serialize, Deserialize};
+use super::workout_templates_models::WorkoutTemplate;
+use super::wk_template_elements_models::WkTemplateElement;
+use super::workout_execution_models::WorkoutExecution;
+use super::wk_execution_elements_models::WkExecutionElement;
+use super::unit_models::{InUnits, Units};
+use crate::schema::syncchanges;
+
+// Kinds of record kept in sync
+pub const WORKOUT_TEMPLATE: i16 = 0;
+pub const WK_TEMPLATE_ELEMENT: i16 = 1;
+pub const WORKOUT_EXECUTION: i16 = 2;
+pub const WK_EXECUTION_ELEMENT: i16 = 3;
+
+/// Latest write of a record of the user, as tracked by the database
+/// * `created` and `updated` are ids of the transactions that did them
+#[derive(Queryable, Selectable, Debug, Clone, PartialEq)]
+#[diesel(table_name = syncchanges)]
+pub struct SyncChange {
+    pub kind: i16,
+    pub id: Uuid,
+    pub created: i64,
+    pub deleted: bool,
+}
+
+/// Point up to which a client is in sync.
+///
+/// It is the oldest transaction still running when the changes were read, every write by an
+/// older one was in them already. Writes of the newer ones may have been too, so the next
+/// changes can repeat a few records, but none is ever missed.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SyncToken(pub i64);
+
+impl std::fmt::Display for SyncToken {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", BASE64_URL_SAFE_NO_PAD.encode(self.0.to_be_bytes()))
+    }
+}
+
+impl std::str::FromStr for SyncToken {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let bytes: [u8; 8] = BASE64_URL_SAFE_NO_PAD.decode(s)
+            .ok()
+            .and_then(|bytes| bytes.try_into().ok())
+            .ok_or_else(|| format!("Invalid sync token '{}'", s))?;
+        Ok(SyncToken(i64::from_be_bytes(bytes)))
+    }
+}
+
+/// Ids of the changed records of one kind, sorted by what happened to them since the token
+#[derive(Debug, Default, PartialEq)]
+pub struct SortedChanges {
+    pub created: HashSet<Uuid>,
+    pub updated: HashSet<Uuid>,
+    pub deleted: Vec<Uuid>,
+}
+
+impl SortedChanges {
+    /// Without a token everything is new and there's nothing to delete.
+    /// Records created and deleted since the token were never seen, so they are left out.
+    pub fn new(changes: Vec<SyncChange>, since: Option<SyncToken>) -> SortedChanges {
+        let mut sorted = SortedChanges::default();
+        for change in changes {
+            let is_new = since.is_none_or(|token| change.created >= token.0);
+            match (change.deleted, is_new) {
+                (true, true) => (),
+                (true, false) => sorted.deleted.push(change.id),
+                (false, true) => { sorted.created.insert(change.id); },
+                (false, false) => { sorted.updated.insert(change.id); },
+            }
+        }
+        sorted
+    }
+
+    /// Ids of the records that still exist
+    pub fn live(&self) -> Vec<Uuid> {
+        self.created.iter().chain(self.updated.iter()).copied().collect()
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Changes<T> {
+    pub created: Vec<T>,
+    pub updated: Vec<T>,
+    pub deleted: Vec<Uuid>,
+}
+
+impl<T> Changes<T> {
+    /// Splits the records loaded for `sorted` into created and updated
+    pub fn new(records: Vec<T>, sorted: SortedChanges, id: impl Fn(&T) -> Uuid) -> Changes<T> {
+        let (created, updated) = records.into_iter().partition(|record| sorted.created.contains(&id(record)));
+        Changes { created, updated, deleted: sorted.deleted }
+    }
+}
+
+/// Everything that changed in the user's templates and executions since a token
+/// * Elements of a deleted template or execution are deleted with it, and have their own tombstones
+/// * `token` is the one to ask for the next changes with
+#[derive(Serialize, Deserialize)]
+pub struct ChangeFeed {
+    pub token: String,
+    pub workout_templates: Changes<WorkoutTemplate>,
+    pub wk_template_elements: Changes<WkTemplateElement>,
+    pub workout_executions: Changes<WorkoutExecution>,
+    pub wk_execution_elements: Changes<WkExecutionElement>,
+}
+
+impl<T: InUnits> InUnits for Changes<T> {
+    fn in_units(self, units: &Units) -> Self {
+        Changes { created: self.created.in_units(units), updated: self.updated.in_units(units), ..self }
+    }
+}
+
+impl InUnits for ChangeFeed {
+    fn in_units(self, units: &Units) -> Self {
+        ChangeFeed {
+            wk_template_elements: self.wk_template_elements.in_units(units),
+            wk_execution_elements: self.wk_execution_elements.in_units(units),
+            ..self
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // TEST CASES
+    // * Token round trip, invalid tokens
+    // * Changes sorted by what happened since the token
+
+    #[test]
+    fn test_sync_token() {
+        let token = SyncToken(1_234_567);
+        assert_eq!(token.to_string().parse::<SyncToken>(), Ok(token));
+        assert!("".parse::<SyncToken>().is_err());
+        assert!("not a token".parse::<SyncToken>().is_err());
+        assert!(BASE64_URL_SAFE_NO_PAD.encode([1, 2, 3]).parse::<SyncToken>().is_err());
+    }
+
+    #[test]
+    fn test_sorted_changes() {
+        let change = |created: i64, deleted: bool| SyncChange { kind: WORKOUT_TEMPLATE, id: Uuid::new_v4(), created, deleted };
+        let changes = vec![change(5, false), change(15, false), change(5, true), change(15, true)];
+        let ids: Vec<Uuid> = changes.iter().map(|change| change.id).collect();
+
+        let sorted = SortedChanges::new(changes.clone(), Some(SyncToken(10)));
+        assert_eq!(sorted.created, HashSet::from([ids[1]]));
+        assert_eq!(sorted.updated, HashSet::from([ids[0]]));
+        assert_eq!(sorted.deleted, vec![ids[2]]);
+
+        let full = SortedChanges::new(changes, None);
+        assert_eq!(full.created, HashSet::from([ids[0], ids[1]]));
+        assert!(full.updated.is_empty() && full.deleted.is_empty());
+    }
+}
@@ -0,0 +1,142 @@
+use std::str::FromStr;
+use diesel::prelude::*;
+use serde::{Serialize, Deserialize};
+use crate::schema::users;
+
+// Everything is stored in kg and cm, units only change what the API takes and returns
+pub const KG: i16 = 0;
+pub const LB: i16 = 1;
+pub const CM: i16 = 0;
+pub const IN: i16 = 1;
+
+pub const KG_PER_LB: f32 = 0.453_592_37;
+pub const CM_PER_IN: f32 = 2.54;
+
+/// Steps per unit of the smallest load change that can be put on a bar, loads are shown rounded to it
+const KG_STEPS: f32 = 4.0;
+const LB_STEPS: f32 = 2.0;
+/// Steps per unit of body weight and lengths, one decimal
+const DECIMAL_STEPS: f32 = 10.0;
+
+/// Units a user reads and writes measures in
+#[derive(Queryable, Selectable, Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[diesel(table_name = users)]
+pub struct Units {
+    pub weight_unit: i16,
+    pub length_unit: i16,
+}
+
+impl Units {
+    pub const METRIC: Units = Units { weight_unit: KG, length_unit: CM };
+    pub const IMPERIAL: Units = Units { weight_unit: LB, length_unit: IN };
+
+    pub fn is_valid(&self) -> bool {
+        [KG, LB].contains(&self.weight_unit) && [CM, IN].contains(&self.length_unit)
+    }
+
+    /// Client weight to kg, as stored
+    pub fn weight_to_kg(&self, value: f32) -> f32 {
+        match self.weight_unit {
+            LB => value * KG_PER_LB,
+            _ => value,
+        }
+    }
+
+    /// Stored training load to the client's unit, rounded to the nearest plate increment
+    pub fn load_from_kg(&self, kg: f32) -> f32 {
+        match self.weight_unit {
+            LB => round_to(kg / KG_PER_LB, LB_STEPS),
+            _ => round_to(kg, KG_STEPS),
+        }
+    }
+
+    /// Stored body weight or training volume to the client's unit, to one decimal
+    pub fn weight_from_kg(&self, kg: f32) -> f32 {
+        match self.weight_unit {
+            LB => round_to(kg / KG_PER_LB, DECIMAL_STEPS),
+            _ => round_to(kg, DECIMAL_STEPS),
+        }
+    }
+
+    /// Client length to cm, as stored
+    pub fn length_to_cm(&self, value: f32) -> f32 {
+        match self.length_unit {
+            IN => value * CM_PER_IN,
+            _ => value,
+        }
+    }
+
+    /// Stored length to the client's unit, to one decimal
+    pub fn length_from_cm(&self, cm: f32) -> f32 {
+        match self.length_unit {
+            IN => round_to(cm / CM_PER_IN, DECIMAL_STEPS),
+            _ => round_to(cm, DECIMAL_STEPS),
+        }
+    }
+}
+
+/// Value of the `units` query parameter, overriding the user's preference
+impl FromStr for Units {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "metric" => Ok(Units::METRIC),
+            "imperial" => Ok(Units::IMPERIAL),
+            _ => Err(format!("Unknown units '{}', expected 'metric' or 'imperial'", s)),
+        }
+    }
+}
+
+/// Responses holding stored measures, converted from metric before being sent
+pub trait InUnits {
+    fn in_units(self, units: &Units) -> Self;
+}
+
+impl<T: InUnits> InUnits for Vec<T> {
+    fn in_units(self, units: &Units) -> Self {
+        self.into_iter().map(|item| item.in_units(units)).collect()
+    }
+}
+
+fn round_to(value: f32, steps: f32) -> f32 {
+    (value * steps).round() / steps
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // TEST CASES
+    // * Parse query parameter
+    // * Round trip of loads entered in pounds
+    // * Rounding to plate increments and lengths
+
+    #[test]
+    fn test_units_from_str() {
+        assert_eq!("metric".parse::<Units>(), Ok(Units::METRIC));
+        assert_eq!("imperial".parse::<Units>(), Ok(Units::IMPERIAL));
+        assert!("lb".parse::<Units>().is_err());
+        assert!(Units::IMPERIAL.is_valid());
+        assert!(!Units { weight_unit: 2, length_unit: CM }.is_valid());
+    }
+
+    #[test]
+    fn test_load_round_trip() {
+        let imperial = Units::IMPERIAL;
+        for lb in [45.0, 135.0, 137.5, 315.0] {
+            let kg = imperial.weight_to_kg(lb);
+            assert_eq!(imperial.load_from_kg(kg), lb);
+        }
+        assert_eq!(Units::METRIC.weight_to_kg(100.0), 100.0);
+    }
+
+    #[test]
+    fn test_rounding() {
+        assert_eq!(Units::METRIC.load_from_kg(61.235), 61.25);
+        assert_eq!(Units::IMPERIAL.load_from_kg(60.0), 132.5);
+        assert_eq!(Units::IMPERIAL.weight_from_kg(80.0), 176.4);
+        assert_eq!(Units::IMPERIAL.length_from_cm(180.0), 70.9);
+        assert!((Units::IMPERIAL.length_to_cm(10.0) - 25.4).abs() < 1e-4);
+    }
+}
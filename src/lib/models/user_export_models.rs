@@ -0,0 +1,52 @@
+use serde::{Serialize, Deserialize};
+use super::user_models::User;
+use super::body_measurement_models::BodyMeasurement;
+use super::follow_models::Follow;
+use super::comment_models::Comment;
+use super::reaction_models::Reaction;
+use super::notification_models::Notification;
+use super::challenge_models::{Challenge, ChallengeParticipant};
+use super::exercise_models::Exercise;
+use super::workout_templates_models::WorkoutTemplate;
+use super::wk_template_elements_models::WkTemplateElement;
+use super::progression_models::ProgressionRule;
+use super::program_models::{Program, ProgramEnrolment, ProgramSlot};
+use super::workout_execution_models::WorkoutExecution;
+use super::wk_execution_elements_models::WkExecutionElement;
+use super::workout_import_models::WorkoutImport;
+use super::schedule_models::{CalendarToken, ScheduledWorkout};
+
+/// Everything stored about a user, one list per table
+/// * Rows reference each other by id, the same way they do in the database
+/// * Exercises are the ones owned by the user or referenced by any element
+/// * Follows in both directions, including pending requests
+/// * Comments and reactions the user left, on anyone's executions
+/// * Notifications the user received
+/// * Challenges the user created, and their invitations to anyone's challenges
+/// * Progression rules of the elements of the user's templates
+/// * Programs of the user with their slots, and every enrolment in them
+/// * Imports marking the executions that came from other apps
+/// * Planned workouts of the user, and the token of their calendar feed if any
+#[derive(Serialize, Deserialize)]
+pub struct UserExport {
+    pub user: User,
+    pub body_measurements: Vec<BodyMeasurement>,
+    pub follows: Vec<Follow>,
+    pub comments: Vec<Comment>,
+    pub reactions: Vec<Reaction>,
+    pub notifications: Vec<Notification>,
+    pub challenges: Vec<Challenge>,
+    pub challenge_participants: Vec<ChallengeParticipant>,
+    pub workout_templates: Vec<WorkoutTemplate>,
+    pub wk_template_elements: Vec<WkTemplateElement>,
+    pub progression_rules: Vec<ProgressionRule>,
+    pub programs: Vec<Program>,
+    pub program_slots: Vec<ProgramSlot>,
+    pub program_enrolments: Vec<ProgramEnrolment>,
+    pub workout_executions: Vec<WorkoutExecution>,
+    pub wk_execution_elements: Vec<WkExecutionElement>,
+    pub workout_imports: Vec<WorkoutImport>,
+    pub scheduled_workouts: Vec<ScheduledWorkout>,
+    pub calendar_tokens: Vec<CalendarToken>,
+    pub exercises: Vec<Exercise>,
+}
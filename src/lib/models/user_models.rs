@@ -1,8 +1,9 @@
 use diesel::prelude::*;
 use serde::{Serialize, Deserialize};
 use uuid::Uuid;
-use chrono::{NaiveDate, Utc};
+use chrono::{NaiveDate, NaiveDateTime, Utc};
 use crate::schema::users;
+use super::unit_models::{InUnits, Units, KG, CM};
 
 #[derive(Queryable, Insertable, Selectable, Serialize, Deserialize, Clone, Debug, PartialEq)]
 #[diesel(table_name = users)]
@@ -17,6 +18,17 @@ pub struct User {
     pub gender: Option<i16>,
     pub fitness_goal: Option<i16>,
     pub fitness_level: Option<i16>,
+    pub discoverable: bool,
+    pub deletion_scheduled_for: Option<NaiveDateTime>,
+    pub weight_unit: i16,
+    pub length_unit: i16,
+    pub private_profile: bool,
+    pub notify_new_followers: bool,
+    pub notify_comments: bool,
+    pub notify_reactions: bool,
+    pub notify_prs: bool,
+    pub notify_template_saves: bool,
+    pub show_in_leaderboards: bool,
 }
 impl Default for User {
     fn default() -> Self {
@@ -31,6 +43,17 @@ impl Default for User {
             gender: None,
             fitness_goal: None,
             fitness_level: None,
+            discoverable: true,
+            deletion_scheduled_for: None,
+            weight_unit: KG,
+            length_unit: CM,
+            private_profile: false,
+            notify_new_followers: true,
+            notify_comments: true,
+            notify_reactions: true,
+            notify_prs: true,
+            notify_template_saves: true,
+            show_in_leaderboards: true,
         }
     }
 }
@@ -39,6 +62,7 @@ impl Default for User {
 #[diesel(table_name = users)]
 pub struct UncompleteUser {
     // USERNAME cannot be changed
+    // DELETION_SCHEDULED_FOR is only changed by deleting the account or logging in
     pub display_name: Option<String>,
     pub date_joined: Option<NaiveDate>,
     pub date_of_birth: Option<NaiveDate>,
@@ -47,6 +71,16 @@ pub struct UncompleteUser {
     pub gender: Option<i16>,
     pub fitness_goal: Option<i16>,
     pub fitness_level: Option<i16>,
+    pub discoverable: Option<bool>,
+    pub weight_unit: Option<i16>,
+    pub length_unit: Option<i16>,
+    pub private_profile: Option<bool>,
+    pub notify_new_followers: Option<bool>,
+    pub notify_comments: Option<bool>,
+    pub notify_reactions: Option<bool>,
+    pub notify_prs: Option<bool>,
+    pub notify_template_saves: Option<bool>,
+    pub show_in_leaderboards: Option<bool>,
 } impl Default for UncompleteUser{
     fn default() -> Self {
         UncompleteUser {
@@ -58,6 +92,54 @@ pub struct UncompleteUser {
             gender: None,
             fitness_goal: None,
             fitness_level: None,
+            discoverable: None,
+            weight_unit: None,
+            length_unit: None,
+            private_profile: None,
+            notify_new_followers: None,
+            notify_comments: None,
+            notify_reactions: None,
+            notify_prs: None,
+            notify_template_saves: None,
+            show_in_leaderboards: None,
         }
     }
 }
+
+impl InUnits for User {
+    fn in_units(self, units: &Units) -> Self {
+        User {
+            height: self.height.map(|cm| units.length_from_cm(cm as f32).round() as i16),
+            weight: self.weight.map(|kg| units.weight_from_kg(kg)),
+            ..self
+        }
+    }
+}
+
+impl UncompleteUser {
+    /// Given units of the user, or the ones being set in the same change
+    pub fn units(&self, current: Units) -> Units {
+        Units {
+            weight_unit: self.weight_unit.unwrap_or(current.weight_unit),
+            length_unit: self.length_unit.unwrap_or(current.length_unit),
+        }
+    }
+
+    /// Converts given height and weight to cm and kg, as stored
+    pub fn to_metric(self, units: &Units) -> Self {
+        UncompleteUser {
+            height: self.height.map(|height| units.length_to_cm(height as f32).round() as i16),
+            weight: self.weight.map(|weight| units.weight_to_kg(weight)),
+            ..self
+        }
+    }
+}
+
+/// Public projection of a user, as shown in search results
+#[derive(Queryable, Selectable, Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[diesel(table_name = users)]
+pub struct UserInfo {
+    pub username: String,
+    pub display_name: String,
+    pub id: Uuid,
+}
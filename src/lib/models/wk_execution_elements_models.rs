@@ -5,6 +5,7 @@ use crate::schema::wkexecutionelements;
 use crate::lib::models::{
     workout_execution_models::WorkoutExecution,
     exercise_models::Exercise,
+    unit_models::{InUnits, Units},
 };
 
 #[derive(Serialize, Deserialize, Queryable, Identifiable, Associations, Clone, Debug, PartialEq)]
@@ -26,9 +27,11 @@ pub struct WkExecutionElement {
 }
 
 
-#[derive(Insertable, Deserialize, Serialize)]
+#[derive(Insertable, AsChangeset, Deserialize, Serialize)]
 #[diesel(table_name = wkexecutionelements)]
+#[diesel(treat_none_as_null = true)]
 pub struct NewWkExecutionElement {
+    pub id: Option<Uuid>,
     pub workout_execution_id: Uuid,
     pub exercise_id: Uuid,
     pub position: i16,
@@ -43,6 +46,7 @@ pub struct NewWkExecutionElement {
 impl Default for NewWkExecutionElement {
     fn default() -> Self {
         NewWkExecutionElement { 
+            id: None,
             workout_execution_id: uuid::Uuid::new_v4(), 
             exercise_id: uuid::Uuid::new_v4(), 
             position: 0,
@@ -79,3 +83,15 @@ pub struct WkExecutionElementFull{
     pub necessary_equipment: Option<i16>,
     pub exercise_type: Option<i16>,
 }
+
+impl InUnits for WkExecutionElement {
+    fn in_units(self, units: &Units) -> Self {
+        WkExecutionElement { weight: self.weight.map(|kg| units.load_from_kg(kg)), ..self }
+    }
+}
+
+impl InUnits for WkExecutionElementFull {
+    fn in_units(self, units: &Units) -> Self {
+        WkExecutionElementFull { weight: self.weight.map(|kg| units.load_from_kg(kg)), ..self }
+    }
+}
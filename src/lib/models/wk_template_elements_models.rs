@@ -5,6 +5,7 @@ use crate::schema::wktemplateelements;
 use crate::lib::models::{
     workout_templates_models::WorkoutTemplate,
     exercise_models::Exercise,
+    unit_models::{InUnits, Units},
 };
 
 #[derive(Serialize, Deserialize, Queryable, Identifiable, Associations)]
@@ -27,6 +28,7 @@ pub struct WkTemplateElement {
 #[derive(Insertable, Deserialize, Serialize)]
 #[diesel(table_name = wktemplateelements)]
 pub struct NewWkTemplateElement {
+    pub id: Option<Uuid>,
     pub workout_template_id: Uuid,
     pub exercise_id: Uuid,
     pub position: i16,
@@ -39,6 +41,7 @@ pub struct NewWkTemplateElement {
 impl Default for NewWkTemplateElement {
     fn default() -> Self {
         NewWkTemplateElement { 
+            id: None,
             workout_template_id: uuid::Uuid::new_v4(), 
             exercise_id: uuid::Uuid::new_v4(), 
             position: 0, 
@@ -72,3 +75,15 @@ pub struct WkTemplateElementFull{
     pub necessary_equipment: Option<i16>,
     pub exercise_type: Option<i16>,
 }
+
+impl InUnits for WkTemplateElement {
+    fn in_units(self, units: &Units) -> Self {
+        WkTemplateElement { weight: self.weight.map(|kg| units.load_from_kg(kg)), ..self }
+    }
+}
+
+impl InUnits for WkTemplateElementFull {
+    fn in_units(self, units: &Units) -> Self {
+        WkTemplateElementFull { weight: self.weight.map(|kg| units.load_from_kg(kg)), ..self }
+    }
+}
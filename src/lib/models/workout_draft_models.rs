@@ -0,0 +1,170 @@
+use std::collections::HashMap;
+use chrono::NaiveDate;
+use uuid::Uuid;
+use serde::{Serialize, Deserialize};
+use super::wk_template_elements_models::WkTemplateElement;
+use super::wk_execution_elements_models::WkExecutionElement;
+use super::unit_models::{InUnits, Units};
+
+/// Seconds a set is assumed to take when there's no earlier one to copy it from
+pub const DEFAULT_SET_TIME: i32 = 30;
+
+/// One set of a draft, in the shape executions are created with
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct DraftSet {
+    pub exercise_id: Uuid,
+    pub position: i16,
+    pub exercise_number: i16,
+    pub reps: i16,
+    pub set_number: i16,
+    pub weight: Option<f32>,
+    pub rest: i16,
+    pub super_set: Option<i16>,
+    pub time: i32,
+}
+
+/// Execution of a template ready to be filled in and posted back once the workout is done
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct WorkoutDraft {
+    pub workout_template_id: Uuid,
+    pub date: NaiveDate,
+    pub survey: i16,
+    pub elements: Vec<DraftSet>,
+}
+
+impl InUnits for DraftSet {
+    fn in_units(self, units: &Units) -> Self {
+        DraftSet { weight: self.weight.map(|kg| units.load_from_kg(kg)), ..self }
+    }
+}
+
+impl InUnits for WorkoutDraft {
+    fn in_units(self, units: &Units) -> Self {
+        WorkoutDraft { elements: self.elements.in_units(units), ..self }
+    }
+}
+
+impl WorkoutDraft {
+    /// Expands each template element into one row per set.
+    ///
+    /// Every element is its own `exercise_number`, in the order of the template. Elements of a
+    /// super set take turns, one set each, and super sets are numbered from 0 in the order they
+    /// come. Reps, weight and time of each set come from the same set in `last_sets`, the sets of
+    /// the exercise in the user's latest execution with it, or its last one if that time had fewer,
+    /// and from the template otherwise.
+    pub fn new(workout_template_id: Uuid, date: NaiveDate, mut elements: Vec<WkTemplateElement>, last_sets: &HashMap<Uuid, Vec<WkExecutionElement>>) -> WorkoutDraft {
+        elements.sort_by_key(|element| element.position);
+
+        // Consecutive elements sharing a super set are done together
+        let mut blocks: Vec<Vec<(i16, &WkTemplateElement)>> = Vec::new();
+        for (exercise_number, element) in elements.iter().enumerate() {
+            match blocks.last_mut() {
+                Some(block) if element.super_set.is_some() && block[0].1.super_set == element.super_set => block.push((exercise_number as i16, element)),
+                _ => blocks.push(vec![(exercise_number as i16, element)]),
+            }
+        }
+
+        let mut rows = Vec::new();
+        let mut super_sets = 0;
+        for block in blocks {
+            let super_set = match block[0].1.super_set {
+                Some(_) => {
+                    super_sets += 1;
+                    Some(super_sets - 1)
+                },
+                None => None,
+            };
+            let rounds = block.iter().map(|(_, element)| element.sets).max().unwrap_or(0);
+            for set_number in 0..rounds {
+                for (exercise_number, element) in block.iter().filter(|(_, element)| set_number < element.sets) {
+                    let previous = last_sets.get(&element.exercise_id)
+                        .and_then(|sets| sets.get(set_number as usize).or(sets.last()));
+                    rows.push(DraftSet {
+                        exercise_id: element.exercise_id,
+                        position: rows.len() as i16,
+                        exercise_number: *exercise_number,
+                        reps: previous.map_or(element.reps, |set| set.reps),
+                        set_number,
+                        weight: previous.map_or(element.weight, |set| set.weight),
+                        rest: element.rest,
+                        super_set,
+                        time: previous.map_or(DEFAULT_SET_TIME, |set| set.time),
+                    });
+                }
+            }
+        }
+        WorkoutDraft { workout_template_id, date, survey: 0, elements: rows }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // TEST CASES
+    // * Rows per set, super sets interleaved
+    // * Prefilled from the last sets, template otherwise
+
+    fn element(position: i16, sets: i16, super_set: Option<i16>) -> WkTemplateElement {
+        WkTemplateElement {
+            id: Uuid::new_v4(),
+            workout_template_id: Uuid::new_v4(),
+            exercise_id: Uuid::new_v4(),
+            position,
+            reps: 10,
+            sets,
+            weight: Some(40.0),
+            rest: 60,
+            super_set,
+        }
+    }
+
+    fn date() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2024, 11, 11).unwrap()
+    }
+
+    #[test]
+    fn test_expand_sets() {
+        // Shuffled, with a super set of uneven elements in the middle
+        let elements = vec![element(3, 1, None), element(1, 2, Some(1)), element(0, 2, None), element(2, 3, Some(1))];
+        let draft = WorkoutDraft::new(Uuid::new_v4(), date(), elements, &HashMap::new());
+
+        let rows: Vec<(i16, i16, i16, Option<i16>)> = draft.elements.iter()
+            .map(|row| (row.position, row.exercise_number, row.set_number, row.super_set))
+            .collect();
+        assert_eq!(rows, vec![
+            (0, 0, 0, None),
+            (1, 0, 1, None),
+            (2, 1, 0, Some(0)),
+            (3, 2, 0, Some(0)),
+            (4, 1, 1, Some(0)),
+            (5, 2, 1, Some(0)),
+            (6, 2, 2, Some(0)),
+            (7, 3, 0, None),
+        ]);
+        assert!(draft.elements.iter().all(|row| row.reps == 10 && row.weight == Some(40.0) && row.time == DEFAULT_SET_TIME));
+    }
+
+    #[test]
+    fn test_prefill_last_sets() {
+        let squat = element(0, 3, None);
+        let last = |set_number: i16, reps: i16, weight: f32| WkExecutionElement {
+            id: Uuid::new_v4(),
+            workout_execution_id: Uuid::new_v4(),
+            exercise_id: squat.exercise_id,
+            position: set_number,
+            exercise_number: 0,
+            reps,
+            set_number,
+            weight: Some(weight),
+            rest: 120,
+            super_set: None,
+            time: 45,
+        };
+        let last_sets = HashMap::from([(squat.exercise_id, vec![last(0, 5, 100.0), last(1, 4, 95.0)])]);
+        let draft = WorkoutDraft::new(Uuid::new_v4(), date(), vec![squat], &last_sets);
+
+        let sets: Vec<(i16, Option<f32>, i16, i32)> = draft.elements.iter().map(|row| (row.reps, row.weight, row.rest, row.time)).collect();
+        assert_eq!(sets, vec![(5, Some(100.0), 60, 45), (4, Some(95.0), 60, 45), (4, Some(95.0), 60, 45)]);
+    }
+}
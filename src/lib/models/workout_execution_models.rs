@@ -1,11 +1,24 @@
 use diesel::prelude::*;
 use uuid::Uuid;
-use chrono::{NaiveDate, Utc};
+use chrono::{NaiveDate, NaiveDateTime, Utc};
 use serde::{Serialize, Deserialize};
 use super::workout_templates_models::WorkoutTemplate;
+use super::exercise_content_models::WithExerciseContent;
 use super::wk_execution_elements_models::{WkExecutionElement, WkExecutionElementFull};
+use super::reaction_models::ReactionCount;
+use super::unit_models::{InUnits, Units};
 use crate::schema::workoutexecutions;
 
+// Status of an execution
+pub const COMPLETED: i16 = 0;
+pub const IN_PROGRESS: i16 = 1;
+pub const ABANDONED: i16 = 2;
+
+/// Hours without any set logged after which a session in progress is abandoned
+pub const SESSION_TIMEOUT_HOURS: i64 = 6;
+
+/// Workout done on a template
+/// * Only completed executions count anywhere else, sessions are still being filled in or were given up on
 #[derive(Queryable, Identifiable, Associations, Selectable, Serialize, Deserialize, Clone)]
 #[diesel(table_name = workoutexecutions)]
 #[diesel(belongs_to(WorkoutTemplate))]
@@ -14,39 +27,80 @@ pub struct WorkoutExecution {
     pub workout_template_id: Uuid,
     pub date: NaiveDate,
     pub survey: i16,
+    pub program_slot_id: Option<Uuid>,
+    pub status: i16,
+    pub last_activity: Option<NaiveDateTime>,
 }
 
 #[derive(Insertable, Serialize, Deserialize)]
 #[diesel(table_name = workoutexecutions)]
 #[diesel(belongs_to(WorkoutExecution))]
 pub struct NewWorkoutExecution {
+    pub id: Option<Uuid>,
     pub workout_template_id: Uuid,
     pub date: NaiveDate,
     pub survey: i16,
+    pub program_slot_id: Option<Uuid>,
 }
 impl Default for NewWorkoutExecution {
     fn default() -> Self {
         NewWorkoutExecution {
+            id: None,
             workout_template_id: Uuid::new_v4(),
             date: Utc::now().naive_utc().date(),
             survey: 0,
+            program_slot_id: None,
         }
     }
 }
 
+/// Execution started before the workout, its sets are logged as they are done
+#[derive(Insertable)]
+#[diesel(table_name = workoutexecutions)]
+pub struct NewWorkoutSession {
+    pub workout_template_id: Uuid,
+    pub date: NaiveDate,
+    pub survey: i16,
+    pub program_slot_id: Option<Uuid>,
+    pub status: i16,
+    pub last_activity: NaiveDateTime,
+}
+impl NewWorkoutSession {
+    pub fn new(workout_template_id: Uuid, date: NaiveDate, program_slot_id: Option<Uuid>, now: NaiveDateTime) -> Self {
+        NewWorkoutSession { workout_template_id, date, survey: 0, program_slot_id, status: IN_PROGRESS, last_activity: now }
+    }
+}
+
 /// Workout template with all the elements that point to it
 #[derive(Serialize, Deserialize)]
 pub struct WkExecutionWithElements{
     #[serde(flatten)]
     pub workout_execution: WorkoutExecution,  
-    pub elements: Vec<WkExecutionElement>
+    pub elements: Vec<WkExecutionElement>,
+    #[serde(default)]
+    pub reactions: Vec<ReactionCount>,
 }
 
 /// Workout template with all the elements that point to it
 /// Including the exercise info each element also points to
+/// and, if requested, its instructional content
 #[derive(Serialize, Deserialize)]
 pub struct WorkoutExecutionFull {
     #[serde(flatten)]
     pub workout_execution: WorkoutExecution,
-    pub elements: Vec<WkExecutionElementFull>,
+    pub elements: Vec<WithExerciseContent<WkExecutionElementFull>>,
+    #[serde(default)]
+    pub reactions: Vec<ReactionCount>,
+}
+
+impl InUnits for WkExecutionWithElements {
+    fn in_units(self, units: &Units) -> Self {
+        WkExecutionWithElements { elements: self.elements.in_units(units), ..self }
+    }
+}
+
+impl InUnits for WorkoutExecutionFull {
+    fn in_units(self, units: &Units) -> Self {
+        WorkoutExecutionFull { elements: self.elements.in_units(units), ..self }
+    }
 }
@@ -0,0 +1,26 @@
+use diesel::prelude::*;
+use uuid::Uuid;
+use chrono::NaiveDate;
+use serde::{Serialize, Deserialize};
+use super::wk_execution_elements_models::NewWkExecutionElement;
+use crate::schema::workoutimports;
+
+/// Marks an execution as imported from another app
+#[derive(Queryable, Insertable, Selectable, Serialize, Deserialize, Debug, PartialEq)]
+#[diesel(table_name = workoutimports)]
+pub struct WorkoutImport {
+    pub user_id: Uuid,
+    pub import_key: String,
+    pub workout_execution_id: Uuid,
+}
+
+/// Workout from another app, with its exercises already mapped
+/// * `import_key` must be the same every time the workout is uploaded
+/// * Executions are added to the user's template named `template_name`, created if missing
+pub struct ImportedWorkout {
+    pub import_key: String,
+    pub template_name: String,
+    pub date: NaiveDate,
+    /// `workout_execution_id` is set when the execution is inserted
+    pub elements: Vec<NewWkExecutionElement>,
+}
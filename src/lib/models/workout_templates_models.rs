@@ -2,7 +2,9 @@ use diesel::prelude::*;
 use uuid::Uuid;
 use serde::{Serialize, Deserialize};
 use super::user_models::User;
+use super::exercise_content_models::WithExerciseContent;
 use super::wk_template_elements_models::{WkTemplateElement, WkTemplateElementFull};
+use super::unit_models::{InUnits, Units};
 use crate::schema::workouttemplates;
 
 
@@ -23,6 +25,7 @@ pub struct WorkoutTemplate {
 #[diesel(table_name = workouttemplates)]
 #[diesel(belongs_to(User))]
 pub struct NewWorkoutTemplate {
+    pub id: Option<Uuid>,
     pub user_id: uuid::Uuid,
     pub name: String,
     pub description: Option<String>,
@@ -31,6 +34,7 @@ pub struct NewWorkoutTemplate {
 impl Default for NewWorkoutTemplate {
     fn default() -> Self {
         NewWorkoutTemplate { 
+            id: None,
             user_id: Uuid::new_v4(), 
             name: "Placeholder".to_string(), 
             description: None, 
@@ -49,9 +53,22 @@ pub struct WkTemplateWithElements{
 
 /// Workout template with all the elements that point to it
 /// Including the exercise info each element also points to
+/// and, if requested, its instructional content
 #[derive(Serialize, Deserialize)]
 pub struct WorkoutTemplateFull {
     #[serde(flatten)]
     pub workout_template: WorkoutTemplate,
-    pub elements: Vec<WkTemplateElementFull>,
+    pub elements: Vec<WithExerciseContent<WkTemplateElementFull>>,
+}
+
+impl InUnits for WkTemplateWithElements {
+    fn in_units(self, units: &Units) -> Self {
+        WkTemplateWithElements { elements: self.elements.in_units(units), ..self }
+    }
+}
+
+impl InUnits for WorkoutTemplateFull {
+    fn in_units(self, units: &Units) -> Self {
+        WorkoutTemplateFull { elements: self.elements.in_units(units), ..self }
+    }
 }
@@ -1,10 +1,25 @@
-use lambda_http::{Body, Response, http::{StatusCode, HeaderMap}, Error};
+use chrono::NaiveDate;
+use lambda_http::{Body, Request, RequestExt, Response, http::{StatusCode, HeaderMap}, Error};
 use base64::prelude::*;
 use uuid::Uuid;
 use serde::{Deserialize, Serialize};
 use serde_json::to_string;
+use tracing::{error, warn};
+
+use crate::lib::db::DBConnector;
+use crate::lib::db::users_db::lookup_units;
+use crate::lib::db::follows_db::can_view_profile;
+use crate::lib::db::notifications_db::insert_notification;
+use crate::lib::db::workout_executions_db::lookup_workout_execution_validated;
+use crate::lib::errors::DBError;
+use crate::lib::models::notification_models::NewNotification;
+use crate::lib::models::unit_models::Units;
+use crate::lib::models::workout_execution_models::COMPLETED;
 
 pub const UUID_PATTERN: &str = r"[0-9a-fA-F-]{36}";
+pub const ADMIN_GROUP: &str = "admin";
+pub const DEFAULT_PAGE_SIZE: i64 = 20;
+pub const MAX_PAGE_SIZE: i64 = 100;
 
 pub fn build_resp<T>(status: StatusCode, data: T) -> Response<Body>
 where
@@ -22,13 +37,51 @@ where
         .expect("Failed to build response")
 }
 
+/// Response with a file to be downloaded, instead of a JSON body
+pub fn build_attachment_resp(filename: &str, content_type: &str, data: Vec<u8>) -> Response<Body> {
+    Response::builder()
+        .status(StatusCode::OK)
+        .header("Content-Type", content_type)
+        .header("Content-Disposition", format!("attachment; filename=\"{}\"", filename))
+        .body(Body::Binary(data))
+        .expect("Failed to build response")
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 struct Claims {
     sub: String,
+    #[serde(rename = "cognito:groups", default)]
+    groups: Vec<String>,
 }
 
 
 pub fn extract_sub(headers: &HeaderMap, url_user_id: Option<Uuid>) -> Result<Uuid, Response<Body>> {
+    let claims = extract_claims(headers)?;
+    let extracted_id = Uuid::parse_str(&claims.sub).map_err(|_| build_resp(StatusCode::UNAUTHORIZED, "Invalid UUID in token"))?;
+
+    // This is the only code that should trigger
+    if let Some(url_id) = url_user_id {
+        if extracted_id != url_id {
+            return Err(build_resp(StatusCode::FORBIDDEN, "Forbidden"));
+        }
+    }
+
+    Ok(extracted_id)
+}
+
+/// Same as `extract_sub`, but the caller must also belong to the admin group
+pub fn extract_admin(headers: &HeaderMap) -> Result<Uuid, Response<Body>> {
+    let claims = extract_claims(headers)?;
+    let extracted_id = Uuid::parse_str(&claims.sub).map_err(|_| build_resp(StatusCode::UNAUTHORIZED, "Invalid UUID in token"))?;
+
+    if !claims.groups.iter().any(|group| group == ADMIN_GROUP) {
+        return Err(build_resp(StatusCode::FORBIDDEN, "Forbidden"));
+    }
+
+    Ok(extracted_id)
+}
+
+fn extract_claims(headers: &HeaderMap) -> Result<Claims, Response<Body>> {
 
     let auth_header = headers.get("Authorization").ok_or_else(|| build_resp(StatusCode::UNAUTHORIZED, "Missing Authorization header"))?;
     let token = auth_header.to_str().map_err(|_| build_resp(StatusCode::UNAUTHORIZED, "Invalid header value"))?.strip_prefix("Bearer ").ok_or_else(|| build_resp(StatusCode::UNAUTHORIZED, "Invalid Authorization header format"))?;
@@ -39,17 +92,124 @@ pub fn extract_sub(headers: &HeaderMap, url_user_id: Option<Uuid>) -> Result<Uui
     }
 
     let payload = BASE64_URL_SAFE_NO_PAD.decode(parts[1]).map_err(|e| build_resp(StatusCode::UNAUTHORIZED, &e.to_string()))?;
-    let claims: Claims = serde_json::from_slice(&payload).map_err(|e| build_resp(StatusCode::UNAUTHORIZED, &e.to_string()))?;
-    let extracted_id = Uuid::parse_str(&claims.sub).map_err(|_| build_resp(StatusCode::UNAUTHORIZED, "Invalid UUID in token"))?;
+    serde_json::from_slice(&payload).map_err(|e| build_resp(StatusCode::UNAUTHORIZED, &e.to_string()))
+}
 
-    // This is the only code that should trigger
-    if let Some(url_id) = url_user_id {
-        if extracted_id != url_id {
-            return Err(build_resp(StatusCode::FORBIDDEN, "Forbidden"));
+/// Locales from the `Accept-Language` header, lowercased and by order of preference
+/// * Region tags fall back to their language (es-mx -> es)
+/// * Wildcards and tags with q=0 are ignored
+pub fn accept_languages(headers: &HeaderMap) -> Vec<String> {
+    let header = match headers.get("Accept-Language").and_then(|value| value.to_str().ok()) {
+        Some(header) => header,
+        None => return Vec::new()
+    };
+
+    let mut weighted: Vec<(f32, String)> = header.split(',')
+        .filter_map(|entry| {
+            let mut parts = entry.split(';');
+            let tag = parts.next()?.trim().to_lowercase();
+            let quality = parts
+                .find_map(|param| param.trim().strip_prefix("q="))
+                .map_or(Some(1.0), |q| q.parse::<f32>().ok())?;
+            (!tag.is_empty() && tag != "*" && quality > 0.0).then_some((quality, tag))
+        })
+        .collect();
+    weighted.sort_by(|a, b| b.0.total_cmp(&a.0));
+
+    let mut locales = Vec::new();
+    for (_, tag) in weighted {
+        let language = tag.split('-').next().unwrap_or_default().to_string();
+        for locale in [tag, language] {
+            if !locales.contains(&locale) {
+                locales.push(locale);
+            }
         }
     }
+    locales
+}
 
-    Ok(extracted_id)
+/// Units measures are taken and returned in
+/// * The `units` query parameter (metric or imperial) overrides the user's preference
+/// * Metric if the user doesn't exist, so handlers can still answer with their own not found
+pub async fn request_units(event: &Request, user_id: Uuid, connector: &DBConnector) -> Result<Units, Response<Body>> {
+    if let Some(units) = event.query_string_parameters().first("units") {
+        return units.parse::<Units>().map_err(|mes| build_resp(StatusCode::BAD_REQUEST, mes));
+    }
+    match lookup_units(user_id, connector).await {
+        Ok(units) => Ok(units),
+        Err(DBError::ItemNotFound(_)) => Ok(Units::METRIC),
+        Err(error) => {
+            error!("INTERNAL SERVER ERROR: {}", error);
+            Err(build_resp(StatusCode::INTERNAL_SERVER_ERROR, ""))
+        }
+    }
+}
+
+/// Parse the `limit` and `offset` query parameters of paginated lists
+/// * Limit defaults to `DEFAULT_PAGE_SIZE` and can't go over `MAX_PAGE_SIZE`
+pub fn pagination(event: &Request) -> Result<(i64, i64), Response<Body>> {
+    let params = event.query_string_parameters();
+    let parse = |name: &str, default: i64| match params.first(name) {
+        Some(value) => value.parse::<i64>()
+            .map_err(|_| build_resp(StatusCode::BAD_REQUEST, format!("Invalid '{}', expected a number", name))),
+        None => Ok(default),
+    };
+    let (limit, offset) = (parse("limit", DEFAULT_PAGE_SIZE)?, parse("offset", 0)?);
+    if !(1..=MAX_PAGE_SIZE).contains(&limit) || offset < 0 {
+        return Err(build_resp(StatusCode::BAD_REQUEST, format!("'limit' must be between 1 and {} and 'offset' can't be negative", MAX_PAGE_SIZE)));
+    }
+    Ok((limit, offset))
+}
+
+/// Parse the `from` and `to` query parameters
+pub fn date_range(event: &Request) -> Result<(Option<NaiveDate>, Option<NaiveDate>), Response<Body>> {
+    let params = event.query_string_parameters();
+    let parse = |name: &str| match params.first(name) {
+        Some(value) => NaiveDate::parse_from_str(value, "%Y-%m-%d")
+            .map(Some)
+            .map_err(|_| build_resp(StatusCode::BAD_REQUEST, format!("Invalid '{}' date, expected YYYY-MM-DD", name))),
+        None => Ok(None),
+    };
+    let (from, to) = (parse("from")?, parse("to")?);
+    if let (Some(from), Some(to)) = (from, to) {
+        if from > to {
+            return Err(build_resp(StatusCode::BAD_REQUEST, "'from' can't be after 'to'"));
+        }
+    }
+    Ok((from, to))
+}
+
+/// Check the execution belongs to `owner_id` and the viewer can see the owner's social data
+/// * Not found if the execution isn't the owner's, or if it isn't completed and the viewer is someone else
+/// * Forbidden if the owner's profile is private to the viewer
+pub async fn check_execution_visible(viewer_id: Uuid, owner_id: Uuid, execution_id: Uuid, connector: &DBConnector) -> Result<(), Response<Body>> {
+    match lookup_workout_execution_validated(execution_id, owner_id, connector).await {
+        Ok(execution) if viewer_id != owner_id && execution.status != COMPLETED => {
+            return Err(build_resp(StatusCode::NOT_FOUND, "No execution exists with the corresponding id"))
+        },
+        Ok(_) => (),
+        Err(DBError::ItemNotFound(mes)) => return Err(build_resp(StatusCode::NOT_FOUND, mes)),
+        Err(error) => {
+            error!("INTERNAL SERVER ERROR: {}", error);
+            return Err(build_resp(StatusCode::INTERNAL_SERVER_ERROR, ""))
+        }
+    };
+    match can_view_profile(viewer_id, owner_id, connector).await {
+        Ok(true) => Ok(()),
+        Ok(false) => Err(build_resp(StatusCode::FORBIDDEN, "This profile is private")),
+        Err(DBError::ItemNotFound(mes)) => Err(build_resp(StatusCode::NOT_FOUND, mes)),
+        Err(error) => {
+            error!("INTERNAL SERVER ERROR: {}", error);
+            Err(build_resp(StatusCode::INTERNAL_SERVER_ERROR, ""))
+        }
+    }
+}
+
+/// Write a notification caused by the request, its failure doesn't fail the request
+pub async fn notify(new_notification: NewNotification, connector: &DBConnector) {
+    if let Err(error) = insert_notification(&new_notification, connector).await {
+        warn!("Could not write notification: {}", error);
+    }
 }
 
 pub fn not_found() -> Result<Response<Body>, Error> {
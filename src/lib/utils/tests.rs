@@ -15,6 +15,7 @@ use crate::lib::models::workout_execution_models::NewWorkoutExecution;
 use crate::lib::models::workout_templates_models::NewWorkoutTemplate;
 use crate::lib::models::wk_template_elements_models::NewWkTemplateElement;
 use crate::lib::db::DBConnector;
+use crate::lib::utils::handlers::ADMIN_GROUP;
 use crate::lib::db::exercises_db::insert_exercise;
 use crate::lib::db::users_db::insert_user;
 use crate::lib::db::workout_templates_db::insert_workout_template;
@@ -44,12 +45,23 @@ pub async fn pg_container() -> (DBConnector, ContainerAsync<postgres::Postgres>)
 struct Claims {
     sub: String,
     exp: usize,
+    #[serde(rename = "cognito:groups")]
+    groups: Vec<String>,
 }
 
 pub fn test_jwt(user_id: Uuid) -> String {
+    encode_jwt(user_id, Vec::new())
+}
+
+pub fn test_admin_jwt(user_id: Uuid) -> String {
+    encode_jwt(user_id, vec![ADMIN_GROUP.to_string()])
+}
+
+fn encode_jwt(user_id: Uuid, groups: Vec<String>) -> String {
     let my_claims = Claims {
         sub: user_id.to_string(),
         exp: 10000000000, // Expiration timestamp
+        groups,
     };
 
     let encoding_key = EncodingKey::from_secret("secret".as_ref());
@@ -1,5 +1,96 @@
 // @generated automatically by Diesel CLI.
 
+diesel::table! {
+    bodymeasurements (id) {
+        id -> Uuid,
+        user_id -> Uuid,
+        date -> Date,
+        weight -> Nullable<Float4>,
+        body_fat -> Nullable<Float4>,
+        waist -> Nullable<Float4>,
+        chest -> Nullable<Float4>,
+        arms -> Nullable<Float4>,
+        thighs -> Nullable<Float4>,
+        hips -> Nullable<Float4>,
+    }
+}
+
+diesel::table! {
+    calendartokens (user_id) {
+        user_id -> Uuid,
+        token -> Uuid,
+        date_created -> Timestamp,
+    }
+}
+
+diesel::table! {
+    challengeparticipants (challenge_id, user_id) {
+        challenge_id -> Uuid,
+        user_id -> Uuid,
+        joined -> Bool,
+        invited_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    challenges (id) {
+        id -> Uuid,
+        creator_id -> Uuid,
+        name -> Varchar,
+        metric -> Int2,
+        exercise_id -> Nullable<Uuid>,
+        start_date -> Date,
+        end_date -> Date,
+        date_created -> Timestamp,
+    }
+}
+
+diesel::table! {
+    comments (id) {
+        id -> Uuid,
+        workout_execution_id -> Uuid,
+        user_id -> Uuid,
+        content -> Text,
+        date_created -> Timestamp,
+        date_edited -> Nullable<Timestamp>,
+    }
+}
+
+diesel::table! {
+    exercisealiases (id) {
+        id -> Uuid,
+        exercise_id -> Uuid,
+        #[max_length = 16]
+        locale -> Varchar,
+        #[max_length = 255]
+        name -> Varchar,
+        preferred -> Bool,
+    }
+}
+
+diesel::table! {
+    exerciseinstructions (exercise_id) {
+        exercise_id -> Uuid,
+        description -> Nullable<Text>,
+        steps -> Array<Text>,
+        cues -> Array<Text>,
+        common_mistakes -> Array<Text>,
+    }
+}
+
+diesel::table! {
+    exercisemedia (id) {
+        id -> Uuid,
+        exercise_id -> Uuid,
+        position -> Int2,
+        media_type -> Int2,
+        url -> Text,
+        thumbnail_url -> Nullable<Text>,
+        width -> Nullable<Int4>,
+        height -> Nullable<Int4>,
+    }
+}
+
 diesel::table! {
     exercises (id) {
         id -> Uuid,
@@ -9,6 +100,111 @@ diesel::table! {
         secondary_muscle_group -> Nullable<Int2>,
         necessary_equipment -> Nullable<Int2>,
         exercise_type -> Nullable<Int2>,
+        retired -> Bool,
+        owner_id -> Nullable<Uuid>,
+    }
+}
+
+diesel::table! {
+    follows (follower_id, followed_id) {
+        follower_id -> Uuid,
+        followed_id -> Uuid,
+        accepted -> Bool,
+        requested_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    notifications (id) {
+        id -> Uuid,
+        user_id -> Uuid,
+        kind -> Int2,
+        actor_id -> Nullable<Uuid>,
+        workout_execution_id -> Nullable<Uuid>,
+        workout_template_id -> Nullable<Uuid>,
+        comment_id -> Nullable<Uuid>,
+        read -> Bool,
+        date_created -> Timestamp,
+    }
+}
+
+diesel::table! {
+    programenrolments (id) {
+        id -> Uuid,
+        program_id -> Uuid,
+        user_id -> Uuid,
+        start_date -> Date,
+        date_ended -> Nullable<Date>,
+        date_created -> Timestamp,
+    }
+}
+
+diesel::table! {
+    programs (id) {
+        id -> Uuid,
+        user_id -> Uuid,
+        #[max_length = 255]
+        name -> Varchar,
+        description -> Nullable<Text>,
+        weeks -> Int2,
+        date_created -> Date,
+    }
+}
+
+diesel::table! {
+    programslots (id) {
+        id -> Uuid,
+        program_id -> Uuid,
+        workout_template_id -> Uuid,
+        week -> Int2,
+        day -> Int2,
+        reps -> Nullable<Int2>,
+        intensity -> Nullable<Int2>,
+    }
+}
+
+diesel::table! {
+    progressionrules (wk_template_element_id) {
+        wk_template_element_id -> Uuid,
+        kind -> Int2,
+        weight_increment -> Float4,
+        min_reps -> Nullable<Int2>,
+        max_reps -> Nullable<Int2>,
+        deload_after -> Nullable<Int2>,
+        deload_percent -> Int2,
+        date_created -> Timestamp,
+    }
+}
+
+diesel::table! {
+    reactions (workout_execution_id, user_id) {
+        workout_execution_id -> Uuid,
+        user_id -> Uuid,
+        reaction -> Int2,
+        date_created -> Timestamp,
+    }
+}
+
+diesel::table! {
+    scheduledworkouts (id) {
+        id -> Uuid,
+        user_id -> Uuid,
+        workout_template_id -> Uuid,
+        start_date -> Date,
+        end_date -> Nullable<Date>,
+        weekdays -> Nullable<Array<Int2>>,
+        date_created -> Timestamp,
+    }
+}
+
+diesel::table! {
+    syncchanges (kind, id) {
+        kind -> Int2,
+        id -> Uuid,
+        user_id -> Uuid,
+        created -> Int8,
+        updated -> Int8,
+        deleted -> Bool,
     }
 }
 
@@ -24,6 +220,17 @@ diesel::table! {
         gender -> Nullable<Int2>,
         fitness_goal -> Nullable<Int2>,
         fitness_level -> Nullable<Int2>,
+        discoverable -> Bool,
+        deletion_scheduled_for -> Nullable<Timestamp>,
+        weight_unit -> Int2,
+        length_unit -> Int2,
+        private_profile -> Bool,
+        notify_new_followers -> Bool,
+        notify_comments -> Bool,
+        notify_reactions -> Bool,
+        notify_prs -> Bool,
+        notify_template_saves -> Bool,
+        show_in_leaderboards -> Bool,
     }
 }
 
@@ -63,6 +270,17 @@ diesel::table! {
         workout_template_id -> Uuid,
         date -> Date,
         survey -> Int2,
+        program_slot_id -> Nullable<Uuid>,
+        status -> Int2,
+        last_activity -> Nullable<Timestamp>,
+    }
+}
+
+diesel::table! {
+    workoutimports (user_id, import_key) {
+        user_id -> Uuid,
+        import_key -> Text,
+        workout_execution_id -> Uuid,
     }
 }
 
@@ -77,18 +295,65 @@ diesel::table! {
     }
 }
 
+diesel::joinable!(bodymeasurements -> users (user_id));
+diesel::joinable!(calendartokens -> users (user_id));
+diesel::joinable!(challengeparticipants -> challenges (challenge_id));
+diesel::joinable!(challengeparticipants -> users (user_id));
+diesel::joinable!(challenges -> exercises (exercise_id));
+diesel::joinable!(challenges -> users (creator_id));
+diesel::joinable!(comments -> users (user_id));
+diesel::joinable!(comments -> workoutexecutions (workout_execution_id));
+diesel::joinable!(exercisealiases -> exercises (exercise_id));
+diesel::joinable!(exerciseinstructions -> exercises (exercise_id));
+diesel::joinable!(exercisemedia -> exercises (exercise_id));
+diesel::joinable!(exercises -> users (owner_id));
+diesel::joinable!(notifications -> comments (comment_id));
+diesel::joinable!(notifications -> workoutexecutions (workout_execution_id));
+diesel::joinable!(notifications -> workouttemplates (workout_template_id));
+diesel::joinable!(programenrolments -> programs (program_id));
+diesel::joinable!(programenrolments -> users (user_id));
+diesel::joinable!(programs -> users (user_id));
+diesel::joinable!(programslots -> programs (program_id));
+diesel::joinable!(programslots -> workouttemplates (workout_template_id));
+diesel::joinable!(progressionrules -> wktemplateelements (wk_template_element_id));
+diesel::joinable!(reactions -> users (user_id));
+diesel::joinable!(reactions -> workoutexecutions (workout_execution_id));
+diesel::joinable!(scheduledworkouts -> users (user_id));
+diesel::joinable!(scheduledworkouts -> workouttemplates (workout_template_id));
+diesel::joinable!(syncchanges -> users (user_id));
 diesel::joinable!(wkexecutionelements -> exercises (exercise_id));
 diesel::joinable!(wkexecutionelements -> workoutexecutions (workout_execution_id));
 diesel::joinable!(wktemplateelements -> exercises (exercise_id));
 diesel::joinable!(wktemplateelements -> workouttemplates (workout_template_id));
+diesel::joinable!(workoutexecutions -> programslots (program_slot_id));
 diesel::joinable!(workoutexecutions -> workouttemplates (workout_template_id));
+diesel::joinable!(workoutimports -> users (user_id));
+diesel::joinable!(workoutimports -> workoutexecutions (workout_execution_id));
 diesel::joinable!(workouttemplates -> users (user_id));
 
 diesel::allow_tables_to_appear_in_same_query!(
+    bodymeasurements,
+    calendartokens,
+    challengeparticipants,
+    challenges,
+    comments,
+    exercisealiases,
+    exerciseinstructions,
+    exercisemedia,
     exercises,
+    follows,
+    notifications,
+    programenrolments,
+    programs,
+    programslots,
+    progressionrules,
+    reactions,
+    scheduledworkouts,
+    syncchanges,
     users,
     wkexecutionelements,
     wktemplateelements,
     workoutexecutions,
+    workoutimports,
     workouttemplates,
 );
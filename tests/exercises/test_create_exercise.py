@@ -0,0 +1,91 @@
+import unittest
+import uuid
+from tests.utils import TestHelper, TestError
+
+class TestExercisesCreateExercise(unittest.TestCase):
+
+    """
+    TEST CASES
+    * Not an admin
+    * Wrong payload
+    * Existing name
+    * Success
+    """
+
+    def test_create_exercise_not_admin(self):
+        try:
+            body = {"name": f"Exercise {TestHelper.random_string(8)}"}
+            forbidden = TestHelper().invoke(
+                    function="exercises",
+                    method="POST",
+                    path="/exercises",
+                    sub=str(uuid.uuid4()),
+                    body=body
+                    )
+            self.assertEqual(forbidden['statusCode'], 403)
+
+        except TestError as e:
+            print(f"TEST ERROR: {e}")
+            raise
+
+    def test_create_exercise_wrong_payload(self):
+        try:
+            body = {"not_name": "Bench Press"}
+            wrong_payload = TestHelper().invoke(
+                    function="exercises",
+                    method="POST",
+                    path="/exercises",
+                    sub=str(uuid.uuid4()),
+                    groups=["admin"],
+                    body=body
+                    )
+            self.assertEqual(wrong_payload['statusCode'], 400)
+
+        except TestError as e:
+            print(f"TEST ERROR: {e}")
+            raise
+
+    def test_create_exercise_existing(self):
+        try:
+            name = TestHelper().get_from_db("SELECT name FROM Exercises LIMIT 1;")[0][0]
+            body = {"name": name}
+            existing = TestHelper().invoke(
+                    function="exercises",
+                    method="POST",
+                    path="/exercises",
+                    sub=str(uuid.uuid4()),
+                    groups=["admin"],
+                    body=body
+                    )
+            self.assertEqual(existing['statusCode'], 409)
+
+        except TestError as e:
+            print(f"TEST ERROR: {e}")
+            raise
+
+    def test_create_exercise_success(self):
+        try:
+            body = {
+                    "name": f"Exercise {TestHelper.random_string(8)}",
+                    "main_muscle_group": 1,
+                    "secondary_muscle_group": None,
+                    "necessary_equipment": 2,
+                    "exercise_type": 1
+                    }
+            success = TestHelper().invoke(
+                    function="exercises",
+                    method="POST",
+                    path="/exercises",
+                    sub=str(uuid.uuid4()),
+                    groups=["admin"],
+                    body=body
+                    )
+            self.assertEqual(success['statusCode'], 201)
+
+        except TestError as e:
+            print(f"TEST ERROR: {e}")
+            raise
+
+
+if __name__ == '__main__':
+    unittest.main()
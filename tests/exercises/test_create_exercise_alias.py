@@ -0,0 +1,91 @@
+import unittest
+import json
+import uuid
+from tests.utils import TestHelper, TestError
+
+class TestExercisesCreateExerciseAlias(unittest.TestCase):
+
+    """
+    TEST CASES
+    * Not an admin
+    * Non existing exercise
+    * Success, found by alias and localized
+    """
+
+    def test_create_exercise_alias_not_admin(self):
+        try:
+            id = TestHelper().get_from_db("SELECT id FROM Exercises LIMIT 1;")[0][0]
+            forbidden = TestHelper().invoke(
+                    function="exercises",
+                    method="POST",
+                    path=f"/exercises/{id}/aliases",
+                    sub=str(uuid.uuid4()),
+                    path_params={"exercise_id": id},
+                    body={"locale": "es", "name": TestHelper.random_string(8)}
+                    )
+            self.assertEqual(forbidden['statusCode'], 403)
+
+        except TestError as e:
+            print(f"TEST ERROR: {e}")
+            raise
+
+    def test_create_exercise_alias_not_found(self):
+        try:
+            id = str(uuid.uuid4())
+            not_found = TestHelper().invoke(
+                    function="exercises",
+                    method="POST",
+                    path=f"/exercises/{id}/aliases",
+                    sub=str(uuid.uuid4()),
+                    groups=["admin"],
+                    path_params={"exercise_id": id},
+                    body={"locale": "es", "name": TestHelper.random_string(8)}
+                    )
+            self.assertEqual(not_found['statusCode'], 404)
+
+        except TestError as e:
+            print(f"TEST ERROR: {e}")
+            raise
+
+    def test_create_exercise_alias_success(self):
+        try:
+            created = TestHelper().invoke(
+                    function="exercises",
+                    method="POST",
+                    path="/exercises",
+                    sub=str(uuid.uuid4()),
+                    groups=["admin"],
+                    body={"name": f"Exercise {TestHelper.random_string(8)}"}
+                    )
+            id = json.loads(created['body'])['id']
+            alias = f"Ejercicio {TestHelper.random_string(8)}"
+            success = TestHelper().invoke(
+                    function="exercises",
+                    method="POST",
+                    path=f"/exercises/{id}/aliases",
+                    sub=str(uuid.uuid4()),
+                    groups=["admin"],
+                    path_params={"exercise_id": id},
+                    body={"locale": "es", "name": alias, "preferred": True}
+                    )
+            self.assertEqual(success['statusCode'], 201)
+
+            search = TestHelper().invoke(
+                    function="exercises",
+                    method="GET",
+                    path="/exercises",
+                    sub=str(uuid.uuid4()),
+                    query_params={"name": alias},
+                    headers={"Accept-Language": "es-ES,es;q=0.9"}
+                    )
+            self.assertEqual(search['statusCode'], 200)
+            exercises = json.loads(search['body'])['exercises']
+            self.assertEqual(exercises[0]['id'], id)
+            self.assertEqual(exercises[0]['name'], alias)
+
+        except TestError as e:
+            print(f"TEST ERROR: {e}")
+            raise
+
+if __name__ == '__main__':
+    unittest.main()
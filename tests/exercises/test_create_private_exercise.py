@@ -0,0 +1,62 @@
+import unittest
+import uuid
+from tests.utils import TestHelper, TestError
+
+class TestExercisesCreatePrivateExercise(unittest.TestCase):
+
+    """
+    TEST CASES
+    * Different user in claim
+    * Success
+    * Not visible to other users
+    """
+
+    def test_create_private_exercise_forbidden(self):
+        try:
+            id = TestHelper().get_from_db("SELECT id FROM Users LIMIT 1;")[0][0]
+            forbidden = TestHelper().invoke(
+                    function="exercises",
+                    method="POST",
+                    path=f"/users/{id}/exercises",
+                    path_params={"user_id": id},
+                    sub=str(uuid.uuid4()),
+                    body={"name": "Landmine press"}
+                    )
+            self.assertEqual(forbidden['statusCode'], 403)
+
+        except TestError as e:
+            print(f"TEST ERROR: {e}")
+            raise
+
+    def test_create_private_exercise_success(self):
+        try:
+            users = TestHelper().get_from_db("SELECT id FROM Users LIMIT 2;")
+            owner, other = users[0][0], users[1][0]
+            name = f"Private {TestHelper.random_string(8)}"
+            success = TestHelper().invoke(
+                    function="exercises",
+                    method="POST",
+                    path=f"/users/{owner}/exercises",
+                    path_params={"user_id": owner},
+                    sub=owner,
+                    body={"name": name, "exercise_type": 1}
+                    )
+            self.assertEqual(success['statusCode'], 201)
+
+            id = TestHelper().get_from_db(f"SELECT id FROM Exercises WHERE name = '{name}';")[0][0]
+            hidden = TestHelper().invoke(
+                    function="exercises",
+                    method="GET",
+                    path=f"/exercises/{id}",
+                    path_params={"exercise_id": id},
+                    sub=other
+                    )
+            self.assertEqual(hidden['statusCode'], 404)
+
+        except TestError as e:
+            print(f"TEST ERROR: {e}")
+            raise
+
+
+if __name__ == '__main__':
+    unittest.main()
@@ -17,6 +17,7 @@ class TestExercisesGetExercise(unittest.TestCase):
                     function="exercises", 
                     method="GET", 
                     path=f"/exercises/NO-UUID",
+                    sub=str(uuid.uuid4()),
                     path_params= {"exercise_id":"NO-UUID"}
                     )
 
@@ -33,6 +34,7 @@ class TestExercisesGetExercise(unittest.TestCase):
                     function="exercises", 
                     method="GET", 
                     path=f"/exercises/{id}",
+                    sub=str(uuid.uuid4()),
                     path_params= {"exercise_id": id}
                     )
             self.assertEqual(not_found['statusCode'], 404)
@@ -49,6 +51,7 @@ class TestExercisesGetExercise(unittest.TestCase):
                     function="exercises", 
                     method="GET", 
                     path=f"/exercises/{id}",
+                    sub=str(uuid.uuid4()),
                     path_params= {"exercise_id": id}
                     )
             self.assertEqual(success['statusCode'], 200)
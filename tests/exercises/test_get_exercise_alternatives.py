@@ -0,0 +1,93 @@
+import unittest
+import json
+import uuid
+from tests.utils import TestHelper, TestError
+
+class TestExercisesGetExerciseAlternatives(unittest.TestCase):
+
+    """
+    TEST CASES
+    * Non existing exercise
+    * Invalid equipment filter
+    * Success, only allowed equipment
+    * Pagination
+    """
+
+    def test_get_exercise_alternatives_not_found(self):
+        try:
+            id = str(uuid.uuid4())
+            not_found = TestHelper().invoke(
+                    function="exercises",
+                    method="GET",
+                    path=f"/exercises/{id}/alternatives",
+                    sub=str(uuid.uuid4()),
+                    path_params={"exercise_id": id}
+                    )
+            self.assertEqual(not_found['statusCode'], 404)
+
+        except TestError as e:
+            print(f"TEST ERROR: {e}")
+            raise
+
+    def test_get_exercise_alternatives_invalid_equipment(self):
+        try:
+            id = TestHelper().get_from_db("SELECT id FROM Exercises WHERE name = 'Push Up';")[0][0]
+            invalid = TestHelper().invoke(
+                    function="exercises",
+                    method="GET",
+                    path=f"/exercises/{id}/alternatives",
+                    sub=str(uuid.uuid4()),
+                    path_params={"exercise_id": id},
+                    query_params={"equipment": "bar"}
+                    )
+            self.assertEqual(invalid['statusCode'], 400)
+
+        except TestError as e:
+            print(f"TEST ERROR: {e}")
+            raise
+
+    def test_get_exercise_alternatives_success(self):
+        try:
+            id = TestHelper().get_from_db("SELECT id FROM Exercises WHERE name = 'Push Up';")[0][0]
+            success = TestHelper().invoke(
+                    function="exercises",
+                    method="GET",
+                    path=f"/exercises/{id}/alternatives",
+                    sub=str(uuid.uuid4()),
+                    path_params={"exercise_id": id},
+                    query_params={"equipment": "1"}
+                    )
+            self.assertEqual(success['statusCode'], 200)
+            exercises = json.loads(success['body'])['exercises']
+            self.assertNotIn(id, [exercise['id'] for exercise in exercises])
+            self.assertTrue(all(exercise['necessary_equipment'] in (None, 1) for exercise in exercises))
+
+        except TestError as e:
+            print(f"TEST ERROR: {e}")
+            raise
+
+    def test_get_exercise_alternatives_paginated(self):
+        try:
+            id = TestHelper().get_from_db("SELECT id FROM Exercises WHERE name = 'Push Up';")[0][0]
+            pages = []
+            for offset in ("0", "1"):
+                response = TestHelper().invoke(
+                        function="exercises",
+                        method="GET",
+                        path=f"/exercises/{id}/alternatives",
+                        sub=str(uuid.uuid4()),
+                        path_params={"exercise_id": id},
+                        query_params={"limit": "1", "offset": offset}
+                        )
+                self.assertEqual(response['statusCode'], 200)
+                pages.append(json.loads(response['body'])['exercises'])
+            self.assertTrue(all(len(page) <= 1 for page in pages))
+            if pages[1]:
+                self.assertNotEqual(pages[0][0]['id'], pages[1][0]['id'])
+
+        except TestError as e:
+            print(f"TEST ERROR: {e}")
+            raise
+
+if __name__ == '__main__':
+    unittest.main()
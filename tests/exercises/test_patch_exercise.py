@@ -0,0 +1,69 @@
+import unittest
+import uuid
+from tests.utils import TestHelper, TestError
+
+class TestExercisesPatchExercise(unittest.TestCase):
+
+    """
+    TEST CASES
+    * Not an admin
+    * Non existing exercise
+    * Success
+    """
+
+    def test_patch_exercise_not_admin(self):
+        try:
+            id = TestHelper().get_from_db("SELECT id FROM Exercises LIMIT 1;")[0][0]
+            forbidden = TestHelper().invoke(
+                    function="exercises",
+                    method="PATCH",
+                    path=f"/exercises/{id}",
+                    path_params={"exercise_id": id},
+                    sub=str(uuid.uuid4()),
+                    body={"exercise_type": 1}
+                    )
+            self.assertEqual(forbidden['statusCode'], 403)
+
+        except TestError as e:
+            print(f"TEST ERROR: {e}")
+            raise
+
+    def test_patch_exercise_not_found(self):
+        try:
+            id = str(uuid.uuid4())
+            not_found = TestHelper().invoke(
+                    function="exercises",
+                    method="PATCH",
+                    path=f"/exercises/{id}",
+                    path_params={"exercise_id": id},
+                    sub=str(uuid.uuid4()),
+                    groups=["admin"],
+                    body={"exercise_type": 1}
+                    )
+            self.assertEqual(not_found['statusCode'], 404)
+
+        except TestError as e:
+            print(f"TEST ERROR: {e}")
+            raise
+
+    def test_patch_exercise_success(self):
+        try:
+            id = TestHelper().get_from_db("SELECT id FROM Exercises LIMIT 1;")[0][0]
+            success = TestHelper().invoke(
+                    function="exercises",
+                    method="PATCH",
+                    path=f"/exercises/{id}",
+                    path_params={"exercise_id": id},
+                    sub=str(uuid.uuid4()),
+                    groups=["admin"],
+                    body={"exercise_type": 1}
+                    )
+            self.assertEqual(success['statusCode'], 200)
+
+        except TestError as e:
+            print(f"TEST ERROR: {e}")
+            raise
+
+
+if __name__ == '__main__':
+    unittest.main()
@@ -0,0 +1,85 @@
+import unittest
+import json
+import uuid
+from tests.utils import TestHelper, TestError
+
+class TestExercisesPutExerciseContent(unittest.TestCase):
+
+    """
+    TEST CASES
+    * Not an admin
+    * Invalid media
+    * Success and returned by get exercise
+    """
+
+    def test_put_exercise_content_not_admin(self):
+        try:
+            id = TestHelper().get_from_db("SELECT id FROM Exercises LIMIT 1;")[0][0]
+            forbidden = TestHelper().invoke(
+                    function="exercises",
+                    method="PUT",
+                    path=f"/exercises/{id}/content",
+                    sub=str(uuid.uuid4()),
+                    path_params={"exercise_id": id},
+                    body={"description": "Test"}
+                    )
+            self.assertEqual(forbidden['statusCode'], 403)
+
+        except TestError as e:
+            print(f"TEST ERROR: {e}")
+            raise
+
+    def test_put_exercise_content_invalid_media(self):
+        try:
+            id = TestHelper().get_from_db("SELECT id FROM Exercises LIMIT 1;")[0][0]
+            invalid = TestHelper().invoke(
+                    function="exercises",
+                    method="PUT",
+                    path=f"/exercises/{id}/content",
+                    sub=str(uuid.uuid4()),
+                    groups=["admin"],
+                    path_params={"exercise_id": id},
+                    body={"media": [{"media_type": 1, "url": "not a url"}]}
+                    )
+            self.assertEqual(invalid['statusCode'], 400)
+
+        except TestError as e:
+            print(f"TEST ERROR: {e}")
+            raise
+
+    def test_put_exercise_content_success(self):
+        try:
+            id = TestHelper().get_from_db("SELECT id FROM Exercises LIMIT 1;")[0][0]
+            content = {
+                    "description": "Test description",
+                    "steps": ["First", "Second"],
+                    "cues": ["Breathe"],
+                    "common_mistakes": [],
+                    "media": [{"media_type": 1, "url": "https://cdn.example.com/a.mp4", "thumbnail_url": None, "width": 1280, "height": 720}]
+                    }
+            success = TestHelper().invoke(
+                    function="exercises",
+                    method="PUT",
+                    path=f"/exercises/{id}/content",
+                    sub=str(uuid.uuid4()),
+                    groups=["admin"],
+                    path_params={"exercise_id": id},
+                    body=content
+                    )
+            self.assertEqual(success['statusCode'], 200)
+
+            fetched = TestHelper().invoke(
+                    function="exercises",
+                    method="GET",
+                    path=f"/exercises/{id}",
+                    sub=str(uuid.uuid4()),
+                    path_params={"exercise_id": id}
+                    )
+            self.assertEqual(json.loads(fetched['body'])['content'], content)
+
+        except TestError as e:
+            print(f"TEST ERROR: {e}")
+            raise
+
+if __name__ == '__main__':
+    unittest.main()
@@ -0,0 +1,68 @@
+import unittest
+import uuid
+from tests.utils import TestHelper, TestError
+
+class TestExercisesRetireExercise(unittest.TestCase):
+
+    """
+    TEST CASES
+    * Not an admin
+    * Non existing exercise
+    * Referenced exercise is retired, not deleted
+    """
+
+    def test_retire_exercise_not_admin(self):
+        try:
+            id = TestHelper().get_from_db("SELECT id FROM Exercises LIMIT 1;")[0][0]
+            forbidden = TestHelper().invoke(
+                    function="exercises",
+                    method="DELETE",
+                    path=f"/exercises/{id}",
+                    path_params={"exercise_id": id},
+                    sub=str(uuid.uuid4())
+                    )
+            self.assertEqual(forbidden['statusCode'], 403)
+
+        except TestError as e:
+            print(f"TEST ERROR: {e}")
+            raise
+
+    def test_retire_exercise_not_found(self):
+        try:
+            id = str(uuid.uuid4())
+            not_found = TestHelper().invoke(
+                    function="exercises",
+                    method="DELETE",
+                    path=f"/exercises/{id}",
+                    path_params={"exercise_id": id},
+                    sub=str(uuid.uuid4()),
+                    groups=["admin"]
+                    )
+            self.assertEqual(not_found['statusCode'], 404)
+
+        except TestError as e:
+            print(f"TEST ERROR: {e}")
+            raise
+
+    def test_retire_exercise_referenced(self):
+        try:
+            id = TestHelper().get_from_db("SELECT exercise_id FROM WKTemplateElements LIMIT 1;")[0][0]
+            success = TestHelper().invoke(
+                    function="exercises",
+                    method="DELETE",
+                    path=f"/exercises/{id}",
+                    path_params={"exercise_id": id},
+                    sub=str(uuid.uuid4()),
+                    groups=["admin"]
+                    )
+            self.assertEqual(success['statusCode'], 204)
+            retired = TestHelper().get_from_db(f"SELECT retired FROM Exercises WHERE id = '{id}';")[0][0]
+            self.assertTrue(retired)
+
+        except TestError as e:
+            print(f"TEST ERROR: {e}")
+            raise
+
+
+if __name__ == '__main__':
+    unittest.main()
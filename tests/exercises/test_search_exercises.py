@@ -1,5 +1,6 @@
 import unittest
 import json
+import uuid
 from tests.utils import TestHelper, TestError
 
 class TestExercisesSearchExercises(unittest.TestCase):
@@ -9,6 +10,8 @@ class TestExercisesSearchExercises(unittest.TestCase):
     * No query parameter
     * No exercises match
     * Multiple match
+    * Case-insensitive match in the middle of the name
+    * Filter without name
     """
 
     def test_search_exercise_incorrect_query_parameters(self):
@@ -17,11 +20,13 @@ class TestExercisesSearchExercises(unittest.TestCase):
                     function="exercises", 
                     method="GET", 
                     path="/exercises",
+                    sub=str(uuid.uuid4()),
                     )
             other_parameter = TestHelper().invoke(
                     function="exercises", 
                     method="GET", 
                     path="/exercises",
+                    sub=str(uuid.uuid4()),
                     query_params={"other": "123"}
                     )
 
@@ -38,6 +43,7 @@ class TestExercisesSearchExercises(unittest.TestCase):
                     function="exercises", 
                     method="GET", 
                     path="/exercises",
+                    sub=str(uuid.uuid4()),
                     query_params={"name": "$$$"}
                     )
 
@@ -54,6 +60,7 @@ class TestExercisesSearchExercises(unittest.TestCase):
                     function="exercises", 
                     method="GET", 
                     path="/exercises",
+                    sub=str(uuid.uuid4()),
                     query_params={"name": ""}
                     )
 
@@ -64,5 +71,42 @@ class TestExercisesSearchExercises(unittest.TestCase):
             print(f"TEST ERROR: {e}")
             raise
 
+    def test_search_exercise_case_insensitive(self):
+        try:
+            success = TestHelper().invoke(
+                    function="exercises", 
+                    method="GET", 
+                    path="/exercises",
+                    sub=str(uuid.uuid4()),
+                    query_params={"name": "up"}
+                    )
+
+            self.assertEqual(success['statusCode'], 200)
+            names = [exercise['name'] for exercise in json.loads(success['body'])['exercises']]
+            self.assertIn("Pull Up", names)
+
+        except TestError as e:
+            print(f"TEST ERROR: {e}")
+            raise
+
+    def test_search_exercise_filter(self):
+        try:
+            success = TestHelper().invoke(
+                    function="exercises", 
+                    method="GET", 
+                    path="/exercises",
+                    sub=str(uuid.uuid4()),
+                    query_params={"exercise_type": "2"}
+                    )
+
+            self.assertEqual(success['statusCode'], 200)
+            exercises = json.loads(success['body'])['exercises']
+            self.assertNotEqual(len(exercises), 0)
+            self.assertTrue(all(exercise['exercise_type'] == 2 for exercise in exercises))
+
+        except TestError as e:
+            print(f"TEST ERROR: {e}")
+            raise
+
 if __name__ == '__main__':
     unittest.main()
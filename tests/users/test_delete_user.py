@@ -0,0 +1,81 @@
+import unittest
+import json
+import uuid
+from tests.utils import TestHelper, TestError
+
+class TestUsersDeleteUser(unittest.TestCase):
+
+    """
+    TEST CASES
+    * Other user's account
+    * Non existing user
+    * Success and hidden from search
+    """
+
+    def test_delete_user_forbidden(self):
+        try:
+            id = TestHelper().get_from_db("SELECT id FROM Users LIMIT 1;")[0][0]
+            forbidden = TestHelper().invoke(
+                    function="users",
+                    method="DELETE",
+                    path=f"/users/{id}",
+                    sub=str(uuid.uuid4()),
+                    path_params={"user_id": id}
+                    )
+            self.assertEqual(forbidden['statusCode'], 403)
+
+        except TestError as e:
+            print(f"TEST ERROR: {e}")
+            raise
+
+    def test_delete_user_not_found(self):
+        try:
+            id = str(uuid.uuid4())
+            not_found = TestHelper().invoke(
+                    function="users",
+                    method="DELETE",
+                    path=f"/users/{id}",
+                    sub=id,
+                    path_params={"user_id": id}
+                    )
+            self.assertEqual(not_found['statusCode'], 404)
+
+        except TestError as e:
+            print(f"TEST ERROR: {e}")
+            raise
+
+    def test_delete_user_success(self):
+        try:
+            id = str(uuid.uuid4())
+            username = TestHelper.random_string(10)
+            TestHelper().invoke(
+                    function="users",
+                    method="POST",
+                    path="/users",
+                    sub=id,
+                    body={"uuid": id, "username": username, "date_joined": "2024-09-27"}
+                    )
+            success = TestHelper().invoke(
+                    function="users",
+                    method="DELETE",
+                    path=f"/users/{id}",
+                    sub=id,
+                    path_params={"user_id": id}
+                    )
+            self.assertEqual(success['statusCode'], 202)
+            self.assertIn('deletion_scheduled_for', json.loads(success['body']))
+
+            search = TestHelper().invoke(
+                    function="users",
+                    method="GET",
+                    path="/users",
+                    query_params={"username": username}
+                    )
+            self.assertEqual(len(json.loads(search['body'])['users']), 0)
+
+        except TestError as e:
+            print(f"TEST ERROR: {e}")
+            raise
+
+if __name__ == '__main__':
+    unittest.main()
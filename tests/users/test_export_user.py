@@ -0,0 +1,96 @@
+import unittest
+import base64
+import io
+import json
+import uuid
+import zipfile
+from tests.utils import TestHelper, TestError
+
+class TestUsersExportUser(unittest.TestCase):
+
+    """
+    TEST CASES
+    * Other user's data
+    * Non existing user
+    * Success, archive holds the JSON and every table
+    """
+
+    def test_export_user_forbidden(self):
+        try:
+            id = TestHelper().get_from_db("SELECT id FROM Users LIMIT 1;")[0][0]
+            forbidden = TestHelper().invoke(
+                    function="users",
+                    method="GET",
+                    path=f"/users/{id}/export",
+                    sub=str(uuid.uuid4()),
+                    path_params={"user_id": id}
+                    )
+            self.assertEqual(forbidden['statusCode'], 403)
+
+        except TestError as e:
+            print(f"TEST ERROR: {e}")
+            raise
+
+    def test_export_user_not_found(self):
+        try:
+            id = str(uuid.uuid4())
+            not_found = TestHelper().invoke(
+                    function="users",
+                    method="GET",
+                    path=f"/users/{id}/export",
+                    sub=id,
+                    path_params={"user_id": id}
+                    )
+            self.assertEqual(not_found['statusCode'], 404)
+
+        except TestError as e:
+            print(f"TEST ERROR: {e}")
+            raise
+
+    def test_export_user_success(self):
+        try:
+            id = TestHelper().get_from_db("SELECT user_id FROM WorkoutTemplates LIMIT 1;")[0][0]
+            success = TestHelper().invoke(
+                    function="users",
+                    method="GET",
+                    path=f"/users/{id}/export",
+                    sub=id,
+                    path_params={"user_id": id}
+                    )
+            self.assertEqual(success['statusCode'], 200)
+            self.assertTrue(success['isBase64Encoded'])
+
+            archive = zipfile.ZipFile(io.BytesIO(base64.b64decode(success['body'])))
+            self.assertEqual(sorted(archive.namelist()), [
+                "body_measurements.csv",
+                "calendar_tokens.csv",
+                "challenge_participants.csv",
+                "challenges.csv",
+                "comments.csv",
+                "exercises.csv",
+                "export.json",
+                "follows.csv",
+                "notifications.csv",
+                "program_enrolments.csv",
+                "program_slots.csv",
+                "programs.csv",
+                "progression_rules.csv",
+                "reactions.csv",
+                "scheduled_workouts.csv",
+                "user.csv",
+                "wk_execution_elements.csv",
+                "wk_template_elements.csv",
+                "workout_executions.csv",
+                "workout_imports.csv",
+                "workout_templates.csv",
+                ])
+            export = json.loads(archive.read("export.json"))
+            self.assertEqual(export['user']['id'], id)
+            self.assertGreater(len(export['workout_templates']), 0)
+
+        except TestError as e:
+            print(f"TEST ERROR: {e}")
+            raise
+
+if __name__ == '__main__':
+    unittest.main()
@@ -0,0 +1,131 @@
+import unittest
+import json
+import uuid
+from tests.utils import TestHelper, TestError
+
+class TestUsersFollows(unittest.TestCase):
+
+    """
+    TEST CASES
+    * Follow public profile, list followers and following, unfollow
+    * Follow private profile, hidden until the request is accepted
+    """
+
+    def create_user(self):
+        id = str(uuid.uuid4())
+        TestHelper().invoke(
+                function="users",
+                method="POST",
+                path="/users",
+                sub=id,
+                body={"uuid": id, "username": TestHelper.random_string(10), "date_joined": "2024-10-11"}
+                )
+        return id
+
+    def test_follows_public(self):
+        try:
+            follower, followed = self.create_user(), self.create_user()
+            followed_resp = TestHelper().invoke(
+                    function="users",
+                    method="POST",
+                    path=f"/users/{follower}/following/{followed}",
+                    sub=follower,
+                    path_params={"user_id": follower, "followed_id": followed}
+                    )
+            self.assertEqual(followed_resp['statusCode'], 201)
+            self.assertTrue(json.loads(followed_resp['body'])['accepted'])
+
+            followers = TestHelper().invoke(
+                    function="users",
+                    method="GET",
+                    path=f"/users/{followed}/followers",
+                    sub=follower,
+                    path_params={"user_id": followed}
+                    )
+            self.assertEqual(followers['statusCode'], 200)
+            self.assertEqual([user['id'] for user in json.loads(followers['body'])['users']], [follower])
+
+            following = TestHelper().invoke(
+                    function="users",
+                    method="GET",
+                    path=f"/users/{follower}/following",
+                    sub=followed,
+                    path_params={"user_id": follower},
+                    query_params={"limit": "10"}
+                    )
+            self.assertEqual(following['statusCode'], 200)
+            self.assertEqual([user['id'] for user in json.loads(following['body'])['users']], [followed])
+
+            unfollowed = TestHelper().invoke(
+                    function="users",
+                    method="DELETE",
+                    path=f"/users/{follower}/following/{followed}",
+                    sub=follower,
+                    path_params={"user_id": follower, "followed_id": followed}
+                    )
+            self.assertEqual(unfollowed['statusCode'], 204)
+
+        except TestError as e:
+            print(f"TEST ERROR: {e}")
+            raise
+
+    def test_follows_private(self):
+        try:
+            follower, followed = self.create_user(), self.create_user()
+            TestHelper().invoke(
+                    function="users",
+                    method="PATCH",
+                    path=f"/users/{followed}",
+                    sub=followed,
+                    path_params={"user_id": followed},
+                    body={"private_profile": True}
+                    )
+            requested = TestHelper().invoke(
+                    function="users",
+                    method="POST",
+                    path=f"/users/{follower}/following/{followed}",
+                    sub=follower,
+                    path_params={"user_id": follower, "followed_id": followed}
+                    )
+            self.assertEqual(requested['statusCode'], 201)
+            self.assertFalse(json.loads(requested['body'])['accepted'])
+
+            hidden = TestHelper().invoke(
+                    function="users",
+                    method="GET",
+                    path=f"/users/{followed}/followers",
+                    sub=follower,
+                    path_params={"user_id": followed}
+                    )
+            self.assertEqual(hidden['statusCode'], 403)
+
+            requests = TestHelper().invoke(
+                    function="users",
+                    method="GET",
+                    path=f"/users/{followed}/follow-requests",
+                    sub=followed,
+                    path_params={"user_id": followed}
+                    )
+            self.assertEqual([user['id'] for user in json.loads(requests['body'])['users']], [follower])
+
+            accepted = TestHelper().invoke(
+                    function="users",
+                    method="POST",
+                    path=f"/users/{followed}/follow-requests/{follower}",
+                    sub=followed,
+                    path_params={"user_id": followed, "follower_id": follower}
+                    )
+            self.assertEqual(accepted['statusCode'], 200)
+
+            visible = TestHelper().invoke(
+                    function="users",
+                    method="GET",
+                    path=f"/users/{followed}/followers",
+                    sub=follower,
+                    path_params={"user_id": followed}
+                    )
+            self.assertEqual(visible['statusCode'], 200)
+
+        except TestError as e:
+            print(f"TEST ERROR: {e}")
+            raise
@@ -0,0 +1,81 @@
+import unittest
+import json
+import uuid
+from tests.utils import TestHelper, TestError
+
+class TestUsersGetChanges(unittest.TestCase):
+
+    """
+    TEST CASES
+    * Other user's changes
+    * Invalid token
+    * Success, everything then nothing new since the token
+    """
+
+    def test_get_changes_forbidden(self):
+        try:
+            id = TestHelper().get_from_db("SELECT id FROM Users LIMIT 1;")[0][0]
+            forbidden = TestHelper().invoke(
+                    function="users",
+                    method="GET",
+                    path=f"/users/{id}/changes",
+                    sub=str(uuid.uuid4()),
+                    path_params={"user_id": id}
+                    )
+            self.assertEqual(forbidden['statusCode'], 403)
+
+        except TestError as e:
+            print(f"TEST ERROR: {e}")
+            raise
+
+    def test_get_changes_invalid_token(self):
+        try:
+            id = TestHelper().get_from_db("SELECT id FROM Users LIMIT 1;")[0][0]
+            invalid = TestHelper().invoke(
+                    function="users",
+                    method="GET",
+                    path=f"/users/{id}/changes",
+                    sub=id,
+                    path_params={"user_id": id},
+                    query_params={"since": "not a token"}
+                    )
+            self.assertEqual(invalid['statusCode'], 400)
+
+        except TestError as e:
+            print(f"TEST ERROR: {e}")
+            raise
+
+    def test_get_changes_success(self):
+        try:
+            id = TestHelper().get_from_db("SELECT user_id FROM WorkoutTemplates LIMIT 1;")[0][0]
+            full = TestHelper().invoke(
+                    function="users",
+                    method="GET",
+                    path=f"/users/{id}/changes",
+                    sub=id,
+                    path_params={"user_id": id}
+                    )
+            self.assertEqual(full['statusCode'], 200)
+            feed = json.loads(full['body'])
+            self.assertGreater(len(feed['workout_templates']['created']), 0)
+            self.assertEqual(feed['workout_templates']['deleted'], [])
+
+            since = TestHelper().invoke(
+                    function="users",
+                    method="GET",
+                    path=f"/users/{id}/changes",
+                    sub=id,
+                    path_params={"user_id": id},
+                    query_params={"since": feed['token']}
+                    )
+            self.assertEqual(since['statusCode'], 200)
+            changes = json.loads(since['body'])
+            self.assertEqual(changes['workout_templates']['created'], [])
+            self.assertEqual(changes['workout_templates']['deleted'], [])
+
+        except TestError as e:
+            print(f"TEST ERROR: {e}")
+            raise
+
+if __name__ == '__main__':
+    unittest.main()
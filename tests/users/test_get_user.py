@@ -1,4 +1,5 @@
 import unittest
+import json
 import uuid
 from tests.utils import TestHelper, TestError
 
@@ -7,8 +8,9 @@ class TestUsersGetUser(unittest.TestCase):
     """
     TEST CASES
     * Wrong path parameter format   
-    * Non existing exercise         
-    * Existing exercise             
+    * Non existing user
+    * Existing user, whole profile
+    * Other viewer, public projection
     """
 
     def test_get_user_incorrect_path_parameters(self):
@@ -33,6 +35,7 @@ class TestUsersGetUser(unittest.TestCase):
                     function="users", 
                     method="GET", 
                     path=f"/users/{id}",
+                    sub=id,
                     path_params= {"user_id": id}
                     )
             self.assertEqual(not_found['statusCode'], 404)
@@ -49,10 +52,29 @@ class TestUsersGetUser(unittest.TestCase):
                     function="users", 
                     method="GET", 
                     path=f"/users/{id}",
+                    sub=id,
                     path_params= {"user_id": id}
                     )
             print(f"BODY: {success['body']}")
             self.assertEqual(success['statusCode'], 200)
+            self.assertIn("date_of_birth", json.loads(success['body']))
+
+        except TestError as e:
+            print(f"TEST ERROR: {e}")
+            raise
+
+    def test_get_user_other_viewer(self):
+        try:
+            id = TestHelper().get_from_db("SELECT id from Users WHERE NOT private_profile;")[0][0]
+            public = TestHelper().invoke(
+                    function="users", 
+                    method="GET", 
+                    path=f"/users/{id}",
+                    sub=str(uuid.uuid4()),
+                    path_params= {"user_id": id}
+                    )
+            self.assertEqual(public['statusCode'], 200)
+            self.assertEqual(sorted(json.loads(public['body']).keys()), ["display_name", "id", "username"])
 
         except TestError as e:
             print(f"TEST ERROR: {e}")
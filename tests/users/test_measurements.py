@@ -0,0 +1,107 @@
+import unittest
+import json
+import uuid
+from tests.utils import TestHelper, TestError
+
+class TestUsersMeasurements(unittest.TestCase):
+
+    """
+    TEST CASES
+    * Other user's measurements
+    * Create, list by range, trend, update, delete, weight mirrored in profile
+    """
+
+    def create_user(self):
+        id = str(uuid.uuid4())
+        TestHelper().invoke(
+                function="users",
+                method="POST",
+                path="/users",
+                sub=id,
+                body={"uuid": id, "username": TestHelper.random_string(10), "date_joined": "2024-10-04"}
+                )
+        return id
+
+    def test_measurements_forbidden(self):
+        try:
+            id = TestHelper().get_from_db("SELECT id FROM Users LIMIT 1;")[0][0]
+            forbidden = TestHelper().invoke(
+                    function="users",
+                    method="GET",
+                    path=f"/users/{id}/measurements",
+                    sub=str(uuid.uuid4()),
+                    path_params={"user_id": id}
+                    )
+            self.assertEqual(forbidden['statusCode'], 403)
+
+        except TestError as e:
+            print(f"TEST ERROR: {e}")
+            raise
+
+    def test_measurements_crud(self):
+        try:
+            id = self.create_user()
+            ids = []
+            for date, weight in [("2024-09-01", 82.0), ("2024-09-02", 81.0), ("2024-09-05", 80.0)]:
+                created = TestHelper().invoke(
+                        function="users",
+                        method="POST",
+                        path=f"/users/{id}/measurements",
+                        sub=id,
+                        path_params={"user_id": id},
+                        body={"date": date, "weight": weight, "waist": 85}
+                        )
+                self.assertEqual(created['statusCode'], 201)
+                ids.append(json.loads(created['body'])['id'])
+
+            listed = TestHelper().invoke(
+                    function="users",
+                    method="GET",
+                    path=f"/users/{id}/measurements",
+                    sub=id,
+                    path_params={"user_id": id},
+                    query_params={"from": "2024-09-02"}
+                    )
+            self.assertEqual(listed['statusCode'], 200)
+            self.assertEqual(len(json.loads(listed['body'])['measurements']), 2)
+
+            trend = TestHelper().invoke(
+                    function="users",
+                    method="GET",
+                    path=f"/users/{id}/measurements/trend",
+                    sub=id,
+                    path_params={"user_id": id},
+                    query_params={"metric": "weight"}
+                    )
+            self.assertEqual(trend['statusCode'], 200)
+            points = json.loads(trend['body'])['trend']
+            self.assertEqual(len(points), 3)
+            self.assertGreater(points[-1]['trend'], 80.0)
+
+            patched = TestHelper().invoke(
+                    function="users",
+                    method="PATCH",
+                    path=f"/users/{id}/measurements/{ids[2]}",
+                    sub=id,
+                    path_params={"user_id": id, "measurement_id": ids[2]},
+                    body={"weight": 79.5}
+                    )
+            self.assertEqual(patched['statusCode'], 200)
+            self.assertEqual(TestHelper().get_from_db(f"SELECT weight FROM Users WHERE id = '{id}';")[0][0], 79.5)
+
+            deleted = TestHelper().invoke(
+                    function="users",
+                    method="DELETE",
+                    path=f"/users/{id}/measurements/{ids[2]}",
+                    sub=id,
+                    path_params={"user_id": id, "measurement_id": ids[2]}
+                    )
+            self.assertEqual(deleted['statusCode'], 204)
+            self.assertEqual(TestHelper().get_from_db(f"SELECT weight FROM Users WHERE id = '{id}';")[0][0], 81.0)
+
+        except TestError as e:
+            print(f"TEST ERROR: {e}")
+            raise
+
+if __name__ == '__main__':
+    unittest.main()
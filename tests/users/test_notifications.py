@@ -0,0 +1,93 @@
+import unittest
+import json
+import uuid
+from tests.utils import TestHelper, TestError
+
+class TestUsersNotifications(unittest.TestCase):
+
+    """
+    TEST CASES
+    * Other user's notifications
+    * New follower notified, marked as read, opted out afterwards
+    """
+
+    def create_user(self):
+        id = str(uuid.uuid4())
+        TestHelper().invoke(
+                function="users",
+                method="POST",
+                path="/users",
+                sub=id,
+                body={"uuid": id, "username": TestHelper.random_string(10), "date_joined": "2024-10-21"}
+                )
+        return id
+
+    def follow(self, follower, followed):
+        TestHelper().invoke(
+                function="users",
+                method="POST",
+                path=f"/users/{follower}/following/{followed}",
+                sub=follower,
+                path_params={"user_id": follower, "followed_id": followed}
+                )
+
+    def inbox(self, id):
+        return TestHelper().invoke(
+                function="users",
+                method="GET",
+                path=f"/users/{id}/notifications",
+                sub=id,
+                path_params={"user_id": id}
+                )
+
+    def test_notifications_forbidden(self):
+        try:
+            id = self.create_user()
+            forbidden = TestHelper().invoke(
+                    function="users",
+                    method="GET",
+                    path=f"/users/{id}/notifications",
+                    sub=str(uuid.uuid4()),
+                    path_params={"user_id": id}
+                    )
+            self.assertEqual(forbidden['statusCode'], 403)
+
+        except TestError as e:
+            print(f"TEST ERROR: {e}")
+            raise
+
+    def test_notifications_new_follower(self):
+        try:
+            owner = self.create_user()
+            self.follow(self.create_user(), owner)
+
+            inbox = self.inbox(owner)
+            self.assertEqual(inbox['statusCode'], 200)
+            body = json.loads(inbox['body'])
+            self.assertEqual(body['unread'], 1)
+            notification = body['notifications'][0]['id']
+
+            read = TestHelper().invoke(
+                    function="users",
+                    method="POST",
+                    path=f"/users/{owner}/notifications/{notification}/read",
+                    sub=owner,
+                    path_params={"user_id": owner, "notification_id": notification}
+                    )
+            self.assertEqual(read['statusCode'], 204)
+            self.assertEqual(json.loads(self.inbox(owner)['body'])['unread'], 0)
+
+            TestHelper().invoke(
+                    function="users",
+                    method="PATCH",
+                    path=f"/users/{owner}",
+                    sub=owner,
+                    path_params={"user_id": owner},
+                    body={"notify_new_followers": False}
+                    )
+            self.follow(self.create_user(), owner)
+            self.assertEqual(len(json.loads(self.inbox(owner)['body'])['notifications']), 1)
+
+        except TestError as e:
+            print(f"TEST ERROR: {e}")
+            raise
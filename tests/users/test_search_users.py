@@ -9,6 +9,7 @@ class TestUsersSearchUsers(unittest.TestCase):
     * No query parameter
     * No exercises match
     * Multiple match
+    * Case-insensitive, exact match first
     """
 
     def test_search_user_incorrect_query_parameters(self):
@@ -64,5 +65,22 @@ class TestUsersSearchUsers(unittest.TestCase):
             print(f"TEST ERROR: {e}")
             raise
 
+    def test_search_user_case_insensitive(self):
+        try:
+            success = TestHelper().invoke(
+                    function="users", 
+                    method="GET", 
+                    path="/users",
+                    query_params={"username": "PATO"}
+                    )
+
+            self.assertEqual(success['statusCode'], 200) 
+            users = json.loads(success['body'])['users']
+            self.assertEqual(users[0]['username'], "pato")
+
+        except TestError as e:
+            print(f"TEST ERROR: {e}")
+            raise
+
 if __name__ == '__main__':
     unittest.main()
@@ -0,0 +1,75 @@
+import unittest
+import json
+import uuid
+from tests.utils import TestHelper, TestError
+
+class TestUsersUnits(unittest.TestCase):
+
+    """
+    TEST CASES
+    * Invalid units override
+    * Imperial preference, values stored in metric, override back to metric
+    """
+
+    def create_user(self):
+        id = str(uuid.uuid4())
+        TestHelper().invoke(
+                function="users",
+                method="POST",
+                path="/users",
+                sub=id,
+                body={"uuid": id, "username": TestHelper.random_string(10), "date_joined": "2024-10-08"}
+                )
+        return id
+
+    def test_units_invalid(self):
+        try:
+            id = self.create_user()
+            invalid = TestHelper().invoke(
+                    function="users",
+                    method="GET",
+                    path=f"/users/{id}",
+                    sub=id,
+                    path_params={"user_id": id},
+                    query_params={"units": "stone"}
+                    )
+            self.assertEqual(invalid['statusCode'], 400)
+
+        except TestError as e:
+            print(f"TEST ERROR: {e}")
+            raise
+
+    def test_units_imperial(self):
+        try:
+            id = self.create_user()
+            patched = TestHelper().invoke(
+                    function="users",
+                    method="PATCH",
+                    path=f"/users/{id}",
+                    sub=id,
+                    path_params={"user_id": id},
+                    body={"weight_unit": 1, "length_unit": 1, "weight": 176.4, "height": 71}
+                    )
+            self.assertEqual(patched['statusCode'], 200)
+            self.assertEqual(json.loads(patched['body'])['weight'], 176.4)
+
+            stored = TestHelper().get_from_db(f"SELECT weight, height FROM Users WHERE id = '{id}';")[0]
+            self.assertAlmostEqual(stored[0], 80.0, delta=0.05)
+            self.assertEqual(stored[1], 180)
+
+            metric = TestHelper().invoke(
+                    function="users",
+                    method="GET",
+                    path=f"/users/{id}",
+                    sub=id,
+                    path_params={"user_id": id},
+                    query_params={"units": "metric"}
+                    )
+            self.assertEqual(metric['statusCode'], 200)
+            body = json.loads(metric['body'])
+            self.assertEqual(body['weight'], 80.0)
+            self.assertEqual(body['height'], 180)
+
+        except TestError as e:
+            print(f"TEST ERROR: {e}")
+            raise
@@ -34,8 +34,10 @@ class TestHelper:
             path: str,
             query_params: dict = {},
             path_params: dict = {},
-            body: dict = {},
-            sub: str = ""
+            body: dict | str = {},
+            sub: str = "",
+            groups: list = [],
+            headers: dict = {}
             ):
         if method not in self.http_methods:
             raise TestError(f"Method '{method}' is not a valid HTTP method")
@@ -48,11 +50,12 @@ class TestHelper:
         event["queryStringParameters"] = query_params
         event["multiValueQueryStringParameters"] = self.__dict_to_list(query_params)
         event["pathParameters"] = path_params
-        event["body"] = json.dumps(body)
+        event["body"] = body if isinstance(body, str) else json.dumps(body)
+        header = dict(headers)
         if sub != "":
-            header = {"Authorization": self.__auth_header(sub)}
-            event["headers"] = header
-            event["multiValueHeaders"] = self.__dict_to_list(header)
+            header["Authorization"] = self.__auth_header(sub, groups)
+        event["headers"] = header
+        event["multiValueHeaders"] = self.__dict_to_list(header)
 
         event_ascii = json.dumps(event, ensure_ascii=True)
         command = [
@@ -121,10 +124,11 @@ class TestHelper:
         return ''.join(secrets.choice(alphabet) for _ in range(n))
 
     @staticmethod
-    def __auth_header(sub):
+    def __auth_header(sub, groups):
         my_claims = {
         'sub': str(sub),
-        'exp': 10000000000  # Expiration timestamp
+        'exp': 10000000000,  # Expiration timestamp
+        'cognito:groups': groups
         }
         
         token = jwt.encode(my_claims, 'secret', algorithm='HS256')
@@ -0,0 +1,120 @@
+import unittest
+import json
+import uuid
+from datetime import date, timedelta
+from tests.utils import TestHelper, TestError
+
+class TestExecutionsChallenges(unittest.TestCase):
+
+    """
+    TEST CASES
+    * Invalid challenge
+    * Create, join, standings and leave
+    * Results of an ongoing challenge
+    """
+
+    def create_user(self):
+        id = str(uuid.uuid4())
+        TestHelper().invoke(
+                function="users",
+                method="POST",
+                path="/users",
+                sub=id,
+                body={"uuid": id, "username": TestHelper.random_string(10), "date_joined": "2024-10-28"}
+                )
+        return id
+
+    def create_challenge(self, creator, invited):
+        today = date.today()
+        return TestHelper().invoke(
+                function="workout_executions",
+                method="POST",
+                path=f"/users/{creator}/challenges",
+                sub=creator,
+                path_params={"user_id": creator},
+                body={
+                    "name": "This week",
+                    "metric": 1,
+                    "start_date": str(today - timedelta(days=3)),
+                    "end_date": str(today + timedelta(days=3)),
+                    "invited": invited
+                    }
+                )
+
+    def test_create_challenge_invalid(self):
+        try:
+            id = self.create_user()
+            invalid = TestHelper().invoke(
+                    function="workout_executions",
+                    method="POST",
+                    path=f"/users/{id}/challenges",
+                    sub=id,
+                    path_params={"user_id": id},
+                    body={"name": "Reps", "metric": 2, "start_date": "2024-11-01", "end_date": "2024-11-30"}
+                    )
+            self.assertEqual(invalid['statusCode'], 400)
+
+        except TestError as e:
+            print(f"TEST ERROR: {e}")
+            raise
+
+    def test_challenge_lifecycle(self):
+        try:
+            creator, friend = self.create_user(), self.create_user()
+            created = self.create_challenge(creator, [friend])
+            self.assertEqual(created['statusCode'], 201)
+            challenge_id = json.loads(created['body'])['id']
+            params = {"user_id": friend, "challenge_id": challenge_id}
+
+            joined = TestHelper().invoke(
+                    function="workout_executions",
+                    method="POST",
+                    path=f"/users/{friend}/challenges/{challenge_id}",
+                    sub=friend,
+                    path_params=params
+                    )
+            self.assertEqual(joined['statusCode'], 200)
+            self.assertTrue(json.loads(joined['body'])['joined'])
+
+            standings = TestHelper().invoke(
+                    function="workout_executions",
+                    method="GET",
+                    path=f"/users/{friend}/challenges/{challenge_id}/standings",
+                    sub=friend,
+                    path_params=params
+                    )
+            self.assertEqual(standings['statusCode'], 200)
+            users = [entry['user']['id'] for entry in json.loads(standings['body'])['standings']]
+            self.assertCountEqual(users, [creator, friend])
+
+            left = TestHelper().invoke(
+                    function="workout_executions",
+                    method="DELETE",
+                    path=f"/users/{friend}/challenges/{challenge_id}",
+                    sub=friend,
+                    path_params=params
+                    )
+            self.assertEqual(left['statusCode'], 204)
+            joined = TestHelper().get_from_db(f"SELECT joined FROM ChallengeParticipants WHERE challenge_id = '{challenge_id}' AND user_id = '{friend}';")[0][0]
+            self.assertFalse(joined)
+
+        except TestError as e:
+            print(f"TEST ERROR: {e}")
+            raise
+
+    def test_challenge_results_ongoing(self):
+        try:
+            creator = self.create_user()
+            challenge_id = json.loads(self.create_challenge(creator, [])['body'])['id']
+            results = TestHelper().invoke(
+                    function="workout_executions",
+                    method="GET",
+                    path=f"/users/{creator}/challenges/{challenge_id}/results",
+                    sub=creator,
+                    path_params={"user_id": creator, "challenge_id": challenge_id}
+                    )
+            self.assertEqual(results['statusCode'], 409)
+
+        except TestError as e:
+            print(f"TEST ERROR: {e}")
+            raise
@@ -0,0 +1,130 @@
+import unittest
+import json
+import uuid
+from tests.utils import TestHelper, TestError
+
+class TestExecutionsCommentsReactions(unittest.TestCase):
+
+    """
+    TEST CASES
+    * Comment, edit, list and delete as the author
+    * React, counts embedded in the execution, remove the reaction
+    """
+
+    def create_user(self):
+        id = str(uuid.uuid4())
+        TestHelper().invoke(
+                function="users",
+                method="POST",
+                path="/users",
+                sub=id,
+                body={"uuid": id, "username": TestHelper.random_string(10), "date_joined": "2024-10-18"}
+                )
+        return id
+
+    def existing_execution(self):
+        execution, owner = TestHelper().get_from_db(
+            """
+            SELECT WorkoutExecutions.id, WorkoutTemplates.user_id
+            FROM WorkoutExecutions
+            JOIN WorkoutTemplates ON WorkoutExecutions.workout_template_id = WorkoutTemplates.id
+            JOIN Users ON WorkoutTemplates.user_id = Users.id
+            WHERE NOT Users.private_profile
+            LIMIT 1;
+            """
+            )[0]
+        return str(execution), str(owner)
+
+    def test_comments(self):
+        try:
+            viewer = self.create_user()
+            execution, owner = self.existing_execution()
+            path = f"/users/{owner}/workout-executions/{execution}/comments"
+            params = {"user_id": owner, "workout_execution_id": execution}
+
+            created = TestHelper().invoke(
+                    function="workout_executions",
+                    method="POST",
+                    path=path,
+                    sub=viewer,
+                    path_params=params,
+                    body={"content": "Great session"}
+                    )
+            self.assertEqual(created['statusCode'], 201)
+            comment = json.loads(created['body'])['id']
+
+            edited = TestHelper().invoke(
+                    function="workout_executions",
+                    method="PATCH",
+                    path=f"{path}/{comment}",
+                    sub=viewer,
+                    path_params={**params, "comment_id": comment},
+                    body={"content": "Great session!"}
+                    )
+            self.assertEqual(edited['statusCode'], 200)
+            self.assertIsNotNone(json.loads(edited['body'])['date_edited'])
+
+            listed = TestHelper().invoke(
+                    function="workout_executions",
+                    method="GET",
+                    path=path,
+                    sub=viewer,
+                    path_params=params,
+                    query_params={"limit": "100"}
+                    )
+            self.assertEqual(listed['statusCode'], 200)
+            comments = json.loads(listed['body'])['comments']
+            self.assertIn("Great session!", [c['content'] for c in comments if c['author']['id'] == viewer])
+
+            deleted = TestHelper().invoke(
+                    function="workout_executions",
+                    method="DELETE",
+                    path=f"{path}/{comment}",
+                    sub=viewer,
+                    path_params={**params, "comment_id": comment}
+                    )
+            self.assertEqual(deleted['statusCode'], 204)
+
+        except TestError as e:
+            print(f"TEST ERROR: {e}")
+            raise
+
+    def test_reactions(self):
+        try:
+            viewer = self.create_user()
+            execution, owner = self.existing_execution()
+            params = {"user_id": owner, "workout_execution_id": execution}
+
+            reacted = TestHelper().invoke(
+                    function="workout_executions",
+                    method="PUT",
+                    path=f"/users/{owner}/workout-executions/{execution}/reactions",
+                    sub=viewer,
+                    path_params=params,
+                    body={"reaction": 1}
+                    )
+            self.assertEqual(reacted['statusCode'], 200)
+
+            fetched = TestHelper().invoke(
+                    function="workout_executions",
+                    method="GET",
+                    path=f"/users/{owner}/workout-executions/{execution}",
+                    sub=owner,
+                    path_params=params
+                    )
+            self.assertEqual(fetched['statusCode'], 200)
+            reactions = json.loads(fetched['body'])['reactions']
+            self.assertIn(1, [r['reaction'] for r in reactions])
+
+            removed = TestHelper().invoke(
+                    function="workout_executions",
+                    method="DELETE",
+                    path=f"/users/{owner}/workout-executions/{execution}/reactions",
+                    sub=viewer,
+                    path_params=params
+                    )
+            self.assertEqual(removed['statusCode'], 204)
+
+        except TestError as e:
+            print(f"TEST ERROR: {e}")
+            raise
@@ -0,0 +1,76 @@
+import unittest
+import json
+import uuid
+from tests.utils import TestHelper, TestError
+
+class TestExecutionsGetFeed(unittest.TestCase):
+
+    """
+    TEST CASES
+    * Other user's feed
+    * Success, executions of a followed user with their summary
+    """
+
+    def create_user(self):
+        id = str(uuid.uuid4())
+        TestHelper().invoke(
+                function="users",
+                method="POST",
+                path="/users",
+                sub=id,
+                body={"uuid": id, "username": TestHelper.random_string(10), "date_joined": "2024-10-15"}
+                )
+        return id
+
+    def test_get_feed_forbidden(self):
+        try:
+            id = self.create_user()
+            forbidden = TestHelper().invoke(
+                    function="workout_executions",
+                    method="GET",
+                    path=f"/users/{id}/feed",
+                    sub=str(uuid.uuid4()),
+                    path_params={"user_id": id}
+                    )
+            self.assertEqual(forbidden['statusCode'], 403)
+
+        except TestError as e:
+            print(f"TEST ERROR: {e}")
+            raise
+
+    def test_get_feed_success(self):
+        try:
+            viewer = self.create_user()
+            author = str(TestHelper().get_from_db(
+                """
+                SELECT WorkoutTemplates.user_id
+                FROM WorkoutExecutions
+                JOIN WorkoutTemplates ON WorkoutExecutions.workout_template_id = WorkoutTemplates.id
+                LIMIT 1;
+                """
+                )[0][0])
+            TestHelper().invoke(
+                    function="users",
+                    method="POST",
+                    path=f"/users/{viewer}/following/{author}",
+                    sub=viewer,
+                    path_params={"user_id": viewer, "followed_id": author}
+                    )
+
+            feed = TestHelper().invoke(
+                    function="workout_executions",
+                    method="GET",
+                    path=f"/users/{viewer}/feed",
+                    sub=viewer,
+                    path_params={"user_id": viewer},
+                    query_params={"limit": "5"}
+                    )
+            self.assertEqual(feed['statusCode'], 200)
+            items = json.loads(feed['body'])['items']
+            self.assertTrue(len(items) > 0)
+            self.assertTrue(all(item['author']['id'] == author for item in items))
+            self.assertTrue(all(set(item['summary']) == {"template_name", "duration", "sets", "volume", "prs"} for item in items))
+
+        except TestError as e:
+            print(f"TEST ERROR: {e}")
+            raise
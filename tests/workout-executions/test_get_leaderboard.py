@@ -0,0 +1,78 @@
+import unittest
+import json
+import uuid
+from tests.utils import TestHelper, TestError
+
+class TestExecutionsGetLeaderboard(unittest.TestCase):
+
+    """
+    TEST CASES
+    * Missing metric
+    * Success, weekly workouts of the caller and the users they follow
+    """
+
+    def create_user(self):
+        id = str(uuid.uuid4())
+        TestHelper().invoke(
+                function="users",
+                method="POST",
+                path="/users",
+                sub=id,
+                body={"uuid": id, "username": TestHelper.random_string(10), "date_joined": "2024-10-24"}
+                )
+        return id
+
+    def test_get_leaderboard_invalid(self):
+        try:
+            id = self.create_user()
+            invalid = TestHelper().invoke(
+                    function="workout_executions",
+                    method="GET",
+                    path=f"/users/{id}/leaderboard",
+                    sub=id,
+                    path_params={"user_id": id}
+                    )
+            self.assertEqual(invalid['statusCode'], 400)
+
+        except TestError as e:
+            print(f"TEST ERROR: {e}")
+            raise
+
+    def test_get_leaderboard_success(self):
+        try:
+            viewer = self.create_user()
+            author, date = TestHelper().get_from_db(
+                """
+                SELECT WorkoutTemplates.user_id, WorkoutExecutions.date
+                FROM WorkoutExecutions
+                JOIN WorkoutTemplates ON WorkoutExecutions.workout_template_id = WorkoutTemplates.id
+                JOIN Users ON WorkoutTemplates.user_id = Users.id
+                WHERE NOT Users.private_profile AND Users.show_in_leaderboards
+                LIMIT 1;
+                """
+                )[0]
+            author = str(author)
+            TestHelper().invoke(
+                    function="users",
+                    method="POST",
+                    path=f"/users/{viewer}/following/{author}",
+                    sub=viewer,
+                    path_params={"user_id": viewer, "followed_id": author}
+                    )
+
+            leaderboard = TestHelper().invoke(
+                    function="workout_executions",
+                    method="GET",
+                    path=f"/users/{viewer}/leaderboard",
+                    sub=viewer,
+                    path_params={"user_id": viewer},
+                    query_params={"metric": "weekly_workouts", "week": str(date)}
+                    )
+            self.assertEqual(leaderboard['statusCode'], 200)
+            entries = json.loads(leaderboard['body'])['entries']
+            self.assertIn(author, [entry['user']['id'] for entry in entries])
+            self.assertEqual(entries[0]['rank'], 1)
+
+        except TestError as e:
+            print(f"TEST ERROR: {e}")
+            raise
@@ -0,0 +1,95 @@
+import unittest
+import json
+import uuid
+from tests.utils import TestHelper, TestError
+
+HEVY_EXPORT = """"title","start_time","end_time","description","exercise_title","superset_id","exercise_notes","set_index","set_type","weight_kg","reps","distance_km","duration_seconds","rpe"
+"Legs","15 Jan 2023, 08:30","15 Jan 2023, 09:30","","Squat (Barbell)","","","0","normal","100","5","","",""
+"Legs","15 Jan 2023, 08:30","15 Jan 2023, 09:30","","Squat (Barbell)","","","1","normal","100","5","","",""
+"Legs","15 Jan 2023, 08:30","15 Jan 2023, 09:30","","Nordic Hamstring Curl","","","0","normal","","6","","",""
+"""
+
+class TestExecutionsImportWkExecutions(unittest.TestCase):
+
+    """
+    TEST CASES
+    * Other user's history
+    * Invalid file
+    * Unknown exercises reported, dry run, created and imported, imported again
+    """
+
+    def create_user(self):
+        id = str(uuid.uuid4())
+        TestHelper().invoke(
+                function="users",
+                method="POST",
+                path="/users",
+                sub=id,
+                body={"uuid": id, "username": TestHelper.random_string(10), "date_joined": "2024-10-01"}
+                )
+        return id
+
+    def import_history(self, user_id, body, query_params={}, sub=None):
+        return TestHelper().invoke(
+                function="workout_executions",
+                method="POST",
+                path=f"/users/{user_id}/workout-executions/import",
+                path_params={"user_id": user_id},
+                query_params=query_params,
+                body=body,
+                sub=sub or user_id
+                )
+
+    def test_import_workout_executions_forbidden(self):
+        try:
+            user_id = TestHelper().get_from_db("SELECT id FROM Users LIMIT 1;")[0][0]
+            forbidden = self.import_history(user_id, HEVY_EXPORT, sub=str(uuid.uuid4()))
+            self.assertEqual(forbidden['statusCode'], 403)
+
+        except TestError as e:
+            print(f"TEST ERROR: {e}")
+            raise
+
+    def test_import_workout_executions_invalid_file(self):
+        try:
+            user_id = self.create_user()
+            invalid = self.import_history(user_id, "name,sets\nSquat,3\n")
+            self.assertEqual(invalid['statusCode'], 400)
+
+        except TestError as e:
+            print(f"TEST ERROR: {e}")
+            raise
+
+    def test_import_workout_executions_success(self):
+        try:
+            user_id = self.create_user()
+
+            unknown = self.import_history(user_id, HEVY_EXPORT)
+            self.assertEqual(unknown['statusCode'], 422)
+            self.assertEqual(json.loads(unknown['body'])['unknown'], ["Nordic Hamstring Curl"])
+
+            dry_run = self.import_history(user_id, HEVY_EXPORT, {"dry_run": "true", "create_missing": "true"})
+            self.assertEqual(dry_run['statusCode'], 200)
+            self.assertEqual(json.loads(dry_run['body'])['imported'], 0)
+
+            success = self.import_history(user_id, HEVY_EXPORT, {"create_missing": "true"})
+            self.assertEqual(success['statusCode'], 201)
+            report = json.loads(success['body'])
+            self.assertEqual(report['imported'], 1)
+            self.assertEqual(report['matched']['Squat (Barbell)']['name'], "Squat")
+            self.assertEqual(report['created'], ["Nordic Hamstring Curl"])
+
+            again = self.import_history(user_id, HEVY_EXPORT)
+            self.assertEqual(again['statusCode'], 200)
+            self.assertEqual(json.loads(again['body'])['already_imported'], 1)
+
+            executions = TestHelper().get_from_db(
+                    f"SELECT COUNT(*) FROM WorkoutExecutions e JOIN WorkoutTemplates t ON e.workout_template_id = t.id WHERE t.user_id = '{user_id}';")
+            self.assertEqual(executions[0][0], 1)
+
+        except TestError as e:
+            print(f"TEST ERROR: {e}")
+            raise
+
+if __name__ == '__main__':
+    unittest.main()
@@ -0,0 +1,80 @@
+import unittest
+import json
+from tests.utils import TestHelper, TestError
+
+class TestExecutionsSessions(unittest.TestCase):
+
+    """
+    TEST CASES
+    * Start, log sets and finish
+    * Start and discard
+    """
+
+    def start_session(self, user_id, template_id):
+        return TestHelper().invoke(
+                function="workout_executions",
+                method="POST",
+                path=f"/users/{user_id}/workout-sessions",
+                sub=user_id,
+                path_params={"user_id": user_id},
+                body={"workout_template_id": template_id}
+                )
+
+    def test_session_finish(self):
+        try:
+            template_id, user_id = TestHelper().get_from_db("SELECT id, user_id FROM WorkoutTemplates;")[0]
+            exercise_id = TestHelper().get_from_db("SELECT id FROM Exercises WHERE owner_id IS NULL;")[0][0]
+            user_id, template_id, exercise_id = str(user_id), str(template_id), str(exercise_id)
+            started = self.start_session(user_id, template_id)
+            self.assertEqual(started['statusCode'], 201)
+            session = json.loads(started['body'])
+            self.assertEqual(session['status'], 1)
+            session_id = session['id']
+
+            for position in range(2):
+                logged = TestHelper().invoke(
+                        function="workout_executions",
+                        method="PUT",
+                        path=f"/users/{user_id}/workout-sessions/{session_id}/sets/{position}",
+                        sub=user_id,
+                        path_params={"user_id": user_id, "workout_execution_id": session_id, "position": str(position)},
+                        body={"exercise_id": exercise_id, "exercise_number": 0, "reps": 8, "set_number": position, "weight": 40.0, "rest": 60, "time": 30}
+                        )
+                self.assertEqual(logged['statusCode'], 200)
+
+            finished = TestHelper().invoke(
+                    function="workout_executions",
+                    method="POST",
+                    path=f"/users/{user_id}/workout-sessions/{session_id}/finish",
+                    sub=user_id,
+                    path_params={"user_id": user_id, "workout_execution_id": session_id},
+                    body={"survey": 3}
+                    )
+            self.assertEqual(finished['statusCode'], 200)
+            execution = json.loads(finished['body'])
+            self.assertEqual(execution['status'], 0)
+            self.assertEqual(len(execution['elements']), 2)
+
+        except TestError as e:
+            print(f"TEST ERROR: {e}")
+            raise
+
+    def test_session_discard(self):
+        try:
+            template_id, user_id = TestHelper().get_from_db("SELECT id, user_id FROM WorkoutTemplates;")[0]
+            user_id, template_id = str(user_id), str(template_id)
+            session_id = json.loads(self.start_session(user_id, template_id)['body'])['id']
+
+            discarded = TestHelper().invoke(
+                    function="workout_executions",
+                    method="DELETE",
+                    path=f"/users/{user_id}/workout-sessions/{session_id}",
+                    sub=user_id,
+                    path_params={"user_id": user_id, "workout_execution_id": session_id}
+                    )
+            self.assertEqual(discarded['statusCode'], 204)
+            self.assertEqual(TestHelper().get_from_db(f"SELECT id FROM WorkoutExecutions WHERE id = '{session_id}';"), [])
+
+        except TestError as e:
+            print(f"TEST ERROR: {e}")
+            raise
@@ -0,0 +1,93 @@
+import unittest
+import json
+from tests.utils import TestHelper, TestError
+
+class TestTemplatesPrograms(unittest.TestCase):
+
+    """
+    TEST CASES
+    * Invalid program
+    * Create, enrol, today's workout, progress and unenrol
+    """
+
+    def test_create_program_invalid(self):
+        try:
+            template_id, user_id = TestHelper().get_from_db("SELECT id, user_id FROM WorkoutTemplates;")[0]
+            user_id = str(user_id)
+            invalid = TestHelper().invoke(
+                    function="workout_templates",
+                    method="POST",
+                    path=f"/users/{user_id}/programs",
+                    sub=user_id,
+                    path_params={"user_id": user_id},
+                    body={"name": "Block", "weeks": 1, "slots": [{"workout_template_id": str(template_id), "week": 2, "day": 1}]}
+                    )
+            self.assertEqual(invalid['statusCode'], 400)
+
+        except TestError as e:
+            print(f"TEST ERROR: {e}")
+            raise
+
+    def test_program_lifecycle(self):
+        try:
+            template_id, user_id = TestHelper().get_from_db("SELECT id, user_id FROM WorkoutTemplates;")[0]
+            user_id = str(user_id)
+            created = TestHelper().invoke(
+                    function="workout_templates",
+                    method="POST",
+                    path=f"/users/{user_id}/programs",
+                    sub=user_id,
+                    path_params={"user_id": user_id},
+                    body={
+                        "name": "Block",
+                        "weeks": 1,
+                        "slots": [{"workout_template_id": str(template_id), "week": 1, "day": 1, "reps": 5, "intensity": 80}]
+                        }
+                    )
+            self.assertEqual(created['statusCode'], 201)
+            program_id = json.loads(created['body'])['id']
+            params = {"user_id": user_id, "program_id": program_id}
+
+            enrolled = TestHelper().invoke(
+                    function="workout_templates",
+                    method="POST",
+                    path=f"/users/{user_id}/programs/{program_id}/enrolment",
+                    sub=user_id,
+                    path_params=params
+                    )
+            self.assertEqual(enrolled['statusCode'], 201)
+
+            today = TestHelper().invoke(
+                    function="workout_templates",
+                    method="GET",
+                    path=f"/users/{user_id}/programs/today",
+                    sub=user_id,
+                    path_params={"user_id": user_id}
+                    )
+            self.assertEqual(today['statusCode'], 200)
+            body = json.loads(today['body'])
+            self.assertEqual(body['workout_template']['id'], str(template_id))
+            self.assertTrue(all(element['reps'] == 5 for element in body['workout_template']['elements']))
+
+            progress = TestHelper().invoke(
+                    function="workout_templates",
+                    method="GET",
+                    path=f"/users/{user_id}/programs/{program_id}/progress",
+                    sub=user_id,
+                    path_params=params
+                    )
+            self.assertEqual(progress['statusCode'], 200)
+            self.assertEqual(json.loads(progress['body'])['total'], 1)
+
+            unenrolled = TestHelper().invoke(
+                    function="workout_templates",
+                    method="DELETE",
+                    path=f"/users/{user_id}/programs/{program_id}/enrolment",
+                    sub=user_id,
+                    path_params=params
+                    )
+            self.assertEqual(unenrolled['statusCode'], 204)
+
+        except TestError as e:
+            print(f"TEST ERROR: {e}")
+            raise
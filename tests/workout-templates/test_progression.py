@@ -0,0 +1,73 @@
+import unittest
+import json
+from tests.utils import TestHelper, TestError
+
+class TestTemplatesProgression(unittest.TestCase):
+
+    """
+    TEST CASES
+    * Invalid rule
+    * Set a rule, read the next targets and remove it
+    """
+
+    def test_progression_rule_invalid(self):
+        try:
+            element_id, template_id, user_id = TestHelper().get_from_db(
+                    "SELECT el.id, wt.id, wt.user_id FROM WkTemplateElements el JOIN WorkoutTemplates wt ON wt.id = el.workout_template_id;"
+                    )[0]
+            user_id, template_id, element_id = str(user_id), str(template_id), str(element_id)
+            invalid = TestHelper().invoke(
+                    function="workout_templates",
+                    method="PUT",
+                    path=f"/users/{user_id}/workout-templates/{template_id}/elements/{element_id}/progression",
+                    sub=user_id,
+                    path_params={"user_id": user_id, "workout_template_id": template_id, "wk_template_element_id": element_id},
+                    body={"kind": 0, "weight_increment": 2.5, "min_reps": 8, "max_reps": 12}
+                    )
+            self.assertEqual(invalid['statusCode'], 400)
+
+        except TestError as e:
+            print(f"TEST ERROR: {e}")
+            raise
+
+    def test_progression_lifecycle(self):
+        try:
+            element_id, template_id, user_id = TestHelper().get_from_db(
+                    "SELECT el.id, wt.id, wt.user_id FROM WkTemplateElements el JOIN WorkoutTemplates wt ON wt.id = el.workout_template_id;"
+                    )[0]
+            user_id, template_id, element_id = str(user_id), str(template_id), str(element_id)
+            params = {"user_id": user_id, "workout_template_id": template_id, "wk_template_element_id": element_id}
+            created = TestHelper().invoke(
+                    function="workout_templates",
+                    method="PUT",
+                    path=f"/users/{user_id}/workout-templates/{template_id}/elements/{element_id}/progression",
+                    sub=user_id,
+                    path_params=params,
+                    body={"kind": 0, "weight_increment": 2.5, "deload_after": 3}
+                    )
+            self.assertEqual(created['statusCode'], 200)
+
+            progression = TestHelper().invoke(
+                    function="workout_templates",
+                    method="GET",
+                    path=f"/users/{user_id}/workout-templates/{template_id}/progression",
+                    sub=user_id,
+                    path_params={"user_id": user_id, "workout_template_id": template_id}
+                    )
+            self.assertEqual(progression['statusCode'], 200)
+            targets = json.loads(progression['body'])['targets']
+            target = next(target for target in targets if target['wk_template_element_id'] == element_id)
+            self.assertNotEqual(target['outcome'], "no_rule")
+
+            deleted = TestHelper().invoke(
+                    function="workout_templates",
+                    method="DELETE",
+                    path=f"/users/{user_id}/workout-templates/{template_id}/elements/{element_id}/progression",
+                    sub=user_id,
+                    path_params=params
+                    )
+            self.assertEqual(deleted['statusCode'], 204)
+
+        except TestError as e:
+            print(f"TEST ERROR: {e}")
+            raise
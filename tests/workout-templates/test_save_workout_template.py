@@ -0,0 +1,76 @@
+import unittest
+import json
+import uuid
+from tests.utils import TestHelper, TestError
+
+class TestSaveWorkoutTemplate(unittest.TestCase):
+
+    """
+    TEST CASES
+    * Template of another user than the one in the path
+    * Copy of a public user's template, owned by the caller
+    """
+
+    def create_user(self):
+        id = str(uuid.uuid4())
+        TestHelper().invoke(
+                function="users",
+                method="POST",
+                path="/users",
+                sub=id,
+                body={"uuid": id, "username": TestHelper.random_string(10), "date_joined": "2024-10-18"}
+                )
+        return id
+
+    def public_template(self):
+        template_id, owner_id = TestHelper().get_from_db(
+            """
+            SELECT wt.id, wt.user_id
+            FROM WorkoutTemplates wt
+            JOIN Users u ON u.id = wt.user_id
+            WHERE NOT u.private_profile
+            AND NOT EXISTS (
+                SELECT 1 FROM WkTemplateElements el JOIN Exercises e ON e.id = el.exercise_id
+                WHERE el.workout_template_id = wt.id AND e.owner_id IS NOT NULL
+            )
+            LIMIT 1;
+            """
+            )[0]
+        return str(template_id), str(owner_id)
+
+    def test_save_workout_template_not_found(self):
+        try:
+            viewer = self.create_user()
+            template_id, _ = self.public_template()
+            response = TestHelper().invoke(
+                    function="workout_templates",
+                    method="POST",
+                    path=f"/users/{viewer}/workout-templates/{template_id}/save",
+                    sub=viewer,
+                    path_params={"user_id": viewer, "workout_template_id": template_id}
+                    )
+            self.assertEqual(response['statusCode'], 404)
+
+        except TestError as e:
+            print(f"TEST ERROR: {e}")
+            raise
+
+    def test_save_workout_template(self):
+        try:
+            viewer = self.create_user()
+            template_id, owner_id = self.public_template()
+            response = TestHelper().invoke(
+                    function="workout_templates",
+                    method="POST",
+                    path=f"/users/{owner_id}/workout-templates/{template_id}/save",
+                    sub=viewer,
+                    path_params={"user_id": owner_id, "workout_template_id": template_id}
+                    )
+            self.assertEqual(response['statusCode'], 201)
+            copy = json.loads(response['body'])
+            self.assertEqual(copy['user_id'], viewer)
+            self.assertNotEqual(copy['id'], template_id)
+
+        except TestError as e:
+            print(f"TEST ERROR: {e}")
+            raise
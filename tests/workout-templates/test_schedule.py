@@ -0,0 +1,114 @@
+import unittest
+import json
+from datetime import date
+from tests.utils import TestHelper, TestError
+
+class TestTemplatesSchedule(unittest.TestCase):
+
+    """
+    TEST CASES
+    * Invalid schedule
+    * Schedule, calendar and unschedule
+    * Share, read and revoke the iCal feed
+    """
+
+    def test_schedule_invalid(self):
+        try:
+            template_id, user_id = TestHelper().get_from_db("SELECT id, user_id FROM WorkoutTemplates;")[0]
+            user_id = str(user_id)
+            invalid = TestHelper().invoke(
+                    function="workout_templates",
+                    method="POST",
+                    path=f"/users/{user_id}/schedule",
+                    sub=user_id,
+                    path_params={"user_id": user_id},
+                    body={"workout_template_id": str(template_id), "start_date": "2024-11-04", "weekdays": [8]}
+                    )
+            self.assertEqual(invalid['statusCode'], 400)
+
+        except TestError as e:
+            print(f"TEST ERROR: {e}")
+            raise
+
+    def test_schedule_lifecycle(self):
+        try:
+            template_id, user_id = TestHelper().get_from_db("SELECT id, user_id FROM WorkoutTemplates;")[0]
+            user_id = str(user_id)
+            today = date.today().isoformat()
+            created = TestHelper().invoke(
+                    function="workout_templates",
+                    method="POST",
+                    path=f"/users/{user_id}/schedule",
+                    sub=user_id,
+                    path_params={"user_id": user_id},
+                    body={"workout_template_id": str(template_id), "start_date": today}
+                    )
+            self.assertEqual(created['statusCode'], 201)
+            scheduled_id = json.loads(created['body'])['id']
+
+            calendar = TestHelper().invoke(
+                    function="workout_templates",
+                    method="GET",
+                    path=f"/users/{user_id}/calendar",
+                    sub=user_id,
+                    path_params={"user_id": user_id},
+                    query_params={"from": today, "to": today}
+                    )
+            self.assertEqual(calendar['statusCode'], 200)
+            planned = json.loads(calendar['body'])['planned']
+            self.assertTrue(any(workout['scheduled_workout_id'] == scheduled_id for workout in planned))
+
+            deleted = TestHelper().invoke(
+                    function="workout_templates",
+                    method="DELETE",
+                    path=f"/users/{user_id}/schedule/{scheduled_id}",
+                    sub=user_id,
+                    path_params={"user_id": user_id, "scheduled_workout_id": scheduled_id}
+                    )
+            self.assertEqual(deleted['statusCode'], 204)
+
+        except TestError as e:
+            print(f"TEST ERROR: {e}")
+            raise
+
+    def test_calendar_feed(self):
+        try:
+            user_id = str(TestHelper().get_from_db("SELECT user_id FROM WorkoutTemplates;")[0][0])
+            shared = TestHelper().invoke(
+                    function="workout_templates",
+                    method="POST",
+                    path=f"/users/{user_id}/calendar/token",
+                    sub=user_id,
+                    path_params={"user_id": user_id}
+                    )
+            self.assertEqual(shared['statusCode'], 201)
+            token = json.loads(shared['body'])['token']
+
+            feed = TestHelper().invoke(
+                    function="workout_templates",
+                    method="GET",
+                    path=f"/calendar/{token}.ics",
+                    path_params={"token": token}
+                    )
+            self.assertEqual(feed['statusCode'], 200)
+
+            revoked = TestHelper().invoke(
+                    function="workout_templates",
+                    method="DELETE",
+                    path=f"/users/{user_id}/calendar/token",
+                    sub=user_id,
+                    path_params={"user_id": user_id}
+                    )
+            self.assertEqual(revoked['statusCode'], 204)
+
+            gone = TestHelper().invoke(
+                    function="workout_templates",
+                    method="GET",
+                    path=f"/calendar/{token}.ics",
+                    path_params={"token": token}
+                    )
+            self.assertEqual(gone['statusCode'], 404)
+
+        except TestError as e:
+            print(f"TEST ERROR: {e}")
+            raise
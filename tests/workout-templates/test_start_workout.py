@@ -0,0 +1,53 @@
+import unittest
+import json
+from tests.utils import TestHelper, TestError
+
+class TestStartWorkout(unittest.TestCase):
+
+    """
+    TEST CASES
+    * Template of another user
+    * Draft with one row per set of the template
+    """
+
+    def test_start_workout_not_found(self):
+        try:
+            template_id, owner_id = TestHelper().get_from_db("SELECT id, user_id FROM WorkoutTemplates;")[0]
+            user_id = TestHelper().get_from_db(f"SELECT id FROM Users WHERE id <> '{owner_id}';")[0][0]
+            user_id, template_id = str(user_id), str(template_id)
+            response = TestHelper().invoke(
+                    function="workout_templates",
+                    method="POST",
+                    path=f"/users/{user_id}/workout-templates/{template_id}/start",
+                    sub=user_id,
+                    path_params={"user_id": user_id, "workout_template_id": template_id}
+                    )
+            self.assertEqual(response['statusCode'], 404)
+
+        except TestError as e:
+            print(f"TEST ERROR: {e}")
+            raise
+
+    def test_start_workout(self):
+        try:
+            template_id, user_id = TestHelper().get_from_db(
+                    "SELECT wt.id, wt.user_id FROM WorkoutTemplates wt JOIN WkTemplateElements el ON el.workout_template_id = wt.id;"
+                    )[0]
+            sets = TestHelper().get_from_db(f"SELECT SUM(sets) FROM WkTemplateElements WHERE workout_template_id = '{template_id}';")[0][0]
+            user_id, template_id = str(user_id), str(template_id)
+            response = TestHelper().invoke(
+                    function="workout_templates",
+                    method="POST",
+                    path=f"/users/{user_id}/workout-templates/{template_id}/start",
+                    sub=user_id,
+                    path_params={"user_id": user_id, "workout_template_id": template_id}
+                    )
+            self.assertEqual(response['statusCode'], 200)
+            draft = json.loads(response['body'])
+            self.assertEqual(draft['workout_template_id'], template_id)
+            self.assertEqual(len(draft['elements']), sets)
+            self.assertEqual([row['position'] for row in draft['elements']], list(range(sets)))
+
+        except TestError as e:
+            print(f"TEST ERROR: {e}")
+            raise